@@ -3,6 +3,7 @@ use crate::components::center_middle::CenterMiddle;
 use crate::components::image::Image;
 use crate::components::loading::Loading;
 use crate::components::modal_dialog::ModalDialog;
+use crate::fragment::api_token::list::ApiTokenList;
 use crate::fragment::auth::login_or_register::LoginOrRegister;
 use crate::fragment::auth::logout::Logout;
 use crate::fragment::auth::oauth2_authorize::Oauth2Authorize;
@@ -15,6 +16,9 @@ use crate::fragment::index::Index;
 use crate::fragment::job::list::JobList;
 use crate::fragment::job_record::detail::JobRecordDetail;
 use crate::fragment::job_record::list::JobRecordList;
+use crate::fragment::job_schedule::list::JobScheduleList;
+use crate::fragment::retention_policy::RetentionPolicy;
+use crate::fragment::runner::list::RunnerList;
 use crate::fragment::sys_menu::SysMenu;
 use crate::fragment::user::list::UserList;
 use crate::route::is_white_list_route;
@@ -143,7 +147,11 @@ pub fn RootApp(props: &RootProps) -> Html {
                                                     Route::JobRecordListByJob { job_id } => html! { <JobRecordList job_id={job_id} /> },
                                                     Route::JobRecordListByEnvironment { environment_id } => html! { <JobRecordList environment_id={environment_id} /> },
                                                     Route::JobRecord { id } => html! { <JobRecordDetail id={id} /> },
+                                                    Route::JobScheduleList => html! { <JobScheduleList /> },
                                                     Route::UserList => html! { <UserList /> },
+                                                    Route::RunnerList => html! { <RunnerList /> },
+                                                    Route::ApiTokenList => html! { <ApiTokenList /> },
+                                                    Route::RetentionPolicy => html! { <RetentionPolicy /> },
                                                 }
                                             }
                                         </div>
@@ -0,0 +1,260 @@
+use crate::components::button::Button;
+use crate::components::checkbox::Checkbox;
+use crate::components::checkbox_group::CheckboxGroup;
+use crate::components::input::BindingInput;
+use crate::components::radio_group::BindingRadioGroup;
+use crate::components::required::Required;
+use crate::components::validate_wrapper::ValidateData;
+use crate::sdk;
+use crate::utils;
+use crate::utils::request::ApiExt;
+use crate::utils::validator::RequiredValidator;
+use crate::utils::validator::Validators;
+use crate::LightString;
+use sdk::api_token::enums::Scope;
+use sdk::api_token::insert_api_token::InsertApiTokenApi;
+use sdk::api_token::insert_api_token::InsertApiTokenReq;
+use sdk::job::query_job::QueryJobApi;
+use sdk::job::query_job::QueryJobReq;
+use std::ops::Deref;
+use tihu::Id;
+use tihu::PrimaryKey;
+use yew::prelude::*;
+
+type ScopeRadioGroup = BindingRadioGroup<(Scope, String)>;
+
+//新建令牌默认的有效天数
+const DEFAULT_EXPIRE_DAYS: &str = "90";
+
+#[derive(Clone)]
+struct EditForm {
+    name: ValidateData<LightString>,
+    scope: UseStateHandle<Scope>,
+    job_ids: UseStateHandle<Option<Vec<Id>>>, //为空表示不限制能执行的任务
+    expire_days: ValidateData<LightString>,
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    //服务账号id，为空时给自己创建个人令牌
+    #[prop_or_default]
+    pub user_id: Option<Id>,
+    #[prop_or_default]
+    pub onsave: Option<Callback<PrimaryKey>>,
+}
+
+#[function_component]
+pub fn ApiTokenEdit(props: &Props) -> Html {
+    let is_saving: UseStateHandle<bool> = use_state(|| false);
+    let err_msg: UseStateHandle<Option<LightString>> = use_state(|| None);
+    let job_list: UseStateHandle<Vec<(Id, String)>> = use_state(|| Vec::new());
+    let edit_form = EditForm {
+        name: ValidateData::new(
+            Default::default(),
+            Some(Validators::new().add(RequiredValidator::new("请输入令牌名称"))),
+        ),
+        scope: use_state(|| Scope::Read),
+        job_ids: use_state(|| None),
+        expire_days: ValidateData::new(
+            LightString::from(DEFAULT_EXPIRE_DAYS),
+            Some(Validators::new().add(RequiredValidator::new("请输入有效天数"))),
+        ),
+    };
+    let job_list_clone = job_list.clone();
+    use_effect_with((), move |_| {
+        wasm_bindgen_futures::spawn_local(async move {
+            query_job_list(&job_list_clone).await.ok();
+        });
+        || ()
+    });
+    let user_id = props.user_id;
+    let edit_form_clone = edit_form.clone();
+    let is_saving_clone = is_saving.clone();
+    let err_msg_clone = err_msg.clone();
+    let onsave_clone = props.onsave.clone();
+    let on_save = Callback::from(move |_| {
+        let edit_form = edit_form_clone.clone();
+        let is_saving = is_saving_clone.clone();
+        let err_msg = err_msg_clone.clone();
+        let onsave = onsave_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            save_api_token(user_id, &edit_form, is_saving, &err_msg, &onsave)
+                .await
+                .ok();
+        });
+    });
+    let job_ids_clone = edit_form.job_ids.clone();
+    let on_limit_change = Callback::from(move |limit_jobs: bool| {
+        job_ids_clone.set(if limit_jobs { Some(Vec::new()) } else { None });
+    });
+    let job_ids_clone = edit_form.job_ids.clone();
+    let on_job_change = Callback::from(move |value: Vec<Id>| {
+        job_ids_clone.set(Some(value));
+    });
+    html! {
+        <div class="width-fill height-fill border-box" style="padding:0.25em;">
+            <table class="width-fill" style="border-collapse:collapse;table-layout: fixed;">
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;"><Required/>{"名称："}</td>
+                    <td>
+                        {
+                            edit_form.name.view(move |name: UseStateHandle<LightString>, validator| {
+                                html! {
+                                    <BindingInput value={name} onupdate={validator} placeholder="例如：流水线部署"/>
+                                }
+                            })
+                        }
+                    </td>
+                </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;"><Required/>{"权限范围："}</td>
+                    <td>
+                        <ScopeRadioGroup value={edit_form.scope.clone()} options={get_scope_list()} />
+                    </td>
+                </tr>
+                if Scope::Execute == *edit_form.scope {
+                    <tr>
+                        <td class="align-right" style="width:8em;vertical-align: top;">{"任务："}</td>
+                        <td>
+                            <Checkbox value={edit_form.job_ids.is_some()} label={"只能执行指定的任务"} onchange={on_limit_change}/>
+                            {
+                                match edit_form.job_ids.deref() {
+                                    Some(value) => html! {
+                                        <CheckboxGroup<(Id, String)> value={value.clone()} options={job_list.deref().clone()} onchange={on_job_change}/>
+                                    },
+                                    None => html! {}
+                                }
+                            }
+                        </td>
+                    </tr>
+                }
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;"><Required/>{"有效天数："}</td>
+                    <td>
+                        {
+                            edit_form.expire_days.view(move |expire_days: UseStateHandle<LightString>, validator| {
+                                html! {
+                                    <BindingInput value={expire_days} onupdate={validator}/>
+                                }
+                            })
+                        }
+                    </td>
+                </tr>
+                <tr>
+                    <td></td>
+                    <td>
+                        <Button disabled={*is_saving} onclick={on_save}>{"保存"}</Button>
+                        {
+                            match err_msg.as_ref() {
+                                Some(err_msg) => {
+                                    html!{
+                                        <span class="middle" style="color:red;margin-left: 0.5em;">{err_msg}</span>
+                                    }
+                                },
+                                None => html!{}
+                            }
+                        }
+                    </td>
+                </tr>
+            </table>
+        </div>
+    }
+}
+
+fn get_scope_list() -> Vec<(Scope, String)> {
+    return [Scope::Read, Scope::Execute]
+        .iter()
+        .map(|value| (*value, value.to_string()))
+        .collect();
+}
+
+fn chk_form_err(edit_form: &EditForm) -> Vec<LightString> {
+    let mut err_msgs: Vec<LightString> = Vec::new();
+    if let Err(error) = edit_form.name.validate(true) {
+        err_msgs.push(error);
+    }
+    if let Err(error) = edit_form.expire_days.validate(true) {
+        err_msgs.push(error);
+    }
+    if let Err(_err) = edit_form.expire_days.get().trim().parse::<u32>() {
+        err_msgs.push(LightString::from("有效天数必须是正整数"));
+    }
+    if Scope::Execute == *edit_form.scope {
+        if let Some(job_ids) = edit_form.job_ids.as_ref() {
+            if job_ids.is_empty() {
+                err_msgs.push(LightString::from("请选择令牌可以执行的任务"));
+            }
+        }
+    }
+    return err_msgs;
+}
+
+async fn save_api_token(
+    user_id: Option<Id>,
+    edit_form: &EditForm,
+    is_saving: UseStateHandle<bool>,
+    err_msg: &UseStateHandle<Option<LightString>>,
+    onsave: &Option<Callback<PrimaryKey>>,
+) -> Result<(), LightString> {
+    let err_msgs = chk_form_err(edit_form);
+    if let Some(first) = err_msgs.first() {
+        err_msg.set(Some(first.clone()));
+        return Err(first.clone());
+    }
+    let scope = *edit_form.scope;
+    let params = InsertApiTokenReq {
+        user_id: user_id,
+        name: edit_form.name.get().to_string(),
+        scope: scope,
+        job_ids: if Scope::Execute == scope {
+            edit_form.job_ids.deref().clone()
+        } else {
+            None
+        },
+        expire_days: edit_form.expire_days.get().trim().parse().unwrap_or(0),
+    };
+    let ret = InsertApiTokenApi
+        .lock_handler(is_saving)
+        .call(&params)
+        .await;
+    match ret {
+        Err(err) => {
+            log::error!("{}", err);
+            err_msg.set(Some(err));
+        }
+        Ok(insert_api_token_resp) => {
+            //访问令牌只在创建时显示这一次，服务端只保存摘要
+            utils::alert(
+                LightString::from(format!(
+                    "请妥善保存访问令牌，调用api时放到Authorization: Bearer请求头里，关闭之后无法再次查看：{}",
+                    insert_api_token_resp.token
+                )),
+                Some(LightString::from("访问令牌")),
+            )
+            .await;
+            if let Some(onsave) = onsave {
+                onsave.emit(PrimaryKey {
+                    id: insert_api_token_resp.id,
+                });
+            }
+        }
+    }
+    return Ok(());
+}
+
+async fn query_job_list(job_list: &UseStateHandle<Vec<(Id, String)>>) -> Result<(), LightString> {
+    let pagination_list = QueryJobApi
+        .call(&QueryJobReq {
+            page_no: Some(1),
+            ..QueryJobReq::empty()
+        })
+        .await?;
+    job_list.set(
+        pagination_list
+            .list
+            .into_iter()
+            .map(|job| (job.id, job.name))
+            .collect(),
+    );
+    return Ok(());
+}
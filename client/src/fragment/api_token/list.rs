@@ -0,0 +1,238 @@
+use super::edit::ApiTokenEdit;
+use crate::components::button::Button;
+use crate::components::button_group::ButtonGroup;
+use crate::components::drawer::Drawer;
+use crate::sdk;
+use crate::utils;
+use crate::utils::format_time_local;
+use crate::utils::request::ApiExt;
+use crate::utils::LoadStatus;
+use crate::LightString;
+use chrono::Utc;
+use sdk::api_token::delete_api_token::DeleteApiTokenApi;
+use sdk::api_token::delete_api_token::DeleteApiTokenReq;
+use sdk::api_token::query_api_token::ApiToken;
+use sdk::api_token::query_api_token::QueryApiTokenApi;
+use sdk::api_token::query_api_token::QueryApiTokenReq;
+use std::ops::Deref;
+use tihu::Id;
+use tihu::PrimaryKey;
+use yew::prelude::*;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    //服务账号id，为空时管理自己的个人令牌
+    #[prop_or_default]
+    pub user_id: Option<Id>,
+}
+
+#[function_component]
+pub fn ApiTokenList(props: &Props) -> Html {
+    let user_id = props.user_id;
+    let list: UseStateHandle<Vec<ApiToken>> = use_state(|| Vec::new());
+    let list_load_status: UseStateHandle<LoadStatus> = use_state(|| LoadStatus::NotStarted);
+    let edit_active: UseStateHandle<bool> = use_state(|| false);
+    let edit_active_clone = edit_active.clone();
+    let on_open_insert = Callback::from(move |_: ()| {
+        edit_active_clone.set(true);
+    });
+    let list_clone = list.clone();
+    let list_load_status_clone = list_load_status.clone();
+    let on_query = Callback::from(move |_| {
+        let list = list_clone.clone();
+        let list_load_status = list_load_status_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            query_api_token_list(user_id, &list, &list_load_status).await;
+        });
+    });
+    let on_query_clone = on_query.clone();
+    use_effect_with(user_id, move |_| {
+        on_query_clone.emit(());
+        || ()
+    });
+    let edit_active_clone = edit_active.clone();
+    let list_clone = list.clone();
+    let list_load_status_clone = list_load_status.clone();
+    let on_finish_save = Callback::from(move |_pri_key: PrimaryKey| {
+        edit_active_clone.set(false);
+        let list = list_clone.clone();
+        let list_load_status = list_load_status_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            query_api_token_list(user_id, &list, &list_load_status).await;
+        });
+    });
+    let edit_active_clone = edit_active.clone();
+    let on_leave_edit = Callback::from(move |_| {
+        edit_active_clone.set(false);
+    });
+    html! {
+        <div class="relative width-fill height-fill" style="overflow:hidden;">
+            <div class="width-fill height-fill border-box" style="padding:0.25em;">
+                <div class="width-fill height-fill" style="display: -webkit-box;display: flex;-webkit-box-direction: normal;-webkit-box-orient: vertical;flex-direction: column;">
+                    <header style="-webkit-box-flex: 0;flex-basis: auto;flex-grow: 0;">
+                        <div class="align-right" style="padding-bottom: 0.25em;">
+                            <ButtonGroup>
+                                <Button onclick={on_open_insert}>{"新增"}</Button>
+                                <Button onclick={on_query}>{"查询"}</Button>
+                            </ButtonGroup>
+                        </div>
+                    </header>
+                    <div style="-webkit-box-flex: 1;flex-basis: auto;flex-grow: 1;overflow-y: auto;">
+                        { table_view(user_id, &list, &list_load_status) }
+                        { list_exception_view(list.is_empty(), list_load_status.deref().clone()) }
+                    </div>
+                </div>
+            </div>
+            <Drawer active={*edit_active} onclickother={on_leave_edit}>
+                if *edit_active {
+                    <ApiTokenEdit user_id={user_id} onsave={on_finish_save} />
+                }
+            </Drawer>
+        </div>
+    }
+}
+
+fn table_view(
+    user_id: Option<Id>,
+    list: &UseStateHandle<Vec<ApiToken>>,
+    list_load_status: &UseStateHandle<LoadStatus>,
+) -> Html {
+    return html! {
+        <table class="e-table width-fill">
+            <thead>
+                <tr>
+                    <th class="e-table-hcell">{"名称"}</th>
+                    <th class="e-table-hcell">{"令牌"}</th>
+                    <th class="e-table-hcell">{"权限范围"}</th>
+                    <th class="e-table-hcell">{"过期时间"}</th>
+                    <th class="e-table-hcell">{"最后使用"}</th>
+                    <th class="e-table-hcell">{"创建时间"}</th>
+                    <th class="e-table-hcell">{"操作"}</th>
+                </tr>
+            </thead>
+            {
+                if list_load_status.deref() == &LoadStatus::LoadOk || list_load_status.deref() == &LoadStatus::Loading {
+                    html! {
+                        <tbody>
+                            {
+                                for list.iter().map(|item| {
+                                    row_view(user_id, item, list, list_load_status)
+                                })
+                            }
+                        </tbody>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+        </table>
+    };
+}
+
+fn row_view(
+    user_id: Option<Id>,
+    api_token: &ApiToken,
+    list: &UseStateHandle<Vec<ApiToken>>,
+    list_load_status: &UseStateHandle<LoadStatus>,
+) -> Html {
+    let delete_id = api_token.id;
+    let list = list.clone();
+    let list_load_status = list_load_status.clone();
+    let on_confirm_delete = Callback::from(move |_: ()| {
+        let list = list.clone();
+        let list_load_status = list_load_status.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let ok = utils::confirm(
+                LightString::from("删除之后该访问令牌立即失效，确定删除该访问令牌？"),
+                None,
+            )
+            .await;
+            if ok {
+                delete_api_token(user_id, delete_id, &list, &list_load_status)
+                    .await
+                    .ok();
+            }
+        });
+    });
+    let scope_text = match api_token.job_ids.as_ref() {
+        Some(job_ids) => format!("{}（{}个任务）", api_token.scope, job_ids.len()),
+        None => api_token.scope.to_string(),
+    };
+    let expired = api_token.expire_time <= Utc::now();
+    return html! {
+        <tr class="e-table-row">
+            <td class="e-table-cell align-center">{&api_token.name}</td>
+            <td class="e-table-cell align-center">{format!("{}...", api_token.token_prefix)}</td>
+            <td class="e-table-cell align-center">{scope_text}</td>
+            <td class="e-table-cell align-center" style={if expired { "color:gray;" } else { "" }}>
+                {format_time_local(&api_token.expire_time).to_string()}
+                if expired {
+                    {"（已过期）"}
+                }
+            </td>
+            <td class="e-table-cell align-center">{api_token.last_used_time.as_ref().map(|time| format_time_local(time).to_string()).unwrap_or_default()}</td>
+            <td class="e-table-cell align-center">{ html!{&format_time_local(&api_token.created_time)} }</td>
+            <td class="e-table-cell align-center">
+                <ButtonGroup>
+                    <Button onclick={on_confirm_delete}>{"删除"}</Button>
+                </ButtonGroup>
+            </td>
+        </tr>
+    };
+}
+
+fn list_exception_view(is_empty: bool, list_load_status: LoadStatus) -> Html {
+    return html! {
+        match list_load_status {
+            LoadStatus::LoadFailed => {
+                html! {
+                    <p class="align-center">{"列表加载失败"}</p>
+                }
+            },
+            LoadStatus::LoadOk => {
+                if is_empty {
+                    html! {
+                        <p class="align-center">{"列表数据为空"}</p>
+                    }
+                } else {
+                    html! {}
+                }
+            },
+            _ => html! {}
+        }
+    };
+}
+
+async fn query_api_token_list(
+    user_id: Option<Id>,
+    list: &UseStateHandle<Vec<ApiToken>>,
+    list_load_status: &UseStateHandle<LoadStatus>,
+) {
+    let params = QueryApiTokenReq { user_id: user_id };
+    list_load_status.set(LoadStatus::Loading);
+    let ret = QueryApiTokenApi.call(&params).await;
+    match ret {
+        Err(err) => {
+            log::error!("{}", err);
+            list_load_status.set(LoadStatus::LoadFailed);
+            list.set(Vec::new());
+        }
+        Ok(api_token_list) => {
+            list_load_status.set(LoadStatus::LoadOk);
+            list.set(api_token_list);
+        }
+    }
+}
+
+async fn delete_api_token(
+    user_id: Option<Id>,
+    id: Id,
+    list: &UseStateHandle<Vec<ApiToken>>,
+    list_load_status: &UseStateHandle<LoadStatus>,
+) -> Result<(), LightString> {
+    let params = DeleteApiTokenReq { id: id };
+    DeleteApiTokenApi.call(&params).await?;
+    utils::success(LightString::from("删除成功"));
+    query_api_token_list(user_id, list, list_load_status).await;
+    return Ok(());
+}
@@ -1,6 +1,7 @@
 use super::super::extension::config_detail_view;
 use super::super::extension::get_configuration_schema;
 use super::super::extension::parse_config;
+use super::render_lock;
 use crate::components::show::Show;
 use crate::sdk;
 use crate::utils;
@@ -66,6 +67,22 @@ pub fn EnvironmentDetail(props: &Props) -> Html {
                     <td class="align-right" style="width:8em;vertical-align: top;">{"环境规格："}</td>
                     <td>{environment_schema_detail.as_ref().map(|environment_schema|{html!{&environment_schema.name}}).unwrap_or_else(utils::empty_html)}</td>
                 </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;">{"环境被占用时："}</td>
+                    <td>{detail.as_ref().map(|environment|{html!{environment.lock_policy.to_string()}}).unwrap_or_else(utils::empty_html)}</td>
+                    <td class="align-right" style="width:8em;vertical-align: top;">{"占用情况："}</td>
+                    <td>{detail.as_ref().map(|environment|render_lock(environment.lock.as_ref())).unwrap_or_else(utils::empty_html)}</td>
+                </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;">{"执行器标签："}</td>
+                    <td>{detail.as_ref().map(|environment|{html!{environment.runner_label.clone().unwrap_or_else(|| String::from("在服务端执行"))}}).unwrap_or_else(utils::empty_html)}</td>
+                    <td class="align-right" style="width:8em;vertical-align: top;">{"受保护："}</td>
+                    <td>{detail.as_ref().map(|environment|{html!{if environment.protected {"是，需要单独授权才能执行任务、修改资源和审批"} else {"否"}}}).unwrap_or_else(utils::empty_html)}</td>
+                </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;">{"执行前审批："}</td>
+                    <td colspan="3">{detail.as_ref().map(|environment|{html!{if environment.protected && 0 < environment.required_approvals {format!("需要{}人审批通过", environment.required_approvals)} else {String::from("不需要")}}}).unwrap_or_else(utils::empty_html)}</td>
+                </tr>
             </table>
             <div style="flex-grow: 1;flex-shrink: 1;position: relative;border-top: 1px solid #CCC;border-bottom: 1px solid #CCC;overflow: auto;">
                 <div style="width:16em;height:100%;display:flex;flex-direction:column;border-right: 1px solid #CCC;box-sizing: border-box;">
@@ -5,7 +5,10 @@ use super::super::extension::parse_config;
 use super::super::extension::serialize_config;
 use super::super::extension::AttributeValue;
 use crate::components::button::Button;
+use crate::components::checkbox::BindingCheckbox;
 use crate::components::input::BindingInput;
+use crate::components::r#if::If;
+use crate::components::radio_group::BindingRadioGroup;
 use crate::components::required::Required;
 use crate::components::rich_text::upload_resource;
 use crate::components::selection::BindingSelection;
@@ -22,6 +25,7 @@ use crate::utils::request::ApiExt;
 use crate::utils::validator::RequiredValidator;
 use crate::utils::validator::Validators;
 use crate::LightString;
+use sdk::environment::enums::LockPolicy;
 use sdk::environment::insert_environment::InsertEnvironmentApi;
 use sdk::environment::insert_environment::InsertEnvironmentReq;
 use sdk::environment::read_environment::Environment;
@@ -50,6 +54,16 @@ use yew::prelude::*;
 use yew::virtual_dom::Key;
 
 type EnvironmentSchemaSelection = BindingSelection<(Id, String)>;
+type LockPolicyRadioGroup = BindingRadioGroup<(LockPolicy, String)>;
+
+fn get_lock_policy_list() -> Vec<(LockPolicy, String)> {
+    return [LockPolicy::Reject, LockPolicy::Queue]
+        .iter()
+        .map(|value| {
+            return (value.clone(), value.to_string());
+        })
+        .collect();
+}
 
 #[derive(Clone, PartialEq, Debug)]
 pub struct EnvironmentResource {
@@ -76,6 +90,10 @@ struct EditForm {
     active_resource_key: UseStateHandle<Option<Key>>,
     environment_schema_id: ValidateData<Option<Id>>,
     name: ValidateData<LightString>,
+    lock_policy: UseStateHandle<LockPolicy>,
+    runner_label: UseStateHandle<LightString>,
+    protected: UseStateHandle<bool>,
+    required_approvals: UseStateHandle<LightString>,
     schema_resource_list: UseStateHandle<Vec<(Key, EnvironmentSchemaResource)>>,
 }
 
@@ -116,6 +134,10 @@ pub fn EnvironmentEdit(props: &Props) -> Html {
             Default::default(),
             Some(Validators::new().add(RequiredValidator::new("请输入环境名称"))),
         ),
+        lock_policy: use_state(|| LockPolicy::Reject),
+        runner_label: use_state(|| Default::default()),
+        protected: use_state(|| false),
+        required_approvals: use_state(|| LightString::from("0")),
         schema_resource_list: use_state(|| Vec::new()),
     };
     let environment_edit_state = EnvironmentEditState {
@@ -265,6 +287,31 @@ pub fn EnvironmentEdit(props: &Props) -> Html {
                         }
                     </td>
                 </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;">{"环境被占用时："}</td>
+                    <td>
+                        <LockPolicyRadioGroup value={edit_form.lock_policy.clone()} options={get_lock_policy_list()} />
+                    </td>
+                    <td class="align-right" style="width:8em;vertical-align: top;">{"执行器标签："}</td>
+                    <td>
+                        <BindingInput value={edit_form.runner_label.clone()} placeholder="为空时在服务端执行"/>
+                    </td>
+                </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;">{"受保护："}</td>
+                    <td colspan="3">
+                        <BindingCheckbox value={edit_form.protected.clone()} label="只有单独授权的用户才能执行任务、修改资源和审批"/>
+                    </td>
+                </tr>
+                <If condition={*edit_form.protected}>
+                    <tr>
+                        <td class="align-right" style="width:8em;vertical-align: top;">{"审批人数："}</td>
+                        <td colspan="3">
+                            <BindingInput value={edit_form.required_approvals.clone()} style="width:6em;"/>
+                            <span style="margin-left:0.5em;color:gray;">{"执行之前需要几个有审批授权的人（不包括发起人）通过，0表示不需要审批"}</span>
+                        </td>
+                    </tr>
+                </If>
             </table>
             <div style="flex-grow: 1;flex-shrink: 1;position: relative;border-top: 1px solid #CCC;border-bottom: 1px solid #CCC;overflow: auto;">
                 <div style="width:16em;height:100%;display:flex;flex-direction:column;border-right: 1px solid #CCC;box-sizing: border-box;">
@@ -445,6 +492,7 @@ impl EnvironmentEditState {
         name_validators: Validators<LightString>,
     ) -> Html {
         let extension_configuration = resource.extension_configuration.clone();
+        let resource_id = resource.id;
         html! {
             <div style="padding: 0.25em;">
                 <table>
@@ -475,7 +523,7 @@ impl EnvironmentEditState {
                                         let test_error = test_error.clone();
                                         Callback::from(move |_| {
                                             test_error.set(None);
-                                            test_configuration(extension_id.clone(), extension_configuration.clone(), test_error.clone());
+                                            test_configuration(extension_id.clone(), resource_id, extension_configuration.clone(), test_error.clone());
                                         })
                                     };
                                     html! {
@@ -578,6 +626,30 @@ async fn handle_environment_schema_change(
     return Ok(());
 }
 
+//审批人数必须是非负整数，不受保护的环境不需要审批
+fn get_required_approvals(edit_form: &EditForm) -> Result<i32, LightString> {
+    if !*edit_form.protected {
+        return Ok(0);
+    }
+    let required_approvals = edit_form.required_approvals.trim();
+    if required_approvals.is_empty() {
+        return Ok(0);
+    }
+    match required_approvals.parse::<i32>() {
+        Ok(value) if 0 <= value => Ok(value),
+        _ => Err(LightString::from("审批人数必须是非负整数")),
+    }
+}
+
+fn get_runner_label(edit_form: &EditForm) -> Option<String> {
+    let runner_label = edit_form.runner_label.trim();
+    if runner_label.is_empty() {
+        return None;
+    } else {
+        return Some(runner_label.to_string());
+    }
+}
+
 async fn read_environment_detail(
     edit_form: &EditForm,
     extension_list: &[Extension],
@@ -589,6 +661,14 @@ async fn read_environment_detail(
         .environment_schema_id
         .set(environment.environment_schema_id.into());
     edit_form.name.set(environment.name.clone().into());
+    edit_form.lock_policy.set(environment.lock_policy);
+    edit_form
+        .runner_label
+        .set(environment.runner_label.clone().unwrap_or_default().into());
+    edit_form.protected.set(environment.protected);
+    edit_form
+        .required_approvals
+        .set(environment.required_approvals.to_string().into());
     edit_form.schema_resource_list.set(
         environment
             .schema_resource_list
@@ -635,18 +715,25 @@ async fn read_environment_detail(
 
 fn test_configuration(
     extension_id: String,
+    resource_id: Option<Id>,
     extension_configuration: Vec<(Key, Attribute, AttributeValue)>,
     test_error: UseStateHandle<Option<Result<(), LightString>>>,
 ) {
     wasm_bindgen_futures::spawn_local(async move {
-        try_test_configuration(extension_id, &extension_configuration, &test_error)
-            .await
-            .ok();
+        try_test_configuration(
+            extension_id,
+            resource_id,
+            &extension_configuration,
+            &test_error,
+        )
+        .await
+        .ok();
     });
 }
 
 async fn try_test_configuration(
     extension_id: String,
+    resource_id: Option<Id>,
     extension_configuration: &[(Key, Attribute, AttributeValue)],
     test_error: &UseStateHandle<Option<Result<(), LightString>>>,
 ) -> Result<(), LightString> {
@@ -660,6 +747,7 @@ async fn try_test_configuration(
         .call(&TestConfigurationReq {
             extension_id: extension_id,
             extension_configuration: extension_configuration,
+            resource_id: resource_id,
         })
         .await;
     test_error.set(Some(ret));
@@ -776,6 +864,9 @@ async fn chk_form_err(id: Option<Id>, edit_form: &EditForm) -> Vec<LightString>
     if let Err(error) = edit_form.name.validate(true) {
         err_msgs.push(error);
     }
+    if let Err(error) = get_required_approvals(edit_form) {
+        err_msgs.push(error);
+    }
     let mut empty_id = None;
     let mut active_ids = None;
     for (_key, schema_resource) in edit_form.schema_resource_list.iter() {
@@ -964,6 +1055,10 @@ async fn save_environment(
             .call(&UpdateEnvironmentReq {
                 id: id,
                 name: edit_form.name.get().to_string(),
+                lock_policy: *edit_form.lock_policy,
+                runner_label: get_runner_label(edit_form),
+                protected: *edit_form.protected,
+                required_approvals: get_required_approvals(edit_form).unwrap_or_default(),
                 schema_resource_list: schema_resource_list
                     .into_iter()
                     .map(|(id, extension_id, resource_list)| {
@@ -1006,6 +1101,10 @@ async fn save_environment(
             .call(&InsertEnvironmentReq {
                 environment_schema_id: edit_form.environment_schema_id.get().unwrap(),
                 name: edit_form.name.get().to_string(),
+                lock_policy: *edit_form.lock_policy,
+                runner_label: get_runner_label(edit_form),
+                protected: *edit_form.protected,
+                required_approvals: get_required_approvals(edit_form).unwrap_or_default(),
                 schema_resource_list: schema_resource_list
                     .into_iter()
                     .map(|(id, extension_id, resource_list)| {
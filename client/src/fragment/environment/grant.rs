@@ -0,0 +1,245 @@
+use crate::components::button::Button;
+use crate::components::button_group::ButtonGroup;
+use crate::components::selection::BindingSelection;
+use crate::sdk;
+use crate::utils;
+use crate::utils::request::ApiExt;
+use crate::LightString;
+use sdk::environment::enums::GrantPermission;
+use sdk::environment::query_environment_grant::QueryEnvironmentGrantApi;
+use sdk::environment::query_environment_grant::QueryEnvironmentGrantReq;
+use sdk::environment::save_environment_grant::EnvironmentGrant;
+use sdk::environment::save_environment_grant::SaveEnvironmentGrantApi;
+use sdk::environment::save_environment_grant::SaveEnvironmentGrantReq;
+use sdk::user::query_user::QueryUserApi;
+use sdk::user::query_user::QueryUserReq;
+use std::ops::Deref;
+use tihu::Id;
+use yew::prelude::*;
+
+type UserSelection = BindingSelection<(Id, String)>;
+type PermissionSelection = BindingSelection<(GrantPermission, String)>;
+
+fn get_permission_list() -> Vec<(GrantPermission, String)> {
+    return [
+        GrantPermission::Run,
+        GrantPermission::Edit,
+        GrantPermission::Approve,
+    ]
+    .iter()
+    .map(|value| (*value, value.to_string()))
+    .collect();
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub environment_id: Id,
+    #[prop_or_default]
+    pub onsave: Option<Callback<()>>,
+}
+
+#[function_component]
+pub fn EnvironmentGrantEdit(props: &Props) -> Html {
+    let grant_list: UseStateHandle<Vec<(EnvironmentGrant, Option<String>)>> =
+        use_state(|| Vec::new());
+    let user_list: UseStateHandle<Vec<(Id, String)>> = use_state(|| Vec::new());
+    let user_id: UseStateHandle<Option<Id>> = use_state(|| None);
+    let permission: UseStateHandle<Option<GrantPermission>> =
+        use_state(|| Some(GrantPermission::Run));
+    let is_saving: UseStateHandle<bool> = use_state(|| false);
+    let err_msg: UseStateHandle<Option<LightString>> = use_state(|| None);
+    let environment_id = props.environment_id;
+    let grant_list_clone = grant_list.clone();
+    let user_list_clone = user_list.clone();
+    use_effect_with(environment_id, move |_| {
+        wasm_bindgen_futures::spawn_local(async move {
+            query_user_list(&user_list_clone).await.ok();
+            query_grant_list(environment_id, &grant_list_clone)
+                .await
+                .ok();
+        });
+        || ()
+    });
+    let grant_list_clone = grant_list.clone();
+    let user_list_clone = user_list.clone();
+    let user_id_clone = user_id.clone();
+    let permission_clone = permission.clone();
+    let err_msg_clone = err_msg.clone();
+    let on_add = Callback::from(move |_: ()| {
+        let (user_id, permission) = match (*user_id_clone, *permission_clone) {
+            (Some(user_id), Some(permission)) => (user_id, permission),
+            _ => {
+                err_msg_clone.set(Some(LightString::from("请选择用户和权限")));
+                return;
+            }
+        };
+        err_msg_clone.set(None);
+        let exists = grant_list_clone
+            .iter()
+            .any(|(grant, _)| user_id == grant.user_id && permission == grant.permission);
+        if exists {
+            return;
+        }
+        let user_name = user_list_clone
+            .iter()
+            .find(|(id, _)| user_id == *id)
+            .map(|(_, name)| name.clone());
+        let mut grant_list = grant_list_clone.deref().clone();
+        grant_list.push((
+            EnvironmentGrant {
+                user_id: user_id,
+                permission: permission,
+            },
+            user_name,
+        ));
+        grant_list_clone.set(grant_list);
+    });
+    let grant_list_clone = grant_list.clone();
+    let is_saving_clone = is_saving.clone();
+    let err_msg_clone = err_msg.clone();
+    let onsave_clone = props.onsave.clone();
+    let on_save = Callback::from(move |_: ()| {
+        let grant_list = grant_list_clone.clone();
+        let is_saving = is_saving_clone.clone();
+        let err_msg = err_msg_clone.clone();
+        let onsave = onsave_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            save_grant_list(environment_id, &grant_list, is_saving, &err_msg, &onsave)
+                .await
+                .ok();
+        });
+    });
+    html! {
+        <div class="width-fill height-fill border-box" style="padding:0.25em;">
+            <p style="color:gray;margin-top:0;">{"受保护的环境只有被授权的用户才能执行任务、修改资源和审批，未受保护的环境按照组织内的角色判断"}</p>
+            <table class="e-table width-fill">
+                <thead>
+                    <tr>
+                        <th class="e-table-hcell">{"用户"}</th>
+                        <th class="e-table-hcell">{"权限"}</th>
+                        <th class="e-table-hcell">{"操作"}</th>
+                    </tr>
+                </thead>
+                <tbody>
+                    {
+                        for grant_list.iter().enumerate().map(|(index, (grant, user_name))| {
+                            let grant_list = grant_list.clone();
+                            let on_remove = Callback::from(move |_: ()| {
+                                let mut new_grant_list = grant_list.deref().clone();
+                                new_grant_list.remove(index);
+                                grant_list.set(new_grant_list);
+                            });
+                            html! {
+                                <tr class="e-table-row">
+                                    <td class="e-table-cell align-center">{user_name.clone().unwrap_or_else(|| grant.user_id.to_string())}</td>
+                                    <td class="e-table-cell align-center">{grant.permission.to_string()}</td>
+                                    <td class="e-table-cell align-center">
+                                        <Button onclick={on_remove}>{"移除"}</Button>
+                                    </td>
+                                </tr>
+                            }
+                        })
+                    }
+                    <tr class="e-table-row">
+                        <td class="e-table-cell align-center">
+                            <UserSelection value={user_id.clone()} options={user_list.deref().clone()}/>
+                        </td>
+                        <td class="e-table-cell align-center">
+                            <PermissionSelection value={permission.clone()} options={get_permission_list()}/>
+                        </td>
+                        <td class="e-table-cell align-center">
+                            <Button onclick={on_add}>{"添加"}</Button>
+                        </td>
+                    </tr>
+                </tbody>
+            </table>
+            <div style="padding-top:0.5em;">
+                <ButtonGroup>
+                    <Button disabled={*is_saving} onclick={on_save}>{"保存"}</Button>
+                </ButtonGroup>
+                {
+                    match err_msg.as_ref() {
+                        Some(err_msg) => {
+                            html!{
+                                <span class="middle" style="color:red;margin-left: 0.5em;">{err_msg}</span>
+                            }
+                        },
+                        None => html!{}
+                    }
+                }
+            </div>
+        </div>
+    }
+}
+
+async fn query_user_list(user_list: &UseStateHandle<Vec<(Id, String)>>) -> Result<(), LightString> {
+    let pagination_list = QueryUserApi
+        .call(&QueryUserReq {
+            page_no: Some(1),
+            ..QueryUserReq::empty()
+        })
+        .await?;
+    user_list.set(
+        pagination_list
+            .list
+            .into_iter()
+            .map(|user| (user.id, user.name))
+            .collect(),
+    );
+    return Ok(());
+}
+
+async fn query_grant_list(
+    environment_id: Id,
+    grant_list: &UseStateHandle<Vec<(EnvironmentGrant, Option<String>)>>,
+) -> Result<(), LightString> {
+    let list = QueryEnvironmentGrantApi
+        .call(&QueryEnvironmentGrantReq {
+            environment_id: environment_id,
+        })
+        .await?;
+    grant_list.set(
+        list.into_iter()
+            .map(|grant| {
+                (
+                    EnvironmentGrant {
+                        user_id: grant.user_id,
+                        permission: grant.permission,
+                    },
+                    grant.user_name,
+                )
+            })
+            .collect(),
+    );
+    return Ok(());
+}
+
+async fn save_grant_list(
+    environment_id: Id,
+    grant_list: &UseStateHandle<Vec<(EnvironmentGrant, Option<String>)>>,
+    is_saving: UseStateHandle<bool>,
+    err_msg: &UseStateHandle<Option<LightString>>,
+    onsave: &Option<Callback<()>>,
+) -> Result<(), LightString> {
+    let ret = SaveEnvironmentGrantApi
+        .lock_handler(is_saving)
+        .call(&SaveEnvironmentGrantReq {
+            environment_id: environment_id,
+            grant_list: grant_list.iter().map(|(grant, _)| grant.clone()).collect(),
+        })
+        .await;
+    match ret {
+        Err(err) => {
+            log::error!("{}", err);
+            err_msg.set(Some(err.clone()));
+            return Err(err);
+        }
+        Ok(_) => {
+            if let Some(onsave) = onsave {
+                onsave.emit(());
+            }
+            utils::success(LightString::from("保存成功"));
+            return Ok(());
+        }
+    }
+}
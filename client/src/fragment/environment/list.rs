@@ -1,6 +1,8 @@
 use super::super::job_record::list::JobRecordList;
 use super::detail::EnvironmentDetail;
 use super::edit::EnvironmentEdit;
+use super::grant::EnvironmentGrantEdit;
+use super::render_lock;
 use crate::components::button::Button;
 use crate::components::button_group::ButtonGroup;
 use crate::components::drawer::Drawer;
@@ -33,6 +35,8 @@ pub fn EnvironmentList() -> Html {
     let edit_active: UseStateHandle<bool> = use_state(|| false);
     let active_edit_id: UseStateHandle<Option<Id>> = use_state(|| None);
     let record_list_active: UseStateHandle<bool> = use_state(|| false);
+    let grant_active: UseStateHandle<bool> = use_state(|| false);
+    let active_grant_id: UseStateHandle<Option<Id>> = use_state(|| None);
     let active_edit_id_clone = active_edit_id.clone();
     let edit_active_clone = edit_active.clone();
     let on_open_insert = Callback::from(move |_: ()| {
@@ -97,6 +101,16 @@ pub fn EnvironmentList() -> Html {
         edit_active_clone.set(false);
         active_edit_id_clone.set(None);
     });
+    let grant_active_clone = grant_active.clone();
+    let active_grant_id_clone = active_grant_id.clone();
+    let on_leave_grant = Callback::from(move |_| {
+        grant_active_clone.set(false);
+        active_grant_id_clone.set(None);
+    });
+    let grant_active_clone = grant_active.clone();
+    let on_finish_grant = Callback::from(move |_| {
+        grant_active_clone.set(false);
+    });
     html! {
         <div class="relative width-fill height-fill" style="overflow:hidden;">
             <If condition={!*record_list_active}>
@@ -112,7 +126,7 @@ pub fn EnvironmentList() -> Html {
                             </div>
                         </header>
                         <div style="-webkit-box-flex: 1;flex-basis: auto;flex-grow: 1;overflow-y: auto;">
-                            { table_view(&list, &list_load_status, &pagination, &record_list_active, &active_environment_id, &detail_active, &active_detail_id, &edit_active, &active_edit_id) }
+                            { table_view(&list, &list_load_status, &pagination, &record_list_active, &active_environment_id, &detail_active, &active_detail_id, &edit_active, &active_edit_id, &grant_active, &active_grant_id) }
                             { list_exception_view(list.is_empty(), list_load_status.deref().clone()) }
                         </div>
                         <div style="-webkit-box-flex: 0;flex-basis: auto;flex-grow: 0;padding-top: 0.25em;">
@@ -134,6 +148,16 @@ pub fn EnvironmentList() -> Html {
             <Drawer active={*edit_active} onclickother={on_leave_edit}>
                 <EnvironmentEdit id={active_edit_id.deref().clone()} onsave={on_finish_save} />
             </Drawer>
+            <Drawer active={*grant_active} onclickother={on_leave_grant}>
+                {
+                    match active_grant_id.as_ref() {
+                        Some(active_grant_id) => html! {
+                            <EnvironmentGrantEdit environment_id={*active_grant_id} onsave={on_finish_grant} />
+                        },
+                        None => html! {}
+                    }
+                }
+            </Drawer>
             {
                 if let (true, Some(active_environment_id)) = (*record_list_active, active_environment_id.as_ref()) {
                     html! {
@@ -159,6 +183,8 @@ fn table_view(
     active_detail_id: &UseStateHandle<Option<Id>>,
     edit_active: &UseStateHandle<bool>,
     active_edit_id: &UseStateHandle<Option<Id>>,
+    grant_active: &UseStateHandle<bool>,
+    active_grant_id: &UseStateHandle<Option<Id>>,
 ) -> Html {
     return html! {
         <table class="e-table width-fill">
@@ -166,6 +192,8 @@ fn table_view(
                 <tr>
                     <th class="e-table-hcell">{"环境名称"}</th>
                     <th class="e-table-hcell">{"环境规格"}</th>
+                    <th class="e-table-hcell">{"受保护"}</th>
+                    <th class="e-table-hcell">{"占用情况"}</th>
                     <th class="e-table-hcell">{"操作"}</th>
                 </tr>
             </thead>
@@ -175,7 +203,7 @@ fn table_view(
                         <tbody>
                             {
                                 for list.iter().map(|item| {
-                                    row_view(list, list_load_status, pagination, item, record_list_active, active_environment_id, detail_active, active_detail_id, edit_active, active_edit_id)
+                                    row_view(list, list_load_status, pagination, item, record_list_active, active_environment_id, detail_active, active_detail_id, edit_active, active_edit_id, grant_active, active_grant_id)
                                 })
                             }
                         </tbody>
@@ -199,6 +227,8 @@ fn row_view(
     active_detail_id: &UseStateHandle<Option<Id>>,
     edit_active: &UseStateHandle<bool>,
     active_edit_id: &UseStateHandle<Option<Id>>,
+    grant_active: &UseStateHandle<bool>,
+    active_grant_id: &UseStateHandle<Option<Id>>,
 ) -> Html {
     let detail_id = environment.id;
     let active_environment_id = active_environment_id.clone();
@@ -220,6 +250,13 @@ fn row_view(
         active_edit_id.set(Some(update_id));
         edit_active.set(true);
     });
+    let grant_id = environment.id;
+    let active_grant_id = active_grant_id.clone();
+    let grant_active = grant_active.clone();
+    let on_open_grant = Callback::from(move |_: ()| {
+        active_grant_id.set(Some(grant_id));
+        grant_active.set(true);
+    });
     let delete_id = environment.id;
     let list = list.clone();
     let list_load_status = list_load_status.clone();
@@ -244,11 +281,14 @@ fn row_view(
         <tr class="e-table-row">
             <td class="e-table-cell align-center">{environment.name.clone()}</td>
             <td class="e-table-cell align-center">{environment.environment_schema_name.clone()}</td>
+            <td class="e-table-cell align-center">{if environment.protected {"是"} else {"否"}}</td>
+            <td class="e-table-cell align-center">{render_lock(environment.lock.as_ref())}</td>
             <td class="e-table-cell align-center">
                 <ButtonGroup>
                     <Button onclick={on_open_record_list}>{"执行记录"}</Button>
                     <Button onclick={on_open_detail}>{"详情"}</Button>
                     <Button onclick={on_open_update}>{"编辑"}</Button>
+                    <Button onclick={on_open_grant}>{"授权"}</Button>
                     <Button onclick={on_confirm_delete}>{"删除"}</Button>
                 </ButtonGroup>
             </td>
@@ -1,3 +1,22 @@
 pub mod detail;
 pub mod edit;
+pub mod grant;
 pub mod list;
+use crate::sdk;
+use sdk::environment::EnvironmentLock;
+use tihu::datetime_format::FORMAT;
+use yew::prelude::*;
+
+//展示环境被谁占用，方便了解为什么任务在排队或者被拒绝执行
+pub fn render_lock(lock: Option<&EnvironmentLock>) -> Html {
+    match lock {
+        Some(lock) => html! {
+            <span style="color:orange;">
+                {format!("{}执行任务\"{}\"中，{}开始占用", lock.holder_name, lock.job_name, lock.created_time.format(FORMAT))}
+            </span>
+        },
+        None => html! {
+            <span style="color:green;">{"空闲"}</span>
+        },
+    }
+}
@@ -16,6 +16,7 @@ use sdk::environment_schema::read_environment_schema::ReadEnvironmentSchemaReq;
 use sdk::extension::query_extension::QueryExtensionApi;
 use sdk::extension::query_extension::QueryExtensionReq;
 use sdk::extension::Extension;
+use sdk::job::enums::SignoffRole;
 use sdk::job::read_job::Job;
 use sdk::job::read_job::JobStep;
 use sdk::job::read_job::ReadJobApi;
@@ -63,6 +64,30 @@ pub fn JobDetail(props: &Props) -> Html {
                     <td class="align-right" style="width:8em;vertical-align: top;">{"备注："}</td>
                     <td colspan="3">{detail.as_ref().map(|job|{job.remark.as_ref().map(|remark|{html!{remark}}).unwrap_or_else(utils::empty_html)}).unwrap_or_else(utils::empty_html)}</td>
                 </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;">{"中断策略："}</td>
+                    <td colspan="3">{detail.as_ref().map(|job|{html!{job.interrupt_policy.to_string()}}).unwrap_or_else(utils::empty_html)}</td>
+                </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;">{"执行参数："}</td>
+                    <td colspan="3">
+                        {
+                            detail.as_ref().map(|job| {
+                                if job.parameter_schema.is_empty() {
+                                    html! {"无"}
+                                } else {
+                                    html! {
+                                        for job.parameter_schema.iter().map(|attribute| {
+                                            html! {
+                                                <div>{format!("{}（{{{{params.{}}}}}）{}", attribute.name, attribute.id, if attribute.required { "，必填" } else { "" })}</div>
+                                            }
+                                        })
+                                    }
+                                }
+                            }).unwrap_or_else(utils::empty_html)
+                        }
+                    </td>
+                </tr>
             </table>
             <div style="flex-grow: 1;flex-shrink: 1;position: relative;border-top: 1px solid #CCC;border-bottom: 1px solid #CCC;overflow: auto;">
                 <div style="width:24em;height:100%;display:flex;flex-direction:column;border-right: 1px solid #CCC;box-sizing: border-box;">
@@ -71,7 +96,7 @@ pub fn JobDetail(props: &Props) -> Html {
                         {
                             if let Some(detail) = detail.as_ref() {
                                 html! {
-                                    for detail.job_step_list.iter().map(|job_step| {
+                                    for detail.job_step_list.iter().enumerate().map(|(index, job_step)| {
                                         let active_job_step_id = active_job_step_id.clone();
                                         let job_step_id = get_step_id(job_step);
                                         let is_active = active_job_step_id.deref() == &Some(job_step_id);
@@ -113,9 +138,17 @@ pub fn JobDetail(props: &Props) -> Html {
                                                                 }
                                                             </td>
                                                         </tr>
+                                                        <tr>
+                                                            <td class="align-right" style="vertical-align: top;">{"依赖步骤："}</td>
+                                                            <td>{ get_depends_on_text(&detail.job_step_list, index) }</td>
+                                                        </tr>
+                                                        <tr>
+                                                            <td class="align-right" style="vertical-align: top;">{"执行条件："}</td>
+                                                            <td>{ get_run_condition_text(job_step) }</td>
+                                                        </tr>
                                                         {
                                                             match job_step {
-                                                                JobStep::Auto { schema_resource_id, operation_id, operation_name, operation_parameter, .. } => {
+                                                                JobStep::Auto { schema_resource_id, operation_id, operation_name, operation_parameter, timeout_seconds, max_retries, retry_backoff, max_parallel, batch_size, pause_between_batches, failure_policy, .. } => {
                                                                     let schema_resource = environment_schema_detail.as_ref().map(|environment_schema_detail|environment_schema_detail.resource_list.iter().find(|resource| &resource.id == schema_resource_id)).flatten();
                                                                     if let Some(schema_resource) = schema_resource {
                                                                         let operation_parameter = extension_list
@@ -144,6 +177,26 @@ pub fn JobDetail(props: &Props) -> Html {
                                                                                     <td class="align-right" style="vertical-align: top;">{"操作类型："}</td>
                                                                                     <td>{ operation_name }</td>
                                                                                 </tr>
+                                                                                <tr>
+                                                                                    <td class="align-right" style="vertical-align: top;">{"超时时间："}</td>
+                                                                                    <td>{ timeout_seconds.map(|timeout_seconds| format!("{}秒", timeout_seconds)).unwrap_or_else(|| String::from("不限制")) }</td>
+                                                                                </tr>
+                                                                                <tr>
+                                                                                    <td class="align-right" style="vertical-align: top;">{"失败重试："}</td>
+                                                                                    <td>{ if 0 < *max_retries { format!("最多{}次，首次间隔{}秒", max_retries, retry_backoff) } else { String::from("不重试") } }</td>
+                                                                                </tr>
+                                                                                <tr>
+                                                                                    <td class="align-right" style="vertical-align: top;">{"最大并发："}</td>
+                                                                                    <td>{ max_parallel.map(|max_parallel| max_parallel.to_string()).unwrap_or_else(|| String::from("不限制")) }</td>
+                                                                                </tr>
+                                                                                <tr>
+                                                                                    <td class="align-right" style="vertical-align: top;">{"分批执行："}</td>
+                                                                                    <td>{ batch_size.map(|batch_size| format!("每批{}个资源，批次间隔{}秒", batch_size, pause_between_batches)).unwrap_or_else(|| String::from("不分批")) }</td>
+                                                                                </tr>
+                                                                                <tr>
+                                                                                    <td class="align-right" style="vertical-align: top;">{"失败策略："}</td>
+                                                                                    <td>{ failure_policy.to_string() }</td>
+                                                                                </tr>
                                                                                 <tr>
                                                                                     <td class="align-right" style="vertical-align: top;">{"操作参数："}</td>
                                                                                     <td>
@@ -163,7 +216,7 @@ pub fn JobDetail(props: &Props) -> Html {
                                                                         }
                                                                     }
                                                                 },
-                                                                JobStep::Manual { attachments, .. } => {
+                                                                JobStep::Manual { attachments, signoff_role, signoff_user_list, required_signoffs, .. } => {
                                                                     let files: Vec<File> = attachments.as_ref().map(|attachments| {
                                                                         serde_json::from_str::<Value>(&attachments).map(|value| {
                                                                             value
@@ -210,29 +263,40 @@ pub fn JobDetail(props: &Props) -> Html {
                                                                         .unwrap_or_default()
                                                                     })
                                                                     .unwrap_or_default();
+                                                                    let signoff_policy_text = get_signoff_policy_text(
+                                                                        *signoff_role,
+                                                                        signoff_user_list.as_ref().map(|signoff_user_list| signoff_user_list.len()),
+                                                                        *required_signoffs,
+                                                                    );
                                                                     html! {
-                                                                        <tr>
-                                                                            <td class="align-right" style="vertical-align: top;">{"附件："}</td>
-                                                                            <td>
-                                                                                {
-                                                                                    for files.iter().map(|file| {
-                                                                                        match file {
-                                                                                            File::Remote { key, name, .. } => {
-                                                                                                let url = format!("/{}", key);
-                                                                                                html! {
-                                                                                                    <div>
-                                                                                                        <a href={url} target="_blank" download={name.clone()}>{name}</a>
-                                                                                                    </div>
+                                                                        <>
+                                                                            <tr>
+                                                                                <td class="align-right" style="vertical-align: top;">{"签收要求："}</td>
+                                                                                <td>{ signoff_policy_text }</td>
+                                                                            </tr>
+                                                                            <tr>
+                                                                                <td class="align-right" style="vertical-align: top;">{"附件："}</td>
+                                                                                <td>
+                                                                                    {
+                                                                                        for files.iter().map(|file| {
+                                                                                            match file {
+                                                                                                File::Remote { key, name, .. } => {
+                                                                                                    let url = format!("/{}", key);
+                                                                                                    html! {
+                                                                                                        <div>
+                                                                                                            <a href={url} target="_blank" download={name.clone()}>{name}</a>
+                                                                                                        </div>
+                                                                                                    }
+                                                                                                }
+                                                                                                File::Local(hashing_file) => {
+                                                                                                    html! { hashing_file.file.name() }
                                                                                                 }
                                                                                             }
-                                                                                            File::Local(hashing_file) => {
-                                                                                                html! { hashing_file.file.name() }
-                                                                                            }
-                                                                                        }
-                                                                                    })
-                                                                                }
-                                                                            </td>
-                                                                        </tr>
+                                                                                        })
+                                                                                    }
+                                                                                </td>
+                                                                            </tr>
+                                                                        </>
                                                                     }
                                                                 }
                                                             }
@@ -304,6 +368,87 @@ fn get_step_name(job_step: &JobStep) -> &String {
     }
 }
 
+fn get_step_seq(job_step: &JobStep) -> i32 {
+    match job_step {
+        JobStep::Auto { seq, .. } => *seq,
+        JobStep::Manual { seq, .. } => *seq,
+    }
+}
+
+fn get_depends_on_text(job_step_list: &[JobStep], index: usize) -> String {
+    let depends_on = match &job_step_list[index] {
+        JobStep::Auto { depends_on, .. } => depends_on,
+        JobStep::Manual { depends_on, .. } => depends_on,
+    };
+    match depends_on {
+        Some(depends_on) => {
+            let names: Vec<&str> = depends_on
+                .iter()
+                .filter_map(|dependency| {
+                    job_step_list
+                        .iter()
+                        .find(|job_step| get_step_seq(job_step) == *dependency)
+                        .map(|job_step| get_step_name(job_step).as_str())
+                })
+                .collect();
+            if names.is_empty() {
+                String::from("无")
+            } else {
+                names.join("、")
+            }
+        }
+        None => {
+            if 0 == index {
+                String::from("无")
+            } else {
+                String::from("上一个步骤")
+            }
+        }
+    }
+}
+
+fn get_run_condition_text(job_step: &JobStep) -> String {
+    let (run_if, run_expression) = match job_step {
+        JobStep::Auto {
+            run_if,
+            run_expression,
+            ..
+        } => (run_if, run_expression),
+        JobStep::Manual {
+            run_if,
+            run_expression,
+            ..
+        } => (run_if, run_expression),
+    };
+    match run_expression {
+        Some(run_expression) => format!("{}，并且满足\"{}\"", run_if.to_string(), run_expression),
+        None => run_if.to_string(),
+    }
+}
+
+/**
+ * 手动步骤的签收要求，执行记录详情里也会用到
+ */
+pub fn get_signoff_policy_text(
+    signoff_role: Option<SignoffRole>,
+    signoff_user_count: Option<usize>,
+    required_signoffs: i32,
+) -> String {
+    let mut signers = Vec::new();
+    if let Some(signoff_role) = signoff_role {
+        signers.push(format!("{}及以上角色", signoff_role.to_string()));
+    }
+    if let Some(signoff_user_count) = signoff_user_count {
+        signers.push(format!("指定的{}个签收人", signoff_user_count));
+    }
+    let signers = if signers.is_empty() {
+        String::from("所有能执行任务的成员")
+    } else {
+        signers.join("或者")
+    };
+    format!("{}可以签收，需要{}人确认成功", signers, required_signoffs)
+}
+
 fn get_step_remark(job_step: &JobStep) -> Option<&String> {
     match job_step {
         JobStep::Auto { remark, .. } => remark.as_ref(),
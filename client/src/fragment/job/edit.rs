@@ -4,7 +4,14 @@ use super::super::extension::get_parameter_schema;
 use super::super::extension::parse_config;
 use super::super::extension::serialize_config;
 use super::super::extension::AttributeValue;
+use super::parameter::chk_parameter_err;
+use super::parameter::from_attribute;
+use super::parameter::parameter_schema_view;
+use super::parameter::to_attribute;
+use super::parameter::JobParameter;
 use crate::components::button::Button;
+use crate::components::checkbox::Checkbox;
+use crate::components::checkbox_group::CheckboxGroup;
 use crate::components::files_upload::BindingFilesUpload;
 use crate::components::input::BindingInput;
 use crate::components::r#if::If;
@@ -26,6 +33,8 @@ use crate::utils;
 use crate::utils::binding::Binding;
 use crate::utils::gen_id;
 use crate::utils::request::ApiExt;
+use crate::utils::validator::OptionalValidator;
+use crate::utils::validator::PositiveI32Validator;
 use crate::utils::validator::RequiredValidator;
 use crate::utils::validator::Validators;
 use crate::LightString;
@@ -42,6 +51,10 @@ use sdk::extension::query_extension::QueryExtensionReq;
 use sdk::extension::Attribute;
 use sdk::extension::Extension;
 use sdk::extension::Operation;
+use sdk::job::enums::FailurePolicy;
+use sdk::job::enums::InterruptPolicy;
+use sdk::job::enums::RunIf;
+use sdk::job::enums::SignoffRole;
 use sdk::job::insert_job::InsertJobApi;
 use sdk::job::insert_job::InsertJobReq;
 use sdk::job::read_job::Job;
@@ -49,6 +62,8 @@ use sdk::job::read_job::ReadJobApi;
 use sdk::job::read_job::ReadJobReq;
 use sdk::job::update_job::UpdateJobApi;
 use sdk::job::update_job::UpdateJobReq;
+use sdk::user::query_user::QueryUserApi;
+use sdk::user::query_user::QueryUserReq;
 use serde_json::Value;
 use std::ops::Deref;
 use std::sync::Arc;
@@ -61,7 +76,11 @@ use yew::virtual_dom::Key;
 
 type EnvironmentSchemaSelection = BindingSelection<(Id, String)>;
 type StepTypeRadioGroup = BindingRadioGroup<(StepType, String)>;
+type InterruptPolicyRadioGroup = BindingRadioGroup<(InterruptPolicy, String)>;
+type FailurePolicyRadioGroup = BindingRadioGroup<(FailurePolicy, String)>;
+type RunIfRadioGroup = BindingRadioGroup<(RunIf, String)>;
 type SchemaResourceSelection = BindingSelection<(Id, String)>;
+type SignoffRoleSelection = BindingSelection<(SignoffRole, String)>;
 #[derive(Clone, PartialEq)]
 struct OperationOption(Operation);
 type OperationSelection = BindingSelection<OperationOption>;
@@ -85,6 +104,46 @@ fn get_step_type_list() -> Vec<(StepType, String)> {
         .collect();
 }
 
+fn get_interrupt_policy_list() -> Vec<(InterruptPolicy, String)> {
+    return [InterruptPolicy::Interrupt, InterruptPolicy::Resume]
+        .iter()
+        .map(|value| {
+            return (value.clone(), value.to_string());
+        })
+        .collect();
+}
+
+fn get_run_if_list() -> Vec<(RunIf, String)> {
+    return [RunIf::OnSuccess, RunIf::OnFailure, RunIf::Always]
+        .iter()
+        .map(|value| {
+            return (value.clone(), value.to_string());
+        })
+        .collect();
+}
+
+fn get_failure_policy_list() -> Vec<(FailurePolicy, String)> {
+    return [FailurePolicy::Continue, FailurePolicy::Stop]
+        .iter()
+        .map(|value| {
+            return (value.clone(), value.to_string());
+        })
+        .collect();
+}
+
+fn get_signoff_role_list() -> Vec<(SignoffRole, String)> {
+    return [
+        SignoffRole::Owner,
+        SignoffRole::Admin,
+        SignoffRole::Operator,
+    ]
+    .iter()
+    .map(|value| {
+        return (value.clone(), value.to_string());
+    })
+    .collect();
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub enum StepType {
     Auto,   //自动
@@ -105,6 +164,20 @@ pub struct AutoStep {
     schema_resource_id: ValidateData<Option<Id>>, //环境规格资源id
     operation_id: ValidateData<Option<String>>,   //操作id
     operation_parameter: Binding<Vec<(Key, Attribute, AttributeValue)>>, //操作参数
+    timeout_seconds: ValidateData<LightString>,   //超时时间（秒），为空表示不限制
+    max_retries: ValidateData<LightString>,       //失败后最大重试次数
+    retry_backoff: ValidateData<LightString>,     //首次重试前等待的秒数
+    max_parallel: ValidateData<LightString>,      //最大并发数，为空表示不限制
+    batch_size: ValidateData<LightString>,        //每批资源数，为空表示不分批
+    pause_between_batches: ValidateData<LightString>, //批次间隔（秒）
+    failure_policy: Binding<FailurePolicy>,       //资源执行失败时，是否继续执行剩下的资源
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ManualStep {
+    signoff_role: Binding<Option<SignoffRole>>, //可以签收的最低角色，为空表示不按角色限制
+    signoff_user_list: Binding<Option<Vec<Id>>>, //可以签收的用户，为空表示不按用户限制
+    required_signoffs: ValidateData<LightString>, //需要确认成功的签收人数
 }
 
 #[derive(Clone, PartialEq, Debug)]
@@ -114,7 +187,11 @@ pub struct JobStep {
     name: ValidateData<LightString>,            //步骤名称
     remark: Binding<JsValue>,                   //备注
     attachments: Binding<Vec<(Key, File, ())>>, //附件
+    depends_on: Binding<Option<Vec<Key>>>,      //依赖的步骤，为空时依赖上一个步骤
+    run_if: Binding<RunIf>,                     //执行条件
+    run_expression: Binding<LightString>,       //条件表达式，为空表示不限制
     auto_step: AutoStep,
+    manual_step: ManualStep,
 }
 
 #[derive(Clone)]
@@ -123,6 +200,8 @@ struct EditForm {
     environment_schema_id: ValidateData<Option<Id>>,
     name: ValidateData<LightString>,
     remark: UseStateHandle<LightString>,
+    interrupt_policy: UseStateHandle<InterruptPolicy>,
+    parameter_list: UseStateHandle<Vec<(Key, JobParameter)>>,
     job_step_list: UseStateHandle<Vec<(Key, JobStep)>>,
 }
 
@@ -141,6 +220,7 @@ struct JobEditState {
     environment_schema_list: UseStateHandle<Vec<EnvironmentSchema>>,
     environment_schema_detail: UseStateHandle<Option<EnvironmentSchemaDetail>>,
     extension_list: UseStateHandle<Vec<Extension>>,
+    user_list: UseStateHandle<Vec<(Id, String)>>,
     edit_form: EditForm,
 }
 
@@ -152,6 +232,7 @@ pub fn JobEdit(props: &Props) -> Html {
     let environment_schema_detail: UseStateHandle<Option<EnvironmentSchemaDetail>> =
         use_state(|| None);
     let extension_list: UseStateHandle<Vec<Extension>> = use_state(|| Default::default());
+    let user_list: UseStateHandle<Vec<(Id, String)>> = use_state(|| Vec::new());
     let edit_form = EditForm {
         active_job_step_key: use_state(|| Default::default()),
         environment_schema_id: ValidateData::new(
@@ -163,6 +244,8 @@ pub fn JobEdit(props: &Props) -> Html {
             Some(Validators::new().add(RequiredValidator::new("请输入任务名称"))),
         ),
         remark: use_state(|| Default::default()),
+        interrupt_policy: use_state(|| InterruptPolicy::Interrupt),
+        parameter_list: use_state(|| Default::default()),
         job_step_list: use_state(|| Default::default()),
     };
     let job_edit_state = JobEditState {
@@ -171,6 +254,7 @@ pub fn JobEdit(props: &Props) -> Html {
         environment_schema_list: environment_schema_list.clone(),
         environment_schema_detail: environment_schema_detail.clone(),
         extension_list: extension_list.clone(),
+        user_list: user_list.clone(),
         edit_form: edit_form.clone(),
     };
     let id = props.id;
@@ -180,7 +264,11 @@ pub fn JobEdit(props: &Props) -> Html {
     let environment_schema_detail_clone2 = environment_schema_detail.clone();
     let environment_schema_list_clone = environment_schema_list.clone();
     let extension_list_clone = extension_list.clone();
+    let user_list_clone = user_list.clone();
     use_effect_with(id, move |_| {
+        wasm_bindgen_futures::spawn_local(async move {
+            query_user_list(&user_list_clone).await.ok();
+        });
         wasm_bindgen_futures::spawn_local(async move {
             match query_environment_schema_list(&environment_schema_list_clone).await {
                 Ok(environment_schema_list) => {
@@ -321,6 +409,18 @@ pub fn JobEdit(props: &Props) -> Html {
                         <BindingTextarea value={edit_form.remark.clone()} onfocus={clear_err_msg.clone()} style="width:100%;"/>
                     </td>
                 </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;">{"中断策略："}</td>
+                    <td colspan="3">
+                        <InterruptPolicyRadioGroup value={edit_form.interrupt_policy.clone()} options={get_interrupt_policy_list()} onchange={clear_err_msg.reform(|_| ())} />
+                    </td>
+                </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;">{"执行参数："}</td>
+                    <td colspan="3">
+                        {parameter_schema_view(&edit_form.parameter_list, &clear_err_msg)}
+                    </td>
+                </tr>
             </table>
             <div style="flex-grow: 1;flex-shrink: 1;position: relative;border-top: 1px solid #CCC;border-bottom: 1px solid #CCC;overflow: auto;">
                 {
@@ -410,7 +510,7 @@ pub fn JobEdit(props: &Props) -> Html {
                                                                                 <Button onclick={on_remove} style="margin-left:0.5em;">{"移除"}</Button>
                                                                             </div>
                                                                             <Show condition={is_active} style="position:absolute;left:24em;right:0;top:0;bottom:0;overflow: auto;">
-                                                                                {job_edit_state.job_step_edit_view(&job_step, name.clone(), error, name_validators)}
+                                                                                {job_edit_state.job_step_edit_view(index, &job_step, name.clone(), error, name_validators)}
                                                                             </Show>
                                                                         </div>
                                                                     }
@@ -441,11 +541,22 @@ pub fn JobEdit(props: &Props) -> Html {
                                                 name: init_step_name(Default::default()),
                                                 remark: Binding::new(get_default_rich_text()),
                                                 attachments: Default::default(),
+                                                depends_on: Default::default(),
+                                                run_if: Binding::new(RunIf::OnSuccess),
+                                                run_expression: Default::default(),
                                                 auto_step: AutoStep {
                                                     schema_resource_id: init_schema_resource_id(schema_resource_id),
                                                     operation_id: init_operation_id(operation_id),
                                                     operation_parameter: Binding::new(operation_parameter),
+                                                    timeout_seconds: init_timeout_seconds(Default::default()),
+                                                    max_retries: init_max_retries(LightString::from("0")),
+                                                    retry_backoff: init_retry_backoff(LightString::from("0")),
+                                                    max_parallel: init_max_parallel(Default::default()),
+                                                    batch_size: init_batch_size(Default::default()),
+                                                    pause_between_batches: init_pause_between_batches(LightString::from("0")),
+                                                    failure_policy: Binding::new(FailurePolicy::Continue),
                                                 },
+                                                manual_step: init_manual_step(None, None, 1),
                                             };
                                             let mut new_list = job_step_list_clone.deref().clone();
                                             let new_key: Key = gen_id().into();
@@ -480,6 +591,7 @@ pub fn JobEdit(props: &Props) -> Html {
 impl JobEditState {
     fn job_step_edit_view(
         &self,
+        index: usize,
         job_step: &JobStep,
         name: UseStateHandle<LightString>,
         error: UseStateHandle<Option<LightString>>,
@@ -492,7 +604,17 @@ impl JobEditState {
             err_msg.set(None);
         });
         let auto_step = job_step.auto_step.clone();
+        let manual_step = job_step.manual_step.clone();
         let attachments = job_step.attachments.clone();
+        let user_list = self.user_list.deref().clone();
+        //只能依赖排在前面的步骤
+        let prev_step_list: Vec<(Key, String)> = self
+            .edit_form
+            .job_step_list
+            .iter()
+            .take(index)
+            .map(|(key, job_step)| (key.clone(), job_step.name.get().to_string()))
+            .collect();
         html! {
             <table style="width: 100%;table-layout: fixed;">
                 <tr>
@@ -517,6 +639,69 @@ impl JobEditState {
                         }
                     </td>
                 </tr>
+                <tr>
+                    <td class="align-right" style="vertical-align: top;">{"依赖步骤："}</td>
+                    <td>
+                        {
+                            job_step.depends_on.view(move |depends_on: UseStateHandle<Option<Vec<Key>>>| {
+                                let depends_on_clone = depends_on.clone();
+                                let on_follow_prev_change = Callback::from(move |follow_prev: bool| {
+                                    depends_on_clone.set(if follow_prev { None } else { Some(Vec::new()) });
+                                });
+                                let depends_on_clone = depends_on.clone();
+                                let on_depends_on_change = Callback::from(move |value: Vec<Key>| {
+                                    depends_on_clone.set(Some(value));
+                                });
+                                html! {
+                                    <>
+                                        <Checkbox value={depends_on.is_none()} label={"依赖上一个步骤"} onchange={on_follow_prev_change}/>
+                                        {
+                                            match depends_on.deref() {
+                                                Some(value) => html! {
+                                                    <>
+                                                        <CheckboxGroup<(Key, String)> value={value.clone()} options={prev_step_list.clone()} onchange={on_depends_on_change}/>
+                                                        <div style="color:#999;">{"不选择任何步骤时，和前面的步骤同时开始执行"}</div>
+                                                    </>
+                                                },
+                                                None => html! {},
+                                            }
+                                        }
+                                    </>
+                                }
+                            })
+                        }
+                    </td>
+                </tr>
+                <tr>
+                    <td class="align-right"><Required/>{"执行条件："}</td>
+                    <td>
+                        {
+                            job_step.run_if.view(move |run_if: UseStateHandle<RunIf>| {
+                                html! {
+                                    <>
+                                        <RunIfRadioGroup value={run_if} options={get_run_if_list()}/>
+                                        <div style="color:#999;">{"选择\"有步骤失败时\"可以作为回滚步骤，只在任务有步骤失败后执行"}</div>
+                                    </>
+                                }
+                            })
+                        }
+                    </td>
+                </tr>
+                <tr>
+                    <td class="align-right" style="vertical-align: top;">{"条件表达式："}</td>
+                    <td>
+                        {
+                            job_step.run_expression.view(move |run_expression: UseStateHandle<LightString>| {
+                                html! {
+                                    <>
+                                        <BindingInput value={run_expression} placeholder="如：env == \"生产环境\" && params.confirm，为空表示不限制"/>
+                                        <div style="color:#999;">{"启动任务时根据目标环境名称(env)和执行参数(params.参数标识)计算，不满足时跳过该步骤"}</div>
+                                    </>
+                                }
+                            })
+                        }
+                    </td>
+                </tr>
                 {
                     job_step.step_type.view(move |step_type: UseStateHandle<StepType>| {
                         html! {
@@ -534,6 +719,13 @@ impl JobEditState {
                                             let schema_resource_id = auto_step.schema_resource_id.clone();
                                             let operation_id = auto_step.operation_id.clone();
                                             let operation_parameter = auto_step.operation_parameter.clone();
+                                            let timeout_seconds = auto_step.timeout_seconds.clone();
+                                            let max_retries = auto_step.max_retries.clone();
+                                            let retry_backoff = auto_step.retry_backoff.clone();
+                                            let max_parallel = auto_step.max_parallel.clone();
+                                            let batch_size = auto_step.batch_size.clone();
+                                            let pause_between_batches = auto_step.pause_between_batches.clone();
+                                            let failure_policy = auto_step.failure_policy.clone();
                                             let environment_schema_detail = environment_schema_detail.clone();
                                             html! {
                                                 schema_resource_id.data.view(move |schema_resource_id: UseStateHandle<Option<Id>>| {
@@ -552,6 +744,13 @@ impl JobEditState {
                                                     let schema_resource_id_clone = schema_resource_id.clone();
                                                     let operation_id = operation_id.clone();
                                                     let operation_id_clone = operation_id.clone();
+                                                    let timeout_seconds = timeout_seconds.clone();
+                                                    let max_retries = max_retries.clone();
+                                                    let retry_backoff = retry_backoff.clone();
+                                                    let max_parallel = max_parallel.clone();
+                                                    let batch_size = batch_size.clone();
+                                                    let pause_between_batches = pause_between_batches.clone();
+                                                    let failure_policy = failure_policy.clone();
                                                     let on_schema_resource_change = Callback::from(move |schema_resource| {
                                                         if let Some((schema_resource_id, _)) = schema_resource {
                                                             let extension_id = get_extension_id(&environment_schema_detail_clone, schema_resource_id);
@@ -639,6 +838,93 @@ impl JobEditState {
                                                                     }
                                                                 </td>
                                                             </tr>
+                                                            <tr>
+                                                                <td class="align-right">{"超时时间："}</td>
+                                                                <td>
+                                                                    {
+                                                                        timeout_seconds.view(move |timeout_seconds: UseStateHandle<LightString>, validator| {
+                                                                            html! {
+                                                                                <BindingInput value={timeout_seconds} onupdate={validator} placeholder="单位：秒，为空表示不限制"/>
+                                                                            }
+                                                                        })
+                                                                    }
+                                                                </td>
+                                                            </tr>
+                                                            <tr>
+                                                                <td class="align-right"><Required/>{"重试次数："}</td>
+                                                                <td>
+                                                                    {
+                                                                        max_retries.view(move |max_retries: UseStateHandle<LightString>, validator| {
+                                                                            html! {
+                                                                                <BindingInput value={max_retries} onupdate={validator}/>
+                                                                            }
+                                                                        })
+                                                                    }
+                                                                </td>
+                                                            </tr>
+                                                            <tr>
+                                                                <td class="align-right"><Required/>{"重试间隔："}</td>
+                                                                <td>
+                                                                    {
+                                                                        retry_backoff.view(move |retry_backoff: UseStateHandle<LightString>, validator| {
+                                                                            html! {
+                                                                                <BindingInput value={retry_backoff} onupdate={validator} placeholder="单位：秒，之后每次重试翻倍"/>
+                                                                            }
+                                                                        })
+                                                                    }
+                                                                </td>
+                                                            </tr>
+                                                            <tr>
+                                                                <td class="align-right">{"最大并发："}</td>
+                                                                <td>
+                                                                    {
+                                                                        max_parallel.view(move |max_parallel: UseStateHandle<LightString>, validator| {
+                                                                            html! {
+                                                                                <BindingInput value={max_parallel} onupdate={validator} placeholder="同时执行的资源数，为空表示不限制"/>
+                                                                            }
+                                                                        })
+                                                                    }
+                                                                </td>
+                                                            </tr>
+                                                            <tr>
+                                                                <td class="align-right">{"分批执行："}</td>
+                                                                <td>
+                                                                    {
+                                                                        batch_size.view(move |batch_size: UseStateHandle<LightString>, validator| {
+                                                                            html! {
+                                                                                <BindingInput value={batch_size} onupdate={validator} placeholder="每批资源数，为空表示不分批"/>
+                                                                            }
+                                                                        })
+                                                                    }
+                                                                </td>
+                                                            </tr>
+                                                            <tr>
+                                                                <td class="align-right"><Required/>{"批次间隔："}</td>
+                                                                <td>
+                                                                    {
+                                                                        pause_between_batches.view(move |pause_between_batches: UseStateHandle<LightString>, validator| {
+                                                                            html! {
+                                                                                <BindingInput value={pause_between_batches} onupdate={validator} placeholder="单位：秒，上一批执行完之后等待的时间"/>
+                                                                            }
+                                                                        })
+                                                                    }
+                                                                </td>
+                                                            </tr>
+                                                            <tr>
+                                                                <td class="align-right"><Required/>{"失败策略："}</td>
+                                                                <td>
+                                                                    {
+                                                                        failure_policy.view(move |failure_policy: UseStateHandle<FailurePolicy>| {
+                                                                            html! {
+                                                                                <>
+                                                                                    <FailurePolicyRadioGroup value={failure_policy} options={get_failure_policy_list()}/>
+                                                                                    <div style="color:#999;">{"有资源执行失败时，停止执行还没开始的资源；分批执行时，失败批次之后的资源都会跳过"}</div>
+                                                                                </>
+                                                                            }
+                                                                        })
+                                                                    }
+                                                                </td>
+                                                            </tr>
                                                             <tr>
                                                                 <td class="align-right" style="vertical-align: top;">{"操作参数："}</td>
                                                                 <td>
@@ -647,6 +933,7 @@ impl JobEditState {
                                                                             config_view(&operation_parameter)
                                                                         })
                                                                     }
+                                                                    <div style="color:#999;">{"可以通过{{steps.步骤名称.outputs.字段}}引用所依赖步骤的输出"}</div>
                                                                 </td>
                                                             </tr>
                                                         </>
@@ -654,17 +941,82 @@ impl JobEditState {
                                                 })
                                             }
                                         },
-                                        StepType::Manual => html!{
-                                            attachments.view(move |files: UseStateHandle<Vec<(Key, File, ())>>| {
-                                                html! {
+                                        StepType::Manual => {
+                                            let user_list = user_list.clone();
+                                            html!{
+                                                <>
+                                                    {
+                                                        attachments.view(move |files: UseStateHandle<Vec<(Key, File, ())>>| {
+                                                            html! {
+                                                                <tr>
+                                                                    <td class="align-right" style="vertical-align: top;">{"附件："}</td>
+                                                                    <td>
+                                                                        <BindingFilesUpload<()> files={files}/>
+                                                                    </td>
+                                                                </tr>
+                                                            }
+                                                        })
+                                                    }
                                                     <tr>
-                                                        <td class="align-right" style="vertical-align: top;">{"附件："}</td>
+                                                        <td class="align-right" style="vertical-align: top;">{"签收角色："}</td>
                                                         <td>
-                                                            <BindingFilesUpload<()> files={files}/>
+                                                            {
+                                                                manual_step.signoff_role.view(move |signoff_role: UseStateHandle<Option<SignoffRole>>| {
+                                                                    html! {
+                                                                        <>
+                                                                            <SignoffRoleSelection value={signoff_role} options={get_signoff_role_list()} clearable={true} placeholder="不按角色限制"/>
+                                                                            <div style="color:#999;">{"该角色以及权限更高的角色都可以签收，所有者总是可以签收"}</div>
+                                                                        </>
+                                                                    }
+                                                                })
+                                                            }
                                                         </td>
                                                     </tr>
-                                                }
-                                            })
+                                                    <tr>
+                                                        <td class="align-right" style="vertical-align: top;">{"签收人："}</td>
+                                                        <td>
+                                                            {
+                                                                manual_step.signoff_user_list.view(move |signoff_user_list: UseStateHandle<Option<Vec<Id>>>| {
+                                                                    let signoff_user_list_clone = signoff_user_list.clone();
+                                                                    let on_limit_change = Callback::from(move |limit_users: bool| {
+                                                                        signoff_user_list_clone.set(if limit_users { Some(Vec::new()) } else { None });
+                                                                    });
+                                                                    let signoff_user_list_clone = signoff_user_list.clone();
+                                                                    let on_user_change = Callback::from(move |value: Vec<Id>| {
+                                                                        signoff_user_list_clone.set(Some(value));
+                                                                    });
+                                                                    html! {
+                                                                        <>
+                                                                            <Checkbox value={signoff_user_list.is_some()} label={"指定签收人"} onchange={on_limit_change}/>
+                                                                            {
+                                                                                match signoff_user_list.deref() {
+                                                                                    Some(value) => html! {
+                                                                                        <CheckboxGroup<(Id, String)> value={value.clone()} options={user_list.clone()} onchange={on_user_change}/>
+                                                                                    },
+                                                                                    None => html! {},
+                                                                                }
+                                                                            }
+                                                                            <div style="color:#999;">{"同时设置了签收角色时，满足角色或者在签收人里都可以签收；都不设置时所有能执行任务的成员都可以签收"}</div>
+                                                                        </>
+                                                                    }
+                                                                })
+                                                            }
+                                                        </td>
+                                                    </tr>
+                                                    <tr>
+                                                        <td class="align-right"><Required/>{"签收人数："}</td>
+                                                        <td>
+                                                            {
+                                                                manual_step.required_signoffs.view(move |required_signoffs: UseStateHandle<LightString>, validator| {
+                                                                    html! {
+                                                                        <BindingInput value={required_signoffs} onupdate={validator} placeholder="确认成功的人数达到之后步骤才算成功"/>
+                                                                    }
+                                                                })
+                                                            }
+                                                        </td>
+                                                    </tr>
+                                                </>
+                                            }
                                         }
                                     }
                                 }
@@ -691,6 +1043,78 @@ fn init_schema_resource_id(value: Option<Id>) -> ValidateData<Option<Id>> {
     )
 }
 
+fn init_timeout_seconds(value: LightString) -> ValidateData<LightString> {
+    ValidateData::new(
+        value,
+        Some(
+            Validators::new().add(OptionalValidator::new(PositiveI32Validator::new(
+                "超时时间必须为正整数",
+                false,
+            ))),
+        ),
+    )
+}
+
+fn init_max_retries(value: LightString) -> ValidateData<LightString> {
+    ValidateData::new(
+        value,
+        Some(Validators::new().add(PositiveI32Validator::new("重试次数必须为非负整数", true))),
+    )
+}
+
+fn init_retry_backoff(value: LightString) -> ValidateData<LightString> {
+    ValidateData::new(
+        value,
+        Some(Validators::new().add(PositiveI32Validator::new("重试间隔必须为非负整数", true))),
+    )
+}
+
+fn init_max_parallel(value: LightString) -> ValidateData<LightString> {
+    ValidateData::new(
+        value,
+        Some(
+            Validators::new().add(OptionalValidator::new(PositiveI32Validator::new(
+                "最大并发数必须为正整数",
+                false,
+            ))),
+        ),
+    )
+}
+
+fn init_batch_size(value: LightString) -> ValidateData<LightString> {
+    ValidateData::new(
+        value,
+        Some(
+            Validators::new().add(OptionalValidator::new(PositiveI32Validator::new(
+                "每批资源数必须为正整数",
+                false,
+            ))),
+        ),
+    )
+}
+
+fn init_pause_between_batches(value: LightString) -> ValidateData<LightString> {
+    ValidateData::new(
+        value,
+        Some(Validators::new().add(PositiveI32Validator::new("批次间隔必须为非负整数", true))),
+    )
+}
+
+fn init_manual_step(
+    signoff_role: Option<SignoffRole>,
+    signoff_user_list: Option<Vec<Id>>,
+    required_signoffs: i32,
+) -> ManualStep {
+    ManualStep {
+        signoff_role: Binding::new(signoff_role),
+        signoff_user_list: Binding::new(signoff_user_list),
+        required_signoffs: ValidateData::new(
+            required_signoffs.to_string().into(),
+            Some(Validators::new().add(PositiveI32Validator::new("签收人数必须为正整数", false))),
+        ),
+    }
+}
+
 fn init_operation_id(value: Option<String>) -> ValidateData<Option<String>> {
     ValidateData::new(
         value,
@@ -698,6 +1122,23 @@ fn init_operation_id(value: Option<String>) -> ValidateData<Option<String>> {
     )
 }
 
+async fn query_user_list(user_list: &UseStateHandle<Vec<(Id, String)>>) -> Result<(), LightString> {
+    let pagination_list = QueryUserApi
+        .call(&QueryUserReq {
+            page_no: Some(1),
+            ..QueryUserReq::empty()
+        })
+        .await?;
+    user_list.set(
+        pagination_list
+            .list
+            .into_iter()
+            .map(|user| (user.id, user.name))
+            .collect(),
+    );
+    return Ok(());
+}
+
 async fn query_extension_list(
     extension_list: &UseStateHandle<Vec<Extension>>,
 ) -> Result<Vec<Extension>, LightString> {
@@ -738,16 +1179,46 @@ async fn read_job_detail(
     edit_form
         .remark
         .set(job.remark.clone().unwrap_or_default().into());
+    edit_form.interrupt_policy.set(job.interrupt_policy);
+    edit_form.parameter_list.set(
+        job.parameter_schema
+            .iter()
+            .map(|attribute| (utils::gen_id().into(), from_attribute(attribute)))
+            .collect(),
+    );
     let environment_schema_detail =
         read_environment_schema_detail(environment_schema_detail, job.environment_schema_id)
             .await?;
     let resource_list = environment_schema_detail.resource_list;
+    //依赖关系保存的是步骤的执行顺序，编辑时换成步骤的key，调整顺序之后仍然有效
+    let key_list: Vec<(i32, Key)> = job
+        .job_step_list
+        .iter()
+        .map(|job_step| match job_step {
+            sdk::job::read_job::JobStep::Auto { seq, .. } => (*seq, gen_id().into()),
+            sdk::job::read_job::JobStep::Manual { seq, .. } => (*seq, gen_id().into()),
+        })
+        .collect();
+    let to_depends_on_keys = |depends_on: &Option<Vec<i32>>| -> Binding<Option<Vec<Key>>> {
+        Binding::new(depends_on.as_ref().map(|depends_on| {
+            depends_on
+                .iter()
+                .filter_map(|dependency| {
+                    key_list
+                        .iter()
+                        .find(|(seq, _)| seq == dependency)
+                        .map(|(_, key)| key.clone())
+                })
+                .collect()
+        }))
+    };
     edit_form.job_step_list.set(
         job.job_step_list
             .iter()
-            .map(|job_step| {
+            .zip(key_list.iter())
+            .map(|(job_step, (_, key))| {
                 (
-                    utils::gen_id().into(),
+                    key.clone(),
                     match job_step {
                         sdk::job::read_job::JobStep::Auto {
                             id,
@@ -756,6 +1227,16 @@ async fn read_job_detail(
                             operation_id,
                             operation_parameter,
                             remark,
+                            timeout_seconds,
+                            max_retries,
+                            retry_backoff,
+                            max_parallel,
+                            batch_size,
+                            pause_between_batches,
+                            failure_policy,
+                            run_if,
+                            run_expression,
+                            depends_on,
                             ..
                         } => {
                             let operation_parameter =
@@ -798,13 +1279,44 @@ async fn read_job_detail(
                                         .unwrap_or_else(get_default_rich_text),
                                 ),
                                 attachments: Default::default(),
+                                depends_on: to_depends_on_keys(depends_on),
+                                run_if: Binding::new(*run_if),
+                                run_expression: Binding::new(
+                                    run_expression.clone().unwrap_or_default().into(),
+                                ),
                                 auto_step: AutoStep {
                                     schema_resource_id: init_schema_resource_id(Some(
                                         *schema_resource_id,
                                     )),
                                     operation_id: init_operation_id(operation_id.clone().into()),
                                     operation_parameter: Binding::new(operation_parameter),
+                                    timeout_seconds: init_timeout_seconds(
+                                        timeout_seconds
+                                            .map(|timeout_seconds| {
+                                                timeout_seconds.to_string().into()
+                                            })
+                                            .unwrap_or_default(),
+                                    ),
+                                    max_retries: init_max_retries(max_retries.to_string().into()),
+                                    retry_backoff: init_retry_backoff(
+                                        retry_backoff.to_string().into(),
+                                    ),
+                                    max_parallel: init_max_parallel(
+                                        max_parallel
+                                            .map(|max_parallel| max_parallel.to_string().into())
+                                            .unwrap_or_default(),
+                                    ),
+                                    batch_size: init_batch_size(
+                                        batch_size
+                                            .map(|batch_size| batch_size.to_string().into())
+                                            .unwrap_or_default(),
+                                    ),
+                                    pause_between_batches: init_pause_between_batches(
+                                        pause_between_batches.to_string().into(),
+                                    ),
+                                    failure_policy: Binding::new(*failure_policy),
                                 },
+                                manual_step: init_manual_step(None, None, 1),
                             }
                         }
                         sdk::job::read_job::JobStep::Manual {
@@ -812,6 +1324,12 @@ async fn read_job_detail(
                             name,
                             remark,
                             attachments,
+                            signoff_role,
+                            signoff_user_list,
+                            required_signoffs,
+                            run_if,
+                            run_expression,
+                            depends_on,
                             ..
                         } => JobStep {
                             step_type: Binding::new(StepType::Manual),
@@ -890,11 +1408,30 @@ async fn read_job_detail(
                                     })
                                     .unwrap_or_default(),
                             ),
+                            depends_on: to_depends_on_keys(depends_on),
+                            run_if: Binding::new(*run_if),
+                            run_expression: Binding::new(
+                                run_expression.clone().unwrap_or_default().into(),
+                            ),
                             auto_step: AutoStep {
                                 schema_resource_id: Default::default(),
                                 operation_id: Default::default(),
                                 operation_parameter: Default::default(),
+                                timeout_seconds: init_timeout_seconds(Default::default()),
+                                max_retries: init_max_retries(LightString::from("0")),
+                                retry_backoff: init_retry_backoff(LightString::from("0")),
+                                max_parallel: init_max_parallel(Default::default()),
+                                batch_size: init_batch_size(Default::default()),
+                                pause_between_batches: init_pause_between_batches(
+                                    LightString::from("0"),
+                                ),
+                                failure_policy: Binding::new(FailurePolicy::Continue),
                             },
+                            manual_step: init_manual_step(
+                                *signoff_role,
+                                signoff_user_list.clone(),
+                                *required_signoffs,
+                            ),
                         },
                     },
                 )
@@ -959,14 +1496,37 @@ async fn chk_form_err(id: Option<Id>, edit_form: &EditForm) -> Vec<LightString>
     if let Err(error) = edit_form.name.validate(true) {
         err_msgs.push(error);
     }
+    err_msgs.extend(chk_parameter_err(&edit_form.parameter_list));
     let mut active_step_key = None;
-    for (step_key, job_step) in edit_form.job_step_list.iter() {
+    for (index, (step_key, job_step)) in edit_form.job_step_list.iter().enumerate() {
         if let Err(error) = job_step.name.validate(true) {
             err_msgs.push(error);
             if active_step_key.is_none() {
                 active_step_key.replace(step_key.clone());
             }
         }
+        //调整顺序之后，依赖的步骤可能排到了后面
+        let depends_on_later = job_step
+            .depends_on
+            .get()
+            .unwrap_or_default()
+            .iter()
+            .any(|key| {
+                edit_form
+                    .job_step_list
+                    .iter()
+                    .skip(index)
+                    .any(|(step_key, _)| step_key == key)
+            });
+        if depends_on_later {
+            err_msgs.push(LightString::from(format!(
+                "步骤\"{}\"只能依赖排在它前面的步骤",
+                job_step.name.get()
+            )));
+            if active_step_key.is_none() {
+                active_step_key.replace(step_key.clone());
+            }
+        }
         let step_type = job_step.step_type.get();
         if StepType::Auto == step_type {
             if let Err(error) = job_step.auto_step.schema_resource_id.validate(true) {
@@ -981,6 +1541,21 @@ async fn chk_form_err(id: Option<Id>, edit_form: &EditForm) -> Vec<LightString>
                     active_step_key.replace(step_key.clone());
                 }
             }
+            for retry_data in [
+                &job_step.auto_step.timeout_seconds,
+                &job_step.auto_step.max_retries,
+                &job_step.auto_step.retry_backoff,
+                &job_step.auto_step.max_parallel,
+                &job_step.auto_step.batch_size,
+                &job_step.auto_step.pause_between_batches,
+            ] {
+                if let Err(error) = retry_data.validate(true) {
+                    err_msgs.push(error);
+                    if active_step_key.is_none() {
+                        active_step_key.replace(step_key.clone());
+                    }
+                }
+            }
             for (_, _, value) in job_step.auto_step.operation_parameter.get().iter() {
                 if let Err(error) = value.validate(true) {
                     err_msgs.push(error);
@@ -989,6 +1564,24 @@ async fn chk_form_err(id: Option<Id>, edit_form: &EditForm) -> Vec<LightString>
                     }
                 }
             }
+        } else {
+            if let Err(error) = job_step.manual_step.required_signoffs.validate(true) {
+                err_msgs.push(error);
+                if active_step_key.is_none() {
+                    active_step_key.replace(step_key.clone());
+                }
+            }
+            if let Some(signoff_user_list) = job_step.manual_step.signoff_user_list.get() {
+                if signoff_user_list.is_empty() {
+                    err_msgs.push(LightString::from(format!(
+                        "步骤\"{}\"请至少选择一个签收人",
+                        job_step.name.get()
+                    )));
+                    if active_step_key.is_none() {
+                        active_step_key.replace(step_key.clone());
+                    }
+                }
+            }
         }
     }
     if let Some(active_step_key) = active_step_key {
@@ -1147,10 +1740,43 @@ fn collect_job_step_list_list(
     String,
     Option<String>,
     Option<String>,
-    Option<(Id, String, String)>,
+    (Option<SignoffRole>, Option<Vec<Id>>, i32),
+    Option<Vec<i32>>,
+    (RunIf, Option<String>),
+    Option<(
+        Id,
+        String,
+        String,
+        Option<i32>,
+        i32,
+        i32,
+        (Option<i32>, Option<i32>, i32, FailurePolicy),
+    )>,
 )> {
     let mut job_step_list: Vec<_> = Vec::with_capacity(edit_form.job_step_list.len());
     for (_, job_step) in edit_form.job_step_list.deref().iter() {
+        //依赖的步骤换成执行顺序，已移除的步骤忽略掉
+        let run_expression = job_step.run_expression.get();
+        let run_condition = (
+            job_step.run_if.get(),
+            if run_expression.is_empty() {
+                None
+            } else {
+                Some(run_expression.to_string())
+            },
+        );
+        let depends_on = job_step.depends_on.get().map(|depends_on| {
+            depends_on
+                .iter()
+                .filter_map(|key| {
+                    edit_form
+                        .job_step_list
+                        .iter()
+                        .position(|(step_key, _)| step_key == key)
+                        .map(|index| index as i32)
+                })
+                .collect()
+        });
         let step_type = job_step.step_type.get();
         let id = job_step.id.get();
         let name = job_step.name.get();
@@ -1162,15 +1788,36 @@ fn collect_job_step_list_list(
                 let operation_id = job_step.auto_step.operation_id.get();
                 let operation_parameter =
                     serialize_config(&job_step.auto_step.operation_parameter.get());
+                let timeout_seconds = job_step.auto_step.timeout_seconds.get().parse().ok();
+                let max_retries = job_step.auto_step.max_retries.get().parse().unwrap_or(0);
+                let retry_backoff = job_step.auto_step.retry_backoff.get().parse().unwrap_or(0);
+                let batch_policy = (
+                    job_step.auto_step.max_parallel.get().parse().ok(),
+                    job_step.auto_step.batch_size.get().parse().ok(),
+                    job_step
+                        .auto_step
+                        .pause_between_batches
+                        .get()
+                        .parse()
+                        .unwrap_or(0),
+                    job_step.auto_step.failure_policy.get(),
+                );
                 job_step_list.push((
                     id,
                     name.to_string(),
                     remark,
                     None,
+                    (None, None, 1),
+                    depends_on,
+                    run_condition,
                     Some((
                         schema_resource_id.unwrap(),
                         operation_id.unwrap(),
                         operation_parameter,
+                        timeout_seconds,
+                        max_retries,
+                        retry_backoff,
+                        batch_policy,
                     )),
                 ));
             }
@@ -1203,7 +1850,26 @@ fn collect_job_step_list_list(
                 } else {
                     serde_json::to_string(&Value::Array(files)).ok()
                 };
-                job_step_list.push((id, name.to_string(), remark, attachments, None));
+                let signoff_policy = (
+                    job_step.manual_step.signoff_role.get(),
+                    job_step.manual_step.signoff_user_list.get(),
+                    job_step
+                        .manual_step
+                        .required_signoffs
+                        .get()
+                        .parse()
+                        .unwrap_or(1),
+                );
+                job_step_list.push((
+                    id,
+                    name.to_string(),
+                    remark,
+                    attachments,
+                    signoff_policy,
+                    depends_on,
+                    run_condition,
+                    None,
+                ));
             }
         }
     }
@@ -1227,6 +1893,11 @@ async fn save_job(
         return Err(err);
     }
     let job_step_list = collect_job_step_list_list(edit_form);
+    let parameter_schema: Vec<Attribute> = edit_form
+        .parameter_list
+        .iter()
+        .map(|(_, parameter)| to_attribute(parameter))
+        .collect();
     let name = edit_form.name.get().to_string();
     let remark = if edit_form.remark.is_empty() {
         None
@@ -1238,31 +1909,71 @@ async fn save_job(
             id: id,
             name: name,
             remark: remark,
+            interrupt_policy: *edit_form.interrupt_policy,
+            parameter_schema: parameter_schema,
             job_step_list: job_step_list
                 .into_iter()
                 .enumerate()
-                .map(|(index, (id, name, remark, attachments, auto_step))| {
-                    if let Some((schema_resource_id, operation_id, operation_parameter)) = auto_step
-                    {
-                        sdk::job::update_job::JobStep::Auto {
-                            id: id,
-                            name: name,
-                            schema_resource_id: schema_resource_id,
-                            operation_id: operation_id,
-                            operation_parameter: operation_parameter,
-                            remark: remark,
-                            seq: index as i32,
-                        }
-                    } else {
-                        sdk::job::update_job::JobStep::Manual {
-                            id: id,
-                            name: name,
-                            remark: remark,
-                            attachments: attachments,
-                            seq: index as i32,
+                .map(
+                    |(
+                        index,
+                        (
+                            id,
+                            name,
+                            remark,
+                            attachments,
+                            (signoff_role, signoff_user_list, required_signoffs),
+                            depends_on,
+                            (run_if, run_expression),
+                            auto_step,
+                        ),
+                    )| {
+                        if let Some((
+                            schema_resource_id,
+                            operation_id,
+                            operation_parameter,
+                            timeout_seconds,
+                            max_retries,
+                            retry_backoff,
+                            (max_parallel, batch_size, pause_between_batches, failure_policy),
+                        )) = auto_step
+                        {
+                            sdk::job::update_job::JobStep::Auto {
+                                id: id,
+                                name: name,
+                                schema_resource_id: schema_resource_id,
+                                operation_id: operation_id,
+                                operation_parameter: operation_parameter,
+                                remark: remark,
+                                timeout_seconds: timeout_seconds,
+                                max_retries: max_retries,
+                                retry_backoff: retry_backoff,
+                                max_parallel: max_parallel,
+                                batch_size: batch_size,
+                                pause_between_batches: pause_between_batches,
+                                failure_policy: failure_policy,
+                                run_if: run_if,
+                                run_expression: run_expression,
+                                seq: index as i32,
+                                depends_on: depends_on,
+                            }
+                        } else {
+                            sdk::job::update_job::JobStep::Manual {
+                                id: id,
+                                name: name,
+                                remark: remark,
+                                attachments: attachments,
+                                signoff_role: signoff_role,
+                                signoff_user_list: signoff_user_list,
+                                required_signoffs: required_signoffs,
+                                run_if: run_if,
+                                run_expression: run_expression,
+                                seq: index as i32,
+                                depends_on: depends_on,
+                            }
                         }
-                    }
-                })
+                    },
+                )
                 .collect(),
         };
         let ret = UpdateJobApi.lock_handler(is_saving).call(&params).await;
@@ -1286,29 +1997,69 @@ async fn save_job(
             environment_schema_id: edit_form.environment_schema_id.get().unwrap(),
             name: name,
             remark: remark,
+            interrupt_policy: *edit_form.interrupt_policy,
+            parameter_schema: parameter_schema,
             job_step_list: job_step_list
                 .into_iter()
                 .enumerate()
-                .map(|(index, (_id, name, remark, attachments, auto_step))| {
-                    if let Some((schema_resource_id, operation_id, operation_parameter)) = auto_step
-                    {
-                        sdk::job::insert_job::JobStep::Auto {
-                            name: name,
-                            schema_resource_id: schema_resource_id,
-                            operation_id: operation_id,
-                            operation_parameter: operation_parameter,
-                            remark: remark,
-                            seq: index as i32,
-                        }
-                    } else {
-                        sdk::job::insert_job::JobStep::Manual {
-                            name: name,
-                            remark: remark,
-                            attachments: attachments,
-                            seq: index as i32,
+                .map(
+                    |(
+                        index,
+                        (
+                            _id,
+                            name,
+                            remark,
+                            attachments,
+                            (signoff_role, signoff_user_list, required_signoffs),
+                            depends_on,
+                            (run_if, run_expression),
+                            auto_step,
+                        ),
+                    )| {
+                        if let Some((
+                            schema_resource_id,
+                            operation_id,
+                            operation_parameter,
+                            timeout_seconds,
+                            max_retries,
+                            retry_backoff,
+                            (max_parallel, batch_size, pause_between_batches, failure_policy),
+                        )) = auto_step
+                        {
+                            sdk::job::insert_job::JobStep::Auto {
+                                name: name,
+                                schema_resource_id: schema_resource_id,
+                                operation_id: operation_id,
+                                operation_parameter: operation_parameter,
+                                remark: remark,
+                                timeout_seconds: timeout_seconds,
+                                max_retries: max_retries,
+                                retry_backoff: retry_backoff,
+                                max_parallel: max_parallel,
+                                batch_size: batch_size,
+                                pause_between_batches: pause_between_batches,
+                                failure_policy: failure_policy,
+                                run_if: run_if,
+                                run_expression: run_expression,
+                                seq: index as i32,
+                                depends_on: depends_on,
+                            }
+                        } else {
+                            sdk::job::insert_job::JobStep::Manual {
+                                name: name,
+                                remark: remark,
+                                attachments: attachments,
+                                signoff_role: signoff_role,
+                                signoff_user_list: signoff_user_list,
+                                required_signoffs: required_signoffs,
+                                run_if: run_if,
+                                run_expression: run_expression,
+                                seq: index as i32,
+                                depends_on: depends_on,
+                            }
                         }
-                    }
-                })
+                    },
+                )
                 .collect(),
         };
         let ret = InsertJobApi.lock_handler(is_saving).call(&params).await;
@@ -1,4 +1,5 @@
 pub mod detail;
 pub mod edit;
 pub mod list;
+pub mod parameter;
 pub mod start_job;
@@ -0,0 +1,350 @@
+use crate::components::button::Button;
+use crate::components::checkbox::BindingCheckbox;
+use crate::components::input::BindingInput;
+use crate::components::r#if::If;
+use crate::components::selection::BindingSelection;
+use crate::components::table::ArcRowRenderer;
+use crate::components::table::Column;
+use crate::components::table::Table;
+use crate::components::validate_wrapper::ValidateData;
+use crate::components::ArcRenderer;
+use crate::sdk;
+use crate::utils;
+use crate::utils::binding::Binding;
+use crate::utils::validator::RequiredValidator;
+use crate::utils::validator::Validators;
+use crate::LightString;
+use sdk::extension::Attribute;
+use sdk::extension::AttributeType;
+use sdk::extension::EnumOption;
+use std::collections::HashSet;
+use std::ops::Deref;
+use yew::prelude::*;
+use yew::virtual_dom::Key;
+
+type ParameterTypeSelection = BindingSelection<(ParameterType, String)>;
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ParameterType {
+    String,
+    LongString,
+    Password,
+    Bool,
+    Enum,
+    StringList,
+}
+
+impl ToString for ParameterType {
+    fn to_string(&self) -> String {
+        match *self {
+            ParameterType::String => "文本".into(),
+            ParameterType::LongString => "长文本".into(),
+            ParameterType::Password => "密码".into(),
+            ParameterType::Bool => "开关".into(),
+            ParameterType::Enum => "枚举".into(),
+            ParameterType::StringList => "文本列表".into(),
+        }
+    }
+}
+
+fn get_parameter_type_list() -> Vec<(ParameterType, String)> {
+    return [
+        ParameterType::String,
+        ParameterType::LongString,
+        ParameterType::Password,
+        ParameterType::Bool,
+        ParameterType::Enum,
+        ParameterType::StringList,
+    ]
+    .iter()
+    .map(|value| {
+        return (value.clone(), value.to_string());
+    })
+    .collect();
+}
+
+//任务执行时需要填写的参数
+#[derive(Clone, PartialEq, Debug)]
+pub struct JobParameter {
+    id: ValidateData<LightString>,     //参数标识，通过{{params.标识}}引用
+    name: ValidateData<LightString>,   //参数名称
+    description: Binding<LightString>, //参数说明
+    parameter_type: ValidateData<Option<ParameterType>>, //参数类型
+    required: Binding<bool>,           //是否必填
+    options: Binding<LightString>,     //枚举选项，多个用英文逗号分隔
+}
+
+fn init_parameter_id(value: LightString) -> ValidateData<LightString> {
+    ValidateData::new(
+        value,
+        Some(Validators::new().add(RequiredValidator::new("请输入参数标识"))),
+    )
+}
+
+fn init_parameter_name(value: LightString) -> ValidateData<LightString> {
+    ValidateData::new(
+        value,
+        Some(Validators::new().add(RequiredValidator::new("请输入参数名称"))),
+    )
+}
+
+fn init_parameter_type(value: Option<ParameterType>) -> ValidateData<Option<ParameterType>> {
+    ValidateData::new(
+        value,
+        Some(Validators::new().add(RequiredValidator::new("请选择参数类型"))),
+    )
+}
+
+pub fn from_attribute(attribute: &Attribute) -> JobParameter {
+    let (parameter_type, options) = match &attribute.r#type {
+        AttributeType::String => (ParameterType::String, Default::default()),
+        AttributeType::LongString | AttributeType::Code { .. } => {
+            (ParameterType::LongString, Default::default())
+        }
+        AttributeType::Password => (ParameterType::Password, Default::default()),
+        AttributeType::Bool => (ParameterType::Bool, Default::default()),
+        AttributeType::Enum { options } | AttributeType::EnumList { options } => {
+            let options: Vec<&str> = options.iter().map(|option| option.value.as_str()).collect();
+            (ParameterType::Enum, LightString::from(options.join(",")))
+        }
+        AttributeType::StringList => (ParameterType::StringList, Default::default()),
+        AttributeType::File | AttributeType::FileList => {
+            (ParameterType::String, Default::default())
+        }
+    };
+    return JobParameter {
+        id: init_parameter_id(attribute.id.clone().into()),
+        name: init_parameter_name(attribute.name.clone().into()),
+        description: Binding::new(attribute.description.clone().unwrap_or_default().into()),
+        parameter_type: init_parameter_type(Some(parameter_type)),
+        required: Binding::new(attribute.required),
+        options: Binding::new(options),
+    };
+}
+
+fn split_options(options: &str) -> Vec<String> {
+    return options
+        .split(',')
+        .map(|option| option.trim())
+        .filter(|option| !option.is_empty())
+        .map(|option| option.to_string())
+        .collect();
+}
+
+pub fn to_attribute(parameter: &JobParameter) -> Attribute {
+    let description = parameter.description.get();
+    let r#type = match parameter.parameter_type.get().unwrap() {
+        ParameterType::String => AttributeType::String,
+        ParameterType::LongString => AttributeType::LongString,
+        ParameterType::Password => AttributeType::Password,
+        ParameterType::Bool => AttributeType::Bool,
+        ParameterType::Enum => AttributeType::Enum {
+            options: split_options(&parameter.options.get())
+                .into_iter()
+                .map(|option| EnumOption {
+                    value: option.clone(),
+                    label: option,
+                })
+                .collect(),
+        },
+        ParameterType::StringList => AttributeType::StringList,
+    };
+    return Attribute {
+        id: parameter.id.get().to_string(),
+        name: parameter.name.get().to_string(),
+        description: if description.is_empty() {
+            None
+        } else {
+            Some(description.to_string())
+        },
+        required: parameter.required.get(),
+        r#type: r#type,
+    };
+}
+
+pub fn chk_parameter_err(parameter_list: &[(Key, JobParameter)]) -> Vec<LightString> {
+    let mut err_msgs: Vec<LightString> = Vec::new();
+    let mut ids = HashSet::new();
+    for (_, parameter) in parameter_list {
+        if let Err(error) = parameter.id.validate(true) {
+            err_msgs.push(error);
+        }
+        if let Err(error) = parameter.name.validate(true) {
+            err_msgs.push(error);
+        }
+        if let Err(error) = parameter.parameter_type.validate(true) {
+            err_msgs.push(error);
+        }
+        let id = parameter.id.get();
+        if !id.chars().all(|c| c.is_ascii_alphanumeric() || '_' == c) {
+            err_msgs.push(LightString::from(format!(
+                "参数标识\"{}\"只能包含字母、数字和下划线",
+                id
+            )));
+        }
+        if !id.is_empty() && !ids.insert(id.clone()) {
+            err_msgs.push(LightString::from(format!("参数标识\"{}\"重复", id)));
+        }
+        if Some(ParameterType::Enum) == parameter.parameter_type.get()
+            && split_options(&parameter.options.get()).is_empty()
+        {
+            err_msgs.push(LightString::from(format!(
+                "请填写参数\"{}\"的枚举选项",
+                parameter.name.get()
+            )));
+        }
+    }
+    return err_msgs;
+}
+
+pub fn parameter_schema_view(
+    parameter_list: &UseStateHandle<Vec<(Key, JobParameter)>>,
+    clear_err_msg: &Callback<()>,
+) -> Html {
+    let parameter_list_clone = parameter_list.clone();
+    let clear_err_msg_clone = clear_err_msg.clone();
+    let clear_err_msg_clone2 = clear_err_msg.clone();
+    let clear_err_msg_clone3 = clear_err_msg.clone();
+    let columns: Vec<Column<JobParameter>> = vec![
+        Column {
+            key: "id".into(),
+            head: ArcRenderer::from(move |_: &'_ ()| {
+                html! { "参数标识" }
+            }),
+            row: ArcRowRenderer::from(move |item: &JobParameter, _index: usize| {
+                let clear_err_msg = clear_err_msg_clone.clone();
+                item.id.view(move |id: UseStateHandle<LightString>, validator: Callback<LightString>| {
+                    html! {
+                        <BindingInput value={id} placeholder={"如image_tag"} onupdate={validator} onfocus={clear_err_msg.clone()}/>
+                    }
+                })
+            }),
+            head_style: None,
+            data_style: None,
+        },
+        Column {
+            key: "name".into(),
+            head: ArcRenderer::from(move |_: &'_ ()| {
+                html! { "参数名称" }
+            }),
+            row: ArcRowRenderer::from(move |item: &JobParameter, _index: usize| {
+                let clear_err_msg = clear_err_msg_clone2.clone();
+                item.name.view(move |name: UseStateHandle<LightString>, validator: Callback<LightString>| {
+                    html! {
+                        <BindingInput value={name} placeholder={"参数名称"} onupdate={validator} onfocus={clear_err_msg.clone()}/>
+                    }
+                })
+            }),
+            head_style: None,
+            data_style: None,
+        },
+        Column {
+            key: "type".into(),
+            head: ArcRenderer::from(move |_: &'_ ()| {
+                html! { "参数类型" }
+            }),
+            row: ArcRowRenderer::from(move |item: &JobParameter, _index: usize| {
+                let options = item.options.clone();
+                item.parameter_type.view(move |parameter_type: UseStateHandle<Option<ParameterType>>, validator: Callback<Option<ParameterType>>| {
+                    let options = options.clone();
+                    let onchange = validator.reform(|option: Option<(ParameterType, String)>| {
+                        option.map(|option| option.0)
+                    });
+                    html! {
+                        <>
+                            <ParameterTypeSelection value={parameter_type.clone()} options={get_parameter_type_list()} onchange={onchange}/>
+                            {
+                                if Some(ParameterType::Enum) == *parameter_type {
+                                    options.view(move |options: UseStateHandle<LightString>| {
+                                        html! {
+                                            <BindingInput value={options} placeholder={"枚举选项，多个用英文逗号分隔"} style="margin-top:0.25em;"/>
+                                        }
+                                    })
+                                } else {
+                                    html! {}
+                                }
+                            }
+                        </>
+                    }
+                })
+            }),
+            head_style: None,
+            data_style: None,
+        },
+        Column {
+            key: "required".into(),
+            head: ArcRenderer::from(move |_: &'_ ()| {
+                html! { "必填" }
+            }),
+            row: ArcRowRenderer::from(move |item: &JobParameter, _index: usize| {
+                item.required.view(move |required: UseStateHandle<bool>| {
+                    html! {
+                        <BindingCheckbox value={required}/>
+                    }
+                })
+            }),
+            head_style: None,
+            data_style: None,
+        },
+        Column {
+            key: "description".into(),
+            head: ArcRenderer::from(move |_: &'_ ()| {
+                html! { "说明" }
+            }),
+            row: ArcRowRenderer::from(move |item: &JobParameter, _index: usize| {
+                let clear_err_msg = clear_err_msg_clone3.clone();
+                item.description.view(move |description: UseStateHandle<LightString>| {
+                    html! {
+                        <BindingInput value={description} placeholder={"说明"} onfocus={clear_err_msg.clone()}/>
+                    }
+                })
+            }),
+            head_style: None,
+            data_style: None,
+        },
+        Column {
+            key: "operation".into(),
+            head: ArcRenderer::from(move |_: &'_ ()| {
+                html! { "操作" }
+            }),
+            row: ArcRowRenderer::from(move |_item: &JobParameter, index: usize| {
+                let parameter_list = parameter_list_clone.clone();
+                let on_remove = Callback::from(move |_| {
+                    utils::remove_item(&parameter_list, index);
+                });
+                html! {
+                    <Button onclick={on_remove}>{"移除"}</Button>
+                }
+            }),
+            head_style: None,
+            data_style: Some((|_index: usize| AttrValue::from("vertical-align: top;")).into()),
+        },
+    ];
+    let parameter_list_clone = parameter_list.clone();
+    let on_add = Callback::from(move |_| {
+        let mut new_parameter_list = parameter_list_clone.deref().clone();
+        new_parameter_list.push((
+            utils::gen_id().into(),
+            JobParameter {
+                id: init_parameter_id(Default::default()),
+                name: init_parameter_name(Default::default()),
+                description: Binding::new(Default::default()),
+                parameter_type: init_parameter_type(Some(ParameterType::String)),
+                required: Binding::new(true),
+                options: Binding::new(Default::default()),
+            },
+        ));
+        parameter_list_clone.set(new_parameter_list);
+    });
+    html! {
+        <>
+            <If condition={!parameter_list.is_empty()}>
+                <Table<JobParameter> list={parameter_list.deref().clone()} columns={columns} />
+            </If>
+            <div style="margin-top:0.5em">
+                <Button onclick={on_add}>{"添加参数"}</Button>
+                <span style="margin-left:0.5em;color:#999;">{"在操作参数中通过{{params.参数标识}}引用"}</span>
+            </div>
+        </>
+    }
+}
@@ -1,5 +1,10 @@
+use super::super::extension::config_view;
+use super::super::extension::get_default_config;
+use super::super::extension::serialize_config;
+use super::super::extension::AttributeValue;
 use super::super::job_record::detail::JobRecordDetail;
 use crate::components::button::Button;
+use crate::components::input::BindingInput;
 use crate::components::r#if::If;
 use crate::components::selection::BindingSelection;
 use crate::sdk;
@@ -8,6 +13,11 @@ use crate::LightString;
 use sdk::environment::query_environment::Environment;
 use sdk::environment::query_environment::QueryEnvironmentApi;
 use sdk::environment::query_environment::QueryEnvironmentReq;
+use sdk::extension::Attribute;
+use sdk::job::plan_job::PlanJobApi;
+use sdk::job::plan_job::PlanJobReq;
+use sdk::job::plan_job::PlanResult;
+use sdk::job::plan_job::StepPlan;
 use sdk::job::read_job::Job;
 use sdk::job::read_job::ReadJobApi;
 use sdk::job::read_job::ReadJobReq;
@@ -16,6 +26,7 @@ use sdk::job::start_job::StartJobReq;
 use tihu::Id;
 use tihu::PrimaryKey;
 use yew::prelude::*;
+use yew::virtual_dom::Key;
 
 type EnvironmentSelection = BindingSelection<(Id, String)>;
 
@@ -32,14 +43,19 @@ pub fn StartJob(props: &Props) -> Html {
     let detail: UseStateHandle<Option<Job>> = use_state(|| None);
     let active_environment_id: UseStateHandle<Option<Id>> = use_state(|| None);
     let environment_list: UseStateHandle<Vec<Environment>> = use_state(|| Vec::new());
+    let params: UseStateHandle<Vec<(Key, Attribute, AttributeValue)>> = use_state(|| Vec::new());
+    let step_plan_list: UseStateHandle<Option<Vec<StepPlan>>> = use_state(|| None);
+    let change_note: UseStateHandle<LightString> = use_state(|| "".into());
     let id = props.id;
     let detail_clone = detail.clone();
+    let params_clone = params.clone();
     let environment_list_clone = environment_list.clone();
     let active_environment_id_clone = active_environment_id.clone();
     use_effect_with(id, move |_| {
         wasm_bindgen_futures::spawn_local(async move {
             match read_job_detail(&detail_clone, id).await {
                 Ok(job) => {
+                    params_clone.set(get_default_config(job.parameter_schema.clone()));
                     let environment_schema_id = job.environment_schema_id;
                     match query_environment_list(environment_schema_id, &environment_list_clone)
                         .await
@@ -63,19 +79,45 @@ pub fn StartJob(props: &Props) -> Html {
     });
     let environment_list: Vec<_> = environment_list
         .iter()
-        .map(|environment| (environment.id, environment.name.clone()))
+        .map(|environment| {
+            //受保护的环境需要单独授权才能执行，提前标出来
+            if environment.protected && 0 < environment.required_approvals {
+                (
+                    environment.id,
+                    format!(
+                        "{}（受保护，需要{}人审批）",
+                        environment.name, environment.required_approvals
+                    ),
+                )
+            } else if environment.protected {
+                (environment.id, format!("{}（受保护）", environment.name))
+            } else {
+                (environment.id, environment.name.clone())
+            }
+        })
         .collect();
     let job_id = id;
     let active_environment_id_clone = active_environment_id.clone();
     let record_detail_active_clone = record_detail_active.clone();
     let active_record_detail_id_clone = active_record_detail_id.clone();
+    let params_clone = params.clone();
+    let change_note_clone = change_note.clone();
     let on_run = Callback::from(move |_: ()| {
         if let Some(active_environment_id) = active_environment_id_clone.as_ref() {
             let active_environment_id = *active_environment_id;
+            let params = match get_params(&params_clone) {
+                Ok(params) => params,
+                Err(_) => return,
+            };
+            let change_note = if change_note_clone.is_empty() {
+                None
+            } else {
+                Some(change_note_clone.to_string())
+            };
             let record_detail_active = record_detail_active_clone.clone();
             let active_record_detail_id = active_record_detail_id_clone.clone();
             wasm_bindgen_futures::spawn_local(async move {
-                match start_job(job_id, active_environment_id).await {
+                match start_job(job_id, active_environment_id, params, change_note).await {
                     Ok(job_record) => {
                         active_record_detail_id.set(Some(job_record.id));
                         record_detail_active.set(true);
@@ -87,12 +129,51 @@ pub fn StartJob(props: &Props) -> Html {
             });
         }
     });
+    let active_environment_id_clone = active_environment_id.clone();
+    let params_clone = params.clone();
+    let step_plan_list_clone = step_plan_list.clone();
+    let on_plan = Callback::from(move |_: ()| {
+        if let Some(active_environment_id) = active_environment_id_clone.as_ref() {
+            let active_environment_id = *active_environment_id;
+            let params = match get_params(&params_clone) {
+                Ok(params) => params,
+                Err(_) => return,
+            };
+            let step_plan_list = step_plan_list_clone.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                match plan_job(job_id, active_environment_id, params).await {
+                    Ok(list) => {
+                        step_plan_list.set(Some(list));
+                    }
+                    Err(_err) => {
+                        //
+                    }
+                }
+            });
+        }
+    });
     html! {
         <div class="width-fill height-fill border-box" style="padding:0.25em;">
             <If condition={!*record_detail_active}>
                 {"环境: "}
                 <EnvironmentSelection value={active_environment_id.clone()} options={environment_list}/>
+                <Button disabled={active_environment_id.is_none()} onclick={on_plan} style="margin-left:0.5em;">{"预览"}</Button>
                 <Button disabled={active_environment_id.is_none()} onclick={on_run} style="margin-left:0.5em;">{"执行"}</Button>
+                <div style="margin-top:0.5em;">
+                    {"变更说明: "}
+                    <BindingInput value={change_note} placeholder="选填，说明这次执行的目的"/>
+                </div>
+                <If condition={!params.is_empty()}>
+                    <div style="margin-top:0.5em;font-weight: bold;">{"执行参数"}</div>
+                    {config_view(&params)}
+                </If>
+                {
+                    if let Some(step_plan_list) = step_plan_list.as_ref() {
+                        render_plan(step_plan_list)
+                    } else {
+                        html!{}
+                    }
+                }
             </If>
             {
                 if let (true, Some(active_record_detail_id)) = (*record_detail_active, active_record_detail_id.as_ref()) {
@@ -129,12 +210,118 @@ async fn query_environment_list(
     return Ok(pagination_list.list);
 }
 
-async fn start_job(job_id: Id, environment_id: Id) -> Result<PrimaryKey, LightString> {
+async fn start_job(
+    job_id: Id,
+    environment_id: Id,
+    params: Option<String>,
+    change_note: Option<String>,
+) -> Result<PrimaryKey, LightString> {
     let job_record = StartJobApi
         .call(&StartJobReq {
             job_id: job_id,
             environment_id: environment_id,
+            params: params,
+            change_note: change_note,
         })
         .await?;
     return Ok(job_record);
 }
+
+//校验并序列化执行参数，任务没有定义执行参数时不需要传
+fn get_params(params: &[(Key, Attribute, AttributeValue)]) -> Result<Option<String>, ()> {
+    let mut has_error = false;
+    for (_, _, value) in params.iter() {
+        if value.validate(true).is_err() {
+            has_error = true;
+        }
+    }
+    if has_error {
+        return Err(());
+    }
+    if params.is_empty() {
+        return Ok(None);
+    }
+    return Ok(Some(serialize_config(params)));
+}
+
+async fn plan_job(
+    job_id: Id,
+    environment_id: Id,
+    params: Option<String>,
+) -> Result<Vec<StepPlan>, LightString> {
+    let resp = PlanJobApi
+        .call(&PlanJobReq {
+            job_id: job_id,
+            environment_id: environment_id,
+            params: params,
+        })
+        .await?;
+    return Ok(resp.step_plan_list);
+}
+
+fn render_plan(step_plan_list: &[StepPlan]) -> Html {
+    let list = step_plan_list.iter().map(|step_plan| match step_plan {
+        StepPlan::Auto {
+            step_name,
+            operation_name,
+            skip_reason,
+            resource_plan_list,
+        } => {
+            let resource_list = resource_plan_list.iter().map(|resource_plan| {
+                let result = match &resource_plan.result {
+                    PlanResult::Changes(changes) => html! {
+                        <pre style="margin: 0.25em 0;white-space: pre-wrap;">{changes.clone()}</pre>
+                    },
+                    PlanResult::Unsupported => html! {
+                        <div style="color:#999;">{"该操作不支持预览"}</div>
+                    },
+                    PlanResult::Deferred => html! {
+                        <div style="color:#999;">{"操作参数引用了前面步骤的输出，执行时才能确定"}</div>
+                    },
+                    PlanResult::Error(err) => html! {
+                        <div style="color:red;">{format!("预览失败：{}", err)}</div>
+                    },
+                };
+                html! {
+                    <div style="margin-left:1em;">
+                        <div>{resource_plan.resource_name.clone()}</div>
+                        {result}
+                    </div>
+                }
+            });
+            html! {
+                <div style="margin-top:0.5em;">
+                    <div style="font-weight: bold;">{format!("{}（{}）", step_name, operation_name)}</div>
+                    {
+                        if let Some(skip_reason) = skip_reason {
+                            html! { <div style="color:#999;">{format!("跳过：{}", skip_reason)}</div> }
+                        } else {
+                            html! { for resource_list }
+                        }
+                    }
+                </div>
+            }
+        }
+        StepPlan::Manual {
+            step_name,
+            skip_reason,
+        } => html! {
+            <div style="margin-top:0.5em;">
+                <div style="font-weight: bold;">{format!("{}（手动步骤）", step_name)}</div>
+                {
+                    if let Some(skip_reason) = skip_reason {
+                        html! { <div style="color:#999;">{format!("跳过：{}", skip_reason)}</div> }
+                    } else {
+                        html! {}
+                    }
+                }
+            </div>
+        },
+    });
+    html! {
+        <div style="margin-top:0.5em;">
+            <div style="font-weight: bold;">{"预览结果"}</div>
+            { for list }
+        </div>
+    }
+}
@@ -1,5 +1,10 @@
 use super::super::extension::wrap_content;
+use super::super::job::detail::get_signoff_policy_text;
+use super::live_output::LiveOutput;
+use super::signoff::parse_evidence;
+use super::signoff::ManualSignoff;
 use crate::components::button::Button;
+use crate::components::input::BindingInput;
 use crate::components::r#if::If;
 use crate::components::rich_text::render_rich_rext;
 use crate::components::running::Running;
@@ -10,17 +15,23 @@ use crate::utils;
 use crate::utils::request::ApiExt;
 use crate::LightString;
 use js_sys::JSON;
-use sdk::job::continue_job::ContinueJobApi;
-use sdk::job::continue_job::ContinueJobReq;
+use sdk::job::cancel_job::CancelJobApi;
+use sdk::job::cancel_job::CancelJobReq;
+use sdk::job::retry_job_record::RetryJobRecordApi;
+use sdk::job::retry_job_record::RetryJobRecordReq;
+use sdk::job_record::approve_job_record::ApproveJobRecordApi;
+use sdk::job_record::approve_job_record::ApproveJobRecordReq;
+use sdk::job_record::enums::ApprovalDecision;
 use sdk::job_record::enums::RecordStatus;
 use sdk::job_record::enums::StepRecordStatus;
 use sdk::job_record::enums::StepResourceRecordStatus;
 use sdk::job_record::read_job_record::JobRecord;
-use sdk::job_record::read_job_record::LogLevel;
+use sdk::job_record::read_job_record::JobStepRecord;
 use sdk::job_record::read_job_record::ReadJobRecordApi;
 use sdk::job_record::read_job_record::ReadJobRecordReq;
 use sdk::job_record::read_job_record::StepRecord;
-use sdk::job_record::read_job_record::StepResLog;
+use sdk::job_record::reject_job_record::RejectJobRecordApi;
+use sdk::job_record::reject_job_record::RejectJobRecordReq;
 use serde_json::Value;
 use std::future::Future;
 use std::ops::Deref;
@@ -28,7 +39,6 @@ use std::pin::Pin;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
-use tihu::datetime_format::FORMAT;
 use tihu::Id;
 use yew::prelude::*;
 
@@ -44,7 +54,67 @@ pub fn JobRecordDetail(props: &Props) -> Html {
     let active_job_step_record_id: UseStateHandle<Option<Id>> = use_state(|| None);
     let active_step_resource_record_id: UseStateHandle<Option<Id>> = use_state(|| None);
     let detail: UseStateHandle<Option<JobRecord>> = use_state(|| None);
-    let id = props.id;
+    //当前查看的执行记录，切换到其它执行尝试时会变化
+    let record_id: UseStateHandle<Id> = use_state(|| props.id);
+    let record_id_clone = record_id.clone();
+    use_effect_with(props.id, move |id| {
+        record_id_clone.set(*id);
+        || ()
+    });
+    let id = *record_id;
+    let is_saving_clone = is_saving.clone();
+    let on_cancel = Callback::from(move |_| {
+        let is_saving = is_saving_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            cancel_job(id, is_saving).await.ok();
+        });
+    });
+    let is_saving_clone = is_saving.clone();
+    let record_id_clone = record_id.clone();
+    let on_retry = Callback::from(move |_| {
+        let is_saving = is_saving_clone.clone();
+        let record_id = record_id_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            if let Ok(Some(new_record_id)) = retry_job_record(id, is_saving).await {
+                record_id.set(new_record_id);
+            }
+        });
+    });
+    let approval_comment: UseStateHandle<LightString> = use_state(|| LightString::from(""));
+    let is_saving_clone = is_saving.clone();
+    let detail_clone = detail.clone();
+    let approval_comment_clone = approval_comment.clone();
+    let on_approve = Callback::from(move |_| {
+        let is_saving = is_saving_clone.clone();
+        let detail = detail_clone.clone();
+        let approval_comment = approval_comment_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            decide_job_record(id, true, &approval_comment, is_saving, &detail)
+                .await
+                .ok();
+        });
+    });
+    let is_saving_clone = is_saving.clone();
+    let detail_clone = detail.clone();
+    let approval_comment_clone = approval_comment.clone();
+    let on_reject = Callback::from(move |_| {
+        let is_saving = is_saving_clone.clone();
+        let detail = detail_clone.clone();
+        let approval_comment = approval_comment_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            decide_job_record(id, false, &approval_comment, is_saving, &detail)
+                .await
+                .ok();
+        });
+    });
+    let detail_clone = detail.clone();
+    //实时日志推送了新的执行状态时，立即刷新执行记录
+    let on_refresh = Callback::from(move |_| {
+        let detail = detail_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            read_job_record_detail(&detail, id, false).await.ok();
+        });
+    });
     let detail_clone = detail.clone();
     use_effect_with(id, move |_| {
         let destroyed = Arc::new(AtomicBool::new(false));
@@ -73,8 +143,55 @@ pub fn JobRecordDetail(props: &Props) -> Html {
                                 render_record_status(job_record.status)
                             }).unwrap_or_else(utils::empty_html)
                         }
+                        <If condition={detail.as_ref().map(|job_record|RecordStatus::Running == job_record.status || RecordStatus::Queued == job_record.status || RecordStatus::PendingApproval == job_record.status).unwrap_or(false)}>
+                            <Button onclick={on_cancel} style="margin-left:0.5em;">{"取消执行"}</Button>
+                        </If>
+                        <If condition={detail.as_ref().map(can_retry).unwrap_or(false)}>
+                            <Button onclick={on_retry} style="margin-left:0.5em;">{"从失败步骤重新执行"}</Button>
+                        </If>
+                    </td>
+                </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;">{"执行次数："}</td>
+                    <td colspan="3">
+                        {
+                            detail.as_ref().map(|job_record|{
+                                render_attempts(job_record, &record_id)
+                            }).unwrap_or_else(utils::empty_html)
+                        }
                     </td>
                 </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;">{"发起人："}</td>
+                    <td>{detail.as_ref().and_then(|job_record|job_record.created_by_name.clone()).unwrap_or_default()}</td>
+                    <td class="align-right" style="width:8em;vertical-align: top;">{"触发方式："}</td>
+                    <td>{detail.as_ref().map(|job_record|job_record.trigger_type.to_string()).unwrap_or_default()}</td>
+                </tr>
+                <If condition={detail.as_ref().map(|job_record|job_record.change_note.is_some()).unwrap_or(false)}>
+                    <tr>
+                        <td class="align-right" style="width:8em;vertical-align: top;">{"变更说明："}</td>
+                        <td colspan="3">{detail.as_ref().and_then(|job_record|job_record.change_note.clone()).unwrap_or_default()}</td>
+                    </tr>
+                </If>
+                <If condition={detail.as_ref().map(|job_record|job_record.required_approvals.is_some()).unwrap_or(false)}>
+                    <tr>
+                        <td class="align-right" style="width:8em;vertical-align: top;">{"审批："}</td>
+                        <td colspan="3">
+                            {
+                                detail.as_ref().map(|job_record|{
+                                    render_approvals(job_record)
+                                }).unwrap_or_else(utils::empty_html)
+                            }
+                            <If condition={detail.as_ref().map(|job_record|RecordStatus::PendingApproval == job_record.status).unwrap_or(false)}>
+                                <div style="margin-top:0.25em;">
+                                    <BindingInput value={approval_comment.clone()} placeholder="审批意见（可选）" style="width:20em;"/>
+                                    <Button disabled={*is_saving} onclick={on_approve} style="margin-left:0.5em;">{"通过"}</Button>
+                                    <Button disabled={*is_saving} onclick={on_reject} style="margin-left:0.5em;">{"驳回"}</Button>
+                                </div>
+                            </If>
+                        </td>
+                    </tr>
+                </If>
                 <tr>
                     <td class="align-right" style="width:8em;vertical-align: top;">{"创建时间："}</td>
                     <td>{detail.as_ref().map(|job_record|{html!{&job_record.created_time}}).unwrap_or_else(utils::empty_html)}</td>
@@ -88,7 +205,7 @@ pub fn JobRecordDetail(props: &Props) -> Html {
                     <div style="flex-grow: 1;flex-shrink: 1;overflow: auto;">
                         {
                             detail.as_ref().map(|job_record|{
-                                render_steps(&active_job_step_record_id, &active_step_resource_record_id, &job_record.step_record_list, &is_saving)
+                                render_steps(&active_job_step_record_id, &active_step_resource_record_id, &job_record.step_record_list, &on_refresh)
                             }).unwrap_or_else(utils::empty_html)
                         }
                     </div>
@@ -98,6 +215,136 @@ pub fn JobRecordDetail(props: &Props) -> Html {
     }
 }
 
+//只有最近一次执行没有成功时，才可以从失败的步骤重新执行
+fn can_retry(job_record: &JobRecord) -> bool {
+    let is_latest = job_record
+        .attempt_list
+        .iter()
+        .all(|attempt| attempt.attempt <= job_record.attempt);
+    return is_latest
+        && match job_record.status {
+            RecordStatus::Failure
+            | RecordStatus::Interrupted
+            | RecordStatus::Cancelled
+            | RecordStatus::Rejected
+            | RecordStatus::Expired => true,
+            RecordStatus::Running
+            | RecordStatus::Success
+            | RecordStatus::Queued
+            | RecordStatus::PendingApproval => false,
+        };
+}
+
+//步骤输出是json格式，格式化之后再展示
+fn render_outputs(outputs: Option<&str>) -> String {
+    let outputs = outputs.unwrap_or_default();
+    return serde_json::from_str::<serde_json::Value>(outputs)
+        .and_then(|value| serde_json::to_string_pretty(&value))
+        .unwrap_or_else(|_| outputs.to_string());
+}
+
+//审批进度以及每个人的审批意见
+fn render_approvals(job_record: &JobRecord) -> Html {
+    let approve_count = job_record
+        .approval_list
+        .iter()
+        .filter(|approval| ApprovalDecision::Approve == approval.decision)
+        .count();
+    html! {
+        <>
+            { format!("需要{}人审批，已通过{}人", job_record.required_approvals.unwrap_or(0), approve_count) }
+            {
+                for job_record.approval_list.iter().map(|approval| {
+                    let color = match approval.decision {
+                        ApprovalDecision::Approve => "color:green;",
+                        ApprovalDecision::Reject => "color:red;",
+                    };
+                    html! {
+                        <div style="margin-top:0.25em;">
+                            <span>{approval.approver_name.clone().unwrap_or_else(|| approval.approver_id.to_string())}</span>
+                            <span style={format!("margin-left:0.5em;{}", color)}>{approval.decision.to_string()}</span>
+                            <span style="margin-left:0.5em;color:gray;">{&approval.created_time}</span>
+                            <If condition={approval.comment.is_some()}>
+                                <span style="margin-left:0.5em;">{approval.comment.clone().unwrap_or_default()}</span>
+                            </If>
+                        </div>
+                    }
+                })
+            }
+        </>
+    }
+}
+
+//手动步骤的签收进度以及每个人的签收意见和凭证
+fn render_signoffs(job_step_record: &JobStepRecord) -> Html {
+    let success_count = job_step_record
+        .signoff_list
+        .iter()
+        .filter(|signoff| signoff.success)
+        .count();
+    html! {
+        <>
+            { format!("已确认成功{}人", success_count) }
+            {
+                for job_step_record.signoff_list.iter().map(|signoff| {
+                    let (color, text) = if signoff.success {
+                        ("color:green;", "确认成功")
+                    } else {
+                        ("color:red;", "确认失败")
+                    };
+                    html! {
+                        <div style="margin-top:0.25em;">
+                            <span>{signoff.user_name.clone().unwrap_or_else(|| signoff.user_id.to_string())}</span>
+                            <span style={format!("margin-left:0.5em;{}", color)}>{text}</span>
+                            <span style="margin-left:0.5em;color:gray;">{&signoff.created_time}</span>
+                            <If condition={signoff.comment.is_some()}>
+                                <span style="margin-left:0.5em;">{signoff.comment.clone().unwrap_or_default()}</span>
+                            </If>
+                            {
+                                for parse_evidence(signoff.attachments.as_deref()).into_iter().map(|(key, name)| {
+                                    let url = format!("/{}", key);
+                                    html! {
+                                        <a href={url} target="_blank" download={name.clone()} style="margin-left:0.5em;">{name}</a>
+                                    }
+                                })
+                            }
+                        </div>
+                    }
+                })
+            }
+        </>
+    }
+}
+
+fn render_attempts(job_record: &JobRecord, record_id: &UseStateHandle<Id>) -> Html {
+    html! {
+        <>
+            { format!("第{}次", job_record.attempt) }
+            <If condition={1 < job_record.attempt_list.len()}>
+                <span style="margin-left:0.5em;">{"执行历史："}</span>
+                {
+                    for job_record.attempt_list.iter().map(|attempt| {
+                        let attempt_id = attempt.id;
+                        let record_id = record_id.clone();
+                        let text = format!("第{}次（{}）", attempt.attempt, attempt.status.to_string());
+                        if attempt_id == job_record.id {
+                            html! {
+                                <span style="margin-left:0.5em;font-weight:bold;">{text}</span>
+                            }
+                        } else {
+                            html! {
+                                <a href="javascript:void(0);" style="margin-left:0.5em;" onclick={Callback::from(move |_| {
+                                    record_id.set(attempt_id);
+                                })}>{text}</a>
+                            }
+                        }
+                    })
+                }
+            </If>
+        </>
+    }
+}
+
 fn render_record_status(status: RecordStatus) -> Html {
     match status {
         RecordStatus::Running => html! {
@@ -112,6 +359,24 @@ fn render_record_status(status: RecordStatus) -> Html {
         RecordStatus::Failure => html! {
             <span style="color:red;">{status.to_string()}</span>
         },
+        RecordStatus::Interrupted => html! {
+            <span style="color:orange;">{status.to_string()}</span>
+        },
+        RecordStatus::Cancelled => html! {
+            <span style="color:gray;">{status.to_string()}</span>
+        },
+        RecordStatus::Queued => html! {
+            <span style="color:orange;">{status.to_string()}</span>
+        },
+        RecordStatus::PendingApproval => html! {
+            <span style="color:orange;">{status.to_string()}</span>
+        },
+        RecordStatus::Rejected => html! {
+            <span style="color:red;">{status.to_string()}</span>
+        },
+        RecordStatus::Expired => html! {
+            <span style="color:gray;">{status.to_string()}</span>
+        },
     }
 }
 
@@ -134,6 +399,15 @@ fn render_step_status(status: StepRecordStatus, show_running: bool) -> Html {
         StepRecordStatus::Failure => html! {
             <span style="color:red;">{status.to_string()}</span>
         },
+        StepRecordStatus::Interrupted => html! {
+            <span style="color:orange;">{status.to_string()}</span>
+        },
+        StepRecordStatus::Cancelled => html! {
+            <span style="color:gray;">{status.to_string()}</span>
+        },
+        StepRecordStatus::Skipped => html! {
+            <span style="color:gray;">{status.to_string()}</span>
+        },
     }
 }
 
@@ -154,6 +428,15 @@ fn render_step_resource_status(status: StepResourceRecordStatus) -> Html {
         StepResourceRecordStatus::Failure => html! {
             <span style="color:red;">{status.to_string()}</span>
         },
+        StepResourceRecordStatus::Interrupted => html! {
+            <span style="color:orange;">{status.to_string()}</span>
+        },
+        StepResourceRecordStatus::Cancelled => html! {
+            <span style="color:gray;">{status.to_string()}</span>
+        },
+        StepResourceRecordStatus::Skipped => html! {
+            <span style="color:gray;">{status.to_string()}</span>
+        },
     }
 }
 
@@ -161,10 +444,10 @@ fn render_steps(
     active_job_step_record_id: &UseStateHandle<Option<Id>>,
     active_step_resource_record_id: &UseStateHandle<Option<Id>>,
     steps: &[StepRecord],
-    is_saving: &UseStateHandle<bool>,
+    on_refresh: &Callback<()>,
 ) -> Html {
     html! {
-        for steps.iter().map(|step_record| {
+        for steps.iter().enumerate().map(|(index, step_record)| {
             let active_job_step_record_id = active_job_step_record_id.clone();
             let job_step_record_id = get_step_record_id(step_record);
             let is_active = active_job_step_record_id.deref() == &Some(job_step_record_id);
@@ -217,6 +500,26 @@ fn render_steps(
                                     }
                                 </td>
                             </tr>
+                            <tr>
+                                <td class="align-right" style="vertical-align: top;">{"依赖步骤："}</td>
+                                <td>{ get_depends_on_text(steps, index) }</td>
+                            </tr>
+                            <tr>
+                                <td class="align-right" style="vertical-align: top;">{"执行条件："}</td>
+                                <td>{ get_run_condition_text(get_job_step_record(step_record)) }</td>
+                            </tr>
+                            {
+                                if let Some(skip_reason) = get_job_step_record(step_record).skip_reason.as_ref() {
+                                    html! {
+                                        <tr>
+                                            <td class="align-right" style="vertical-align: top;">{"跳过原因："}</td>
+                                            <td style="color:gray;">{ skip_reason }</td>
+                                        </tr>
+                                    }
+                                } else {
+                                    html! {}
+                                }
+                            }
                             {
                                 match step_record {
                                     StepRecord::Auto { .. } => {
@@ -270,6 +573,7 @@ fn render_steps(
                                         })
                                         .unwrap_or_default();
                                         html! {
+                                            <>
                                             <tr>
                                                 <td class="align-right" style="vertical-align: top;">{"附件："}</td>
                                                 <td>
@@ -292,6 +596,29 @@ fn render_steps(
                                                     }
                                                 </td>
                                             </tr>
+                                            <tr>
+                                                <td class="align-right" style="vertical-align: top;">{"签收要求："}</td>
+                                                <td>
+                                                    {
+                                                        get_signoff_policy_text(
+                                                            job_step_record.signoff_role,
+                                                            job_step_record.signoff_user_list.as_ref().map(|signoff_user_list| signoff_user_list.len()),
+                                                            job_step_record.required_signoffs,
+                                                        )
+                                                    }
+                                                </td>
+                                            </tr>
+                                            <tr>
+                                                <td class="align-right" style="vertical-align: top;">{"签收记录："}</td>
+                                                <td>{ render_signoffs(job_step_record) }</td>
+                                            </tr>
+                                            <If condition={job_step_record.completed_by.is_some()}>
+                                                <tr>
+                                                    <td class="align-right" style="vertical-align: top;">{"处理人："}</td>
+                                                    <td>{job_step_record.completed_by_name.clone().unwrap_or_default()}</td>
+                                                </tr>
+                                            </If>
+                                            </>
                                         }
                                     },
                                 }
@@ -332,18 +659,19 @@ fn render_steps(
                                                                         {render_step_resource_status(step_resource_record.status)}
                                                                     </div>
                                                                     <Show condition={is_active} style="position:absolute;left:20em;right:0;top:0;bottom:0;padding:0.25em;overflow: auto;">
+                                                                        <If condition={1 < step_resource_record.attempts}>
+                                                                            <div>{format!("执行次数: {}", step_resource_record.attempts)}</div>
+                                                                        </If>
+                                                                        <If condition={step_resource_record.outputs.is_some()}>
+                                                                            <div>{"步骤输出:"}</div>
+                                                                            <pre style="margin: 0.25em 0;white-space: pre-wrap;">{render_outputs(step_resource_record.outputs.as_deref())}</pre>
+                                                                        </If>
                                                                         <div>{"输出:"}</div>
-                                                                        {
-                                                                            if let Some(output) = step_resource_record.output.as_ref() {
-                                                                                html! {
-                                                                                    <div>
-                                                                                        {render_output(output)}
-                                                                                    </div>
-                                                                                }
-                                                                            } else {
-                                                                                html! {}
-                                                                            }
-                                                                        }
+                                                                        <If condition={is_active}>
+                                                                            <div>
+                                                                                <LiveOutput id={step_resource_record_id} status={step_resource_record.status} log_summary={step_resource_record.log_summary.clone()} onchange={on_refresh.clone()} />
+                                                                            </div>
+                                                                        </If>
                                                                     </Show>
                                                                 </div>
                                                             }
@@ -357,29 +685,12 @@ fn render_steps(
                                 StepRecord::Manual {
                                     job_step_record
                                 } => {
-                                    let record_id = job_step_record.record_id;
-                                    let step_record_id = job_step_record.id;
-                                    let is_saving_clone = is_saving.clone();
-                                    let on_continue = Callback::from(move |_| {
-                                        let is_saving = is_saving_clone.clone();
-                                        wasm_bindgen_futures::spawn_local(async move {
-                                            continue_job(record_id, step_record_id, true, is_saving).await.ok();
-                                        });
-                                    });
-                                    let is_saving_clone = is_saving.clone();
-                                    let on_stop = Callback::from(move |_| {
-                                        let is_saving = is_saving_clone.clone();
-                                        wasm_bindgen_futures::spawn_local(async move {
-                                            continue_job(record_id, step_record_id, false, is_saving).await.ok();
-                                        });
-                                    });
                                     html! {
-                                        <div style="border: 1px solid #CCC;padding: 0.5em;margin-bottom: 0.5em;">
-                                            <If condition={StepRecordStatus::Running==job_step_record.status}>
-                                                <Button onclick={on_continue} style="margin-left:0.5em;">{"继续"}</Button>
-                                                <Button onclick={on_stop} style="margin-left:0.5em;">{"终止"}</Button>
-                                            </If>
-                                        </div>
+                                        <If condition={StepRecordStatus::Running==job_step_record.status}>
+                                            <div style="border: 1px solid #CCC;padding: 0.5em;margin-bottom: 0.5em;">
+                                                <ManualSignoff record_id={job_step_record.record_id} step_record_id={job_step_record.id} onsave={on_refresh.clone()}/>
+                                            </div>
+                                        </If>
                                     }
                                 },
                             }
@@ -413,51 +724,66 @@ fn get_step_name(step_record: &StepRecord) -> &String {
     }
 }
 
-fn get_step_remark(step_record: &StepRecord) -> &Option<String> {
+fn get_job_step_record(step_record: &StepRecord) -> &JobStepRecord {
     match step_record {
         StepRecord::Auto {
             job_step_record, ..
-        } => &job_step_record.step_remark,
+        } => job_step_record,
         StepRecord::Manual {
             job_step_record, ..
-        } => &job_step_record.step_remark,
+        } => job_step_record,
     }
 }
 
-fn render_output(output: &str) -> Html {
-    let logs: Vec<StepResLog> = serde_json::from_str(output).unwrap_or_default();
-    let mut list = Vec::new();
-    for log in logs {
-        let color = match log.level {
-            LogLevel::Error => Some("red"),
-            _ => None,
-        };
-        let style = color.map(|color| format!("color: {}", color));
-        let mut sub_list = Vec::new();
-        for (index, item) in log
-            .content
-            .replace("\r\n", "\n")
-            .replace("\r", "\n")
-            .split("\n")
-            .enumerate()
-        {
-            if 0 < index {
-                sub_list.push(html! {
-                    <br/>
-                });
+fn get_depends_on_text(steps: &[StepRecord], index: usize) -> String {
+    match &get_job_step_record(&steps[index]).depends_on {
+        Some(depends_on) => {
+            let names: Vec<&str> = depends_on
+                .iter()
+                .filter_map(|dependency| {
+                    steps
+                        .iter()
+                        .map(get_job_step_record)
+                        .find(|job_step_record| job_step_record.job_step_seq == *dependency)
+                        .map(|job_step_record| job_step_record.step_name.as_str())
+                })
+                .collect();
+            if names.is_empty() {
+                String::from("无")
+            } else {
+                names.join("、")
             }
-            if !item.is_empty() {
-                sub_list.push(Html::from(item));
+        }
+        None => {
+            if 0 == index {
+                String::from("无")
+            } else {
+                String::from("上一个步骤")
             }
         }
-        list.push(html! {
-            <p style={style.clone()}>
-                { format!("{} {} ", log.time.format(FORMAT), log.level) }
-                { for sub_list.into_iter() }
-            </p>
-        });
     }
-    html! { for list.into_iter() }
+}
+
+fn get_run_condition_text(job_step_record: &JobStepRecord) -> String {
+    match job_step_record.run_expression.as_ref() {
+        Some(run_expression) => format!(
+            "{}，并且满足\"{}\"",
+            job_step_record.run_if.to_string(),
+            run_expression
+        ),
+        None => job_step_record.run_if.to_string(),
+    }
+}
+
+fn get_step_remark(step_record: &StepRecord) -> &Option<String> {
+    match step_record {
+        StepRecord::Auto {
+            job_step_record, ..
+        } => &job_step_record.step_remark,
+        StepRecord::Manual {
+            job_step_record, ..
+        } => &job_step_record.step_remark,
+    }
 }
 
 async fn read_job_record_detail(
@@ -498,7 +824,11 @@ async fn start_read_loop(
         } else {
             match read_job_record_detail(detail, id, 0 == times).await {
                 Ok(detail) => {
-                    if RecordStatus::Running == detail.status {
+                    //等待审批时也要刷新，其他人审批之后可以看到最新的进度
+                    if RecordStatus::Running == detail.status
+                        || RecordStatus::Queued == detail.status
+                        || RecordStatus::PendingApproval == detail.status
+                    {
                         times += 1;
                         utils::wait(2000).await;
                     } else {
@@ -514,20 +844,80 @@ async fn start_read_loop(
     }
 }
 
-async fn continue_job(
+async fn retry_job_record(
+    record_id: Id,
+    is_saving: UseStateHandle<bool>,
+) -> Result<Option<Id>, LightString> {
+    let ok = utils::confirm(
+        LightString::from("确定从失败的步骤开始重新执行？已经成功的步骤不会再执行。"),
+        None,
+    )
+    .await;
+    if !ok {
+        return Ok(None);
+    }
+    let resp = RetryJobRecordApi
+        .lock_handler(is_saving)
+        .call(&RetryJobRecordReq {
+            record_id: record_id,
+            change_note: None,
+        })
+        .await?;
+    utils::success(LightString::from("已开始重新执行"));
+    return Ok(Some(resp.id));
+}
+
+async fn decide_job_record(
     record_id: Id,
-    step_record_id: Id,
-    success: bool,
+    approve: bool,
+    approval_comment: &UseStateHandle<LightString>,
     is_saving: UseStateHandle<bool>,
+    detail: &UseStateHandle<Option<JobRecord>>,
 ) -> Result<(), LightString> {
-    ContinueJobApi
+    let comment = Some(approval_comment.to_string()).filter(|comment| !comment.is_empty());
+    if approve {
+        ApproveJobRecordApi
+            .lock_handler(is_saving)
+            .call(&ApproveJobRecordReq {
+                record_id: record_id,
+                comment: comment,
+            })
+            .await?;
+        utils::success(LightString::from("已通过"));
+    } else {
+        let ok = utils::confirm(
+            LightString::from("确定驳回该执行？驳回之后不会再执行。"),
+            None,
+        )
+        .await;
+        if !ok {
+            return Ok(());
+        }
+        RejectJobRecordApi
+            .lock_handler(is_saving)
+            .call(&RejectJobRecordReq {
+                record_id: record_id,
+                comment: comment,
+            })
+            .await?;
+        utils::success(LightString::from("已驳回"));
+    }
+    approval_comment.set(LightString::from(""));
+    read_job_record_detail(detail, record_id, false).await?;
+    return Ok(());
+}
+
+async fn cancel_job(record_id: Id, is_saving: UseStateHandle<bool>) -> Result<(), LightString> {
+    let ok = utils::confirm(LightString::from("确定取消该任务的执行？"), None).await;
+    if !ok {
+        return Ok(());
+    }
+    CancelJobApi
         .lock_handler(is_saving)
-        .call(&ContinueJobReq {
+        .call(&CancelJobReq {
             record_id: record_id,
-            step_record_id: step_record_id,
-            success: success,
         })
         .await?;
-    utils::success(LightString::from("操作成功"));
+    utils::success(LightString::from("已取消"));
     return Ok(());
 }
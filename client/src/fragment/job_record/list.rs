@@ -3,18 +3,38 @@ use crate::components::button::Button;
 use crate::components::button_group::ButtonGroup;
 use crate::components::drawer::Drawer;
 use crate::components::pagination::Pagination as PaginationComp;
+use crate::components::selection::BindingSelection;
 use crate::sdk;
+use crate::utils;
 use crate::utils::request::ApiExt;
 use crate::utils::LoadStatus;
+use crate::LightString;
+use sdk::job_record::delete_job_record::DeleteJobRecordApi;
+use sdk::job_record::delete_job_record::DeleteJobRecordReq;
+use sdk::job_record::enums::TriggerType;
+use sdk::job_record::pin_job_record::PinJobRecordApi;
+use sdk::job_record::pin_job_record::PinJobRecordReq;
 use sdk::job_record::query_job_record::JobRecord;
 use sdk::job_record::query_job_record::QueryJobRecordApi;
 use sdk::job_record::query_job_record::QueryJobRecordReq;
+use sdk::user::query_user::QueryUserApi;
+use sdk::user::query_user::QueryUserReq;
 use std::ops::Deref;
 use tihu::datetime_format::FORMAT;
 use tihu::Id;
 use tihu::Pagination;
 use yew::prelude::*;
 
+type UserSelection = BindingSelection<(Id, String)>;
+type TriggerTypeSelection = BindingSelection<(TriggerType, String)>;
+
+//列表上的筛选条件
+#[derive(Clone)]
+struct RecordFilter {
+    created_by: Option<Id>,
+    trigger_type: Option<TriggerType>,
+}
+
 #[derive(Clone, PartialEq, Properties)]
 pub struct Props {
     #[prop_or_default]
@@ -32,17 +52,33 @@ pub fn JobRecordList(props: &Props) -> Html {
     let list_load_status: UseStateHandle<LoadStatus> = use_state(|| LoadStatus::NotStarted);
     let detail_active: UseStateHandle<bool> = use_state(|| false);
     let active_detail_id: UseStateHandle<Option<Id>> = use_state(|| None);
+    let created_by: UseStateHandle<Option<Id>> = use_state(|| None);
+    let trigger_type: UseStateHandle<Option<TriggerType>> = use_state(|| None);
+    let user_list: UseStateHandle<Vec<(Id, String)>> = use_state(|| Vec::new());
+    let user_list_clone = user_list.clone();
+    use_effect_with((), move |_| {
+        wasm_bindgen_futures::spawn_local(async move {
+            query_user_list(&user_list_clone).await.ok();
+        });
+        || ()
+    });
+    let filter = RecordFilter {
+        created_by: created_by.deref().clone(),
+        trigger_type: trigger_type.deref().clone(),
+    };
     let props_clone = props.clone();
     let list_clone = list.clone();
     let list_load_status_clone = list_load_status.clone();
     let pagination_clone = pagination.clone();
+    let filter_clone = filter.clone();
     let on_query = Callback::from(move |_| {
         let props = props_clone.clone();
+        let filter = filter_clone.clone();
         let list = list_clone.clone();
         let list_load_status = list_load_status_clone.clone();
         let pagination = pagination_clone.clone();
         wasm_bindgen_futures::spawn_local(async move {
-            query_job_record_list(&props, 1, &list, &list_load_status, &pagination).await;
+            query_job_record_list(&props, &filter, 1, &list, &list_load_status, &pagination).await;
         });
     });
     let on_query_clone = on_query.clone();
@@ -54,13 +90,41 @@ pub fn JobRecordList(props: &Props) -> Html {
     let list_clone = list.clone();
     let list_load_status_clone = list_load_status.clone();
     let pagination_clone = pagination.clone();
+    let filter_clone = filter.clone();
     let on_page = Callback::from(move |page: u64| {
         let props = props_clone.clone();
+        let filter = filter_clone.clone();
         let list = list_clone.clone();
         let list_load_status = list_load_status_clone.clone();
         let pagination = pagination_clone.clone();
         wasm_bindgen_futures::spawn_local(async move {
-            query_job_record_list(&props, page, &list, &list_load_status, &pagination).await;
+            query_job_record_list(&props, &filter, page, &list, &list_load_status, &pagination)
+                .await;
+        });
+    });
+
+    let props_clone = props.clone();
+    let list_clone = list.clone();
+    let list_load_status_clone = list_load_status.clone();
+    let pagination_clone = pagination.clone();
+    let filter_clone = filter.clone();
+    let on_refresh = Callback::from(move |_: ()| {
+        let props = props_clone.clone();
+        let filter = filter_clone.clone();
+        let list = list_clone.clone();
+        let list_load_status = list_load_status_clone.clone();
+        let pagination = pagination_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let page_no = pagination.page_no.max(1);
+            query_job_record_list(
+                &props,
+                &filter,
+                page_no,
+                &list,
+                &list_load_status,
+                &pagination,
+            )
+            .await;
         });
     });
 
@@ -76,13 +140,19 @@ pub fn JobRecordList(props: &Props) -> Html {
                 <div class="width-fill height-fill" style="display: -webkit-box;display: flex;-webkit-box-direction: normal;-webkit-box-orient: vertical;flex-direction: column;">
                     <header style="-webkit-box-flex: 0;flex-basis: auto;flex-grow: 0;display:flex;justify-content: space-between;padding-bottom: 0.25em;">
                         <span style="font-weight:bold;">{"执行记录列表"}</span>
+                        <div>
+                            {"发起人: "}
+                            <UserSelection value={created_by.clone()} options={user_list.deref().clone()} clearable={true}/>
+                            <span style="margin-left:0.5em;">{"触发方式: "}</span>
+                            <TriggerTypeSelection value={trigger_type.clone()} options={trigger_type_options()} clearable={true}/>
+                        </div>
                         <ButtonGroup>
                             <Button onclick={on_query}>{"查询"}</Button>
                             <Button onclick={props.onclose.clone()}>{"返回"}</Button>
                         </ButtonGroup>
                     </header>
                     <div style="-webkit-box-flex: 1;flex-basis: auto;flex-grow: 1;overflow-y: auto;">
-                        { table_view(&list, &list_load_status, &detail_active, &active_detail_id) }
+                        { table_view(&list, &list_load_status, &detail_active, &active_detail_id, &on_refresh) }
                         { list_exception_view(list.is_empty(), list_load_status.deref().clone()) }
                     </div>
                     <div style="-webkit-box-flex: 0;flex-basis: auto;flex-grow: 0;padding-top: 0.25em;">
@@ -109,6 +179,7 @@ fn table_view(
     list_load_status: &UseStateHandle<LoadStatus>,
     detail_active: &UseStateHandle<bool>,
     active_detail_id: &UseStateHandle<Option<Id>>,
+    on_refresh: &Callback<()>,
 ) -> Html {
     return html! {
         <table class="e-table width-fill">
@@ -116,7 +187,11 @@ fn table_view(
                 <tr>
                     <th class="e-table-hcell">{"任务名称"}</th>
                     <th class="e-table-hcell">{"环境名称"}</th>
+                    <th class="e-table-hcell">{"执行次数"}</th>
                     <th class="e-table-hcell">{"执行状态"}</th>
+                    <th class="e-table-hcell">{"发起人"}</th>
+                    <th class="e-table-hcell">{"触发方式"}</th>
+                    <th class="e-table-hcell">{"变更说明"}</th>
                     <th class="e-table-hcell">{"创建时间"}</th>
                     <th class="e-table-hcell">{"操作"}</th>
                 </tr>
@@ -127,7 +202,7 @@ fn table_view(
                         <tbody>
                             {
                                 for list.iter().map(|item| {
-                                    row_view(item, detail_active, active_detail_id)
+                                    row_view(item, detail_active, active_detail_id, on_refresh)
                                 })
                             }
                         </tbody>
@@ -144,6 +219,7 @@ fn row_view(
     job_record: &JobRecord,
     detail_active: &UseStateHandle<bool>,
     active_detail_id: &UseStateHandle<Option<Id>>,
+    on_refresh: &Callback<()>,
 ) -> Html {
     let detail_id = job_record.id;
     let active_detail_id = active_detail_id.clone();
@@ -152,14 +228,62 @@ fn row_view(
         active_detail_id.set(Some(detail_id));
         detail_active.set(true);
     });
+    let status = match job_record.queue_position {
+        Some(queue_position) => format!(
+            "{}（第{}位）",
+            job_record.status.to_string(),
+            queue_position
+        ),
+        None => job_record.status.to_string(),
+    };
+    let record_id = job_record.id;
+    let pinned = job_record.pinned;
+    let on_refresh_clone = on_refresh.clone();
+    let on_toggle_pin = Callback::from(move |_: ()| {
+        let on_refresh = on_refresh_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let params = PinJobRecordReq {
+                id: record_id,
+                pinned: !pinned,
+            };
+            if PinJobRecordApi.call(&params).await.is_ok() {
+                on_refresh.emit(());
+            }
+        });
+    });
+    let on_refresh_clone = on_refresh.clone();
+    let on_confirm_delete = Callback::from(move |_: ()| {
+        let on_refresh = on_refresh_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let ok = utils::confirm(
+                LightString::from("删除之后执行日志也会一起删除，确定删除该执行记录？"),
+                None,
+            )
+            .await;
+            if ok {
+                let params = DeleteJobRecordReq { id: record_id };
+                if DeleteJobRecordApi.call(&params).await.is_ok() {
+                    on_refresh.emit(());
+                }
+            }
+        });
+    });
     return html! {
         <tr class="e-table-row">
             <td class="e-table-cell align-center">{job_record.job_name.clone()}</td>
             <td class="e-table-cell align-center">{job_record.environment_name.clone()}</td>
-            <td class="e-table-cell align-center">{job_record.status.to_string()}</td>
+            <td class="e-table-cell align-center">{format!("第{}次", job_record.attempt)}</td>
+            <td class="e-table-cell align-center">{status}{if pinned { "（已置顶）" } else { "" }}</td>
+            <td class="e-table-cell align-center">{job_record.created_by_name.clone()}</td>
+            <td class="e-table-cell align-center">{job_record.trigger_type.to_string()}</td>
+            <td class="e-table-cell align-center">{job_record.change_note.clone()}</td>
             <td class="e-table-cell align-center">{format!("{}", job_record.created_time.format(FORMAT))}</td>
             <td class="e-table-cell align-center">
-                <Button onclick={on_open_detail}>{"详情"}</Button>
+                <ButtonGroup>
+                    <Button onclick={on_open_detail}>{"详情"}</Button>
+                    <Button onclick={on_toggle_pin}>{if pinned { "取消置顶" } else { "置顶" }}</Button>
+                    <Button onclick={on_confirm_delete}>{"删除"}</Button>
+                </ButtonGroup>
             </td>
         </tr>
     };
@@ -192,8 +316,39 @@ fn clear_list(list: &UseStateHandle<Vec<JobRecord>>, pagination: &UseStateHandle
     list.set(Vec::new());
 }
 
+fn trigger_type_options() -> Vec<(TriggerType, String)> {
+    return [
+        TriggerType::Manual,
+        TriggerType::Schedule,
+        TriggerType::Webhook,
+        TriggerType::Retry,
+        TriggerType::ApiToken,
+    ]
+    .iter()
+    .map(|trigger_type| (*trigger_type, trigger_type.to_string()))
+    .collect();
+}
+
+async fn query_user_list(user_list: &UseStateHandle<Vec<(Id, String)>>) -> Result<(), LightString> {
+    let pagination_list = QueryUserApi
+        .call(&QueryUserReq {
+            page_no: Some(1),
+            ..QueryUserReq::empty()
+        })
+        .await?;
+    user_list.set(
+        pagination_list
+            .list
+            .into_iter()
+            .map(|user| (user.id, user.name))
+            .collect(),
+    );
+    return Ok(());
+}
+
 async fn query_job_record_list(
     props: &Props,
+    filter: &RecordFilter,
     page_no: u64,
     list: &UseStateHandle<Vec<JobRecord>>,
     list_load_status: &UseStateHandle<LoadStatus>,
@@ -203,6 +358,8 @@ async fn query_job_record_list(
         page_no: Some(page_no),
         job_id: props.job_id.clone(),
         environment_id: props.environment_id.clone(),
+        created_by: filter.created_by,
+        trigger_type: filter.trigger_type,
         ..QueryJobRecordReq::empty()
     };
     list_load_status.set(LoadStatus::Loading);
@@ -0,0 +1,360 @@
+use crate::components::button::Button;
+use crate::components::pagination::Pagination as PaginationComp;
+use crate::components::r#if::If;
+use crate::components::radio_group::RadioGroup;
+use crate::sdk;
+use crate::utils::request::ApiExt;
+use crate::LightString;
+use sdk::job_record::download_step_resource_log::DownloadStepResourceLogApi;
+use sdk::job_record::download_step_resource_log::DownloadStepResourceLogReq;
+use sdk::job_record::download_step_resource_log::STEP_RESOURCE_LOG_DOWNLOAD_PATH;
+use sdk::job_record::enums::StepResourceRecordStatus;
+use sdk::job_record::query_step_resource_log::QueryStepResourceLogApi;
+use sdk::job_record::query_step_resource_log::QueryStepResourceLogReq;
+use sdk::job_record::read_job_record::LogLevel;
+use sdk::job_record::read_job_record::StepResLog;
+use sdk::job_record::read_job_record::StepResLogSummary;
+use sdk::job_record::watch_step_resource_log::StepResLogEvent;
+use sdk::job_record::watch_step_resource_log::WatchStepResourceLogApi;
+use sdk::job_record::watch_step_resource_log::WatchStepResourceLogReq;
+use sdk::job_record::watch_step_resource_log::STEP_RESOURCE_LOG_STREAM_PATH;
+use std::future::Future;
+use std::pin::Pin;
+use tihu::datetime_format::FORMAT;
+use tihu::pagination::PaginationList;
+use tihu::Id;
+use tihu::Pagination;
+use wasm_bindgen::closure::Closure;
+use wasm_bindgen::JsCast;
+use web_sys::EventSource;
+use web_sys::MessageEvent;
+use yew::prelude::*;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub id: Id,
+    pub status: StepResourceRecordStatus,
+    #[prop_or_default]
+    pub log_summary: Option<StepResLogSummary>,
+    //推送的执行状态有变化或者执行结束时触发，用于刷新执行记录
+    #[prop_or_default]
+    pub onchange: Callback<()>,
+}
+
+pub enum Msg {
+    Open(String),
+    Event(StepResLogEvent),
+    Close,
+    Query(u64),
+    Loaded(PaginationList<StepResLog>),
+    ChangeLevel(Option<LogLevel>),
+    Download,
+}
+
+type LevelRadioGroup = RadioGroup<(Option<LogLevel>, String)>;
+
+//执行结束后每页展示的日志条数
+const LOG_PAGE_SIZE: u64 = 200;
+
+/**
+ * 资源执行的输出，执行中时通过SSE实时追加日志，执行结束后分页查询归档的日志
+ */
+pub struct LiveOutput {
+    logs: Vec<StepResLog>,
+    watched: bool,
+    streaming: bool,
+    clear_tasks: Vec<Box<dyn Fn()>>,
+    level: Option<LogLevel>,
+    pagination: Pagination,
+    page_logs: Vec<StepResLog>,
+}
+
+fn need_watch(status: StepResourceRecordStatus) -> bool {
+    match status {
+        StepResourceRecordStatus::Pending | StepResourceRecordStatus::Running => true,
+        _ => false,
+    }
+}
+
+impl LiveOutput {
+    fn watch(&mut self, ctx: &Context<Self>) {
+        if self.watched || !need_watch(ctx.props().status) {
+            return;
+        }
+        self.watched = true;
+        let link = ctx.link().clone();
+        let id = ctx.props().id;
+        wasm_bindgen_futures::spawn_local(async move {
+            match watch_step_resource_log(id).await {
+                Ok(token) => link.send_message(Msg::Open(token)),
+                Err(_err) => link.send_message(Msg::Close),
+            }
+        });
+    }
+
+    fn open(&mut self, ctx: &Context<Self>, token: &str) -> Result<(), LightString> {
+        let query: String = form_urlencoded::Serializer::new(String::new())
+            .append_pair("token", token)
+            .finish();
+        let url = format!("{}?{}", STEP_RESOURCE_LOG_STREAM_PATH, query);
+        let event_source = EventSource::new(&url).map_err(|err| {
+            log::error!("打开实时日志失败: {:?}", err);
+            return LightString::from_static("打开实时日志失败");
+        })?;
+        let link = ctx.link().clone();
+        let on_message = Closure::wrap(Box::new(move |event: MessageEvent| {
+            let data = event.data().as_string().unwrap_or_default();
+            match serde_json::from_str::<StepResLogEvent>(&data) {
+                Ok(event) => link.send_message(Msg::Event(event)),
+                Err(err) => log::error!("解析实时日志失败: {}", err),
+            }
+        }) as Box<dyn Fn(MessageEvent)>);
+        event_source.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        let link = ctx.link().clone();
+        //凭证过期或者服务端断开后不再重连，改为展示执行记录里的输出
+        let on_error = Closure::wrap(Box::new(move |_event: Event| {
+            link.send_message(Msg::Close);
+        }) as Box<dyn Fn(Event)>);
+        event_source.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+        self.clear_tasks.push(Box::new(move || {
+            event_source.set_onmessage(None);
+            event_source.set_onerror(None);
+            event_source.close();
+            let _ = (&on_message, &on_error);
+        }));
+        self.streaming = true;
+        return Ok(());
+    }
+
+    fn query(&self, ctx: &Context<Self>, page_no: u64) {
+        let link = ctx.link().clone();
+        let req = QueryStepResourceLogReq {
+            id: ctx.props().id,
+            level: self.level,
+            page_no: Some(page_no),
+            page_size: Some(LOG_PAGE_SIZE),
+        };
+        wasm_bindgen_futures::spawn_local(async move {
+            match QueryStepResourceLogApi.call(&req).await {
+                Ok(pagination_list) => link.send_message(Msg::Loaded(pagination_list)),
+                Err(err) => log::error!("{}", err),
+            }
+        });
+    }
+
+    fn close(&mut self) {
+        for clear_task in self.clear_tasks.drain(..) {
+            clear_task();
+        }
+        self.streaming = false;
+    }
+}
+
+impl Component for LiveOutput {
+    type Message = Msg;
+    type Properties = Props;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut live_output = LiveOutput {
+            logs: Vec::new(),
+            watched: false,
+            streaming: false,
+            clear_tasks: Vec::new(),
+            level: None,
+            pagination: Pagination::new(0, 1, Some(LOG_PAGE_SIZE), None),
+            page_logs: Vec::new(),
+        };
+        live_output.watch(ctx);
+        if !need_watch(ctx.props().status) {
+            live_output.query(ctx, 1);
+        }
+        live_output
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Open(token) => {
+                if let Err(err) = self.open(ctx, &token) {
+                    log::error!("{}", err);
+                }
+            }
+            Msg::Event(StepResLogEvent::Log(log)) => {
+                self.logs.push(log);
+            }
+            Msg::Event(StepResLogEvent::Status(status)) => {
+                if status != ctx.props().status {
+                    ctx.props().onchange.emit(());
+                }
+                return false;
+            }
+            Msg::Event(StepResLogEvent::End) => {
+                self.close();
+                ctx.props().onchange.emit(());
+            }
+            Msg::Close => {
+                self.close();
+            }
+            Msg::Query(page_no) => {
+                self.query(ctx, page_no);
+                return false;
+            }
+            Msg::Loaded(pagination_list) => {
+                self.pagination = pagination_list.pagination;
+                self.page_logs = pagination_list.list;
+            }
+            Msg::ChangeLevel(level) => {
+                self.level = level;
+                self.query(ctx, 1);
+            }
+            Msg::Download => {
+                let id = ctx.props().id;
+                wasm_bindgen_futures::spawn_local(async move {
+                    let resp = DownloadStepResourceLogApi
+                        .call(&DownloadStepResourceLogReq { id: id })
+                        .await;
+                    match resp {
+                        Ok(resp) => {
+                            let query: String = form_urlencoded::Serializer::new(String::new())
+                                .append_pair("token", &resp.token)
+                                .finish();
+                            let url = format!("{}?{}", STEP_RESOURCE_LOG_DOWNLOAD_PATH, query);
+                            //响应是附件，在当前窗口打开不会离开页面
+                            if let Some(window) = web_sys::window() {
+                                if let Err(err) = window.open_with_url_and_target(&url, "_self") {
+                                    log::error!("下载日志失败: {:?}", err);
+                                }
+                            }
+                        }
+                        Err(err) => log::error!("{}", err),
+                    }
+                });
+                return false;
+            }
+        }
+        true
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        let id_changed = ctx.props().id != old_props.id;
+        if id_changed {
+            self.close();
+            self.logs.clear();
+            self.page_logs.clear();
+            self.level = None;
+            self.watched = false;
+        }
+        self.watch(ctx);
+        //执行结束后日志已经归档，改为分页查询
+        if (id_changed || ctx.props().status != old_props.status) && !need_watch(ctx.props().status)
+        {
+            self.query(ctx, 1);
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        if self.streaming || need_watch(ctx.props().status) {
+            return render_logs(&self.logs);
+        }
+        let level_options = vec![
+            (None, String::from("全部")),
+            (Some(LogLevel::Error), String::from("错误")),
+            (Some(LogLevel::Warn), String::from("警告及以上")),
+            (Some(LogLevel::Info), String::from("信息及以上")),
+        ];
+        let on_level_change = ctx
+            .link()
+            .callback(|(level, _): (Option<LogLevel>, String)| Msg::ChangeLevel(level));
+        let on_download = ctx.link().callback(|_: ()| Msg::Download);
+        let on_page = ctx.link().callback(Msg::Query);
+        html! {
+            <div>
+                {
+                    match ctx.props().log_summary.as_ref() {
+                        Some(log_summary) => render_summary(log_summary),
+                        None => html! {},
+                    }
+                }
+                <div style="display:flex;justify-content: space-between;align-items: center;padding: 0.25em 0;">
+                    <LevelRadioGroup value={Some(self.level)} options={level_options} onchange={on_level_change} />
+                    <Button onclick={on_download}>{"下载完整日志"}</Button>
+                </div>
+                { render_logs(&self.page_logs) }
+                <If condition={1 < self.pagination.page_count}>
+                    <PaginationComp pagination={self.pagination.clone()} onpage={on_page} />
+                </If>
+            </div>
+        }
+    }
+
+    fn destroy(&mut self, _ctx: &Context<Self>) {
+        self.close();
+    }
+}
+
+async fn watch_step_resource_log(id: Id) -> Result<String, LightString> {
+    let resp = WatchStepResourceLogApi
+        .disable_loading()
+        .unwrap_error_handler(|err| -> Pin<Box<dyn Future<Output = ()>>> {
+            log::error!("{}", err);
+            Box::pin(async {})
+        })
+        .req_error_handler(|err| -> Pin<Box<dyn Future<Output = ()>>> {
+            log::error!("{}", err);
+            Box::pin(async {})
+        })
+        .call(&WatchStepResourceLogReq { id: id })
+        .await?;
+    return Ok(resp.token);
+}
+
+fn render_summary(log_summary: &StepResLogSummary) -> Html {
+    html! {
+        <div>
+            <div>{format!("共{}条日志，错误{}条，警告{}条", log_summary.total, log_summary.error_count, log_summary.warn_count)}</div>
+            {
+                match log_summary.last_error.as_ref() {
+                    Some(last_error) => html! {
+                        <div style="color: red">{format!("最后一条错误: {}", last_error)}</div>
+                    },
+                    None => html! {},
+                }
+            }
+        </div>
+    }
+}
+
+fn render_logs(logs: &[StepResLog]) -> Html {
+    let mut list = Vec::new();
+    for log in logs {
+        let color = match log.level {
+            LogLevel::Error => Some("red"),
+            _ => None,
+        };
+        let style = color.map(|color| format!("color: {}", color));
+        let mut sub_list = Vec::new();
+        for (index, item) in log
+            .content
+            .replace("\r\n", "\n")
+            .replace("\r", "\n")
+            .split("\n")
+            .enumerate()
+        {
+            if 0 < index {
+                sub_list.push(html! {
+                    <br/>
+                });
+            }
+            if !item.is_empty() {
+                sub_list.push(Html::from(item));
+            }
+        }
+        list.push(html! {
+            <p style={style.clone()}>
+                { format!("{} {} ", log.time.format(FORMAT), log.level) }
+                { for sub_list.into_iter() }
+            </p>
+        });
+    }
+    html! { for list.into_iter() }
+}
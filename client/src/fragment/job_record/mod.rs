@@ -1,2 +1,4 @@
 pub mod detail;
 pub mod list;
+pub mod live_output;
+pub mod signoff;
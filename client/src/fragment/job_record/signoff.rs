@@ -0,0 +1,185 @@
+use crate::components::button::Button;
+use crate::components::files_upload::BindingFilesUpload;
+use crate::components::input::BindingInput;
+use crate::components::uploading_files::upload_files;
+use crate::components::File;
+use crate::sdk;
+use crate::utils;
+use crate::utils::request::ApiExt;
+use crate::LightString;
+use sdk::job::continue_job::ContinueJobApi;
+use sdk::job::continue_job::ContinueJobReq;
+use serde_json::Value;
+use std::sync::Arc;
+use std::sync::Mutex;
+use tihu::Id;
+use yew::prelude::*;
+use yew::virtual_dom::Key;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    pub record_id: Id,
+    pub step_record_id: Id,
+    //签收之后触发，用于刷新执行记录
+    #[prop_or_default]
+    pub onsave: Callback<()>,
+}
+
+/**
+ * 签收进行中的手动步骤，可以填写签收意见和上传凭证
+ */
+#[function_component]
+pub fn ManualSignoff(props: &Props) -> Html {
+    let is_saving: UseStateHandle<bool> = use_state(|| false);
+    let comment: UseStateHandle<LightString> = use_state(|| LightString::from(""));
+    let files: UseStateHandle<Vec<(Key, File, ())>> = use_state(|| Vec::new());
+    let record_id = props.record_id;
+    let step_record_id = props.step_record_id;
+    let on_signoff = {
+        let is_saving = is_saving.clone();
+        let comment = comment.clone();
+        let files = files.clone();
+        let onsave = props.onsave.clone();
+        Callback::from(move |success: bool| {
+            let is_saving = is_saving.clone();
+            let comment = comment.clone();
+            let files = files.clone();
+            let onsave = onsave.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let ret = signoff(
+                    record_id,
+                    step_record_id,
+                    success,
+                    &comment,
+                    &files,
+                    is_saving,
+                )
+                .await;
+                if let Ok(true) = ret {
+                    comment.set(LightString::from(""));
+                    files.set(Vec::new());
+                    onsave.emit(());
+                }
+            });
+        })
+    };
+    html! {
+        <div>
+            <div>
+                <BindingInput value={comment} placeholder="签收意见（可选）" style="width:20em;"/>
+            </div>
+            <div style="margin-top:0.25em;">
+                <BindingFilesUpload<()> files={files}/>
+            </div>
+            <div style="margin-top:0.25em;">
+                <Button disabled={*is_saving} onclick={on_signoff.reform(|_| true)}>{"确认成功"}</Button>
+                <Button disabled={*is_saving} onclick={on_signoff.reform(|_| false)} style="margin-left:0.5em;">{"确认失败"}</Button>
+            </div>
+        </div>
+    }
+}
+
+/**
+ * 凭证和步骤附件的格式一样，是文件信息组成的json数组
+ */
+pub fn parse_evidence(attachments: Option<&str>) -> Vec<(String, String)> {
+    attachments
+        .and_then(|attachments| serde_json::from_str::<Value>(attachments).ok())
+        .and_then(|value| value.as_array().cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|value| {
+            let map = value.as_object()?;
+            let key = map.get("key")?.as_str()?.to_string();
+            let name = map.get("name")?.as_str()?.to_string();
+            Some((key, name))
+        })
+        .collect()
+}
+
+fn to_file_value(file: &File) -> Option<Value> {
+    match file {
+        File::Remote {
+            key,
+            name,
+            size,
+            mime_type,
+        } => {
+            let mut map = serde_json::Map::new();
+            map.insert(String::from("key"), Value::String(key.clone()));
+            map.insert(String::from("name"), Value::String(name.clone()));
+            map.insert(String::from("size"), Value::from(*size));
+            map.insert(String::from("mime_type"), Value::String(mime_type.clone()));
+            Some(Value::Object(map))
+        }
+        File::Local(_) => None,
+    }
+}
+
+async fn signoff(
+    record_id: Id,
+    step_record_id: Id,
+    success: bool,
+    comment: &UseStateHandle<LightString>,
+    files: &UseStateHandle<Vec<(Key, File, ())>>,
+    is_saving: UseStateHandle<bool>,
+) -> Result<bool, LightString> {
+    let tips = if success {
+        "确定确认该步骤成功？"
+    } else {
+        "确定确认该步骤失败？失败之后任务会按失败处理。"
+    };
+    let ok = utils::confirm(LightString::from(tips), None).await;
+    if !ok {
+        return Ok(false);
+    }
+    //先上传本地文件，上传之后按原来的顺序组装凭证
+    let file_list: Vec<File> = files.iter().map(|(_, file, _)| file.clone()).collect();
+    let uploaded: Arc<Mutex<Vec<Option<Value>>>> =
+        Arc::new(Mutex::new(file_list.iter().map(to_file_value).collect()));
+    let mut local_files = Vec::new();
+    for (index, file) in file_list.into_iter().enumerate() {
+        if let File::Local(hashing_file) = file {
+            let uploaded = uploaded.clone();
+            local_files.push((
+                hashing_file.clone(),
+                Callback::from(move |result: Result<String, LightString>| match result {
+                    Ok(key) => {
+                        uploaded.lock().unwrap()[index] = to_file_value(&File::Remote {
+                            key: key,
+                            name: hashing_file.file.name(),
+                            size: hashing_file.file.size(),
+                            mime_type: hashing_file.file.type_(),
+                        });
+                    }
+                    Err(err) => {
+                        log::error!("上传文件失败: {:?}", err);
+                    }
+                }),
+            ));
+        }
+    }
+    upload_files(local_files).await?;
+    let evidence: Vec<Value> = uploaded.lock().unwrap().iter().flatten().cloned().collect();
+    let attachments = if evidence.is_empty() {
+        None
+    } else {
+        serde_json::to_string(&Value::Array(evidence)).ok()
+    };
+    ContinueJobApi
+        .lock_handler(is_saving)
+        .call(&ContinueJobReq {
+            record_id: record_id,
+            step_record_id: step_record_id,
+            success: success,
+            comment: if comment.is_empty() {
+                None
+            } else {
+                Some(comment.to_string())
+            },
+            attachments: attachments,
+        })
+        .await?;
+    utils::success(LightString::from("签收成功"));
+    return Ok(true);
+}
@@ -0,0 +1,393 @@
+use super::super::extension::config_view;
+use super::super::extension::get_default_config;
+use super::super::extension::parse_config;
+use super::super::extension::serialize_config;
+use super::super::extension::AttributeValue;
+use crate::components::button::Button;
+use crate::components::input::BindingInput;
+use crate::components::r#if::If;
+use crate::components::radio_group::BindingRadioGroup;
+use crate::components::required::Required;
+use crate::components::selection::BindingSelection;
+use crate::components::validate_wrapper::ValidateData;
+use crate::sdk;
+use crate::utils;
+use crate::utils::request::ApiExt;
+use crate::utils::validator::RequiredValidator;
+use crate::utils::validator::Validators;
+use crate::LightString;
+use sdk::environment::query_environment::Environment;
+use sdk::environment::query_environment::QueryEnvironmentApi;
+use sdk::environment::query_environment::QueryEnvironmentReq;
+use sdk::extension::Attribute;
+use sdk::job::query_job::Job;
+use sdk::job::query_job::QueryJobApi;
+use sdk::job::query_job::QueryJobReq;
+use sdk::job::read_job::ReadJobApi;
+use sdk::job::read_job::ReadJobReq;
+use sdk::job_schedule::enums::ScheduleStatus;
+use sdk::job_schedule::insert_job_schedule::InsertJobScheduleApi;
+use sdk::job_schedule::insert_job_schedule::InsertJobScheduleReq;
+use sdk::job_schedule::read_job_schedule::JobSchedule;
+use sdk::job_schedule::read_job_schedule::ReadJobScheduleApi;
+use sdk::job_schedule::read_job_schedule::ReadJobScheduleReq;
+use sdk::job_schedule::update_job_schedule::UpdateJobScheduleApi;
+use sdk::job_schedule::update_job_schedule::UpdateJobScheduleReq;
+use tihu::Id;
+use tihu::PrimaryKey;
+use yew::prelude::*;
+use yew::virtual_dom::Key;
+
+type JobSelection = BindingSelection<(Id, String)>;
+type EnvironmentSelection = BindingSelection<(Id, String)>;
+type ScheduleStatusRadioGroup = BindingRadioGroup<(ScheduleStatus, String)>;
+
+fn get_schedule_status_list() -> Vec<(ScheduleStatus, String)> {
+    return [ScheduleStatus::Enabled, ScheduleStatus::Disabled]
+        .iter()
+        .map(|value| {
+            return (value.clone(), value.to_string());
+        })
+        .collect();
+}
+
+#[derive(Clone)]
+struct EditForm {
+    job_id: ValidateData<Option<Id>>,
+    environment_id: ValidateData<Option<Id>>,
+    name: ValidateData<LightString>,
+    cron_expression: ValidateData<LightString>,
+    status: UseStateHandle<ScheduleStatus>,
+    params: UseStateHandle<Vec<(Key, Attribute, AttributeValue)>>,
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    #[prop_or_default]
+    pub id: Option<Id>,
+    #[prop_or_default]
+    pub onsave: Option<Callback<PrimaryKey>>,
+}
+
+#[function_component]
+pub fn JobScheduleEdit(props: &Props) -> Html {
+    let is_saving: UseStateHandle<bool> = use_state(|| false);
+    let err_msg: UseStateHandle<Option<LightString>> = use_state(|| None);
+    let job_list: UseStateHandle<Vec<Job>> = use_state(|| Vec::new());
+    let environment_list: UseStateHandle<Vec<Environment>> = use_state(|| Vec::new());
+    let edit_form = EditForm {
+        job_id: ValidateData::new(
+            Default::default(),
+            Some(Validators::new().add(RequiredValidator::new("请选择任务"))),
+        ),
+        environment_id: ValidateData::new(
+            Default::default(),
+            Some(Validators::new().add(RequiredValidator::new("请选择环境"))),
+        ),
+        name: ValidateData::new(
+            Default::default(),
+            Some(Validators::new().add(RequiredValidator::new("请输入计划名称"))),
+        ),
+        cron_expression: ValidateData::new(
+            Default::default(),
+            Some(Validators::new().add(RequiredValidator::new("请输入cron表达式"))),
+        ),
+        status: use_state(|| ScheduleStatus::Enabled),
+        params: use_state(|| Vec::new()),
+    };
+    let id = props.id;
+    let edit_form_clone = edit_form.clone();
+    let job_list_clone = job_list.clone();
+    let environment_list_clone = environment_list.clone();
+    use_effect_with(id, move |_| {
+        wasm_bindgen_futures::spawn_local(async move {
+            query_job_list(&job_list_clone).await.ok();
+            if let Some(id) = id {
+                read_job_schedule_detail(&edit_form_clone, &environment_list_clone, id)
+                    .await
+                    .ok();
+            }
+        });
+        || ()
+    });
+    let err_msg_clone = err_msg.clone();
+    let clear_err_msg = Callback::from(move |_: ()| {
+        err_msg_clone.set(None);
+    });
+    let edit_form_clone = edit_form.clone();
+    let environment_list_clone = environment_list.clone();
+    let on_job_change = Callback::from(move |job: Option<(Id, String)>| {
+        if let Some((job_id, _)) = job {
+            let edit_form = edit_form_clone.clone();
+            let environment_list = environment_list_clone.clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                handle_job_change(&edit_form, &environment_list, job_id, None)
+                    .await
+                    .ok();
+            });
+        }
+    });
+    let edit_form_clone = edit_form.clone();
+    let is_saving_clone = is_saving.clone();
+    let err_msg_clone = err_msg.clone();
+    let onsave_clone = props.onsave.clone();
+    let on_save = Callback::from(move |_| {
+        let edit_form: EditForm = edit_form_clone.clone();
+        let is_saving = is_saving_clone.clone();
+        let err_msg = err_msg_clone.clone();
+        let onsave = onsave_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            save_job_schedule(id, &edit_form, is_saving, &err_msg, &onsave)
+                .await
+                .ok();
+        });
+    });
+    let job_list: Vec<_> = job_list
+        .iter()
+        .map(|job| (job.id, job.name.clone()))
+        .collect();
+    let environment_list: Vec<_> = environment_list
+        .iter()
+        .map(|environment| (environment.id, environment.name.clone()))
+        .collect();
+    html! {
+        <div class="width-fill height-fill border-box" style="padding:0.25em;display:flex;flex-direction: column;">
+            <table class="width-fill" style="border-collapse:collapse;table-layout: fixed;">
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;"><Required/>{"计划名称："}</td>
+                    <td>
+                        {
+                            edit_form.name.view(move |name: UseStateHandle<LightString>, validator| {
+                                html! {
+                                    <BindingInput value={name} onupdate={validator}/>
+                                }
+                            })
+                        }
+                    </td>
+                    <td class="align-right" style="width:8em;vertical-align: top;"><Required/>{"任务："}</td>
+                    <td style="vertical-align: top;">
+                        {
+                            if id.is_none() {
+                                edit_form.job_id.view(move |job_id, _validator| {
+                                    html! {
+                                        <JobSelection value={job_id} options={job_list.clone()} onchange={on_job_change.clone()}/>
+                                    }
+                                })
+                            } else {
+                                //计划创建之后不能更换任务，执行参数跟任务绑定
+                                let job_name = edit_form.job_id.get().and_then(|job_id| {
+                                    job_list.iter().find(|(id, _)| *id == job_id).map(|(_, name)| name.clone())
+                                });
+                                html! {job_name.unwrap_or_default()}
+                            }
+                        }
+                    </td>
+                </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;"><Required/>{"环境："}</td>
+                    <td style="vertical-align: top;">
+                        {
+                            edit_form.environment_id.view(move |environment_id, _validator| {
+                                html! {
+                                    <EnvironmentSelection value={environment_id} options={environment_list.clone()}/>
+                                }
+                            })
+                        }
+                    </td>
+                    <td class="align-right" style="width:8em;vertical-align: top;">{"状态："}</td>
+                    <td style="vertical-align: top;">
+                        <ScheduleStatusRadioGroup value={edit_form.status.clone()} options={get_schedule_status_list()} onchange={clear_err_msg.reform(|_| ())} />
+                    </td>
+                </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;"><Required/>{"cron表达式："}</td>
+                    <td colspan="3">
+                        {
+                            edit_form.cron_expression.view(move |cron_expression: UseStateHandle<LightString>, validator| {
+                                html! {
+                                    <BindingInput value={cron_expression} onupdate={validator}/>
+                                }
+                            })
+                        }
+                        <div style="color:#999;">{"按服务器时区计算，格式为\"分 时 日 月 周\"，如\"0 2 * * *\"表示每天凌晨2点，也支持带秒的6段写法"}</div>
+                    </td>
+                </tr>
+            </table>
+            <If condition={!edit_form.params.is_empty()}>
+                <div style="margin-top:0.5em;font-weight: bold;">{"执行参数"}</div>
+                {config_view(&edit_form.params)}
+            </If>
+            <div style="margin-top: 0.5em;">
+                <Button disabled={*is_saving} onclick={on_save}>{"保存"}</Button>
+                {
+                    match err_msg.as_ref() {
+                        Some(err_msg) => {
+                            html!{
+                                <span class="middle" style="color:red;margin-left: 0.5em;">{err_msg}</span>
+                            }
+                        },
+                        None => html!{}
+                    }
+                }
+            </div>
+        </div>
+    }
+}
+
+async fn query_job_list(job_list: &UseStateHandle<Vec<Job>>) -> Result<Vec<Job>, LightString> {
+    let pagination_list = QueryJobApi
+        .call(&QueryJobReq {
+            page_no: Some(1),
+            ..QueryJobReq::empty()
+        })
+        .await?;
+    job_list.set(pagination_list.list.clone());
+    return Ok(pagination_list.list);
+}
+
+//切换任务之后，重新加载可选的环境和执行参数，params为已保存的执行参数
+async fn handle_job_change(
+    edit_form: &EditForm,
+    environment_list: &UseStateHandle<Vec<Environment>>,
+    job_id: Id,
+    params: Option<&str>,
+) -> Result<(), LightString> {
+    let job = ReadJobApi.call(&ReadJobReq { id: job_id }).await?;
+    match params {
+        Some(params) => edit_form
+            .params
+            .set(parse_config(job.parameter_schema, params)),
+        None => edit_form
+            .params
+            .set(get_default_config(job.parameter_schema)),
+    }
+    let pagination_list = QueryEnvironmentApi
+        .call(&QueryEnvironmentReq {
+            environment_schema_id: Some(job.environment_schema_id),
+            page_no: Some(1),
+            ..QueryEnvironmentReq::empty()
+        })
+        .await?;
+    if params.is_none() {
+        //新选择的任务，默认选择第一个环境
+        edit_form.environment_id.set(
+            pagination_list
+                .list
+                .first()
+                .map(|environment| environment.id),
+        );
+    }
+    environment_list.set(pagination_list.list);
+    return Ok(());
+}
+
+async fn read_job_schedule_detail(
+    edit_form: &EditForm,
+    environment_list: &UseStateHandle<Vec<Environment>>,
+    id: Id,
+) -> Result<JobSchedule, LightString> {
+    let job_schedule = ReadJobScheduleApi
+        .call(&ReadJobScheduleReq { id: id })
+        .await?;
+    edit_form.job_id.set(Some(job_schedule.job_id));
+    edit_form
+        .environment_id
+        .set(Some(job_schedule.environment_id));
+    edit_form.name.set(job_schedule.name.clone().into());
+    edit_form
+        .cron_expression
+        .set(job_schedule.cron_expression.clone().into());
+    edit_form.status.set(job_schedule.status);
+    handle_job_change(
+        edit_form,
+        environment_list,
+        job_schedule.job_id,
+        Some(job_schedule.params.as_deref().unwrap_or("{}")),
+    )
+    .await?;
+    return Ok(job_schedule);
+}
+
+async fn chk_form_err(id: Option<Id>, edit_form: &EditForm) -> Vec<LightString> {
+    let mut err_msgs: Vec<LightString> = Vec::new();
+    if let Err(error) = edit_form.name.validate(true) {
+        err_msgs.push(error);
+    }
+    if id.is_none() {
+        if let Err(error) = edit_form.job_id.validate(true) {
+            err_msgs.push(error);
+        }
+    }
+    if let Err(error) = edit_form.environment_id.validate(true) {
+        err_msgs.push(error);
+    }
+    if let Err(error) = edit_form.cron_expression.validate(true) {
+        err_msgs.push(error);
+    }
+    for (_, _, value) in edit_form.params.iter() {
+        if let Err(error) = value.validate(true) {
+            err_msgs.push(error);
+        }
+    }
+    return err_msgs;
+}
+
+async fn save_job_schedule(
+    id: Option<Id>,
+    edit_form: &EditForm,
+    is_saving: UseStateHandle<bool>,
+    err_msg: &UseStateHandle<Option<LightString>>,
+    onsave: &Option<Callback<PrimaryKey>>,
+) -> Result<(), LightString> {
+    let err_msgs = chk_form_err(id, edit_form).await;
+    if let Some(first) = err_msgs.first() {
+        err_msg.set(Some(first.clone()));
+        return Err(first.clone());
+    }
+    //任务没有定义执行参数时不需要传
+    let params = if edit_form.params.is_empty() {
+        None
+    } else {
+        Some(serialize_config(&edit_form.params))
+    };
+    let ret = if let Some(id) = id {
+        UpdateJobScheduleApi
+            .lock_handler(is_saving)
+            .call(&UpdateJobScheduleReq {
+                id: id,
+                environment_id: edit_form.environment_id.get().unwrap(),
+                name: edit_form.name.get().to_string(),
+                cron_expression: edit_form.cron_expression.get().to_string(),
+                params: params,
+                status: *edit_form.status,
+            })
+            .await
+            .map(|_| PrimaryKey { id: id })
+    } else {
+        InsertJobScheduleApi
+            .lock_handler(is_saving)
+            .call(&InsertJobScheduleReq {
+                job_id: edit_form.job_id.get().unwrap(),
+                environment_id: edit_form.environment_id.get().unwrap(),
+                name: edit_form.name.get().to_string(),
+                cron_expression: edit_form.cron_expression.get().to_string(),
+                params: params,
+                status: *edit_form.status,
+            })
+            .await
+    };
+    match ret {
+        Err(err) => {
+            log::error!("{}", err);
+            err_msg.set(Some(err.clone()));
+            return Err(err);
+        }
+        Ok(pri_key) => {
+            if let Some(onsave) = onsave {
+                onsave.emit(pri_key);
+            }
+            utils::success(LightString::from("保存成功"));
+            return Ok(());
+        }
+    }
+}
@@ -0,0 +1,346 @@
+use super::super::job_record::detail::JobRecordDetail;
+use super::edit::JobScheduleEdit;
+use crate::components::button::Button;
+use crate::components::button_group::ButtonGroup;
+use crate::components::drawer::Drawer;
+use crate::components::pagination::Pagination as PaginationComp;
+use crate::sdk;
+use crate::utils;
+use crate::utils::request::ApiExt;
+use crate::utils::LoadStatus;
+use crate::LightString;
+use sdk::job_schedule::delete_job_schedule::DeleteJobScheduleApi;
+use sdk::job_schedule::delete_job_schedule::DeleteJobScheduleReq;
+use sdk::job_schedule::enums::ScheduleStatus;
+use sdk::job_schedule::query_job_schedule::QueryJobScheduleApi;
+use sdk::job_schedule::query_job_schedule::QueryJobScheduleReq;
+use sdk::job_schedule::read_job_schedule::JobSchedule;
+use std::ops::Deref;
+use tihu::datetime_format::FORMAT;
+use tihu::Id;
+use tihu::Pagination;
+use tihu::PrimaryKey;
+use yew::prelude::*;
+
+#[function_component]
+pub fn JobScheduleList() -> Html {
+    let pagination: UseStateHandle<Pagination> = use_state(|| Pagination::new(0, 1, None, None));
+    let list: UseStateHandle<Vec<JobSchedule>> = use_state(|| Vec::new());
+    let list_load_status: UseStateHandle<LoadStatus> = use_state(|| LoadStatus::NotStarted);
+    let record_detail_active: UseStateHandle<bool> = use_state(|| false);
+    let active_record_detail_id: UseStateHandle<Option<Id>> = use_state(|| None);
+    let edit_active: UseStateHandle<bool> = use_state(|| false);
+    let active_edit_id: UseStateHandle<Option<Id>> = use_state(|| None);
+    let active_edit_id_clone = active_edit_id.clone();
+    let edit_active_clone = edit_active.clone();
+    let on_open_insert = Callback::from(move |_: ()| {
+        active_edit_id_clone.set(None);
+        edit_active_clone.set(true);
+    });
+    let list_clone = list.clone();
+    let list_load_status_clone = list_load_status.clone();
+    let pagination_clone = pagination.clone();
+    let on_query = Callback::from(move |_| {
+        let list = list_clone.clone();
+        let list_load_status = list_load_status_clone.clone();
+        let pagination = pagination_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            query_job_schedule_list(1, &list, &list_load_status, &pagination).await;
+        });
+    });
+    let on_query_clone = on_query.clone();
+    use_effect_with((), move |_| {
+        on_query_clone.emit(());
+        || ()
+    });
+    let list_clone = list.clone();
+    let list_load_status_clone = list_load_status.clone();
+    let pagination_clone = pagination.clone();
+    let on_page = Callback::from(move |page: u64| {
+        let list = list_clone.clone();
+        let list_load_status = list_load_status_clone.clone();
+        let pagination = pagination_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            query_job_schedule_list(page, &list, &list_load_status, &pagination).await;
+        });
+    });
+    let edit_active_clone = edit_active.clone();
+    let active_edit_id_clone = active_edit_id.clone();
+    let list_clone = list.clone();
+    let list_load_status_clone = list_load_status.clone();
+    let pagination_clone = pagination.clone();
+    let on_finish_save = Callback::from(move |_pri_key: PrimaryKey| {
+        edit_active_clone.set(false);
+        let active_edit_id = active_edit_id_clone.clone();
+        let list = list_clone.clone();
+        let list_load_status = list_load_status_clone.clone();
+        let pagination = pagination_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            if active_edit_id.is_some() {
+                refresh_list(&list, &list_load_status, &pagination).await;
+            } else {
+                query_job_schedule_list(1, &list, &list_load_status, &pagination).await;
+            }
+        });
+    });
+    let record_detail_active_clone = record_detail_active.clone();
+    let active_record_detail_id_clone = active_record_detail_id.clone();
+    let on_leave_record_detail = Callback::from(move |_| {
+        record_detail_active_clone.set(false);
+        active_record_detail_id_clone.set(None);
+    });
+    let edit_active_clone = edit_active.clone();
+    let active_edit_id_clone = active_edit_id.clone();
+    let on_leave_edit = Callback::from(move |_| {
+        edit_active_clone.set(false);
+        active_edit_id_clone.set(None);
+    });
+    html! {
+        <div class="relative width-fill height-fill" style="overflow:hidden;">
+            <div class="width-fill height-fill border-box" style="padding:0.25em;">
+                <div class="width-fill height-fill" style="display: -webkit-box;display: flex;-webkit-box-direction: normal;-webkit-box-orient: vertical;flex-direction: column;">
+                    <header style="-webkit-box-flex: 0;flex-basis: auto;flex-grow: 0;">
+                        <div style="display:flex;justify-content: space-between;padding-bottom: 0.25em;">
+                            <span style="font-weight:bold;">{"任务计划列表"}</span>
+                            <ButtonGroup>
+                                <Button onclick={on_open_insert}>{"新增"}</Button>
+                                <Button onclick={on_query}>{"查询"}</Button>
+                            </ButtonGroup>
+                        </div>
+                    </header>
+                    <div style="-webkit-box-flex: 1;flex-basis: auto;flex-grow: 1;overflow-y: auto;">
+                        { table_view(&list, &list_load_status, &pagination, &record_detail_active, &active_record_detail_id, &edit_active, &active_edit_id) }
+                        { list_exception_view(list.is_empty(), list_load_status.deref().clone()) }
+                    </div>
+                    <div style="-webkit-box-flex: 0;flex-basis: auto;flex-grow: 0;padding-top: 0.25em;">
+                        <PaginationComp pagination={pagination.deref().clone()} onpage={on_page} />
+                    </div>
+                </div>
+            </div>
+            <Drawer active={*record_detail_active} onclickother={on_leave_record_detail}>
+                {
+                    match active_record_detail_id.as_ref() {
+                        Some(active_record_detail_id) => html! {
+                            <JobRecordDetail id={*active_record_detail_id} />
+                        },
+                        None => html! {}
+                    }
+                }
+            </Drawer>
+            <Drawer active={*edit_active} onclickother={on_leave_edit}>
+                <JobScheduleEdit id={active_edit_id.deref().clone()} onsave={on_finish_save} />
+            </Drawer>
+        </div>
+    }
+}
+
+fn table_view(
+    list: &UseStateHandle<Vec<JobSchedule>>,
+    list_load_status: &UseStateHandle<LoadStatus>,
+    pagination: &UseStateHandle<Pagination>,
+    record_detail_active: &UseStateHandle<bool>,
+    active_record_detail_id: &UseStateHandle<Option<Id>>,
+    edit_active: &UseStateHandle<bool>,
+    active_edit_id: &UseStateHandle<Option<Id>>,
+) -> Html {
+    return html! {
+        <table class="e-table width-fill">
+            <thead>
+                <tr>
+                    <th class="e-table-hcell">{"计划名称"}</th>
+                    <th class="e-table-hcell">{"任务"}</th>
+                    <th class="e-table-hcell">{"环境"}</th>
+                    <th class="e-table-hcell">{"cron表达式"}</th>
+                    <th class="e-table-hcell">{"状态"}</th>
+                    <th class="e-table-hcell">{"下次触发时间"}</th>
+                    <th class="e-table-hcell">{"上次触发时间"}</th>
+                    <th class="e-table-hcell">{"操作"}</th>
+                </tr>
+            </thead>
+            {
+                if list_load_status.deref() == &LoadStatus::LoadOk || list_load_status.deref() == &LoadStatus::Loading {
+                    html! {
+                        <tbody>
+                            {
+                                for list.iter().map(|item| {
+                                    row_view(list, list_load_status, pagination, item, record_detail_active, active_record_detail_id, edit_active, active_edit_id)
+                                })
+                            }
+                        </tbody>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+        </table>
+    };
+}
+
+fn row_view(
+    list: &UseStateHandle<Vec<JobSchedule>>,
+    list_load_status: &UseStateHandle<LoadStatus>,
+    pagination: &UseStateHandle<Pagination>,
+    job_schedule: &JobSchedule,
+    record_detail_active: &UseStateHandle<bool>,
+    active_record_detail_id: &UseStateHandle<Option<Id>>,
+    edit_active: &UseStateHandle<bool>,
+    active_edit_id: &UseStateHandle<Option<Id>>,
+) -> Html {
+    let last_record_id = job_schedule.last_record_id;
+    let record_detail_active = record_detail_active.clone();
+    let active_record_detail_id = active_record_detail_id.clone();
+    let on_open_last_record = Callback::from(move |_: ()| {
+        if let Some(last_record_id) = last_record_id {
+            active_record_detail_id.set(Some(last_record_id));
+            record_detail_active.set(true);
+        }
+    });
+    let update_id = job_schedule.id;
+    let active_edit_id = active_edit_id.clone();
+    let edit_active = edit_active.clone();
+    let on_open_update = Callback::from(move |_: ()| {
+        active_edit_id.set(Some(update_id));
+        edit_active.set(true);
+    });
+    let delete_id = job_schedule.id;
+    let list = list.clone();
+    let list_load_status = list_load_status.clone();
+    let pagination = pagination.clone();
+    let on_confirm_delete = Callback::from(move |_: ()| {
+        let list = list.clone();
+        let list_load_status = list_load_status.clone();
+        let pagination = pagination.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let ok = utils::confirm(LightString::from("确定删除该计划？"), None).await;
+            if ok {
+                let list = list.clone();
+                let list_load_status = list_load_status.clone();
+                let pagination = pagination.clone();
+                delete_job_schedule(delete_id, &list, &list_load_status, &pagination)
+                    .await
+                    .ok();
+            }
+        });
+    });
+    let status_color = match job_schedule.status {
+        ScheduleStatus::Enabled => "green",
+        ScheduleStatus::Disabled => "gray",
+    };
+    return html! {
+        <tr class="e-table-row">
+            <td class="e-table-cell align-center">{job_schedule.name.clone()}</td>
+            <td class="e-table-cell align-center">{job_schedule.job_name.clone().unwrap_or_default()}</td>
+            <td class="e-table-cell align-center">{job_schedule.environment_name.clone().unwrap_or_default()}</td>
+            <td class="e-table-cell align-center">{job_schedule.cron_expression.clone()}</td>
+            <td class="e-table-cell align-center" style={format!("color:{};", status_color)}>{job_schedule.status.to_string()}</td>
+            <td class="e-table-cell align-center">{job_schedule.next_fire_time.map(|time| format!("{}", time.format(FORMAT))).unwrap_or_default()}</td>
+            <td class="e-table-cell align-center">{job_schedule.last_fire_time.map(|time| format!("{}", time.format(FORMAT))).unwrap_or_default()}</td>
+            <td class="e-table-cell align-center">
+                <ButtonGroup>
+                    <Button disabled={last_record_id.is_none()} onclick={on_open_last_record}>{"最近执行"}</Button>
+                    <Button onclick={on_open_update}>{"编辑"}</Button>
+                    <Button onclick={on_confirm_delete}>{"删除"}</Button>
+                </ButtonGroup>
+            </td>
+        </tr>
+    };
+}
+
+fn list_exception_view(is_empty: bool, list_load_status: LoadStatus) -> Html {
+    return html! {
+        match list_load_status {
+            LoadStatus::LoadFailed => {
+                html! {
+                    <p class="align-center">{"列表加载失败"}</p>
+                }
+            },
+            LoadStatus::LoadOk => {
+                if is_empty {
+                    html! {
+                        <p class="align-center">{"列表数据为空"}</p>
+                    }
+                } else {
+                    html! {}
+                }
+            },
+            _ => html! {}
+        }
+    };
+}
+
+fn clear_list(list: &UseStateHandle<Vec<JobSchedule>>, pagination: &UseStateHandle<Pagination>) {
+    pagination.set(Pagination::new(0, 1, None, None));
+    list.set(Vec::new());
+}
+
+async fn query_job_schedule_list(
+    page_no: u64,
+    list: &UseStateHandle<Vec<JobSchedule>>,
+    list_load_status: &UseStateHandle<LoadStatus>,
+    pagination: &UseStateHandle<Pagination>,
+) {
+    let params = QueryJobScheduleReq {
+        page_no: Some(page_no),
+        ..QueryJobScheduleReq::empty()
+    };
+    list_load_status.set(LoadStatus::Loading);
+    let ret = QueryJobScheduleApi.call(&params).await;
+    match ret {
+        Err(err) => {
+            log::error!("{}", err);
+            list_load_status.set(LoadStatus::LoadFailed);
+            clear_list(list, pagination);
+        }
+        Ok(pagination_list) => {
+            list_load_status.set(LoadStatus::LoadOk);
+            list.set(pagination_list.list);
+            pagination.set(pagination_list.pagination);
+        }
+    }
+}
+
+async fn refresh_list(
+    list: &UseStateHandle<Vec<JobSchedule>>,
+    list_load_status: &UseStateHandle<LoadStatus>,
+    pagination: &UseStateHandle<Pagination>,
+) {
+    let page_no = if list.is_empty() {
+        pagination.page_no - 1
+    } else {
+        pagination.page_no
+    };
+    query_job_schedule_list(page_no.max(1), list, list_load_status, pagination).await;
+}
+
+async fn delete_job_schedule(
+    id: Id,
+    list: &UseStateHandle<Vec<JobSchedule>>,
+    list_load_status: &UseStateHandle<LoadStatus>,
+    pagination: &UseStateHandle<Pagination>,
+) -> Result<(), LightString> {
+    let params = DeleteJobScheduleReq { id: id };
+    DeleteJobScheduleApi.call(&params).await?;
+    remove_job_schedule(id, list);
+    utils::success(LightString::from("删除成功"));
+    let list = list.clone();
+    let list_load_status = list_load_status.clone();
+    let pagination = pagination.clone();
+    wasm_bindgen_futures::spawn_local(async move {
+        refresh_list(&list, &list_load_status, &pagination).await;
+    });
+    return Ok(());
+}
+
+fn remove_job_schedule(id: Id, list: &UseStateHandle<Vec<JobSchedule>>) {
+    let new_list: Vec<_> = list
+        .iter()
+        .filter_map(|item| {
+            if item.id == id {
+                None
+            } else {
+                Some(item.clone())
+            }
+        })
+        .collect();
+    list.set(new_list);
+}
@@ -1,3 +1,4 @@
+pub mod api_token;
 pub mod auth;
 pub mod change_password;
 pub mod environment;
@@ -6,5 +7,8 @@ mod extension;
 pub mod index;
 pub mod job;
 pub mod job_record;
+pub mod job_schedule;
+pub mod retention_policy;
+pub mod runner;
 pub mod sys_menu;
 pub mod user;
@@ -0,0 +1,192 @@
+use crate::components::button::Button;
+use crate::components::input::BindingInput;
+use crate::sdk;
+use crate::utils;
+use crate::utils::format_time_local;
+use crate::utils::request::ApiExt;
+use crate::LightString;
+use sdk::organization::read_retention_policy::ReadRetentionPolicyApi;
+use sdk::organization::read_retention_policy::ReadRetentionPolicyReq;
+use sdk::organization::read_retention_policy::RetentionPolicy as RetentionPolicyInfo;
+use sdk::organization::update_retention_policy::UpdateRetentionPolicyApi;
+use sdk::organization::update_retention_policy::UpdateRetentionPolicyReq;
+use yew::prelude::*;
+
+#[derive(Clone)]
+struct PolicyForm {
+    retention_count: UseStateHandle<LightString>,
+    retention_days: UseStateHandle<LightString>,
+}
+
+#[function_component]
+pub fn RetentionPolicy() -> Html {
+    let form = PolicyForm {
+        retention_count: use_state(|| "".into()),
+        retention_days: use_state(|| "".into()),
+    };
+    let policy: UseStateHandle<Option<RetentionPolicyInfo>> = use_state(|| None);
+    let is_saving: UseStateHandle<bool> = use_state(|| false);
+    let err_msg: UseStateHandle<Option<LightString>> = use_state(|| None);
+
+    let form_clone = form.clone();
+    let policy_clone = policy.clone();
+    use_effect_with((), move |_| {
+        wasm_bindgen_futures::spawn_local(async move {
+            read_retention_policy(&form_clone, &policy_clone).await.ok();
+        });
+        || ()
+    });
+
+    let err_msg_clone = err_msg.clone();
+    let clear_err_msg = Callback::from(move |_| {
+        err_msg_clone.set(None);
+    });
+
+    let form_clone = form.clone();
+    let policy_clone = policy.clone();
+    let is_saving_clone = is_saving.clone();
+    let err_msg_clone = err_msg.clone();
+    let on_save = Callback::from(move |_| {
+        let form = form_clone.clone();
+        let policy = policy_clone.clone();
+        let is_saving = is_saving_clone.clone();
+        let err_msg = err_msg_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            save_policy(&form, &policy, &is_saving, &err_msg).await;
+        });
+    });
+    html! {
+        <div class="width-fill height-fill border-box" style="padding:0.25em;">
+            <table style="border-collapse:collapse;table-layout: fixed;">
+                <tr>
+                    <td class="align-right" style="width:10em;">{"保留最近条数："}</td>
+                    <td>
+                        <BindingInput value={form.retention_count.clone()} onfocus={clear_err_msg.clone()} placeholder="每个任务在每个环境下保留的条数，为空表示不限制"/>
+                    </td>
+                </tr>
+                <tr>
+                    <td class="align-right" style="width:10em;">{"保留最近天数："}</td>
+                    <td>
+                        <BindingInput value={form.retention_days.clone()} onfocus={clear_err_msg} placeholder="单位：天，为空表示不限制"/>
+                    </td>
+                </tr>
+                <tr>
+                    <td></td>
+                    <td style="color:gray;">{"两个条件都设置时满足任意一个的记录都会保留，置顶的记录始终保留"}</td>
+                </tr>
+                <tr>
+                    <td></td>
+                    <td>
+                        <Button disabled={*is_saving} onclick={on_save}>{"保存"}</Button>
+                        {
+                            match err_msg.as_ref() {
+                                Some(err_msg) => {
+                                    html!{
+                                        <span class="middle" style="color:red;margin-left: 0.5em;">{err_msg}</span>
+                                    }
+                                },
+                                None => html!{}
+                            }
+                        }
+                    </td>
+                </tr>
+                <tr>
+                    <td class="align-right" style="width:10em;">{"最后清理时间："}</td>
+                    <td>
+                        {
+                            policy.as_ref().and_then(|policy| policy.last_purge_time.as_ref()).map(|time| format_time_local(time).to_string()).unwrap_or_else(|| String::from("未清理过"))
+                        }
+                    </td>
+                </tr>
+                <tr>
+                    <td class="align-right" style="width:10em;">{"最后清理结果："}</td>
+                    <td>
+                        {
+                            match policy.as_ref().and_then(|policy| policy.last_purge_report.as_ref()) {
+                                Some(report) => format!(
+                                    "执行记录{}条，步骤记录{}条，资源记录{}条，日志对象{}个，日志文件{}个",
+                                    report.record_count,
+                                    report.step_record_count,
+                                    report.step_resource_record_count,
+                                    report.log_object_count,
+                                    report.log_file_count
+                                ),
+                                None => String::new(),
+                            }
+                        }
+                    </td>
+                </tr>
+            </table>
+        </div>
+    }
+}
+
+fn to_input(value: Option<u32>) -> LightString {
+    return value
+        .map(|value| LightString::from(value.to_string()))
+        .unwrap_or_else(|| "".into());
+}
+
+async fn read_retention_policy(
+    form: &PolicyForm,
+    policy: &UseStateHandle<Option<RetentionPolicyInfo>>,
+) -> Result<(), LightString> {
+    let params = ReadRetentionPolicyReq {};
+    let retention_policy = ReadRetentionPolicyApi.call(&params).await?;
+    form.retention_count
+        .set(to_input(retention_policy.retention_count));
+    form.retention_days
+        .set(to_input(retention_policy.retention_days));
+    policy.set(Some(retention_policy));
+    return Ok(());
+}
+
+//为空表示不限制，否则必须是正整数
+fn parse_input(value: &str, name: &str) -> Result<Option<u32>, LightString> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+    match value.parse::<u32>() {
+        Ok(value) if 0 < value => Ok(Some(value)),
+        _ => Err(LightString::from(format!("{}必须是正整数", name))),
+    }
+}
+
+async fn save_policy(
+    form: &PolicyForm,
+    policy: &UseStateHandle<Option<RetentionPolicyInfo>>,
+    is_saving: &UseStateHandle<bool>,
+    err_msg: &UseStateHandle<Option<LightString>>,
+) {
+    if **is_saving {
+        return;
+    }
+    let params =
+        match parse_input(&form.retention_count, "保留最近条数").and_then(|retention_count| {
+            parse_input(&form.retention_days, "保留最近天数").map(|retention_days| {
+                UpdateRetentionPolicyReq {
+                    retention_count: retention_count,
+                    retention_days: retention_days,
+                }
+            })
+        }) {
+            Ok(params) => params,
+            Err(err) => {
+                err_msg.set(Some(err));
+                return;
+            }
+        };
+    is_saving.set(true);
+    let result = UpdateRetentionPolicyApi.call(&params).await;
+    is_saving.set(false);
+    match result {
+        Err(err) => {
+            log::error!("{}", err);
+            err_msg.set(Some(err));
+        }
+        Ok(_) => {
+            utils::success(LightString::from("保存成功"));
+            read_retention_policy(form, policy).await.ok();
+        }
+    }
+}
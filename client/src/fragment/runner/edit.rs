@@ -0,0 +1,155 @@
+use crate::components::button::Button;
+use crate::components::input::BindingInput;
+use crate::components::required::Required;
+use crate::components::validate_wrapper::ValidateData;
+use crate::sdk;
+use crate::utils;
+use crate::utils::request::ApiExt;
+use crate::utils::validator::RequiredValidator;
+use crate::utils::validator::Validators;
+use crate::LightString;
+use sdk::runner::insert_runner::InsertRunnerApi;
+use sdk::runner::insert_runner::InsertRunnerReq;
+use tihu::PrimaryKey;
+use yew::prelude::*;
+
+#[derive(Clone)]
+struct EditForm {
+    name: ValidateData<LightString>,
+    label: ValidateData<LightString>,
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    #[prop_or_default]
+    pub onsave: Option<Callback<PrimaryKey>>,
+}
+
+#[function_component]
+pub fn RunnerEdit(props: &Props) -> Html {
+    let is_saving: UseStateHandle<bool> = use_state(|| false);
+    let err_msg: UseStateHandle<Option<LightString>> = use_state(|| None);
+    let edit_form = EditForm {
+        name: ValidateData::new(
+            Default::default(),
+            Some(Validators::new().add(RequiredValidator::new("请输入执行器名称"))),
+        ),
+        label: ValidateData::new(
+            Default::default(),
+            Some(Validators::new().add(RequiredValidator::new("请输入执行器标签"))),
+        ),
+    };
+    let edit_form_clone = edit_form.clone();
+    let is_saving_clone = is_saving.clone();
+    let err_msg_clone = err_msg.clone();
+    let onsave_clone = props.onsave.clone();
+    let on_save = Callback::from(move |_| {
+        let edit_form = edit_form_clone.clone();
+        let is_saving = is_saving_clone.clone();
+        let err_msg = err_msg_clone.clone();
+        let onsave = onsave_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            save_runner(&edit_form, is_saving, &err_msg, &onsave)
+                .await
+                .ok();
+        });
+    });
+    html! {
+        <div class="width-fill height-fill border-box" style="padding:0.25em;">
+            <table class="width-fill" style="border-collapse:collapse;table-layout: fixed;">
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;"><Required/>{"名称："}</td>
+                    <td>
+                        {
+                            edit_form.name.view(move |name: UseStateHandle<LightString>, validator| {
+                                html! {
+                                    <BindingInput value={name} onupdate={validator}/>
+                                }
+                            })
+                        }
+                    </td>
+                </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;"><Required/>{"标签："}</td>
+                    <td>
+                        {
+                            edit_form.label.view(move |label: UseStateHandle<LightString>, validator| {
+                                html! {
+                                    <BindingInput value={label} onupdate={validator} placeholder="环境指定了相同的标签时，步骤交给该执行器执行"/>
+                                }
+                            })
+                        }
+                    </td>
+                </tr>
+                <tr>
+                    <td></td>
+                    <td>
+                        <Button disabled={*is_saving} onclick={on_save}>{"保存"}</Button>
+                        {
+                            match err_msg.as_ref() {
+                                Some(err_msg) => {
+                                    html!{
+                                        <span class="middle" style="color:red;margin-left: 0.5em;">{err_msg}</span>
+                                    }
+                                },
+                                None => html!{}
+                            }
+                        }
+                    </td>
+                </tr>
+            </table>
+        </div>
+    }
+}
+
+fn chk_form_err(edit_form: &EditForm) -> Vec<LightString> {
+    let mut err_msgs: Vec<LightString> = Vec::new();
+    if let Err(error) = edit_form.name.validate(true) {
+        err_msgs.push(error);
+    }
+    if let Err(error) = edit_form.label.validate(true) {
+        err_msgs.push(error);
+    }
+    return err_msgs;
+}
+
+async fn save_runner(
+    edit_form: &EditForm,
+    is_saving: UseStateHandle<bool>,
+    err_msg: &UseStateHandle<Option<LightString>>,
+    onsave: &Option<Callback<PrimaryKey>>,
+) -> Result<(), LightString> {
+    let err_msgs = chk_form_err(edit_form);
+    if let Some(first) = err_msgs.first() {
+        err_msg.set(Some(first.clone()));
+        return Err(first.clone());
+    }
+    let params = InsertRunnerReq {
+        name: edit_form.name.get().to_string(),
+        label: edit_form.label.get().to_string(),
+    };
+    let ret = InsertRunnerApi.lock_handler(is_saving).call(&params).await;
+    match ret {
+        Err(err) => {
+            log::error!("{}", err);
+            err_msg.set(Some(err));
+        }
+        Ok(insert_runner_resp) => {
+            //访问令牌只在创建时显示这一次，服务端只保存摘要
+            utils::alert(
+                LightString::from(format!(
+                    "请把访问令牌配置到执行器上，关闭之后无法再次查看：{}",
+                    insert_runner_resp.token
+                )),
+                Some(LightString::from("访问令牌")),
+            )
+            .await;
+            if let Some(onsave) = onsave {
+                onsave.emit(PrimaryKey {
+                    id: insert_runner_resp.id,
+                });
+            }
+        }
+    }
+    return Ok(());
+}
@@ -0,0 +1,222 @@
+use super::edit::RunnerEdit;
+use crate::components::button::Button;
+use crate::components::button_group::ButtonGroup;
+use crate::components::drawer::Drawer;
+use crate::sdk;
+use crate::utils;
+use crate::utils::format_time_local;
+use crate::utils::request::ApiExt;
+use crate::utils::LoadStatus;
+use crate::LightString;
+use sdk::runner::delete_runner::DeleteRunnerApi;
+use sdk::runner::delete_runner::DeleteRunnerReq;
+use sdk::runner::query_runner::QueryRunnerApi;
+use sdk::runner::query_runner::QueryRunnerReq;
+use sdk::runner::query_runner::Runner;
+use std::ops::Deref;
+use tihu::Id;
+use tihu::PrimaryKey;
+use yew::prelude::*;
+
+#[function_component]
+pub fn RunnerList() -> Html {
+    let list: UseStateHandle<Vec<Runner>> = use_state(|| Vec::new());
+    let list_load_status: UseStateHandle<LoadStatus> = use_state(|| LoadStatus::NotStarted);
+    let edit_active: UseStateHandle<bool> = use_state(|| false);
+    let edit_active_clone = edit_active.clone();
+    let on_open_insert = Callback::from(move |_: ()| {
+        edit_active_clone.set(true);
+    });
+    let list_clone = list.clone();
+    let list_load_status_clone = list_load_status.clone();
+    let on_query = Callback::from(move |_| {
+        let list = list_clone.clone();
+        let list_load_status = list_load_status_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            query_runner_list(&list, &list_load_status).await;
+        });
+    });
+    let on_query_clone = on_query.clone();
+    use_effect_with((), move |_| {
+        on_query_clone.emit(());
+        || ()
+    });
+    let edit_active_clone = edit_active.clone();
+    let list_clone = list.clone();
+    let list_load_status_clone = list_load_status.clone();
+    let on_finish_save = Callback::from(move |_pri_key: PrimaryKey| {
+        edit_active_clone.set(false);
+        let list = list_clone.clone();
+        let list_load_status = list_load_status_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            query_runner_list(&list, &list_load_status).await;
+        });
+    });
+    let edit_active_clone = edit_active.clone();
+    let on_leave_edit = Callback::from(move |_| {
+        edit_active_clone.set(false);
+    });
+    html! {
+        <div class="relative width-fill height-fill" style="overflow:hidden;">
+            <div class="width-fill height-fill border-box" style="padding:0.25em;">
+                <div class="width-fill height-fill" style="display: -webkit-box;display: flex;-webkit-box-direction: normal;-webkit-box-orient: vertical;flex-direction: column;">
+                    <header style="-webkit-box-flex: 0;flex-basis: auto;flex-grow: 0;">
+                        <div class="align-right" style="padding-bottom: 0.25em;">
+                            <ButtonGroup>
+                                <Button onclick={on_open_insert}>{"新增"}</Button>
+                                <Button onclick={on_query}>{"查询"}</Button>
+                            </ButtonGroup>
+                        </div>
+                    </header>
+                    <div style="-webkit-box-flex: 1;flex-basis: auto;flex-grow: 1;overflow-y: auto;">
+                        { table_view(&list, &list_load_status) }
+                        { list_exception_view(list.is_empty(), list_load_status.deref().clone()) }
+                    </div>
+                </div>
+            </div>
+            <Drawer active={*edit_active} onclickother={on_leave_edit}>
+                if *edit_active {
+                    <RunnerEdit onsave={on_finish_save} />
+                }
+            </Drawer>
+        </div>
+    }
+}
+
+fn table_view(
+    list: &UseStateHandle<Vec<Runner>>,
+    list_load_status: &UseStateHandle<LoadStatus>,
+) -> Html {
+    return html! {
+        <table class="e-table width-fill">
+            <thead>
+                <tr>
+                    <th class="e-table-hcell">{"名称"}</th>
+                    <th class="e-table-hcell">{"标签"}</th>
+                    <th class="e-table-hcell">{"状态"}</th>
+                    <th class="e-table-hcell">{"版本"}</th>
+                    <th class="e-table-hcell">{"扩展"}</th>
+                    <th class="e-table-hcell">{"最近心跳"}</th>
+                    <th class="e-table-hcell">{"创建时间"}</th>
+                    <th class="e-table-hcell">{"操作"}</th>
+                </tr>
+            </thead>
+            {
+                if list_load_status.deref() == &LoadStatus::LoadOk || list_load_status.deref() == &LoadStatus::Loading {
+                    html! {
+                        <tbody>
+                            {
+                                for list.iter().map(|item| {
+                                    row_view(item, list, list_load_status)
+                                })
+                            }
+                        </tbody>
+                    }
+                } else {
+                    html! {}
+                }
+            }
+        </table>
+    };
+}
+
+fn row_view(
+    runner: &Runner,
+    list: &UseStateHandle<Vec<Runner>>,
+    list_load_status: &UseStateHandle<LoadStatus>,
+) -> Html {
+    let delete_id = runner.id;
+    let list = list.clone();
+    let list_load_status = list_load_status.clone();
+    let on_confirm_delete = Callback::from(move |_: ()| {
+        let list = list.clone();
+        let list_load_status = list_load_status.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let ok = utils::confirm(
+                LightString::from("删除之后该执行器的访问令牌立即失效，确定删除该执行器？"),
+                None,
+            )
+            .await;
+            if ok {
+                delete_runner(delete_id, &list, &list_load_status)
+                    .await
+                    .ok();
+            }
+        });
+    });
+    let (status_text, status_color) = if runner.online {
+        ("在线", "green")
+    } else {
+        ("离线", "gray")
+    };
+    return html! {
+        <tr class="e-table-row">
+            <td class="e-table-cell align-center">{&runner.name}</td>
+            <td class="e-table-cell align-center">{&runner.label}</td>
+            <td class="e-table-cell align-center" style={format!("color:{};", status_color)}>{status_text}</td>
+            <td class="e-table-cell align-center">{runner.version.clone().unwrap_or_default()}</td>
+            <td class="e-table-cell align-center">{runner.extension_ids.join(", ")}</td>
+            <td class="e-table-cell align-center">{runner.last_heartbeat_time.as_ref().map(|time| format_time_local(time).to_string()).unwrap_or_default()}</td>
+            <td class="e-table-cell align-center">{ html!{&format_time_local(&runner.created_time)} }</td>
+            <td class="e-table-cell align-center">
+                <ButtonGroup>
+                    <Button onclick={on_confirm_delete}>{"删除"}</Button>
+                </ButtonGroup>
+            </td>
+        </tr>
+    };
+}
+
+fn list_exception_view(is_empty: bool, list_load_status: LoadStatus) -> Html {
+    return html! {
+        match list_load_status {
+            LoadStatus::LoadFailed => {
+                html! {
+                    <p class="align-center">{"列表加载失败"}</p>
+                }
+            },
+            LoadStatus::LoadOk => {
+                if is_empty {
+                    html! {
+                        <p class="align-center">{"列表数据为空"}</p>
+                    }
+                } else {
+                    html! {}
+                }
+            },
+            _ => html! {}
+        }
+    };
+}
+
+async fn query_runner_list(
+    list: &UseStateHandle<Vec<Runner>>,
+    list_load_status: &UseStateHandle<LoadStatus>,
+) {
+    let params = QueryRunnerReq::empty();
+    list_load_status.set(LoadStatus::Loading);
+    let ret = QueryRunnerApi.call(&params).await;
+    match ret {
+        Err(err) => {
+            log::error!("{}", err);
+            list_load_status.set(LoadStatus::LoadFailed);
+            list.set(Vec::new());
+        }
+        Ok(runner_list) => {
+            list_load_status.set(LoadStatus::LoadOk);
+            list.set(runner_list);
+        }
+    }
+}
+
+async fn delete_runner(
+    id: Id,
+    list: &UseStateHandle<Vec<Runner>>,
+    list_load_status: &UseStateHandle<LoadStatus>,
+) -> Result<(), LightString> {
+    let params = DeleteRunnerReq { id: id };
+    DeleteRunnerApi.call(&params).await?;
+    utils::success(LightString::from("删除成功"));
+    query_runner_list(list, list_load_status).await;
+    return Ok(());
+}
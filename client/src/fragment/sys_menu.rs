@@ -49,6 +49,13 @@ pub fn SysMenu(props: &Props) -> Html {
             route: Some(AnyRoute::new(Route::JobList.to_path())),
             children: None,
         },
+        MenuNode {
+            state: state.clone(),
+            key: gen_id().into(),
+            name: String::from("任务计划"),
+            route: Some(AnyRoute::new(Route::JobScheduleList.to_path())),
+            children: None,
+        },
         MenuNode {
             state: state.clone(),
             key: gen_id().into(),
@@ -56,6 +63,27 @@ pub fn SysMenu(props: &Props) -> Html {
             route: Some(AnyRoute::new(Route::UserList.to_path())),
             children: None,
         },
+        MenuNode {
+            state: state.clone(),
+            key: gen_id().into(),
+            name: String::from("执行器"),
+            route: Some(AnyRoute::new(Route::RunnerList.to_path())),
+            children: None,
+        },
+        MenuNode {
+            state: state.clone(),
+            key: gen_id().into(),
+            name: String::from("访问令牌"),
+            route: Some(AnyRoute::new(Route::ApiTokenList.to_path())),
+            children: None,
+        },
+        MenuNode {
+            state: state.clone(),
+            key: gen_id().into(),
+            name: String::from("执行记录保留"),
+            route: Some(AnyRoute::new(Route::RetentionPolicy.to_path())),
+            children: None,
+        },
     ];
     html! {
         <Menu list={list.clone()} />
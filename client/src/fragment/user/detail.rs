@@ -35,6 +35,10 @@ pub fn UserDetail(props: &Props) -> Html {
                     <td class="align-right" style="width:8em;vertical-align: top;">{"名称："}</td>
                     <td>{detail.as_ref().map(|user|{html!{&user.name}}).unwrap_or_else(utils::empty_html)}</td>
                 </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;">{"角色："}</td>
+                    <td>{detail.as_ref().map(|user|{html!{user.role.to_string()}}).unwrap_or_else(utils::empty_html)}</td>
+                </tr>
                 <tr>
                     <td class="align-right" style="width:8em;vertical-align: top;">{"头像："}</td>
                     <td>{detail.as_ref().map(|user|{user.avatar_url.as_ref().map(|avatar_url|{
@@ -84,6 +88,14 @@ pub fn UserDetail(props: &Props) -> Html {
                                     </>
                                 }
                             }
+                            UserSource::ServiceAccount => {
+                                html! {
+                                    <tr>
+                                        <td class="align-right" style="width:8em;vertical-align: top;">{"来源："}</td>
+                                        <td>{"服务账号"}</td>
+                                    </tr>
+                                }
+                            }
                         }
                     } else {
                         html! {}
@@ -1,5 +1,7 @@
+use super::get_role_list;
 use crate::components::button::Button;
 use crate::components::input::BindingInput;
+use crate::components::radio_group::BindingRadioGroup;
 use crate::components::validate_wrapper::ValidateData;
 use crate::sdk;
 use crate::utils;
@@ -7,15 +9,19 @@ use crate::utils::request::ApiExt;
 use crate::utils::validator::RequiredValidator;
 use crate::utils::validator::Validators;
 use crate::LightString;
+use sdk::user::enums::Role;
 use sdk::user::invite_user::InviteUserApi;
 use sdk::user::invite_user::InviteUserReq;
 use tihu::PrimaryKey;
 use uuid::Uuid;
 use yew::prelude::*;
 
+type RoleRadioGroup = BindingRadioGroup<(Role, String)>;
+
 #[derive(Clone)]
 struct InviteForm {
     user_id: ValidateData<LightString>,
+    role: UseStateHandle<Role>,
 }
 
 #[derive(Clone, PartialEq, Properties)]
@@ -33,6 +39,7 @@ pub fn InviteEdit(props: &Props) -> Html {
             Default::default(),
             Some(Validators::new().add(RequiredValidator::new("请填写用户id"))),
         ),
+        role: use_state(|| Role::Operator),
     };
     let invite_form_clone = invite_form.clone();
     let is_saving_clone = is_saving.clone();
@@ -64,6 +71,12 @@ pub fn InviteEdit(props: &Props) -> Html {
                         }
                     </td>
                 </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;"><span style="color:red;margin-right: 0.25em;">{"*"}</span>{"角色："}</td>
+                    <td>
+                        <RoleRadioGroup value={invite_form.role.clone()} options={get_role_list()} />
+                    </td>
+                </tr>
                 <tr>
                     <td></td>
                     <td>
@@ -108,7 +121,10 @@ async fn save_user(
         return Err(first.clone());
     }
     let user_id = Uuid::parse_str(invite_form.user_id.get().as_ref()).unwrap();
-    let params = InviteUserReq { user_id: user_id };
+    let params = InviteUserReq {
+        user_id: user_id,
+        role: *invite_form.role,
+    };
     let ret = InviteUserApi.lock_handler(is_saving).call(&params).await;
     match ret {
         Err(err) => {
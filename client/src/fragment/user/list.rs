@@ -1,24 +1,37 @@
 use super::detail::UserDetail;
+use super::get_role_list;
 use super::invite::InviteEdit;
+use super::service_account::ServiceAccountEdit;
 use crate::components::button::Button;
 use crate::components::button_group::ButtonGroup;
 use crate::components::drawer::Drawer;
 use crate::components::image::Image;
 use crate::components::pagination::Pagination as PaginationComp;
+use crate::components::selection::Selection;
+use crate::fragment::api_token::list::ApiTokenList;
 use crate::sdk;
+use crate::utils;
 use crate::utils::format_time_local;
 use crate::utils::request::ApiExt;
 use crate::utils::LoadStatus;
 use crate::LightString;
+use sdk::user::delete_service_account::DeleteServiceAccountApi;
+use sdk::user::delete_service_account::DeleteServiceAccountReq;
+use sdk::user::enums::Role;
+use sdk::user::enums::UserSource;
 use sdk::user::query_user::QueryUserApi;
 use sdk::user::query_user::QueryUserReq;
 use sdk::user::query_user::User;
+use sdk::user::update_user_role::UpdateUserRoleApi;
+use sdk::user::update_user_role::UpdateUserRoleReq;
 use std::ops::Deref;
 use tihu::Id;
 use tihu::Pagination;
 use tihu::PrimaryKey;
 use yew::prelude::*;
 
+type RoleSelection = Selection<(Role, String)>;
+
 #[function_component]
 pub fn UserList() -> Html {
     let pagination: UseStateHandle<Pagination> = use_state(|| Pagination::new(0, 1, None, None));
@@ -27,10 +40,17 @@ pub fn UserList() -> Html {
     let detail_active: UseStateHandle<bool> = use_state(|| false);
     let active_detail_id: UseStateHandle<Option<Id>> = use_state(|| None);
     let invite_active: UseStateHandle<bool> = use_state(|| false);
+    let service_account_active: UseStateHandle<bool> = use_state(|| false);
+    //正在管理访问令牌的服务账号
+    let token_user_id: UseStateHandle<Option<Id>> = use_state(|| None);
     let invite_active_clone = invite_active.clone();
     let on_open_invite = Callback::from(move |_: ()| {
         invite_active_clone.set(true);
     });
+    let service_account_active_clone = service_account_active.clone();
+    let on_open_service_account = Callback::from(move |_: ()| {
+        service_account_active_clone.set(true);
+    });
     let list_clone = list.clone();
     let list_load_status_clone = list_load_status.clone();
     let pagination_clone = pagination.clone();
@@ -81,6 +101,27 @@ pub fn UserList() -> Html {
     let on_leave_invite = Callback::from(move |_| {
         invite_active_clone.set(false);
     });
+    let service_account_active_clone = service_account_active.clone();
+    let list_clone = list.clone();
+    let list_load_status_clone = list_load_status.clone();
+    let pagination_clone = pagination.clone();
+    let on_finish_service_account = Callback::from(move |_pri_key: PrimaryKey| {
+        service_account_active_clone.set(false);
+        let list = list_clone.clone();
+        let list_load_status = list_load_status_clone.clone();
+        let pagination = pagination_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            query_user_list(1, &list, &list_load_status, &pagination).await;
+        });
+    });
+    let service_account_active_clone = service_account_active.clone();
+    let on_leave_service_account = Callback::from(move |_| {
+        service_account_active_clone.set(false);
+    });
+    let token_user_id_clone = token_user_id.clone();
+    let on_leave_token = Callback::from(move |_| {
+        token_user_id_clone.set(None);
+    });
     html! {
         <div class="relative width-fill height-fill" style="overflow:hidden;">
             <div class="width-fill height-fill border-box" style="padding:0.25em;">
@@ -89,12 +130,13 @@ pub fn UserList() -> Html {
                         <div class="align-right" style="padding-bottom: 0.25em;">
                             <ButtonGroup>
                                 <Button onclick={on_open_invite}>{"邀请"}</Button>
+                                <Button onclick={on_open_service_account}>{"新增服务账号"}</Button>
                                 <Button onclick={on_query}>{"查询"}</Button>
                             </ButtonGroup>
                         </div>
                     </header>
                     <div style="-webkit-box-flex: 1;flex-basis: auto;flex-grow: 1;overflow-y: auto;">
-                        { table_view(&list, &list_load_status, &detail_active, &active_detail_id) }
+                        { table_view(&list, &list_load_status, &pagination, &detail_active, &active_detail_id, &token_user_id) }
                         { list_exception_view(list.is_empty(), list_load_status.deref().clone()) }
                     </div>
                     <div style="-webkit-box-flex: 0;flex-basis: auto;flex-grow: 0;padding-top: 0.25em;">
@@ -115,6 +157,21 @@ pub fn UserList() -> Html {
             <Drawer active={*invite_active} onclickother={on_leave_invite}>
                 <InviteEdit onsave={on_finish_invite} />
             </Drawer>
+            <Drawer active={*service_account_active} onclickother={on_leave_service_account}>
+                if *service_account_active {
+                    <ServiceAccountEdit onsave={on_finish_service_account} />
+                }
+            </Drawer>
+            <Drawer active={token_user_id.is_some()} onclickother={on_leave_token}>
+                {
+                    match token_user_id.as_ref() {
+                        Some(token_user_id) => html! {
+                            <ApiTokenList user_id={Some(*token_user_id)} />
+                        },
+                        None => html! {}
+                    }
+                }
+            </Drawer>
         </div>
     }
 }
@@ -122,8 +179,10 @@ pub fn UserList() -> Html {
 fn table_view(
     list: &UseStateHandle<Vec<User>>,
     list_load_status: &UseStateHandle<LoadStatus>,
+    pagination: &UseStateHandle<Pagination>,
     detail_active: &UseStateHandle<bool>,
     active_detail_id: &UseStateHandle<Option<Id>>,
+    token_user_id: &UseStateHandle<Option<Id>>,
 ) -> Html {
     return html! {
         <table class="e-table width-fill">
@@ -131,6 +190,7 @@ fn table_view(
                 <tr>
                     <th class="e-table-hcell">{"用户来源"}</th>
                     <th class="e-table-hcell">{"名称"}</th>
+                    <th class="e-table-hcell">{"角色"}</th>
                     <th class="e-table-hcell">{"头像"}</th>
                     <th class="e-table-hcell">{"创建时间"}</th>
                     <th class="e-table-hcell">{"更新时间"}</th>
@@ -143,7 +203,7 @@ fn table_view(
                         <tbody>
                             {
                                 for list.iter().map(|item| {
-                                    row_view(item, detail_active, active_detail_id)
+                                    row_view(list, list_load_status, pagination, item, detail_active, active_detail_id, token_user_id)
                                 })
                             }
                         </tbody>
@@ -157,9 +217,13 @@ fn table_view(
 }
 
 fn row_view(
+    list: &UseStateHandle<Vec<User>>,
+    list_load_status: &UseStateHandle<LoadStatus>,
+    pagination: &UseStateHandle<Pagination>,
     user: &User,
     detail_active: &UseStateHandle<bool>,
     active_detail_id: &UseStateHandle<Option<Id>>,
+    token_user_id: &UseStateHandle<Option<Id>>,
 ) -> Html {
     let detail_id = user.id;
     let active_detail_id = active_detail_id.clone();
@@ -168,10 +232,67 @@ fn row_view(
         active_detail_id.set(Some(detail_id));
         detail_active.set(true);
     });
+    let is_service_account = UserSource::ServiceAccount == user.user_source;
+    let user_id = user.id;
+    let token_user_id = token_user_id.clone();
+    let on_open_token = Callback::from(move |_: ()| {
+        token_user_id.set(Some(user_id));
+    });
+    let list_clone = list.clone();
+    let list_load_status_clone = list_load_status.clone();
+    let pagination_clone = pagination.clone();
+    let on_confirm_delete = Callback::from(move |_: ()| {
+        let list = list_clone.clone();
+        let list_load_status = list_load_status_clone.clone();
+        let pagination = pagination_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let ok = utils::confirm(
+                LightString::from("删除之后该服务账号的访问令牌立即失效，确定删除该服务账号？"),
+                None,
+            )
+            .await;
+            if ok {
+                delete_service_account(user_id, &list, &list_load_status, &pagination)
+                    .await
+                    .ok();
+            }
+        });
+    });
+    let list = list.clone();
+    let list_load_status = list_load_status.clone();
+    let pagination = pagination.clone();
+    let on_change_role = Callback::from(move |role: Option<(Role, String)>| {
+        let list = list.clone();
+        let list_load_status = list_load_status.clone();
+        let pagination = pagination.clone();
+        if let Some((role, role_name)) = role {
+            wasm_bindgen_futures::spawn_local(async move {
+                let ok = utils::confirm(
+                    LightString::from(format!("确定把该用户的角色修改为{}？", role_name)),
+                    None,
+                )
+                .await;
+                //取消或者修改失败都需要重新加载，恢复下拉框的选中值
+                if ok {
+                    update_user_role(user_id, role).await.ok();
+                }
+                query_user_list(
+                    pagination.page_no.max(1),
+                    &list,
+                    &list_load_status,
+                    &pagination,
+                )
+                .await;
+            });
+        }
+    });
     return html! {
         <tr class="e-table-row">
             <td class="e-table-cell align-center">{&user.user_source.to_string()}</td>
             <td class="e-table-cell align-center">{&user.name}</td>
+            <td class="e-table-cell align-center">
+                <RoleSelection value={Some(user.role)} options={if is_service_account { get_role_list().into_iter().filter(|(role, _)| Role::Owner != *role).collect() } else { get_role_list() }} onchange={on_change_role}/>
+            </td>
             <td class="e-table-cell align-center">
                 if let Some(avatar_url) = user.avatar_url.as_ref() {
                     <Image src={LightString::from(avatar_url.clone())} style="max-height: 3em;"/>
@@ -182,6 +303,10 @@ fn row_view(
             <td class="e-table-cell align-center">
                 <ButtonGroup>
                     <Button onclick={on_open_detail}>{"详情"}</Button>
+                    if is_service_account {
+                        <Button onclick={on_open_token}>{"令牌"}</Button>
+                        <Button onclick={on_confirm_delete}>{"删除"}</Button>
+                    }
                 </ButtonGroup>
             </td>
         </tr>
@@ -240,3 +365,32 @@ async fn query_user_list(
         }
     }
 }
+
+async fn update_user_role(user_id: Id, role: Role) -> Result<(), LightString> {
+    let params = UpdateUserRoleReq {
+        user_id: user_id,
+        role: role,
+    };
+    UpdateUserRoleApi.call(&params).await?;
+    utils::success(LightString::from("修改成功"));
+    return Ok(());
+}
+
+async fn delete_service_account(
+    id: Id,
+    list: &UseStateHandle<Vec<User>>,
+    list_load_status: &UseStateHandle<LoadStatus>,
+    pagination: &UseStateHandle<Pagination>,
+) -> Result<(), LightString> {
+    let params = DeleteServiceAccountReq { id: id };
+    DeleteServiceAccountApi.call(&params).await?;
+    utils::success(LightString::from("删除成功"));
+    query_user_list(
+        pagination.page_no.max(1),
+        list,
+        list_load_status,
+        pagination,
+    )
+    .await;
+    return Ok(());
+}
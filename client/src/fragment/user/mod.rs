@@ -1,3 +1,13 @@
 pub mod detail;
 pub mod invite;
 pub mod list;
+pub mod service_account;
+use crate::sdk;
+use sdk::user::enums::Role;
+
+fn get_role_list() -> Vec<(Role, String)> {
+    return [Role::Owner, Role::Admin, Role::Operator, Role::Viewer]
+        .iter()
+        .map(|value| (*value, value.to_string()))
+        .collect();
+}
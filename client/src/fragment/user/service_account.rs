@@ -0,0 +1,151 @@
+use crate::components::button::Button;
+use crate::components::input::BindingInput;
+use crate::components::radio_group::BindingRadioGroup;
+use crate::components::required::Required;
+use crate::components::validate_wrapper::ValidateData;
+use crate::sdk;
+use crate::utils;
+use crate::utils::request::ApiExt;
+use crate::utils::validator::RequiredValidator;
+use crate::utils::validator::Validators;
+use crate::LightString;
+use sdk::user::enums::Role;
+use sdk::user::insert_service_account::InsertServiceAccountApi;
+use sdk::user::insert_service_account::InsertServiceAccountReq;
+use tihu::PrimaryKey;
+use yew::prelude::*;
+
+type RoleRadioGroup = BindingRadioGroup<(Role, String)>;
+
+#[derive(Clone)]
+struct EditForm {
+    name: ValidateData<LightString>,
+    role: UseStateHandle<Role>,
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct Props {
+    #[prop_or_default]
+    pub onsave: Option<Callback<PrimaryKey>>,
+}
+
+/**
+ * 新增服务账号，服务账号不能登录，只能通过访问令牌调用api
+ */
+#[function_component]
+pub fn ServiceAccountEdit(props: &Props) -> Html {
+    let is_saving: UseStateHandle<bool> = use_state(|| false);
+    let err_msg: UseStateHandle<Option<LightString>> = use_state(|| None);
+    let edit_form = EditForm {
+        name: ValidateData::new(
+            Default::default(),
+            Some(Validators::new().add(RequiredValidator::new("请输入服务账号名称"))),
+        ),
+        role: use_state(|| Role::Operator),
+    };
+    let edit_form_clone = edit_form.clone();
+    let is_saving_clone = is_saving.clone();
+    let err_msg_clone = err_msg.clone();
+    let onsave_clone = props.onsave.clone();
+    let on_save = Callback::from(move |_| {
+        let edit_form = edit_form_clone.clone();
+        let is_saving = is_saving_clone.clone();
+        let err_msg = err_msg_clone.clone();
+        let onsave = onsave_clone.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            save_service_account(&edit_form, is_saving, &err_msg, &onsave)
+                .await
+                .ok();
+        });
+    });
+    html! {
+        <div class="width-fill height-fill border-box" style="padding:0.25em;">
+            <table class="width-fill" style="border-collapse:collapse;table-layout: fixed;">
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;"><Required/>{"名称："}</td>
+                    <td>
+                        {
+                            edit_form.name.view(move |name: UseStateHandle<LightString>, validator| {
+                                html! {
+                                    <BindingInput value={name} onupdate={validator}/>
+                                }
+                            })
+                        }
+                    </td>
+                </tr>
+                <tr>
+                    <td class="align-right" style="width:8em;vertical-align: top;"><Required/>{"角色："}</td>
+                    <td>
+                        <RoleRadioGroup value={edit_form.role.clone()} options={get_service_account_role_list()} />
+                    </td>
+                </tr>
+                <tr>
+                    <td></td>
+                    <td>
+                        <Button disabled={*is_saving} onclick={on_save}>{"保存"}</Button>
+                        {
+                            match err_msg.as_ref() {
+                                Some(err_msg) => {
+                                    html!{
+                                        <span class="middle" style="color:red;margin-left: 0.5em;">{err_msg}</span>
+                                    }
+                                },
+                                None => html!{}
+                            }
+                        }
+                    </td>
+                </tr>
+            </table>
+        </div>
+    }
+}
+
+//服务账号不能是所有者
+fn get_service_account_role_list() -> Vec<(Role, String)> {
+    return [Role::Admin, Role::Operator, Role::Viewer]
+        .iter()
+        .map(|value| (*value, value.to_string()))
+        .collect();
+}
+
+fn chk_form_err(edit_form: &EditForm) -> Vec<LightString> {
+    let mut err_msgs: Vec<LightString> = Vec::new();
+    if let Err(error) = edit_form.name.validate(true) {
+        err_msgs.push(error);
+    }
+    return err_msgs;
+}
+
+async fn save_service_account(
+    edit_form: &EditForm,
+    is_saving: UseStateHandle<bool>,
+    err_msg: &UseStateHandle<Option<LightString>>,
+    onsave: &Option<Callback<PrimaryKey>>,
+) -> Result<(), LightString> {
+    let err_msgs = chk_form_err(edit_form);
+    if let Some(first) = err_msgs.first() {
+        err_msg.set(Some(first.clone()));
+        return Err(first.clone());
+    }
+    let params = InsertServiceAccountReq {
+        name: edit_form.name.get().to_string(),
+        role: *edit_form.role,
+    };
+    let ret = InsertServiceAccountApi
+        .lock_handler(is_saving)
+        .call(&params)
+        .await;
+    match ret {
+        Err(err) => {
+            log::error!("{}", err);
+            err_msg.set(Some(err));
+        }
+        Ok(pri_key) => {
+            if let Some(onsave) = onsave {
+                onsave.emit(pri_key);
+            }
+            utils::success(LightString::from("保存成功"));
+        }
+    }
+    return Ok(());
+}
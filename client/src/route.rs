@@ -27,8 +27,16 @@ pub enum Route {
     JobRecordListByEnvironment { environment_id: Id },
     #[at("/jobRecord/:id")]
     JobRecord { id: Id },
+    #[at("/jobScheduleList")]
+    JobScheduleList,
     #[at("/userList")]
     UserList,
+    #[at("/runnerList")]
+    RunnerList,
+    #[at("/apiTokenList")]
+    ApiTokenList,
+    #[at("/retentionPolicy")]
+    RetentionPolicy,
 }
 
 pub fn is_white_list_route(route: &Route) -> bool {
@@ -193,3 +193,70 @@ where
         }
     }
 }
+
+pub struct PositiveI32Validator {
+    message: LightString,
+    equal: bool,
+}
+
+impl PositiveI32Validator {
+    pub fn new(message: impl Into<LightString>, equal: bool) -> Self {
+        Self {
+            message: message.into(),
+            equal: equal,
+        }
+    }
+}
+
+impl<T> Validator<T> for PositiveI32Validator
+where
+    T: AsRef<str>,
+{
+    fn validate(&self, data: &T) -> Option<LightString> {
+        match i32::from_str(data.as_ref()) {
+            Ok(value) => {
+                if 0 <= value {
+                    if self.equal {
+                        None
+                    } else {
+                        if 0 < value {
+                            None
+                        } else {
+                            Some(self.message.clone())
+                        }
+                    }
+                } else {
+                    Some(self.message.clone())
+                }
+            }
+            Err(_) => Some(self.message.clone()),
+        }
+    }
+}
+
+//内容为空时不校验，用于非必填项
+pub struct OptionalValidator<V> {
+    validator: V,
+}
+
+impl<V> OptionalValidator<V> {
+    pub fn new(validator: V) -> Self {
+        Self {
+            validator: validator,
+        }
+    }
+}
+
+impl<T, V> Validator<T> for OptionalValidator<V>
+where
+    T: AsRef<str>,
+    V: Validator<T>,
+{
+    fn validate(&self, data: &T) -> Option<LightString> {
+        if data.as_ref().is_empty() {
+            None
+        } else {
+            self.validator.validate(data)
+        }
+    }
+}
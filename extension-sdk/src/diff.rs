@@ -0,0 +1,45 @@
+use serde_json::Value;
+
+//比较修改前后的json，每处差异一行，如"~ $.a.b: 1 => 2"，"+"表示新增，"-"表示删除，没有差异时返回空文本
+pub fn diff_json(old: &Value, new: &Value) -> String {
+    let mut lines = Vec::new();
+    diff_value("$", old, new, &mut lines);
+    return lines.join("\n");
+}
+
+fn diff_value(path: &str, old: &Value, new: &Value, lines: &mut Vec<String>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                let path = format!("{}.{}", path, key);
+                match new.get(key) {
+                    Some(new_value) => diff_value(&path, old_value, new_value, lines),
+                    None => lines.push(format!("- {}: {}", path, old_value)),
+                }
+            }
+            for (key, new_value) in new {
+                if !old.contains_key(key) {
+                    lines.push(format!("+ {}.{}: {}", path, key, new_value));
+                }
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            for index in 0..old.len().max(new.len()) {
+                let path = format!("{}[{}]", path, index);
+                match (old.get(index), new.get(index)) {
+                    (Some(old_value), Some(new_value)) => {
+                        diff_value(&path, old_value, new_value, lines)
+                    }
+                    (Some(old_value), None) => lines.push(format!("- {}: {}", path, old_value)),
+                    (None, Some(new_value)) => lines.push(format!("+ {}: {}", path, new_value)),
+                    (None, None) => (),
+                }
+            }
+        }
+        _ => {
+            if old != new {
+                lines.push(format!("~ {}: {} => {}", path, old, new));
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 pub use async_trait;
+pub mod diff;
 pub mod pluginator;
 use serde::{Deserialize, Serialize};
 pub use serde_json;
@@ -80,6 +81,14 @@ pub trait ContextTrait {
         new_value: Value,
     ) -> Result<Value, String>;
     async fn download_file(&self, key: &str) -> Result<std::fs::File, String>;
+    //当前执行是否已被取消
+    fn is_cancelled(&self) -> bool {
+        return false;
+    }
+    //等待当前执行被取消，返回true表示执行被取消，返回false表示执行已经结束，不会再被取消
+    async fn cancelled(&self) -> bool {
+        return false;
+    }
 }
 
 pub type Context = Arc<dyn ContextTrait + Send + Sync>;
@@ -111,7 +120,7 @@ pub trait Extension: Sync + Send + 'static {
     ) -> Result<(), String>;
     //该扩展可以执行哪些操作
     fn operations(&self) -> Vec<Operation>;
-    //执行对应的操作
+    //执行对应的操作，返回操作的输出，后续步骤可以通过{{steps.步骤名称.outputs.字段}}引用，没有输出时返回Value::Null
     async fn handle(
         &self,
         configuration: Value,
@@ -120,7 +129,18 @@ pub trait Extension: Sync + Send + 'static {
         context: &Context,
         append_log: &AppendLog,
         resource_index: u32,
-    ) -> Result<(), String>;
+    ) -> Result<Value, String>;
+    //预览操作会做哪些修改，不会真正修改，返回修改内容的描述，不支持预览的操作返回None
+    async fn plan(
+        &self,
+        _configuration: Value,
+        _operation_id: &str,
+        _operation_parameter: Value,
+        _context: &Context,
+        _resource_index: u32,
+    ) -> Result<Option<String>, String> {
+        return Ok(None);
+    }
 }
 
 plugin_trait!(Extension);
@@ -149,7 +149,7 @@ impl<T: Extension> Extension for ExtensionWrapper<T> {
         context: &Context,
         append_log: &AppendLog,
         resource_index: u32,
-    ) -> Result<(), String> {
+    ) -> Result<Value, String> {
         self.inner
             .handle(
                 configuration,
@@ -161,4 +161,22 @@ impl<T: Extension> Extension for ExtensionWrapper<T> {
             )
             .await
     }
+    async fn plan(
+        &self,
+        configuration: Value,
+        operation_id: &str,
+        operation_parameter: Value,
+        context: &Context,
+        resource_index: u32,
+    ) -> Result<Option<String>, String> {
+        self.inner
+            .plan(
+                configuration,
+                operation_id,
+                operation_parameter,
+                context,
+                resource_index,
+            )
+            .await
+    }
 }
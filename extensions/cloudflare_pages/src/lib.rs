@@ -61,7 +61,7 @@ impl Extension for CloudflarePagesExtension {
         context: &Context,
         append_log: &AppendLog,
         _resource_index: u32,
-    ) -> Result<(), String> {
+    ) -> Result<Value, String> {
         if "deploy" == operation_id {
             return deploy::handle(configuration, operation_parameter, context, append_log).await;
         }
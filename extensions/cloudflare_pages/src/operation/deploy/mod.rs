@@ -6,6 +6,7 @@ use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
 use headers::{Authorization, HeaderMapExt};
 use mould_extension_sdk::serde_json;
+use mould_extension_sdk::serde_json::json;
 use mould_extension_sdk::serde_json::Value;
 use mould_extension_sdk::AppendLog;
 use mould_extension_sdk::Context;
@@ -26,7 +27,7 @@ pub async fn handle(
     parameter: Value,
     context: &Context,
     append_log: &AppendLog,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     let configuration = Config::try_form_value(configuration)?;
     append_log(LogLevel::Info, String::from("正在解析参数"));
     let parameter = Parameter::try_form_value(parameter)?;
@@ -34,16 +35,16 @@ pub async fn handle(
     let file = context.download_file(&parameter.file.key).await?;
     append_log(LogLevel::Info, String::from("获取文件完成，开始部署"));
     let result = await_future(try_handle(file, configuration, append_log.clone())).await?;
-    result?;
+    let deployment = result?;
     append_log(LogLevel::Info, String::from("部署成功!"));
-    return Ok(());
+    return Ok(deployment);
 }
 
 async fn try_handle(
     package: std::fs::File,
     configuration: Config,
     append_log: AppendLog,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     let files = parse_package(package)?;
     append_log(LogLevel::Info, String::from("开始获取上传token"));
     let token = fetch_upload_token(&configuration).await?;
@@ -52,8 +53,21 @@ async fn try_handle(
     append_log(LogLevel::Info, String::from("开始更新文件哈希"));
     upsert_hashes(&token, &files).await?;
     append_log(LogLevel::Info, String::from("开始提交部署"));
-    deploy(&configuration, &files).await?;
-    return Ok(());
+    let deployment = deploy(&configuration, &files).await?;
+    //部署接口返回的部署id和预览地址
+    let field = |name: &str| {
+        deployment
+            .as_ref()
+            .and_then(|deployment| deployment.get(name))
+            .cloned()
+            .unwrap_or(Value::Null)
+    };
+    return Ok(json!({
+        "project_name": configuration.project_name,
+        "deployment_id": field("id"),
+        "url": field("url"),
+        "file_count": files.len(),
+    }));
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -265,7 +279,7 @@ async fn upsert_hashes(token: &str, files: &[FileInfo]) -> Result<(), String> {
     }
 }
 
-async fn deploy(configuration: &Config, files: &[FileInfo]) -> Result<(), String> {
+async fn deploy(configuration: &Config, files: &[FileInfo]) -> Result<Option<Value>, String> {
     let mut form = reqwest::multipart::Form::new();
     let manifest: HashMap<String, String> = files
         .iter()
@@ -298,7 +312,7 @@ async fn deploy(configuration: &Config, files: &[FileInfo]) -> Result<(), String
     let resp: ApiResult<Option<serde_json::Value>> = serde_json::from_str(&resp_text)
         .map_err(|_err| format!("部署接口响应格式不正确: {resp_text}"))?;
     if resp.success {
-        return Ok(());
+        return Ok(resp.result);
     } else {
         return Err(format!("部署失败, 接口响应：{}", resp_text));
     }
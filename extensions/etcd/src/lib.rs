@@ -82,14 +82,15 @@ impl Extension for EtcdExtension {
         context: &Context,
         append_log: &AppendLog,
         resource_index: u32,
-    ) -> Result<(), String> {
+    ) -> Result<Value, String> {
         if "put" == operation_id {
             return operation::await_future(put::handle(
                 configuration,
                 operation_parameter,
                 append_log.clone(),
             ))
-            .await?;
+            .await?
+            .map(|_| Value::Null);
         } else if "modify_json" == operation_id {
             return operation::await_future(modify_json::handle(
                 configuration,
@@ -97,7 +98,8 @@ impl Extension for EtcdExtension {
                 context.clone(),
                 append_log.clone(),
             ))
-            .await?;
+            .await?
+            .map(|_| Value::Null);
         } else if "modify_json_custom" == operation_id {
             return operation::await_future(modify_json_custom::handle(
                 configuration,
@@ -106,11 +108,41 @@ impl Extension for EtcdExtension {
                 append_log.clone(),
                 resource_index,
             ))
-            .await?;
+            .await?
+            .map(|_| Value::Null);
         } else {
             return Err(String::from("没有此操作"));
         }
     }
+    async fn plan(
+        &self,
+        configuration: Value,
+        operation_id: &str,
+        operation_parameter: Value,
+        context: &Context,
+        resource_index: u32,
+    ) -> Result<Option<String>, String> {
+        if "modify_json" == operation_id {
+            return operation::await_future(modify_json::plan(
+                configuration,
+                operation_parameter,
+                context.clone(),
+            ))
+            .await?
+            .map(Some);
+        } else if "modify_json_custom" == operation_id {
+            return operation::await_future(modify_json_custom::plan(
+                configuration,
+                operation_parameter,
+                context.clone(),
+                resource_index,
+            ))
+            .await?
+            .map(Some);
+        } else {
+            return Ok(None);
+        }
+    }
 }
 
 mould_extension_sdk::plugin_implementation!(Extension, EtcdExtension);
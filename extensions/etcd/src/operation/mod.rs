@@ -6,6 +6,7 @@ use crate::config::Config;
 use etcd_rs::Client;
 use etcd_rs::ClientConfig;
 use futures::channel::oneshot;
+use mould_extension_sdk::diff::diff_json;
 use mould_extension_sdk::serde_json::Value;
 use std::fmt::Debug;
 use std::future::Future;
@@ -39,3 +40,21 @@ pub async fn await_future<O: Send + Debug + 'static>(
     });
     receiver.await.map_err(|err| err.to_string())
 }
+
+//把每个key修改前后的json整理成预览的修改内容
+pub fn describe_json_changes(modified_list: Vec<(Vec<u8>, Value, Value)>) -> String {
+    if modified_list.is_empty() {
+        return String::from("没有找到key对应的配置");
+    }
+    let mut list = Vec::with_capacity(modified_list.len());
+    for (key, root_value, new_root_value) in modified_list {
+        let diff = diff_json(&root_value, &new_root_value);
+        let diff = if diff.is_empty() {
+            String::from("没有变化")
+        } else {
+            diff
+        };
+        list.push(format!("key：{}\n{}", String::from_utf8_lossy(&key), diff));
+    }
+    return list.join("\n\n");
+}
@@ -1,5 +1,7 @@
 pub mod parameter;
+use super::describe_json_changes;
 use super::get_client;
+use etcd_rs::Client;
 use etcd_rs::KeyValueOp;
 use etcd_rs::PutRequest;
 use json5;
@@ -21,7 +23,39 @@ pub async fn handle(
     append_log(LogLevel::Info, String::from("正在连接etcd服务器"));
     let client = get_client(configuration).await?;
     append_log(LogLevel::Info, String::from("连接etcd服务器成功"));
-    append_log(LogLevel::Info, String::from("正在获取配置"));
+    append_log(LogLevel::Info, String::from("正在获取并修改配置"));
+    let modified_list = modify_values(&client, &parameter, &context).await?;
+    let key_prefix = format!("key：{}, ", parameter.key);
+    for (key, _, new_root_value) in modified_list {
+        let new_root_value = serde_json::to_vec_pretty(&new_root_value)
+            .map_err(|err| format!("{key_prefix}序列化json数据失败: {err}"))?;
+        let req = PutRequest::new(key, new_root_value);
+        client
+            .put(req)
+            .await
+            .map_err(|err| format!("{key_prefix}写入json配置失败: {err}"))?;
+    }
+    append_log(LogLevel::Info, String::from("修改成功!"));
+    return Ok(());
+}
+
+pub async fn plan(
+    configuration: Value,
+    parameter: Value,
+    context: Context,
+) -> Result<String, String> {
+    let parameter = Parameter::try_form_value(parameter)?;
+    let client = get_client(configuration).await?;
+    let modified_list = modify_values(&client, &parameter, &context).await?;
+    return Ok(describe_json_changes(modified_list));
+}
+
+//获取key对应的json配置并在内存中修改，返回每个key以及修改前后的json
+async fn modify_values(
+    client: &Client,
+    parameter: &Parameter,
+    context: &Context,
+) -> Result<Vec<(Vec<u8>, Value, Value)>, String> {
     let get_resp = client
         .get(parameter.key.as_str())
         .await
@@ -30,10 +64,10 @@ pub async fn handle(
         json5::from_str(&parameter.value)
             .map_err(|err| format!("解析json值失败: {}, 值：{}", err, parameter.value))?
     } else {
-        Value::String(parameter.value)
+        Value::String(parameter.value.clone())
     };
     let key_prefix = format!("key：{}, ", parameter.key);
-    append_log(LogLevel::Info, String::from("正在修改配置"));
+    let mut modified_list = Vec::with_capacity(get_resp.kvs.len());
     for kv in get_resp.kvs {
         let key = kv.key;
         let value = kv.value;
@@ -42,15 +76,8 @@ pub async fn handle(
         let root_value: Value = json5::from_str(&value)
             .map_err(|err| format!("{key_prefix}解析json配置失败: {err}"))?;
         let new_root_value =
-            context.modify_json(root_value, &parameter.json_path, new_value.clone())?;
-        let new_root_value = serde_json::to_vec_pretty(&new_root_value)
-            .map_err(|err| format!("{key_prefix}序列化json数据失败: {err}"))?;
-        let req = PutRequest::new(key, new_root_value);
-        client
-            .put(req)
-            .await
-            .map_err(|err| format!("{key_prefix}写入json配置失败: {err}"))?;
+            context.modify_json(root_value.clone(), &parameter.json_path, new_value.clone())?;
+        modified_list.push((key, root_value, new_root_value));
     }
-    append_log(LogLevel::Info, String::from("修改成功!"));
-    return Ok(());
+    return Ok(modified_list);
 }
@@ -104,13 +104,14 @@ impl Extension for KubernetesExtension {
         context: &Context,
         append_log: &AppendLog,
         resource_index: u32,
-    ) -> Result<(), String> {
+    ) -> Result<Value, String> {
         if "set_image" == operation_id {
             return set_image::handle(configuration, operation_parameter, context, append_log)
                 .await;
         } else if "put_config_map" == operation_id {
             return put_config_map::handle(configuration, operation_parameter, context, append_log)
-                .await;
+                .await
+                .map(|_| Value::Null);
         } else if "modify_config_map_json" == operation_id {
             return modify_config_map_json::handle(
                 configuration,
@@ -118,7 +119,8 @@ impl Extension for KubernetesExtension {
                 context,
                 append_log,
             )
-            .await;
+            .await
+            .map(|_| Value::Null);
         } else if "modify_config_map_json_custom" == operation_id {
             return modify_config_map_json_custom::handle(
                 configuration,
@@ -127,7 +129,8 @@ impl Extension for KubernetesExtension {
                 append_log,
                 resource_index,
             )
-            .await;
+            .await
+            .map(|_| Value::Null);
         } else if "restart_deployment" == operation_id {
             return restart_deployment::handle(
                 configuration,
@@ -135,10 +138,39 @@ impl Extension for KubernetesExtension {
                 context,
                 append_log,
             )
-            .await;
+            .await
+            .map(|_| Value::Null);
         }
         return Err(String::from("没有此操作"));
     }
+    async fn plan(
+        &self,
+        configuration: Value,
+        operation_id: &str,
+        operation_parameter: Value,
+        context: &Context,
+        resource_index: u32,
+    ) -> Result<Option<String>, String> {
+        if "set_image" == operation_id {
+            return set_image::plan(configuration, operation_parameter, context)
+                .await
+                .map(Some);
+        } else if "modify_config_map_json" == operation_id {
+            return modify_config_map_json::plan(configuration, operation_parameter, context)
+                .await
+                .map(Some);
+        } else if "modify_config_map_json_custom" == operation_id {
+            return modify_config_map_json_custom::plan(
+                configuration,
+                operation_parameter,
+                context,
+                resource_index,
+            )
+            .await
+            .map(Some);
+        }
+        return Ok(None);
+    }
 }
 
 mould_extension_sdk::plugin_implementation!(Extension, KubernetesExtension);
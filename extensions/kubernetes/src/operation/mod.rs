@@ -4,6 +4,8 @@ pub mod put_config_map;
 pub mod restart_deployment;
 use crate::config::Config;
 use futures::channel::oneshot;
+use mould_extension_sdk::diff::diff_json;
+use mould_extension_sdk::serde_json::Value;
 use std::fmt::Debug;
 use std::future::Future;
 pub mod set_image;
@@ -50,3 +52,12 @@ pub async fn await_future<O: Send + Debug + 'static>(
     });
     receiver.await.map_err(|err| err.to_string())
 }
+
+//把修改前后的json整理成预览的修改内容
+pub fn describe_json_change(root_value: &Value, new_root_value: &Value) -> String {
+    let diff = diff_json(root_value, new_root_value);
+    if diff.is_empty() {
+        return String::from("没有变化");
+    }
+    return diff;
+}
@@ -1,5 +1,6 @@
 pub mod parameter;
 use super::await_future;
+use super::describe_json_change;
 use super::get_client;
 use crate::config::Config;
 use json5;
@@ -27,74 +28,111 @@ pub async fn handle(
     return result;
 }
 
+pub async fn plan(
+    configuration: Value,
+    parameter: Value,
+    context: &Context,
+) -> Result<String, String> {
+    let configuration = Config::try_form_value(configuration)?;
+    let parameter = Parameter::try_form_value(parameter)?;
+    let context = context.clone();
+    let result = await_future(try_plan(configuration, parameter, context)).await?;
+    return result;
+}
+
 async fn try_handle(
     configuration: Config,
     parameter: Parameter,
     context: Context,
     append_log: AppendLog,
 ) -> Result<(), String> {
+    append_log(LogLevel::Info, String::from("正在构造kubernetes客户端"));
+    let client = get_client(&configuration).await?;
+    let config_maps: Api<ConfigMap> = Api::namespaced(client, &configuration.namespace);
+    append_log(LogLevel::Info, String::from("正在获取并修改ConfigMap"));
+    match modify_config_map(&config_maps, &parameter, &context).await? {
+        Some((config_map, _, _)) => {
+            append_log(LogLevel::Info, String::from("正在提交新配置"));
+            config_maps
+                .replace(
+                    &parameter.config_map_name,
+                    &PostParams::default(),
+                    &config_map,
+                )
+                .await
+                .map_err(|err| format!("更新ConfigMap失败: {err}"))?;
+            append_log(LogLevel::Info, String::from("修改ConfigMap成功!"));
+        }
+        None => {
+            append_log(LogLevel::Warn, String::from("没有找到key对应的数据"));
+        }
+    }
+    return Ok(());
+}
+
+async fn try_plan(
+    configuration: Config,
+    parameter: Parameter,
+    context: Context,
+) -> Result<String, String> {
+    let client = get_client(&configuration).await?;
+    let config_maps: Api<ConfigMap> = Api::namespaced(client, &configuration.namespace);
+    match modify_config_map(&config_maps, &parameter, &context).await? {
+        Some((config_map, root_value, new_root_value)) => {
+            //服务端试运行，确认修改后的ConfigMap可以提交成功
+            let post_params = PostParams {
+                dry_run: true,
+                ..PostParams::default()
+            };
+            config_maps
+                .replace(&parameter.config_map_name, &post_params, &config_map)
+                .await
+                .map_err(|err| format!("试运行更新ConfigMap失败: {err}"))?;
+            return Ok(describe_json_change(&root_value, &new_root_value));
+        }
+        None => return Ok(String::from("没有找到key对应的数据")),
+    }
+}
+
+//获取ConfigMap并在内存中修改key对应的json配置，返回修改后的ConfigMap以及修改前后的json，没有找到key时返回None
+async fn modify_config_map(
+    config_maps: &Api<ConfigMap>,
+    parameter: &Parameter,
+    context: &Context,
+) -> Result<Option<(ConfigMap, Value, Value)>, String> {
     let new_value = if parameter.json_value {
         json5::from_str(&parameter.value)
             .map_err(|err| format!("解析json值失败: {}, 值：{}", err, parameter.value))?
     } else {
-        Value::String(parameter.value)
+        Value::String(parameter.value.clone())
     };
-    append_log(LogLevel::Info, String::from("正在构造kubernetes客户端"));
-    let client = get_client(&configuration).await?;
-    let config_maps: Api<ConfigMap> = Api::namespaced(client, &configuration.namespace);
-    append_log(LogLevel::Info, String::from("正在获取ConfigMap"));
     let mut config_map = config_maps
         .get(&parameter.config_map_name)
         .await
         .map_err(|err| format!("获取ConfigMap失败: {err}"))?;
-    let mut found = false;
     if let Some(data) = config_map.data.as_mut() {
         if let Some(content) = data.get_mut(&parameter.key) {
-            append_log(LogLevel::Info, String::from("正在解析配置"));
             let root_value: Value =
                 json5::from_str(content).map_err(|err| format!("解析json配置文件失败: {err}"))?;
-            append_log(LogLevel::Info, String::from("正在修改配置"));
             let new_root_value =
-                context.modify_json(root_value, &parameter.json_path, new_value.clone())?;
-            append_log(LogLevel::Info, String::from("正在生成新配置"));
-            let new_root_value = serde_json::to_string_pretty(&new_root_value)
+                context.modify_json(root_value.clone(), &parameter.json_path, new_value.clone())?;
+            *content = serde_json::to_string_pretty(&new_root_value)
                 .map_err(|err| format!("序列化json配置文件失败: {err}"))?;
-            *content = new_root_value;
-            found = true;
+            return Ok(Some((config_map, root_value, new_root_value)));
         }
     }
-    if !found {
-        if let Some(binary_data) = config_map.binary_data.as_mut() {
-            if let Some(content) = binary_data.get_mut(&parameter.key) {
-                append_log(LogLevel::Info, String::from("正在解析配置"));
-                let root_value = String::from_utf8(content.0.clone())
-                    .map_err(|err| format!("原始配置不是UTF-8格式字符串: {err}"))?;
-                let root_value: Value = json5::from_str(&root_value)
-                    .map_err(|err| format!("解析json配置文件失败: {err}"))?;
-                append_log(LogLevel::Info, String::from("正在修改配置"));
-                let new_root_value =
-                    context.modify_json(root_value, &parameter.json_path, new_value.clone())?;
-                append_log(LogLevel::Info, String::from("正在生成新配置"));
-                let new_root_value = serde_json::to_vec_pretty(&new_root_value)
-                    .map_err(|err| format!("序列化json配置文件失败: {err}"))?;
-                content.0 = new_root_value;
-                found = true;
-            }
+    if let Some(binary_data) = config_map.binary_data.as_mut() {
+        if let Some(content) = binary_data.get_mut(&parameter.key) {
+            let root_value = String::from_utf8(content.0.clone())
+                .map_err(|err| format!("原始配置不是UTF-8格式字符串: {err}"))?;
+            let root_value: Value = json5::from_str(&root_value)
+                .map_err(|err| format!("解析json配置文件失败: {err}"))?;
+            let new_root_value =
+                context.modify_json(root_value.clone(), &parameter.json_path, new_value.clone())?;
+            content.0 = serde_json::to_vec_pretty(&new_root_value)
+                .map_err(|err| format!("序列化json配置文件失败: {err}"))?;
+            return Ok(Some((config_map, root_value, new_root_value)));
         }
     }
-    if found {
-        append_log(LogLevel::Info, String::from("正在提交新配置"));
-        config_maps
-            .replace(
-                &parameter.config_map_name,
-                &PostParams::default(),
-                &config_map,
-            )
-            .await
-            .map_err(|err| format!("更新ConfigMap失败: {err}"))?;
-        append_log(LogLevel::Info, String::from("修改ConfigMap成功!"));
-    } else {
-        append_log(LogLevel::Warn, String::from("没有找到key对应的数据"));
-    }
-    return Ok(());
+    return Ok(None);
 }
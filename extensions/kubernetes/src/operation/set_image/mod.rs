@@ -4,6 +4,7 @@ use super::get_client;
 use crate::config::Config;
 use k8s_openapi::api::apps::v1::Deployment;
 use kube::api::{Api, PostParams};
+use mould_extension_sdk::serde_json::json;
 use mould_extension_sdk::serde_json::Value;
 use mould_extension_sdk::AppendLog;
 use mould_extension_sdk::Context;
@@ -15,7 +16,7 @@ pub async fn handle(
     parameter: Value,
     _context: &Context,
     append_log: &AppendLog,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     let configuration = Config::try_form_value(configuration)?;
     append_log(LogLevel::Info, String::from("正在解析参数"));
     let parameter = Parameter::try_form_value(parameter)?;
@@ -23,11 +24,22 @@ pub async fn handle(
     return result;
 }
 
+pub async fn plan(
+    configuration: Value,
+    parameter: Value,
+    _context: &Context,
+) -> Result<String, String> {
+    let configuration = Config::try_form_value(configuration)?;
+    let parameter = Parameter::try_form_value(parameter)?;
+    let result = await_future(try_plan(configuration, parameter)).await?;
+    return result;
+}
+
 async fn try_handle(
     configuration: Config,
     parameter: Parameter,
     append_log: AppendLog,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     append_log(LogLevel::Info, String::from("正在构造kubernetes客户端"));
     let client = get_client(&configuration).await?;
     let deployments: Api<Deployment> = Api::namespaced(client, &configuration.namespace);
@@ -36,23 +48,9 @@ async fn try_handle(
         .get(&parameter.deployment_name)
         .await
         .map_err(|err| format!("获取工作负载失败: {err}"))?;
-    let mut found = false;
-    if let Some(spec) = deployment.spec.as_mut() {
-        if let Some(spec) = spec.template.spec.as_mut() {
-            for container in &mut spec.containers {
-                if parameter.container_name.is_none()
-                    || parameter.container_name.as_deref() == Some(container.name.as_str())
-                {
-                    container.image.replace(parameter.image);
-                    found = true;
-                    break;
-                }
-            }
-        }
-    }
-    if found {
+    if set_container_image(&mut deployment, &parameter).is_some() {
         append_log(LogLevel::Info, String::from("正在修改镜像"));
-        deployments
+        let deployment = deployments
             .replace(
                 &parameter.deployment_name,
                 &PostParams::default(),
@@ -61,8 +59,64 @@ async fn try_handle(
             .await
             .map_err(|err| format!("更新工作负载镜像失败: {err}"))?;
         append_log(LogLevel::Info, String::from("修改镜像成功!"));
+        //修改后的generation可以用来确认滚动更新是否完成
+        return Ok(json!({
+            "image": parameter.image,
+            "generation": deployment.metadata.generation,
+        }));
     } else {
         append_log(LogLevel::Warn, String::from("没有找到工作负载"));
     }
-    return Ok(());
+    return Ok(Value::Null);
+}
+
+async fn try_plan(configuration: Config, parameter: Parameter) -> Result<String, String> {
+    let client = get_client(&configuration).await?;
+    let deployments: Api<Deployment> = Api::namespaced(client, &configuration.namespace);
+    let mut deployment = deployments
+        .get(&parameter.deployment_name)
+        .await
+        .map_err(|err| format!("获取工作负载失败: {err}"))?;
+    match set_container_image(&mut deployment, &parameter) {
+        Some((container_name, old_image)) => {
+            //服务端试运行，由kubernetes校验修改后的工作负载，不会真正修改
+            let post_params = PostParams {
+                dry_run: true,
+                ..PostParams::default()
+            };
+            deployments
+                .replace(&parameter.deployment_name, &post_params, &deployment)
+                .await
+                .map_err(|err| format!("试运行更新工作负载镜像失败: {err}"))?;
+            let old_image = old_image.unwrap_or_default();
+            if old_image == parameter.image {
+                return Ok(format!("容器\"{}\"的镜像没有变化", container_name));
+            }
+            return Ok(format!(
+                "容器\"{}\"的镜像：{} => {}",
+                container_name, old_image, parameter.image
+            ));
+        }
+        None => return Ok(String::from("没有找到工作负载")),
+    }
+}
+
+//修改匹配的容器的镜像，返回容器名称和原来的镜像，没有匹配的容器时返回None
+fn set_container_image(
+    deployment: &mut Deployment,
+    parameter: &Parameter,
+) -> Option<(String, Option<String>)> {
+    if let Some(spec) = deployment.spec.as_mut() {
+        if let Some(spec) = spec.template.spec.as_mut() {
+            for container in &mut spec.containers {
+                if parameter.container_name.is_none()
+                    || parameter.container_name.as_deref() == Some(container.name.as_str())
+                {
+                    let old_image = container.image.replace(parameter.image.clone());
+                    return Some((container.name.clone(), old_image));
+                }
+            }
+        }
+    }
+    return None;
 }
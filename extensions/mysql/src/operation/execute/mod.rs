@@ -2,6 +2,7 @@ pub mod parameter;
 use super::await_future;
 use super::get_conn;
 use crate::config::Config;
+use mould_extension_sdk::serde_json::json;
 use mould_extension_sdk::serde_json::Value;
 use mould_extension_sdk::AppendLog;
 use mould_extension_sdk::Context;
@@ -15,7 +16,7 @@ pub async fn handle(
     parameter: Value,
     context: &Context,
     append_log: &AppendLog,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     let configuration = Config::try_form_value(configuration)?;
     append_log(LogLevel::Info, String::from("正在解析参数"));
     let parameter = Parameter::try_form_value(parameter)?;
@@ -42,7 +43,7 @@ async fn try_handle(
     root_cert: Option<File>,
     sql: String,
     append_log: AppendLog,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     append_log(LogLevel::Info, String::from("正在连接mysql服务器"));
     let mut client = get_conn(configuration, root_cert).await?;
     append_log(LogLevel::Info, String::from("连接mysql服务器成功"));
@@ -52,5 +53,8 @@ async fn try_handle(
         .await
         .map_err(|err| format!("执行sql语句失败: {err}, sql: {sql}"))?;
     append_log(LogLevel::Info, String::from("执行sql成功!"));
-    return Ok(());
+    //执行多条语句时是最后一条语句影响的行数
+    return Ok(json!({
+        "affected_rows": client.affected_rows(),
+    }));
 }
@@ -73,7 +73,7 @@ impl Extension for NacosExtension {
         _context: &Context,
         append_log: &AppendLog,
         _resource_index: u32,
-    ) -> Result<(), String> {
+    ) -> Result<Value, String> {
         if "put" == operation_id {
             return put::handle(configuration, operation_parameter, append_log).await;
         } else if "modify_properties" == operation_id {
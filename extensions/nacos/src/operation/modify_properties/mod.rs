@@ -2,6 +2,7 @@ pub mod parameter;
 use super::get_client;
 use java_properties::PropertiesIter;
 use java_properties::PropertiesWriter;
+use mould_extension_sdk::serde_json::json;
 use mould_extension_sdk::serde_json::Value;
 use mould_extension_sdk::AppendLog;
 use mould_extension_sdk::LogLevel;
@@ -14,7 +15,7 @@ pub async fn handle(
     configuration: Value,
     parameter: Value,
     append_log: &AppendLog,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     append_log(LogLevel::Info, String::from("正在解析参数"));
     let parameter = Parameter::try_form_value(parameter)?;
     append_log(LogLevel::Info, String::from("正在连接nacos服务器"));
@@ -37,7 +38,7 @@ pub async fn handle(
             map.insert(key, value);
         })
         .map_err(|err| format!("{key_prefix}解析properties配置失败: {err}, 内容：{content}"))?;
-    map.insert(parameter.key, parameter.value);
+    map.insert(parameter.key.clone(), parameter.value);
     append_log(LogLevel::Info, String::from("正在重新生成配置"));
     let mut new_content: Vec<u8> = Vec::new();
     let mut writer = PropertiesWriter::new(Cursor::new(&mut new_content));
@@ -65,5 +66,9 @@ pub async fn handle(
         return Err(format!("{key_prefix}设置properties配置失败!"));
     }
     append_log(LogLevel::Info, String::from("修改成功!"));
-    return Ok(());
+    return Ok(json!({
+        "data_id": parameter.data_id,
+        "group": parameter.group,
+        "key": parameter.key,
+    }));
 }
@@ -1,5 +1,6 @@
 pub mod parameter;
 use super::get_client;
+use mould_extension_sdk::serde_json::json;
 use mould_extension_sdk::serde_json::Value;
 use mould_extension_sdk::AppendLog;
 use mould_extension_sdk::LogLevel;
@@ -10,7 +11,7 @@ pub async fn handle(
     configuration: Value,
     parameter: Value,
     append_log: &AppendLog,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     append_log(LogLevel::Info, String::from("正在解析参数"));
     let parameter = Parameter::try_form_value(parameter)?;
     append_log(LogLevel::Info, String::from("正在连接nacos服务器"));
@@ -39,5 +40,8 @@ pub async fn handle(
         return Err(format!("{key_prefix}设置失败!"));
     }
     append_log(LogLevel::Info, String::from("设置成功!"));
-    return Ok(());
+    return Ok(json!({
+        "data_id": parameter.data_id,
+        "group": parameter.group,
+    }));
 }
@@ -61,7 +61,7 @@ impl Extension for PostgresqlExtension {
         context: &Context,
         append_log: &AppendLog,
         _resource_index: u32,
-    ) -> Result<(), String> {
+    ) -> Result<Value, String> {
         if "execute" == operation_id {
             return execute::handle(configuration, operation_parameter, context, append_log).await;
         }
@@ -2,19 +2,21 @@ pub mod parameter;
 use super::await_future;
 use super::get_client;
 use crate::config::Config;
+use mould_extension_sdk::serde_json::json;
 use mould_extension_sdk::serde_json::Value;
 use mould_extension_sdk::AppendLog;
 use mould_extension_sdk::Context;
 use mould_extension_sdk::LogLevel;
 use parameter::Parameter;
 use tokio::fs::File;
+use tokio_postgres::SimpleQueryMessage;
 
 pub async fn handle(
     configuration: Value,
     parameter: Value,
     context: &Context,
     append_log: &AppendLog,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     let configuration = Config::try_form_value(configuration)?;
     append_log(LogLevel::Info, String::from("正在解析参数"));
     let parameter = Parameter::try_form_value(parameter)?;
@@ -41,15 +43,26 @@ async fn try_handle(
     root_cert: Option<File>,
     sql: String,
     append_log: AppendLog,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     append_log(LogLevel::Info, String::from("正在连接postgresql服务器"));
     let client = get_client(configuration, root_cert).await?;
     append_log(LogLevel::Info, String::from("连接postgresql服务器成功"));
     append_log(LogLevel::Info, String::from("正在执行sql"));
-    client
-        .batch_execute(&sql)
+    let messages = client
+        .simple_query(&sql)
         .await
         .map_err(|err| format!("执行sql语句失败: {err}, sql: {sql}"))?;
     append_log(LogLevel::Info, String::from("执行sql成功!"));
-    return Ok(());
+    //执行多条语句时是最后一条语句影响的行数，和mysql扩展保持一致
+    let affected_rows = messages
+        .iter()
+        .rev()
+        .find_map(|message| match message {
+            SimpleQueryMessage::CommandComplete(rows) => Some(*rows),
+            _ => None,
+        })
+        .unwrap_or(0);
+    return Ok(json!({
+        "affected_rows": affected_rows,
+    }));
 }
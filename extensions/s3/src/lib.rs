@@ -63,7 +63,7 @@ impl Extension for S3Extension {
         context: &Context,
         append_log: &AppendLog,
         _resource_index: u32,
-    ) -> Result<(), String> {
+    ) -> Result<Value, String> {
         if "upload_file" == operation_id {
             return upload_file::handle(configuration, operation_parameter, context, append_log)
                 .await;
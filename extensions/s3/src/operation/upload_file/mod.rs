@@ -3,6 +3,7 @@ use super::await_future;
 use super::get_client;
 use aws_sdk_s3::primitives::ByteStream;
 use aws_sdk_s3::Client;
+use mould_extension_sdk::serde_json::json;
 use mould_extension_sdk::serde_json::Value;
 use mould_extension_sdk::AppendLog;
 use mould_extension_sdk::Context;
@@ -15,7 +16,7 @@ pub async fn handle(
     parameter: Value,
     context: &Context,
     append_log: &AppendLog,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     append_log(LogLevel::Info, String::from("正在构造s3客户端"));
     let (client, bucket) = get_client(configuration)?;
     append_log(LogLevel::Info, String::from("正在解析参数"));
@@ -32,16 +33,19 @@ pub async fn handle(
     let result = await_future(try_handle(
         client,
         file,
-        bucket,
+        bucket.clone(),
         content_length as i64,
-        key,
+        key.clone(),
         mime_type,
         append_log.clone(),
     ))
     .await?;
     result?;
     append_log(LogLevel::Info, String::from("上传成功!"));
-    return Ok(());
+    return Ok(json!({
+        "bucket": bucket,
+        "key": key,
+    }));
 }
 
 async fn try_handle(
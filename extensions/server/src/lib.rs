@@ -99,7 +99,7 @@ impl Extension for PostgresqlExtension {
         context: &Context,
         append_log: &AppendLog,
         resource_index: u32,
-    ) -> Result<(), String> {
+    ) -> Result<Value, String> {
         if "execute" == operation_id {
             return execute::handle(configuration, operation_parameter, context, append_log).await;
         } else if "put" == operation_id {
@@ -3,6 +3,10 @@ use super::await_task;
 use super::download_file;
 use super::get_session;
 use super::upload_file;
+use futures::channel::oneshot;
+use futures::future::select;
+use futures::future::Either;
+use mould_extension_sdk::serde_json::json;
 use mould_extension_sdk::serde_json::Value;
 use mould_extension_sdk::AppendLog;
 use mould_extension_sdk::Context;
@@ -17,40 +21,113 @@ pub async fn handle(
     parameter: Value,
     context: &Context,
     append_log: &AppendLog,
-) -> Result<(), String> {
+) -> Result<Value, String> {
+    let task_id = Uuid::new_v4().to_string();
+    let script_path = format!("{}/task-{}.sh", TMP_DIR, task_id);
+    let log_path = format!("{}/task-{}.log", TMP_DIR, task_id);
+    //任务被取消时，终止远程脚本并清理脚本文件和脚本输出日志
+    let watch_context = context.clone();
+    let watch_configuration = configuration.clone();
+    let watch_parameter = parameter.clone();
+    let watch_script_path = script_path.clone();
+    let watch_log_path = log_path.clone();
+    let watch_append_log = append_log.clone();
+    //操作结束时通知监听取消的任务退出
+    let (done_sender, done_receiver) = oneshot::channel::<()>();
+    context.spawn_future(Box::pin(async move {
+        let cancelled = match select(watch_context.cancelled(), done_receiver).await {
+            Either::Left((cancelled, _)) => cancelled,
+            Either::Right(_) => false,
+        };
+        if cancelled {
+            let append_log = watch_append_log.clone();
+            let task_context = watch_context.clone();
+            await_task(&task_context, move || {
+                clean_remote(
+                    watch_configuration,
+                    watch_parameter,
+                    &watch_script_path,
+                    &watch_log_path,
+                    &append_log,
+                )
+            })
+            .await
+            .ok();
+        }
+    }))?;
     let append_log = append_log.clone();
     let result = await_task(&context.clone(), move || {
-        try_handle(configuration, parameter, &append_log)
+        try_handle(
+            configuration,
+            parameter,
+            &script_path,
+            &log_path,
+            &append_log,
+        )
     })
-    .await?;
-    return result;
+    .await;
+    drop(done_sender);
+    return result?;
+}
+
+const TMP_DIR: &str = "/tmp/mould-server-extension";
+
+fn clean_remote(
+    configuration: Value,
+    parameter: Value,
+    script_path: &str,
+    log_path: &str,
+    append_log: &AppendLog,
+) {
+    append_log(
+        LogLevel::Warn,
+        String::from("任务被取消，正在终止脚本并清理文件"),
+    );
+    let result = Parameter::try_form_value(parameter).and_then(|parameter| {
+        let (session, config) = get_session(configuration)?;
+        let (sudo_prefix, sudo_input) = sudo_command(parameter.sudo, &config.password);
+        //脚本可能已经执行结束，终止失败不影响清理
+        exec_remote(
+            &session,
+            &format!("{sudo_prefix}pkill -f {script_path}"),
+            false,
+            sudo_input.as_deref(),
+        )
+        .ok();
+        exec_remote(
+            &session,
+            &format!("{sudo_prefix}rm -rf {} {}", script_path, log_path),
+            false,
+            sudo_input.as_deref(),
+        )
+    });
+    if let Err(err) = result {
+        append_log(
+            LogLevel::Error,
+            format!("清理脚本文件和脚本输出日志失败, {}", err),
+        );
+    }
 }
 
 fn try_handle(
     configuration: Value,
     parameter: Value,
+    script_path: &str,
+    log_path: &str,
     append_log: &AppendLog,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     append_log(LogLevel::Info, String::from("正在解析参数"));
     let parameter = Parameter::try_form_value(parameter)?;
     append_log(LogLevel::Info, String::from("正在连接服务器"));
     let (session, config) = get_session(configuration)?;
     append_log(LogLevel::Info, String::from("连接服务器成功"));
-    let task_id = Uuid::new_v4().to_string();
-    let tmp_dir = "/tmp/mould-server-extension";
-    let script_path = format!("{}/task-{}.sh", tmp_dir, task_id);
-    let log_path = format!("{}/task-{}.log", tmp_dir, task_id);
     let shell = parameter.shell;
     let script = if parameter.script.starts_with("#!/bin/") {
         parameter.script.clone()
     } else {
         format!("#!{}\n{}", shell, parameter.script)
     };
-    let sudo_prefix = if parameter.sudo {
-        format!("echo {} | sudo -S ", config.password)
-    } else {
-        String::from("")
-    };
+    let (sudo_prefix, sudo_input) = sudo_command(parameter.sudo, &config.password);
     let command = if config.workspace.is_empty() {
         format!("{sudo_prefix}{shell} {script_path} > {log_path}")
     } else {
@@ -58,13 +135,14 @@ fn try_handle(
         format!("cd {workspace} && {sudo_prefix}{shell} {script_path} > {log_path}")
     };
     append_log(LogLevel::Info, String::from("正在服务器上准备工作目录"));
-    exec_remote(&session, &format!("mkdir -p {}", tmp_dir), true)
+    exec_remote(&session, &format!("mkdir -p {}", TMP_DIR), true, None)
         .map_err(|err| format!("准备工作目录失败, {}", err))?;
     append_log(LogLevel::Info, String::from("正在上传脚本"));
     upload_file(&session, &script_path, script.as_bytes(), 0o754)
         .map_err(|err| format!("上传脚本失败, {}", err))?;
     append_log(LogLevel::Info, String::from("正在执行脚本"));
-    exec_remote(&session, &command, false).map_err(|err| format!("执行脚本失败, {}", err))?;
+    exec_remote(&session, &command, false, sudo_input.as_deref())
+        .map_err(|err| format!("执行脚本失败, {}", err))?;
     append_log(LogLevel::Info, String::from("正在下载脚本输出日志"));
     let output =
         download_file(&session, &log_path).map_err(|err| format!("下载脚本日志失败, {}", err))?;
@@ -76,6 +154,7 @@ fn try_handle(
         &session,
         &format!("rm -rf {} {}", script_path, log_path),
         true,
+        None,
     )
     .map_err(|err| format!("移除脚本和日志失败, {}", err))?;
     append_log(
@@ -85,13 +164,25 @@ fn try_handle(
             String::from_utf8_lossy(&output)
         ),
     );
-    return Ok(());
+    return Ok(json!({
+        "output": String::from_utf8_lossy(&output),
+    }));
+}
+
+//sudo从标准输入读取密码，不把密码拼进命令里，避免出现在远程的进程列表和错误信息中
+fn sudo_command(sudo: bool, password: &str) -> (&'static str, Option<String>) {
+    if sudo {
+        return ("sudo -S -p '' ", Some(format!("{}\n", password)));
+    } else {
+        return ("", None);
+    }
 }
 
 fn exec_remote(
     session: &Session,
     command: &str,
     print_cmd_on_error: bool,
+    input: Option<&str>,
 ) -> Result<String, String> {
     let cmd_prefix = if print_cmd_on_error {
         format!("命令：{command}, ")
@@ -105,6 +196,14 @@ fn exec_remote(
     channel
         .exec(command)
         .map_err(|err| format!("{cmd_prefix}执行远程命令失败: {err}"))?;
+    if let Some(input) = input {
+        channel
+            .write_all(input.as_bytes())
+            .map_err(|err| format!("{cmd_prefix}写入远程命令输入失败: {err}"))?;
+    }
+    channel
+        .send_eof()
+        .map_err(|err| format!("{cmd_prefix}关闭远程命令输入失败: {err}"))?;
     channel
         .read_to_string(&mut output)
         .map_err(|err| format!("{cmd_prefix}读取远程命令输出结果失败: {err}"))?;
@@ -5,6 +5,7 @@ use super::get_session;
 use super::upload_file;
 use json5;
 use mould_extension_sdk::serde_json;
+use mould_extension_sdk::serde_json::json;
 use mould_extension_sdk::serde_json::Value;
 use mould_extension_sdk::AppendLog;
 use mould_extension_sdk::Context;
@@ -17,7 +18,7 @@ pub async fn handle(
     context: &Context,
     append_log: &AppendLog,
     resource_index: u32,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     let context = context.clone();
     let append_log = append_log.clone();
     let result = await_task(&context.clone(), move || {
@@ -39,7 +40,7 @@ fn try_handle(
     context: &Context,
     append_log: &AppendLog,
     resource_index: u32,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     append_log(LogLevel::Info, String::from("正在解析参数"));
     let parameter = Parameter::try_form_value(parameter)?;
     append_log(LogLevel::Info, String::from("正在连接服务器"));
@@ -78,5 +79,7 @@ fn try_handle(
     append_log(LogLevel::Info, String::from("正在上传新配置文件"));
     upload_file(&session, &remote_path, &new_root_value, 0o644)?;
     append_log(LogLevel::Info, String::from("修改成功!"));
-    return Ok(());
+    return Ok(json!({
+        "file_path": remote_path,
+    }));
 }
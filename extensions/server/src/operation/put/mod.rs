@@ -2,6 +2,7 @@ pub mod parameter;
 use super::await_task;
 use super::get_session;
 use super::upload_file;
+use mould_extension_sdk::serde_json::json;
 use mould_extension_sdk::serde_json::Value;
 use mould_extension_sdk::AppendLog;
 use mould_extension_sdk::Context;
@@ -13,7 +14,7 @@ pub async fn handle(
     parameter: Value,
     context: &Context,
     append_log: &AppendLog,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     let append_log = append_log.clone();
     let result = await_task(&context.clone(), move || {
         try_handle(configuration, parameter, &append_log)
@@ -26,7 +27,7 @@ fn try_handle(
     configuration: Value,
     parameter: Value,
     append_log: &AppendLog,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     append_log(LogLevel::Info, String::from("正在解析参数"));
     let parameter = Parameter::try_form_value(parameter)?;
     append_log(LogLevel::Info, String::from("正在连接服务器"));
@@ -47,5 +48,7 @@ fn try_handle(
     append_log(LogLevel::Info, String::from("正在上传配置文件"));
     upload_file(&session, &remote_path, parameter.content.as_bytes(), 0o644)?;
     append_log(LogLevel::Info, String::from("修改成功!"));
-    return Ok(());
+    return Ok(json!({
+        "file_path": remote_path,
+    }));
 }
@@ -1,5 +1,6 @@
 pub mod parameter;
 use super::get_session;
+use mould_extension_sdk::serde_json::json;
 use mould_extension_sdk::serde_json::Value;
 use mould_extension_sdk::AppendLog;
 use mould_extension_sdk::Context;
@@ -17,7 +18,7 @@ pub async fn handle(
     parameter: Value,
     context: &Context,
     append_log: &AppendLog,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     append_log(LogLevel::Info, String::from("正在解析参数"));
     let parameter = Parameter::try_form_value(parameter)?;
     append_log(LogLevel::Info, String::from("正在获取文件"));
@@ -40,7 +41,7 @@ fn try_handle(
     parameter: Parameter,
     mut file: File,
     append_log: &AppendLog,
-) -> Result<(), String> {
+) -> Result<Value, String> {
     append_log(LogLevel::Info, String::from("正在连接服务器"));
     let (session, config) = get_session(configuration)?;
     append_log(LogLevel::Info, String::from("连接服务器成功"));
@@ -59,7 +60,9 @@ fn try_handle(
     append_log(LogLevel::Info, String::from("正在上传文件"));
     upload_file(&session, &remote_path, &mut file, 0o644)?;
     append_log(LogLevel::Info, String::from("上传文件成功!"));
-    return Ok(());
+    return Ok(json!({
+        "file_path": remote_path,
+    }));
 }
 
 fn upload_file(
@@ -0,0 +1,107 @@
+use futures::StreamExt;
+use reqwest::header;
+use reqwest::StatusCode;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use server_sdk::runner::agent::DownloadRunnerFileReq;
+use server_sdk::runner::agent::DOWNLOAD_RUNNER_FILE_API;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::time::Duration;
+use tihu::api::Response;
+use tihu::Api;
+
+//拉取任务时服务端最多等待25秒，超时时间要比这个长
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/**
+ * 调用mould服务的执行器接口
+ */
+pub struct ServerClient {
+    server_url: String,
+    token: String,
+    client: reqwest::Client,
+}
+
+impl ServerClient {
+    pub fn new(server_url: String, token: String) -> Result<ServerClient, anyhow::Error> {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()?;
+        return Ok(ServerClient {
+            server_url: server_url,
+            token: token,
+            client: client,
+        });
+    }
+
+    async fn post<T: Serialize>(&self, api: &str, req: &T) -> Result<reqwest::Response, String> {
+        let resp = self
+            .client
+            .post(format!("{}{}", self.server_url, api))
+            .header(header::AUTHORIZATION, format!("Bearer {}", self.token))
+            .json(req)
+            .send()
+            .await
+            .map_err(|err| err.to_string())?;
+        if StatusCode::UNAUTHORIZED == resp.status() {
+            return Err(String::from("访问令牌无效，请检查执行器配置"));
+        }
+        if !resp.status().is_success() {
+            return Err(format!("请求失败，状态码：{}", resp.status()));
+        }
+        return Ok(resp);
+    }
+
+    pub async fn call<A: Api>(&self, req: &A::Input) -> Result<A::Output, String>
+    where
+        A::Input: Serialize,
+        A::Output: DeserializeOwned,
+    {
+        let resp: Response<A::Output> = self
+            .post(&A::namespace(), req)
+            .await?
+            .json()
+            .await
+            .map_err(|err| err.to_string())?;
+        if 0 != resp.code {
+            return Err(resp.message.to_string());
+        }
+        return resp.data.ok_or_else(|| String::from("响应数据格式不正确"));
+    }
+
+    /**
+     * 下载步骤参数里引用的文件到临时文件
+     */
+    pub async fn download_file(&self, key: &str) -> Result<std::fs::File, String> {
+        let resp = self
+            .post(
+                DOWNLOAD_RUNNER_FILE_API,
+                &DownloadRunnerFileReq {
+                    key: key.to_string(),
+                },
+            )
+            .await?;
+        let mut tmp_file = tempfile::tempfile().map_err(|err| {
+            log::error!("创建临时文件失败: {:?}", err);
+            err.to_string()
+        })?;
+        let mut stream = resp.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|err| {
+                log::error!("下载文件数据失败: {:?}", err);
+                err.to_string()
+            })?;
+            tmp_file.write_all(&chunk).map_err(|err| {
+                log::error!("写入数据到临时文件失败: {:?}", err);
+                err.to_string()
+            })?;
+        }
+        tmp_file.flush().map_err(|err| err.to_string())?;
+        tmp_file
+            .seek(SeekFrom::Start(0))
+            .map_err(|err| err.to_string())?;
+        return Ok(tmp_file);
+    }
+}
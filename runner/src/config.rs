@@ -0,0 +1,34 @@
+use serde::{Deserialize, Serialize};
+use std::fs::read_to_string;
+
+//默认同时执行的任务数
+const DEFAULT_CONCURRENCY: usize = 4;
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Config {
+    //mould服务的地址
+    pub server_url: String,
+    //新增执行器时生成的访问令牌
+    pub token: String,
+    pub log_cfg_path: Option<String>,
+    pub extension_dir: String,
+    //同时执行的任务数，默认4
+    pub concurrency: Option<usize>,
+}
+
+impl Config {
+    pub fn try_load_from_file(file_path: &str) -> Result<Self, anyhow::Error> {
+        let content = read_to_string(file_path)
+            .map_err(|err| anyhow::anyhow!("read file to string error: {}", err))?;
+        let mut config: Config = json5::from_str(&content)
+            .map_err(|err| anyhow::anyhow!("Invalid configuration file, {:?}", err))?;
+        if config.server_url.ends_with('/') {
+            config.server_url.pop();
+        }
+        return Ok(config);
+    }
+
+    pub fn get_concurrency(&self) -> usize {
+        return self.concurrency.unwrap_or(DEFAULT_CONCURRENCY).max(1);
+    }
+}
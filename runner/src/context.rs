@@ -0,0 +1,65 @@
+use crate::client::ServerClient;
+use mould_extension_sdk::ContextTrait;
+use serde_json::Value;
+use server_sdk::utils::json_script;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/**
+ * 执行器上调用扩展时的上下文，下载文件通过mould服务中转
+ */
+pub struct RunnerContext {
+    pub client: Arc<ServerClient>,
+    //服务端取消任务或者任务超时之后收到取消信号
+    pub cancel_receiver: watch::Receiver<bool>,
+}
+
+#[async_trait::async_trait]
+impl ContextTrait for RunnerContext {
+    async fn spawn_blocking(&self, task: Box<dyn FnOnce() + Send + 'static>) -> Result<(), String> {
+        tokio::task::spawn_blocking(task)
+            .await
+            .map_err(|err| err.to_string())?;
+        return Ok(());
+    }
+    fn spawn_future(
+        &self,
+        future: Pin<Box<dyn Future<Output = ()> + Send + 'static>>,
+    ) -> Result<(), String> {
+        tokio::spawn(future);
+        return Ok(());
+    }
+    fn modify_json_custom(
+        &self,
+        target: Value,
+        json_path: &str,
+        js_func: &str,
+        resource_index: u32,
+    ) -> Result<Value, String> {
+        return json_script::modify_json_custom(target, json_path, js_func, resource_index);
+    }
+    fn modify_json(
+        &self,
+        target: Value,
+        json_path: &str,
+        new_value: Value,
+    ) -> Result<Value, String> {
+        return json_script::modify_json(target, json_path, new_value);
+    }
+    async fn download_file(&self, key: &str) -> Result<std::fs::File, String> {
+        return self.client.download_file(key).await;
+    }
+    fn is_cancelled(&self) -> bool {
+        return *self.cancel_receiver.borrow();
+    }
+    async fn cancelled(&self) -> bool {
+        //发送端被丢弃说明任务已经执行结束
+        let mut cancel_receiver = self.cancel_receiver.clone();
+        return cancel_receiver
+            .wait_for(|cancelled| *cancelled)
+            .await
+            .is_ok();
+    }
+}
@@ -0,0 +1,120 @@
+mod client;
+mod config;
+mod context;
+mod worker;
+
+use client::ServerClient;
+use config::Config;
+use log::LevelFilter;
+use log4rs::append::console::ConsoleAppender;
+use log4rs::config::{Appender, Root};
+use log4rs::encode::pattern::PatternEncoder;
+use mould_extension_sdk::pluginator::LoadedPlugin;
+use mould_extension_sdk::Extension;
+use server_sdk::runner::agent::RegisterRunnerApi;
+use server_sdk::runner::agent::RegisterRunnerReq;
+use std::collections::HashMap;
+use std::env;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::fs::read_dir;
+use tokio::time::sleep;
+
+mould_extension_sdk::plugin_trait!(Extension);
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+//扩展id到扩展的映射
+pub type Extensions = HashMap<String, Arc<LoadedPlugin<dyn Extension>>>;
+
+fn init_console_log() -> Result<(), anyhow::Error> {
+    let console = ConsoleAppender::builder()
+        .encoder(Box::new(PatternEncoder::new(
+            "{d(%Y-%m-%d %H:%M:%S)} {l} {t}: {m}{n}",
+        )))
+        .build();
+    let config = log4rs::config::Config::builder()
+        .appender(Appender::builder().build("CONSOLE", Box::new(console)))
+        .build(Root::builder().appender("CONSOLE").build(LevelFilter::Info))?;
+    log4rs::init_config(config)?;
+    return Ok(());
+}
+
+async fn load_extensions(extension_dir: &str) -> Result<Extensions, anyhow::Error> {
+    let mut extensions = HashMap::new();
+    let mut entrys = read_dir(extension_dir).await?;
+    while let Some(entry) = entrys.next_entry().await? {
+        if !entry.file_type().await?.is_file() {
+            continue;
+        }
+        let path = entry.path();
+        if let Some(extension) = path.extension() {
+            if extension == "dll" || extension == "so" {
+                let extension = unsafe { load_plugin(&path) }
+                    .map_err(|err| anyhow::anyhow!("加载扩展{:?}失败: {:?}", path, err))?;
+                extensions.insert(extension.id(), Arc::new(extension));
+            }
+        }
+    }
+    return Ok(extensions);
+}
+
+fn init_v8() {
+    let platform = v8::new_default_platform(0, false).make_shared();
+    v8::V8::initialize_platform(platform);
+    v8::V8::initialize();
+}
+
+#[tokio::main]
+async fn main() -> Result<(), anyhow::Error> {
+    init_v8();
+    let config_path = env::args()
+        .nth(1)
+        .ok_or_else(|| anyhow::anyhow!("app require 1 parameter!"))?;
+    let config = Config::try_load_from_file(&config_path)?;
+    if let Some(log_cfg_path) = config.log_cfg_path.as_ref() {
+        if let Err(err) = log4rs::init_file(log_cfg_path, Default::default()) {
+            println!("init log4rs failed, {}", err);
+            init_console_log()?;
+        }
+    } else {
+        init_console_log()?;
+    }
+    let extensions = Arc::new(load_extensions(&config.extension_dir).await?);
+    let client = Arc::new(ServerClient::new(
+        config.server_url.clone(),
+        config.token.clone(),
+    )?);
+    let mut extension_ids: Vec<String> = extensions.keys().cloned().collect();
+    extension_ids.sort();
+    let register_req = RegisterRunnerReq {
+        version: String::from(VERSION),
+        extension_ids: extension_ids,
+    };
+    //服务端暂时连不上时一直重试，访问令牌无效时直接退出
+    let runner = loop {
+        match client.call::<RegisterRunnerApi>(&register_req).await {
+            Ok(runner) => break runner,
+            Err(err) => {
+                log::error!("连接服务端失败: {}", err);
+                if err.contains("访问令牌无效") {
+                    return Err(anyhow::anyhow!("{}", err));
+                }
+                sleep(Duration::from_secs(5)).await;
+            }
+        }
+    };
+    log::info!(
+        "执行器\"{}\"已连接到{}，标签: {}",
+        runner.name,
+        config.server_url,
+        runner.label
+    );
+    let workers: Vec<_> = (0..config.get_concurrency())
+        .map(|_| tokio::spawn(worker::run_worker(client.clone(), extensions.clone())))
+        .collect();
+    for worker in workers {
+        worker.await?;
+    }
+    return Ok(());
+}
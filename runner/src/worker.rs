@@ -0,0 +1,177 @@
+use crate::client::ServerClient;
+use crate::context::RunnerContext;
+use crate::Extensions;
+use chrono::Utc;
+use mould_extension_sdk::AppendLog;
+use mould_extension_sdk::Context;
+use serde_json::Value;
+use server_sdk::job_record::read_job_record::LogLevel;
+use server_sdk::job_record::read_job_record::StepResLog;
+use server_sdk::runner::agent::PullRunnerTaskApi;
+use server_sdk::runner::agent::PullRunnerTaskReq;
+use server_sdk::runner::agent::PullRunnerTaskResp;
+use server_sdk::runner::agent::ReportRunnerTaskApi;
+use server_sdk::runner::agent::ReportRunnerTaskReq;
+use server_sdk::runner::agent::RunnerTask;
+use server_sdk::runner::agent::RunnerTaskResult;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time::interval;
+use tokio::time::sleep;
+
+//执行期间上报日志的间隔，同时也是执行器的心跳
+const REPORT_INTERVAL: Duration = Duration::from_secs(1);
+//请求失败之后等待多久再重试
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+//上报执行结果失败时最多重试的次数
+const MAX_REPORT_RETRIES: u32 = 10;
+
+/**
+ * 不断从服务端拉取任务并执行
+ */
+pub async fn run_worker(client: Arc<ServerClient>, extensions: Arc<Extensions>) {
+    loop {
+        match client
+            .call::<PullRunnerTaskApi>(&PullRunnerTaskReq {})
+            .await
+        {
+            Ok(PullRunnerTaskResp { task: Some(task) }) => {
+                let task_id = task.task_id.clone();
+                log::info!(
+                    "开始执行任务{}，扩展: {}，操作: {}",
+                    task_id,
+                    task.extension_id,
+                    task.operation_id
+                );
+                run_task(&client, &extensions, task).await;
+                log::info!("任务{}执行结束", task_id);
+            }
+            Ok(PullRunnerTaskResp { task: None }) => (),
+            Err(err) => {
+                log::error!("拉取任务失败: {}", err);
+                sleep(RETRY_DELAY).await;
+            }
+        }
+    }
+}
+
+fn to_sdk_log_level(level: mould_extension_sdk::LogLevel) -> LogLevel {
+    match level {
+        mould_extension_sdk::LogLevel::Error => LogLevel::Error,
+        mould_extension_sdk::LogLevel::Warn => LogLevel::Warn,
+        mould_extension_sdk::LogLevel::Info => LogLevel::Info,
+        mould_extension_sdk::LogLevel::Debug => LogLevel::Debug,
+        mould_extension_sdk::LogLevel::Trace => LogLevel::Trace,
+    }
+}
+
+async fn run_task(client: &Arc<ServerClient>, extensions: &Extensions, task: RunnerTask) {
+    let RunnerTask {
+        task_id,
+        extension_id,
+        operation_id,
+        operation_parameter,
+        extension_configuration,
+        resource_index,
+    } = task;
+    let logs: Arc<Mutex<Vec<StepResLog>>> = Arc::new(Mutex::new(Vec::new()));
+    let logs_clone = logs.clone();
+    let append_log: AppendLog = Arc::new(
+        move |level: mould_extension_sdk::LogLevel, content: String| {
+            logs_clone.lock().unwrap().push(StepResLog {
+                time: Utc::now(),
+                level: to_sdk_log_level(level),
+                content: content,
+            });
+        },
+    );
+    let (cancel_sender, cancel_receiver) = watch::channel(false);
+    let context: Context = Arc::new(RunnerContext {
+        client: client.clone(),
+        cancel_receiver: cancel_receiver,
+    });
+    let call = async {
+        let extension = extensions
+            .get(&extension_id)
+            .ok_or_else(|| format!("执行器上没有找到id为{}的扩展!", extension_id))?;
+        let extension_configuration: Value =
+            serde_json::from_str(&extension_configuration).map_err(|err| err.to_string())?;
+        let operation_parameter: Value =
+            serde_json::from_str(&operation_parameter).map_err(|err| err.to_string())?;
+        return extension
+            .handle(
+                extension_configuration,
+                &operation_id,
+                operation_parameter,
+                &context,
+                &append_log,
+                resource_index,
+            )
+            .await;
+    };
+    tokio::pin!(call);
+    let mut report_interval = interval(REPORT_INTERVAL);
+    let result = loop {
+        tokio::select! {
+            result = &mut call => break result,
+            _ = report_interval.tick() => {
+                let cancelled = report_task(client, &task_id, &logs, None).await;
+                if cancelled && !*cancel_sender.borrow() {
+                    log::info!("任务{}已被取消", task_id);
+                    cancel_sender.send(true).ok();
+                }
+            }
+        }
+    };
+    let result = match result {
+        Ok(outputs) => {
+            let outputs = if outputs.is_null() {
+                None
+            } else {
+                Some(serde_json::to_string(&outputs).unwrap_or_default())
+            };
+            RunnerTaskResult::Success { outputs: outputs }
+        }
+        Err(error) => RunnerTaskResult::Failure { error: error },
+    };
+    report_task(client, &task_id, &logs, Some(result)).await;
+}
+
+//上报新产生的日志，带有执行结果时失败了会重试，返回任务是否已被取消
+async fn report_task(
+    client: &ServerClient,
+    task_id: &str,
+    logs: &Mutex<Vec<StepResLog>>,
+    result: Option<RunnerTaskResult>,
+) -> bool {
+    let is_final = result.is_some();
+    let req = ReportRunnerTaskReq {
+        task_id: task_id.to_string(),
+        logs: std::mem::take(&mut *logs.lock().unwrap()),
+        result: result,
+    };
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        match client.call::<ReportRunnerTaskApi>(&req).await {
+            Ok(resp) => return resp.cancelled,
+            Err(err) => {
+                log::error!("上报任务{}的执行情况失败: {}", task_id, err);
+                if !is_final {
+                    //没上报成功的日志放回去，下次一起上报
+                    let mut logs = logs.lock().unwrap();
+                    let newer_logs = std::mem::take(&mut *logs);
+                    *logs = req.logs;
+                    logs.extend(newer_logs);
+                    return false;
+                }
+                if MAX_REPORT_RETRIES <= attempt {
+                    return false;
+                }
+                sleep(RETRY_DELAY).await;
+            }
+        }
+    }
+}
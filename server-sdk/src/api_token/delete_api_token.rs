@@ -0,0 +1,16 @@
+use tihu::Api;
+use tihu::LightString;
+use tihu::PrimaryKey;
+
+pub const DELETE_API_TOKEN_API: &str = "/api/apiToken/deleteApiToken";
+
+pub type DeleteApiTokenReq = PrimaryKey;
+pub type DeleteApiTokenResp = ();
+pub struct DeleteApiTokenApi;
+impl Api for DeleteApiTokenApi {
+    type Input = DeleteApiTokenReq;
+    type Output = DeleteApiTokenResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(DELETE_API_TOKEN_API);
+    }
+}
@@ -0,0 +1,32 @@
+use super::enums;
+use serde;
+use serde::{Deserialize, Serialize};
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+pub const INSERT_API_TOKEN_API: &str = "/api/apiToken/insertApiToken";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct InsertApiTokenReq {
+    pub user_id: Option<Id>, //服务账号id，为空时给自己创建个人令牌
+    pub name: String,
+    pub scope: enums::Scope,
+    pub job_ids: Option<Vec<Id>>, //只能执行这些任务，为空时不限制
+    pub expire_days: u32,         //有效天数
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct InsertApiTokenResp {
+    pub id: Id,
+    pub token: String, //访问令牌，只在新增时返回一次
+}
+
+pub struct InsertApiTokenApi;
+impl Api for InsertApiTokenApi {
+    type Input = InsertApiTokenReq;
+    type Output = InsertApiTokenResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(INSERT_API_TOKEN_API);
+    }
+}
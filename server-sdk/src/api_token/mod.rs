@@ -0,0 +1,29 @@
+pub mod delete_api_token;
+pub mod insert_api_token;
+pub mod query_api_token;
+
+pub mod enums {
+    use serde::{Deserialize, Serialize};
+    use std::fmt;
+
+    /**
+     * 访问令牌的权限范围，令牌不能调用管理类的api
+     */
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum Scope {
+        Read = 1,    //只读
+        Execute = 2, //执行任务
+    }
+    impl fmt::Display for Scope {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "{}",
+                match self {
+                    Scope::Read => "只读",
+                    Scope::Execute => "执行任务",
+                }
+            )
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use super::enums;
+use chrono;
+use chrono::DateTime;
+use chrono::Utc;
+use serde;
+use serde::{Deserialize, Serialize};
+use tihu::datetime_format;
+use tihu::datetime_format_opt;
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+pub const QUERY_API_TOKEN_API: &str = "/api/apiToken/queryApiToken";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct QueryApiTokenReq {
+    pub user_id: Option<Id>, //服务账号id，为空时查询自己的个人令牌
+}
+
+impl QueryApiTokenReq {
+    pub fn empty() -> QueryApiTokenReq {
+        return QueryApiTokenReq { user_id: None };
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ApiToken {
+    pub id: Id,
+    pub user_id: Id,
+    pub name: String,
+    pub token_prefix: String, //令牌的前几位，用于辨认令牌
+    pub scope: enums::Scope,
+    pub job_ids: Option<Vec<Id>>,
+    #[serde(with = "datetime_format")]
+    pub expire_time: DateTime<Utc>,
+    #[serde(with = "datetime_format_opt")]
+    pub last_used_time: Option<DateTime<Utc>>,
+    pub created_by: Id,
+    #[serde(with = "datetime_format")]
+    pub created_time: DateTime<Utc>,
+}
+
+pub type QueryApiTokenResp = Vec<ApiToken>;
+
+pub struct QueryApiTokenApi;
+impl Api for QueryApiTokenApi {
+    type Input = QueryApiTokenReq;
+    type Output = QueryApiTokenResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(QUERY_API_TOKEN_API);
+    }
+}
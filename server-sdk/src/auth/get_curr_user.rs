@@ -1,4 +1,5 @@
 use super::super::user::enums::ProviderType;
+use super::super::user::enums::Role;
 use serde::{Deserialize, Serialize};
 use tihu::Api;
 use tihu::Id;
@@ -13,6 +14,7 @@ pub struct GetCurrUserReq {}
 pub struct User {
     pub id: Id,
     pub org_id: Option<Id>,
+    pub role: Option<Role>, //组织内的角色，没有归属组织时为空
     pub name: Option<String>,
     pub avatar_url: Option<String>,
     pub auth_source: AuthSource,
@@ -1,3 +1,4 @@
+use super::enums::LockPolicy;
 use serde;
 use serde::{Deserialize, Serialize};
 use tihu::Api;
@@ -30,6 +31,10 @@ pub struct EnvironmentSchemaResource {
 pub struct InsertEnvironmentReq {
     pub environment_schema_id: Id,
     pub name: String,
+    pub lock_policy: LockPolicy,      //环境被占用时的处理方式
+    pub runner_label: Option<String>, //执行器标签，为空时在服务端执行
+    pub protected: bool, //是否受保护，受保护的环境只有单独授权的用户才能执行任务、修改资源和审批
+    pub required_approvals: i32, //执行之前需要几个人审批，只对受保护的环境生效，0表示不需要审批
     pub schema_resource_list: Vec<EnvironmentSchemaResource>,
 }
 
@@ -1,9 +1,11 @@
 pub mod delete_environment;
 pub mod insert_environment;
 pub mod query_environment;
+pub mod query_environment_grant;
 pub mod read_environment;
 use chrono::DateTime;
 use chrono::Utc;
+pub mod save_environment_grant;
 pub mod update_environment;
 use chrono;
 use serde;
@@ -11,11 +13,64 @@ use serde::{Deserialize, Serialize};
 use tihu::datetime_format;
 use tihu::Id;
 
+pub mod enums {
+    use serde::{Deserialize, Serialize};
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum LockPolicy {
+        Reject = 1, //拒绝执行
+        Queue = 2,  //排队等待
+    }
+    impl ToString for LockPolicy {
+        fn to_string(&self) -> String {
+            match *self {
+                LockPolicy::Reject => "拒绝执行".into(),
+                LockPolicy::Queue => "排队等待".into(),
+            }
+        }
+    }
+
+    /**
+     * 环境授权的权限，受保护的环境只有被授予对应权限的用户才能操作
+     */
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum GrantPermission {
+        Run = 1,     //执行任务
+        Edit = 2,    //修改资源
+        Approve = 3, //审批
+    }
+    impl ToString for GrantPermission {
+        fn to_string(&self) -> String {
+            match *self {
+                GrantPermission::Run => "执行任务".into(),
+                GrantPermission::Edit => "修改资源".into(),
+                GrantPermission::Approve => "审批".into(),
+            }
+        }
+    }
+}
+
+/**
+ * 环境的占用信息，任务执行期间占用环境，执行结束或者取消之后释放
+ */
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EnvironmentLock {
+    pub record_id: Id,       //占用环境的任务执行记录id
+    pub job_id: Id,          //任务id
+    pub job_name: String,    //任务名称
+    pub holder_name: String, //发起执行的人
+    #[serde(with = "datetime_format")]
+    pub created_time: DateTime<Utc>, //开始占用的时间
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Environment {
     pub id: Id,
     pub environment_schema_id: Id,
     pub name: String,
+    pub lock_policy: enums::LockPolicy,
+    pub runner_label: Option<String>,
+    pub protected: bool, //是否受保护，受保护的环境只有单独授权的用户才能执行任务、修改资源和审批
+    pub required_approvals: i32, //执行之前需要几个人审批，只对受保护的环境生效，0表示不需要审批
     #[serde(with = "datetime_format")]
     pub created_time: DateTime<Utc>,
     #[serde(with = "datetime_format")]
@@ -0,0 +1,39 @@
+use super::enums::GrantPermission;
+use chrono::DateTime;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tihu::datetime_format;
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+pub const QUERY_ENVIRONMENT_GRANT_API: &str = "/api/environment/queryEnvironmentGrant";
+
+/**
+ * 环境授权
+ */
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct EnvironmentGrant {
+    pub id: Id,
+    pub user_id: Id,                 //被授权的用户id
+    pub user_name: Option<String>,   //被授权的用户名称
+    pub permission: GrantPermission, //授予的权限
+    #[serde(with = "datetime_format")]
+    pub created_time: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct QueryEnvironmentGrantReq {
+    pub environment_id: Id,
+}
+
+pub type QueryEnvironmentGrantResp = Vec<EnvironmentGrant>;
+
+pub struct QueryEnvironmentGrantApi;
+impl Api for QueryEnvironmentGrantApi {
+    type Input = QueryEnvironmentGrantReq;
+    type Output = QueryEnvironmentGrantResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(QUERY_ENVIRONMENT_GRANT_API);
+    }
+}
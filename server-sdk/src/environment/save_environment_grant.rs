@@ -0,0 +1,33 @@
+use super::enums::GrantPermission;
+use serde::{Deserialize, Serialize};
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+pub const SAVE_ENVIRONMENT_GRANT_API: &str = "/api/environment/saveEnvironmentGrant";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct EnvironmentGrant {
+    pub user_id: Id,                 //被授权的用户id
+    pub permission: GrantPermission, //授予的权限
+}
+
+/**
+ * 保存环境的全部授权，不在列表中的授权会被收回
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SaveEnvironmentGrantReq {
+    pub environment_id: Id,
+    pub grant_list: Vec<EnvironmentGrant>,
+}
+
+pub type SaveEnvironmentGrantResp = ();
+
+pub struct SaveEnvironmentGrantApi;
+impl Api for SaveEnvironmentGrantApi {
+    type Input = SaveEnvironmentGrantReq;
+    type Output = SaveEnvironmentGrantResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(SAVE_ENVIRONMENT_GRANT_API);
+    }
+}
@@ -2,6 +2,9 @@ pub mod query_extension;
 pub mod test_configuration;
 use serde::{Deserialize, Serialize};
 
+//读取配置时密码类型的属性用该值代替，保存时仍是该值表示密码没有修改
+pub const MASKED_PASSWORD: &str = "******";
+
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
 pub struct EnumOption {
     pub value: String,
@@ -1,6 +1,7 @@
 use serde;
 use serde::{Deserialize, Serialize};
 use tihu::Api;
+use tihu::Id;
 use tihu::LightString;
 
 pub const TEST_CONFIGURATION_API: &str = "/api/extension/testConfiguration";
@@ -9,6 +10,7 @@ pub const TEST_CONFIGURATION_API: &str = "/api/extension/testConfiguration";
 pub struct TestConfigurationReq {
     pub extension_id: String,
     pub extension_configuration: String,
+    pub resource_id: Option<Id>, //已保存的环境资源id，用于还原没有修改的密码
 }
 
 pub type TestConfigurationResp = ();
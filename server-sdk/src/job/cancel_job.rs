@@ -0,0 +1,21 @@
+use serde::{Deserialize, Serialize};
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+pub const CANCEL_JOB_API: &str = "/api/job/cancelJob";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CancelJobReq {
+    pub record_id: Id,
+}
+
+pub type CancelJobResp = ();
+pub struct CancelJobApi;
+impl Api for CancelJobApi {
+    type Input = CancelJobReq;
+    type Output = CancelJobResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(CANCEL_JOB_API);
+    }
+}
@@ -10,6 +10,8 @@ pub struct ContinueJobReq {
     pub record_id: Id,
     pub step_record_id: Id,
     pub success: bool,
+    pub comment: Option<String>,     //签收意见
+    pub attachments: Option<String>, //签收凭证，格式和步骤附件一样
 }
 
 pub type ContinueJobResp = ();
@@ -1,3 +1,5 @@
+use super::enums;
+use crate::extension::Attribute;
 use serde;
 use serde::{Deserialize, Serialize};
 use tihu::Api;
@@ -10,18 +12,34 @@ pub const INSERT_JOB_API: &str = "/api/job/insertJob";
 #[derive(Serialize, Deserialize, Debug)]
 pub enum JobStep {
     Auto {
-        name: String,                //步骤名称
-        schema_resource_id: Id,      //环境规格资源id
-        operation_id: String,        //操作id
-        operation_parameter: String, //操作参数
-        remark: Option<String>,      //备注
-        seq: i32,                    //执行顺序
+        name: String,                         //步骤名称
+        schema_resource_id: Id,               //环境规格资源id
+        operation_id: String,                 //操作id
+        operation_parameter: String,          //操作参数
+        remark: Option<String>,               //备注
+        timeout_seconds: Option<i32>,         //超时时间（秒）
+        max_retries: i32,                     //最大重试次数
+        retry_backoff: i32,                   //重试间隔（秒）
+        max_parallel: Option<i32>,            //最大并发数，为空表示不限制
+        batch_size: Option<i32>,              //每批资源数，为空表示不分批
+        pause_between_batches: i32,           //批次间隔（秒）
+        failure_policy: enums::FailurePolicy, //资源执行失败时，是否继续执行剩下的资源
+        run_if: enums::RunIf,                 //执行条件
+        run_expression: Option<String>,       //条件表达式，为空表示不限制
+        seq: i32,                             //执行顺序
+        depends_on: Option<Vec<i32>>,         //依赖的步骤（执行顺序），为空时依赖上一个步骤
     },
     Manual {
-        name: String,                //步骤名称
-        remark: Option<String>,      //备注
-        attachments: Option<String>, //附件
-        seq: i32,                    //执行顺序
+        name: String,                             //步骤名称
+        remark: Option<String>,                   //备注
+        attachments: Option<String>,              //附件
+        signoff_role: Option<enums::SignoffRole>, //可以签收的最低角色，为空表示不按角色限制
+        signoff_user_list: Option<Vec<Id>>,       //可以签收的用户，为空表示不按用户限制
+        required_signoffs: i32,                   //需要确认成功的签收人数
+        run_if: enums::RunIf,                     //执行条件
+        run_expression: Option<String>,           //条件表达式，为空表示不限制
+        seq: i32,                                 //执行顺序
+        depends_on: Option<Vec<i32>>,             //依赖的步骤（执行顺序），为空时依赖上一个步骤
     },
 }
 
@@ -30,6 +48,8 @@ pub struct InsertJobReq {
     pub environment_schema_id: Id,
     pub name: String,
     pub remark: Option<String>,
+    pub interrupt_policy: enums::InterruptPolicy,
+    pub parameter_schema: Vec<Attribute>, //执行时需要填写的参数
     pub job_step_list: Vec<JobStep>,
 }
 
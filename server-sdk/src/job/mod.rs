@@ -1,10 +1,74 @@
+pub mod cancel_job;
 pub mod continue_job;
 pub mod delete_job;
 pub mod insert_job;
+pub mod plan_job;
 pub mod query_job;
 pub mod read_job;
+pub mod retry_job_record;
 pub mod start_job;
 pub mod update_job;
+
+pub mod enums {
+    use serde::{Deserialize, Serialize};
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum InterruptPolicy {
+        Interrupt = 1, //标记中断
+        Resume = 2,    //继续执行
+    }
+    impl ToString for InterruptPolicy {
+        fn to_string(&self) -> String {
+            match *self {
+                InterruptPolicy::Interrupt => "标记中断".into(),
+                InterruptPolicy::Resume => "继续执行".into(),
+            }
+        }
+    }
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum FailurePolicy {
+        Continue = 1, //继续执行
+        Stop = 2,     //停止执行
+    }
+    impl ToString for FailurePolicy {
+        fn to_string(&self) -> String {
+            match *self {
+                FailurePolicy::Continue => "继续执行".into(),
+                FailurePolicy::Stop => "停止执行".into(),
+            }
+        }
+    }
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum RunIf {
+        OnSuccess = 1, //前置步骤成功时
+        OnFailure = 2, //有步骤失败时
+        Always = 3,    //总是执行
+    }
+    impl ToString for RunIf {
+        fn to_string(&self) -> String {
+            match *self {
+                RunIf::OnSuccess => "前置步骤成功时".into(),
+                RunIf::OnFailure => "有步骤失败时".into(),
+                RunIf::Always => "总是执行".into(),
+            }
+        }
+    }
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum SignoffRole {
+        Owner = 1,    //所有者
+        Admin = 2,    //管理员
+        Operator = 3, //操作员
+    }
+    impl ToString for SignoffRole {
+        fn to_string(&self) -> String {
+            match *self {
+                SignoffRole::Owner => "所有者".into(),
+                SignoffRole::Admin => "管理员".into(),
+                SignoffRole::Operator => "操作员".into(),
+            }
+        }
+    }
+}
+
 use chrono;
 use chrono::DateTime;
 use chrono::Utc;
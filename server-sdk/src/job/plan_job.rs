@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+pub const PLAN_JOB_API: &str = "/api/job/planJob";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PlanJobReq {
+    pub job_id: Id,
+    pub environment_id: Id,
+    pub params: Option<String>, //执行参数，json对象
+}
+
+/**
+ * 资源上的预览结果
+ */
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum PlanResult {
+    Changes(String), //将要做的修改
+    Unsupported,     //该操作不支持预览
+    Deferred,        //操作参数引用了前面步骤的输出，执行时才能确定
+    Error(String),   //预览失败的原因
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ResourcePlan {
+    pub resource_name: String, //资源名称
+    pub result: PlanResult,    //预览结果
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub enum StepPlan {
+    Auto {
+        step_name: String,                     //步骤名称
+        operation_name: String,                //操作名称
+        skip_reason: Option<String>,           //条件表达式不满足时的跳过原因
+        resource_plan_list: Vec<ResourcePlan>, //各个资源上的预览结果
+    },
+    Manual {
+        step_name: String,           //步骤名称
+        skip_reason: Option<String>, //条件表达式不满足时的跳过原因
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PlanJobResp {
+    pub step_plan_list: Vec<StepPlan>,
+}
+
+pub struct PlanJobApi;
+impl Api for PlanJobApi {
+    type Input = PlanJobReq;
+    type Output = PlanJobResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(PLAN_JOB_API);
+    }
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+use tihu::PrimaryKey;
+
+pub const RETRY_JOB_RECORD_API: &str = "/api/job/retryJobRecord";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RetryJobRecordReq {
+    pub record_id: Id,               //要重新执行的执行记录id，只能是最近一次执行
+    pub change_note: Option<String>, //变更说明
+}
+
+pub type RetryJobRecordResp = PrimaryKey; //重新执行产生的执行记录id
+pub struct RetryJobRecordApi;
+impl Api for RetryJobRecordApi {
+    type Input = RetryJobRecordReq;
+    type Output = RetryJobRecordResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(RETRY_JOB_RECORD_API);
+    }
+}
@@ -10,6 +10,8 @@ pub const START_JOB_API: &str = "/api/job/startJob";
 pub struct StartJobReq {
     pub job_id: Id,
     pub environment_id: Id,
+    pub params: Option<String>,      //执行参数，json对象
+    pub change_note: Option<String>, //变更说明
 }
 
 pub type StartJobResp = PrimaryKey;
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+pub const APPROVE_JOB_RECORD_API: &str = "/api/job/approveJobRecord";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ApproveJobRecordReq {
+    pub record_id: Id,           //待审批的执行记录id
+    pub comment: Option<String>, //审批意见
+}
+
+pub type ApproveJobRecordResp = ();
+pub struct ApproveJobRecordApi;
+impl Api for ApproveJobRecordApi {
+    type Input = ApproveJobRecordReq;
+    type Output = ApproveJobRecordResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(APPROVE_JOB_RECORD_API);
+    }
+}
@@ -0,0 +1,16 @@
+use tihu::Api;
+use tihu::LightString;
+use tihu::PrimaryKey;
+
+pub const DELETE_JOB_RECORD_API: &str = "/api/job/deleteJobRecord";
+
+pub type DeleteJobRecordReq = PrimaryKey;
+pub type DeleteJobRecordResp = ();
+pub struct DeleteJobRecordApi;
+impl Api for DeleteJobRecordApi {
+    type Input = DeleteJobRecordReq;
+    type Output = DeleteJobRecordResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(DELETE_JOB_RECORD_API);
+    }
+}
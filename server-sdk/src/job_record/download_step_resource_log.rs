@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+pub const DOWNLOAD_STEP_RESOURCE_LOG_API: &str = "/api/job/downloadStepResourceLog";
+//下载完整日志的地址，通过token参数指定要下载的资源执行记录，返回纯文本
+pub const STEP_RESOURCE_LOG_DOWNLOAD_PATH: &str = "/download/stepResourceLog";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DownloadStepResourceLogReq {
+    pub id: Id, //资源执行记录id
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DownloadStepResourceLogResp {
+    pub token: String, //下载日志的凭证，短时间内有效
+}
+
+pub struct DownloadStepResourceLogApi;
+impl Api for DownloadStepResourceLogApi {
+    type Input = DownloadStepResourceLogReq;
+    type Output = DownloadStepResourceLogResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(DOWNLOAD_STEP_RESOURCE_LOG_API);
+    }
+}
@@ -1,13 +1,26 @@
+pub mod approve_job_record;
+pub mod delete_job_record;
+pub mod download_step_resource_log;
+pub mod pin_job_record;
 pub mod query_job_record;
+pub mod query_step_resource_log;
 pub mod read_job_record;
+pub mod reject_job_record;
+pub mod watch_step_resource_log;
 
 pub mod enums {
     use serde::{Deserialize, Serialize};
     #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
     pub enum RecordStatus {
-        Running = 1, //进行中
-        Success = 2, //成功
-        Failure = 3, //失败
+        Running = 1,         //进行中
+        Success = 2,         //成功
+        Failure = 3,         //失败
+        Interrupted = 4,     //已中断
+        Cancelled = 5,       //已取消
+        Queued = 6,          //排队中
+        PendingApproval = 7, //待审批
+        Rejected = 8,        //已驳回
+        Expired = 9,         //审批已过期
     }
     impl ToString for RecordStatus {
         fn to_string(&self) -> String {
@@ -15,6 +28,44 @@ pub mod enums {
                 RecordStatus::Running => "进行中".into(),
                 RecordStatus::Success => "成功".into(),
                 RecordStatus::Failure => "失败".into(),
+                RecordStatus::Interrupted => "已中断".into(),
+                RecordStatus::Cancelled => "已取消".into(),
+                RecordStatus::Queued => "排队中".into(),
+                RecordStatus::PendingApproval => "待审批".into(),
+                RecordStatus::Rejected => "已驳回".into(),
+                RecordStatus::Expired => "审批已过期".into(),
+            }
+        }
+    }
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum ApprovalDecision {
+        Approve = 1, //通过
+        Reject = 2,  //驳回
+    }
+    impl ToString for ApprovalDecision {
+        fn to_string(&self) -> String {
+            match *self {
+                ApprovalDecision::Approve => "通过".into(),
+                ApprovalDecision::Reject => "驳回".into(),
+            }
+        }
+    }
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum TriggerType {
+        Manual = 1,   //手动
+        Schedule = 2, //定时
+        Webhook = 3,  //Webhook
+        Retry = 4,    //重新执行
+        ApiToken = 5, //访问令牌
+    }
+    impl ToString for TriggerType {
+        fn to_string(&self) -> String {
+            match *self {
+                TriggerType::Manual => "手动".into(),
+                TriggerType::Schedule => "定时".into(),
+                TriggerType::Webhook => "Webhook".into(),
+                TriggerType::Retry => "重新执行".into(),
+                TriggerType::ApiToken => "访问令牌".into(),
             }
         }
     }
@@ -25,10 +76,13 @@ pub mod enums {
     }
     #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
     pub enum StepRecordStatus {
-        Pending = 1, //未开始
-        Running = 2, //进行中
-        Success = 3, //成功
-        Failure = 4, //失败
+        Pending = 1,     //未开始
+        Running = 2,     //进行中
+        Success = 3,     //成功
+        Failure = 4,     //失败
+        Interrupted = 5, //已中断
+        Cancelled = 6,   //已取消
+        Skipped = 7,     //已跳过
     }
     impl ToString for StepRecordStatus {
         fn to_string(&self) -> String {
@@ -37,15 +91,21 @@ pub mod enums {
                 StepRecordStatus::Running => "进行中".into(),
                 StepRecordStatus::Success => "成功".into(),
                 StepRecordStatus::Failure => "失败".into(),
+                StepRecordStatus::Interrupted => "已中断".into(),
+                StepRecordStatus::Cancelled => "已取消".into(),
+                StepRecordStatus::Skipped => "已跳过".into(),
             }
         }
     }
     #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
     pub enum StepResourceRecordStatus {
-        Pending = 1, //未开始
-        Running = 2, //进行中
-        Success = 3, //成功
-        Failure = 4, //失败
+        Pending = 1,     //未开始
+        Running = 2,     //进行中
+        Success = 3,     //成功
+        Failure = 4,     //失败
+        Interrupted = 5, //已中断
+        Cancelled = 6,   //已取消
+        Skipped = 7,     //已跳过
     }
     impl ToString for StepResourceRecordStatus {
         fn to_string(&self) -> String {
@@ -54,6 +114,9 @@ pub mod enums {
                 StepResourceRecordStatus::Running => "进行中".into(),
                 StepResourceRecordStatus::Success => "成功".into(),
                 StepResourceRecordStatus::Failure => "失败".into(),
+                StepResourceRecordStatus::Interrupted => "已中断".into(),
+                StepResourceRecordStatus::Cancelled => "已取消".into(),
+                StepResourceRecordStatus::Skipped => "已跳过".into(),
             }
         }
     }
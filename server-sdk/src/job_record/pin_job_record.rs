@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+pub const PIN_JOB_RECORD_API: &str = "/api/job/pinJobRecord";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PinJobRecordReq {
+    pub id: Id,       //执行记录id
+    pub pinned: bool, //置顶或者取消置顶
+}
+
+pub type PinJobRecordResp = ();
+pub struct PinJobRecordApi;
+impl Api for PinJobRecordApi {
+    type Input = PinJobRecordReq;
+    type Output = PinJobRecordResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(PIN_JOB_RECORD_API);
+    }
+}
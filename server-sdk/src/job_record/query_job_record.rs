@@ -17,6 +17,8 @@ pub struct QueryJobRecordReq {
     pub job_id: Option<Id>,
     pub environment_id: Option<Id>,
     pub status: Option<enums::RecordStatus>,
+    pub created_by: Option<Id>,                   //发起执行的用户id
+    pub trigger_type: Option<enums::TriggerType>, //触发方式
     pub page_no: Option<u64>,
     pub page_size: Option<u64>,
 }
@@ -27,6 +29,8 @@ impl QueryJobRecordReq {
             job_id: None,
             environment_id: None,
             status: None,
+            created_by: None,
+            trigger_type: None,
             page_no: None,
             page_size: None,
         };
@@ -40,7 +44,14 @@ pub struct JobRecord {
     pub job_name: Option<String>,
     pub environment_id: Id,
     pub environment_name: Option<String>,
+    pub attempt: i32, //第几次执行
     pub status: enums::RecordStatus,
+    pub queue_position: Option<u64>, //排队中的记录在任务队列里的位置，从1开始
+    pub pinned: bool,                //是否置顶，置顶的记录不会被清理
+    pub created_by: Option<Id>,      //发起执行的用户id，定时触发时为空
+    pub created_by_name: Option<String>,
+    pub trigger_type: enums::TriggerType, //触发方式
+    pub change_note: Option<String>,      //变更说明
     #[serde(with = "datetime_format")]
     pub created_time: DateTime<Utc>,
     #[serde(with = "datetime_format")]
@@ -0,0 +1,28 @@
+use super::read_job_record::LogLevel;
+use super::read_job_record::StepResLog;
+use serde::{Deserialize, Serialize};
+use tihu::pagination::PaginationList;
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+pub const QUERY_STEP_RESOURCE_LOG_API: &str = "/api/job/queryStepResourceLog";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct QueryStepResourceLogReq {
+    pub id: Id,                  //资源执行记录id
+    pub level: Option<LogLevel>, //只查询该级别及更严重的日志，为空时查询所有日志
+    pub page_no: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+pub type QueryStepResourceLogResp = PaginationList<StepResLog>;
+
+pub struct QueryStepResourceLogApi;
+impl Api for QueryStepResourceLogApi {
+    type Input = QueryStepResourceLogReq;
+    type Output = QueryStepResourceLogResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(QUERY_STEP_RESOURCE_LOG_API);
+    }
+}
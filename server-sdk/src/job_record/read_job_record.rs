@@ -1,4 +1,7 @@
 use super::enums;
+use crate::job::enums::FailurePolicy;
+use crate::job::enums::RunIf;
+use crate::job::enums::SignoffRole;
 use chrono::DateTime;
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
@@ -27,14 +30,46 @@ pub struct JobStepRecord {
     pub operation_id: String,
     pub operation_parameter: String,
     pub attachments: Option<String>,
+    pub signoff_role: Option<SignoffRole>, //可以签收的最低角色，为空表示不按角色限制
+    pub signoff_user_list: Option<Vec<Id>>, //可以签收的用户，为空表示不按用户限制
+    pub required_signoffs: i32,            //需要确认成功的签收人数
+    pub signoff_list: Vec<JobStepSignoff>, //手动步骤的签收记录
+    pub timeout_seconds: Option<i32>,
+    pub max_retries: i32,
+    pub retry_backoff: i32,
+    pub max_parallel: Option<i32>,
+    pub batch_size: Option<i32>,
+    pub pause_between_batches: i32,
+    pub failure_policy: FailurePolicy,
+    pub run_if: RunIf,
+    pub run_expression: Option<String>,
+    pub skip_reason: Option<String>, //跳过原因
     pub job_step_seq: i32,
+    pub depends_on: Option<Vec<i32>>, //依赖的步骤（执行顺序），为空时依赖上一个步骤
     pub status: enums::StepRecordStatus,
+    pub completed_by: Option<Id>, //处理手动步骤的用户id
+    pub completed_by_name: Option<String>,
     #[serde(with = "datetime_format")]
     pub created_time: DateTime<Utc>,
     #[serde(with = "datetime_format")]
     pub last_modified_time: DateTime<Utc>,
 }
 
+/**
+ * 手动步骤的签收记录
+ */
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JobStepSignoff {
+    pub id: Id,
+    pub user_id: Id,                 //签收人id
+    pub user_name: Option<String>,   //签收人
+    pub success: bool,               //是否确认成功
+    pub comment: Option<String>,     //签收意见
+    pub attachments: Option<String>, //签收凭证，格式和步骤附件一样
+    #[serde(with = "datetime_format")]
+    pub created_time: DateTime<Utc>,
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Hash, Serialize, Deserialize)]
 pub enum LogLevel {
     Error = 1,
@@ -68,6 +103,17 @@ pub struct StepResLog {
     pub content: String,
 }
 
+/**
+ * 资源执行日志的摘要，完整的日志通过分页接口查询
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct StepResLogSummary {
+    pub total: u64,                 //日志条数
+    pub error_count: u64,           //错误日志条数
+    pub warn_count: u64,            //警告日志条数
+    pub last_error: Option<String>, //最后一条错误日志，过长时截断
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct JobStepResourceRecord {
     pub id: Id,
@@ -75,8 +121,9 @@ pub struct JobStepResourceRecord {
     pub job_step_record_id: Id,
     pub environment_resource_id: Id,
     pub resource_name: String,
-    pub extension_configuration: String,
-    pub output: Option<String>,
+    pub log_summary: Option<StepResLogSummary>, //日志摘要，执行结束之后才有
+    pub outputs: Option<String>,                //步骤输出，json格式
+    pub attempts: i32,
     pub status: enums::StepResourceRecordStatus,
     #[serde(with = "datetime_format")]
     pub created_time: DateTime<Utc>,
@@ -95,6 +142,32 @@ pub enum StepRecord {
     },
 }
 
+/**
+ * 同一次执行的各次尝试，重新执行会产生新的执行记录
+ */
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JobRecordAttempt {
+    pub id: Id,
+    pub attempt: i32, //第几次执行
+    pub status: enums::RecordStatus,
+    #[serde(with = "datetime_format")]
+    pub created_time: DateTime<Utc>,
+}
+
+/**
+ * 执行之前的审批记录
+ */
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JobRecordApproval {
+    pub id: Id,
+    pub approver_id: Id,                   //审批人id
+    pub approver_name: Option<String>,     //审批人
+    pub decision: enums::ApprovalDecision, //审批结果
+    pub comment: Option<String>,           //审批意见
+    #[serde(with = "datetime_format")]
+    pub created_time: DateTime<Utc>,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct JobRecord {
     pub id: Id,
@@ -102,7 +175,16 @@ pub struct JobRecord {
     pub job_name: Option<String>,
     pub environment_id: Id,
     pub environment_name: Option<String>,
+    pub attempt: i32,                        //第几次执行
+    pub attempt_list: Vec<JobRecordAttempt>, //所有的执行尝试，按照第几次执行排序
     pub status: enums::RecordStatus,
+    pub pinned: bool,           //是否置顶，置顶的记录不会被清理
+    pub created_by: Option<Id>, //发起执行的用户id，定时触发时为空
+    pub created_by_name: Option<String>,
+    pub trigger_type: enums::TriggerType,      //触发方式
+    pub change_note: Option<String>,           //变更说明
+    pub required_approvals: Option<i32>,       //需要的审批人数，不需要审批时为空
+    pub approval_list: Vec<JobRecordApproval>, //审批记录，按照审批时间排序
     pub step_record_list: Vec<StepRecord>,
     #[serde(with = "datetime_format")]
     pub created_time: DateTime<Utc>,
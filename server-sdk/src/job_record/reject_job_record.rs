@@ -0,0 +1,22 @@
+use serde::{Deserialize, Serialize};
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+pub const REJECT_JOB_RECORD_API: &str = "/api/job/rejectJobRecord";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RejectJobRecordReq {
+    pub record_id: Id,           //待审批的执行记录id
+    pub comment: Option<String>, //审批意见
+}
+
+pub type RejectJobRecordResp = ();
+pub struct RejectJobRecordApi;
+impl Api for RejectJobRecordApi {
+    type Input = RejectJobRecordReq;
+    type Output = RejectJobRecordResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(REJECT_JOB_RECORD_API);
+    }
+}
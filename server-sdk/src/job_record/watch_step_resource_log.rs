@@ -0,0 +1,39 @@
+use super::enums;
+use super::read_job_record::StepResLog;
+use serde::{Deserialize, Serialize};
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+pub const WATCH_STEP_RESOURCE_LOG_API: &str = "/api/job/watchStepResourceLog";
+//实时日志的推送地址（SSE），通过token参数指定要订阅的资源执行记录
+pub const STEP_RESOURCE_LOG_STREAM_PATH: &str = "/stream/stepResourceLog";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WatchStepResourceLogReq {
+    pub id: Id, //资源执行记录id
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct WatchStepResourceLogResp {
+    pub token: String, //订阅实时日志的凭证，短时间内有效
+}
+
+/**
+ * 实时日志推送的事件，每个事件序列化为一条SSE消息的data
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub enum StepResLogEvent {
+    Status(enums::StepResourceRecordStatus), //资源执行状态
+    Log(StepResLog),                         //新增的日志
+    End,                                     //执行已结束，不会再有新的事件
+}
+
+pub struct WatchStepResourceLogApi;
+impl Api for WatchStepResourceLogApi {
+    type Input = WatchStepResourceLogReq;
+    type Output = WatchStepResourceLogResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(WATCH_STEP_RESOURCE_LOG_API);
+    }
+}
@@ -0,0 +1,16 @@
+use tihu::Api;
+use tihu::LightString;
+use tihu::PrimaryKey;
+
+pub const DELETE_JOB_SCHEDULE_API: &str = "/api/jobSchedule/deleteJobSchedule";
+
+pub type DeleteJobScheduleReq = PrimaryKey;
+pub type DeleteJobScheduleResp = ();
+pub struct DeleteJobScheduleApi;
+impl Api for DeleteJobScheduleApi {
+    type Input = DeleteJobScheduleReq;
+    type Output = DeleteJobScheduleResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(DELETE_JOB_SCHEDULE_API);
+    }
+}
@@ -0,0 +1,29 @@
+use super::enums;
+use serde;
+use serde::{Deserialize, Serialize};
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+use tihu::PrimaryKey;
+
+pub const INSERT_JOB_SCHEDULE_API: &str = "/api/jobSchedule/insertJobSchedule";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct InsertJobScheduleReq {
+    pub job_id: Id,
+    pub environment_id: Id,
+    pub name: String,
+    pub cron_expression: String, //cron表达式，如"0 2 * * *"
+    pub params: Option<String>,  //执行参数，json对象
+    pub status: enums::ScheduleStatus,
+}
+
+pub type InsertJobScheduleResp = PrimaryKey;
+pub struct InsertJobScheduleApi;
+impl Api for InsertJobScheduleApi {
+    type Input = InsertJobScheduleReq;
+    type Output = InsertJobScheduleResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(INSERT_JOB_SCHEDULE_API);
+    }
+}
@@ -0,0 +1,22 @@
+pub mod delete_job_schedule;
+pub mod insert_job_schedule;
+pub mod query_job_schedule;
+pub mod read_job_schedule;
+pub mod update_job_schedule;
+
+pub mod enums {
+    use serde::{Deserialize, Serialize};
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum ScheduleStatus {
+        Enabled = 1,  //启用
+        Disabled = 2, //停用
+    }
+    impl ToString for ScheduleStatus {
+        fn to_string(&self) -> String {
+            match *self {
+                ScheduleStatus::Enabled => "启用".into(),
+                ScheduleStatus::Disabled => "停用".into(),
+            }
+        }
+    }
+}
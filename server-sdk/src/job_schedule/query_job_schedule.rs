@@ -0,0 +1,42 @@
+use super::enums;
+use super::read_job_schedule::JobSchedule;
+use serde;
+use serde::{Deserialize, Serialize};
+use tihu::pagination::PaginationList;
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+pub const QUERY_JOB_SCHEDULE_API: &str = "/api/jobSchedule/queryJobSchedule";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct QueryJobScheduleReq {
+    pub job_id: Option<Id>,
+    pub environment_id: Option<Id>,
+    pub status: Option<enums::ScheduleStatus>,
+    pub page_no: Option<u64>,
+    pub page_size: Option<u64>,
+}
+
+impl QueryJobScheduleReq {
+    pub fn empty() -> QueryJobScheduleReq {
+        return QueryJobScheduleReq {
+            job_id: None,
+            environment_id: None,
+            status: None,
+            page_no: None,
+            page_size: None,
+        };
+    }
+}
+
+pub type QueryJobScheduleResp = PaginationList<JobSchedule>;
+
+pub struct QueryJobScheduleApi;
+impl Api for QueryJobScheduleApi {
+    type Input = QueryJobScheduleReq;
+    type Output = QueryJobScheduleResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(QUERY_JOB_SCHEDULE_API);
+    }
+}
@@ -0,0 +1,47 @@
+use super::enums;
+use chrono;
+use chrono::DateTime;
+use chrono::Utc;
+use serde;
+use serde::{Deserialize, Serialize};
+use tihu::datetime_format;
+use tihu::datetime_format_opt;
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+use tihu::PrimaryKey;
+
+pub const READ_JOB_SCHEDULE_API: &str = "/api/jobSchedule/readJobSchedule";
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct JobSchedule {
+    pub id: Id,
+    pub job_id: Id,
+    pub job_name: Option<String>,
+    pub environment_id: Id,
+    pub environment_name: Option<String>,
+    pub name: String,
+    pub cron_expression: String,
+    pub params: Option<String>, //执行参数，json对象
+    pub status: enums::ScheduleStatus,
+    #[serde(with = "datetime_format_opt")]
+    pub next_fire_time: Option<DateTime<Utc>>, //下次触发时间
+    #[serde(with = "datetime_format_opt")]
+    pub last_fire_time: Option<DateTime<Utc>>, //上次触发时间
+    pub last_record_id: Option<Id>, //上次触发的任务记录id
+    #[serde(with = "datetime_format")]
+    pub created_time: DateTime<Utc>,
+    #[serde(with = "datetime_format")]
+    pub last_modified_time: DateTime<Utc>,
+}
+
+pub type ReadJobScheduleReq = PrimaryKey;
+pub type ReadJobScheduleResp = JobSchedule;
+pub struct ReadJobScheduleApi;
+impl Api for ReadJobScheduleApi {
+    type Input = ReadJobScheduleReq;
+    type Output = ReadJobScheduleResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(READ_JOB_SCHEDULE_API);
+    }
+}
@@ -0,0 +1,29 @@
+use super::enums;
+use serde;
+use serde::{Deserialize, Serialize};
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+pub const UPDATE_JOB_SCHEDULE_API: &str = "/api/jobSchedule/updateJobSchedule";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdateJobScheduleReq {
+    pub id: Id,
+    pub environment_id: Id,
+    pub name: String,
+    pub cron_expression: String, //cron表达式，如"0 2 * * *"
+    pub params: Option<String>,  //执行参数，json对象
+    pub status: enums::ScheduleStatus,
+}
+
+pub type UpdateJobScheduleResp = ();
+
+pub struct UpdateJobScheduleApi;
+impl Api for UpdateJobScheduleApi {
+    type Input = UpdateJobScheduleReq;
+    type Output = UpdateJobScheduleResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(UPDATE_JOB_SCHEDULE_API);
+    }
+}
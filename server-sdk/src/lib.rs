@@ -1,9 +1,13 @@
+pub mod api_token;
 pub mod auth;
 pub mod environment;
 pub mod environment_schema;
 pub mod extension;
 pub mod job;
 pub mod job_record;
+pub mod job_schedule;
+pub mod organization;
+pub mod runner;
 pub mod schema;
 pub mod storage;
 pub mod system;
@@ -0,0 +1,15 @@
+pub mod read_retention_policy;
+pub mod update_retention_policy;
+use serde::{Deserialize, Serialize};
+
+/**
+ * 一次清理过期执行记录的结果
+ */
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct JobRecordPurgeReport {
+    pub record_count: u64,               //清理的执行记录数
+    pub step_record_count: u64,          //清理的步骤执行记录数
+    pub step_resource_record_count: u64, //清理的资源执行记录数
+    pub log_object_count: u64,           //删除的日志对象数
+    pub log_file_count: u64,             //删除的日志文件数
+}
@@ -0,0 +1,31 @@
+use super::JobRecordPurgeReport;
+use chrono::DateTime;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use tihu::datetime_format_opt;
+use tihu::Api;
+use tihu::LightString;
+
+pub const READ_RETENTION_POLICY_API: &str = "/api/organization/readRetentionPolicy";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReadRetentionPolicyReq {}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RetentionPolicy {
+    pub retention_count: Option<u32>, //每个任务在每个环境下保留最近的执行记录条数
+    pub retention_days: Option<u32>,  //保留最近多少天的执行记录
+    #[serde(with = "datetime_format_opt")]
+    pub last_purge_time: Option<DateTime<Utc>>, //最后一次清理的时间
+    pub last_purge_report: Option<JobRecordPurgeReport>, //最后一次清理的结果
+}
+
+pub type ReadRetentionPolicyResp = RetentionPolicy;
+pub struct ReadRetentionPolicyApi;
+impl Api for ReadRetentionPolicyApi {
+    type Input = ReadRetentionPolicyReq;
+    type Output = ReadRetentionPolicyResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(READ_RETENTION_POLICY_API);
+    }
+}
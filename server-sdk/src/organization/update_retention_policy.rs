@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use tihu::Api;
+use tihu::LightString;
+
+pub const UPDATE_RETENTION_POLICY_API: &str = "/api/organization/updateRetentionPolicy";
+
+/**
+ * 两个条件都设置时，满足任意一个的记录都会保留；都不设置时不清理执行记录
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdateRetentionPolicyReq {
+    pub retention_count: Option<u32>, //每个任务在每个环境下保留最近的执行记录条数
+    pub retention_days: Option<u32>,  //保留最近多少天的执行记录
+}
+
+pub type UpdateRetentionPolicyResp = ();
+pub struct UpdateRetentionPolicyApi;
+impl Api for UpdateRetentionPolicyApi {
+    type Input = UpdateRetentionPolicyReq;
+    type Output = UpdateRetentionPolicyResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(UPDATE_RETENTION_POLICY_API);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::job_record::read_job_record::StepResLog;
+use serde;
+use serde::{Deserialize, Serialize};
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+/**
+ * 执行器调用的接口，请求头带上"Authorization: Bearer 访问令牌"认证，不走用户会话
+ */
+pub const RUNNER_AGENT_API_PREFIX: &str = "/api/runnerAgent/";
+pub const REGISTER_RUNNER_API: &str = "/api/runnerAgent/register";
+pub const PULL_RUNNER_TASK_API: &str = "/api/runnerAgent/pullTask";
+pub const REPORT_RUNNER_TASK_API: &str = "/api/runnerAgent/reportTask";
+//下载文件直接返回文件内容，不是json
+pub const DOWNLOAD_RUNNER_FILE_API: &str = "/api/runnerAgent/downloadFile";
+
+/**
+ * 执行器启动时登记自己的版本和加载的扩展
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RegisterRunnerReq {
+    pub version: String,
+    pub extension_ids: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RegisterRunnerResp {
+    pub id: Id,
+    pub name: String,
+    pub label: String,
+}
+
+pub struct RegisterRunnerApi;
+impl Api for RegisterRunnerApi {
+    type Input = RegisterRunnerReq;
+    type Output = RegisterRunnerResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(REGISTER_RUNNER_API);
+    }
+}
+
+/**
+ * 拉取分配给执行器的任务，没有任务时服务端会等待一段时间再返回空
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PullRunnerTaskReq {}
+
+/**
+ * 在一个资源上调用扩展的操作
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RunnerTask {
+    pub task_id: String,
+    pub extension_id: String,
+    pub operation_id: String,
+    pub operation_parameter: String,     //操作参数，json格式
+    pub extension_configuration: String, //解密之后的扩展配置，json格式
+    pub resource_index: u32,             //资源在步骤里的序号
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PullRunnerTaskResp {
+    pub task: Option<RunnerTask>, //等待期间没有任务时为空
+}
+
+pub struct PullRunnerTaskApi;
+impl Api for PullRunnerTaskApi {
+    type Input = PullRunnerTaskReq;
+    type Output = PullRunnerTaskResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(PULL_RUNNER_TASK_API);
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub enum RunnerTaskResult {
+    Success { outputs: Option<String> }, //操作的输出，json格式，没有输出时为空
+    Failure { error: String },
+}
+
+/**
+ * 上报任务的执行日志和结果，执行期间即使没有新日志也要定期上报，服务端以此判断执行器是否还在
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReportRunnerTaskReq {
+    pub task_id: String,
+    pub logs: Vec<StepResLog>,
+    pub result: Option<RunnerTaskResult>, //执行结束时才有
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ReportRunnerTaskResp {
+    pub cancelled: bool, //任务已被取消或者超时，执行器应当停止执行
+}
+
+pub struct ReportRunnerTaskApi;
+impl Api for ReportRunnerTaskApi {
+    type Input = ReportRunnerTaskReq;
+    type Output = ReportRunnerTaskResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(REPORT_RUNNER_TASK_API);
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct DownloadRunnerFileReq {
+    pub key: String,
+}
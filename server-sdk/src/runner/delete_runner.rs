@@ -0,0 +1,16 @@
+use tihu::Api;
+use tihu::LightString;
+use tihu::PrimaryKey;
+
+pub const DELETE_RUNNER_API: &str = "/api/runner/deleteRunner";
+
+pub type DeleteRunnerReq = PrimaryKey;
+pub type DeleteRunnerResp = ();
+pub struct DeleteRunnerApi;
+impl Api for DeleteRunnerApi {
+    type Input = DeleteRunnerReq;
+    type Output = DeleteRunnerResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(DELETE_RUNNER_API);
+    }
+}
@@ -0,0 +1,28 @@
+use serde;
+use serde::{Deserialize, Serialize};
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+pub const INSERT_RUNNER_API: &str = "/api/runner/insertRunner";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct InsertRunnerReq {
+    pub name: String,
+    pub label: String, //执行器标签，环境设置了相同的标签时，该环境的资源由执行器执行
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct InsertRunnerResp {
+    pub id: Id,
+    pub token: String, //执行器的访问令牌，只在新增时返回一次
+}
+
+pub struct InsertRunnerApi;
+impl Api for InsertRunnerApi {
+    type Input = InsertRunnerReq;
+    type Output = InsertRunnerResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(INSERT_RUNNER_API);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod agent;
+pub mod delete_runner;
+pub mod insert_runner;
+pub mod query_runner;
@@ -0,0 +1,48 @@
+use chrono;
+use chrono::DateTime;
+use chrono::Utc;
+use serde;
+use serde::{Deserialize, Serialize};
+use tihu::datetime_format;
+use tihu::datetime_format_opt;
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+pub const QUERY_RUNNER_API: &str = "/api/runner/queryRunner";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct QueryRunnerReq {
+    pub label: Option<String>,
+}
+
+impl QueryRunnerReq {
+    pub fn empty() -> QueryRunnerReq {
+        return QueryRunnerReq { label: None };
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct Runner {
+    pub id: Id,
+    pub name: String,
+    pub label: String,
+    pub version: Option<String>,    //执行器版本，执行器连接之后才有
+    pub extension_ids: Vec<String>, //执行器加载的扩展
+    pub online: bool,               //最近有没有心跳
+    #[serde(with = "datetime_format_opt")]
+    pub last_heartbeat_time: Option<DateTime<Utc>>,
+    #[serde(with = "datetime_format")]
+    pub created_time: DateTime<Utc>,
+}
+
+pub type QueryRunnerResp = Vec<Runner>;
+
+pub struct QueryRunnerApi;
+impl Api for QueryRunnerApi {
+    type Input = QueryRunnerReq;
+    type Output = QueryRunnerResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(QUERY_RUNNER_API);
+    }
+}
@@ -0,0 +1,16 @@
+use tihu::Api;
+use tihu::LightString;
+use tihu::PrimaryKey;
+
+pub const DELETE_SERVICE_ACCOUNT_API: &str = "/api/user/deleteServiceAccount";
+
+pub type DeleteServiceAccountReq = PrimaryKey;
+pub type DeleteServiceAccountResp = ();
+pub struct DeleteServiceAccountApi;
+impl Api for DeleteServiceAccountApi {
+    type Input = DeleteServiceAccountReq;
+    type Output = DeleteServiceAccountResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(DELETE_SERVICE_ACCOUNT_API);
+    }
+}
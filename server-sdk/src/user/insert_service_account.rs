@@ -0,0 +1,24 @@
+use super::enums::Role;
+use serde;
+use serde::{Deserialize, Serialize};
+use tihu::Api;
+use tihu::LightString;
+use tihu::PrimaryKey;
+
+pub const INSERT_SERVICE_ACCOUNT_API: &str = "/api/user/insertServiceAccount";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct InsertServiceAccountReq {
+    pub name: String,
+    pub role: Role, //服务账号在组织内的角色，不能是所有者
+}
+
+pub type InsertServiceAccountResp = PrimaryKey;
+pub struct InsertServiceAccountApi;
+impl Api for InsertServiceAccountApi {
+    type Input = InsertServiceAccountReq;
+    type Output = InsertServiceAccountResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(INSERT_SERVICE_ACCOUNT_API);
+    }
+}
@@ -1,3 +1,4 @@
+use super::enums::Role;
 use serde;
 use serde::{Deserialize, Serialize};
 use tihu::Api;
@@ -9,6 +10,7 @@ pub const INVITE_USER_API: &str = "/api/user/inviteUser";
 #[derive(Serialize, Deserialize, Debug)]
 pub struct InviteUserReq {
     pub user_id: Id,
+    pub role: Role, //邀请加入组织之后的角色
 }
 
 pub type InviteUserResp = ();
@@ -1,6 +1,9 @@
+pub mod delete_service_account;
+pub mod insert_service_account;
 pub mod invite_user;
 pub mod query_user;
 pub mod read_user;
+pub mod update_user_role;
 
 pub mod enums {
     use serde::{Deserialize, Serialize};
@@ -8,8 +11,9 @@ pub mod enums {
 
     #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
     pub enum UserSource {
-        System = 1,   //系统用户
-        External = 2, //外部用户
+        System = 1,         //系统用户
+        External = 2,       //外部用户
+        ServiceAccount = 3, //服务账号
     }
     impl fmt::Display for UserSource {
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -19,6 +23,32 @@ pub mod enums {
                 match self {
                     UserSource::System => "系统用户",
                     UserSource::External => "外部用户",
+                    UserSource::ServiceAccount => "服务账号",
+                }
+            )
+        }
+    }
+
+    /**
+     * 组织内的角色，所有者和管理员可以管理组织的配置，操作员可以执行任务，只读成员只能查看
+     */
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum Role {
+        Owner = 1,    //所有者
+        Admin = 2,    //管理员
+        Operator = 3, //操作员
+        Viewer = 4,   //只读成员
+    }
+    impl fmt::Display for Role {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(
+                f,
+                "{}",
+                match self {
+                    Role::Owner => "所有者",
+                    Role::Admin => "管理员",
+                    Role::Operator => "操作员",
+                    Role::Viewer => "只读成员",
                 }
             )
         }
@@ -37,6 +37,7 @@ impl QueryUserReq {
 pub struct User {
     pub id: Id,
     pub user_source: enums::UserSource,
+    pub role: enums::Role, //组织内的角色
     pub name: String,
     pub avatar_url: Option<String>,
     #[serde(with = "datetime_format")]
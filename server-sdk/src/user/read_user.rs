@@ -39,6 +39,7 @@ pub struct ExternalUser {
 pub enum UserSource {
     System(SystemUser),     //系统用户
     External(ExternalUser), //外部用户
+    ServiceAccount,         //服务账号，只能通过访问令牌调用api
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -47,6 +48,7 @@ pub struct User {
     pub name: String,
     pub avatar_url: Option<String>,
     pub user_source: UserSource,
+    pub role: enums::Role, //组织内的角色
     #[serde(with = "datetime_format")]
     pub created_time: DateTime<Utc>,
     #[serde(with = "datetime_format")]
@@ -0,0 +1,23 @@
+use super::enums::Role;
+use serde::{Deserialize, Serialize};
+use tihu::Api;
+use tihu::Id;
+use tihu::LightString;
+
+pub const UPDATE_USER_ROLE_API: &str = "/api/user/updateUserRole";
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdateUserRoleReq {
+    pub user_id: Id,
+    pub role: Role,
+}
+
+pub type UpdateUserRoleResp = ();
+pub struct UpdateUserRoleApi;
+impl Api for UpdateUserRoleApi {
+    type Input = UpdateUserRoleReq;
+    type Output = UpdateUserRoleResp;
+    fn namespace() -> LightString {
+        return LightString::from_static(UPDATE_USER_ROLE_API);
+    }
+}
@@ -0,0 +1,70 @@
+use serde_json::Value;
+
+/**
+ * 把target里json_path匹配到的值替换成new_value
+ */
+pub fn modify_json(target: Value, json_path: &str, new_value: Value) -> Result<Value, String> {
+    let new_target =
+        jsonpath_lib::replace_with(target, json_path, &mut |_old_val| Some(new_value.clone()))
+            .map_err(|err| err.to_string())?;
+    return Ok(new_target);
+}
+
+/**
+ * 用js函数修改target里json_path匹配到的值，js函数的参数是原来的值和资源的序号，返回新的值
+ */
+pub fn modify_json_custom(
+    target: Value,
+    json_path: &str,
+    js_func: &str,
+    resource_index: u32,
+) -> Result<Value, String> {
+    let isolate = &mut v8::Isolate::new(Default::default());
+    let scope = &mut v8::HandleScope::new(isolate);
+    let context = v8::Context::new(scope, Default::default());
+    let mut error_opt = None;
+    let scope = &mut v8::ContextScope::new(scope, context);
+    let new_target = jsonpath_lib::replace_with(target, json_path, &mut |old_val| {
+        if error_opt.is_none() {
+            match try_replace(scope, js_func, old_val, resource_index) {
+                Ok(new_val) => Some(new_val),
+                Err(error) => {
+                    error_opt.replace(error);
+                    None
+                }
+            }
+        } else {
+            None
+        }
+    })
+    .map_err(|err| err.to_string())?;
+    if let Some(error) = error_opt {
+        return Err(error);
+    }
+    return Ok(new_target);
+}
+
+fn try_replace(
+    scope: &mut v8::ContextScope<v8::HandleScope>,
+    js_func: &str,
+    old_val: Value,
+    resource_index: u32,
+) -> Result<Value, String> {
+    let old_val = serde_json::to_string(&old_val).map_err(|err| err.to_string())?;
+    let replace_script = format!(
+        r#"JSON.stringify(({})({},{}))"#,
+        js_func, old_val, resource_index
+    );
+    let code =
+        v8::String::new(scope, &replace_script).ok_or_else(|| String::from("构建js脚本失败"))?;
+    let script =
+        v8::Script::compile(scope, code, None).ok_or_else(|| String::from("编译js脚本失败"))?;
+    let result = script
+        .run(scope)
+        .ok_or_else(|| String::from("运行js脚本失败"))?;
+    let result = result
+        .to_string(scope)
+        .ok_or_else(|| String::from("获取运行结果失败"))?;
+    let result = result.to_rust_string_lossy(scope);
+    return serde_json::from_str(&result).map_err(|err| err.to_string());
+}
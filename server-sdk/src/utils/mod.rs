@@ -1,3 +1,5 @@
 mod hmac_sha;
+#[cfg(feature = "script")]
+pub mod json_script;
 pub mod pbkdf2;
 mod sha_hash;
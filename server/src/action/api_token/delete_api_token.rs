@@ -0,0 +1,50 @@
+use crate::get_context;
+use crate::middleware::auth::has_permission;
+use crate::middleware::auth::Permission;
+use crate::middleware::auth::User;
+use crate::model::api_token::ApiTokenOpt;
+use crate::sdk;
+use crate::service::base::ApiTokenBaseService;
+use sdk::api_token::delete_api_token::DeleteApiTokenReq;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+//删除之后令牌立即失效，自己的令牌可以直接删除，管理员可以删除组织内的任何令牌
+pub async fn delete_api_token(
+    org_id: Id,
+    user: User,
+    delete_api_token_req: DeleteApiTokenReq,
+) -> Result<(), ErrNo> {
+    let DeleteApiTokenReq { id } = delete_api_token_req;
+    //只能在登录会话里删除令牌
+    if user.token.is_some() {
+        return Err(ErrNo::NotAllowed);
+    }
+    let api_token_id = id;
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let api_token_base_service = ApiTokenBaseService::new(&transaction);
+    let api_token = api_token_base_service
+        .query_api_token_one(&ApiTokenOpt {
+            org_id: Some(org_id),
+            id: Some(api_token_id),
+            ..ApiTokenOpt::empty()
+        })
+        .await?
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("待删除的访问令牌不存在！")))?;
+    if api_token.user_id != user.user_id && !has_permission(user.role, Permission::Manage) {
+        return Err(ErrNo::NotAllowed);
+    }
+    api_token_base_service
+        .delete_api_token(api_token_id)
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(());
+}
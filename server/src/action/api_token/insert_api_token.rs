@@ -0,0 +1,153 @@
+use super::from_sdk_scope;
+use super::hash_api_token;
+use super::serialize_job_ids;
+use super::API_TOKEN_PREFIX;
+use super::MAX_API_TOKEN_EXPIRE_DAYS;
+use crate::get_context;
+use crate::middleware::auth::has_permission;
+use crate::middleware::auth::Permission;
+use crate::middleware::auth::User;
+use crate::model::api_token::ApiToken;
+use crate::model::api_token::ApiTokenOpt;
+use crate::model::user::enums::UserSource;
+use crate::sdk;
+use crate::service::base::ApiTokenBaseService;
+use crate::service::base::JobBaseService;
+use crate::service::base::UserBaseService;
+use chrono::Duration;
+use chrono::Utc;
+use sdk::api_token::insert_api_token::InsertApiTokenReq;
+use sdk::api_token::insert_api_token::InsertApiTokenResp;
+use std::collections::HashSet;
+use tihu::base62;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+use uuid::Uuid;
+
+/**
+ * 新增访问令牌，不指定用户时给自己创建个人令牌，管理员可以给服务账号创建令牌
+ */
+pub async fn insert_api_token(
+    org_id: Id,
+    user: User,
+    insert_api_token_req: InsertApiTokenReq,
+) -> Result<InsertApiTokenResp, ErrNo> {
+    let InsertApiTokenReq {
+        user_id,
+        name,
+        scope,
+        job_ids,
+        expire_days,
+    } = insert_api_token_req;
+    //只能在登录会话里创建令牌，泄露的令牌不能用来签发新的令牌
+    if user.token.is_some() {
+        return Err(ErrNo::NotAllowed);
+    }
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err(ErrNo::CommonError(LightString::from_static(
+            "令牌名称不能为空",
+        )));
+    }
+    if 0 == expire_days || MAX_API_TOKEN_EXPIRE_DAYS < expire_days {
+        return Err(ErrNo::CommonError(LightString::from(format!(
+            "令牌的有效天数必须在1到{}之间",
+            MAX_API_TOKEN_EXPIRE_DAYS
+        ))));
+    }
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let api_token_base_service = ApiTokenBaseService::new(&transaction);
+    let job_base_service = JobBaseService::new(&transaction);
+    let user_base_service = UserBaseService::new(&transaction);
+    let token_user_id = match user_id {
+        Some(user_id) if user_id != user.user_id => {
+            if !has_permission(user.role, Permission::Manage) {
+                return Err(ErrNo::NotAllowed);
+            }
+            user_base_service
+                .read_user(user_id)
+                .await?
+                .filter(|target_user| {
+                    Some(org_id) == target_user.org_id
+                        && UserSource::ServiceAccount == target_user.user_source
+                })
+                .ok_or_else(|| -> ErrNo {
+                    ErrNo::CommonError(LightString::from_static("组织内不存在此服务账号！"))
+                })?
+                .id
+        }
+        _ => user.user_id,
+    };
+    if let Some(job_ids) = job_ids.as_ref() {
+        let unique_ids: HashSet<Id> = job_ids.iter().cloned().collect();
+        if unique_ids.is_empty() || unique_ids.len() != job_ids.len() {
+            return Err(ErrNo::CommonError(LightString::from_static(
+                "令牌指定的任务不能为空，也不能重复",
+            )));
+        }
+        let job_count = job_base_service
+            .read_job_batch(job_ids)
+            .await?
+            .into_iter()
+            .filter(|job| org_id == job.org_id)
+            .count();
+        if job_count != job_ids.len() {
+            return Err(ErrNo::CommonError(LightString::from_static(
+                "令牌指定的任务不存在",
+            )));
+        }
+    }
+    let existed = api_token_base_service
+        .query_api_token_one(&ApiTokenOpt {
+            org_id: Some(org_id),
+            user_id: Some(token_user_id),
+            name: Some(name.clone()),
+            ..ApiTokenOpt::empty()
+        })
+        .await?;
+    if existed.is_some() {
+        return Err(ErrNo::CommonError(LightString::from(format!(
+            "令牌\"{}\"已经存在",
+            name
+        ))));
+    }
+    let token = format!(
+        "{}{}{}",
+        API_TOKEN_PREFIX,
+        base62::encode(&Uuid::new_v4().as_u128().to_be_bytes()),
+        base62::encode(&Uuid::new_v4().as_u128().to_be_bytes())
+    );
+    let token_prefix: String = token.chars().take(API_TOKEN_PREFIX.len() + 4).collect();
+    let api_token_id = context.new_id();
+    let curr_time = Utc::now();
+    api_token_base_service
+        .insert_api_token(&ApiToken {
+            id: api_token_id,
+            org_id: org_id,
+            user_id: token_user_id,
+            name: name,
+            token_prefix: token_prefix,
+            token_hash: hash_api_token(&token),
+            scope: from_sdk_scope(scope),
+            job_ids: serialize_job_ids(job_ids)?,
+            expire_time: curr_time + Duration::days(expire_days as i64),
+            last_used_time: None,
+            created_by: user.user_id,
+            created_time: curr_time,
+            last_modified_time: curr_time,
+        })
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(InsertApiTokenResp {
+        id: api_token_id,
+        token: token,
+    });
+}
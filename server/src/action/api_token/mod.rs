@@ -0,0 +1,243 @@
+pub mod delete_api_token;
+pub mod insert_api_token;
+pub mod query_api_token;
+use crate::middleware::auth::TokenScope;
+use crate::middleware::auth::User;
+use crate::middleware::session::SessionId;
+use crate::model::api_token::enums::Scope;
+use crate::model::api_token::ApiTokenOpt;
+use crate::model::api_token::ApiTokenProperty;
+use crate::native_common;
+use crate::sdk;
+use crate::service::base::ApiTokenBaseService;
+use crate::service::base::UserBaseService;
+use crate::Context;
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
+use native_common::utils::sha256;
+use native_common::utils::HexStr;
+use std::sync::Arc;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+use tokio_postgres::Transaction;
+
+//访问令牌的前缀，方便在日志和代码里识别出令牌
+pub const API_TOKEN_PREFIX: &str = "mould_";
+
+//访问令牌最长的有效天数
+pub const MAX_API_TOKEN_EXPIRE_DAYS: u32 = 365;
+
+//最后使用时间最多每隔这么久更新一次，避免每次调用都写数据库
+const LAST_USED_UPDATE_SECONDS: i64 = 60;
+
+//数据库里只保存访问令牌的摘要
+pub fn hash_api_token(token: &str) -> String {
+    return HexStr(&sha256(token.as_bytes())).to_string();
+}
+
+pub fn to_sdk_scope(val: Scope) -> sdk::api_token::enums::Scope {
+    match val {
+        Scope::Read => sdk::api_token::enums::Scope::Read,
+        Scope::Execute => sdk::api_token::enums::Scope::Execute,
+    }
+}
+
+pub fn from_sdk_scope(val: sdk::api_token::enums::Scope) -> Scope {
+    match val {
+        sdk::api_token::enums::Scope::Read => Scope::Read,
+        sdk::api_token::enums::Scope::Execute => Scope::Execute,
+    }
+}
+
+pub fn serialize_job_ids(job_ids: Option<Vec<Id>>) -> Result<Option<String>, ErrNo> {
+    return job_ids
+        .map(|job_ids| serde_json::to_string(&job_ids).map_err(ErrNo::SerializeError))
+        .transpose();
+}
+
+pub fn parse_job_ids(job_ids: Option<&str>) -> Result<Option<Vec<Id>>, ErrNo> {
+    return job_ids
+        .map(|job_ids| serde_json::from_str(job_ids).map_err(ErrNo::DeserializeError))
+        .transpose();
+}
+
+//令牌到了过期时间之后不能再使用
+fn is_token_expired(expire_time: &DateTime<Utc>, curr_time: &DateTime<Utc>) -> bool {
+    return expire_time <= curr_time;
+}
+
+//从来没有用过，或者距离上次更新超过了间隔时，更新令牌的最后使用时间
+fn need_update_last_used(last_used_time: Option<DateTime<Utc>>, curr_time: DateTime<Utc>) -> bool {
+    return last_used_time
+        .map(|last_used_time| {
+            last_used_time + Duration::seconds(LAST_USED_UPDATE_SECONDS) <= curr_time
+        })
+        .unwrap_or(true);
+}
+
+//令牌的权限范围，指定了任务的令牌只能执行这些任务
+fn to_token_scope(token_id: Id, scope: Scope, job_ids: Option<&str>) -> TokenScope {
+    return TokenScope {
+        token_id: token_id,
+        execute: Scope::Execute == scope,
+        job_scoped: job_ids.is_some(),
+    };
+}
+
+//没有指定任务的令牌可以执行所有任务
+fn is_token_job_allowed(job_ids: Option<&[Id]>, job_id: Id) -> bool {
+    return job_ids
+        .map(|job_ids| job_ids.contains(&job_id))
+        .unwrap_or(true);
+}
+
+/**
+ * 用访问令牌找到对应的用户，令牌不存在、已过期或者用户已经不在令牌所属的组织时返回None
+ */
+pub async fn authenticate_api_token(
+    context: &Arc<Context>,
+    token: &str,
+) -> Result<Option<User>, ErrNo> {
+    if !token.starts_with(API_TOKEN_PREFIX) {
+        return Ok(None);
+    }
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let api_token_base_service = ApiTokenBaseService::new(&transaction);
+    let user_base_service = UserBaseService::new(&transaction);
+    let api_token = api_token_base_service
+        .query_api_token_one(&ApiTokenOpt {
+            token_hash: Some(hash_api_token(token)),
+            ..ApiTokenOpt::empty()
+        })
+        .await?;
+    let api_token = match api_token {
+        Some(api_token) => api_token,
+        None => return Ok(None),
+    };
+    let curr_time = Utc::now();
+    if is_token_expired(&api_token.expire_time, &curr_time) {
+        return Ok(None);
+    }
+    let user = user_base_service
+        .read_user(api_token.user_id)
+        .await?
+        .filter(|user| Some(api_token.org_id) == user.org_id);
+    let user = match user {
+        Some(user) => user,
+        None => return Ok(None),
+    };
+    if need_update_last_used(api_token.last_used_time, curr_time) {
+        api_token_base_service
+            .update_api_token(
+                api_token.id,
+                &[
+                    ApiTokenProperty::LastUsedTime(Some(curr_time)),
+                    ApiTokenProperty::LastModifiedTime(curr_time),
+                ],
+            )
+            .await?;
+        transaction
+            .commit()
+            .await
+            .map_err(commit_transaction_error)?;
+    }
+    return Ok(Some(User {
+        session_id: SessionId(api_token.id.as_u128()),
+        user_id: user.id,
+        org_id: Some(api_token.org_id),
+        role: user.role,
+        token: Some(to_token_scope(
+            api_token.id,
+            api_token.scope,
+            api_token.job_ids.as_deref(),
+        )),
+    }));
+}
+
+/**
+ * 只能执行指定任务的访问令牌，检查任务是否在令牌指定的任务里
+ */
+pub async fn check_token_job(
+    transaction: &Transaction<'_>,
+    user: &User,
+    job_id: Id,
+) -> Result<(), ErrNo> {
+    let token = match user.token.as_ref() {
+        Some(token) if token.job_scoped => token,
+        _ => return Ok(()),
+    };
+    let api_token_base_service = ApiTokenBaseService::new(transaction);
+    let api_token = api_token_base_service
+        .read_api_token(token.token_id)
+        .await?
+        .ok_or_else(|| ErrNo::NotAllowed)?;
+    let job_ids = parse_job_ids(api_token.job_ids.as_deref())?;
+    if is_token_job_allowed(job_ids.as_deref(), job_id) {
+        return Ok(());
+    }
+    return Err(ErrNo::CommonError(LightString::from_static(
+        "访问令牌不能执行该任务",
+    )));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_token_expired() {
+        let curr_time = Utc::now();
+        assert!(!is_token_expired(
+            &(curr_time + Duration::days(1)),
+            &curr_time
+        ));
+        assert!(is_token_expired(&curr_time, &curr_time));
+        assert!(is_token_expired(
+            &(curr_time - Duration::seconds(1)),
+            &curr_time
+        ));
+    }
+
+    #[test]
+    fn test_need_update_last_used() {
+        let curr_time = Utc::now();
+        assert!(need_update_last_used(None, curr_time));
+        assert!(!need_update_last_used(
+            Some(curr_time - Duration::seconds(10)),
+            curr_time
+        ));
+        assert!(need_update_last_used(
+            Some(curr_time - Duration::seconds(LAST_USED_UPDATE_SECONDS)),
+            curr_time
+        ));
+    }
+
+    #[test]
+    fn test_to_token_scope() {
+        let token_id = Id::from_u128(1);
+        let token_scope = to_token_scope(token_id, Scope::Read, None);
+        assert!(!token_scope.execute);
+        assert!(!token_scope.job_scoped);
+        let job_ids = serialize_job_ids(Some(vec![Id::from_u128(2)])).unwrap();
+        let token_scope = to_token_scope(token_id, Scope::Execute, job_ids.as_deref());
+        assert!(token_scope.execute);
+        assert!(token_scope.job_scoped);
+    }
+
+    #[test]
+    fn test_is_token_job_allowed() {
+        let job_id = Id::from_u128(1);
+        let other_job_id = Id::from_u128(2);
+        assert!(is_token_job_allowed(None, job_id));
+        let job_ids = serialize_job_ids(Some(vec![job_id])).unwrap();
+        let job_ids = parse_job_ids(job_ids.as_deref()).unwrap();
+        assert!(is_token_job_allowed(job_ids.as_deref(), job_id));
+        assert!(!is_token_job_allowed(job_ids.as_deref(), other_job_id));
+        assert!(!is_token_job_allowed(Some(&[]), job_id));
+    }
+}
@@ -0,0 +1,63 @@
+use super::parse_job_ids;
+use super::to_sdk_scope;
+use crate::get_context;
+use crate::middleware::auth::has_permission;
+use crate::middleware::auth::Permission;
+use crate::middleware::auth::User;
+use crate::model::api_token::ApiTokenOpt;
+use crate::sdk;
+use crate::service::base::ApiTokenBaseService;
+use sdk::api_token::query_api_token::ApiToken;
+use sdk::api_token::query_api_token::QueryApiTokenReq;
+use sdk::api_token::query_api_token::QueryApiTokenResp;
+use tihu::Id;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+/**
+ * 查询访问令牌，不指定用户时查询自己的个人令牌，管理员可以查询服务账号的令牌
+ */
+pub async fn query_api_token(
+    org_id: Id,
+    user: User,
+    query_api_token_req: QueryApiTokenReq,
+) -> Result<QueryApiTokenResp, ErrNo> {
+    let QueryApiTokenReq { user_id } = query_api_token_req;
+    let token_user_id = match user_id {
+        Some(user_id) if user_id != user.user_id => {
+            if !has_permission(user.role, Permission::Manage) {
+                return Err(ErrNo::NotAllowed);
+            }
+            user_id
+        }
+        _ => user.user_id,
+    };
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let api_token_base_service = ApiTokenBaseService::new(&transaction);
+    let mut api_token_list = api_token_base_service
+        .query_api_token_batch(&ApiTokenOpt {
+            org_id: Some(org_id),
+            user_id: Some(token_user_id),
+            ..ApiTokenOpt::empty()
+        })
+        .await?;
+    api_token_list.sort_by_key(|api_token| api_token.created_time);
+    let mut list = Vec::with_capacity(api_token_list.len());
+    for api_token in api_token_list {
+        list.push(ApiToken {
+            id: api_token.id,
+            user_id: api_token.user_id,
+            name: api_token.name,
+            token_prefix: api_token.token_prefix,
+            scope: to_sdk_scope(api_token.scope),
+            job_ids: parse_job_ids(api_token.job_ids.as_deref())?,
+            expire_time: api_token.expire_time,
+            last_used_time: api_token.last_used_time,
+            created_by: api_token.created_by,
+            created_time: api_token.created_time,
+        });
+    }
+    return Ok(list);
+}
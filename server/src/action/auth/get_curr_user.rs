@@ -1,3 +1,4 @@
+use crate::action::user::to_sdk_role;
 use crate::context::Context;
 use crate::context::RPC_TIMEOUT;
 use crate::get_context;
@@ -19,6 +20,7 @@ use sdk::auth::get_curr_user::User;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Duration;
+use tihu::Id;
 use tihu::LightString;
 use tihu_native::errno::open_transaction_error;
 use tihu_native::ErrNo;
@@ -59,6 +61,20 @@ pub struct WechatUser {
     unionid: String,
 }
 
+//角色可能被管理员修改，每次都从数据库读取
+async fn get_user_role(
+    context: &Arc<Context>,
+    user_id: Id,
+) -> Result<Option<sdk::user::enums::Role>, ErrNo> {
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let user_base_service = UserBaseService::new(&transaction);
+    let user_opt = user_base_service.read_user(user_id).await?;
+    return Ok(user_opt
+        .filter(|user| user.org_id.is_some())
+        .map(|user| to_sdk_role(user.role)));
+}
+
 pub async fn get_user_info(
     context: &Arc<Context>,
     session_info: &SessionInfo,
@@ -81,6 +97,7 @@ pub async fn get_user_info(
             return Ok(User {
                 id: user_id,
                 org_id: session_info.org_id,
+                role: None,
                 name: Some(user.name),
                 avatar_url: user.avatar_url,
                 auth_source: AuthSource::System {
@@ -113,6 +130,7 @@ pub async fn get_user_info(
             return Ok(User {
                 id: session_info.user_id,
                 org_id: session_info.org_id,
+                role: None,
                 name: userinfo.nickname,
                 avatar_url: userinfo.picture.map(|picture| picture.to_string()),
                 auth_source: AuthSource::External {
@@ -146,6 +164,7 @@ pub async fn get_user_info(
                 return Ok(User {
                     id: session_info.user_id,
                     org_id: session_info.org_id,
+                    role: None,
                     name: user_info.name,
                     avatar_url: user_info.avatar_url,
                     auth_source: AuthSource::External {
@@ -182,6 +201,7 @@ pub async fn get_user_info(
                 return Ok(User {
                     id: session_info.user_id,
                     org_id: session_info.org_id,
+                    role: None,
                     name: user_info.nickname,
                     avatar_url: user_info.headimgurl,
                     auth_source: AuthSource::External {
@@ -209,7 +229,10 @@ pub async fn get_curr_user(
     let context = get_context()?;
     let session_info = get_session_data(&context, &session_id.to_string()).await?;
     if let Some(session_info) = session_info {
-        let user_info = get_user_info(&context, &session_info).await?;
+        let mut user_info = get_user_info(&context, &session_info).await?;
+        if user_info.org_id.is_some() {
+            user_info.role = get_user_role(&context, user_info.id).await?;
+        }
         return Ok(Some(user_info));
     } else {
         return Ok(None);
@@ -1,4 +1,5 @@
 use super::cache_session_info;
+use super::first_user_role;
 use crate::context::RPC_TIMEOUT;
 use crate::get_context;
 use crate::middleware::auth::AuthMethod;
@@ -189,6 +190,10 @@ pub async fn login_by_oauth2_code(
                     .insert_organization(&Organization {
                         id: org_id,
                         name: String::from("默认组织"),
+                        retention_count: None,
+                        retention_days: None,
+                        last_purge_time: None,
+                        last_purge_report: None,
                         created_time: curr_time,
                         last_modified_time: curr_time,
                     })
@@ -202,6 +207,7 @@ pub async fn login_by_oauth2_code(
                 id: user_id,
                 org_id: org_id,
                 user_source: UserSource::External,
+                role: first_user_role(org_id),
                 name: user_info.nickname,
                 avatar_url: user_info.headimgurl,
                 created_time: curr_time,
@@ -355,6 +361,10 @@ pub async fn login_by_oauth2_code(
                     .insert_organization(&Organization {
                         id: org_id,
                         name: String::from("默认组织"),
+                        retention_count: None,
+                        retention_days: None,
+                        last_purge_time: None,
+                        last_purge_report: None,
                         created_time: curr_time,
                         last_modified_time: curr_time,
                     })
@@ -368,6 +378,7 @@ pub async fn login_by_oauth2_code(
                 id: user_id,
                 org_id: org_id,
                 user_source: UserSource::External,
+                role: first_user_role(org_id),
                 name: user_info.name,
                 avatar_url: user_info.avatar_url,
                 created_time: curr_time,
@@ -1,4 +1,5 @@
 use super::cache_session_info;
+use super::first_user_role;
 use crate::get_context;
 use crate::middleware::auth::AuthMethod;
 use crate::middleware::auth::Guest;
@@ -158,6 +159,10 @@ pub async fn login_by_openid_code(
                 .insert_organization(&Organization {
                     id: org_id,
                     name: String::from("默认组织"),
+                    retention_count: None,
+                    retention_days: None,
+                    last_purge_time: None,
+                    last_purge_report: None,
                     created_time: curr_time,
                     last_modified_time: curr_time,
                 })
@@ -171,6 +176,7 @@ pub async fn login_by_openid_code(
             id: user_id,
             org_id: org_id,
             user_source: UserSource::External,
+            role: first_user_role(org_id),
             name: name,
             avatar_url: avatar_url,
             created_time: curr_time,
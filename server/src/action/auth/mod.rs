@@ -15,12 +15,14 @@ use crate::get_context;
 use crate::middleware::auth::SessionInfo;
 use crate::middleware::auth::SESSION_PREFIX;
 use crate::middleware::session::SessionId;
+use crate::model::user::enums::Role;
 use crate::native_common;
 use native_common::cache::AsyncCache;
 use native_common::cache::EliminateType;
 use native_common::utils::decrypt_by_base64;
 use native_common::utils::decrypt_by_rsa_pri_key;
 use rsa::RsaPrivateKey;
+use tihu::Id;
 use tihu::LightString;
 use tihu_native::ErrNo;
 
@@ -61,6 +63,15 @@ fn decrypt_base64_data_by_rsa_pri_key(
     Ok(content)
 }
 
+//第一个用户创建了默认组织，成为组织的所有者，其他用户在被邀请时才分配角色
+fn first_user_role(org_id: Option<Id>) -> Role {
+    if org_id.is_some() {
+        Role::Owner
+    } else {
+        Role::Viewer
+    }
+}
+
 async fn cache_session_info(
     session_id: SessionId,
     session_info: &SessionInfo,
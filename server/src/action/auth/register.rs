@@ -1,6 +1,7 @@
 use super::cache_session_info;
 use super::check_nonce;
 use super::decrypt_base64_data_by_rsa_pri_key;
+use super::first_user_role;
 use crate::get_context;
 use crate::middleware::auth::AuthMethod;
 use crate::middleware::auth::Guest;
@@ -117,6 +118,10 @@ pub async fn register(guest: Guest, register_req: RegisterReq) -> Result<Registe
             .insert_organization(&Organization {
                 id: org_id,
                 name: String::from("默认组织"),
+                retention_count: None,
+                retention_days: None,
+                last_purge_time: None,
+                last_purge_report: None,
                 created_time: curr_time,
                 last_modified_time: curr_time,
             })
@@ -131,6 +136,7 @@ pub async fn register(guest: Guest, register_req: RegisterReq) -> Result<Registe
         id: user_id,
         org_id: org_id,                  //组织id
         user_source: UserSource::System, //用户来源
+        role: first_user_role(org_id),   //角色
         name: email.clone(),
         avatar_url: None,
         created_time: curr_time,
@@ -1,17 +1,27 @@
+use super::grant::check_environment_grant;
 use crate::get_context;
 use crate::middleware::auth::User;
 use crate::model::environment::EnvironmentOpt;
+use crate::model::environment_grant::enums::GrantPermission;
+use crate::model::environment_grant::EnvironmentGrantOpt;
 use crate::model::environment_resource::EnvironmentResourceOpt;
 use crate::model::job_record::enums::Status;
 use crate::model::job_record::JobRecordOpt;
+use crate::model::job_record_approval::JobRecordApprovalOpt;
+use crate::model::job_schedule::JobScheduleOpt;
 use crate::model::job_step_record::JobStepRecordOpt;
 use crate::model::job_step_resource_record::JobStepResourceRecordOpt;
+use crate::model::job_step_signoff::JobStepSignoffOpt;
 use crate::sdk;
 use crate::service::base::EnvironmentBaseService;
+use crate::service::base::EnvironmentGrantBaseService;
 use crate::service::base::EnvironmentResourceBaseService;
+use crate::service::base::JobRecordApprovalBaseService;
 use crate::service::base::JobRecordBaseService;
+use crate::service::base::JobScheduleBaseService;
 use crate::service::base::JobStepRecordBaseService;
 use crate::service::base::JobStepResourceRecordBaseService;
+use crate::service::base::JobStepSignoffBaseService;
 use sdk::environment::delete_environment::DeleteEnvironmentReq;
 use tihu::Id;
 use tihu::LightString;
@@ -21,7 +31,7 @@ use tihu_native::ErrNo;
 
 pub async fn delete_environment(
     org_id: Id,
-    _user: User,
+    user: User,
     delete_environment_req: DeleteEnvironmentReq,
 ) -> Result<(), ErrNo> {
     let DeleteEnvironmentReq { id } = delete_environment_req;
@@ -30,10 +40,14 @@ pub async fn delete_environment(
     let mut client = context.get_db_client().await?;
     let transaction = client.transaction().await.map_err(open_transaction_error)?;
     let environment_base_service = EnvironmentBaseService::new(&transaction);
+    let environment_grant_base_service = EnvironmentGrantBaseService::new(&transaction);
     let environment_resource_base_service = EnvironmentResourceBaseService::new(&transaction);
     let job_record_base_service = JobRecordBaseService::new(&transaction);
+    let job_record_approval_base_service = JobRecordApprovalBaseService::new(&transaction);
+    let job_schedule_base_service = JobScheduleBaseService::new(&transaction);
     let job_step_record_base_service = JobStepRecordBaseService::new(&transaction);
     let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
+    let job_step_signoff_base_service = JobStepSignoffBaseService::new(&transaction);
     let params = EnvironmentOpt {
         org_id: Some(org_id),
         id: Some(environment_id),
@@ -45,6 +59,14 @@ pub async fn delete_environment(
     environment_opt.ok_or_else(|| -> ErrNo {
         ErrNo::CommonError(LightString::from_static("待删除的环境不存在！"))
     })?;
+    check_environment_grant(
+        &transaction,
+        org_id,
+        &user,
+        environment_id,
+        GrantPermission::Edit,
+    )
+    .await?;
     let running_job_count = job_record_base_service
         .query_job_record_count(&JobRecordOpt {
             org_id: Some(org_id),
@@ -53,9 +75,25 @@ pub async fn delete_environment(
             ..JobRecordOpt::empty()
         })
         .await?;
-    if 0 < running_job_count {
+    let queued_job_count = job_record_base_service
+        .query_job_record_count(&JobRecordOpt {
+            org_id: Some(org_id),
+            environment_id: Some(environment_id.into()),
+            status: Some(Status::Queued),
+            ..JobRecordOpt::empty()
+        })
+        .await?;
+    let pending_approval_job_count = job_record_base_service
+        .query_job_record_count(&JobRecordOpt {
+            org_id: Some(org_id),
+            environment_id: Some(environment_id.into()),
+            status: Some(Status::PendingApproval),
+            ..JobRecordOpt::empty()
+        })
+        .await?;
+    if 0 < running_job_count || 0 < queued_job_count || 0 < pending_approval_job_count {
         return Err(ErrNo::CommonError(LightString::from_static(
-            "该环境正在执行更新任务，不能删除！",
+            "该环境正在执行更新任务或者有等待审批的执行，不能删除！",
         )));
     }
     let resource_list = environment_resource_base_service
@@ -72,6 +110,13 @@ pub async fn delete_environment(
             ..JobRecordOpt::empty()
         })
         .await?;
+    let job_schedule_list = job_schedule_base_service
+        .query_job_schedule_batch(&JobScheduleOpt {
+            org_id: Some(org_id),
+            environment_id: Some(environment_id.into()),
+            ..JobScheduleOpt::empty()
+        })
+        .await?;
     let job_step_record_list = job_step_record_base_service
         .query_job_step_record_batch(&JobStepRecordOpt {
             org_id: Some(org_id),
@@ -86,14 +131,57 @@ pub async fn delete_environment(
             ..JobStepResourceRecordOpt::empty()
         })
         .await?;
+    let environment_grant_list = environment_grant_base_service
+        .query_environment_grant_batch(&EnvironmentGrantOpt {
+            org_id: Some(org_id),
+            environment_id: Some(environment_id.into()),
+            ..EnvironmentGrantOpt::empty()
+        })
+        .await?;
     let resource_ids: Vec<_> = resource_list
         .into_iter()
         .map(|resource| resource.id)
         .collect();
+    let environment_grant_ids: Vec<_> = environment_grant_list
+        .into_iter()
+        .map(|environment_grant| environment_grant.id)
+        .collect();
     let job_record_ids: Vec<_> = job_record_list
         .into_iter()
         .map(|job_record| job_record.id)
         .collect();
+    let mut job_record_approval_ids = Vec::new();
+    let mut job_step_signoff_ids = Vec::new();
+    for job_record_id in job_record_ids.iter() {
+        let job_record_approval_list = job_record_approval_base_service
+            .query_job_record_approval_batch(&JobRecordApprovalOpt {
+                org_id: Some(org_id),
+                record_id: Some(*job_record_id),
+                ..JobRecordApprovalOpt::empty()
+            })
+            .await?;
+        job_record_approval_ids.extend(
+            job_record_approval_list
+                .into_iter()
+                .map(|job_record_approval| job_record_approval.id),
+        );
+        let job_step_signoff_list = job_step_signoff_base_service
+            .query_job_step_signoff_batch(&JobStepSignoffOpt {
+                org_id: Some(org_id),
+                record_id: Some(*job_record_id),
+                ..JobStepSignoffOpt::empty()
+            })
+            .await?;
+        job_step_signoff_ids.extend(
+            job_step_signoff_list
+                .into_iter()
+                .map(|job_step_signoff| job_step_signoff.id),
+        );
+    }
+    let job_schedule_ids: Vec<_> = job_schedule_list
+        .into_iter()
+        .map(|job_schedule| job_schedule.id)
+        .collect();
     let job_step_record_ids: Vec<_> = job_step_record_list
         .into_iter()
         .map(|job_step_record| job_step_record.id)
@@ -110,16 +198,36 @@ pub async fn delete_environment(
             .delete_environment_resource_batch(&resource_ids)
             .await?;
     }
+    if !environment_grant_ids.is_empty() {
+        environment_grant_base_service
+            .delete_environment_grant_batch(&environment_grant_ids)
+            .await?;
+    }
     if !job_record_ids.is_empty() {
         job_record_base_service
             .delete_job_record_batch(&job_record_ids)
             .await?;
     }
+    if !job_record_approval_ids.is_empty() {
+        job_record_approval_base_service
+            .delete_job_record_approval_batch(&job_record_approval_ids)
+            .await?;
+    }
+    if !job_schedule_ids.is_empty() {
+        job_schedule_base_service
+            .delete_job_schedule_batch(&job_schedule_ids)
+            .await?;
+    }
     if !job_step_record_ids.is_empty() {
         job_step_record_base_service
             .delete_job_step_record_batch(&job_step_record_ids)
             .await?;
     }
+    if !job_step_signoff_ids.is_empty() {
+        job_step_signoff_base_service
+            .delete_job_step_signoff_batch(&job_step_signoff_ids)
+            .await?;
+    }
     if !job_step_resource_record_ids.is_empty() {
         job_step_resource_record_base_service
             .delete_job_step_resource_record_batch(&job_step_resource_record_ids)
@@ -0,0 +1,86 @@
+use crate::middleware::auth::User;
+use crate::model::environment::EnvironmentOpt;
+use crate::model::environment_grant::enums::GrantPermission;
+use crate::model::environment_grant::EnvironmentGrantOpt;
+use crate::model::user::enums::Role;
+use crate::service::base::EnvironmentBaseService;
+use crate::service::base::EnvironmentGrantBaseService;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::ErrNo;
+use tokio_postgres::Transaction;
+
+fn permission_name(permission: GrantPermission) -> &'static str {
+    match permission {
+        GrantPermission::Run => "执行任务",
+        GrantPermission::Edit => "修改资源",
+        GrantPermission::Approve => "审批",
+    }
+}
+
+/**
+ * 检查用户在环境上的授权，未受保护的环境只按照组织内的角色判断，受保护的环境需要单独授权
+ * 所有者可以给自己授权，所以不受限制
+ */
+pub async fn check_environment_grant(
+    transaction: &Transaction<'_>,
+    org_id: Id,
+    user: &User,
+    environment_id: Id,
+    permission: GrantPermission,
+) -> Result<(), ErrNo> {
+    return check_user_environment_grant(
+        transaction,
+        org_id,
+        user.user_id,
+        user.role,
+        environment_id,
+        permission,
+    )
+    .await;
+}
+
+/**
+ * 检查指定用户在环境上的授权，用于没有登录用户的场景，比如任务计划按照创建人的授权触发执行
+ */
+pub async fn check_user_environment_grant(
+    transaction: &Transaction<'_>,
+    org_id: Id,
+    user_id: Id,
+    role: Role,
+    environment_id: Id,
+    permission: GrantPermission,
+) -> Result<(), ErrNo> {
+    let environment_base_service = EnvironmentBaseService::new(transaction);
+    let environment_grant_base_service = EnvironmentGrantBaseService::new(transaction);
+    let environment_opt = environment_base_service
+        .query_environment_one(&EnvironmentOpt {
+            org_id: Some(org_id),
+            id: Some(environment_id),
+            ..EnvironmentOpt::empty()
+        })
+        .await?;
+    let environment = environment_opt.ok_or_else(|| -> ErrNo {
+        ErrNo::CommonError(LightString::from_static("该环境不存在！"))
+    })?;
+    if !environment.protected || Role::Owner == role {
+        return Ok(());
+    }
+    let grant_count = environment_grant_base_service
+        .query_environment_grant_count(&EnvironmentGrantOpt {
+            org_id: Some(org_id),
+            environment_id: Some(environment_id),
+            user_id: Some(user_id),
+            permission: Some(permission),
+            ..EnvironmentGrantOpt::empty()
+        })
+        .await?;
+    if 0 < grant_count {
+        return Ok(());
+    }
+    return Err(ErrNo::CommonError(LightString::from(format!(
+        "环境\"{}\"是受保护的环境，你没有{}的授权，请联系组织的所有者或者管理员授权！",
+        environment.name,
+        permission_name(permission)
+    ))));
+}
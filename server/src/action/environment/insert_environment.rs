@@ -1,11 +1,17 @@
+use super::check_required_approvals;
+use super::from_sdk_lock_policy;
+use super::normalize_runner_label;
 use crate::get_context;
 use crate::middleware::auth::User;
 use crate::model::environment::Environment;
+use crate::model::environment_grant::enums::GrantPermission;
+use crate::model::environment_grant::EnvironmentGrant;
 use crate::model::environment_resource::EnvironmentResource;
 use crate::model::environment_schema_resource::EnvironmentSchemaResourceOpt;
 use crate::native_common::utils::list::group_sub_list;
 use crate::sdk;
 use crate::service::base::EnvironmentBaseService;
+use crate::service::base::EnvironmentGrantBaseService;
 use crate::service::base::EnvironmentResourceBaseService;
 use crate::service::base::EnvironmentSchemaResourceBaseService;
 use chrono::Utc;
@@ -19,14 +25,19 @@ use tihu_native::ErrNo;
 
 pub async fn insert_environment(
     org_id: Id,
-    _user: User,
+    user: User,
     insert_environment_req: InsertEnvironmentReq,
 ) -> Result<PrimaryKey, ErrNo> {
     let InsertEnvironmentReq {
         environment_schema_id,
         name,
+        lock_policy,
+        runner_label,
+        protected,
+        required_approvals,
         schema_resource_list,
     } = insert_environment_req;
+    check_required_approvals(required_approvals)?;
     let context = get_context()?;
     for schema_resource in &schema_resource_list {
         let extension = context
@@ -56,6 +67,10 @@ pub async fn insert_environment(
         org_id: org_id,
         environment_schema_id: environment_schema_id.into(),
         name: name.into(),
+        lock_policy: from_sdk_lock_policy(lock_policy),
+        runner_label: normalize_runner_label(runner_label),
+        protected: protected,
+        required_approvals: required_approvals,
         created_time: curr_time,
         last_modified_time: curr_time,
     };
@@ -103,7 +118,8 @@ pub async fn insert_environment(
                             name: resource.name,
                             extension_id: environment_schema_resource.extension_id.clone(),
                             extension_name: environment_schema_resource.extension_name.clone(),
-                            extension_configuration: resource.extension_configuration,
+                            extension_configuration: context
+                                .encrypt_configuration(&resource.extension_configuration)?,
                             created_time: curr_time,
                             last_modified_time: curr_time,
                         });
@@ -118,6 +134,28 @@ pub async fn insert_environment(
     environment_resource_base_service
         .insert_environment_resource_batch(&resource_list)
         .await?;
+    //创建受保护的环境时给创建人授予全部权限，避免创建之后自己也无法操作
+    if protected {
+        let environment_grant_list: Vec<_> = [
+            GrantPermission::Run,
+            GrantPermission::Edit,
+            GrantPermission::Approve,
+        ]
+        .iter()
+        .map(|permission| EnvironmentGrant {
+            id: context.new_id(),
+            org_id: org_id,
+            environment_id: environment_id,
+            user_id: user.user_id,
+            permission: *permission,
+            created_time: curr_time,
+            last_modified_time: curr_time,
+        })
+        .collect();
+        EnvironmentGrantBaseService::new(&transaction)
+            .insert_environment_grant_batch(&environment_grant_list)
+            .await?;
+    }
     transaction
         .commit()
         .await
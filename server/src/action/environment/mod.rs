@@ -1,5 +1,64 @@
 pub mod delete_environment;
+pub mod grant;
 pub mod insert_environment;
 pub mod query_environment;
+pub mod query_environment_grant;
 pub mod read_environment;
+pub mod rotate_configuration_key;
+pub mod save_environment_grant;
 pub mod update_environment;
+use crate::model::environment::enums::LockPolicy;
+use crate::model::environment_grant::enums::GrantPermission;
+use crate::sdk;
+use tihu::LightString;
+use tihu_native::ErrNo;
+
+pub fn from_sdk_lock_policy(val: sdk::environment::enums::LockPolicy) -> LockPolicy {
+    match val {
+        sdk::environment::enums::LockPolicy::Reject => LockPolicy::Reject,
+        sdk::environment::enums::LockPolicy::Queue => LockPolicy::Queue,
+    }
+}
+
+pub fn to_sdk_lock_policy(val: LockPolicy) -> sdk::environment::enums::LockPolicy {
+    match val {
+        LockPolicy::Reject => sdk::environment::enums::LockPolicy::Reject,
+        LockPolicy::Queue => sdk::environment::enums::LockPolicy::Queue,
+    }
+}
+
+pub fn from_sdk_grant_permission(val: sdk::environment::enums::GrantPermission) -> GrantPermission {
+    match val {
+        sdk::environment::enums::GrantPermission::Run => GrantPermission::Run,
+        sdk::environment::enums::GrantPermission::Edit => GrantPermission::Edit,
+        sdk::environment::enums::GrantPermission::Approve => GrantPermission::Approve,
+    }
+}
+
+pub fn to_sdk_grant_permission(val: GrantPermission) -> sdk::environment::enums::GrantPermission {
+    match val {
+        GrantPermission::Run => sdk::environment::enums::GrantPermission::Run,
+        GrantPermission::Edit => sdk::environment::enums::GrantPermission::Edit,
+        GrantPermission::Approve => sdk::environment::enums::GrantPermission::Approve,
+    }
+}
+
+//去掉执行器标签两端的空白，空标签表示在服务端执行
+pub fn normalize_runner_label(runner_label: Option<String>) -> Option<String> {
+    return runner_label
+        .map(|runner_label| runner_label.trim().to_string())
+        .filter(|runner_label| !runner_label.is_empty());
+}
+
+//审批人数上限，避免设置了永远凑不齐的人数
+const MAX_REQUIRED_APPROVALS: i32 = 10;
+
+pub fn check_required_approvals(required_approvals: i32) -> Result<(), ErrNo> {
+    if required_approvals < 0 || MAX_REQUIRED_APPROVALS < required_approvals {
+        return Err(ErrNo::CommonError(LightString::from(format!(
+            "审批人数必须在0到{}之间",
+            MAX_REQUIRED_APPROVALS
+        ))));
+    }
+    return Ok(());
+}
@@ -1,3 +1,5 @@
+use super::super::job::environment_lock::query_environment_locks;
+use super::to_sdk_lock_policy;
 use crate::get_context;
 use crate::middleware::auth::User;
 use crate::model::environment::EnvironmentOpt;
@@ -59,6 +61,7 @@ pub async fn query_environment(
             .into_iter()
             .map(|environment_schema| (environment_schema.id, environment_schema.name))
             .collect();
+        let mut environment_lock_map = query_environment_locks(&transaction, org_id, None).await?;
         for environment in environment_list {
             list.push(Environment {
                 id: environment.id.into(),
@@ -68,6 +71,11 @@ pub async fn query_environment(
                     .map(|name| name.clone())
                     .unwrap_or_default(),
                 name: environment.name.into(),
+                lock_policy: to_sdk_lock_policy(environment.lock_policy),
+                runner_label: environment.runner_label,
+                protected: environment.protected,
+                required_approvals: environment.required_approvals,
+                lock: environment_lock_map.remove(&environment.id),
                 created_time: environment.created_time.into(),
                 last_modified_time: environment.last_modified_time.into(),
             })
@@ -0,0 +1,59 @@
+use super::to_sdk_grant_permission;
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::environment_grant::EnvironmentGrantOpt;
+use crate::sdk;
+use crate::service::base::EnvironmentGrantBaseService;
+use crate::service::base::UserBaseService;
+use sdk::environment::query_environment_grant::EnvironmentGrant;
+use sdk::environment::query_environment_grant::QueryEnvironmentGrantReq;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use tihu::Id;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+pub async fn query_environment_grant(
+    org_id: Id,
+    _user: User,
+    query_environment_grant_req: QueryEnvironmentGrantReq,
+) -> Result<Vec<EnvironmentGrant>, ErrNo> {
+    let QueryEnvironmentGrantReq { environment_id } = query_environment_grant_req;
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let environment_grant_base_service = EnvironmentGrantBaseService::new(&transaction);
+    let user_base_service = UserBaseService::new(&transaction);
+    let environment_grant_list = environment_grant_base_service
+        .query_environment_grant_batch(&EnvironmentGrantOpt {
+            org_id: Some(org_id),
+            environment_id: Some(environment_id),
+            ..EnvironmentGrantOpt::empty()
+        })
+        .await?;
+    let user_ids: HashSet<_> = environment_grant_list
+        .iter()
+        .map(|environment_grant| environment_grant.user_id)
+        .collect();
+    let user_map: HashMap<_, _> = if user_ids.is_empty() {
+        HashMap::new()
+    } else {
+        let user_ids: Vec<_> = user_ids.into_iter().collect();
+        user_base_service
+            .read_user_batch(&user_ids)
+            .await?
+            .into_iter()
+            .map(|user| (user.id, user.name))
+            .collect()
+    };
+    return Ok(environment_grant_list
+        .into_iter()
+        .map(|environment_grant| EnvironmentGrant {
+            id: environment_grant.id,
+            user_id: environment_grant.user_id,
+            user_name: user_map.get(&environment_grant.user_id).cloned(),
+            permission: to_sdk_grant_permission(environment_grant.permission),
+            created_time: environment_grant.created_time,
+        })
+        .collect());
+}
@@ -1,8 +1,13 @@
+use super::super::extension::secret::mask_configuration;
+use super::super::extension::secret::redact_configuration;
+use super::super::job::environment_lock::query_environment_locks;
+use super::to_sdk_lock_policy;
 use crate::get_context;
 use crate::middleware::auth::User;
 use crate::model::environment::EnvironmentOpt;
 use crate::model::environment_resource::EnvironmentResourceOpt;
 use crate::model::environment_schema_resource::EnvironmentSchemaResourceOpt;
+use crate::model::user::enums::Role;
 use crate::native_common::utils::list::group_sub_list;
 use crate::sdk;
 use crate::service::base::EnvironmentBaseService;
@@ -19,7 +24,7 @@ use tihu_native::ErrNo;
 
 pub async fn read_environment(
     org_id: Id,
-    _user: User,
+    user: User,
     read_environment_req: ReadEnvironmentReq,
 ) -> Result<Environment, ErrNo> {
     let ReadEnvironmentReq { id } = read_environment_req;
@@ -50,13 +55,39 @@ pub async fn read_environment(
             ..EnvironmentSchemaResourceOpt::empty()
         })
         .await?;
-    let resource_list = environment_resource_base_service
+    let mut resource_list = environment_resource_base_service
         .query_environment_resource_batch(&EnvironmentResourceOpt {
             org_id: Some(org_id),
             environment_id: Some(environment_id.into()),
             ..EnvironmentResourceOpt::empty()
         })
         .await?;
+    for resource in &mut resource_list {
+        let (extension_info, _) = context
+            .get_extension_info(&resource.extension_id)
+            .ok_or_else(|| -> ErrNo {
+                ErrNo::CommonError(LightString::from(format!(
+                    "id为\"{}\"的扩展未找到!",
+                    resource.extension_id,
+                )))
+            })?;
+        //密码不返回给前端，只读成员看不到配置内容
+        let extension_configuration =
+            context.decrypt_configuration(&resource.extension_configuration)?;
+        resource.extension_configuration = if Role::Viewer == user.role {
+            redact_configuration(
+                &extension_info.configuration_schema,
+                &extension_configuration,
+            )?
+        } else {
+            mask_configuration(
+                &extension_info.configuration_schema,
+                &extension_configuration,
+            )?
+        };
+    }
+    let mut environment_lock_map =
+        query_environment_locks(&transaction, org_id, Some(environment_id)).await?;
     let schema_resource_list = group_sub_list(
         environment_schema_resource_list,
         resource_list,
@@ -68,6 +99,11 @@ pub async fn read_environment(
         id: id.into(),
         environment_schema_id: environment.environment_schema_id.into(),
         name: environment.name,
+        lock_policy: to_sdk_lock_policy(environment.lock_policy),
+        runner_label: environment.runner_label,
+        protected: environment.protected,
+        required_approvals: environment.required_approvals,
+        lock: environment_lock_map.remove(&environment_id),
         schema_resource_list: schema_resource_list
             .into_iter()
             .map(
@@ -0,0 +1,79 @@
+use crate::model::environment_resource::properties as environment_resource_properties;
+use crate::model::job_record::properties as job_record_properties;
+use crate::model::job_step_resource_record::properties as job_step_resource_record_properties;
+use crate::model::runner_task::properties as runner_task_properties;
+use crate::service::configuration_cipher::CipherColumn;
+use crate::service::configuration_cipher::ConfigurationCipherService;
+use crate::Context;
+use std::sync::Arc;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+//每个事务最多重新加密的记录数，避免长时间锁表
+const ROTATE_BATCH_SIZE: u64 = 100;
+
+//用主密钥加密保存的字段
+const CIPHER_COLUMNS: [CipherColumn; 5] = [
+    CipherColumn {
+        entity: "environment_resource",
+        column: environment_resource_properties::EXTENSION_CONFIGURATION,
+    },
+    CipherColumn {
+        entity: "job_step_resource_record",
+        column: job_step_resource_record_properties::EXTENSION_CONFIGURATION,
+    },
+    CipherColumn {
+        entity: "job_record",
+        column: job_record_properties::SECRET_PARAMS,
+    },
+    CipherColumn {
+        entity: "runner_task",
+        column: runner_task_properties::EXTENSION_CONFIGURATION,
+    },
+    CipherColumn {
+        entity: "runner_task",
+        column: runner_task_properties::OPERATION_PARAMETER,
+    },
+];
+
+/**
+ * 用当前的主密钥重新加密所有加密保存的内容，包括环境资源和任务执行记录中的配置快照、密码类型的执行参数
+ * 轮换密钥时，把旧密钥移到old_configuration_keys，配置新的configuration_key之后执行
+ * 分批提交，中途失败时重新执行即可，已经重新加密的记录不会再处理
+ */
+pub async fn rotate_configuration_key(context: Arc<Context>) -> Result<(), ErrNo> {
+    let cipher_prefix = context.current_configuration_cipher_prefix();
+    for cipher_column in CIPHER_COLUMNS.iter() {
+        let mut count: u64 = 0;
+        loop {
+            let mut client = context.get_db_client().await?;
+            let transaction = client.transaction().await.map_err(open_transaction_error)?;
+            let configuration_cipher_service = ConfigurationCipherService::new(&transaction);
+            let cipher_list = configuration_cipher_service
+                .query_stale_cipher(cipher_column, &cipher_prefix, ROTATE_BATCH_SIZE)
+                .await?;
+            for (id, cipher) in cipher_list.iter() {
+                let plain = context.decrypt_configuration(cipher)?;
+                configuration_cipher_service
+                    .update_cipher(cipher_column, id, &context.encrypt_configuration(&plain)?)
+                    .await?;
+            }
+            transaction
+                .commit()
+                .await
+                .map_err(commit_transaction_error)?;
+            count += cipher_list.len() as u64;
+            if (cipher_list.len() as u64) < ROTATE_BATCH_SIZE {
+                break;
+            }
+        }
+        log::info!(
+            "{}.{}重新加密完成，共{}条",
+            cipher_column.entity,
+            cipher_column.column,
+            count
+        );
+    }
+    return Ok(());
+}
@@ -0,0 +1,106 @@
+use super::from_sdk_grant_permission;
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::environment::EnvironmentOpt;
+use crate::model::environment_grant::EnvironmentGrant;
+use crate::model::environment_grant::EnvironmentGrantOpt;
+use crate::sdk;
+use crate::service::base::EnvironmentBaseService;
+use crate::service::base::EnvironmentGrantBaseService;
+use crate::service::base::UserBaseService;
+use chrono::Utc;
+use sdk::environment::save_environment_grant::SaveEnvironmentGrantReq;
+use std::collections::HashSet;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+pub async fn save_environment_grant(
+    org_id: Id,
+    _user: User,
+    save_environment_grant_req: SaveEnvironmentGrantReq,
+) -> Result<(), ErrNo> {
+    let SaveEnvironmentGrantReq {
+        environment_id,
+        grant_list,
+    } = save_environment_grant_req;
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let environment_base_service = EnvironmentBaseService::new(&transaction);
+    let environment_grant_base_service = EnvironmentGrantBaseService::new(&transaction);
+    let user_base_service = UserBaseService::new(&transaction);
+    let environment_opt = environment_base_service
+        .query_environment_one(&EnvironmentOpt {
+            org_id: Some(org_id),
+            id: Some(environment_id),
+            ..EnvironmentOpt::empty()
+        })
+        .await?;
+    environment_opt.ok_or_else(|| -> ErrNo {
+        ErrNo::CommonError(LightString::from_static("该环境不存在！"))
+    })?;
+    //去掉重复的授权
+    let mut grant_set = HashSet::new();
+    let grant_list: Vec<_> = grant_list
+        .into_iter()
+        .map(|grant| (grant.user_id, from_sdk_grant_permission(grant.permission)))
+        .filter(|(user_id, permission)| grant_set.insert((*user_id, *permission as i16)))
+        .collect();
+    //只能授权给本组织的用户
+    let user_ids: HashSet<_> = grant_list.iter().map(|(user_id, _)| *user_id).collect();
+    if !user_ids.is_empty() {
+        let user_ids: Vec<_> = user_ids.into_iter().collect();
+        let user_list = user_base_service.read_user_batch(&user_ids).await?;
+        let valid_count = user_list
+            .iter()
+            .filter(|user| Some(org_id) == user.org_id)
+            .count();
+        if valid_count != user_ids.len() {
+            return Err(ErrNo::CommonError(LightString::from_static(
+                "只能授权给本组织的用户！",
+            )));
+        }
+    }
+    let existed_grant_list = environment_grant_base_service
+        .query_environment_grant_batch(&EnvironmentGrantOpt {
+            org_id: Some(org_id),
+            environment_id: Some(environment_id),
+            ..EnvironmentGrantOpt::empty()
+        })
+        .await?;
+    let existed_ids: Vec<_> = existed_grant_list
+        .into_iter()
+        .map(|environment_grant| environment_grant.id)
+        .collect();
+    if !existed_ids.is_empty() {
+        environment_grant_base_service
+            .delete_environment_grant_batch(&existed_ids)
+            .await?;
+    }
+    let curr_time = Utc::now();
+    let environment_grant_list: Vec<_> = grant_list
+        .into_iter()
+        .map(|(user_id, permission)| EnvironmentGrant {
+            id: context.new_id(),
+            org_id: org_id,
+            environment_id: environment_id,
+            user_id: user_id,
+            permission: permission,
+            created_time: curr_time,
+            last_modified_time: curr_time,
+        })
+        .collect();
+    if !environment_grant_list.is_empty() {
+        environment_grant_base_service
+            .insert_environment_grant_batch(&environment_grant_list)
+            .await?;
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(());
+}
@@ -1,7 +1,13 @@
+use super::super::extension::secret::restore_configuration;
+use super::check_required_approvals;
+use super::from_sdk_lock_policy;
+use super::grant::check_environment_grant;
+use super::normalize_runner_label;
 use crate::get_context;
 use crate::middleware::auth::User;
 use crate::model::environment::EnvironmentOpt;
 use crate::model::environment::EnvironmentProperty;
+use crate::model::environment_grant::enums::GrantPermission;
 use crate::model::environment_resource::EnvironmentResource;
 use crate::model::environment_resource::EnvironmentResourceOpt;
 use crate::model::environment_resource::EnvironmentResourceProperty;
@@ -22,26 +28,66 @@ use tihu_native::ErrNo;
 
 pub async fn update_environment(
     org_id: Id,
-    _user: User,
+    user: User,
     update_environment_req: UpdateEnvironmentReq,
 ) -> Result<(), ErrNo> {
     let UpdateEnvironmentReq {
         id,
         name,
-        schema_resource_list,
+        lock_policy,
+        runner_label,
+        protected,
+        required_approvals,
+        mut schema_resource_list,
     } = update_environment_req;
+    check_required_approvals(required_approvals)?;
     let environment_id = id;
     let context = get_context()?;
-    for schema_resource in &schema_resource_list {
-        let extension = context
-            .get_extension(&schema_resource.extension_id)
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    check_environment_grant(
+        &transaction,
+        org_id,
+        &user,
+        environment_id,
+        GrantPermission::Edit,
+    )
+    .await?;
+    let environment_resource_base_service = EnvironmentResourceBaseService::new(&transaction);
+    let mut existed_resource_list = environment_resource_base_service
+        .query_environment_resource_batch(&EnvironmentResourceOpt {
+            org_id: Some(org_id),
+            environment_id: Some(id.into()),
+            ..EnvironmentResourceOpt::empty()
+        })
+        .await?;
+    //已保存的配置是加密的，解密之后才能和提交的配置比较
+    for existed in &mut existed_resource_list {
+        existed.extension_configuration =
+            context.decrypt_configuration(&existed.extension_configuration)?;
+    }
+    for schema_resource in &mut schema_resource_list {
+        let (extension_info, extension) = context
+            .get_extension_info(&schema_resource.extension_id)
             .ok_or_else(|| -> ErrNo {
                 ErrNo::CommonError(LightString::from(format!(
                     "id为\"{}\"的扩展未找到!",
                     schema_resource.extension_id,
                 )))
             })?;
-        for resource in &schema_resource.resource_list {
+        for resource in &mut schema_resource.resource_list {
+            //读取时密码被替换成了掩码，没有修改的密码从已保存的配置中还原
+            let existed_configuration = resource.id.and_then(|id| {
+                existed_resource_list
+                    .iter()
+                    .find(|existed| id == existed.id)
+                    .map(|existed| existed.extension_configuration.as_str())
+            });
+            resource.extension_configuration = restore_configuration(
+                &extension_info.configuration_schema,
+                &resource.extension_configuration,
+                existed_configuration,
+            )?;
             let extension_configuration =
                 serde_json::from_str::<serde_json::Value>(&resource.extension_configuration)
                     .map_err(|err| -> ErrNo {
@@ -53,8 +99,6 @@ pub async fn update_environment(
                 .map_err(|err| ErrNo::CommonError(err.into()))?;
         }
     }
-    let mut client = context.get_db_client().await?;
-    let transaction = client.transaction().await.map_err(open_transaction_error)?;
     let environment_base_service = EnvironmentBaseService::new(&transaction);
     let params = EnvironmentOpt {
         org_id: Some(org_id),
@@ -70,7 +114,6 @@ pub async fn update_environment(
     let environment_schema_id = environment.environment_schema_id;
     let environment_schema_resource_base_service =
         EnvironmentSchemaResourceBaseService::new(&transaction);
-    let environment_resource_base_service = EnvironmentResourceBaseService::new(&transaction);
 
     //查询该环境应该有的资源
     let environment_schema_resource_list = environment_schema_resource_base_service
@@ -113,14 +156,7 @@ pub async fn update_environment(
             }
         }
     }
-    let existed_resource_list = environment_resource_base_service
-        .query_environment_resource_batch(&EnvironmentResourceOpt {
-            org_id: Some(org_id),
-            environment_id: Some(id.into()),
-            ..EnvironmentResourceOpt::empty()
-        })
-        .await?;
-    let action = list::group_list_action(
+    let mut action = list::group_list_action(
         resource_list,
         existed_resource_list,
         |(resource, schema_resource_id, extension_id, extension_name)| {
@@ -174,6 +210,10 @@ pub async fn update_environment(
         },
     );
 
+    for resource in &mut action.add_list {
+        resource.extension_configuration =
+            context.encrypt_configuration(&resource.extension_configuration)?;
+    }
     let mut has_operation = false;
     if !action.add_list.is_empty() {
         has_operation = true;
@@ -185,7 +225,9 @@ pub async fn update_environment(
         has_operation = true;
         let changes: Vec<EnvironmentResourceProperty> = vec![
             EnvironmentResourceProperty::Name(resource.name),
-            EnvironmentResourceProperty::ExtensionConfiguration(resource.extension_configuration),
+            EnvironmentResourceProperty::ExtensionConfiguration(
+                context.encrypt_configuration(&resource.extension_configuration)?,
+            ),
             EnvironmentResourceProperty::LastModifiedTime(curr_time),
         ];
         environment_resource_base_service
@@ -203,7 +245,13 @@ pub async fn update_environment(
             .delete_environment_resource_batch(&removed_ids)
             .await?;
     }
-    let mut changes: Vec<EnvironmentProperty> = vec![EnvironmentProperty::Name(name.into())];
+    let mut changes: Vec<EnvironmentProperty> = vec![
+        EnvironmentProperty::Name(name.into()),
+        EnvironmentProperty::LockPolicy(from_sdk_lock_policy(lock_policy)),
+        EnvironmentProperty::RunnerLabel(normalize_runner_label(runner_label)),
+        EnvironmentProperty::Protected(protected),
+        EnvironmentProperty::RequiredApprovals(required_approvals),
+    ];
     changes.retain(|property| !environment.eq(property));
     if !changes.is_empty() {
         has_operation = true;
@@ -1,2 +1,3 @@
 pub mod query_extension;
+pub mod secret;
 pub mod test_configuration;
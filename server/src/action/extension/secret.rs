@@ -0,0 +1,391 @@
+use crate::sdk;
+use crate::Context;
+use mould_extension_sdk::AppendLog;
+use mould_extension_sdk::LogLevel;
+use sdk::extension::Attribute;
+use sdk::extension::AttributeType;
+use sdk::extension::MASKED_PASSWORD;
+use serde_json::Map;
+use serde_json::Value;
+use std::sync::Arc;
+use tihu::LightString;
+use tihu_native::ErrNo;
+
+fn parse_configuration(configuration: &str) -> Result<Map<String, Value>, ErrNo> {
+    return match serde_json::from_str::<Value>(configuration) {
+        Ok(Value::Object(configuration)) => Ok(configuration),
+        _ => Err(ErrNo::CommonError(LightString::from_static(
+            "扩展配置格式不正确",
+        ))),
+    };
+}
+
+fn password_fields(configuration_schema: &[Attribute]) -> impl Iterator<Item = &str> {
+    return configuration_schema
+        .iter()
+        .filter(|attribute| AttributeType::Password == attribute.r#type)
+        .map(|attribute| attribute.id.as_str());
+}
+
+//把配置中的密码替换成掩码，未填写的密码保持为空
+pub fn mask_configuration(
+    configuration_schema: &[Attribute],
+    configuration: &str,
+) -> Result<String, ErrNo> {
+    let mut configuration = parse_configuration(configuration)?;
+    for field in password_fields(configuration_schema) {
+        if let Some(Value::String(value)) = configuration.get_mut(field) {
+            if !value.is_empty() {
+                *value = MASKED_PASSWORD.to_string();
+            }
+        }
+    }
+    return serde_json::to_string(&configuration).map_err(ErrNo::SerializeError);
+}
+
+//只读成员看不到配置的具体内容，只保留枚举和开关这类不敏感的值，文件直接去掉
+pub fn redact_configuration(
+    configuration_schema: &[Attribute],
+    configuration: &str,
+) -> Result<String, ErrNo> {
+    let mut configuration = parse_configuration(configuration)?;
+    for attribute in configuration_schema {
+        let field = attribute.id.as_str();
+        match &attribute.r#type {
+            AttributeType::String
+            | AttributeType::LongString
+            | AttributeType::Code { .. }
+            | AttributeType::Password => {
+                if let Some(Value::String(value)) = configuration.get_mut(field) {
+                    if !value.is_empty() {
+                        *value = MASKED_PASSWORD.to_string();
+                    }
+                }
+            }
+            AttributeType::StringList => {
+                if let Some(Value::Array(values)) = configuration.get_mut(field) {
+                    for value in values {
+                        *value = Value::String(MASKED_PASSWORD.to_string());
+                    }
+                }
+            }
+            AttributeType::File | AttributeType::FileList => {
+                configuration.remove(field);
+            }
+            AttributeType::Enum { .. } | AttributeType::EnumList { .. } | AttributeType::Bool => {}
+        }
+    }
+    return serde_json::to_string(&configuration).map_err(ErrNo::SerializeError);
+}
+
+//提交的配置中密码仍是掩码的，说明没有修改，用已保存的密码还原
+pub fn restore_configuration(
+    configuration_schema: &[Attribute],
+    configuration: &str,
+    existed_configuration: Option<&str>,
+) -> Result<String, ErrNo> {
+    let mut parsed_configuration = parse_configuration(configuration)?;
+    let masked_fields: Vec<&str> = password_fields(configuration_schema)
+        .filter(|field| {
+            parsed_configuration
+                .get(*field)
+                .map(|value| *value == MASKED_PASSWORD)
+                .unwrap_or(false)
+        })
+        .collect();
+    if masked_fields.is_empty() {
+        return Ok(configuration.to_string());
+    }
+    let existed_configuration = existed_configuration
+        .map(parse_configuration)
+        .transpose()?
+        .unwrap_or_default();
+    for field in masked_fields {
+        let existed_value = existed_configuration.get(field).ok_or_else(|| {
+            ErrNo::CommonError(LightString::from_static("密码已失效，请重新填写"))
+        })?;
+        parsed_configuration.insert(field.to_string(), existed_value.clone());
+    }
+    return serde_json::to_string(&parsed_configuration).map_err(ErrNo::SerializeError);
+}
+
+//取出配置中已填写的密码，用于单独加密保存
+pub fn pick_passwords(
+    configuration_schema: &[Attribute],
+    configuration: &Map<String, Value>,
+) -> Map<String, Value> {
+    return password_fields(configuration_schema)
+        .filter_map(|field| {
+            configuration
+                .get(field)
+                .filter(|value| {
+                    value
+                        .as_str()
+                        .map(|value| !value.is_empty())
+                        .unwrap_or(false)
+                })
+                .map(|value| (field.to_string(), value.clone()))
+        })
+        .collect();
+}
+
+//找出配置中所有已填写的密码
+pub fn collect_secrets(configuration_schema: &[Attribute], configuration: &Value) -> Vec<String> {
+    return password_fields(configuration_schema)
+        .filter_map(|field| configuration.get(field))
+        .filter_map(|value| value.as_str())
+        .filter(|value| !value.is_empty())
+        .map(|value| value.to_string())
+        .collect();
+}
+
+/**
+ * 解析扩展配置，同时返回日志、错误信息和结果里需要替换成掩码的密码，包括配置里的密码和密码类型的执行参数
+ */
+pub fn parse_extension_configuration(
+    configuration_schema: &[Attribute],
+    configuration: &str,
+    param_secrets: &[String],
+) -> Result<(Value, Vec<String>), ErrNo> {
+    let configuration = Value::Object(parse_configuration(configuration)?);
+    let mut secrets = collect_secrets(configuration_schema, &configuration);
+    secrets.extend(param_secrets.iter().cloned());
+    return Ok((configuration, secrets));
+}
+
+/**
+ * 解密加密保存的扩展配置，执行步骤和预览执行计划都通过这里拿到配置
+ * 扩展可能把密码写进日志、错误信息和结果里，都要用返回的密码替换成掩码之后再保存或者返回
+ */
+pub fn decrypt_extension_configuration(
+    context: &Context,
+    configuration_schema: &[Attribute],
+    encrypted_configuration: &str,
+    param_secrets: &[String],
+) -> Result<(Value, Vec<String>), ErrNo> {
+    let configuration = context.decrypt_configuration(encrypted_configuration)?;
+    return parse_extension_configuration(configuration_schema, &configuration, param_secrets);
+}
+
+//把文本中出现的密码替换成掩码，先替换长的密码，避免一个密码包含另一个密码时露出剩下的部分
+pub fn redact_secrets(content: &str, secrets: &[String]) -> String {
+    let mut secrets: Vec<&String> = secrets.iter().filter(|secret| !secret.is_empty()).collect();
+    secrets.sort_by(|left, right| right.len().cmp(&left.len()));
+    let mut content = content.to_string();
+    for secret in secrets {
+        if content.contains(secret.as_str()) {
+            content = content.replace(secret.as_str(), MASKED_PASSWORD);
+        }
+    }
+    return content;
+}
+
+//把json中字符串里出现的密码替换成掩码，逐个字符串替换，不受json转义的影响
+pub fn redact_value_secrets(value: &mut Value, secrets: &[String]) {
+    match value {
+        Value::String(content) => {
+            *content = redact_secrets(content, secrets);
+        }
+        Value::Array(list) => {
+            for item in list.iter_mut() {
+                redact_value_secrets(item, secrets);
+            }
+        }
+        Value::Object(map) => {
+            for item in map.values_mut() {
+                redact_value_secrets(item, secrets);
+            }
+        }
+        _ => (),
+    }
+}
+
+//包装日志输出，写入日志之前先把密码替换成掩码
+pub fn redact_append_log(append_log: AppendLog, secrets: Vec<String>) -> AppendLog {
+    if secrets.is_empty() {
+        return append_log;
+    }
+    return Arc::new(move |level: LogLevel, content: String| {
+        append_log(level, redact_secrets(&content, &secrets));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    fn attribute(id: &str, r#type: AttributeType) -> Attribute {
+        return Attribute {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: None,
+            required: false,
+            r#type: r#type,
+        };
+    }
+
+    fn configuration_schema() -> Vec<Attribute> {
+        return vec![
+            attribute("host", AttributeType::String),
+            attribute("password", AttributeType::Password),
+            attribute("token", AttributeType::Password),
+            attribute("tags", AttributeType::StringList),
+            attribute("enabled", AttributeType::Bool),
+            attribute("cert", AttributeType::File),
+        ];
+    }
+
+    fn parse(configuration: &str) -> Value {
+        return serde_json::from_str(configuration).unwrap();
+    }
+
+    #[test]
+    fn test_mask_configuration() {
+        let configuration = json!({
+            "host": "example.com",
+            "password": "secret",
+            "token": "",
+        })
+        .to_string();
+        let masked = mask_configuration(&configuration_schema(), &configuration).unwrap();
+        //未填写的密码保持为空
+        assert_eq!(
+            json!({"host": "example.com", "password": MASKED_PASSWORD, "token": ""}),
+            parse(&masked)
+        );
+        assert!(mask_configuration(&configuration_schema(), "[]").is_err());
+    }
+
+    #[test]
+    fn test_redact_configuration() {
+        let configuration = json!({
+            "host": "example.com",
+            "password": "secret",
+            "tags": ["a", "b"],
+            "enabled": true,
+            "cert": {"key": "cert.pem"},
+        })
+        .to_string();
+        let redacted = redact_configuration(&configuration_schema(), &configuration).unwrap();
+        assert_eq!(
+            json!({
+                "host": MASKED_PASSWORD,
+                "password": MASKED_PASSWORD,
+                "tags": [MASKED_PASSWORD, MASKED_PASSWORD],
+                "enabled": true,
+            }),
+            parse(&redacted)
+        );
+    }
+
+    #[test]
+    fn test_restore_configuration() {
+        let existed =
+            json!({"host": "example.com", "password": "secret", "token": "t0"}).to_string();
+        let configuration = json!({
+            "host": "example.org",
+            "password": MASKED_PASSWORD,
+            "token": "t1",
+        })
+        .to_string();
+        let restored =
+            restore_configuration(&configuration_schema(), &configuration, Some(&existed)).unwrap();
+        //仍是掩码的密码用已保存的还原，修改过的密码保持新值
+        assert_eq!(
+            json!({"host": "example.org", "password": "secret", "token": "t1"}),
+            parse(&restored)
+        );
+        //没有掩码时原样返回
+        let configuration = json!({"password": "new"}).to_string();
+        assert_eq!(
+            configuration,
+            restore_configuration(&configuration_schema(), &configuration, None).unwrap()
+        );
+        //没有已保存的密码可以还原
+        let configuration = json!({"password": MASKED_PASSWORD}).to_string();
+        assert!(restore_configuration(&configuration_schema(), &configuration, None).is_err());
+    }
+
+    #[test]
+    fn test_pick_passwords() {
+        let configuration = json!({"host": "example.com", "password": "secret", "token": ""});
+        let configuration = configuration.as_object().unwrap();
+        let passwords = pick_passwords(&configuration_schema(), configuration);
+        assert_eq!(
+            json!({"password": "secret"}).as_object().unwrap(),
+            &passwords
+        );
+        assert!(pick_passwords(&configuration_schema(), &Map::new()).is_empty());
+    }
+
+    #[test]
+    fn test_collect_secrets() {
+        let configuration = json!({"host": "example.com", "password": "secret", "token": ""});
+        assert_eq!(
+            vec![String::from("secret")],
+            collect_secrets(&configuration_schema(), &configuration)
+        );
+    }
+
+    #[test]
+    fn test_parse_extension_configuration() {
+        let configuration = json!({"host": "example.com", "password": "secret", "token": ""});
+        let (parsed, secrets) = parse_extension_configuration(
+            &configuration_schema(),
+            &configuration.to_string(),
+            &["p1".to_string()],
+        )
+        .unwrap();
+        assert_eq!(configuration, parsed);
+        assert_eq!(vec!["secret".to_string(), "p1".to_string()], secrets);
+        //格式不正确时不把配置内容带到错误信息里
+        let err =
+            parse_extension_configuration(&configuration_schema(), "\"secret\"", &[]).unwrap_err();
+        assert!(!err.message().contains("secret"));
+    }
+
+    #[test]
+    fn test_redact_secrets() {
+        let secrets = vec![String::from("abc"), String::from("abcdef"), String::new()];
+        assert_eq!(
+            format!("token={} key={}", MASKED_PASSWORD, MASKED_PASSWORD),
+            redact_secrets("token=abcdef key=abc", &secrets)
+        );
+        assert_eq!("nothing", redact_secrets("nothing", &secrets));
+    }
+
+    #[test]
+    fn test_redact_value_secrets() {
+        let mut value = json!({
+            "sql": "create user u identified by 'p\"w'",
+            "hosts": ["a", {"password": "p\"w"}],
+            "port": 3306,
+        });
+        redact_value_secrets(&mut value, &["p\"w".to_string()]);
+        assert_eq!(
+            json!({
+                "sql": format!("create user u identified by '{}'", MASKED_PASSWORD),
+                "hosts": ["a", {"password": MASKED_PASSWORD}],
+                "port": 3306,
+            }),
+            value
+        );
+    }
+
+    #[test]
+    fn test_redact_append_log() {
+        let logs: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+        let collected = logs.clone();
+        let append_log: AppendLog = Arc::new(move |_level: LogLevel, content: String| {
+            collected.lock().unwrap().push(content);
+        });
+        let append_log = redact_append_log(append_log, vec![String::from("secret")]);
+        append_log(LogLevel::Info, String::from("login with secret"));
+        assert_eq!(
+            vec![format!("login with {}", MASKED_PASSWORD)],
+            *logs.lock().unwrap()
+        );
+    }
+}
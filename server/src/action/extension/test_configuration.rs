@@ -1,40 +1,69 @@
+use super::secret::parse_extension_configuration;
+use super::secret::redact_secrets;
+use super::secret::restore_configuration;
 use crate::get_context;
 use crate::middleware::auth::User;
+use crate::model::environment_resource::EnvironmentResourceOpt;
 use crate::sdk;
+use crate::service::base::EnvironmentResourceBaseService;
 use sdk::extension::test_configuration::TestConfigurationReq;
 use sdk::extension::test_configuration::TestConfigurationResp;
 use tihu::Id;
 use tihu::LightString;
+use tihu_native::errno::open_transaction_error;
 use tihu_native::ErrNo;
 
 pub async fn test_configuration(
-    _org_id: Id,
+    org_id: Id,
     _user: User,
     test_configuration_req: TestConfigurationReq,
 ) -> Result<TestConfigurationResp, ErrNo> {
     let TestConfigurationReq {
         extension_id,
         extension_configuration,
+        resource_id,
     } = test_configuration_req;
     let context = get_context()?;
-    let extension = context
-        .get_extension(&extension_id)
-        .ok_or_else(|| -> ErrNo {
-            ErrNo::CommonError(LightString::from(format!(
-                "id为\"{}\"的扩展未找到!",
-                extension_id,
-            )))
-        })?;
-    let extension_configuration = serde_json::from_str::<serde_json::Value>(
+    let (extension_info, extension) =
+        context
+            .get_extension_info(&extension_id)
+            .ok_or_else(|| -> ErrNo {
+                ErrNo::CommonError(LightString::from(format!(
+                    "id为\"{}\"的扩展未找到!",
+                    extension_id,
+                )))
+            })?;
+    let extension_configuration = if let Some(resource_id) = resource_id {
+        let mut client = context.get_db_client().await?;
+        let transaction = client.transaction().await.map_err(open_transaction_error)?;
+        let environment_resource_base_service = EnvironmentResourceBaseService::new(&transaction);
+        let environment_resource = environment_resource_base_service
+            .query_environment_resource_one(&EnvironmentResourceOpt {
+                org_id: Some(org_id),
+                id: Some(resource_id),
+                ..EnvironmentResourceOpt::empty()
+            })
+            .await?
+            .ok_or_else(|| ErrNo::CommonError(LightString::from_static("该环境资源不存在")))?;
+        let existed_configuration =
+            context.decrypt_configuration(&environment_resource.extension_configuration)?;
+        restore_configuration(
+            &extension_info.configuration_schema,
+            &extension_configuration,
+            Some(&existed_configuration),
+        )?
+    } else {
+        extension_configuration
+    };
+    //和执行步骤一样，测试失败的原因里可能带有配置里的密码，需要替换成掩码
+    let (extension_configuration, secrets) = parse_extension_configuration(
+        &extension_info.configuration_schema,
         &extension_configuration,
-    )
-    .map_err(|err| -> ErrNo {
-        log::error!("扩展配置格式不正确：{}", err);
-        return ErrNo::CommonError(LightString::Static("扩展配置格式不正确"));
-    })?;
+        &[],
+    )?;
     extension
         .test_configuration(extension_configuration, context.get_extension_context())
         .await
-        .map_err(|err| ErrNo::CommonError(err.into()))?;
+        .map_err(|err| ErrNo::CommonError(redact_secrets(&err, &secrets).into()))?;
     return Ok(());
 }
@@ -0,0 +1,471 @@
+use super::super::environment::grant::check_environment_grant;
+use super::environment_lock::acquire_environment_lock;
+use super::environment_lock::LockHolder;
+use super::queue::notify_job_queue;
+use crate::context::Context;
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::environment::EnvironmentOpt;
+use crate::model::environment_grant::enums::GrantPermission;
+use crate::model::environment_grant::EnvironmentGrantOpt;
+use crate::model::job_record::enums::Status;
+use crate::model::job_record::JobRecordOpt;
+use crate::model::job_record::JobRecordProperty;
+use crate::model::job_record_approval::enums::Decision;
+use crate::model::job_record_approval::JobRecordApproval;
+use crate::model::job_record_approval::JobRecordApprovalOpt;
+use crate::model::job_step_record::enums::Status as StepStatus;
+use crate::model::job_step_record::JobStepRecordOpt;
+use crate::model::job_step_record::JobStepRecordProperty;
+use crate::model::job_step_resource_record::enums::Status as StepResourceStatus;
+use crate::model::job_step_resource_record::JobStepResourceRecordOpt;
+use crate::model::job_step_resource_record::JobStepResourceRecordProperty;
+use crate::model::user::enums::Role;
+use crate::model::user::UserOpt;
+use crate::service::base::EnvironmentBaseService;
+use crate::service::base::EnvironmentGrantBaseService;
+use crate::service::base::JobRecordApprovalBaseService;
+use crate::service::base::JobRecordBaseService;
+use crate::service::base::JobStepRecordBaseService;
+use crate::service::base::JobStepResourceRecordBaseService;
+use crate::service::base::UserBaseService;
+use crate::service::job_record::JobRecordService;
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
+use std::collections::HashSet;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+use tokio_postgres::Transaction;
+
+//每个事务最多处理的过期审批数
+const EXPIRE_BATCH_SIZE: u64 = 100;
+
+//能审批的人数，同一个人可能既有审批授权又是所有者，发起执行的人不能审批自己的执行
+fn count_approvers(approver_ids: Vec<Id>, requester: Option<Id>) -> usize {
+    let mut approver_ids: HashSet<Id> = approver_ids.into_iter().collect();
+    if let Some(requester) = requester {
+        approver_ids.remove(&requester);
+    }
+    return approver_ids.len();
+}
+
+//检查能否审批执行记录，decided_count是这个人已经审批过的次数
+fn check_decision(
+    status: Status,
+    created_by: Option<Id>,
+    approver_id: Id,
+    decided_count: u64,
+) -> Result<(), ErrNo> {
+    if Status::PendingApproval != status {
+        return Err(ErrNo::CommonError(LightString::from_static(
+            "该执行记录不在等待审批",
+        )));
+    }
+    if Some(approver_id) == created_by {
+        return Err(ErrNo::CommonError(LightString::from_static(
+            "不能审批自己发起的执行",
+        )));
+    }
+    if 0 < decided_count {
+        return Err(ErrNo::CommonError(LightString::from_static(
+            "你已经审批过该执行记录",
+        )));
+    }
+    return Ok(());
+}
+
+//通过的人数是否达到了要求
+fn is_approval_reached(approve_count: u64, required_approvals: Option<i32>) -> bool {
+    return (required_approvals.unwrap_or(0) as i64) <= (approve_count as i64);
+}
+
+//在这个时间之前发起、还没有审批完的执行记录已经过期
+fn approval_expire_before(curr_time: DateTime<Utc>, expire_hours: u32) -> DateTime<Utc> {
+    return curr_time - Duration::hours(expire_hours as i64);
+}
+
+//锁住执行记录之后再确认还在等待审批并且已经过期，避免和审批同时进行
+fn is_approval_expired(
+    status: Status,
+    created_time: &DateTime<Utc>,
+    created_before: &DateTime<Utc>,
+) -> bool {
+    return Status::PendingApproval == status && created_time < created_before;
+}
+
+/**
+ * 查询在环境上发起执行需要几个人审批，不需要审批时返回None
+ * 受保护并且设置了审批人数的环境才需要审批，发起执行的人不能审批自己的执行，能审批的人不够时直接拒绝发起
+ */
+pub async fn query_required_approvals(
+    transaction: &Transaction<'_>,
+    org_id: Id,
+    environment_id: Id,
+    requester: Option<Id>,
+) -> Result<Option<i32>, ErrNo> {
+    let environment_base_service = EnvironmentBaseService::new(transaction);
+    let environment_grant_base_service = EnvironmentGrantBaseService::new(transaction);
+    let user_base_service = UserBaseService::new(transaction);
+    let environment = environment_base_service
+        .query_environment_one(&EnvironmentOpt {
+            org_id: Some(org_id),
+            id: Some(environment_id),
+            ..EnvironmentOpt::empty()
+        })
+        .await?
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("目标环境不存在")))?;
+    if !environment.protected || environment.required_approvals <= 0 {
+        return Ok(None);
+    }
+    //有审批授权的用户和所有者都可以审批
+    let mut approver_ids: Vec<Id> = environment_grant_base_service
+        .query_environment_grant_batch(&EnvironmentGrantOpt {
+            org_id: Some(org_id),
+            environment_id: Some(environment_id),
+            permission: Some(GrantPermission::Approve),
+            ..EnvironmentGrantOpt::empty()
+        })
+        .await?
+        .into_iter()
+        .map(|environment_grant| environment_grant.user_id)
+        .collect();
+    approver_ids.extend(
+        user_base_service
+            .query_user_batch(&UserOpt {
+                org_id: Some(org_id),
+                role: Some(Role::Owner),
+                ..UserOpt::empty()
+            })
+            .await?
+            .into_iter()
+            .map(|user| user.id),
+    );
+    let approver_count = count_approvers(approver_ids, requester);
+    if (approver_count as i32) < environment.required_approvals {
+        return Err(ErrNo::CommonError(LightString::from(format!(
+            "环境\"{}\"需要{}个人审批之后才能执行，但是能审批的人只有{}个，请联系组织的所有者或者管理员授权！",
+            environment.name,
+            environment.required_approvals,
+            approver_count
+        ))));
+    }
+    return Ok(Some(environment.required_approvals));
+}
+
+/**
+ * 审批等待中的执行记录，任何一个人驳回都会结束这次执行，通过的人数达到要求之后放入任务队列
+ */
+pub async fn decide_job_record(
+    org_id: Id,
+    user: User,
+    record_id: Id,
+    decision: Decision,
+    comment: Option<String>,
+) -> Result<(), ErrNo> {
+    let comment = comment
+        .map(|comment| comment.trim().to_string())
+        .filter(|comment| !comment.is_empty());
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_record_base_service = JobRecordBaseService::new(&transaction);
+    let job_record_approval_base_service = JobRecordApprovalBaseService::new(&transaction);
+    let user_base_service = UserBaseService::new(&transaction);
+    let curr_time = Utc::now();
+    //先更新执行记录，锁住这一行，避免同时审批
+    job_record_base_service
+        .update_job_record(record_id, &[JobRecordProperty::LastModifiedTime(curr_time)])
+        .await?;
+    let job_record = job_record_base_service
+        .query_job_record_one(&JobRecordOpt {
+            org_id: Some(org_id),
+            id: Some(record_id),
+            ..JobRecordOpt::empty()
+        })
+        .await?
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("该任务执行记录不存在")))?;
+    check_environment_grant(
+        &transaction,
+        org_id,
+        &user,
+        job_record.environment_id,
+        GrantPermission::Approve,
+    )
+    .await?;
+    let decided_count = job_record_approval_base_service
+        .query_job_record_approval_count(&JobRecordApprovalOpt {
+            org_id: Some(org_id),
+            record_id: Some(record_id),
+            approver_id: Some(user.user_id),
+            ..JobRecordApprovalOpt::empty()
+        })
+        .await?;
+    check_decision(
+        job_record.status,
+        job_record.created_by,
+        user.user_id,
+        decided_count,
+    )?;
+    job_record_approval_base_service
+        .insert_job_record_approval(&JobRecordApproval {
+            id: context.new_id(),
+            org_id: org_id,
+            record_id: record_id,
+            approver_id: user.user_id,
+            decision: decision,
+            comment: comment,
+            created_time: curr_time,
+            last_modified_time: curr_time,
+        })
+        .await?;
+    let queued = match decision {
+        Decision::Reject => {
+            close_pending_record(&transaction, org_id, record_id, Status::Rejected).await?;
+            false
+        }
+        Decision::Approve => {
+            let approve_count = job_record_approval_base_service
+                .query_job_record_approval_count(&JobRecordApprovalOpt {
+                    org_id: Some(org_id),
+                    record_id: Some(record_id),
+                    decision: Some(Decision::Approve),
+                    ..JobRecordApprovalOpt::empty()
+                })
+                .await?;
+            if !is_approval_reached(approve_count, job_record.required_approvals) {
+                false
+            } else {
+                //审批通过之后才占用环境，和直接发起的执行一样排队
+                let holder_name = match job_record.created_by {
+                    Some(created_by) => user_base_service
+                        .read_user(created_by)
+                        .await?
+                        .map(|user| user.name)
+                        .unwrap_or_else(|| created_by.to_string()),
+                    None => String::from("定时任务"),
+                };
+                acquire_environment_lock(
+                    &context,
+                    &transaction,
+                    org_id,
+                    job_record.environment_id,
+                    job_record.job_id,
+                    record_id,
+                    LockHolder {
+                        holder_id: job_record.created_by,
+                        holder_name: holder_name,
+                    },
+                )
+                .await?;
+                job_record_base_service
+                    .update_job_record(
+                        record_id,
+                        &[
+                            JobRecordProperty::Status(Status::Queued),
+                            JobRecordProperty::LastModifiedTime(curr_time),
+                        ],
+                    )
+                    .await?;
+                true
+            }
+        }
+    };
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    if queued {
+        notify_job_queue();
+    }
+    return Ok(());
+}
+
+/**
+ * 结束还在等待审批的执行记录，没有开始的步骤都标记为已取消
+ */
+async fn close_pending_record(
+    transaction: &Transaction<'_>,
+    org_id: Id,
+    record_id: Id,
+    status: Status,
+) -> Result<(), ErrNo> {
+    let job_record_base_service = JobRecordBaseService::new(transaction);
+    let job_step_record_base_service = JobStepRecordBaseService::new(transaction);
+    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(transaction);
+    let curr_time = Utc::now();
+    let job_step_record_list = job_step_record_base_service
+        .query_job_step_record_batch(&JobStepRecordOpt {
+            org_id: Some(org_id),
+            record_id: Some(record_id),
+            status: Some(StepStatus::Pending),
+            ..JobStepRecordOpt::empty()
+        })
+        .await?;
+    for job_step_record in job_step_record_list {
+        job_step_record_base_service
+            .update_job_step_record(
+                job_step_record.id,
+                &[
+                    JobStepRecordProperty::Status(StepStatus::Cancelled),
+                    JobStepRecordProperty::LastModifiedTime(curr_time),
+                ],
+            )
+            .await?;
+    }
+    let job_step_resource_record_list = job_step_resource_record_base_service
+        .query_job_step_resource_record_batch(&JobStepResourceRecordOpt {
+            org_id: Some(org_id),
+            record_id: Some(record_id),
+            status: Some(StepResourceStatus::Pending),
+            ..JobStepResourceRecordOpt::empty()
+        })
+        .await?;
+    for job_step_resource_record in job_step_resource_record_list {
+        job_step_resource_record_base_service
+            .update_job_step_resource_record(
+                job_step_resource_record.id,
+                &[
+                    JobStepResourceRecordProperty::Status(StepResourceStatus::Cancelled),
+                    JobStepResourceRecordProperty::LastModifiedTime(curr_time),
+                ],
+            )
+            .await?;
+    }
+    job_record_base_service
+        .update_job_record(
+            record_id,
+            &[
+                JobRecordProperty::Status(status),
+                JobRecordProperty::LastModifiedTime(curr_time),
+            ],
+        )
+        .await?;
+    return Ok(());
+}
+
+/**
+ * 把超过有效期还没有审批完的执行记录标记为审批已过期，返回过期的记录数
+ */
+pub async fn expire_pending_approvals(context: &Context, expire_hours: u32) -> Result<u64, ErrNo> {
+    let created_before = approval_expire_before(Utc::now(), expire_hours);
+    let mut count = 0;
+    loop {
+        let mut client = context.get_db_client().await?;
+        let transaction = client.transaction().await.map_err(open_transaction_error)?;
+        let job_record_base_service = JobRecordBaseService::new(&transaction);
+        let job_record_service = JobRecordService::new(&transaction);
+        let record_ids = job_record_service
+            .query_stale_pending_approval_ids(&created_before, EXPIRE_BATCH_SIZE)
+            .await?;
+        if record_ids.is_empty() {
+            break;
+        }
+        let batch_count = record_ids.len() as u64;
+        for record_id in record_ids {
+            //先锁住执行记录，再确认还在等待审批并且已经过期
+            job_record_base_service
+                .update_job_record(
+                    record_id,
+                    &[JobRecordProperty::LastModifiedTime(Utc::now())],
+                )
+                .await?;
+            let job_record = job_record_base_service.read_job_record(record_id).await?;
+            let job_record = match job_record {
+                Some(job_record)
+                    if is_approval_expired(
+                        job_record.status,
+                        &job_record.created_time,
+                        &created_before,
+                    ) =>
+                {
+                    job_record
+                }
+                _ => continue,
+            };
+            close_pending_record(&transaction, job_record.org_id, record_id, Status::Expired)
+                .await?;
+            count += 1;
+        }
+        transaction
+            .commit()
+            .await
+            .map_err(commit_transaction_error)?;
+        if batch_count < EXPIRE_BATCH_SIZE {
+            break;
+        }
+    }
+    return Ok(count);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count_approvers() {
+        let requester = Id::from_u128(1);
+        let approver_ids = vec![
+            Id::from_u128(1),
+            Id::from_u128(2),
+            Id::from_u128(2),
+            Id::from_u128(3),
+        ];
+        assert_eq!(3, count_approvers(approver_ids.clone(), None));
+        //发起执行的人不算
+        assert_eq!(2, count_approvers(approver_ids, Some(requester)));
+        assert_eq!(0, count_approvers(vec![requester], Some(requester)));
+    }
+
+    #[test]
+    fn test_check_decision() {
+        let requester = Id::from_u128(1);
+        let approver = Id::from_u128(2);
+        assert!(check_decision(Status::PendingApproval, Some(requester), approver, 0).is_ok());
+        //定时触发的执行没有发起人
+        assert!(check_decision(Status::PendingApproval, None, approver, 0).is_ok());
+        //不能审批自己发起的执行
+        assert!(check_decision(Status::PendingApproval, Some(requester), requester, 0).is_err());
+        //不能重复审批
+        assert!(check_decision(Status::PendingApproval, Some(requester), approver, 1).is_err());
+        //已经结束审批的执行记录不能再审批
+        assert!(check_decision(Status::Queued, Some(requester), approver, 0).is_err());
+        assert!(check_decision(Status::Expired, Some(requester), approver, 0).is_err());
+    }
+
+    #[test]
+    fn test_is_approval_reached() {
+        assert!(!is_approval_reached(1, Some(2)));
+        assert!(is_approval_reached(2, Some(2)));
+        assert!(is_approval_reached(3, Some(2)));
+        assert!(is_approval_reached(0, None));
+    }
+
+    #[test]
+    fn test_is_approval_expired() {
+        let curr_time = Utc::now();
+        let created_before = approval_expire_before(curr_time, 24);
+        assert_eq!(curr_time - Duration::hours(24), created_before);
+        let stale_time = curr_time - Duration::hours(25);
+        let fresh_time = curr_time - Duration::hours(23);
+        assert!(is_approval_expired(
+            Status::PendingApproval,
+            &stale_time,
+            &created_before
+        ));
+        assert!(!is_approval_expired(
+            Status::PendingApproval,
+            &fresh_time,
+            &created_before
+        ));
+        //过期检查时已经审批完的不再处理
+        assert!(!is_approval_expired(
+            Status::Queued,
+            &stale_time,
+            &created_before
+        ));
+    }
+}
@@ -0,0 +1,297 @@
+use super::super::environment::grant::check_environment_grant;
+use super::super::job_record::merge_step_and_resource_record;
+use super::environment_lock::release_environment_lock;
+use super::queue::notify_job_queue;
+use super::salvage_outputs;
+use super::unregister_running_job;
+use super::StepRecord;
+use super::RUNNING_JOBS;
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::environment_grant::enums::GrantPermission;
+use crate::model::job_record::enums::Status;
+use crate::model::job_record::JobRecordOpt;
+use crate::model::job_record::JobRecordProperty;
+use crate::model::job_step_record::enums::Status as StepStatus;
+use crate::model::job_step_record::JobStepRecordOpt;
+use crate::model::job_step_record::JobStepRecordProperty;
+use crate::model::job_step_resource_record::enums::Status as StepResourceStatus;
+use crate::model::job_step_resource_record::JobStepResourceRecordOpt;
+use crate::model::job_step_resource_record::JobStepResourceRecordProperty;
+use crate::sdk;
+use crate::service::base::JobRecordBaseService;
+use crate::service::base::JobStepRecordBaseService;
+use crate::service::base::JobStepResourceRecordBaseService;
+use crate::service::environment::EnvironmentService;
+use crate::Context;
+use chrono::Utc;
+use sdk::job::cancel_job::CancelJobReq;
+use sdk::job::cancel_job::CancelJobResp;
+use std::time::Duration;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+use tokio::task::AbortHandle;
+use tokio::time::timeout;
+
+//发出取消信号之后，等待扩展自行清理的最长时间
+const CANCEL_WAIT_SECONDS: u64 = 10;
+//检查任务是否已经结束的间隔
+const FINISH_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+pub async fn cancel_job(
+    org_id: Id,
+    user: User,
+    cancel_job_req: CancelJobReq,
+) -> Result<CancelJobResp, ErrNo> {
+    let CancelJobReq { record_id } = cancel_job_req;
+    let context = get_context()?;
+    let (environment_id, queued) = {
+        let mut client = context.get_db_client().await?;
+        let transaction = client.transaction().await.map_err(open_transaction_error)?;
+        let job_record_base_service = JobRecordBaseService::new(&transaction);
+        let job_record = job_record_base_service
+            .query_job_record_one(&JobRecordOpt {
+                org_id: Some(org_id),
+                id: Some(record_id),
+                ..JobRecordOpt::empty()
+            })
+            .await?
+            .ok_or_else(|| ErrNo::CommonError(LightString::from_static("该任务执行记录不存在")))?;
+        //受保护的环境上，有执行授权的用户才能取消执行
+        check_environment_grant(
+            &transaction,
+            org_id,
+            &user,
+            job_record.environment_id,
+            GrantPermission::Execute,
+        )
+        .await?;
+        if Status::Running != job_record.status
+            && Status::Queued != job_record.status
+            && Status::PendingApproval != job_record.status
+        {
+            return Err(ErrNo::CommonError(LightString::from_static(
+                "不是执行中的任务",
+            )));
+        }
+        let queued =
+            Status::Queued == job_record.status || Status::PendingApproval == job_record.status;
+        //由其它实例执行的记录，只标记请求取消，由执行的实例续约时取消，执行的实例已经退出时由接手的实例取消
+        let run_elsewhere = job_record
+            .instance_id
+            .map(|instance_id| context.get_instance_id() != instance_id)
+            .unwrap_or(false);
+        if !queued && run_elsewhere {
+            job_record_base_service
+                .update_job_record(
+                    record_id,
+                    &[
+                        JobRecordProperty::CancelRequested(true),
+                        JobRecordProperty::LastModifiedTime(Utc::now()),
+                    ],
+                )
+                .await?;
+            transaction
+                .commit()
+                .await
+                .map_err(commit_transaction_error)?;
+            return Ok(());
+        }
+        (job_record.environment_id, queued)
+    };
+    return cancel_job_record(&context, org_id, record_id, environment_id, queued).await;
+}
+
+/**
+ * 处理其它实例转过来的取消请求，执行记录由本实例执行
+ */
+pub async fn cancel_requested_job_record(context: &Context, record_id: Id) -> Result<(), ErrNo> {
+    let job_record = {
+        let mut client = context.get_db_client().await?;
+        let transaction = client.transaction().await.map_err(open_transaction_error)?;
+        let job_record_base_service = JobRecordBaseService::new(&transaction);
+        job_record_base_service.read_job_record(record_id).await?
+    };
+    if let Some(job_record) = job_record {
+        cancel_job_record(
+            context,
+            job_record.org_id,
+            record_id,
+            job_record.environment_id,
+            false,
+        )
+        .await?;
+    }
+    return Ok(());
+}
+
+//本实例正在执行时先通知执行的任务取消，再把未结束的步骤和执行记录标记为已取消
+async fn cancel_job_record(
+    context: &Context,
+    org_id: Id,
+    record_id: Id,
+    environment_id: Id,
+    queued: bool,
+) -> Result<(), ErrNo> {
+    stop_running_job(record_id).await;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_record_base_service = JobRecordBaseService::new(&transaction);
+    let job_step_record_base_service = JobStepRecordBaseService::new(&transaction);
+    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
+    //先锁住环境，再锁住执行记录，避免排队的任务在取消的同时被任务队列开始执行
+    EnvironmentService::new(&transaction)
+        .lock_environment(&environment_id)
+        .await?;
+    let curr_time = Utc::now();
+    job_record_base_service
+        .update_job_record(record_id, &[JobRecordProperty::LastModifiedTime(curr_time)])
+        .await?;
+    let job_record = job_record_base_service
+        .query_job_record_one(&JobRecordOpt {
+            org_id: Some(org_id),
+            id: Some(record_id),
+            ..JobRecordOpt::empty()
+        })
+        .await?
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("该任务执行记录不存在")))?;
+    if queued && Status::Running == job_record.status {
+        return Err(ErrNo::CommonError(LightString::from_static(
+            "任务已经开始执行，请重新取消",
+        )));
+    }
+    if Status::Running != job_record.status
+        && Status::Cancelled != job_record.status
+        && Status::Queued != job_record.status
+        && Status::PendingApproval != job_record.status
+    {
+        //等待期间任务已经执行结束
+        return Ok(());
+    }
+    let job_step_record_list = job_step_record_base_service
+        .query_job_step_record_batch(&JobStepRecordOpt {
+            org_id: Some(org_id),
+            record_id: Some(record_id),
+            ..JobStepRecordOpt::empty()
+        })
+        .await?;
+    let job_step_resource_record_list = job_step_resource_record_base_service
+        .query_job_step_resource_record_batch(&JobStepResourceRecordOpt {
+            org_id: Some(org_id),
+            record_id: Some(record_id),
+            ..JobStepResourceRecordOpt::empty()
+        })
+        .await?;
+    let step_record_list =
+        merge_step_and_resource_record(job_step_record_list, job_step_resource_record_list);
+    //残留的日志文件在事务提交之后再保存
+    let mut salvage_list = Vec::new();
+    //把未结束的步骤都标记为已取消
+    for step_record in step_record_list {
+        let job_step_record = match step_record {
+            StepRecord::Auto {
+                job_step_record,
+                step_resource_record_list,
+            } => {
+                for step_resource_record in step_resource_record_list {
+                    if StepResourceStatus::Pending != step_resource_record.status
+                        && StepResourceStatus::Running != step_resource_record.status
+                    {
+                        continue;
+                    }
+                    let changes: Vec<JobStepResourceRecordProperty> = vec![
+                        JobStepResourceRecordProperty::Status(StepResourceStatus::Cancelled),
+                        JobStepResourceRecordProperty::LastModifiedTime(curr_time),
+                    ];
+                    //被强制终止的执行还残留着日志文件
+                    if step_resource_record.output_content.is_none()
+                        && step_resource_record.log_key.is_none()
+                    {
+                        if let Some(output_file) = step_resource_record.output_file.as_ref() {
+                            salvage_list.push((
+                                step_resource_record.org_id,
+                                step_resource_record.id,
+                                format!("{}/{}", context.config.job_log_dir, output_file),
+                            ));
+                        }
+                    }
+                    job_step_resource_record_base_service
+                        .update_job_step_resource_record(step_resource_record.id, &changes)
+                        .await?;
+                }
+                job_step_record
+            }
+            StepRecord::Manual { job_step_record } => job_step_record,
+        };
+        if StepStatus::Pending == job_step_record.status
+            || StepStatus::Running == job_step_record.status
+        {
+            let changes: Vec<JobStepRecordProperty> = vec![
+                JobStepRecordProperty::Status(StepStatus::Cancelled),
+                JobStepRecordProperty::LastModifiedTime(curr_time),
+            ];
+            job_step_record_base_service
+                .update_job_step_record(job_step_record.id, &changes)
+                .await?;
+        }
+    }
+    let changes: Vec<JobRecordProperty> = vec![
+        JobRecordProperty::Status(Status::Cancelled),
+        JobRecordProperty::LastModifiedTime(curr_time),
+    ];
+    job_record_base_service
+        .update_job_record(record_id, &changes)
+        .await?;
+    release_environment_lock(&transaction, record_id).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    notify_job_queue();
+    salvage_outputs(context, &salvage_list, "执行被取消").await;
+    return Ok(());
+}
+
+//通知正在执行的任务取消，等待任务结束，超时再强制终止，任务结束之前保留登记，避免同一条记录被重新开始执行
+async fn stop_running_job(record_id: Id) {
+    let running_job = RUNNING_JOBS
+        .lock()
+        .unwrap()
+        .get(&record_id)
+        .map(|running_job| {
+            running_job.cancel_sender.send(true).ok();
+            (
+                running_job.abort_handle.clone(),
+                running_job.cancel_sender.subscribe(),
+            )
+        });
+    let (abort_handle, own_receiver) = match running_job {
+        Some(running_job) => running_job,
+        None => return,
+    };
+    //先给扩展清理的机会，超时再强制终止
+    if timeout(
+        Duration::from_secs(CANCEL_WAIT_SECONDS),
+        wait_finished(&abort_handle),
+    )
+    .await
+    .is_err()
+    {
+        log::warn!("任务{}未能在限定时间内结束，强制终止", record_id);
+        abort_handle.abort();
+        wait_finished(&abort_handle).await;
+        //被强制终止的任务没有机会移除自己的登记
+        let mut running_jobs = RUNNING_JOBS.lock().unwrap();
+        unregister_running_job(&mut running_jobs, record_id, &own_receiver);
+    }
+}
+
+async fn wait_finished(abort_handle: &AbortHandle) {
+    let mut interval = tokio::time::interval(FINISH_CHECK_INTERVAL);
+    while !abort_handle.is_finished() {
+        interval.tick().await;
+    }
+}
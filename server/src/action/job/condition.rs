@@ -0,0 +1,451 @@
+use crate::sdk;
+use sdk::extension::Attribute;
+use serde_json::Map;
+use serde_json::Value;
+use tihu::LightString;
+use tihu_native::ErrNo;
+
+//条件表达式中引用执行参数的前缀，如params.confirm
+const PARAMS_PREFIX: &str = "params.";
+//条件表达式中引用目标环境名称的标识
+const ENV_NAME: &str = "env";
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    LeftParen,
+    RightParen,
+    And,
+    Or,
+    Not,
+    Eq,
+    Ne,
+    Ident(String),
+    Literal(Value),
+}
+
+#[derive(Debug)]
+enum Operand {
+    Env,
+    Param(String),
+    Literal(Value),
+}
+
+/**
+ * 步骤的执行条件，如 env == "prod" && params.confirm
+ */
+#[derive(Debug)]
+enum Condition {
+    Or(Box<Condition>, Box<Condition>),
+    And(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+    Eq(Operand, Operand),
+    Ne(Operand, Operand),
+    Test(Operand),
+}
+
+fn tokenize(expression: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = expression.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' | '\t' | '\r' | '\n' => (),
+            '(' => tokens.push(Token::LeftParen),
+            ')' => tokens.push(Token::RightParen),
+            '&' | '|' | '=' => {
+                if chars.next() != Some(c) {
+                    return Err(format!("无法识别的运算符\"{}\"", c));
+                }
+                tokens.push(match c {
+                    '&' => Token::And,
+                    '|' => Token::Or,
+                    _ => Token::Eq,
+                });
+            }
+            '!' => {
+                if chars.peek() == Some(&'=') {
+                    chars.next();
+                    tokens.push(Token::Ne);
+                } else {
+                    tokens.push(Token::Not);
+                }
+            }
+            '"' | '\'' => {
+                let mut text = String::new();
+                loop {
+                    match chars.next() {
+                        Some('\\') => match chars.next() {
+                            Some(escaped) => text.push(escaped),
+                            None => return Err(String::from("字符串没有结束")),
+                        },
+                        Some(next) if next == c => break,
+                        Some(next) => text.push(next),
+                        None => return Err(String::from("字符串没有结束")),
+                    }
+                }
+                tokens.push(Token::Literal(Value::String(text)));
+            }
+            _ if c.is_ascii_alphanumeric() || '_' == c || '-' == c => {
+                let mut word = String::from(c);
+                while let Some(next) = chars.peek() {
+                    if next.is_ascii_alphanumeric() || '_' == *next || '.' == *next {
+                        word.push(*next);
+                        chars.next();
+                    } else {
+                        break;
+                    }
+                }
+                if c.is_ascii_digit() || '-' == c {
+                    let number = serde_json::from_str::<Value>(&word)
+                        .ok()
+                        .filter(|number| number.is_number())
+                        .ok_or_else(|| format!("无法识别的数字\"{}\"", word))?;
+                    tokens.push(Token::Literal(number));
+                } else {
+                    tokens.push(match word.as_str() {
+                        "true" => Token::Literal(Value::Bool(true)),
+                        "false" => Token::Literal(Value::Bool(false)),
+                        "null" => Token::Literal(Value::Null),
+                        _ => Token::Ident(word),
+                    });
+                }
+            }
+            _ => return Err(format!("无法识别的字符\"{}\"", c)),
+        }
+    }
+    return Ok(tokens);
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        return self.tokens.get(self.pos);
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        if token.is_some() {
+            self.pos += 1;
+        }
+        return token;
+    }
+
+    fn parse_or(&mut self) -> Result<Condition, String> {
+        let mut condition = self.parse_and()?;
+        while Some(&Token::Or) == self.peek() {
+            self.next();
+            let right = self.parse_and()?;
+            condition = Condition::Or(Box::new(condition), Box::new(right));
+        }
+        return Ok(condition);
+    }
+
+    fn parse_and(&mut self) -> Result<Condition, String> {
+        let mut condition = self.parse_unary()?;
+        while Some(&Token::And) == self.peek() {
+            self.next();
+            let right = self.parse_unary()?;
+            condition = Condition::And(Box::new(condition), Box::new(right));
+        }
+        return Ok(condition);
+    }
+
+    fn parse_unary(&mut self) -> Result<Condition, String> {
+        match self.peek() {
+            Some(Token::Not) => {
+                self.next();
+                return Ok(Condition::Not(Box::new(self.parse_unary()?)));
+            }
+            Some(Token::LeftParen) => {
+                self.next();
+                let condition = self.parse_or()?;
+                if Some(Token::RightParen) != self.next() {
+                    return Err(String::from("缺少右括号"));
+                }
+                return Ok(condition);
+            }
+            _ => {
+                let left = self.parse_operand()?;
+                match self.peek() {
+                    Some(Token::Eq) => {
+                        self.next();
+                        return Ok(Condition::Eq(left, self.parse_operand()?));
+                    }
+                    Some(Token::Ne) => {
+                        self.next();
+                        return Ok(Condition::Ne(left, self.parse_operand()?));
+                    }
+                    _ => return Ok(Condition::Test(left)),
+                }
+            }
+        }
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand, String> {
+        match self.next() {
+            Some(Token::Literal(value)) => Ok(Operand::Literal(value)),
+            Some(Token::Ident(ident)) => {
+                if ENV_NAME == ident {
+                    Ok(Operand::Env)
+                } else if let Some(name) = ident.strip_prefix(PARAMS_PREFIX) {
+                    Ok(Operand::Param(name.to_string()))
+                } else {
+                    Err(format!(
+                        "无法识别的标识\"{}\"，只能引用env或者params.参数标识",
+                        ident
+                    ))
+                }
+            }
+            Some(_) => Err(String::from("运算符的位置不正确")),
+            None => Err(String::from("表达式不完整")),
+        }
+    }
+}
+
+fn parse_condition(expression: &str) -> Result<Condition, String> {
+    let mut parser = Parser {
+        tokens: tokenize(expression)?,
+        pos: 0,
+    };
+    let condition = parser.parse_or()?;
+    if parser.peek().is_some() {
+        return Err(String::from("表达式末尾有多余的内容"));
+    }
+    return Ok(condition);
+}
+
+fn collect_operand_refs<'a>(operand: &'a Operand, param_refs: &mut Vec<&'a str>) {
+    if let Operand::Param(name) = operand {
+        param_refs.push(name);
+    }
+}
+
+fn collect_param_refs<'a>(condition: &'a Condition, param_refs: &mut Vec<&'a str>) {
+    match condition {
+        Condition::Or(left, right) | Condition::And(left, right) => {
+            collect_param_refs(left, param_refs);
+            collect_param_refs(right, param_refs);
+        }
+        Condition::Not(condition) => collect_param_refs(condition, param_refs),
+        Condition::Eq(left, right) | Condition::Ne(left, right) => {
+            collect_operand_refs(left, param_refs);
+            collect_operand_refs(right, param_refs);
+        }
+        Condition::Test(operand) => collect_operand_refs(operand, param_refs),
+    }
+}
+
+fn operand_value(
+    operand: &Operand,
+    environment_name: &str,
+    params: &Map<String, Value>,
+) -> Result<Value, String> {
+    match operand {
+        Operand::Env => Ok(Value::String(environment_name.to_string())),
+        Operand::Param(name) => params
+            .get(name)
+            .cloned()
+            .ok_or_else(|| format!("未定义的参数\"{}\"", name)),
+        Operand::Literal(value) => Ok(value.clone()),
+    }
+}
+
+//空值、false、空文本、0以及空列表都视为不满足
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(value) => *value,
+        Value::Number(value) => value.as_f64().map(|value| 0.0 != value).unwrap_or(true),
+        Value::String(value) => !value.is_empty(),
+        Value::Array(value) => !value.is_empty(),
+        Value::Object(_) => true,
+    }
+}
+
+fn is_equal(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        //数字统一按照浮点数比较，避免1和1.0不相等
+        (Value::Number(left), Value::Number(right)) => left.as_f64() == right.as_f64(),
+        _ => left == right,
+    }
+}
+
+fn evaluate(
+    condition: &Condition,
+    environment_name: &str,
+    params: &Map<String, Value>,
+) -> Result<bool, String> {
+    match condition {
+        Condition::Or(left, right) => {
+            Ok(evaluate(left, environment_name, params)?
+                || evaluate(right, environment_name, params)?)
+        }
+        Condition::And(left, right) => {
+            Ok(evaluate(left, environment_name, params)?
+                && evaluate(right, environment_name, params)?)
+        }
+        Condition::Not(condition) => Ok(!evaluate(condition, environment_name, params)?),
+        Condition::Eq(left, right) => Ok(is_equal(
+            &operand_value(left, environment_name, params)?,
+            &operand_value(right, environment_name, params)?,
+        )),
+        Condition::Ne(left, right) => Ok(!is_equal(
+            &operand_value(left, environment_name, params)?,
+            &operand_value(right, environment_name, params)?,
+        )),
+        Condition::Test(operand) => Ok(is_truthy(&operand_value(
+            operand,
+            environment_name,
+            params,
+        )?)),
+    }
+}
+
+//检查步骤的条件表达式是否合格，以及引用的执行参数是否都已定义
+pub fn check_run_expression(
+    step_name: &str,
+    run_expression: &str,
+    parameter_schema: &[Attribute],
+) -> Result<(), ErrNo> {
+    let condition = parse_condition(run_expression).map_err(|err| {
+        ErrNo::CommonError(LightString::from(format!(
+            "步骤\"{}\"的条件表达式不正确：{}",
+            step_name, err
+        )))
+    })?;
+    let mut param_refs = Vec::new();
+    collect_param_refs(&condition, &mut param_refs);
+    for param_ref in param_refs {
+        if !parameter_schema
+            .iter()
+            .any(|attribute| attribute.id == param_ref)
+        {
+            return Err(ErrNo::CommonError(LightString::from(format!(
+                "步骤\"{}\"的条件表达式引用了未定义的参数\"{}\"",
+                step_name, param_ref
+            ))));
+        }
+    }
+    return Ok(());
+}
+
+//用目标环境名称和执行参数计算步骤的条件表达式，返回步骤是否需要执行
+pub fn eval_run_expression(
+    step_name: &str,
+    run_expression: &str,
+    environment_name: &str,
+    params: &Map<String, Value>,
+) -> Result<bool, ErrNo> {
+    return parse_condition(run_expression)
+        .and_then(|condition| evaluate(&condition, environment_name, params))
+        .map_err(|err| {
+            ErrNo::CommonError(LightString::from(format!(
+                "步骤\"{}\"的条件表达式计算失败：{}",
+                step_name, err
+            )))
+        });
+}
+
+//条件表达式不满足时记录的跳过原因，重新执行时也据此判断步骤是否是因为条件不满足而跳过的
+pub fn condition_skip_reason(run_expression: &str) -> String {
+    return format!("执行条件\"{}\"不满足", run_expression);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sdk::extension::AttributeType;
+    use serde_json::json;
+
+    fn eval(run_expression: &str, environment_name: &str, params: Value) -> Result<bool, ErrNo> {
+        let params = match params {
+            Value::Object(params) => params,
+            _ => Map::new(),
+        };
+        return eval_run_expression("deploy", run_expression, environment_name, &params);
+    }
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            vec![
+                Token::Ident(String::from("env")),
+                Token::Ne,
+                Token::Literal(json!("it's")),
+                Token::And,
+                Token::Not,
+                Token::LeftParen,
+                Token::Ident(String::from("params.count")),
+                Token::Eq,
+                Token::Literal(json!(-1.5)),
+                Token::RightParen,
+            ],
+            tokenize(r#"env != 'it\'s' && !(params.count == -1.5)"#).unwrap()
+        );
+        assert!(tokenize("env = 'prod'").is_err());
+        assert!(tokenize("env == 'prod").is_err());
+        assert!(tokenize("params.count > 1").is_err());
+        assert!(tokenize("1.2.3").is_err());
+    }
+
+    #[test]
+    fn test_eval_run_expression() {
+        let params = json!({
+            "confirm": true,
+            "count": 1,
+            "tag": "",
+            "targets": ["a"],
+            "skip": null,
+        });
+        assert!(eval(r#"env == "prod""#, "prod", params.clone()).unwrap());
+        assert!(!eval(r#"env == "prod""#, "test", params.clone()).unwrap());
+        assert!(eval("params.confirm && params.targets", "prod", params.clone()).unwrap());
+        assert!(!eval("params.tag || params.skip", "prod", params.clone()).unwrap());
+        assert!(eval("!params.tag", "prod", params.clone()).unwrap());
+        //数字按照数值比较
+        assert!(eval("params.count == 1.0", "prod", params.clone()).unwrap());
+        assert!(eval("params.count != 2", "prod", params.clone()).unwrap());
+        assert!(eval("params.skip == null", "prod", params.clone()).unwrap());
+    }
+
+    #[test]
+    fn test_eval_precedence() {
+        let params = json!({"a": true, "b": false, "c": false});
+        //&&的优先级高于||
+        assert!(eval("params.a || params.b && params.c", "prod", params.clone()).unwrap());
+        assert!(!eval("(params.a || params.b) && params.c", "prod", params.clone()).unwrap());
+        assert!(eval("!params.b && !params.c", "prod", params.clone()).unwrap());
+    }
+
+    #[test]
+    fn test_eval_error() {
+        assert!(eval("params.other", "prod", json!({})).is_err());
+        assert!(eval("(env == 'prod'", "prod", json!({})).is_err());
+        assert!(eval("env == 'prod' env", "prod", json!({})).is_err());
+        assert!(eval("env ==", "prod", json!({})).is_err());
+        assert!(eval("other", "prod", json!({})).is_err());
+        assert!(eval("&& env", "prod", json!({})).is_err());
+    }
+
+    #[test]
+    fn test_check_run_expression() {
+        let parameter_schema = [Attribute {
+            id: String::from("confirm"),
+            name: String::from("确认"),
+            description: None,
+            required: false,
+            r#type: AttributeType::Bool,
+        }];
+        assert!(check_run_expression(
+            "deploy",
+            "env == 'prod' && params.confirm",
+            &parameter_schema
+        )
+        .is_ok());
+        assert!(check_run_expression("deploy", "params.other", &parameter_schema).is_err());
+        assert!(check_run_expression("deploy", "env ==", &parameter_schema).is_err());
+    }
+}
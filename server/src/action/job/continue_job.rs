@@ -1,18 +1,21 @@
-use super::super::job_record::merge_step_and_resource_record;
-use super::start_run;
-use super::StepRecord;
+use super::super::environment::grant::check_environment_grant;
+use super::resume_run;
+use super::signoff::can_signoff;
+use super::signoff::parse_signoff_users;
 use crate::get_context;
 use crate::middleware::auth::User;
-use crate::model::job_record::enums::Status as RecordStatus;
+use crate::model::environment_grant::enums::GrantPermission;
 use crate::model::job_record::JobRecordProperty;
 use crate::model::job_step_record::enums::Status;
+use crate::model::job_step_record::enums::StepType;
 use crate::model::job_step_record::JobStepRecordOpt;
 use crate::model::job_step_record::JobStepRecordProperty;
-use crate::model::job_step_resource_record::JobStepResourceRecordOpt;
+use crate::model::job_step_signoff::JobStepSignoff;
+use crate::model::job_step_signoff::JobStepSignoffOpt;
 use crate::sdk;
 use crate::service::base::JobRecordBaseService;
 use crate::service::base::JobStepRecordBaseService;
-use crate::service::base::JobStepResourceRecordBaseService;
+use crate::service::base::JobStepSignoffBaseService;
 use chrono::Utc;
 use sdk::job::continue_job::ContinueJobReq;
 use sdk::job::continue_job::ContinueJobResp;
@@ -24,19 +27,43 @@ use tihu_native::ErrNo;
 
 pub async fn continue_job(
     org_id: Id,
-    _user: User,
+    user: User,
     continue_job_req: ContinueJobReq,
 ) -> Result<ContinueJobResp, ErrNo> {
     let ContinueJobReq {
         record_id,
         step_record_id,
         success,
+        comment,
+        attachments,
     } = continue_job_req;
+    let comment = comment
+        .map(|comment| comment.trim().to_string())
+        .filter(|comment| !comment.is_empty());
+    let attachments = attachments.filter(|attachments| !attachments.is_empty());
     let context = get_context()?;
     let mut client = context.get_db_client().await?;
     let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_record_base_service = JobRecordBaseService::new(&transaction);
     let job_step_record_base_service = JobStepRecordBaseService::new(&transaction);
-    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
+    let job_step_signoff_base_service = JobStepSignoffBaseService::new(&transaction);
+    let curr_time = Utc::now();
+    //先更新执行记录锁住这一行，和执行实例交出执行记录互斥，顺序和执行过程一致，避免死锁
+    job_record_base_service
+        .update_job_record(record_id, &[JobRecordProperty::LastModifiedTime(curr_time)])
+        .await?;
+    let job_record = job_record_base_service
+        .read_job_record(record_id)
+        .await?
+        .filter(|job_record| org_id == job_record.org_id)
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("该执行记录不存在")))?;
+    //再更新步骤记录，锁住这一行，避免同时签收
+    job_step_record_base_service
+        .update_job_step_record(
+            step_record_id,
+            &[JobStepRecordProperty::LastModifiedTime(curr_time)],
+        )
+        .await?;
     //查询任务生成的执行记录
     let job_step_record_list = job_step_record_base_service
         .query_job_step_record_batch(&JobStepRecordOpt {
@@ -45,82 +72,116 @@ pub async fn continue_job(
             ..JobStepRecordOpt::empty()
         })
         .await?;
-    //查询任务生成的执行资源记录
-    let job_step_resource_record_list = job_step_resource_record_base_service
-        .query_job_step_resource_record_batch(&JobStepResourceRecordOpt {
+    //步骤按依赖关系执行，等待处理的手动步骤可能不止一个
+    let job_step_record = job_step_record_list
+        .into_iter()
+        .find(|job_step_record| {
+            step_record_id == job_step_record.id && StepType::Manual == job_step_record.step_type
+        })
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("该步骤不存在")))?;
+    check_environment_grant(
+        &transaction,
+        org_id,
+        &user,
+        job_step_record.environment_id,
+        GrantPermission::Approve,
+    )
+    .await?;
+    if Status::Running != job_step_record.status {
+        return Err(ErrNo::CommonError(LightString::from_static(
+            "不是进行中的步骤",
+        )));
+    }
+    let signoff_user_list = parse_signoff_users(job_step_record.signoff_users.as_deref())?;
+    if !can_signoff(
+        &user,
+        job_step_record.signoff_role,
+        signoff_user_list.as_deref(),
+    ) {
+        return Err(ErrNo::CommonError(LightString::from_static(
+            "你不是该步骤指定的签收人",
+        )));
+    }
+    let signed_count = job_step_signoff_base_service
+        .query_job_step_signoff_count(&JobStepSignoffOpt {
             org_id: Some(org_id),
-            record_id: Some(record_id.into()),
-            ..JobStepResourceRecordOpt::empty()
+            job_step_record_id: Some(step_record_id),
+            user_id: Some(user.user_id),
+            ..JobStepSignoffOpt::empty()
         })
         .await?;
-    let job_step_list =
-        merge_step_and_resource_record(job_step_record_list, job_step_resource_record_list);
-    let mut found = false;
-    let mut sub_job_step_list = Vec::new();
-    for job_step in job_step_list {
-        if found {
-            sub_job_step_list.push(job_step);
-        } else {
-            match job_step {
-                StepRecord::Manual { job_step_record } => {
-                    if step_record_id == job_step_record.id {
-                        if Status::Running == job_step_record.status {
-                            found = true;
-                            let curr_time = Utc::now();
-                            let changes: Vec<JobStepRecordProperty> = vec![
-                                JobStepRecordProperty::Status(if success {
-                                    Status::Success
-                                } else {
-                                    Status::Failure
-                                }),
-                                JobStepRecordProperty::LastModifiedTime(curr_time),
-                            ];
-                            job_step_record_base_service
-                                .update_job_step_record(job_step_record.id, &changes)
-                                .await?;
-                            if !success {
-                                //如果手动操作任务是失败，则不再操作
-                                break;
-                            }
-                        } else {
-                            return Err(ErrNo::CommonError(LightString::from_static(
-                                "不是进行中的步骤",
-                            )));
-                        }
-                    }
-                }
-                _ => (),
-            }
-        }
+    if 0 < signed_count {
+        return Err(ErrNo::CommonError(LightString::from_static(
+            "你已经签收过该步骤",
+        )));
     }
-    if found {
-        if !success {
-            let job_record_base_service = JobRecordBaseService::new(&transaction);
-            let curr_time = Utc::now();
-            let changes: Vec<JobRecordProperty> = vec![
-                JobRecordProperty::Status(RecordStatus::Failure),
-                JobRecordProperty::LastModifiedTime(curr_time),
-            ];
+    job_step_signoff_base_service
+        .insert_job_step_signoff(&JobStepSignoff {
+            id: context.new_id(),
+            org_id: org_id,
+            record_id: record_id,
+            job_step_record_id: step_record_id,
+            user_id: user.user_id,
+            success: success,
+            comment: comment,
+            attachments: attachments,
+            created_time: curr_time,
+            last_modified_time: curr_time,
+        })
+        .await?;
+    //任何一个人确认失败，步骤就失败，确认成功的人数达到要求之后步骤才成功
+    let completed = if success {
+        let success_count = job_step_signoff_base_service
+            .query_job_step_signoff_count(&JobStepSignoffOpt {
+                org_id: Some(org_id),
+                job_step_record_id: Some(step_record_id),
+                success: Some(true),
+                ..JobStepSignoffOpt::empty()
+            })
+            .await?;
+        (job_step_record.required_signoffs as i64) <= (success_count as i64)
+    } else {
+        true
+    };
+    //执行记录没有实例在执行（已经停下来等待人工处理）时，由本实例接手继续执行
+    let mut run_here = false;
+    if completed {
+        run_here = match job_record.instance_id {
+            Some(instance_id) => context.get_instance_id() == instance_id,
+            None => true,
+        };
+        if run_here {
             job_record_base_service
-                .update_job_record(record_id.into(), &changes)
+                .update_job_record(
+                    record_id,
+                    &[
+                        JobRecordProperty::InstanceId(Some(context.get_instance_id())),
+                        JobRecordProperty::HeartbeatTime(Some(curr_time)),
+                    ],
+                )
                 .await?;
         }
-        transaction
-            .commit()
-            .await
-            .map_err(commit_transaction_error)?;
-        if !success {
-            //如果手动操作任务是失败，则不再操作
-            return Ok(());
-        }
+        let changes: Vec<JobStepRecordProperty> = vec![
+            JobStepRecordProperty::Status(if success {
+                Status::Success
+            } else {
+                Status::Failure
+            }),
+            JobStepRecordProperty::CompletedBy(Some(user.user_id)),
+            JobStepRecordProperty::LastModifiedTime(curr_time),
+        ];
+        job_step_record_base_service
+            .update_job_step_record(job_step_record.id, &changes)
+            .await?;
     }
-    if !sub_job_step_list.is_empty() {
-        //还有后续步骤就继续执行
-        tokio::spawn(async move {
-            if let Err(err) = start_run(context, sub_job_step_list, record_id).await {
-                log::error!("执行任务发生错误, {:?}", err);
-            }
-        });
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    //由其它实例执行时，该实例在只剩手动步骤时会从数据库读取处理结果
+    if run_here {
+        //通知正在执行的任务，如果任务已经停下来等待，重新开始执行后续步骤，处理失败时由执行过程更新记录状态
+        resume_run(context, org_id, record_id, step_record_id, success);
     }
     return Ok(());
 }
@@ -1,17 +1,17 @@
+use super::super::job_record::purge_job_record::delete_job_records;
+use super::super::job_record::purge_job_record::remove_job_record_logs;
 use crate::get_context;
 use crate::middleware::auth::User;
 use crate::model::job::JobOpt;
 use crate::model::job_record::enums::Status;
 use crate::model::job_record::JobRecordOpt;
+use crate::model::job_schedule::JobScheduleOpt;
 use crate::model::job_step::JobStepOpt;
-use crate::model::job_step_record::JobStepRecordOpt;
-use crate::model::job_step_resource_record::JobStepResourceRecordOpt;
 use crate::sdk;
 use crate::service::base::JobBaseService;
 use crate::service::base::JobRecordBaseService;
+use crate::service::base::JobScheduleBaseService;
 use crate::service::base::JobStepBaseService;
-use crate::service::base::JobStepRecordBaseService;
-use crate::service::base::JobStepResourceRecordBaseService;
 use sdk::job::delete_job::DeleteJobReq;
 use tihu::Id;
 use tihu::LightString;
@@ -32,8 +32,7 @@ pub async fn delete_job(
     let job_base_service = JobBaseService::new(&transaction);
     let job_step_base_service = JobStepBaseService::new(&transaction);
     let job_record_base_service = JobRecordBaseService::new(&transaction);
-    let job_step_record_base_service = JobStepRecordBaseService::new(&transaction);
-    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
+    let job_schedule_base_service = JobScheduleBaseService::new(&transaction);
     let params = JobOpt {
         org_id: Some(org_id),
         id: Some(job_id),
@@ -51,9 +50,25 @@ pub async fn delete_job(
             ..JobRecordOpt::empty()
         })
         .await?;
-    if 0 < running_job_count {
+    let queued_job_count = job_record_base_service
+        .query_job_record_count(&JobRecordOpt {
+            org_id: Some(org_id),
+            job_id: Some(job_id.into()),
+            status: Some(Status::Queued),
+            ..JobRecordOpt::empty()
+        })
+        .await?;
+    let pending_approval_job_count = job_record_base_service
+        .query_job_record_count(&JobRecordOpt {
+            org_id: Some(org_id),
+            job_id: Some(job_id.into()),
+            status: Some(Status::PendingApproval),
+            ..JobRecordOpt::empty()
+        })
+        .await?;
+    if 0 < running_job_count || 0 < queued_job_count || 0 < pending_approval_job_count {
         return Err(ErrNo::CommonError(LightString::from_static(
-            "该任务正在执行，不能删除！",
+            "该任务正在执行或者等待审批，不能删除！",
         )));
     }
     let job_step_list = job_step_base_service
@@ -70,18 +85,11 @@ pub async fn delete_job(
             ..JobRecordOpt::empty()
         })
         .await?;
-    let job_step_record_list = job_step_record_base_service
-        .query_job_step_record_batch(&JobStepRecordOpt {
-            org_id: Some(org_id),
-            job_id: Some(job_id.into()),
-            ..JobStepRecordOpt::empty()
-        })
-        .await?;
-    let job_step_resource_record_list = job_step_resource_record_base_service
-        .query_job_step_resource_record_batch(&JobStepResourceRecordOpt {
+    let job_schedule_list = job_schedule_base_service
+        .query_job_schedule_batch(&JobScheduleOpt {
             org_id: Some(org_id),
             job_id: Some(job_id.into()),
-            ..JobStepResourceRecordOpt::empty()
+            ..JobScheduleOpt::empty()
         })
         .await?;
     let job_step_ids: Vec<_> = job_step_list
@@ -92,13 +100,9 @@ pub async fn delete_job(
         .into_iter()
         .map(|job_record| job_record.id)
         .collect();
-    let job_step_record_ids: Vec<_> = job_step_record_list
+    let job_schedule_ids: Vec<_> = job_schedule_list
         .into_iter()
-        .map(|job_step_record| job_step_record.id)
-        .collect();
-    let job_step_resource_record_ids: Vec<_> = job_step_resource_record_list
-        .into_iter()
-        .map(|job_step_resource_record| job_step_resource_record.id)
+        .map(|job_schedule| job_schedule.id)
         .collect();
     job_base_service.delete_job(job_id.into()).await?;
     if !job_step_ids.is_empty() {
@@ -106,24 +110,17 @@ pub async fn delete_job(
             .delete_job_step_batch(&job_step_ids)
             .await?;
     }
-    if !job_record_ids.is_empty() {
-        job_record_base_service
-            .delete_job_record_batch(&job_record_ids)
-            .await?;
-    }
-    if !job_step_record_ids.is_empty() {
-        job_step_record_base_service
-            .delete_job_step_record_batch(&job_step_record_ids)
-            .await?;
-    }
-    if !job_step_resource_record_ids.is_empty() {
-        job_step_resource_record_base_service
-            .delete_job_step_resource_record_batch(&job_step_resource_record_ids)
+    if !job_schedule_ids.is_empty() {
+        job_schedule_base_service
+            .delete_job_schedule_batch(&job_schedule_ids)
             .await?;
     }
+    //执行记录连同日志一起删除
+    let removed = delete_job_records(&transaction, org_id, &job_record_ids).await?;
     transaction
         .commit()
         .await
         .map_err(commit_transaction_error)?;
+    remove_job_record_logs(&context, removed).await;
     return Ok(());
 }
@@ -0,0 +1,297 @@
+use crate::middleware::auth::User;
+use crate::model::environment::enums::LockPolicy;
+use crate::model::environment_lock::enums::Status as LockStatus;
+use crate::model::environment_lock::EnvironmentLock;
+use crate::model::environment_lock::EnvironmentLockOpt;
+use crate::model::environment_lock::EnvironmentLockProperty;
+use crate::sdk;
+use crate::service::base::EnvironmentLockBaseService;
+use crate::service::base::JobBaseService;
+use crate::service::base::UserBaseService;
+use crate::service::environment::EnvironmentService;
+use crate::Context;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::ErrNo;
+use tokio_postgres::Transaction;
+
+/**
+ * 发起执行的人，占用环境时记录下来，其他人可以看到环境被谁占用
+ */
+pub struct LockHolder {
+    pub holder_id: Option<Id>, //发起执行的用户id，定时触发时为空
+    pub holder_name: String,   //发起执行的人
+}
+
+//手动发起的执行，用用户名称作为环境的占用人
+pub async fn user_lock_holder(
+    transaction: &Transaction<'_>,
+    user: &User,
+) -> Result<LockHolder, ErrNo> {
+    let user_base_service = UserBaseService::new(transaction);
+    let holder_name = user_base_service
+        .read_user(user.user_id)
+        .await?
+        .map(|user| user.name)
+        .unwrap_or_else(|| user.user_id.to_string());
+    return Ok(LockHolder {
+        holder_id: Some(user.user_id),
+        holder_name: holder_name,
+    });
+}
+
+/**
+ * 执行记录开始执行之前占用环境，环境已经被其它执行记录占用时，按照环境的设置拒绝执行或者排队等待
+ * 任务队列只会开始执行已经占用到环境的执行记录
+ */
+pub async fn acquire_environment_lock(
+    context: &Context,
+    transaction: &Transaction<'_>,
+    org_id: Id,
+    environment_id: Id,
+    job_id: Id,
+    record_id: Id,
+    holder: LockHolder,
+) -> Result<(), ErrNo> {
+    let environment_service = EnvironmentService::new(transaction);
+    let environment_lock_base_service = EnvironmentLockBaseService::new(transaction);
+    let environment = environment_service
+        .lock_environment(&environment_id)
+        .await?
+        .filter(|environment| org_id == environment.org_id)
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("目标环境不存在")))?;
+    let holding_lock = environment_lock_base_service
+        .query_environment_lock_one(&EnvironmentLockOpt {
+            environment_id: Some(environment_id),
+            status: Some(LockStatus::Holding),
+            ..EnvironmentLockOpt::empty()
+        })
+        .await?;
+    let status = match new_lock_status(holding_lock.is_some(), environment.lock_policy) {
+        Some(status) => status,
+        None => {
+            return Err(ErrNo::CommonError(LightString::from(format!(
+                "环境\"{}\"正在被{}发起的执行占用，请等执行结束之后再试",
+                environment.name,
+                holding_lock
+                    .map(|holding_lock| holding_lock.holder_name)
+                    .unwrap_or_default()
+            ))));
+        }
+    };
+    let curr_time = Utc::now();
+    environment_lock_base_service
+        .insert_environment_lock(&EnvironmentLock {
+            id: context.new_id(),
+            org_id: org_id,
+            environment_id: environment_id,
+            record_id: record_id,
+            job_id: job_id,
+            holder_id: holder.holder_id,
+            holder_name: holder.holder_name,
+            status: status,
+            created_time: curr_time,
+            last_modified_time: curr_time,
+        })
+        .await?;
+    return Ok(());
+}
+
+/**
+ * 执行记录结束或者被取消之后释放环境，并把环境交给排队最久的执行记录
+ * 接手环境的执行记录仍在排队，事务提交之后需要通知任务队列
+ */
+pub async fn release_environment_lock(
+    transaction: &Transaction<'_>,
+    record_id: Id,
+) -> Result<(), ErrNo> {
+    let environment_service = EnvironmentService::new(transaction);
+    let environment_lock_base_service = EnvironmentLockBaseService::new(transaction);
+    let lock_opt = EnvironmentLockOpt {
+        record_id: Some(record_id),
+        ..EnvironmentLockOpt::empty()
+    };
+    let environment_id = match environment_lock_base_service
+        .query_environment_lock_one(&lock_opt)
+        .await?
+    {
+        Some(environment_lock) => environment_lock.environment_id,
+        None => return Ok(()),
+    };
+    //锁住环境之后再查一次，同一个执行记录可能同时在取消和结束，只能释放一次
+    environment_service
+        .lock_environment(&environment_id)
+        .await?;
+    let environment_lock = match environment_lock_base_service
+        .query_environment_lock_one(&lock_opt)
+        .await?
+    {
+        Some(environment_lock) => environment_lock,
+        None => return Ok(()),
+    };
+    environment_lock_base_service
+        .delete_environment_lock(environment_lock.id)
+        .await?;
+    let waiting_list = environment_lock_base_service
+        .query_environment_lock_batch(&EnvironmentLockOpt {
+            environment_id: Some(environment_lock.environment_id),
+            status: Some(LockStatus::Waiting),
+            ..EnvironmentLockOpt::empty()
+        })
+        .await?;
+    let next_lock = match next_holding_lock(environment_lock.status, waiting_list) {
+        Some(next_lock) => next_lock,
+        None => return Ok(()),
+    };
+    let curr_time = Utc::now();
+    environment_lock_base_service
+        .update_environment_lock(
+            next_lock.id,
+            &[
+                EnvironmentLockProperty::Status(LockStatus::Holding),
+                EnvironmentLockProperty::LastModifiedTime(curr_time),
+            ],
+        )
+        .await?;
+    return Ok(());
+}
+
+//新的执行记录占用环境时的状态，环境已经被占用并且设置了拒绝执行时返回None
+fn new_lock_status(occupied: bool, lock_policy: LockPolicy) -> Option<LockStatus> {
+    if !occupied {
+        return Some(LockStatus::Holding);
+    }
+    return match lock_policy {
+        LockPolicy::Reject => None,
+        LockPolicy::Queue => Some(LockStatus::Waiting),
+    };
+}
+
+//释放环境之后接手环境的执行记录，排队最久的优先，还在排队的执行记录被取消时，环境仍然被别人占用着
+fn next_holding_lock(
+    released_status: LockStatus,
+    lock_list: Vec<EnvironmentLock>,
+) -> Option<EnvironmentLock> {
+    if LockStatus::Holding != released_status {
+        return None;
+    }
+    return lock_list
+        .into_iter()
+        .filter(|environment_lock| LockStatus::Waiting == environment_lock.status)
+        .min_by_key(|environment_lock| environment_lock.created_time);
+}
+
+/**
+ * 查询环境当前的占用信息，key为环境id
+ */
+pub async fn query_environment_locks(
+    transaction: &Transaction<'_>,
+    org_id: Id,
+    environment_id: Option<Id>,
+) -> Result<HashMap<Id, sdk::environment::EnvironmentLock>, ErrNo> {
+    let environment_lock_base_service = EnvironmentLockBaseService::new(transaction);
+    let job_base_service = JobBaseService::new(transaction);
+    let environment_lock_list = environment_lock_base_service
+        .query_environment_lock_batch(&EnvironmentLockOpt {
+            org_id: Some(org_id),
+            environment_id: environment_id,
+            status: Some(LockStatus::Holding),
+            ..EnvironmentLockOpt::empty()
+        })
+        .await?;
+    if environment_lock_list.is_empty() {
+        return Ok(HashMap::new());
+    }
+    let job_ids: HashSet<Id> = environment_lock_list
+        .iter()
+        .map(|environment_lock| environment_lock.job_id)
+        .collect();
+    let job_ids: Vec<Id> = job_ids.into_iter().collect();
+    let job_map: HashMap<Id, String> = job_base_service
+        .read_job_batch(&job_ids)
+        .await?
+        .into_iter()
+        .map(|job| (job.id, job.name))
+        .collect();
+    return Ok(environment_lock_list
+        .into_iter()
+        .map(|environment_lock| {
+            (
+                environment_lock.environment_id,
+                sdk::environment::EnvironmentLock {
+                    record_id: environment_lock.record_id,
+                    job_id: environment_lock.job_id,
+                    job_name: job_map
+                        .get(&environment_lock.job_id)
+                        .cloned()
+                        .unwrap_or_default(),
+                    holder_name: environment_lock.holder_name,
+                    //排队等待的执行记录从接手环境时开始算
+                    created_time: environment_lock.last_modified_time,
+                },
+            )
+        })
+        .collect());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn environment_lock(seq: u128, status: LockStatus, minutes_ago: i64) -> EnvironmentLock {
+        let created_time = Utc::now() - Duration::minutes(minutes_ago);
+        return EnvironmentLock {
+            id: Id::from_u128(seq),
+            org_id: Id::from_u128(100),
+            environment_id: Id::from_u128(200),
+            record_id: Id::from_u128(300 + seq),
+            job_id: Id::from_u128(400),
+            holder_id: None,
+            holder_name: String::from("定时任务"),
+            status: status,
+            created_time: created_time,
+            last_modified_time: created_time,
+        };
+    }
+
+    #[test]
+    fn test_new_lock_status() {
+        assert!(matches!(
+            new_lock_status(false, LockPolicy::Reject),
+            Some(LockStatus::Holding)
+        ));
+        assert!(matches!(
+            new_lock_status(false, LockPolicy::Queue),
+            Some(LockStatus::Holding)
+        ));
+        assert!(new_lock_status(true, LockPolicy::Reject).is_none());
+        assert!(matches!(
+            new_lock_status(true, LockPolicy::Queue),
+            Some(LockStatus::Waiting)
+        ));
+    }
+
+    #[test]
+    fn test_next_holding_lock() {
+        let lock_list = vec![
+            environment_lock(1, LockStatus::Waiting, 5),
+            environment_lock(2, LockStatus::Waiting, 10),
+            environment_lock(3, LockStatus::Holding, 20),
+            environment_lock(4, LockStatus::Waiting, 1),
+        ];
+        //排队最久的执行记录接手环境
+        let next_lock = next_holding_lock(LockStatus::Holding, lock_list);
+        assert_eq!(
+            Some(Id::from_u128(2)),
+            next_lock.map(|next_lock| next_lock.id)
+        );
+        //取消的是排队中的执行记录时，不交出环境
+        let lock_list = vec![environment_lock(1, LockStatus::Waiting, 5)];
+        assert!(next_holding_lock(LockStatus::Waiting, lock_list).is_none());
+        assert!(next_holding_lock(LockStatus::Holding, vec![]).is_none());
+    }
+}
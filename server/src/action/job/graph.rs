@@ -0,0 +1,181 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use tihu::LightString;
+use tihu_native::ErrNo;
+
+pub fn serialize_depends_on(depends_on: Option<Vec<i32>>) -> Result<Option<String>, ErrNo> {
+    return depends_on
+        .map(|depends_on| serde_json::to_string(&depends_on).map_err(ErrNo::SerializeError))
+        .transpose();
+}
+
+pub fn parse_depends_on(depends_on: Option<&str>) -> Result<Option<Vec<i32>>, ErrNo> {
+    return depends_on
+        .map(|depends_on| serde_json::from_str(depends_on).map_err(ErrNo::DeserializeError))
+        .transpose();
+}
+
+//只能依赖执行顺序在前面的步骤，这样步骤之间不会形成环
+pub fn check_depends_on(
+    step_name: &str,
+    seq: i32,
+    depends_on: Option<&[i32]>,
+    seq_list: &[i32],
+) -> Result<(), ErrNo> {
+    let depends_on = match depends_on {
+        Some(depends_on) => depends_on,
+        None => return Ok(()),
+    };
+    let mut checked = HashSet::with_capacity(depends_on.len());
+    for dependency in depends_on {
+        if seq <= *dependency || !seq_list.contains(dependency) {
+            return Err(ErrNo::CommonError(LightString::from(format!(
+                "步骤\"{}\"只能依赖排在它前面的步骤",
+                step_name
+            ))));
+        }
+        if !checked.insert(*dependency) {
+            return Err(ErrNo::CommonError(LightString::from(format!(
+                "步骤\"{}\"的依赖步骤重复",
+                step_name
+            ))));
+        }
+    }
+    return Ok(());
+}
+
+//计算每个步骤实际依赖的步骤，没有指定依赖的步骤依赖上一个步骤，step_list需要按执行顺序排好
+pub fn resolve_depends_on(step_list: &[(i32, Option<&str>)]) -> Result<Vec<Vec<i32>>, ErrNo> {
+    let mut resolved_list = Vec::with_capacity(step_list.len());
+    let mut prev_seq = None;
+    for (seq, depends_on) in step_list {
+        let resolved = match parse_depends_on(*depends_on)? {
+            Some(depends_on) => depends_on,
+            None => prev_seq.into_iter().collect(),
+        };
+        resolved_list.push(resolved);
+        prev_seq = Some(*seq);
+    }
+    return Ok(resolved_list);
+}
+
+/**
+ * 检查步骤之间引用关系时用到的步骤信息
+ */
+pub struct StepNode {
+    pub seq: i32,                     //执行顺序
+    pub name: String,                 //步骤名称
+    pub depends_on: Option<Vec<i32>>, //依赖的步骤
+    pub is_auto: bool,                //是否是自动步骤
+}
+
+//找出一定会在指定步骤之前结束的步骤，也就是它直接或者间接依赖的步骤
+pub fn upstream_steps(seq: i32, step_node_list: &[StepNode]) -> Vec<&StepNode> {
+    let mut sorted_list: Vec<&StepNode> = step_node_list.iter().collect();
+    sorted_list.sort_by_key(|step_node| step_node.seq);
+    let mut depends_on_map: HashMap<i32, Vec<i32>> = HashMap::with_capacity(sorted_list.len());
+    let mut prev_seq = None;
+    for step_node in &sorted_list {
+        let depends_on = match step_node.depends_on.as_ref() {
+            Some(depends_on) => depends_on.clone(),
+            None => prev_seq.into_iter().collect(),
+        };
+        depends_on_map.insert(step_node.seq, depends_on);
+        prev_seq = Some(step_node.seq);
+    }
+    let mut upstream: HashSet<i32> = HashSet::new();
+    let mut stack = vec![seq];
+    while let Some(curr) = stack.pop() {
+        if let Some(depends_on) = depends_on_map.get(&curr) {
+            for dependency in depends_on {
+                if upstream.insert(*dependency) {
+                    stack.push(*dependency);
+                }
+            }
+        }
+    }
+    return sorted_list
+        .into_iter()
+        .filter(|step_node| upstream.contains(&step_node.seq))
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn step_node(seq: i32, depends_on: Option<Vec<i32>>) -> StepNode {
+        return StepNode {
+            seq: seq,
+            name: format!("step{}", seq),
+            depends_on: depends_on,
+            is_auto: true,
+        };
+    }
+
+    #[test]
+    fn test_depends_on_round_trip() {
+        let depends_on = serialize_depends_on(Some(vec![1, 3])).unwrap();
+        assert_eq!(Some(String::from("[1,3]")), depends_on);
+        assert_eq!(
+            Some(vec![1, 3]),
+            parse_depends_on(depends_on.as_deref()).unwrap()
+        );
+        assert_eq!(None, serialize_depends_on(None).unwrap());
+        assert_eq!(None, parse_depends_on(None).unwrap());
+        assert!(parse_depends_on(Some("[1,")).is_err());
+    }
+
+    #[test]
+    fn test_check_depends_on() {
+        let seq_list = [1, 2, 3, 4];
+        assert!(check_depends_on("step3", 3, None, &seq_list).is_ok());
+        assert!(check_depends_on("step3", 3, Some(&[]), &seq_list).is_ok());
+        assert!(check_depends_on("step3", 3, Some(&[1, 2]), &seq_list).is_ok());
+        //不能依赖自己和后面的步骤
+        assert!(check_depends_on("step3", 3, Some(&[3]), &seq_list).is_err());
+        assert!(check_depends_on("step3", 3, Some(&[4]), &seq_list).is_err());
+        //不能依赖不存在的步骤
+        assert!(check_depends_on("step3", 3, Some(&[0]), &seq_list).is_err());
+        //不能重复依赖
+        assert!(check_depends_on("step3", 3, Some(&[1, 1]), &seq_list).is_err());
+    }
+
+    #[test]
+    fn test_resolve_depends_on() {
+        let resolved = resolve_depends_on(&[
+            (1, None),
+            (2, None),
+            (3, Some("[1]")),
+            (4, Some("[]")),
+            (5, None),
+        ])
+        .unwrap();
+        assert_eq!(vec![vec![], vec![1], vec![1], vec![], vec![4]], resolved);
+    }
+
+    #[test]
+    fn test_upstream_steps() {
+        //1 <- 2 <- 3，4依赖1，5没有指定依赖，依赖上一个步骤4
+        let step_node_list = vec![
+            step_node(5, None),
+            step_node(1, None),
+            step_node(2, None),
+            step_node(3, None),
+            step_node(4, Some(vec![1])),
+        ];
+        let seqs = |seq: i32| -> Vec<i32> {
+            upstream_steps(seq, &step_node_list)
+                .into_iter()
+                .map(|step_node| step_node.seq)
+                .collect()
+        };
+        assert_eq!(Vec::<i32>::new(), seqs(1));
+        assert_eq!(vec![1, 2], seqs(3));
+        assert_eq!(vec![1], seqs(4));
+        assert_eq!(vec![1, 4], seqs(5));
+        //并行的步骤互相不是上游
+        assert!(!seqs(4).contains(&2));
+        assert!(!seqs(3).contains(&4));
+    }
+}
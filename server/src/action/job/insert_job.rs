@@ -1,7 +1,24 @@
+use super::check_batch_policy;
+use super::check_retry_policy;
+use super::condition::check_run_expression;
+use super::from_sdk_failure_policy;
+use super::from_sdk_interrupt_policy;
+use super::from_sdk_run_if;
+use super::from_sdk_signoff_role;
+use super::graph::check_depends_on;
+use super::graph::serialize_depends_on;
+use super::graph::StepNode;
+use super::signoff::check_signoff_policy;
+use super::signoff::serialize_signoff_users;
+use super::template::check_param_refs;
+use super::template::check_parameter_schema;
+use super::template::check_step_refs;
+use super::template::serialize_parameter_schema;
 use crate::get_context;
 use crate::middleware::auth::User;
 use crate::model::environment_schema_resource::EnvironmentSchemaResourceOpt;
 use crate::model::job::Job;
+use crate::model::job_step::enums::FailurePolicy;
 use crate::model::job_step::enums::StepType;
 use crate::model::job_step::JobStep;
 use crate::sdk;
@@ -26,8 +43,47 @@ pub async fn insert_job(
         environment_schema_id,
         name,
         remark,
+        interrupt_policy,
+        parameter_schema,
         job_step_list,
     } = insert_job_req;
+    check_parameter_schema(&parameter_schema)?;
+    //步骤的执行顺序，用于检查步骤之间的依赖
+    let seq_list: Vec<i32> = job_step_list
+        .iter()
+        .map(|job_step| match job_step {
+            sdk::job::insert_job::JobStep::Auto { seq, .. }
+            | sdk::job::insert_job::JobStep::Manual { seq, .. } => *seq,
+        })
+        .collect();
+    //步骤之间的依赖关系，用于检查步骤引用的输出
+    let step_node_list: Vec<StepNode> = job_step_list
+        .iter()
+        .map(|job_step| match job_step {
+            sdk::job::insert_job::JobStep::Auto {
+                seq,
+                name,
+                depends_on,
+                ..
+            } => StepNode {
+                seq: *seq,
+                name: name.clone(),
+                depends_on: depends_on.clone(),
+                is_auto: true,
+            },
+            sdk::job::insert_job::JobStep::Manual {
+                seq,
+                name,
+                depends_on,
+                ..
+            } => StepNode {
+                seq: *seq,
+                name: name.clone(),
+                depends_on: depends_on.clone(),
+                is_auto: false,
+            },
+        })
+        .collect();
     let context = get_context()?;
     let mut client = context.get_db_client().await?;
     let transaction = client.transaction().await.map_err(open_transaction_error)?;
@@ -51,6 +107,8 @@ pub async fn insert_job(
         environment_schema_id: environment_schema_id.into(),
         name: name.into(),
         remark: remark.into(),
+        interrupt_policy: from_sdk_interrupt_policy(interrupt_policy),
+        parameter_schema: serialize_parameter_schema(&parameter_schema)?,
         created_time: curr_time,
         last_modified_time: curr_time,
     };
@@ -63,8 +121,24 @@ pub async fn insert_job(
                 operation_id,
                 operation_parameter,
                 remark,
+                timeout_seconds,
+                max_retries,
+                retry_backoff,
+                max_parallel,
+                batch_size,
+                pause_between_batches,
+                failure_policy,
+                run_if,
+                run_expression,
                 seq,
+                depends_on,
             } => {
+                check_retry_policy(&name, timeout_seconds, max_retries, retry_backoff)?;
+                check_batch_policy(&name, max_parallel, batch_size, pause_between_batches)?;
+                check_depends_on(&name, seq, depends_on.as_deref(), &seq_list)?;
+                if let Some(run_expression) = &run_expression {
+                    check_run_expression(&name, run_expression, &parameter_schema)?;
+                }
                 if let Some(schema_resource) = schema_resource_list
                     .iter()
                     .find(|schema_resource| schema_resource_id == schema_resource.id)
@@ -90,9 +164,16 @@ pub async fn insert_job(
                                         "操作参数格式不正确",
                                     ));
                                 })?;
-                        extension
-                            .validate_operation_parameter(&operation_id, parameter)
-                            .map_err(|err| ErrNo::CommonError(err.into()))?;
+                        //引用了执行参数或者步骤输出的操作参数，等到渲染之后再校验
+                        let has_param_refs =
+                            check_param_refs(&name, &parameter, &parameter_schema)?;
+                        let has_step_refs =
+                            check_step_refs(&name, seq, &parameter, &step_node_list)?;
+                        if !has_param_refs && !has_step_refs {
+                            extension
+                                .validate_operation_parameter(&operation_id, parameter)
+                                .map_err(|err| ErrNo::CommonError(err.into()))?;
+                        }
                         let id = context.new_id();
                         step_list.push(JobStep {
                             id: id, //步骤id
@@ -106,7 +187,20 @@ pub async fn insert_job(
                             operation_parameter: operation_parameter,     //操作参数
                             remark: remark,                               //备注
                             attachments: None,                            //附件
+                            signoff_role: None,                           //签收角色
+                            signoff_users: None,                          //签收人
+                            required_signoffs: 1,                         //需要的签收人数
+                            timeout_seconds: timeout_seconds,             //超时时间（秒）
+                            max_retries: max_retries,                     //最大重试次数
+                            retry_backoff: retry_backoff,                 //重试间隔（秒）
+                            max_parallel: max_parallel,                   //最大并发数
+                            batch_size: batch_size,                       //每批资源数
+                            pause_between_batches: pause_between_batches, //批次间隔（秒）
+                            failure_policy: from_sdk_failure_policy(failure_policy), //失败策略
+                            run_if: from_sdk_run_if(run_if),              //执行条件
+                            run_expression: run_expression,               //条件表达式
                             seq: seq,                                     //执行顺序
+                            depends_on: serialize_depends_on(depends_on)?, //依赖的步骤
                             created_time: curr_time,                      //创建时间
                             last_modified_time: curr_time,                //更新时间
                         });
@@ -127,24 +221,56 @@ pub async fn insert_job(
                 name,
                 remark,
                 attachments,
+                signoff_role,
+                signoff_user_list,
+                required_signoffs,
+                run_if,
+                run_expression,
                 seq,
+                depends_on,
             } => {
+                check_depends_on(&name, seq, depends_on.as_deref(), &seq_list)?;
+                check_signoff_policy(
+                    &transaction,
+                    org_id,
+                    &name,
+                    signoff_role.is_some(),
+                    signoff_user_list.as_deref(),
+                    required_signoffs,
+                )
+                .await?;
+                if let Some(run_expression) = &run_expression {
+                    check_run_expression(&name, run_expression, &parameter_schema)?;
+                }
                 let id = context.new_id();
                 step_list.push(JobStep {
                     id: id, //步骤id
                     org_id: org_id,
-                    job_id: job_id,                        //任务id
-                    name: name,                            //步骤名称
-                    step_type: StepType::Manual,           //步骤类型
-                    schema_resource_id: None,              //环境规格资源id
-                    operation_id: String::from(""),        //操作id
-                    operation_name: String::from(""),      //操作名称
-                    operation_parameter: String::from(""), //操作参数
-                    remark: remark,                        //备注
-                    attachments: attachments,              //附件
-                    seq: seq,                              //执行顺序
-                    created_time: curr_time,               //创建时间
-                    last_modified_time: curr_time,         //更新时间
+                    job_id: job_id,                                        //任务id
+                    name: name,                                            //步骤名称
+                    step_type: StepType::Manual,                           //步骤类型
+                    schema_resource_id: None,                              //环境规格资源id
+                    operation_id: String::from(""),                        //操作id
+                    operation_name: String::from(""),                      //操作名称
+                    operation_parameter: String::from(""),                 //操作参数
+                    remark: remark,                                        //备注
+                    attachments: attachments,                              //附件
+                    signoff_role: signoff_role.map(from_sdk_signoff_role), //签收角色
+                    signoff_users: serialize_signoff_users(signoff_user_list)?, //签收人
+                    required_signoffs: required_signoffs,                  //需要的签收人数
+                    timeout_seconds: None,                                 //超时时间（秒）
+                    max_retries: 0,                                        //最大重试次数
+                    retry_backoff: 0,                                      //重试间隔（秒）
+                    max_parallel: None,                                    //最大并发数
+                    batch_size: None,                                      //每批资源数
+                    pause_between_batches: 0,                              //批次间隔（秒）
+                    failure_policy: FailurePolicy::Continue,               //失败策略
+                    run_if: from_sdk_run_if(run_if),                       //执行条件
+                    run_expression: run_expression,                        //条件表达式
+                    seq: seq,                                              //执行顺序
+                    depends_on: serialize_depends_on(depends_on)?,         //依赖的步骤
+                    created_time: curr_time,                               //创建时间
+                    last_modified_time: curr_time,                         //更新时间
                 });
             }
         }
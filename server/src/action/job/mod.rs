@@ -1,43 +1,95 @@
+pub mod approval;
+pub mod cancel_job;
+pub mod condition;
 pub mod continue_job;
 pub mod delete_job;
+pub mod environment_lock;
+pub mod graph;
 pub mod insert_job;
+pub mod plan_job;
 pub mod query_job;
+pub mod queue;
 pub mod read_job;
+pub mod recover_job;
+pub mod retry_job_record;
+pub mod signoff;
 pub mod start_job;
+pub mod template;
 pub mod update_job;
+use super::extension::secret::decrypt_extension_configuration;
+use super::extension::secret::redact_append_log;
+use super::extension::secret::redact_secrets;
+use super::extension::secret::redact_value_secrets;
+use super::job_record::log_store::read_log_file;
+use super::job_record::log_store::start_live_log_writer;
+use super::job_record::log_store::store_step_resource_log;
+use super::job_record::merge_step_and_resource_record;
 use super::job_record::StepRecord;
+use super::runner::dispatch::call_runner;
+use super::runner::dispatch::RunnerCall;
 use crate::model::environment_resource::EnvironmentResource;
 use crate::model::environment_schema_resource::EnvironmentSchemaResource;
+use crate::model::job::enums::InterruptPolicy;
 use crate::model::job_record::enums::Status;
 use crate::model::job_record::JobRecordProperty;
+use crate::model::job_step::enums::FailurePolicy;
+use crate::model::job_step::enums::RunIf;
+use crate::model::job_step::enums::SignoffRole;
 use crate::model::job_step::enums::StepType;
 use crate::model::job_step::JobStep;
+use crate::model::job_step_record::enums::FailurePolicy as RecordFailurePolicy;
+use crate::model::job_step_record::enums::RunIf as RecordRunIf;
+use crate::model::job_step_record::enums::SignoffRole as RecordSignoffRole;
 use crate::model::job_step_record::enums::Status as StepStatus;
+use crate::model::job_step_record::JobStepRecord;
+use crate::model::job_step_record::JobStepRecordOpt;
 use crate::model::job_step_record::JobStepRecordProperty;
 use crate::model::job_step_resource_record::enums::Status as StepResourceStatus;
+use crate::model::job_step_resource_record::JobStepResourceRecord;
+use crate::model::job_step_resource_record::JobStepResourceRecordOpt;
 use crate::model::job_step_resource_record::JobStepResourceRecordProperty;
 use crate::sdk;
+use crate::service::base::EnvironmentBaseService;
 use crate::service::base::JobRecordBaseService;
 use crate::service::base::JobStepRecordBaseService;
 use crate::service::base::JobStepResourceRecordBaseService;
 use crate::Context;
 use chrono::Utc;
-use futures::future::join_all;
+use environment_lock::release_environment_lock;
+use futures::stream::FuturesUnordered;
+use futures::StreamExt;
+use graph::resolve_depends_on;
 use mould_extension_sdk::AppendLog;
+use queue::notify_job_queue;
 use sdk::job_record::read_job_record::LogLevel;
 use sdk::job_record::read_job_record::StepResLog;
 use serde::{Deserialize, Serialize};
+use serde_json::Map;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::fs::remove_file;
 use std::fs::File;
+use std::future::Future;
 use std::io::Write;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::time::Duration;
+use template::has_step_refs;
+use template::render_operation_parameter;
+use template::render_step_parameter;
 use tihu::Id;
 use tihu::LightString;
 use tihu_native::errno::commit_transaction_error;
 use tihu_native::errno::open_transaction_error;
 use tihu_native::ErrNo;
-use tokio::fs::read_to_string;
+use tokio::sync::mpsc;
+use tokio::sync::watch;
+use tokio::task::AbortHandle;
+use tokio::time::sleep;
+use tokio::time::sleep_until;
+use tokio::time::timeout;
+use tokio::time::Instant;
 use uuid::Uuid;
 
 /**
@@ -52,54 +104,1058 @@ pub struct Resource {
     pub extension_configuration: String, //扩展配置
 }
 
+/**
+ * 正在执行的任务
+ */
+struct RunningJob {
+    org_id: Id,                                       //组织id，用于统计组织同时执行的任务数
+    cancel_sender: watch::Sender<bool>,               //取消信号
+    manual_sender: mpsc::UnboundedSender<(Id, bool)>, //手动步骤的处理结果
+    abort_handle: AbortHandle,                        //执行任务的句柄，用于强制终止
+}
+
+lazy_static::lazy_static! {
+    //正在执行的任务，key为执行记录id
+    static ref RUNNING_JOBS: Mutex<HashMap<Id, RunningJob>> = Mutex::new(HashMap::new());
+}
+
+/**
+ * 在后台执行任务步骤，并登记执行句柄以便可以取消
+ */
+fn spawn_run(context: Arc<Context>, org_id: Id, step_record_list: Vec<StepRecord>, record_id: Id) {
+    //持有锁直到登记完成，避免任务先执行完毕导致登记的句柄无法移除
+    let mut running_jobs = RUNNING_JOBS.lock().unwrap();
+    spawn_run_locked(
+        &mut running_jobs,
+        context,
+        org_id,
+        Some(step_record_list),
+        record_id,
+    );
+}
+
+//step_record_list为空时从数据库重新查询执行记录
+fn spawn_run_locked(
+    running_jobs: &mut HashMap<Id, RunningJob>,
+    context: Arc<Context>,
+    org_id: Id,
+    step_record_list: Option<Vec<StepRecord>>,
+    record_id: Id,
+) {
+    let (cancel_sender, cancel_receiver) = watch::channel(false);
+    let (manual_sender, manual_receiver) = mpsc::unbounded_channel();
+    let own_receiver = cancel_receiver.clone();
+    let join_handle = tokio::spawn(async move {
+        let step_record_list = match step_record_list {
+            Some(step_record_list) => Ok(step_record_list),
+            None => query_step_record_list(context.clone(), record_id).await,
+        };
+        let result = match step_record_list {
+            Ok(step_record_list) => {
+                start_run(
+                    context,
+                    step_record_list,
+                    record_id,
+                    cancel_receiver,
+                    manual_receiver,
+                )
+                .await
+            }
+            Err(err) => {
+                //步骤查询失败时无法开始执行，标记失败并释放环境，否则记录会一直占着环境
+                if let Err(err) = finish_job_record(&context, record_id, Status::Failure).await {
+                    log::error!("标记任务记录{}失败时发生错误, {:?}", record_id, err);
+                }
+                Err(err)
+            }
+        };
+        if let Err(err) = result {
+            log::error!("执行任务发生错误, {:?}", err);
+        }
+        {
+            let mut running_jobs = RUNNING_JOBS.lock().unwrap();
+            unregister_running_job(&mut running_jobs, record_id, &own_receiver);
+        }
+        //空出了执行的名额，排队的任务可以开始执行了
+        notify_job_queue();
+    });
+    running_jobs.insert(
+        record_id,
+        RunningJob {
+            org_id: org_id,
+            cancel_sender: cancel_sender,
+            manual_sender: manual_sender,
+            abort_handle: join_handle.abort_handle(),
+        },
+    );
+}
+
+//只移除自己登记的句柄，同一个执行记录可能已经重新开始执行
+fn unregister_running_job(
+    running_jobs: &mut HashMap<Id, RunningJob>,
+    record_id: Id,
+    cancel_receiver: &watch::Receiver<bool>,
+) {
+    let is_self = running_jobs
+        .get(&record_id)
+        .map(|running_job| cancel_receiver.same_channel(&running_job.cancel_sender.subscribe()))
+        .unwrap_or(false);
+    if is_self {
+        running_jobs.remove(&record_id);
+    }
+}
+
+/**
+ * 手动步骤处理完之后，通知还在执行的任务，任务已经停下来等待时重新开始执行
+ */
+fn resume_run(context: Arc<Context>, org_id: Id, record_id: Id, step_record_id: Id, success: bool) {
+    let mut running_jobs = RUNNING_JOBS.lock().unwrap();
+    if let Some(running_job) = running_jobs.get(&record_id) {
+        if running_job
+            .manual_sender
+            .send((step_record_id, success))
+            .is_ok()
+        {
+            return;
+        }
+    }
+    //任务已经停下来，说明其它步骤的结果都已经保存，重新查询执行记录即可
+    //处理失败时也要重新开始执行，失败时执行的步骤还没有执行
+    //已经开始执行的任务继续执行，不再重新排队
+    spawn_run_locked(&mut running_jobs, context, org_id, None, record_id);
+}
+
+async fn query_step_record_list(
+    context: Arc<Context>,
+    record_id: Id,
+) -> Result<Vec<StepRecord>, ErrNo> {
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_step_record_base_service = JobStepRecordBaseService::new(&transaction);
+    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
+    let job_step_record_list = job_step_record_base_service
+        .query_job_step_record_batch(&JobStepRecordOpt {
+            record_id: Some(record_id),
+            ..JobStepRecordOpt::empty()
+        })
+        .await?;
+    let job_step_resource_record_list = job_step_resource_record_base_service
+        .query_job_step_resource_record_batch(&JobStepResourceRecordOpt {
+            record_id: Some(record_id),
+            ..JobStepResourceRecordOpt::empty()
+        })
+        .await?;
+    return Ok(merge_step_and_resource_record(
+        job_step_record_list,
+        job_step_resource_record_list,
+    ));
+}
+
+pub fn from_sdk_interrupt_policy(val: sdk::job::enums::InterruptPolicy) -> InterruptPolicy {
+    match val {
+        sdk::job::enums::InterruptPolicy::Interrupt => InterruptPolicy::Interrupt,
+        sdk::job::enums::InterruptPolicy::Resume => InterruptPolicy::Resume,
+    }
+}
+
+pub fn to_sdk_interrupt_policy(val: InterruptPolicy) -> sdk::job::enums::InterruptPolicy {
+    match val {
+        InterruptPolicy::Interrupt => sdk::job::enums::InterruptPolicy::Interrupt,
+        InterruptPolicy::Resume => sdk::job::enums::InterruptPolicy::Resume,
+    }
+}
+
+pub fn from_sdk_failure_policy(val: sdk::job::enums::FailurePolicy) -> FailurePolicy {
+    match val {
+        sdk::job::enums::FailurePolicy::Continue => FailurePolicy::Continue,
+        sdk::job::enums::FailurePolicy::Stop => FailurePolicy::Stop,
+    }
+}
+
+pub fn to_sdk_failure_policy(val: FailurePolicy) -> sdk::job::enums::FailurePolicy {
+    match val {
+        FailurePolicy::Continue => sdk::job::enums::FailurePolicy::Continue,
+        FailurePolicy::Stop => sdk::job::enums::FailurePolicy::Stop,
+    }
+}
+
+fn to_record_failure_policy(val: FailurePolicy) -> RecordFailurePolicy {
+    match val {
+        FailurePolicy::Continue => RecordFailurePolicy::Continue,
+        FailurePolicy::Stop => RecordFailurePolicy::Stop,
+    }
+}
+
+pub fn from_sdk_run_if(val: sdk::job::enums::RunIf) -> RunIf {
+    match val {
+        sdk::job::enums::RunIf::OnSuccess => RunIf::OnSuccess,
+        sdk::job::enums::RunIf::OnFailure => RunIf::OnFailure,
+        sdk::job::enums::RunIf::Always => RunIf::Always,
+    }
+}
+
+pub fn to_sdk_run_if(val: RunIf) -> sdk::job::enums::RunIf {
+    match val {
+        RunIf::OnSuccess => sdk::job::enums::RunIf::OnSuccess,
+        RunIf::OnFailure => sdk::job::enums::RunIf::OnFailure,
+        RunIf::Always => sdk::job::enums::RunIf::Always,
+    }
+}
+
+fn to_record_run_if(val: RunIf) -> RecordRunIf {
+    match val {
+        RunIf::OnSuccess => RecordRunIf::OnSuccess,
+        RunIf::OnFailure => RecordRunIf::OnFailure,
+        RunIf::Always => RecordRunIf::Always,
+    }
+}
+
+pub fn from_sdk_signoff_role(val: sdk::job::enums::SignoffRole) -> SignoffRole {
+    match val {
+        sdk::job::enums::SignoffRole::Owner => SignoffRole::Owner,
+        sdk::job::enums::SignoffRole::Admin => SignoffRole::Admin,
+        sdk::job::enums::SignoffRole::Operator => SignoffRole::Operator,
+    }
+}
+
+pub fn to_sdk_signoff_role(val: SignoffRole) -> sdk::job::enums::SignoffRole {
+    match val {
+        SignoffRole::Owner => sdk::job::enums::SignoffRole::Owner,
+        SignoffRole::Admin => sdk::job::enums::SignoffRole::Admin,
+        SignoffRole::Operator => sdk::job::enums::SignoffRole::Operator,
+    }
+}
+
+fn to_record_signoff_role(val: SignoffRole) -> RecordSignoffRole {
+    match val {
+        SignoffRole::Owner => RecordSignoffRole::Owner,
+        SignoffRole::Admin => RecordSignoffRole::Admin,
+        SignoffRole::Operator => RecordSignoffRole::Operator,
+    }
+}
+
+/**
+ * 检查自动步骤的并发和分批设置
+ */
+fn check_batch_policy(
+    step_name: &str,
+    max_parallel: Option<i32>,
+    batch_size: Option<i32>,
+    pause_between_batches: i32,
+) -> Result<(), ErrNo> {
+    if max_parallel
+        .map(|max_parallel| max_parallel <= 0)
+        .unwrap_or(false)
+        || batch_size
+            .map(|batch_size| batch_size <= 0)
+            .unwrap_or(false)
+    {
+        return Err(ErrNo::CommonError(LightString::from(format!(
+            "步骤\"{}\"的最大并发数和每批资源数必须大于0",
+            step_name
+        ))));
+    }
+    if pause_between_batches < 0 {
+        return Err(ErrNo::CommonError(LightString::from(format!(
+            "步骤\"{}\"的批次间隔不能小于0",
+            step_name
+        ))));
+    }
+    return Ok(());
+}
+
+/**
+ * 检查自动步骤的超时和重试设置
+ */
+fn check_retry_policy(
+    step_name: &str,
+    timeout_seconds: Option<i32>,
+    max_retries: i32,
+    retry_backoff: i32,
+) -> Result<(), ErrNo> {
+    if let Some(timeout_seconds) = timeout_seconds {
+        if timeout_seconds <= 0 {
+            return Err(ErrNo::CommonError(LightString::from(format!(
+                "步骤\"{}\"的超时时间必须大于0",
+                step_name
+            ))));
+        }
+    }
+    if max_retries < 0 || retry_backoff < 0 {
+        return Err(ErrNo::CommonError(LightString::from(format!(
+            "步骤\"{}\"的重试次数和重试间隔不能小于0",
+            step_name
+        ))));
+    }
+    return Ok(());
+}
+
+/**
+ * 步骤是否可以开始执行
+ */
+enum Readiness {
+    Run,                //开始执行
+    Skip(&'static str), //不需要执行，附带跳过原因
+    Wait,               //等待依赖的步骤结束
+}
+
+//根据步骤的执行条件以及依赖步骤的结果，判断步骤是否可以开始执行
+fn check_readiness(
+    run_if: RecordRunIf,
+    depends_on: &[i32],
+    finished: &HashSet<i32>,
+    satisfied: &HashSet<i32>,
+    has_failure: bool,
+) -> Readiness {
+    let all_finished = depends_on
+        .iter()
+        .all(|dependency| finished.contains(dependency));
+    match run_if {
+        RecordRunIf::OnSuccess => {
+            if has_failure {
+                Readiness::Skip("有步骤执行失败，不再执行")
+            } else if depends_on
+                .iter()
+                .all(|dependency| satisfied.contains(dependency))
+            {
+                Readiness::Run
+            } else {
+                Readiness::Wait
+            }
+        }
+        //没有步骤失败时先等着，等到其它步骤都结束了还没有失败再跳过
+        RecordRunIf::OnFailure => {
+            if all_finished && has_failure {
+                Readiness::Run
+            } else {
+                Readiness::Wait
+            }
+        }
+        RecordRunIf::Always => {
+            if all_finished {
+                Readiness::Run
+            } else {
+                Readiness::Wait
+            }
+        }
+    }
+}
+
+fn get_job_step_record(step_record: &StepRecord) -> &JobStepRecord {
+    match step_record {
+        StepRecord::Auto {
+            job_step_record, ..
+        } => job_step_record,
+        StepRecord::Manual { job_step_record } => job_step_record,
+    }
+}
+
 async fn try_start_run(
     context: Arc<Context>,
     step_record_list: Vec<StepRecord>,
+    record_id: Id,
+    cancel_receiver: &watch::Receiver<bool>,
+    manual_receiver: &mut mpsc::UnboundedReceiver<(Id, bool)>,
 ) -> Result<Status, ErrNo> {
-    for step_record in step_record_list {
-        match step_record {
-            StepRecord::Auto {
-                job_step_record,
-                step_resource_record_list,
-            } => {
-                update_step_record(context.clone(), job_step_record.id, StepStatus::Running)
-                    .await?;
-                let context = context.clone();
-                let extension_id: LightString = job_step_record.extension_id.into();
-                let operation_id: LightString = job_step_record.operation_id.into();
-                let operation_parameter: LightString = job_step_record.operation_parameter.into();
-                let results = join_all(step_resource_record_list.into_iter().enumerate().map(
-                    |(resource_index, step_resource_record)| {
-                        call_extension(
-                            context.clone(),
-                            extension_id.clone(),
-                            step_resource_record.extension_configuration.into(),
-                            operation_id.clone(),
-                            operation_parameter.clone(),
-                            step_resource_record.id,
-                            resource_index as u32,
-                        )
-                    },
-                ))
-                .await;
-                for result in results {
-                    if let Err(err) = result {
-                        update_step_record(context, job_step_record.id, StepStatus::Failure)
+    let depends_on_list = resolve_depends_on(
+        &step_record_list
+            .iter()
+            .map(|step_record| {
+                let job_step_record = get_job_step_record(step_record);
+                (
+                    job_step_record.job_step_seq,
+                    job_step_record.depends_on.as_deref(),
+                )
+            })
+            .collect::<Vec<_>>(),
+    )?;
+    let mut finished: HashSet<i32> = HashSet::new(); //已结束的步骤
+    let mut satisfied: HashSet<i32> = HashSet::new(); //已成功或者已跳过的步骤
+    let mut waiting_manual: HashMap<Id, (i32, RecordRunIf)> = HashMap::new(); //等待人工处理的手动步骤
+    let mut pending: Vec<(Vec<i32>, StepRecord)> = Vec::new(); //还没开始执行的步骤
+    let mut failure: Option<ErrNo> = None;
+    for (depends_on, step_record) in depends_on_list.into_iter().zip(step_record_list) {
+        let job_step_record = get_job_step_record(&step_record);
+        let seq = job_step_record.job_step_seq;
+        match job_step_record.status {
+            StepStatus::Success | StepStatus::Skipped => {
+                finished.insert(seq);
+                satisfied.insert(seq);
+                continue;
+            }
+            StepStatus::Failure => {
+                //重新开始执行时，之前失败的步骤仍然要触发失败时执行的步骤
+                finished.insert(seq);
+                if failure.is_none() {
+                    failure = Some(ErrNo::CommonError(LightString::from(format!(
+                        "步骤\"{}\"执行失败",
+                        job_step_record.step_name
+                    ))));
+                }
+                continue;
+            }
+            StepStatus::Cancelled => {
+                finished.insert(seq);
+                continue;
+            }
+            StepStatus::Running => {
+                if let StepRecord::Manual { .. } = step_record {
+                    waiting_manual.insert(job_step_record.id, (seq, job_step_record.run_if));
+                    continue;
+                }
+            }
+            _ => (),
+        }
+        pending.push((depends_on, step_record));
+    }
+    let mut running = FuturesUnordered::new();
+    let mut manual_closed = false;
+    loop {
+        let cancelled = *cancel_receiver.borrow();
+        //已取消时不再开始新的步骤，只等待执行中的步骤结束
+        if !cancelled {
+            if failure.is_some() {
+                //有步骤失败，等待人工处理的手动步骤也不用再处理了，失败时也要执行的除外
+                let cancelled_manual: Vec<Id> = waiting_manual
+                    .iter()
+                    .filter(|(_, (_, run_if))| RecordRunIf::OnSuccess == *run_if)
+                    .map(|(step_record_id, _)| *step_record_id)
+                    .collect();
+                for step_record_id in cancelled_manual {
+                    update_step_record(context.clone(), step_record_id, StepStatus::Cancelled)
+                        .await?;
+                    if let Some((seq, _)) = waiting_manual.remove(&step_record_id) {
+                        finished.insert(seq);
+                    }
+                }
+            }
+            //跳过的步骤算作已结束，依赖它的步骤可能也可以开始了，所以要一直检查到没有变化为止
+            loop {
+                let mut has_skipped = false;
+                let mut rest = Vec::with_capacity(pending.len());
+                for (depends_on, step_record) in pending {
+                    let job_step_record = get_job_step_record(&step_record);
+                    let seq = job_step_record.job_step_seq;
+                    match check_readiness(
+                        job_step_record.run_if,
+                        &depends_on,
+                        &finished,
+                        &satisfied,
+                        failure.is_some(),
+                    ) {
+                        Readiness::Wait => rest.push((depends_on, step_record)),
+                        Readiness::Skip(skip_reason) => {
+                            skip_step_record(context.clone(), &step_record, skip_reason).await?;
+                            finished.insert(seq);
+                            satisfied.insert(seq);
+                            has_skipped = true;
+                        }
+                        Readiness::Run => match step_record {
+                            StepRecord::Auto {
+                                job_step_record,
+                                step_resource_record_list,
+                            } => {
+                                let context = context.clone();
+                                let cancel_receiver = cancel_receiver.clone();
+                                running.push(async move {
+                                    let result = run_auto_step(
+                                        context,
+                                        job_step_record,
+                                        step_resource_record_list,
+                                        cancel_receiver,
+                                    )
+                                    .await;
+                                    (seq, result)
+                                });
+                            }
+                            StepRecord::Manual { job_step_record } => {
+                                //手动任务，把步骤状态改成Running，等待人工处理
+                                update_step_record(
+                                    context.clone(),
+                                    job_step_record.id,
+                                    StepStatus::Running,
+                                )
+                                .await?;
+                                waiting_manual
+                                    .insert(job_step_record.id, (seq, job_step_record.run_if));
+                            }
+                        },
+                    }
+                }
+                pending = rest;
+                if !has_skipped {
+                    break;
+                }
+            }
+        }
+        if running.is_empty() {
+            if cancelled {
+                return Ok(Status::Cancelled);
+            }
+            if waiting_manual.is_empty() {
+                if pending.is_empty() {
+                    return match failure {
+                        Some(err) => Err(err),
+                        None => Ok(Status::Success),
+                    };
+                }
+                //其它步骤都已结束并且没有失败，失败时才执行的步骤都不需要执行了
+                if failure.is_none() {
+                    let (skipped, rest): (Vec<_>, Vec<_>) =
+                        pending.into_iter().partition(|(_, step_record)| {
+                            RecordRunIf::OnFailure == get_job_step_record(step_record).run_if
+                        });
+                    pending = rest;
+                    if !skipped.is_empty() {
+                        for (_, step_record) in skipped {
+                            skip_step_record(
+                                context.clone(),
+                                &step_record,
+                                "没有步骤执行失败，不需要执行",
+                            )
                             .await?;
-                        return Err(err);
+                            let seq = get_job_step_record(&step_record).job_step_seq;
+                            finished.insert(seq);
+                            satisfied.insert(seq);
+                        }
+                        continue;
                     }
                 }
-                update_step_record(context, job_step_record.id, StepStatus::Success).await?;
+                return Err(failure.unwrap_or_else(|| {
+                    ErrNo::CommonError(LightString::from_static("步骤的依赖无法满足"))
+                }));
+            }
+            //只剩等待人工处理的步骤，可能已经在其它实例上处理完了
+            let manual_results =
+                park_job_record(context.clone(), record_id, &waiting_manual).await?;
+            if !manual_results.is_empty() {
+                for (step_record_id, success) in manual_results {
+                    on_manual_result(
+                        &mut waiting_manual,
+                        &mut finished,
+                        &mut satisfied,
+                        &mut failure,
+                        step_record_id,
+                        success,
+                    );
+                }
+                continue;
             }
-            StepRecord::Manual { job_step_record } => {
-                //手动任务，把步骤状态改成Running
-                update_step_record(context, job_step_record.id, StepStatus::Running).await?;
+            //没有收到处理结果就先停下来，处理后再重新开始执行
+            let manual_result = {
+                let mut running_jobs = RUNNING_JOBS.lock().unwrap();
+                match manual_receiver.try_recv() {
+                    Ok(manual_result) => Some(manual_result),
+                    Err(_) => {
+                        unregister_running_job(&mut running_jobs, record_id, cancel_receiver);
+                        None
+                    }
+                }
+            };
+            match manual_result {
+                Some((step_record_id, success)) => {
+                    on_manual_result(
+                        &mut waiting_manual,
+                        &mut finished,
+                        &mut satisfied,
+                        &mut failure,
+                        step_record_id,
+                        success,
+                    );
+                    continue;
+                }
                 //全局任务返回Running
-                return Ok(Status::Running);
+                None => return Ok(Status::Running),
             }
         }
+        tokio::select! {
+            Some((seq, result)) = running.next() => {
+                finished.insert(seq);
+                match result {
+                    Ok(StepStatus::Success) => {
+                        satisfied.insert(seq);
+                    }
+                    Ok(_) => (),
+                    Err(err) => {
+                        if failure.is_none() {
+                            failure = Some(err);
+                        }
+                    }
+                }
+            }
+            manual_result = manual_receiver.recv(), if !manual_closed => {
+                match manual_result {
+                    Some((step_record_id, success)) => on_manual_result(
+                        &mut waiting_manual,
+                        &mut finished,
+                        &mut satisfied,
+                        &mut failure,
+                        step_record_id,
+                        success,
+                    ),
+                    None => manual_closed = true,
+                }
+            }
+        }
+    }
+}
+
+//只剩等待人工处理的手动步骤时，返回已经处理完的手动步骤，都没有处理完时交出执行记录，由处理的实例重新开始执行
+async fn park_job_record(
+    context: Arc<Context>,
+    record_id: Id,
+    waiting_manual: &HashMap<Id, (i32, RecordRunIf)>,
+) -> Result<Vec<(Id, bool)>, ErrNo> {
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_record_base_service = JobRecordBaseService::new(&transaction);
+    let job_step_record_base_service = JobStepRecordBaseService::new(&transaction);
+    let curr_time = Utc::now();
+    //先更新执行记录锁住这一行，和手动步骤的处理互斥
+    job_record_base_service
+        .update_job_record(record_id, &[JobRecordProperty::LastModifiedTime(curr_time)])
+        .await?;
+    let step_record_ids: Vec<Id> = waiting_manual.keys().copied().collect();
+    let manual_results: Vec<(Id, bool)> = job_step_record_base_service
+        .read_job_step_record_batch(&step_record_ids)
+        .await?
+        .into_iter()
+        .filter_map(|job_step_record| match job_step_record.status {
+            StepStatus::Success => Some((job_step_record.id, true)),
+            StepStatus::Failure => Some((job_step_record.id, false)),
+            _ => None,
+        })
+        .collect();
+    if manual_results.is_empty() {
+        job_record_base_service
+            .update_job_record(
+                record_id,
+                &[
+                    JobRecordProperty::InstanceId(None),
+                    JobRecordProperty::HeartbeatTime(None),
+                ],
+            )
+            .await?;
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(manual_results);
+}
+
+fn on_manual_result(
+    waiting_manual: &mut HashMap<Id, (i32, RecordRunIf)>,
+    finished: &mut HashSet<i32>,
+    satisfied: &mut HashSet<i32>,
+    failure: &mut Option<ErrNo>,
+    step_record_id: Id,
+    success: bool,
+) {
+    if let Some((seq, _)) = waiting_manual.remove(&step_record_id) {
+        finished.insert(seq);
+        if success {
+            satisfied.insert(seq);
+        } else if failure.is_none() {
+            *failure = Some(ErrNo::CommonError(LightString::from_static(
+                "手动步骤处理失败",
+            )));
+        }
     }
-    return Ok(Status::Success);
+}
+
+//在所有资源上执行自动步骤，返回步骤最终的状态
+async fn run_auto_step(
+    context: Arc<Context>,
+    job_step_record: JobStepRecord,
+    step_resource_record_list: Vec<JobStepResourceRecord>,
+    cancel_receiver: watch::Receiver<bool>,
+) -> Result<StepStatus, ErrNo> {
+    update_step_record(context.clone(), job_step_record.id, StepStatus::Running).await?;
+    let (params, param_secrets) = query_record_params(&context, job_step_record.record_id).await?;
+    let operation_parameter =
+        render_step_operation_parameter(context.clone(), &job_step_record, &params, &param_secrets)
+            .await?;
+    if let Ok(operation_parameter) = operation_parameter.as_ref() {
+        save_rendered_parameter(
+            context.clone(),
+            job_step_record.id,
+            operation_parameter,
+            &param_secrets,
+        )
+        .await?;
+    }
+    let runner_label = read_runner_label(context.clone(), job_step_record.environment_id).await?;
+    let step_call = StepCall {
+        org_id: job_step_record.org_id,
+        extension_id: job_step_record.extension_id.into(),
+        operation_id: job_step_record.operation_id.into(),
+        operation_parameter: operation_parameter,
+        timeout_seconds: job_step_record.timeout_seconds,
+        max_retries: job_step_record.max_retries,
+        retry_backoff: job_step_record.retry_backoff,
+        runner_label: runner_label,
+        param_secrets: param_secrets,
+        cancel_receiver: cancel_receiver.clone(),
+    };
+    //继续执行被中断的步骤时，已经成功的资源不再重新执行，资源序号保持不变
+    let step_resource_record_list: Vec<(usize, JobStepResourceRecord)> = step_resource_record_list
+        .into_iter()
+        .enumerate()
+        .filter(|(_, step_resource_record)| {
+            StepResourceStatus::Success != step_resource_record.status
+        })
+        .collect();
+    //不分批时所有资源作为一批执行
+    let batch_policy = BatchPolicy {
+        batch_size: job_step_record
+            .batch_size
+            .map(|batch_size| batch_size.max(1) as usize)
+            .unwrap_or(step_resource_record_list.len().max(1)),
+        max_parallel: job_step_record
+            .max_parallel
+            .map(|max_parallel| max_parallel.max(1) as usize)
+            .unwrap_or(usize::MAX),
+        pause_between_batches: Duration::from_secs(job_step_record.pause_between_batches as u64),
+        stop_on_failure: RecordFailurePolicy::Stop == job_step_record.failure_policy,
+    };
+    let resource_count = step_resource_record_list.len();
+    let BatchOutcome {
+        started_count,
+        first_err,
+        skipped_list,
+    } = run_batches(
+        step_resource_record_list,
+        &batch_policy,
+        &cancel_receiver,
+        |(resource_index, step_resource_record)| {
+            call_extension(
+                context.clone(),
+                &step_call,
+                step_resource_record.extension_configuration.into(),
+                step_resource_record.id,
+                resource_index as u32,
+            )
+        },
+    )
+    .await;
+    let skipped_list: Vec<Id> = skipped_list
+        .into_iter()
+        .map(|(_, step_resource_record)| step_resource_record.id)
+        .collect();
+    if *cancel_receiver.borrow() && (first_err.is_some() || started_count < resource_count) {
+        update_step_record(context, job_step_record.id, StepStatus::Cancelled).await?;
+        return Ok(StepStatus::Cancelled);
+    }
+    if let Some(err) = first_err {
+        skip_step_resource_records(context.clone(), &skipped_list).await?;
+        update_step_record(context, job_step_record.id, StepStatus::Failure).await?;
+        return Err(err);
+    }
+    update_step_record(context, job_step_record.id, StepStatus::Success).await?;
+    return Ok(StepStatus::Success);
+}
+
+/**
+ * 自动步骤在多个资源上执行时的分批方式
+ */
+struct BatchPolicy {
+    batch_size: usize,               //每批的资源数
+    max_parallel: usize,             //每批内同时执行的资源数
+    pause_between_batches: Duration, //批次之间的暂停时间
+    stop_on_failure: bool,           //有资源失败时，同一批次内没有开始的资源也不再执行
+}
+
+struct BatchOutcome<T, E> {
+    started_count: usize, //开始执行的资源数
+    first_err: Option<E>, //第一个失败的资源的错误
+    skipped_list: Vec<T>, //因为失败没有执行的资源
+}
+
+/**
+ * 按批次执行，一批结束后再开始下一批，有资源失败时后面批次的资源不再执行
+ * 已取消时停止启动新的资源，没有开始的资源由取消操作统一处理，不算在跳过的资源里
+ */
+async fn run_batches<T, E, F, Fut>(
+    list: Vec<T>,
+    batch_policy: &BatchPolicy,
+    cancel_receiver: &watch::Receiver<bool>,
+    mut run: F,
+) -> BatchOutcome<T, E>
+where
+    F: FnMut(T) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+{
+    let mut started_count = 0;
+    let mut first_err: Option<E> = None;
+    let mut skipped_list: Vec<T> = Vec::new();
+    let mut list = list.into_iter();
+    let mut is_first_batch = true;
+    loop {
+        let batch: Vec<T> = list.by_ref().take(batch_policy.batch_size).collect();
+        if batch.is_empty() || *cancel_receiver.borrow() {
+            break;
+        }
+        if first_err.is_some() {
+            //有批次失败，后面批次的资源不再执行
+            skipped_list.extend(batch);
+            continue;
+        }
+        if !is_first_batch && !batch_policy.pause_between_batches.is_zero() {
+            let mut cancel_receiver = cancel_receiver.clone();
+            tokio::select! {
+                _ = sleep(batch_policy.pause_between_batches) => (),
+                _ = cancel_receiver.changed() => (),
+            }
+            if *cancel_receiver.borrow() {
+                break;
+            }
+        }
+        is_first_batch = false;
+        let mut pending = batch.into_iter();
+        let mut running = FuturesUnordered::new();
+        loop {
+            //没有达到最大并发数就继续启动剩下的资源
+            while running.len() < batch_policy.max_parallel
+                && !(batch_policy.stop_on_failure && first_err.is_some())
+                && !*cancel_receiver.borrow()
+            {
+                match pending.next() {
+                    Some(item) => {
+                        started_count += 1;
+                        running.push(run(item));
+                    }
+                    None => break,
+                }
+            }
+            match running.next().await {
+                Some(Ok(_)) => (),
+                Some(Err(err)) => {
+                    if first_err.is_none() {
+                        first_err = Some(err);
+                    }
+                }
+                None => break,
+            }
+        }
+        skipped_list.extend(pending);
+    }
+    return BatchOutcome {
+        started_count: started_count,
+        first_err: first_err,
+        skipped_list: skipped_list,
+    };
+}
+
+//环境指定了执行器标签时，步骤交给带有该标签的执行器执行
+async fn read_runner_label(
+    context: Arc<Context>,
+    environment_id: Id,
+) -> Result<Option<LightString>, ErrNo> {
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let environment_base_service = EnvironmentBaseService::new(&transaction);
+    let environment = environment_base_service
+        .read_environment(environment_id)
+        .await?;
+    return Ok(environment.and_then(|environment| environment.runner_label.map(From::from)));
+}
+
+//执行步骤之前用执行参数和前面步骤的输出渲染操作参数，再重新校验渲染之后的操作参数，失败时返回失败原因
+async fn render_step_operation_parameter(
+    context: Arc<Context>,
+    job_step_record: &JobStepRecord,
+    params: &Map<String, Value>,
+    param_secrets: &[String],
+) -> Result<Result<LightString, LightString>, ErrNo> {
+    let operation_parameter = &job_step_record.operation_parameter;
+    let has_refs = serde_json::from_str::<Value>(operation_parameter)
+        .map(|operation_parameter| has_step_refs(&operation_parameter))
+        .unwrap_or(false);
+    let step_outputs = if has_refs {
+        query_step_outputs(context.clone(), job_step_record.record_id).await?
+    } else {
+        HashMap::new()
+    };
+    let operation_parameter = match render_step_parameter(
+        &job_step_record.step_name,
+        operation_parameter,
+        params,
+        &step_outputs,
+    ) {
+        Ok(operation_parameter) => operation_parameter,
+        Err(err) => return Ok(Err(err.message())),
+    };
+    //没有找到扩展时由调用扩展的地方报错
+    if let Some((_, extension)) = context.get_extension_info(&job_step_record.extension_id) {
+        let value =
+            serde_json::from_str::<Value>(&operation_parameter).map_err(ErrNo::DeserializeError)?;
+        if let Err(err) =
+            extension.validate_operation_parameter(&job_step_record.operation_id, value)
+        {
+            //校验失败的原因里可能带有密码类型的参数
+            return Ok(Err(LightString::from(redact_secrets(&err, param_secrets))));
+        }
+    }
+    return Ok(Ok(operation_parameter.into()));
+}
+
+//查询执行记录的执行参数，密码类型的参数单独加密保存，解密之后替换掉掩码，同时返回这些密码用于日志脱敏
+async fn query_record_params(
+    context: &Context,
+    record_id: Id,
+) -> Result<(Map<String, Value>, Vec<String>), ErrNo> {
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_record_base_service = JobRecordBaseService::new(&transaction);
+    let job_record = job_record_base_service
+        .read_job_record(record_id)
+        .await?
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("该任务执行记录不存在")))?;
+    let mut params = parse_record_params(job_record.params.as_deref())?;
+    let mut secrets = Vec::new();
+    if let Some(secret_params) = job_record.secret_params.as_deref() {
+        let secret_params = context.decrypt_configuration(secret_params)?;
+        let secret_params = parse_record_params(Some(&secret_params))?;
+        secrets.extend(
+            secret_params
+                .values()
+                .filter_map(|value| value.as_str())
+                .map(|value| value.to_string()),
+        );
+        params.extend(secret_params);
+    }
+    return Ok((params, secrets));
+}
+
+//执行记录保存的执行参数，以前的执行记录没有保存执行参数，操作参数已经渲染过了
+pub fn parse_record_params(params: Option<&str>) -> Result<Map<String, Value>, ErrNo> {
+    return match params {
+        Some(params) => match serde_json::from_str::<Value>(params) {
+            Ok(Value::Object(params)) => Ok(params),
+            _ => Err(ErrNo::CommonError(LightString::from_static(
+                "执行参数格式不正确",
+            ))),
+        },
+        None => Ok(Map::new()),
+    };
+}
+
+//查询执行记录中已成功的步骤的输出，多个资源有同名的输出时只保留其中一个
+async fn query_step_outputs(
+    context: Arc<Context>,
+    record_id: Id,
+) -> Result<HashMap<String, Map<String, Value>>, ErrNo> {
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_step_record_base_service = JobStepRecordBaseService::new(&transaction);
+    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
+    let job_step_record_list = job_step_record_base_service
+        .query_job_step_record_batch(&JobStepRecordOpt {
+            record_id: Some(record_id),
+            ..JobStepRecordOpt::empty()
+        })
+        .await?;
+    let job_step_resource_record_list = job_step_resource_record_base_service
+        .query_job_step_resource_record_batch(&JobStepResourceRecordOpt {
+            record_id: Some(record_id),
+            status: Some(StepResourceStatus::Success),
+            ..JobStepResourceRecordOpt::empty()
+        })
+        .await?;
+    let step_name_map: HashMap<Id, &str> = job_step_record_list
+        .iter()
+        .map(|job_step_record| (job_step_record.id, job_step_record.step_name.as_str()))
+        .collect();
+    let mut step_outputs: HashMap<String, Map<String, Value>> = HashMap::new();
+    for step_resource_record in job_step_resource_record_list {
+        let step_name = match step_name_map.get(&step_resource_record.job_step_record_id) {
+            Some(step_name) => *step_name,
+            None => continue,
+        };
+        let outputs = match step_resource_record
+            .outputs
+            .as_deref()
+            .map(serde_json::from_str::<Value>)
+        {
+            Some(Ok(Value::Object(outputs))) => outputs,
+            _ => continue,
+        };
+        let step_output = step_outputs.entry(step_name.to_string()).or_default();
+        for (key, value) in outputs {
+            step_output.entry(key).or_insert(value);
+        }
+    }
+    return Ok(step_outputs);
+}
+
+//不需要执行的步骤标记为已跳过，并记录跳过原因
+async fn skip_step_record(
+    context: Arc<Context>,
+    step_record: &StepRecord,
+    skip_reason: &str,
+) -> Result<(), ErrNo> {
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let curr_time = Utc::now();
+    let job_step_record_base_service = JobStepRecordBaseService::new(&transaction);
+    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
+    if let StepRecord::Auto {
+        step_resource_record_list,
+        ..
+    } = step_record
+    {
+        let changes: Vec<JobStepResourceRecordProperty> = vec![
+            JobStepResourceRecordProperty::Status(StepResourceStatus::Skipped),
+            JobStepResourceRecordProperty::LastModifiedTime(curr_time),
+        ];
+        for step_resource_record in step_resource_record_list {
+            job_step_resource_record_base_service
+                .update_job_step_resource_record(step_resource_record.id, &changes)
+                .await?;
+        }
+    }
+    let changes: Vec<JobStepRecordProperty> = vec![
+        JobStepRecordProperty::Status(StepStatus::Skipped),
+        JobStepRecordProperty::SkipReason(Some(skip_reason.to_string())),
+        JobStepRecordProperty::LastModifiedTime(curr_time),
+    ];
+    job_step_record_base_service
+        .update_job_step_record(get_job_step_record(step_record).id, &changes)
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(());
+}
+
+//批次失败或者设置了失败即停止时，没有开始执行的资源标记为已跳过
+async fn skip_step_resource_records(
+    context: Arc<Context>,
+    step_resource_record_ids: &[Id],
+) -> Result<(), ErrNo> {
+    if step_resource_record_ids.is_empty() {
+        return Ok(());
+    }
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let curr_time = Utc::now();
+    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
+    let changes: Vec<JobStepResourceRecordProperty> = vec![
+        JobStepResourceRecordProperty::Status(StepResourceStatus::Skipped),
+        JobStepResourceRecordProperty::LastModifiedTime(curr_time),
+    ];
+    for step_resource_record_id in step_resource_record_ids {
+        job_step_resource_record_base_service
+            .update_job_step_resource_record(*step_resource_record_id, &changes)
+            .await?;
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(());
 }
 
 async fn update_step_record(
@@ -125,9 +1181,96 @@ async fn update_step_record(
     return Ok(());
 }
 
+//保存渲染之后的操作参数用于查看执行记录，密码类型的参数替换成掩码
+async fn save_rendered_parameter(
+    context: Arc<Context>,
+    step_record_id: Id,
+    operation_parameter: &str,
+    param_secrets: &[String],
+) -> Result<(), ErrNo> {
+    let mut rendered_parameter =
+        serde_json::from_str::<Value>(operation_parameter).map_err(ErrNo::DeserializeError)?;
+    redact_value_secrets(&mut rendered_parameter, param_secrets);
+    let rendered_parameter =
+        serde_json::to_string(&rendered_parameter).map_err(ErrNo::SerializeError)?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_step_record_base_service = JobStepRecordBaseService::new(&transaction);
+    job_step_record_base_service
+        .update_job_step_record(
+            step_record_id,
+            &[JobStepRecordProperty::RenderedParameter(Some(
+                rendered_parameter,
+            ))],
+        )
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(());
+}
+
+async fn update_step_resource_attempts(
+    context: Arc<Context>,
+    step_resource_record_id: Id,
+    attempts: i32,
+) -> Result<(), ErrNo> {
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let curr_time = Utc::now();
+    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
+    let changes: Vec<JobStepResourceRecordProperty> = vec![
+        JobStepResourceRecordProperty::Attempts(attempts),
+        JobStepResourceRecordProperty::LastModifiedTime(curr_time),
+    ];
+    job_step_resource_record_base_service
+        .update_job_step_resource_record(step_resource_record_id, &changes)
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(());
+}
+
 enum Output {
     File(String),
     Content(String),
+    Object { key: String, summary: String },
+}
+
+fn output_properties(output: Output) -> Vec<JobStepResourceRecordProperty> {
+    match output {
+        Output::File(file) => vec![JobStepResourceRecordProperty::OutputFile(Some(file))],
+        Output::Content(content) => {
+            vec![JobStepResourceRecordProperty::OutputContent(Some(content))]
+        }
+        Output::Object { key, summary } => vec![
+            JobStepResourceRecordProperty::LogKey(Some(key)),
+            JobStepResourceRecordProperty::LogSummary(Some(summary)),
+        ],
+    }
+}
+
+//把完整日志归档到对象存储，上传失败时退回到保存在数据库里，避免日志丢失
+async fn archive_logs(
+    context: &Context,
+    org_id: Id,
+    step_resource_record_id: Id,
+    logs: &[StepResLog],
+) -> Result<Output, ErrNo> {
+    match store_step_resource_log(context, org_id, step_resource_record_id, logs).await {
+        Ok((key, summary)) => Ok(Output::Object {
+            key: key,
+            summary: summary,
+        }),
+        Err(err) => {
+            log::error!("归档任务日志到对象存储失败，改为保存到数据库: {:?}", err);
+            let content = serde_json::to_string(logs).map_err(ErrNo::SerializeError)?;
+            Ok(Output::Content(content))
+        }
+    }
 }
 
 async fn update_step_resource_record(
@@ -135,19 +1278,20 @@ async fn update_step_resource_record(
     step_resource_record_id: Id,
     step_resource_status: StepResourceStatus,
     output: Output,
+    outputs: Option<String>,
 ) -> Result<(), ErrNo> {
     let mut client = context.get_db_client().await?;
     let transaction = client.transaction().await.map_err(open_transaction_error)?;
     let curr_time = Utc::now();
     let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
-    let changes: Vec<JobStepResourceRecordProperty> = vec![
+    let mut changes: Vec<JobStepResourceRecordProperty> = vec![
         JobStepResourceRecordProperty::Status(step_resource_status),
-        match output {
-            Output::File(file) => JobStepResourceRecordProperty::OutputFile(Some(file)),
-            Output::Content(content) => JobStepResourceRecordProperty::OutputContent(Some(content)),
-        },
         JobStepResourceRecordProperty::LastModifiedTime(curr_time),
     ];
+    changes.extend(output_properties(output));
+    if let Some(outputs) = outputs {
+        changes.push(JobStepResourceRecordProperty::Outputs(Some(outputs)));
+    }
     job_step_resource_record_base_service
         .update_job_step_resource_record(step_resource_record_id, &changes)
         .await?;
@@ -162,17 +1306,38 @@ async fn start_run(
     context: Arc<Context>,
     step_record_list: Vec<StepRecord>,
     record_id: Id,
+    cancel_receiver: watch::Receiver<bool>,
+    mut manual_receiver: mpsc::UnboundedReceiver<(Id, bool)>,
 ) -> Result<(), ErrNo> {
-    let result = try_start_run(context.clone(), step_record_list).await;
+    let result = try_start_run(
+        context.clone(),
+        step_record_list,
+        record_id,
+        &cancel_receiver,
+        &mut manual_receiver,
+    )
+    .await;
+    if let Ok(Status::Running) = result {
+        //停下来等待人工处理，记录状态仍是执行中，处理后可能已经有新的执行在更新状态
+        return Ok(());
+    }
+    let status = match result {
+        Ok(status) => status,
+        Err(_) => Status::Failure,
+    };
+    finish_job_record(&context, record_id, status).await?;
+    return result.map(|_| ());
+}
+
+//执行结束，释放占用的环境，交给排队等待的执行记录
+async fn finish_job_record(context: &Context, record_id: Id, status: Status) -> Result<(), ErrNo> {
     let mut client = context.get_db_client().await?;
     let transaction = client.transaction().await.map_err(open_transaction_error)?;
     let curr_time = Utc::now();
     let job_record_base_service = JobRecordBaseService::new(&transaction);
+    release_environment_lock(&transaction, record_id).await?;
     let changes: Vec<JobRecordProperty> = vec![
-        JobRecordProperty::Status(match result {
-            Ok(status) => status,
-            Err(_) => Status::Failure,
-        }),
+        JobRecordProperty::Status(status),
         JobRecordProperty::LastModifiedTime(curr_time),
     ];
     job_record_base_service
@@ -182,36 +1347,173 @@ async fn start_run(
         .commit()
         .await
         .map_err(commit_transaction_error)?;
-    return result.map(|_| ());
+    return Ok(());
+}
+
+//执行超时取消后，等待扩展停止的时间，超过后不再等待
+const CANCEL_WAIT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/**
+ * 自动步骤在各个资源上执行时共用的信息
+ */
+struct StepCall {
+    org_id: Id,                                            //组织id
+    extension_id: LightString,                             //扩展id
+    operation_id: LightString,                             //操作id
+    operation_parameter: Result<LightString, LightString>, //渲染之后的操作参数，渲染失败时是失败原因
+    timeout_seconds: Option<i32>,                          //超时时间（秒）
+    max_retries: i32,                                      //最大重试次数
+    retry_backoff: i32,                                    //重试间隔（秒）
+    runner_label: Option<LightString>,                     //执行器标签，为空时在服务端执行
+    param_secrets: Vec<String>, //密码类型的执行参数，日志和错误信息里要替换成掩码
+    cancel_receiver: watch::Receiver<bool>, //取消信号
 }
 
 async fn try_call_extension(
     context: Arc<Context>,
-    extension_id: LightString,
+    step_call: &StepCall,
+    operation_parameter: &str,
     extension_configuration: LightString,
-    operation_id: LightString,
-    operation_parameter: LightString,
     resource_index: u32,
     append_log: AppendLog,
-) -> Result<(), LightString> {
-    let extension_configuration =
-        serde_json::from_str(&extension_configuration).map_err(|err| err.to_string())?;
-    let operation_parameter =
-        serde_json::from_str(&operation_parameter).map_err(|err| err.to_string())?;
-    let extension = context
-        .get_extension(&extension_id)
-        .ok_or_else(|| LightString::from(format!("没有找到id为{}的扩展!", extension_id)))?;
-    return extension
-        .handle(
-            extension_configuration,
-            &operation_id,
-            operation_parameter,
-            context.get_extension_context(),
-            &append_log,
-            resource_index,
-        )
-        .await
-        .map_err(LightString::from);
+) -> Result<Option<String>, LightString> {
+    let (extension_info, extension) = context
+        .get_extension_info(&step_call.extension_id)
+        .ok_or_else(|| {
+            LightString::from(format!("没有找到id为{}的扩展!", step_call.extension_id))
+        })?;
+    //扩展配置是加密保存的，只在调用扩展之前解密，交给执行器的任务也只保存加密的配置
+    //扩展可能把密码拼进命令输出到日志或者错误信息里，需要替换成掩码，包括配置里的密码和密码类型的执行参数
+    let encrypted_configuration = extension_configuration;
+    let (extension_configuration, secrets) = decrypt_extension_configuration(
+        &context,
+        &extension_info.configuration_schema,
+        &encrypted_configuration,
+        &step_call.param_secrets,
+    )
+    .map_err(|err| err.message())?;
+    let operation_parameter: Value = serde_json::from_str(operation_parameter)
+        .map_err(|err| redact_secrets(&err.to_string(), &secrets))?;
+    let append_log = redact_append_log(append_log, secrets.clone());
+    //每次执行单独的取消信号，超时时只取消这一次执行，任务取消时转发过来
+    let (attempt_cancel_sender, attempt_cancel_receiver) =
+        watch::channel(*step_call.cancel_receiver.borrow());
+    let call = async {
+        if let Some(runner_label) = step_call.runner_label.as_ref() {
+            let runner_call = RunnerCall {
+                org_id: step_call.org_id,
+                label: runner_label.to_string(),
+                extension_id: step_call.extension_id.to_string(),
+                operation_id: step_call.operation_id.to_string(),
+                operation_parameter: operation_parameter.to_string(),
+                extension_configuration: encrypted_configuration.to_string(),
+                resource_index: resource_index,
+            };
+            return call_runner(
+                &context,
+                runner_call,
+                append_log.clone(),
+                attempt_cancel_receiver.clone(),
+            )
+            .await;
+        }
+        let extension_context = context.new_job_extension_context(attempt_cancel_receiver.clone());
+        return extension
+            .handle(
+                extension_configuration,
+                &step_call.operation_id,
+                operation_parameter,
+                &extension_context,
+                &append_log,
+                resource_index,
+            )
+            .await;
+    };
+    let deadline = step_call
+        .timeout_seconds
+        .map(|timeout_seconds| Instant::now() + Duration::from_secs(timeout_seconds as u64));
+    let result = match await_call(
+        call,
+        &step_call.cancel_receiver,
+        &attempt_cancel_sender,
+        deadline,
+        CANCEL_WAIT_TIMEOUT,
+    )
+    .await
+    {
+        CallOutcome::Finished(result) => result,
+        CallOutcome::TimedOut { stopped } => {
+            if !stopped {
+                //扩展不响应取消时不再等待，执行的future已经丢弃，不能让它一直占着步骤、环境和执行名额
+                log::warn!(
+                    "扩展{}超时取消后{}秒内没有停止，放弃等待",
+                    step_call.extension_id,
+                    CANCEL_WAIT_TIMEOUT.as_secs()
+                );
+            }
+            Err(format!(
+                "执行超时（{}秒）",
+                step_call.timeout_seconds.unwrap_or_default()
+            ))
+        }
+    };
+    let mut outputs = result.map_err(|err| LightString::from(redact_secrets(&err, &secrets)))?;
+    if outputs.is_null() {
+        return Ok(None);
+    }
+    //输出里可能带有脚本输出等内容，保存之前把密码替换成掩码
+    redact_value_secrets(&mut outputs, &secrets);
+    let outputs = serde_json::to_string(&outputs).map_err(|err| err.to_string())?;
+    return Ok(Some(outputs));
+}
+
+enum CallOutcome<T> {
+    Finished(T),                //执行结束
+    TimedOut { stopped: bool }, //执行超时，stopped表示取消之后扩展是否在等待时间内停了下来
+}
+
+/**
+ * 等待一次执行结束，任务取消时转发给这次执行的取消信号
+ * 到了deadline还没结束时发出取消信号，在cancel_wait内等它停下来，避免和下一次重试同时在资源上执行，超过后丢弃执行的future
+ */
+async fn await_call<T>(
+    call: impl Future<Output = T>,
+    cancel_receiver: &watch::Receiver<bool>,
+    attempt_cancel_sender: &watch::Sender<bool>,
+    deadline: Option<Instant>,
+    cancel_wait: Duration,
+) -> CallOutcome<T> {
+    tokio::pin!(call);
+    let mut cancel_receiver = cancel_receiver.clone();
+    let mut cancel_closed = false;
+    loop {
+        tokio::select! {
+            result = &mut call => return CallOutcome::Finished(result),
+            changed = cancel_receiver.changed(), if !cancel_closed => {
+                if changed.is_ok() {
+                    attempt_cancel_sender.send_replace(*cancel_receiver.borrow());
+                } else {
+                    cancel_closed = true;
+                }
+            }
+            _ = sleep_until(deadline.unwrap_or_else(Instant::now)), if deadline.is_some() => {
+                attempt_cancel_sender.send_replace(true);
+                let stopped = timeout(cancel_wait, &mut call).await.is_ok();
+                return CallOutcome::TimedOut { stopped: stopped };
+            }
+        }
+    }
+}
+
+//第attempt次执行失败之后是否还能重试，max_retries不包括第一次执行
+fn can_retry(max_retries: i32, attempt: i32) -> bool {
+    return attempt <= max_retries;
+}
+
+//第attempt次执行失败后，等待多久再重试，每次重试的间隔翻倍
+fn retry_delay(retry_backoff: i32, attempt: i32) -> Duration {
+    let factor = 1u64 << (attempt - 1).clamp(0, 10);
+    return Duration::from_secs(retry_backoff as u64 * factor);
 }
 
 fn try_write_log(log_file: &Mutex<File>, log: &StepResLog) -> Result<(), anyhow::Error> {
@@ -225,10 +1527,8 @@ fn try_write_log(log_file: &Mutex<File>, log: &StepResLog) -> Result<(), anyhow:
 
 async fn call_extension(
     context: Arc<Context>,
-    extension_id: LightString,
+    step_call: &StepCall,
     extension_configuration: LightString,
-    operation_id: LightString,
-    operation_parameter: LightString,
     step_resource_record_id: Id,
     resource_index: u32,
 ) -> Result<(), ErrNo> {
@@ -238,11 +1538,13 @@ async fn call_extension(
         ErrNo::CommonError(LightString::from(format!("创建任务日志文件报错：{}", err)))
     })?;
     let log_file = Mutex::new(log_file);
+    let live_log_sender = start_live_log_writer(context.clone(), step_resource_record_id);
     update_step_resource_record(
         context.clone(),
         step_resource_record_id,
         StepResourceStatus::Running,
         Output::File(log_file_name),
+        None,
     )
     .await?;
     let append_log: AppendLog = Arc::new(
@@ -261,52 +1563,139 @@ async fn call_extension(
             if let Err(err) = try_write_log(&log_file, &log) {
                 log::error!("写任务日志失败：{:?}", err);
             }
+            //同时写到redis，任何实例上的实时日志订阅都能读到
+            let _ = live_log_sender.send(log);
         },
     );
-    let result = try_call_extension(
-        context.clone(),
-        extension_id,
-        extension_configuration,
-        operation_id,
-        operation_parameter,
-        resource_index,
-        append_log,
-    )
-    .await;
-    let output = read_to_string(&log_file_path).await.map_err(|err| {
-        ErrNo::CommonError(LightString::from(format!("读取任务日志内容失败：{}", err)))
-    })?;
-    let mut output = format!("[{}", output);
+    let mut attempt: i32 = 0;
+    let result = loop {
+        attempt += 1;
+        update_step_resource_attempts(context.clone(), step_resource_record_id, attempt).await?;
+        if 0 < step_call.max_retries {
+            append_log(
+                mould_extension_sdk::LogLevel::Info,
+                format!("开始第{}次执行", attempt),
+            );
+        }
+        let operation_parameter = match step_call.operation_parameter.as_ref() {
+            Ok(operation_parameter) => operation_parameter,
+            //操作参数渲染失败，重试也没有用
+            Err(err) => break Err(err.clone()),
+        };
+        let result = try_call_extension(
+            context.clone(),
+            step_call,
+            operation_parameter,
+            extension_configuration.clone(),
+            resource_index,
+            append_log.clone(),
+        )
+        .await;
+        match result {
+            Ok(outputs) => break Ok(outputs),
+            Err(err) => {
+                if !can_retry(step_call.max_retries, attempt) || *step_call.cancel_receiver.borrow()
+                {
+                    break Err(err);
+                }
+                let delay = retry_delay(step_call.retry_backoff, attempt);
+                append_log(
+                    mould_extension_sdk::LogLevel::Warn,
+                    format!(
+                        "第{}次执行失败：{}，{}秒后重试",
+                        attempt,
+                        err,
+                        delay.as_secs()
+                    ),
+                );
+                let mut cancel_receiver = step_call.cancel_receiver.clone();
+                tokio::select! {
+                    _ = sleep(delay) => (),
+                    _ = cancel_receiver.changed() => (),
+                }
+                if *step_call.cancel_receiver.borrow() {
+                    break Err(err);
+                }
+            }
+        }
+    };
+    let mut logs = read_log_file(&log_file_path).await?;
     let status = match result.as_ref() {
         Ok(_) => StepResourceStatus::Success,
         Err(error) => {
-            let log = StepResLog {
+            logs.push(StepResLog {
                 time: Utc::now(),
                 level: LogLevel::Error,
                 content: error.to_string(),
-            };
-            let log_content = serde_json::to_string(&log).map_err(ErrNo::SerializeError)?;
-            output.push_str(&log_content);
-            StepResourceStatus::Failure
+            });
+            if *step_call.cancel_receiver.borrow() {
+                StepResourceStatus::Cancelled
+            } else {
+                StepResourceStatus::Failure
+            }
         }
     };
-    if output.ends_with(",") {
-        output.pop();
-    }
-    output.push_str("]");
-    update_step_resource_record(
-        context,
-        step_resource_record_id,
-        status,
-        Output::Content(output),
-    )
-    .await?;
+    let output = archive_logs(&context, step_call.org_id, step_resource_record_id, &logs).await?;
+    let outputs = result.as_ref().ok().cloned().flatten();
+    update_step_resource_record(context, step_resource_record_id, status, output, outputs).await?;
     if let Err(err) = remove_file(log_file_path) {
         log::error!("移除任务日志失败：{:?}", err);
     }
     return result.map(|_| ()).map_err(ErrNo::CommonError);
 }
 
+//把残留的日志文件整理后追加一条错误日志，归档保存到执行资源记录，然后移除日志文件
+//在更新状态的事务提交之后调用，上传对象存储期间不持有执行记录的行锁
+async fn salvage_output(
+    context: &Context,
+    org_id: Id,
+    step_resource_record_id: Id,
+    log_file_path: &str,
+    message: &str,
+) -> Result<(), ErrNo> {
+    let mut logs = read_log_file(log_file_path).await.unwrap_or_else(|err| {
+        log::error!("读取任务日志内容失败：{:?}", err);
+        Vec::new()
+    });
+    logs.push(StepResLog {
+        time: Utc::now(),
+        level: LogLevel::Error,
+        content: String::from(message),
+    });
+    let output = archive_logs(context, org_id, step_resource_record_id, &logs).await?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
+    job_step_resource_record_base_service
+        .update_job_step_resource_record(step_resource_record_id, &output_properties(output))
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    if let Err(err) = remove_file(log_file_path) {
+        log::error!("移除任务日志失败：{:?}", err);
+    }
+    return Ok(());
+}
+
+//依次保存残留的日志，某一个失败时保留日志文件，继续处理其它的
+async fn salvage_outputs(context: &Context, salvage_list: &[(Id, Id, String)], message: &str) {
+    for (org_id, step_resource_record_id, log_file_path) in salvage_list {
+        if let Err(err) = salvage_output(
+            context,
+            *org_id,
+            *step_resource_record_id,
+            log_file_path,
+            message,
+        )
+        .await
+        {
+            log::error!("保存残留的任务日志{}失败：{:?}", log_file_path, err);
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub enum Step {
     Auto {
@@ -322,6 +1711,7 @@ pub enum Step {
 fn merge_step_and_resource(
     context: &Context,
     job_step_list: Vec<JobStep>,
+    job_params: &Map<String, Value>,
     environment_schema_resource_list: Vec<EnvironmentSchemaResource>,
     environment_resource_list: Vec<EnvironmentResource>,
 ) -> Result<Vec<Step>, ErrNo> {
@@ -360,9 +1750,23 @@ fn merge_step_and_resource(
                                     "操作参数格式不正确",
                                 ));
                             })?;
-                    extension
-                        .validate_operation_parameter(&job_step.operation_id, operation_parameter)
-                        .map_err(|err| ErrNo::CommonError(err.into()))?;
+                    //引用了步骤输出的操作参数，等到执行步骤渲染之后再校验
+                    if !has_step_refs(&operation_parameter) {
+                        let operation_parameter = render_operation_parameter(
+                            &job_step.name,
+                            &job_step.operation_parameter,
+                            job_params,
+                        )?;
+                        let operation_parameter =
+                            serde_json::from_str::<serde_json::Value>(&operation_parameter)
+                                .map_err(ErrNo::DeserializeError)?;
+                        extension
+                            .validate_operation_parameter(
+                                &job_step.operation_id,
+                                operation_parameter,
+                            )
+                            .map_err(|err| ErrNo::CommonError(err.into()))?;
+                    }
                 } else {
                     return Err(ErrNo::CommonError(LightString::from(format!(
                         "扩展\"{}\"没有名为\"{}\"的操作!",
@@ -410,3 +1814,339 @@ fn merge_step_and_resource(
     }
     return Ok(list);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_delay() {
+        assert_eq!(Duration::from_secs(5), retry_delay(5, 1));
+        assert_eq!(Duration::from_secs(10), retry_delay(5, 2));
+        assert_eq!(Duration::from_secs(20), retry_delay(5, 3));
+        assert_eq!(Duration::from_secs(0), retry_delay(0, 3));
+        //间隔最多翻倍10次
+        assert_eq!(Duration::from_secs(1024), retry_delay(1, 100));
+    }
+
+    #[test]
+    fn test_can_retry() {
+        assert!(!can_retry(0, 1));
+        assert!(can_retry(2, 1));
+        assert!(can_retry(2, 2));
+        assert!(!can_retry(2, 3));
+    }
+
+    #[tokio::test]
+    async fn test_await_call_finished() {
+        let (_cancel_sender, cancel_receiver) = watch::channel(false);
+        let (attempt_cancel_sender, _attempt_cancel_receiver) = watch::channel(false);
+        let deadline = Some(Instant::now() + Duration::from_secs(10));
+        let outcome = await_call(
+            async { 1 },
+            &cancel_receiver,
+            &attempt_cancel_sender,
+            deadline,
+            Duration::from_millis(10),
+        )
+        .await;
+        assert!(matches!(outcome, CallOutcome::Finished(1)));
+        assert!(!*attempt_cancel_sender.borrow());
+    }
+
+    #[tokio::test]
+    async fn test_await_call_timeout_stopped() {
+        let (_cancel_sender, cancel_receiver) = watch::channel(false);
+        let (attempt_cancel_sender, mut attempt_cancel_receiver) = watch::channel(false);
+        //响应取消信号的执行
+        let call = async move {
+            let _ = attempt_cancel_receiver.changed().await;
+            *attempt_cancel_receiver.borrow()
+        };
+        let deadline = Some(Instant::now() + Duration::from_millis(10));
+        let outcome = await_call(
+            call,
+            &cancel_receiver,
+            &attempt_cancel_sender,
+            deadline,
+            Duration::from_secs(10),
+        )
+        .await;
+        assert!(matches!(outcome, CallOutcome::TimedOut { stopped: true }));
+    }
+
+    #[tokio::test]
+    async fn test_await_call_timeout_abandoned() {
+        let (_cancel_sender, cancel_receiver) = watch::channel(false);
+        let (attempt_cancel_sender, _attempt_cancel_receiver) = watch::channel(false);
+        //不响应取消信号的执行，等待一段时间后放弃
+        let call = sleep(Duration::from_secs(60));
+        let deadline = Some(Instant::now() + Duration::from_millis(10));
+        let start = Instant::now();
+        let outcome = await_call(
+            call,
+            &cancel_receiver,
+            &attempt_cancel_sender,
+            deadline,
+            Duration::from_millis(10),
+        )
+        .await;
+        assert!(matches!(outcome, CallOutcome::TimedOut { stopped: false }));
+        assert!(*attempt_cancel_sender.borrow());
+        assert!(start.elapsed() < Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn test_await_call_forward_cancel() {
+        let (cancel_sender, cancel_receiver) = watch::channel(false);
+        let (attempt_cancel_sender, mut attempt_cancel_receiver) = watch::channel(false);
+        let call = async move {
+            let _ = attempt_cancel_receiver.changed().await;
+            *attempt_cancel_receiver.borrow()
+        };
+        let cancel = async {
+            sleep(Duration::from_millis(10)).await;
+            cancel_sender.send_replace(true);
+        };
+        let (outcome, _) = tokio::join!(
+            await_call(
+                call,
+                &cancel_receiver,
+                &attempt_cancel_sender,
+                None,
+                Duration::from_millis(10)
+            ),
+            cancel
+        );
+        assert!(matches!(outcome, CallOutcome::Finished(true)));
+    }
+
+    fn batch_policy(batch_size: usize, max_parallel: usize, stop_on_failure: bool) -> BatchPolicy {
+        return BatchPolicy {
+            batch_size: batch_size,
+            max_parallel: max_parallel,
+            pause_between_batches: Duration::ZERO,
+            stop_on_failure: stop_on_failure,
+        };
+    }
+
+    //执行list里的资源，fail_list里的资源执行失败，返回执行结果和同时执行的最大资源数
+    async fn run_test_batches(
+        list: Vec<i32>,
+        batch_policy: &BatchPolicy,
+        fail_list: &[i32],
+        cancelled: bool,
+    ) -> (BatchOutcome<i32, i32>, usize) {
+        let (_cancel_sender, cancel_receiver) = watch::channel(cancelled);
+        let running = Arc::new(Mutex::new((0usize, 0usize)));
+        let outcome = run_batches(list, batch_policy, &cancel_receiver, |item| {
+            let running = running.clone();
+            let failed = fail_list.contains(&item);
+            async move {
+                {
+                    let mut running = running.lock().unwrap();
+                    running.0 += 1;
+                    running.1 = running.1.max(running.0);
+                }
+                sleep(Duration::from_millis(5)).await;
+                running.lock().unwrap().0 -= 1;
+                if failed {
+                    Err(item)
+                } else {
+                    Ok(())
+                }
+            }
+        })
+        .await;
+        let max_running = running.lock().unwrap().1;
+        return (outcome, max_running);
+    }
+
+    #[tokio::test]
+    async fn test_run_batches_parallel() {
+        //不限并发时一批内的资源同时执行，下一批等上一批结束后才开始
+        let (outcome, max_running) = run_test_batches(
+            (0..5).collect(),
+            &batch_policy(2, usize::MAX, false),
+            &[],
+            false,
+        )
+        .await;
+        assert_eq!(5, outcome.started_count);
+        assert!(outcome.first_err.is_none());
+        assert!(outcome.skipped_list.is_empty());
+        assert_eq!(2, max_running);
+        let (outcome, max_running) =
+            run_test_batches((0..5).collect(), &batch_policy(5, 2, false), &[], false).await;
+        assert_eq!(5, outcome.started_count);
+        assert_eq!(2, max_running);
+    }
+
+    #[tokio::test]
+    async fn test_run_batches_skip_after_failure() {
+        //失败的批次内其它资源继续执行，后面批次的资源跳过
+        let (outcome, _) = run_test_batches(
+            (0..6).collect(),
+            &batch_policy(2, usize::MAX, false),
+            &[1],
+            false,
+        )
+        .await;
+        assert_eq!(2, outcome.started_count);
+        assert_eq!(Some(1), outcome.first_err);
+        assert_eq!(vec![2, 3, 4, 5], outcome.skipped_list);
+        let (outcome, _) =
+            run_test_batches((0..4).collect(), &batch_policy(4, 1, false), &[0, 2], false).await;
+        assert_eq!(4, outcome.started_count);
+        assert_eq!(Some(0), outcome.first_err);
+        assert!(outcome.skipped_list.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_batches_stop_on_failure() {
+        //失败后同一批次内没有开始的资源也不再执行
+        let (outcome, _) =
+            run_test_batches((0..6).collect(), &batch_policy(4, 1, true), &[0], false).await;
+        assert_eq!(1, outcome.started_count);
+        assert_eq!(Some(0), outcome.first_err);
+        assert_eq!(vec![1, 2, 3, 4, 5], outcome.skipped_list);
+    }
+
+    #[tokio::test]
+    async fn test_run_batches_cancelled() {
+        //已取消时不再启动资源，也不算跳过
+        let (outcome, _) = run_test_batches(
+            (0..4).collect(),
+            &batch_policy(2, usize::MAX, true),
+            &[],
+            true,
+        )
+        .await;
+        assert_eq!(0, outcome.started_count);
+        assert!(outcome.first_err.is_none());
+        assert!(outcome.skipped_list.is_empty());
+    }
+
+    fn id_set(list: &[i32]) -> HashSet<i32> {
+        return list.iter().cloned().collect();
+    }
+
+    #[test]
+    fn test_check_readiness_on_success() {
+        let depends_on = [1, 2];
+        //依赖的步骤都成功之后才执行
+        assert!(matches!(
+            check_readiness(
+                RecordRunIf::OnSuccess,
+                &[],
+                &id_set(&[]),
+                &id_set(&[]),
+                false
+            ),
+            Readiness::Run
+        ));
+        assert!(matches!(
+            check_readiness(
+                RecordRunIf::OnSuccess,
+                &depends_on,
+                &id_set(&[1]),
+                &id_set(&[1]),
+                false
+            ),
+            Readiness::Wait
+        ));
+        assert!(matches!(
+            check_readiness(
+                RecordRunIf::OnSuccess,
+                &depends_on,
+                &id_set(&[1, 2]),
+                &id_set(&[1, 2]),
+                false
+            ),
+            Readiness::Run
+        ));
+        //有步骤失败时跳过
+        assert!(matches!(
+            check_readiness(
+                RecordRunIf::OnSuccess,
+                &depends_on,
+                &id_set(&[1]),
+                &id_set(&[]),
+                true
+            ),
+            Readiness::Skip(_)
+        ));
+    }
+
+    #[test]
+    fn test_check_readiness_on_failure() {
+        let depends_on = [1, 2];
+        //没有失败时一直等待，依赖的步骤都结束并且有失败时才执行
+        assert!(matches!(
+            check_readiness(
+                RecordRunIf::OnFailure,
+                &depends_on,
+                &id_set(&[1, 2]),
+                &id_set(&[1, 2]),
+                false
+            ),
+            Readiness::Wait
+        ));
+        assert!(matches!(
+            check_readiness(
+                RecordRunIf::OnFailure,
+                &depends_on,
+                &id_set(&[1]),
+                &id_set(&[]),
+                true
+            ),
+            Readiness::Wait
+        ));
+        assert!(matches!(
+            check_readiness(
+                RecordRunIf::OnFailure,
+                &depends_on,
+                &id_set(&[1, 2]),
+                &id_set(&[2]),
+                true
+            ),
+            Readiness::Run
+        ));
+    }
+
+    #[test]
+    fn test_check_readiness_always() {
+        let depends_on = [1, 2];
+        //不管成功失败，依赖的步骤都结束之后执行
+        assert!(matches!(
+            check_readiness(
+                RecordRunIf::Always,
+                &depends_on,
+                &id_set(&[2]),
+                &id_set(&[2]),
+                false
+            ),
+            Readiness::Wait
+        ));
+        assert!(matches!(
+            check_readiness(
+                RecordRunIf::Always,
+                &depends_on,
+                &id_set(&[1, 2]),
+                &id_set(&[]),
+                true
+            ),
+            Readiness::Run
+        ));
+        assert!(matches!(
+            check_readiness(
+                RecordRunIf::Always,
+                &depends_on,
+                &id_set(&[1, 2]),
+                &id_set(&[1, 2]),
+                false
+            ),
+            Readiness::Run
+        ));
+    }
+}
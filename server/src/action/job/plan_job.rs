@@ -0,0 +1,205 @@
+use super::super::api_token::check_token_job;
+use super::super::environment::grant::check_environment_grant;
+use super::super::extension::secret::decrypt_extension_configuration;
+use super::super::extension::secret::pick_passwords;
+use super::super::extension::secret::redact_secrets;
+use super::start_job::prepare_job;
+use super::start_job::PreparedJob;
+use super::template::has_step_refs;
+use super::template::render_operation_parameter;
+use super::Resource;
+use super::Step;
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::environment_grant::enums::GrantPermission;
+use crate::model::job_step::JobStep;
+use crate::sdk;
+use crate::Context;
+use futures::future::join_all;
+use sdk::job::plan_job::PlanJobReq;
+use sdk::job::plan_job::PlanJobResp;
+use sdk::job::plan_job::PlanResult;
+use sdk::job::plan_job::ResourcePlan;
+use sdk::job::plan_job::StepPlan;
+use serde_json::Value;
+use std::time::Duration;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+use tokio::time::timeout;
+
+/**
+ * 预览任务在目标环境上会做哪些修改，不会真正执行
+ */
+pub async fn plan_job(
+    org_id: Id,
+    user: User,
+    plan_job_req: PlanJobReq,
+) -> Result<PlanJobResp, ErrNo> {
+    let PlanJobReq {
+        job_id,
+        environment_id,
+        params,
+    } = plan_job_req;
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    check_token_job(&transaction, &user, job_id).await?;
+    //预览需要解密目标环境的资源配置，和执行任务需要同样的授权
+    check_environment_grant(
+        &transaction,
+        org_id,
+        &user,
+        environment_id,
+        GrantPermission::Run,
+    )
+    .await?;
+    let PreparedJob {
+        parameter_schema,
+        params: job_params,
+        step_list,
+        mut skip_reasons,
+        ..
+    } = prepare_job(
+        &context,
+        &transaction,
+        org_id,
+        job_id,
+        environment_id,
+        params,
+    )
+    .await?;
+    //预览内容和错误信息里出现的密码类型的参数也要替换成掩码
+    let param_secrets: Vec<String> = pick_passwords(&parameter_schema, &job_params)
+        .values()
+        .filter_map(|value| value.as_str())
+        .map(|value| value.to_string())
+        .collect();
+    let mut step_plan_list = Vec::with_capacity(step_list.len());
+    for step in step_list {
+        match step {
+            Step::Auto {
+                mut job_step,
+                extension_id,
+                resource_list,
+            } => {
+                let skip_reason = skip_reasons.remove(&job_step.id);
+                //跳过的步骤不需要预览
+                let resource_plan_list = if skip_reason.is_some() {
+                    Vec::new()
+                } else {
+                    job_step.operation_parameter = render_operation_parameter(
+                        &job_step.name,
+                        &job_step.operation_parameter,
+                        &job_params,
+                    )?;
+                    join_all(resource_list.into_iter().enumerate().map(
+                        |(resource_index, resource)| {
+                            plan_resource(
+                                &context,
+                                &job_step,
+                                &extension_id,
+                                resource,
+                                resource_index as u32,
+                                &param_secrets,
+                            )
+                        },
+                    ))
+                    .await
+                };
+                step_plan_list.push(StepPlan::Auto {
+                    step_name: job_step.name,
+                    operation_name: job_step.operation_name,
+                    skip_reason: skip_reason,
+                    resource_plan_list: resource_plan_list,
+                });
+            }
+            Step::Manual { job_step } => {
+                step_plan_list.push(StepPlan::Manual {
+                    skip_reason: skip_reasons.remove(&job_step.id),
+                    step_name: job_step.name,
+                });
+            }
+        }
+    }
+    return Ok(PlanJobResp {
+        step_plan_list: step_plan_list,
+    });
+}
+
+async fn plan_resource(
+    context: &Context,
+    job_step: &JobStep,
+    extension_id: &str,
+    resource: Resource,
+    resource_index: u32,
+    param_secrets: &[String],
+) -> ResourcePlan {
+    let result = match try_plan_resource(
+        context,
+        job_step,
+        extension_id,
+        &resource.extension_configuration,
+        resource_index,
+        param_secrets,
+    )
+    .await
+    {
+        Ok(Some(result)) => result,
+        Ok(None) => PlanResult::Unsupported,
+        Err(err) => PlanResult::Error(err.to_string()),
+    };
+    return ResourcePlan {
+        resource_name: resource.name,
+        result: result,
+    };
+}
+
+async fn try_plan_resource(
+    context: &Context,
+    job_step: &JobStep,
+    extension_id: &str,
+    extension_configuration: &str,
+    resource_index: u32,
+    param_secrets: &[String],
+) -> Result<Option<PlanResult>, LightString> {
+    let operation_parameter: Value =
+        serde_json::from_str(&job_step.operation_parameter).map_err(|err| err.to_string())?;
+    if has_step_refs(&operation_parameter) {
+        return Ok(Some(PlanResult::Deferred));
+    }
+    let (extension_info, extension) = context
+        .get_extension_info(extension_id)
+        .ok_or_else(|| LightString::from(format!("没有找到id为{}的扩展!", extension_id)))?;
+    //和执行步骤一样解密配置，预览内容和错误信息里可能带有密码，需要替换成掩码
+    let (extension_configuration, secrets) = decrypt_extension_configuration(
+        context,
+        &extension_info.configuration_schema,
+        extension_configuration,
+        param_secrets,
+    )
+    .map_err(|err| err.message())?;
+    let plan = extension.plan(
+        extension_configuration,
+        &job_step.operation_id,
+        operation_parameter,
+        context.get_extension_context(),
+        resource_index,
+    );
+    //和执行步骤一样受超时时间限制
+    let result = match job_step.timeout_seconds {
+        Some(timeout_seconds) => {
+            match timeout(Duration::from_secs(timeout_seconds as u64), plan).await {
+                Ok(result) => result,
+                Err(_) => Err(format!("预览超时（{}秒）", timeout_seconds)),
+            }
+        }
+        None => plan.await,
+    };
+    return result
+        .map(|changes| {
+            changes.map(|changes| PlanResult::Changes(redact_secrets(&changes, &secrets)))
+        })
+        .map_err(|err| LightString::from(redact_secrets(&err, &secrets)));
+}
@@ -0,0 +1,189 @@
+use super::spawn_run_locked;
+use super::RUNNING_JOBS;
+use crate::model::job_record::enums::Status;
+use crate::model::job_record::JobRecordProperty;
+use crate::service::base::JobRecordBaseService;
+use crate::service::job_record::JobRecordService;
+use crate::Context;
+use chrono::Utc;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tihu::Id;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+use tokio::sync::Mutex;
+use tokio::sync::Notify;
+
+//每次最多从队列里取出的记录数
+const DISPATCH_BATCH_SIZE: u64 = 100;
+
+lazy_static::lazy_static! {
+    //有任务进入队列或者执行结束时通知任务队列
+    pub static ref JOB_QUEUE_NOTIFY: Notify = Notify::new();
+    //同一个实例同时只能有一个调度，避免超出执行上限
+    static ref DISPATCH_LOCK: Mutex<()> = Mutex::new(());
+}
+
+/**
+ * 通知任务队列检查是否有可以开始执行的记录
+ */
+pub fn notify_job_queue() {
+    JOB_QUEUE_NOTIFY.notify_one();
+}
+
+/**
+ * 在不超过执行上限的前提下，按照排队的先后顺序开始执行已经占用到环境的记录
+ */
+pub async fn dispatch_queued_jobs(context: Arc<Context>) -> Result<(), ErrNo> {
+    let _dispatch_guard = DISPATCH_LOCK.lock().await;
+    let max_running_jobs = context.config.max_running_jobs;
+    let max_running_jobs_per_org = context.config.max_running_jobs_per_org;
+    let instance_id = context.get_instance_id();
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_record_service = JobRecordService::new(&transaction);
+    let job_record_base_service = JobRecordBaseService::new(&transaction);
+    //和其它实例的调度互斥，组织的执行数在所有实例之间共享
+    job_record_service.lock_job_queue().await?;
+    //按照数据库里本实例执行中的记录计数，接手和恢复的记录也都算在内
+    let total_count = job_record_service
+        .query_instance_running_count(&instance_id)
+        .await? as usize;
+    if total_count >= max_running_jobs {
+        return Ok(());
+    }
+    let mut org_counts: HashMap<Id, usize> = job_record_service
+        .query_org_running_counts()
+        .await?
+        .into_iter()
+        .map(|(org_id, count)| (org_id, count as usize))
+        .collect();
+    let job_record_list = job_record_service
+        .query_dispatchable_job_record(DISPATCH_BATCH_SIZE)
+        .await?;
+    let queued_org_ids: Vec<Id> = job_record_list
+        .iter()
+        .map(|job_record| job_record.org_id)
+        .collect();
+    let picked_indexes = pick_dispatchable(
+        &queued_org_ids,
+        total_count,
+        &mut org_counts,
+        max_running_jobs,
+        max_running_jobs_per_org,
+    );
+    let mut claimed_list: Vec<(Id, Id)> = Vec::with_capacity(picked_indexes.len());
+    let curr_time = Utc::now();
+    for index in picked_indexes {
+        let job_record = &job_record_list[index];
+        job_record_base_service
+            .update_job_record(
+                job_record.id,
+                &[
+                    JobRecordProperty::Status(Status::Running),
+                    JobRecordProperty::InstanceId(Some(instance_id)),
+                    JobRecordProperty::HeartbeatTime(Some(curr_time)),
+                    JobRecordProperty::LastModifiedTime(curr_time),
+                ],
+            )
+            .await?;
+        claimed_list.push((job_record.org_id, job_record.id));
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    if claimed_list.is_empty() {
+        return Ok(());
+    }
+    let mut running_jobs = RUNNING_JOBS.lock().unwrap();
+    for (org_id, record_id) in claimed_list {
+        spawn_run_locked(&mut running_jobs, context.clone(), org_id, None, record_id);
+    }
+    return Ok(());
+}
+
+/**
+ * 按照排队的先后顺序挑选可以开始执行的记录，返回在queued_org_ids中的下标
+ * queued_org_ids为排队记录所属的组织，total_count为本实例执行中的记录数，org_counts为各个组织执行中的记录数，挑中的记录会计入org_counts
+ */
+fn pick_dispatchable(
+    queued_org_ids: &[Id],
+    mut total_count: usize,
+    org_counts: &mut HashMap<Id, usize>,
+    max_running_jobs: usize,
+    max_running_jobs_per_org: Option<usize>,
+) -> Vec<usize> {
+    let mut picked_indexes = Vec::new();
+    for (index, org_id) in queued_org_ids.iter().enumerate() {
+        if total_count >= max_running_jobs {
+            break;
+        }
+        let org_count = org_counts.entry(*org_id).or_default();
+        //组织的名额满了，让后面其它组织的记录先执行
+        if let Some(max_running_jobs_per_org) = max_running_jobs_per_org {
+            if *org_count >= max_running_jobs_per_org {
+                continue;
+            }
+        }
+        *org_count += 1;
+        total_count += 1;
+        picked_indexes.push(index);
+    }
+    return picked_indexes;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pick_dispatchable_total_limit() {
+        let org_id = Id::from_u128(1);
+        let mut org_counts = HashMap::new();
+        let picked = pick_dispatchable(&[org_id; 5], 1, &mut org_counts, 3, None);
+        assert_eq!(picked, vec![0, 1]);
+        assert_eq!(org_counts.get(&org_id), Some(&2));
+        let mut org_counts = HashMap::new();
+        let picked = pick_dispatchable(&[org_id; 5], 3, &mut org_counts, 3, None);
+        assert!(picked.is_empty());
+    }
+
+    #[test]
+    fn test_pick_dispatchable_org_limit() {
+        let org_a = Id::from_u128(1);
+        let org_b = Id::from_u128(2);
+        //org_a在其它实例上已经有一条执行中的记录
+        let mut org_counts = HashMap::from([(org_a, 1)]);
+        let picked = pick_dispatchable(
+            &[org_a, org_a, org_b, org_a, org_b, org_b],
+            0,
+            &mut org_counts,
+            10,
+            Some(2),
+        );
+        //org_a的名额满了之后跳过，后面org_b的记录继续执行
+        assert_eq!(picked, vec![0, 2, 4]);
+        assert_eq!(org_counts.get(&org_a), Some(&2));
+        assert_eq!(org_counts.get(&org_b), Some(&2));
+    }
+
+    #[test]
+    fn test_pick_dispatchable_both_limits() {
+        let org_a = Id::from_u128(1);
+        let org_b = Id::from_u128(2);
+        let mut org_counts = HashMap::new();
+        let picked = pick_dispatchable(
+            &[org_a, org_a, org_a, org_b, org_b],
+            1,
+            &mut org_counts,
+            3,
+            Some(1),
+        );
+        assert_eq!(picked, vec![0, 3]);
+        let mut org_counts = HashMap::from([(org_a, 5)]);
+        let picked = pick_dispatchable(&[org_a, org_a], 0, &mut org_counts, 3, Some(5));
+        assert!(picked.is_empty());
+    }
+}
@@ -1,3 +1,10 @@
+use super::graph::parse_depends_on;
+use super::signoff::parse_signoff_users;
+use super::template::parse_parameter_schema;
+use super::to_sdk_failure_policy;
+use super::to_sdk_interrupt_policy;
+use super::to_sdk_run_if;
+use super::to_sdk_signoff_role;
 use crate::get_context;
 use crate::middleware::auth::User;
 use crate::model::job::JobOpt;
@@ -54,14 +61,30 @@ pub async fn read_job(org_id: Id, _user: User, read_job_req: ReadJobReq) -> Resu
                 operation_name: job_step.operation_name,
                 operation_parameter: job_step.operation_parameter,
                 remark: job_step.remark,
+                timeout_seconds: job_step.timeout_seconds,
+                max_retries: job_step.max_retries,
+                retry_backoff: job_step.retry_backoff,
+                max_parallel: job_step.max_parallel,
+                batch_size: job_step.batch_size,
+                pause_between_batches: job_step.pause_between_batches,
+                failure_policy: to_sdk_failure_policy(job_step.failure_policy),
+                run_if: to_sdk_run_if(job_step.run_if),
+                run_expression: job_step.run_expression,
                 seq: job_step.seq,
+                depends_on: parse_depends_on(job_step.depends_on.as_deref())?,
             },
             StepType::Manual => JobStep::Manual {
                 id: job_step.id.into(),
                 name: job_step.name,
                 remark: job_step.remark,
                 attachments: job_step.attachments,
+                signoff_role: job_step.signoff_role.map(to_sdk_signoff_role),
+                signoff_user_list: parse_signoff_users(job_step.signoff_users.as_deref())?,
+                required_signoffs: job_step.required_signoffs,
+                run_if: to_sdk_run_if(job_step.run_if),
+                run_expression: job_step.run_expression,
                 seq: job_step.seq,
+                depends_on: parse_depends_on(job_step.depends_on.as_deref())?,
             },
         });
     }
@@ -70,6 +93,8 @@ pub async fn read_job(org_id: Id, _user: User, read_job_req: ReadJobReq) -> Resu
         environment_schema_id: job.environment_schema_id.into(),
         name: job.name,
         remark: job.remark,
+        interrupt_policy: to_sdk_interrupt_policy(job.interrupt_policy),
+        parameter_schema: parse_parameter_schema(&job.parameter_schema)?,
         job_step_list: step_list,
     });
 }
@@ -0,0 +1,316 @@
+use super::super::job_record::merge_step_and_resource_record;
+use super::cancel_job::cancel_requested_job_record;
+use super::environment_lock::release_environment_lock;
+use super::queue::notify_job_queue;
+use super::salvage_outputs;
+use super::spawn_run;
+use super::StepRecord;
+use super::RUNNING_JOBS;
+use crate::model::job::enums::InterruptPolicy;
+use crate::model::job_record::enums::Status;
+use crate::model::job_record::JobRecord;
+use crate::model::job_record::JobRecordProperty;
+use crate::model::job_step_record::enums::Status as StepStatus;
+use crate::model::job_step_record::JobStepRecordOpt;
+use crate::model::job_step_record::JobStepRecordProperty;
+use crate::model::job_step_resource_record::enums::Status as StepResourceStatus;
+use crate::model::job_step_resource_record::JobStepResourceRecordOpt;
+use crate::model::job_step_resource_record::JobStepResourceRecordProperty;
+use crate::service::base::JobBaseService;
+use crate::service::base::JobRecordBaseService;
+use crate::service::base::JobStepRecordBaseService;
+use crate::service::base::JobStepResourceRecordBaseService;
+use crate::service::job_record::JobRecordService;
+use crate::Context;
+use chrono::DateTime;
+use chrono::Duration as ChronoDuration;
+use chrono::Utc;
+use std::sync::Arc;
+use std::time::Duration;
+use tihu::Id;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+//执行实例续约的间隔
+pub const LEASE_RENEW_INTERVAL: Duration = Duration::from_secs(10);
+//执行实例超过这个时间没有续约，认为已经退出，其它实例可以接手它的执行记录
+const LEASE_EXPIRE_SECONDS: i64 = 60;
+//每次最多接手的记录数
+const RECOVER_BATCH_SIZE: u64 = 100;
+
+/**
+ * 为本实例正在执行的记录续约，多个实例共用数据库时，其它实例据此判断执行记录是否还有人在执行
+ */
+pub async fn renew_job_record_leases(context: &Arc<Context>) -> Result<(), ErrNo> {
+    let record_ids: Vec<Id> = RUNNING_JOBS.lock().unwrap().keys().copied().collect();
+    if record_ids.is_empty() {
+        return Ok(());
+    }
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_record_service = JobRecordService::new(&transaction);
+    let renewed_list = job_record_service
+        .renew_job_record_lease(&context.get_instance_id(), &record_ids, &Utc::now())
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    for record_id in record_ids {
+        if !renewed_list
+            .iter()
+            .any(|(renewed_id, _)| record_id == *renewed_id)
+        {
+            log::warn!("任务记录{}已经不归本实例执行", record_id);
+        }
+    }
+    //其它实例收到的取消请求，已经发出取消信号的不再重复处理
+    for (record_id, cancel_requested) in renewed_list {
+        if !cancel_requested || is_cancelling(record_id) {
+            continue;
+        }
+        let context = context.clone();
+        tokio::spawn(async move {
+            if let Err(err) = cancel_requested_job_record(&context, record_id).await {
+                log::error!("取消任务记录{}失败: {:?}", record_id, err);
+            }
+        });
+    }
+    return Ok(());
+}
+
+/**
+ * 接手执行实例已经退出（超过一段时间没有续约）的执行记录，按照任务的中断策略标记中断或者继续执行
+ * 服务启动时以及之后定期检查，本实例正在执行的记录一直在续约，不会被接手
+ */
+pub async fn recover_job_records(context: Arc<Context>) -> Result<(), ErrNo> {
+    let expire_before = Utc::now() - ChronoDuration::seconds(LEASE_EXPIRE_SECONDS);
+    loop {
+        let job_record_list = claim_lease_expired_job_records(&context, &expire_before).await?;
+        let claimed_count = job_record_list.len() as u64;
+        for job_record in job_record_list {
+            let record_id = job_record.id;
+            //本实例续约失败期间被自己接手的记录，仍在执行
+            if RUNNING_JOBS.lock().unwrap().contains_key(&record_id) {
+                continue;
+            }
+            //执行实例退出之前收到的取消请求，由接手的实例取消
+            if job_record.cancel_requested {
+                if let Err(err) = cancel_requested_job_record(&context, record_id).await {
+                    log::error!("取消任务记录{}失败: {:?}", record_id, err);
+                }
+                continue;
+            }
+            if let Err(err) = recover_job_record(context.clone(), job_record).await {
+                log::error!("恢复任务执行记录{}失败: {:?}", record_id, err);
+            }
+        }
+        if claimed_count < RECOVER_BATCH_SIZE {
+            return Ok(());
+        }
+    }
+}
+
+fn is_cancelling(record_id: Id) -> bool {
+    return RUNNING_JOBS
+        .lock()
+        .unwrap()
+        .get(&record_id)
+        .map(|running_job| *running_job.cancel_sender.borrow())
+        .unwrap_or(false);
+}
+
+//把续约过期的执行记录改成由本实例执行，其它实例不会再接手
+async fn claim_lease_expired_job_records(
+    context: &Context,
+    expire_before: &DateTime<Utc>,
+) -> Result<Vec<JobRecord>, ErrNo> {
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_record_service = JobRecordService::new(&transaction);
+    let job_record_base_service = JobRecordBaseService::new(&transaction);
+    let job_record_list = job_record_service
+        .query_lease_expired_job_record(expire_before, RECOVER_BATCH_SIZE)
+        .await?;
+    let curr_time = Utc::now();
+    for job_record in &job_record_list {
+        job_record_base_service
+            .update_job_record(
+                job_record.id,
+                &[
+                    JobRecordProperty::InstanceId(Some(context.get_instance_id())),
+                    JobRecordProperty::HeartbeatTime(Some(curr_time)),
+                ],
+            )
+            .await?;
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(job_record_list);
+}
+
+async fn recover_job_record(context: Arc<Context>, job_record: JobRecord) -> Result<(), ErrNo> {
+    let record_id = job_record.id;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_base_service = JobBaseService::new(&transaction);
+    let job_record_base_service = JobRecordBaseService::new(&transaction);
+    let job_step_record_base_service = JobStepRecordBaseService::new(&transaction);
+    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
+    let curr_time = Utc::now();
+    //先更新执行记录锁住这一行，和手动步骤的处理互斥
+    job_record_base_service
+        .update_job_record(record_id, &[JobRecordProperty::LastModifiedTime(curr_time)])
+        .await?;
+    let job_step_record_list = job_step_record_base_service
+        .query_job_step_record_batch(&JobStepRecordOpt {
+            org_id: Some(job_record.org_id),
+            record_id: Some(record_id),
+            ..JobStepRecordOpt::empty()
+        })
+        .await?;
+    let job_step_resource_record_list = job_step_resource_record_base_service
+        .query_job_step_resource_record_batch(&JobStepResourceRecordOpt {
+            org_id: Some(job_record.org_id),
+            record_id: Some(record_id),
+            ..JobStepResourceRecordOpt::empty()
+        })
+        .await?;
+    let step_record_list =
+        merge_step_and_resource_record(job_step_record_list, job_step_resource_record_list);
+    //只剩手动步骤在等待人工处理的记录，不属于被中断
+    let auto_running = step_record_list
+        .iter()
+        .any(|step_record| match step_record {
+            StepRecord::Auto {
+                job_step_record, ..
+            } => StepStatus::Running == job_step_record.status,
+            StepRecord::Manual { .. } => false,
+        });
+    if !auto_running {
+        let waiting_manual = step_record_list
+            .iter()
+            .any(|step_record| match step_record {
+                StepRecord::Manual { job_step_record } => {
+                    StepStatus::Running == job_step_record.status
+                }
+                StepRecord::Auto { .. } => false,
+            });
+        if waiting_manual {
+            //执行已经停下来等待人工处理，交出执行记录，处理后由处理的实例重新开始执行
+            job_record_base_service
+                .update_job_record(
+                    record_id,
+                    &[
+                        JobRecordProperty::InstanceId(None),
+                        JobRecordProperty::HeartbeatTime(None),
+                    ],
+                )
+                .await?;
+            transaction
+                .commit()
+                .await
+                .map_err(commit_transaction_error)?;
+            return Ok(());
+        }
+    }
+    //任务被删除时按标记中断处理
+    let interrupt_policy = job_base_service
+        .read_job(job_record.job_id)
+        .await?
+        .map(|job| job.interrupt_policy)
+        .unwrap_or(InterruptPolicy::Interrupt);
+    //残留的日志文件在事务提交之后再保存
+    let mut salvage_list = Vec::new();
+    let mut all_success = true;
+    for step_record in &step_record_list {
+        let job_step_record = match step_record {
+            StepRecord::Auto {
+                job_step_record,
+                step_resource_record_list,
+            } => {
+                for step_resource_record in step_resource_record_list {
+                    if StepResourceStatus::Running != step_resource_record.status {
+                        continue;
+                    }
+                    let changes: Vec<JobStepResourceRecordProperty> = vec![
+                        JobStepResourceRecordProperty::Status(StepResourceStatus::Interrupted),
+                        JobStepResourceRecordProperty::LastModifiedTime(curr_time),
+                    ];
+                    if step_resource_record.output_content.is_none()
+                        && step_resource_record.log_key.is_none()
+                    {
+                        if let Some(output_file) = step_resource_record.output_file.as_ref() {
+                            salvage_list.push((
+                                step_resource_record.org_id,
+                                step_resource_record.id,
+                                format!("{}/{}", context.config.job_log_dir, output_file),
+                            ));
+                        }
+                    }
+                    job_step_resource_record_base_service
+                        .update_job_step_resource_record(step_resource_record.id, &changes)
+                        .await?;
+                }
+                job_step_record
+            }
+            StepRecord::Manual { job_step_record } => job_step_record,
+        };
+        //继续执行时，等待人工处理的手动步骤保持原样
+        if StepStatus::Running == job_step_record.status
+            && (InterruptPolicy::Interrupt == interrupt_policy
+                || !matches!(step_record, StepRecord::Manual { .. }))
+        {
+            let changes: Vec<JobStepRecordProperty> = vec![
+                JobStepRecordProperty::Status(StepStatus::Interrupted),
+                JobStepRecordProperty::LastModifiedTime(curr_time),
+            ];
+            job_step_record_base_service
+                .update_job_step_record(job_step_record.id, &changes)
+                .await?;
+        }
+        if StepStatus::Success != job_step_record.status
+            && StepStatus::Skipped != job_step_record.status
+        {
+            all_success = false;
+        }
+    }
+    let status = if all_success {
+        //所有步骤都已成功，只是没来得及更新记录状态
+        Status::Success
+    } else {
+        match interrupt_policy {
+            InterruptPolicy::Interrupt => Status::Interrupted,
+            InterruptPolicy::Resume => Status::Running,
+        }
+    };
+    if Status::Running != status {
+        //不再继续执行，释放占用的环境
+        release_environment_lock(&transaction, record_id).await?;
+        let changes: Vec<JobRecordProperty> = vec![
+            JobRecordProperty::Status(status),
+            JobRecordProperty::LastModifiedTime(curr_time),
+        ];
+        job_record_base_service
+            .update_job_record(record_id, &changes)
+            .await?;
+    }
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    //在继续执行之前保存，避免覆盖重新执行的资源保存的日志
+    salvage_outputs(&context, &salvage_list, "执行实例退出，执行被中断").await;
+    if Status::Running == status {
+        log::info!("继续执行被中断的任务记录: {}", record_id);
+        //已成功的步骤和资源不会重新执行
+        spawn_run(context, job_record.org_id, step_record_list, record_id);
+    } else {
+        log::info!("任务记录{}已标记为{:?}", record_id, status);
+        notify_job_queue();
+    }
+    return Ok(());
+}
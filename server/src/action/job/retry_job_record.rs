@@ -0,0 +1,275 @@
+use super::super::environment::grant::check_environment_grant;
+use super::approval::query_required_approvals;
+use super::condition::condition_skip_reason;
+use super::environment_lock::acquire_environment_lock;
+use super::environment_lock::user_lock_holder;
+use super::queue::notify_job_queue;
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::environment_grant::enums::GrantPermission;
+use crate::model::job_record::enums::Status;
+use crate::model::job_record::enums::TriggerType;
+use crate::model::job_record::JobRecord;
+use crate::model::job_record::JobRecordOpt;
+use crate::model::job_record::JobRecordProperty;
+use crate::model::job_step_record::enums::RunIf;
+use crate::model::job_step_record::enums::Status as StepStatus;
+use crate::model::job_step_record::JobStepRecord;
+use crate::model::job_step_record::JobStepRecordOpt;
+use crate::model::job_step_resource_record::enums::Status as StepResourceStatus;
+use crate::model::job_step_resource_record::JobStepResourceRecord;
+use crate::model::job_step_resource_record::JobStepResourceRecordOpt;
+use crate::sdk;
+use crate::service::base::JobRecordBaseService;
+use crate::service::base::JobStepRecordBaseService;
+use crate::service::base::JobStepResourceRecordBaseService;
+use crate::service::job_record::JobRecordService;
+use chrono::Utc;
+use sdk::job::retry_job_record::RetryJobRecordReq;
+use sdk::job::retry_job_record::RetryJobRecordResp;
+use std::collections::HashMap;
+use tihu::Id;
+use tihu::LightString;
+use tihu::PrimaryKey;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+//上一次执行成功的步骤保留执行结果，因为条件不满足而跳过的步骤仍然跳过
+//其余的步骤都重新执行，失败时执行或者总是执行的步骤要根据这一次的结果重新判断
+fn keep_step_result(job_step_record: &JobStepRecord) -> bool {
+    match job_step_record.status {
+        StepStatus::Success => RunIf::OnSuccess == job_step_record.run_if,
+        StepStatus::Skipped => match (
+            job_step_record.run_expression.as_deref(),
+            job_step_record.skip_reason.as_deref(),
+        ) {
+            (Some(run_expression), Some(skip_reason)) => {
+                condition_skip_reason(run_expression) == skip_reason
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/**
+ * 从失败的步骤开始重新执行，生成一条新的执行记录，沿用原执行记录里保存的步骤快照
+ */
+pub async fn retry_job_record(
+    org_id: Id,
+    user: User,
+    retry_job_record_req: RetryJobRecordReq,
+) -> Result<RetryJobRecordResp, ErrNo> {
+    let RetryJobRecordReq {
+        record_id,
+        change_note,
+    } = retry_job_record_req;
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_record_base_service = JobRecordBaseService::new(&transaction);
+    let job_record_service = JobRecordService::new(&transaction);
+    let job_step_record_base_service = JobStepRecordBaseService::new(&transaction);
+    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
+    let job_record = job_record_base_service
+        .query_job_record_one(&JobRecordOpt {
+            org_id: Some(org_id),
+            id: Some(record_id),
+            ..JobRecordOpt::empty()
+        })
+        .await?
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("该任务执行记录不存在")))?;
+    check_environment_grant(
+        &transaction,
+        org_id,
+        &user,
+        job_record.environment_id,
+        GrantPermission::Run,
+    )
+    .await?;
+    match job_record.status {
+        Status::Running | Status::Queued | Status::PendingApproval => {
+            return Err(ErrNo::CommonError(LightString::from_static(
+                "任务还在执行中，不能重新执行",
+            )));
+        }
+        Status::Success => {
+            return Err(ErrNo::CommonError(LightString::from_static(
+                "任务已经执行成功，不需要重新执行",
+            )));
+        }
+        _ => (),
+    }
+    let curr_time = Utc::now();
+    //先更新原执行记录，锁住这一行，避免同时重新执行两次
+    job_record_base_service
+        .update_job_record(
+            job_record.id,
+            &[JobRecordProperty::LastModifiedTime(curr_time)],
+        )
+        .await?;
+    let origin_record_id = job_record.origin_record_id.unwrap_or(job_record.id);
+    let attempt_list = job_record_service
+        .query_job_record_attempts(&org_id, &origin_record_id)
+        .await?;
+    if attempt_list
+        .iter()
+        .any(|attempt| attempt.attempt > job_record.attempt)
+    {
+        return Err(ErrNo::CommonError(LightString::from_static(
+            "该执行记录已经重新执行过，请在最近一次的执行记录上重新执行",
+        )));
+    }
+    let job_step_record_list = job_step_record_base_service
+        .query_job_step_record_batch(&JobStepRecordOpt {
+            org_id: Some(org_id),
+            record_id: Some(record_id),
+            ..JobStepRecordOpt::empty()
+        })
+        .await?;
+    let job_step_resource_record_list = job_step_resource_record_base_service
+        .query_job_step_resource_record_batch(&JobStepResourceRecordOpt {
+            org_id: Some(org_id),
+            record_id: Some(record_id),
+            ..JobStepResourceRecordOpt::empty()
+        })
+        .await?;
+
+    let new_record_id = context.new_id();
+    let required_approvals = query_required_approvals(
+        &transaction,
+        org_id,
+        job_record.environment_id,
+        Some(user.user_id),
+    )
+    .await?;
+    if required_approvals.is_none() {
+        let holder = user_lock_holder(&transaction, &user).await?;
+        acquire_environment_lock(
+            &context,
+            &transaction,
+            org_id,
+            job_record.environment_id,
+            job_record.job_id,
+            new_record_id,
+            holder,
+        )
+        .await?;
+    }
+    //key为原步骤记录id，value为新的步骤记录id以及是否保留原来的执行结果
+    let mut step_record_map: HashMap<Id, (Id, bool)> = HashMap::new();
+    let mut new_step_record_list = Vec::with_capacity(job_step_record_list.len());
+    for job_step_record in job_step_record_list {
+        let keep = keep_step_result(&job_step_record);
+        let job_step_record_id = context.new_id();
+        step_record_map.insert(job_step_record.id, (job_step_record_id, keep));
+        new_step_record_list.push(JobStepRecord {
+            id: job_step_record_id,
+            record_id: new_record_id,
+            rendered_parameter: if keep {
+                job_step_record.rendered_parameter
+            } else {
+                None
+            },
+            skip_reason: if keep {
+                job_step_record.skip_reason
+            } else {
+                None
+            },
+            status: if keep {
+                job_step_record.status
+            } else {
+                StepStatus::Pending
+            },
+            completed_by: if keep {
+                job_step_record.completed_by
+            } else {
+                None
+            },
+            created_time: curr_time,
+            last_modified_time: curr_time,
+            ..job_step_record
+        });
+    }
+    let mut new_step_resource_record_list = Vec::with_capacity(job_step_resource_record_list.len());
+    for step_resource_record in job_step_resource_record_list {
+        let (job_step_record_id, keep) =
+            match step_record_map.get(&step_resource_record.job_step_record_id) {
+                Some(step_record) => *step_record,
+                None => continue,
+            };
+        //重新执行的资源沿用原来的扩展配置快照，执行结果清空
+        let new_step_resource_record = if keep {
+            JobStepResourceRecord {
+                id: context.new_id(),
+                record_id: new_record_id,
+                job_step_record_id: job_step_record_id,
+                created_time: curr_time,
+                last_modified_time: curr_time,
+                ..step_resource_record
+            }
+        } else {
+            JobStepResourceRecord {
+                id: context.new_id(),
+                record_id: new_record_id,
+                job_step_record_id: job_step_record_id,
+                output_file: None,
+                output_content: None,
+                log_key: None,
+                log_summary: None,
+                outputs: None,
+                attempts: 0,
+                status: StepResourceStatus::Pending,
+                created_time: curr_time,
+                last_modified_time: curr_time,
+                ..step_resource_record
+            }
+        };
+        new_step_resource_record_list.push(new_step_resource_record);
+    }
+    job_record_base_service
+        .insert_job_record(&JobRecord {
+            id: new_record_id,
+            org_id: org_id,
+            job_id: job_record.job_id,
+            environment_id: job_record.environment_id,
+            origin_record_id: Some(origin_record_id),
+            attempt: job_record.attempt + 1,
+            status: if required_approvals.is_some() {
+                Status::PendingApproval
+            } else {
+                Status::Queued
+            },
+            pinned: false,
+            created_by: Some(user.user_id),
+            trigger_type: TriggerType::Retry,
+            change_note: change_note,
+            required_approvals: required_approvals,
+            //步骤执行记录沿用渲染之前的操作参数，执行参数也要沿用
+            params: job_record.params.clone(),
+            secret_params: job_record.secret_params.clone(),
+            instance_id: None,
+            heartbeat_time: None,
+            cancel_requested: false,
+            created_time: curr_time,
+            last_modified_time: curr_time,
+        })
+        .await?;
+    job_step_record_base_service
+        .insert_job_step_record_batch(&new_step_record_list)
+        .await?;
+    job_step_resource_record_base_service
+        .insert_job_step_resource_record_batch(&new_step_resource_record_list)
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    if required_approvals.is_none() {
+        notify_job_queue();
+    }
+    return Ok(PrimaryKey {
+        id: new_record_id.into(),
+    });
+}
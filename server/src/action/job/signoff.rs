@@ -0,0 +1,103 @@
+use crate::middleware::auth::User;
+use crate::model::job_step_record::enums::SignoffRole;
+use crate::model::user::enums::Role;
+use crate::service::base::UserBaseService;
+use std::collections::HashSet;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::ErrNo;
+use tokio_postgres::Transaction;
+
+//手动步骤最多需要的签收人数
+pub const MAX_REQUIRED_SIGNOFFS: i32 = 10;
+
+pub fn serialize_signoff_users(signoff_users: Option<Vec<Id>>) -> Result<Option<String>, ErrNo> {
+    return signoff_users
+        .map(|signoff_users| serde_json::to_string(&signoff_users).map_err(ErrNo::SerializeError))
+        .transpose();
+}
+
+pub fn parse_signoff_users(signoff_users: Option<&str>) -> Result<Option<Vec<Id>>, ErrNo> {
+    return signoff_users
+        .map(|signoff_users| serde_json::from_str(signoff_users).map_err(ErrNo::DeserializeError))
+        .transpose();
+}
+
+/**
+ * 检查手动步骤的签收设置，指定的签收人必须是组织的成员
+ */
+pub async fn check_signoff_policy(
+    transaction: &Transaction<'_>,
+    org_id: Id,
+    step_name: &str,
+    has_signoff_role: bool,
+    signoff_user_list: Option<&[Id]>,
+    required_signoffs: i32,
+) -> Result<(), ErrNo> {
+    if required_signoffs <= 0 || MAX_REQUIRED_SIGNOFFS < required_signoffs {
+        return Err(ErrNo::CommonError(LightString::from(format!(
+            "步骤\"{}\"的签收人数必须在1到{}之间",
+            step_name, MAX_REQUIRED_SIGNOFFS
+        ))));
+    }
+    let signoff_user_list = match signoff_user_list {
+        Some(signoff_user_list) => signoff_user_list,
+        None => return Ok(()),
+    };
+    let user_ids: HashSet<Id> = signoff_user_list.iter().cloned().collect();
+    if user_ids.is_empty() || user_ids.len() != signoff_user_list.len() {
+        return Err(ErrNo::CommonError(LightString::from(format!(
+            "步骤\"{}\"的签收人不能为空，也不能重复",
+            step_name
+        ))));
+    }
+    //只按用户限制时，指定的人数不能少于需要的签收人数
+    if !has_signoff_role && (user_ids.len() as i32) < required_signoffs {
+        return Err(ErrNo::CommonError(LightString::from(format!(
+            "步骤\"{}\"需要{}个人签收，但是只指定了{}个签收人",
+            step_name,
+            required_signoffs,
+            user_ids.len()
+        ))));
+    }
+    let user_base_service = UserBaseService::new(transaction);
+    let user_ids: Vec<Id> = user_ids.into_iter().collect();
+    let member_count = user_base_service
+        .read_user_batch(&user_ids)
+        .await?
+        .into_iter()
+        .filter(|user| Some(org_id) == user.org_id)
+        .count();
+    if member_count != user_ids.len() {
+        return Err(ErrNo::CommonError(LightString::from(format!(
+            "步骤\"{}\"的签收人不是组织的成员",
+            step_name
+        ))));
+    }
+    return Ok(());
+}
+
+/**
+ * 用户能否签收手动步骤，角色和用户都没有限制时所有能执行的成员都可以签收，
+ * 否则角色满足要求或者在指定的用户里都可以签收，所有者总是可以签收
+ */
+pub fn can_signoff(
+    user: &User,
+    signoff_role: Option<SignoffRole>,
+    signoff_user_list: Option<&[Id]>,
+) -> bool {
+    if Role::Owner == user.role {
+        return true;
+    }
+    if signoff_role.is_none() && signoff_user_list.is_none() {
+        return true;
+    }
+    //角色的枚举值越小权限越高
+    let role_matched = signoff_role
+        .map(|signoff_role| (user.role as i16) <= (signoff_role as i16))
+        .unwrap_or(false);
+    let user_matched = signoff_user_list
+        .map(|signoff_user_list| signoff_user_list.contains(&user.user_id))
+        .unwrap_or(false);
+    return role_matched || user_matched;
+}
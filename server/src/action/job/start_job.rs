@@ -1,14 +1,31 @@
-use super::super::job_record::merge_step_and_resource_record;
+use super::super::api_token::check_token_job;
+use super::super::environment::grant::check_environment_grant;
+use super::super::extension::secret::mask_configuration;
+use super::super::extension::secret::pick_passwords;
+use super::approval::query_required_approvals;
+use super::condition::condition_skip_reason;
+use super::condition::eval_run_expression;
+use super::environment_lock::acquire_environment_lock;
+use super::environment_lock::user_lock_holder;
+use super::environment_lock::LockHolder;
 use super::merge_step_and_resource;
-use super::start_run;
+use super::queue::notify_job_queue;
+use super::template::check_params;
+use super::template::parse_parameter_schema;
+use super::to_record_failure_policy;
+use super::to_record_run_if;
+use super::to_record_signoff_role;
 use super::Step;
 use crate::get_context;
 use crate::middleware::auth::User;
 use crate::model::environment::EnvironmentOpt;
+use crate::model::environment_grant::enums::GrantPermission;
 use crate::model::environment_resource::EnvironmentResourceOpt;
 use crate::model::environment_schema_resource::EnvironmentSchemaResourceOpt;
+use crate::model::job::Job;
 use crate::model::job::JobOpt;
 use crate::model::job_record::enums::Status;
+use crate::model::job_record::enums::TriggerType;
 use crate::model::job_record::JobRecord;
 use crate::model::job_step::JobStepOpt;
 use crate::model::job_step_record::enums::Status as StepStatus;
@@ -24,91 +41,106 @@ use crate::service::base::JobRecordBaseService;
 use crate::service::base::JobStepBaseService;
 use crate::service::base::JobStepRecordBaseService;
 use crate::service::base::JobStepResourceRecordBaseService;
+use crate::Context;
 use chrono::Utc;
+use sdk::extension::Attribute;
 use sdk::job::start_job::StartJobReq;
 use sdk::job::start_job::StartJobResp;
+use serde_json::Map;
+use serde_json::Value;
+use std::collections::HashMap;
 use tihu::Id;
 use tihu::LightString;
 use tihu::PrimaryKey;
 use tihu_native::errno::commit_transaction_error;
 use tihu_native::errno::open_transaction_error;
 use tihu_native::ErrNo;
+use tokio_postgres::Transaction;
 
 pub async fn start_job(
     org_id: Id,
-    _user: User,
+    user: User,
     start_job_req: StartJobReq,
+) -> Result<StartJobResp, ErrNo> {
+    let holder = {
+        let context = get_context()?;
+        let mut client = context.get_db_client().await?;
+        let transaction = client.transaction().await.map_err(open_transaction_error)?;
+        check_token_job(&transaction, &user, start_job_req.job_id).await?;
+        check_environment_grant(
+            &transaction,
+            org_id,
+            &user,
+            start_job_req.environment_id,
+            GrantPermission::Run,
+        )
+        .await?;
+        user_lock_holder(&transaction, &user).await?
+    };
+    //通过访问令牌发起的执行单独记录触发方式，方便区分自动化调用
+    let trigger_type = if user.token.is_some() {
+        TriggerType::ApiToken
+    } else {
+        TriggerType::Manual
+    };
+    return run_job(org_id, start_job_req, holder, trigger_type).await;
+}
+
+/**
+ * 创建任务执行记录并放入任务队列，手动启动和定时触发都从这里开始
+ * 目标环境被占用时，按照环境的设置拒绝执行或者排队等待，发起执行的人和触发方式会记录到执行记录上
+ * 需要审批的环境先生成等待审批的执行记录，审批通过之后才占用环境、放入任务队列
+ */
+pub async fn run_job(
+    org_id: Id,
+    start_job_req: StartJobReq,
+    holder: LockHolder,
+    trigger_type: TriggerType,
 ) -> Result<StartJobResp, ErrNo> {
     let StartJobReq {
         job_id,
         environment_id,
+        params: job_params,
+        change_note,
     } = start_job_req;
+    let created_by = holder.holder_id;
     let context = get_context()?;
     let mut client = context.get_db_client().await?;
     let transaction = client.transaction().await.map_err(open_transaction_error)?;
-    let job_base_service = JobBaseService::new(&transaction);
-    let job_step_base_service = JobStepBaseService::new(&transaction);
-    let environment_base_service = EnvironmentBaseService::new(&transaction);
-    let environment_resource_base_service = EnvironmentResourceBaseService::new(&transaction);
-    let environment_schema_resource_base_service =
-        EnvironmentSchemaResourceBaseService::new(&transaction);
     let job_record_base_service = JobRecordBaseService::new(&transaction);
     let job_step_record_base_service = JobStepRecordBaseService::new(&transaction);
     let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
-    let params = JobOpt {
-        org_id: Some(org_id),
-        id: Some(job_id),
-        ..JobOpt::empty()
-    };
-    let job_opt = job_base_service.query_job_one(&params).await?;
-    let job =
-        job_opt.ok_or_else(|| ErrNo::CommonError(LightString::from_static("该任务不存在")))?;
-    let environment_opt = environment_base_service
-        .query_environment_one(&EnvironmentOpt {
-            org_id: Some(org_id),
-            id: Some(environment_id),
-            ..EnvironmentOpt::empty()
-        })
-        .await?;
-    let environment = environment_opt
-        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("目标环境不存在")))?;
-
-    //查询job的所有步骤
-    let mut job_step_list = job_step_base_service
-        .query_job_step_batch(&JobStepOpt {
-            org_id: Some(org_id),
-            job_id: Some(job_id.into()),
-            ..JobStepOpt::empty()
-        })
-        .await?;
-    job_step_list.sort_by_key(|item| item.seq);
-
-    //查询环境的资源规格
-    let environment_schema_resource_list = environment_schema_resource_base_service
-        .query_environment_schema_resource_batch(&EnvironmentSchemaResourceOpt {
-            org_id: Some(org_id),
-            environment_schema_id: Some(environment.environment_schema_id.into()),
-            ..EnvironmentSchemaResourceOpt::empty()
-        })
-        .await?;
-
-    //查询环境所有的资源
-    let environment_resource_list = environment_resource_base_service
-        .query_environment_resource_batch(&EnvironmentResourceOpt {
-            org_id: Some(org_id),
-            environment_id: Some(environment_id.into()),
-            ..EnvironmentResourceOpt::empty()
-        })
-        .await?;
-
-    let job_step_list = merge_step_and_resource(
+    let PreparedJob {
+        job,
+        parameter_schema,
+        params,
+        step_list: job_step_list,
+        mut skip_reasons,
+    } = prepare_job(
         &context,
-        job_step_list,
-        environment_schema_resource_list,
-        environment_resource_list,
-    )?;
+        &transaction,
+        org_id,
+        job_id,
+        environment_id,
+        job_params,
+    )
+    .await?;
 
     let record_id = context.new_id();
+    let required_approvals =
+        query_required_approvals(&transaction, org_id, environment_id, created_by).await?;
+    if required_approvals.is_none() {
+        acquire_environment_lock(
+            &context,
+            &transaction,
+            org_id,
+            environment_id,
+            job_id,
+            record_id,
+            holder,
+        )
+        .await?;
+    }
     let curr_time = Utc::now();
     let mut job_step_record_list = Vec::new();
     let mut job_step_resource_record_list = Vec::new();
@@ -119,6 +151,12 @@ pub async fn start_job(
                 extension_id,
                 resource_list,
             } => {
+                let skip_reason = skip_reasons.remove(&job_step.id);
+                let (step_status, resource_status) = if skip_reason.is_some() {
+                    (StepStatus::Skipped, StepResourceStatus::Skipped)
+                } else {
+                    (StepStatus::Pending, StepResourceStatus::Pending)
+                };
                 let job_step_record_id = context.new_id();
                 let job_step_record = JobStepRecord {
                     id: job_step_record_id,
@@ -131,12 +169,28 @@ pub async fn start_job(
                     step_type: crate::model::job_step_record::enums::StepType::Auto,
                     step_remark: job_step.remark,
                     job_step_seq: job_step.seq,
+                    depends_on: job_step.depends_on,
                     extension_id: extension_id,
                     operation_id: job_step.operation_id,
                     operation_name: job_step.operation_name,
                     operation_parameter: job_step.operation_parameter,
+                    rendered_parameter: None,
                     attachments: job_step.attachments,
-                    status: StepStatus::Pending,
+                    signoff_role: job_step.signoff_role.map(to_record_signoff_role),
+                    signoff_users: job_step.signoff_users,
+                    required_signoffs: job_step.required_signoffs,
+                    timeout_seconds: job_step.timeout_seconds,
+                    max_retries: job_step.max_retries,
+                    retry_backoff: job_step.retry_backoff,
+                    max_parallel: job_step.max_parallel,
+                    batch_size: job_step.batch_size,
+                    pause_between_batches: job_step.pause_between_batches,
+                    failure_policy: to_record_failure_policy(job_step.failure_policy),
+                    run_if: to_record_run_if(job_step.run_if),
+                    run_expression: job_step.run_expression,
+                    skip_reason: skip_reason,
+                    status: step_status,
+                    completed_by: None,
                     created_time: curr_time,
                     last_modified_time: curr_time,
                 };
@@ -155,7 +209,11 @@ pub async fn start_job(
                         extension_configuration: resource.extension_configuration,
                         output_file: None,
                         output_content: None,
-                        status: StepResourceStatus::Pending,
+                        log_key: None,
+                        log_summary: None,
+                        outputs: None,
+                        attempts: 0,
+                        status: resource_status,
                         created_time: curr_time,
                         last_modified_time: curr_time,
                     };
@@ -164,6 +222,12 @@ pub async fn start_job(
             }
             Step::Manual { job_step } => {
                 //手动的步骤，仍然添加一条记录
+                let skip_reason = skip_reasons.remove(&job_step.id);
+                let step_status = if skip_reason.is_some() {
+                    StepStatus::Skipped
+                } else {
+                    StepStatus::Pending
+                };
                 let job_step_record_id = context.new_id();
                 let job_step_record = JobStepRecord {
                     id: job_step_record_id,
@@ -176,12 +240,28 @@ pub async fn start_job(
                     step_type: crate::model::job_step_record::enums::StepType::Manual,
                     step_remark: job_step.remark,
                     job_step_seq: job_step.seq,
+                    depends_on: job_step.depends_on,
                     extension_id: String::from(""),
                     operation_id: String::from(""),
                     operation_name: String::from(""),
                     operation_parameter: String::from(""),
+                    rendered_parameter: None,
                     attachments: job_step.attachments,
-                    status: StepStatus::Pending,
+                    signoff_role: job_step.signoff_role.map(to_record_signoff_role),
+                    signoff_users: job_step.signoff_users,
+                    required_signoffs: job_step.required_signoffs,
+                    timeout_seconds: job_step.timeout_seconds,
+                    max_retries: job_step.max_retries,
+                    retry_backoff: job_step.retry_backoff,
+                    max_parallel: job_step.max_parallel,
+                    batch_size: job_step.batch_size,
+                    pause_between_batches: job_step.pause_between_batches,
+                    failure_policy: to_record_failure_policy(job_step.failure_policy),
+                    run_if: to_record_run_if(job_step.run_if),
+                    run_expression: job_step.run_expression,
+                    skip_reason: skip_reason,
+                    status: step_status,
+                    completed_by: None,
                     created_time: curr_time,
                     last_modified_time: curr_time,
                 };
@@ -189,13 +269,39 @@ pub async fn start_job(
             }
         }
     }
+    //密码类型的参数不能明文保存，执行记录里保存掩码，原值单独加密保存
+    let secret_params = pick_passwords(&parameter_schema, &params);
+    let secret_params = if secret_params.is_empty() {
+        None
+    } else {
+        let secret_params = serde_json::to_string(&secret_params).map_err(ErrNo::SerializeError)?;
+        Some(context.encrypt_configuration(&secret_params)?)
+    };
+    let params = serde_json::to_string(&params).map_err(ErrNo::SerializeError)?;
+    let params = mask_configuration(&parameter_schema, &params)?;
     job_record_base_service
         .insert_job_record(&JobRecord {
             id: record_id,
             org_id: org_id,
             job_id: job.id,
             environment_id: environment_id.into(),
-            status: Status::Running,
+            origin_record_id: None,
+            attempt: 1,
+            status: if required_approvals.is_some() {
+                Status::PendingApproval
+            } else {
+                Status::Queued
+            },
+            pinned: false,
+            created_by: created_by,
+            trigger_type: trigger_type,
+            change_note: change_note,
+            required_approvals: required_approvals,
+            params: Some(params),
+            secret_params: secret_params,
+            instance_id: None,
+            heartbeat_time: None,
+            cancel_requested: false,
             created_time: curr_time,
             last_modified_time: curr_time,
         })
@@ -210,14 +316,114 @@ pub async fn start_job(
         .commit()
         .await
         .map_err(commit_transaction_error)?;
-    let step_record_list =
-        merge_step_and_resource_record(job_step_record_list, job_step_resource_record_list);
-    tokio::spawn(async move {
-        if let Err(err) = start_run(context, step_record_list, record_id).await {
-            log::error!("执行任务发生错误, {:?}", err);
-        }
-    });
+    if required_approvals.is_none() {
+        notify_job_queue();
+    }
     return Ok(PrimaryKey {
         id: record_id.into(),
     });
 }
+
+/**
+ * 执行任务之前准备好的步骤
+ */
+pub struct PreparedJob {
+    pub job: Job,
+    pub parameter_schema: Vec<Attribute>, //任务参数定义
+    pub params: Map<String, Value>,       //检查之后的执行参数
+    pub step_list: Vec<Step>,
+    pub skip_reasons: HashMap<Id, String>, //条件表达式不满足的步骤，key为步骤id，value为跳过原因
+}
+
+//检查执行参数，计算步骤的执行条件，用执行参数渲染操作参数并匹配目标环境的资源，启动任务和预览任务共用
+pub async fn prepare_job(
+    context: &Context,
+    transaction: &Transaction<'_>,
+    org_id: Id,
+    job_id: Id,
+    environment_id: Id,
+    job_params: Option<String>,
+) -> Result<PreparedJob, ErrNo> {
+    let job_base_service = JobBaseService::new(transaction);
+    let job_step_base_service = JobStepBaseService::new(transaction);
+    let environment_base_service = EnvironmentBaseService::new(transaction);
+    let environment_resource_base_service = EnvironmentResourceBaseService::new(transaction);
+    let environment_schema_resource_base_service =
+        EnvironmentSchemaResourceBaseService::new(transaction);
+    let params = JobOpt {
+        org_id: Some(org_id),
+        id: Some(job_id),
+        ..JobOpt::empty()
+    };
+    let job_opt = job_base_service.query_job_one(&params).await?;
+    let job =
+        job_opt.ok_or_else(|| ErrNo::CommonError(LightString::from_static("该任务不存在")))?;
+    let parameter_schema = parse_parameter_schema(&job.parameter_schema)?;
+    let job_params = check_params(&parameter_schema, job_params)?;
+    let environment_opt = environment_base_service
+        .query_environment_one(&EnvironmentOpt {
+            org_id: Some(org_id),
+            id: Some(environment_id),
+            ..EnvironmentOpt::empty()
+        })
+        .await?;
+    let environment = environment_opt
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("目标环境不存在")))?;
+
+    //查询job的所有步骤
+    let mut job_step_list = job_step_base_service
+        .query_job_step_batch(&JobStepOpt {
+            org_id: Some(org_id),
+            job_id: Some(job_id.into()),
+            ..JobStepOpt::empty()
+        })
+        .await?;
+    job_step_list.sort_by_key(|item| item.seq);
+    //条件表达式不满足的步骤直接标记为已跳过，key为步骤id，value为跳过原因
+    let mut skip_reasons: HashMap<Id, String> = HashMap::new();
+    for job_step in job_step_list.iter() {
+        if let Some(run_expression) = job_step.run_expression.as_deref() {
+            if !eval_run_expression(
+                &job_step.name,
+                run_expression,
+                &environment.name,
+                &job_params,
+            )? {
+                skip_reasons.insert(job_step.id, condition_skip_reason(run_expression));
+            }
+        }
+    }
+    //查询环境的资源规格
+    let environment_schema_resource_list = environment_schema_resource_base_service
+        .query_environment_schema_resource_batch(&EnvironmentSchemaResourceOpt {
+            org_id: Some(org_id),
+            environment_schema_id: Some(environment.environment_schema_id.into()),
+            ..EnvironmentSchemaResourceOpt::empty()
+        })
+        .await?;
+
+    //查询环境所有的资源
+    let environment_resource_list = environment_resource_base_service
+        .query_environment_resource_batch(&EnvironmentResourceOpt {
+            org_id: Some(org_id),
+            environment_id: Some(environment_id.into()),
+            ..EnvironmentResourceOpt::empty()
+        })
+        .await?;
+
+    //步骤执行记录保存渲染之前的操作参数，执行步骤时再和步骤输出一起渲染
+    let step_list = merge_step_and_resource(
+        context,
+        job_step_list,
+        &job_params,
+        environment_schema_resource_list,
+        environment_resource_list,
+    )?;
+    return Ok(PreparedJob {
+        job: job,
+        parameter_schema: parameter_schema,
+        params: job_params,
+        step_list: step_list,
+        skip_reasons: skip_reasons,
+    });
+}
@@ -0,0 +1,557 @@
+use super::graph::upstream_steps;
+use super::graph::StepNode;
+use crate::sdk;
+use sdk::extension::Attribute;
+use sdk::extension::AttributeType;
+use serde_json::Map;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ops::Range;
+use tihu::LightString;
+use tihu_native::ErrNo;
+
+//引用执行参数的占位符前缀，如{{params.image_tag}}
+const PARAMS_PREFIX: &str = "params.";
+//引用前面步骤输出的占位符前缀和分隔符，如{{steps.upload.outputs.url}}
+const STEPS_PREFIX: &str = "steps.";
+const OUTPUTS_SEPARATOR: &str = ".outputs.";
+
+pub fn serialize_parameter_schema(parameter_schema: &[Attribute]) -> Result<String, ErrNo> {
+    return serde_json::to_string(parameter_schema).map_err(ErrNo::SerializeError);
+}
+
+pub fn parse_parameter_schema(parameter_schema: &str) -> Result<Vec<Attribute>, ErrNo> {
+    return serde_json::from_str(parameter_schema).map_err(ErrNo::DeserializeError);
+}
+
+//检查任务参数定义是否合格，参数id会出现在占位符中，只允许字母、数字和下划线
+pub fn check_parameter_schema(parameter_schema: &[Attribute]) -> Result<(), ErrNo> {
+    let mut ids = HashSet::with_capacity(parameter_schema.len());
+    for attribute in parameter_schema {
+        if attribute.name.is_empty() {
+            return Err(ErrNo::CommonError(LightString::from_static(
+                "参数名称不能为空",
+            )));
+        }
+        if attribute.id.is_empty()
+            || !attribute
+                .id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || '_' == c)
+        {
+            return Err(ErrNo::CommonError(LightString::from(format!(
+                "参数\"{}\"的标识只能包含字母、数字和下划线",
+                attribute.name
+            ))));
+        }
+        if !ids.insert(attribute.id.as_str()) {
+            return Err(ErrNo::CommonError(LightString::from(format!(
+                "参数标识\"{}\"重复",
+                attribute.id
+            ))));
+        }
+    }
+    return Ok(());
+}
+
+//按照任务参数定义检查启动任务时填写的参数，未填写的非必填参数以null代替
+pub fn check_params(
+    parameter_schema: &[Attribute],
+    params: Option<String>,
+) -> Result<Map<String, Value>, ErrNo> {
+    let mut params = match params {
+        Some(params) => match serde_json::from_str::<Value>(&params) {
+            Ok(Value::Object(params)) => params,
+            _ => {
+                return Err(ErrNo::CommonError(LightString::from_static(
+                    "执行参数格式不正确",
+                )));
+            }
+        },
+        None => Map::new(),
+    };
+    let mut checked_params = Map::with_capacity(parameter_schema.len());
+    for attribute in parameter_schema {
+        let value = params.remove(&attribute.id).unwrap_or(Value::Null);
+        if is_empty_value(&value) {
+            if attribute.required {
+                return Err(ErrNo::CommonError(LightString::from(format!(
+                    "请填写参数\"{}\"",
+                    attribute.name
+                ))));
+            }
+            checked_params.insert(attribute.id.clone(), Value::Null);
+        } else if is_type_matched(&attribute.r#type, &value) {
+            checked_params.insert(attribute.id.clone(), value);
+        } else {
+            return Err(ErrNo::CommonError(LightString::from(format!(
+                "参数\"{}\"的值不正确",
+                attribute.name
+            ))));
+        }
+    }
+    return Ok(checked_params);
+}
+
+fn is_empty_value(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(value) => value.is_empty(),
+        Value::Array(value) => value.is_empty(),
+        _ => false,
+    }
+}
+
+fn is_type_matched(attribute_type: &AttributeType, value: &Value) -> bool {
+    match attribute_type {
+        AttributeType::String
+        | AttributeType::LongString
+        | AttributeType::Code { .. }
+        | AttributeType::Password => value.is_string(),
+        AttributeType::StringList => value
+            .as_array()
+            .map(|list| list.iter().all(|item| item.is_string()))
+            .unwrap_or(false),
+        AttributeType::Enum { options } => value
+            .as_str()
+            .map(|value| options.iter().any(|option| option.value == value))
+            .unwrap_or(false),
+        AttributeType::EnumList { options } => value
+            .as_array()
+            .map(|list| {
+                list.iter().all(|item| {
+                    item.as_str()
+                        .map(|value| options.iter().any(|option| option.value == value))
+                        .unwrap_or(false)
+                })
+            })
+            .unwrap_or(false),
+        AttributeType::Bool => value.is_boolean(),
+        AttributeType::File => value.is_object(),
+        AttributeType::FileList => value
+            .as_array()
+            .map(|list| list.iter().all(|item| item.is_object()))
+            .unwrap_or(false),
+    }
+}
+
+//找出文本中所有的占位符，返回占位符所在的范围以及占位符的内容
+fn find_placeholders(text: &str) -> Vec<(Range<usize>, &str)> {
+    let mut placeholders = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = text[offset..].find("{{") {
+        let start = offset + start;
+        match text[start + 2..].find("}}") {
+            Some(end) => {
+                let end = start + 2 + end;
+                placeholders.push((start..end + 2, text[start + 2..end].trim()));
+                offset = end + 2;
+            }
+            None => break,
+        }
+    }
+    return placeholders;
+}
+
+//解析引用步骤输出的占位符，返回步骤名称和输出字段
+fn parse_step_ref(key: &str) -> Option<(&str, &str)> {
+    return key
+        .strip_prefix(STEPS_PREFIX)
+        .and_then(|key| key.split_once(OUTPUTS_SEPARATOR));
+}
+
+fn collect_placeholder_keys(value: &Value, keys: &mut Vec<String>) {
+    match value {
+        Value::String(text) => {
+            for (_, key) in find_placeholders(text) {
+                keys.push(key.to_string());
+            }
+        }
+        Value::Array(list) => {
+            for item in list {
+                collect_placeholder_keys(item, keys);
+            }
+        }
+        Value::Object(map) => {
+            for item in map.values() {
+                collect_placeholder_keys(item, keys);
+            }
+        }
+        _ => (),
+    }
+}
+
+//检查操作参数中引用的执行参数是否都已定义，返回是否引用了执行参数
+pub fn check_param_refs(
+    step_name: &str,
+    operation_parameter: &Value,
+    parameter_schema: &[Attribute],
+) -> Result<bool, ErrNo> {
+    let mut keys = Vec::new();
+    collect_placeholder_keys(operation_parameter, &mut keys);
+    let param_refs: Vec<&str> = keys
+        .iter()
+        .filter_map(|key| key.strip_prefix(PARAMS_PREFIX))
+        .collect();
+    for param_ref in &param_refs {
+        if !parameter_schema
+            .iter()
+            .any(|attribute| attribute.id == *param_ref)
+        {
+            return Err(ErrNo::CommonError(LightString::from(format!(
+                "步骤\"{}\"引用了未定义的参数\"{}\"",
+                step_name, param_ref
+            ))));
+        }
+    }
+    return Ok(!param_refs.is_empty());
+}
+
+//检查操作参数中引用的步骤输出，只能引用它直接或者间接依赖的自动步骤，返回是否引用了步骤输出
+pub fn check_step_refs(
+    step_name: &str,
+    seq: i32,
+    operation_parameter: &Value,
+    step_node_list: &[StepNode],
+) -> Result<bool, ErrNo> {
+    let mut keys = Vec::new();
+    collect_placeholder_keys(operation_parameter, &mut keys);
+    let step_refs: Vec<&str> = keys
+        .iter()
+        .filter_map(|key| parse_step_ref(key))
+        .map(|(ref_step_name, _)| ref_step_name)
+        .collect();
+    if step_refs.is_empty() {
+        return Ok(false);
+    }
+    let upstream_list = upstream_steps(seq, step_node_list);
+    for step_ref in step_refs {
+        let same_name_count = step_node_list
+            .iter()
+            .filter(|step_node| step_node.name == step_ref)
+            .count();
+        if 1 < same_name_count {
+            return Err(ErrNo::CommonError(LightString::from(format!(
+                "步骤\"{}\"引用的步骤名称\"{}\"不唯一",
+                step_name, step_ref
+            ))));
+        }
+        if !upstream_list
+            .iter()
+            .any(|step_node| step_node.is_auto && step_node.name == step_ref)
+        {
+            return Err(ErrNo::CommonError(LightString::from(format!(
+                "步骤\"{}\"只能引用它所依赖的自动步骤的输出，\"{}\"不是它依赖的自动步骤",
+                step_name, step_ref
+            ))));
+        }
+    }
+    return Ok(true);
+}
+
+//操作参数中是否引用了步骤输出，引用了步骤输出的操作参数要等到执行步骤时才能渲染
+pub fn has_step_refs(operation_parameter: &Value) -> bool {
+    let mut keys = Vec::new();
+    collect_placeholder_keys(operation_parameter, &mut keys);
+    return keys.iter().any(|key| parse_step_ref(key).is_some());
+}
+
+fn get_param<'a>(params: &'a Map<String, Value>, name: &str) -> Result<&'a Value, String> {
+    return params
+        .get(name)
+        .ok_or_else(|| format!("未定义的参数\"{}\"", name));
+}
+
+fn get_step_output<'a>(
+    step_outputs: &'a HashMap<String, Map<String, Value>>,
+    step_name: &str,
+    output_name: &str,
+) -> Result<&'a Value, String> {
+    return step_outputs
+        .get(step_name)
+        .and_then(|outputs| outputs.get(output_name))
+        .ok_or_else(|| format!("步骤\"{}\"没有输出\"{}\"", step_name, output_name));
+}
+
+//resolve返回None表示不是它能处理的占位符，保持原样
+fn render_text<F>(text: &str, resolve: &F) -> Result<Value, String>
+where
+    F: Fn(&str) -> Option<Result<Value, String>>,
+{
+    let placeholders = find_placeholders(text);
+    //整个文本只是一个占位符时，保留参数原本的类型，比如布尔值或者列表，空值仍然渲染成空文本
+    if let [(range, key)] = placeholders.as_slice() {
+        if 0 == range.start && text.len() == range.end {
+            if let Some(value) = resolve(key) {
+                return match value? {
+                    Value::Null => Ok(Value::String(String::new())),
+                    value => Ok(value),
+                };
+            }
+        }
+    }
+    let mut output = String::with_capacity(text.len());
+    let mut last_end = 0;
+    for (range, key) in placeholders {
+        if let Some(value) = resolve(key) {
+            output.push_str(&text[last_end..range.start]);
+            match value? {
+                Value::Null => (),
+                Value::String(value) => output.push_str(&value),
+                value => output.push_str(&value.to_string()),
+            }
+            last_end = range.end;
+        }
+    }
+    output.push_str(&text[last_end..]);
+    return Ok(Value::String(output));
+}
+
+fn render_value<F>(value: Value, resolve: &F) -> Result<Value, String>
+where
+    F: Fn(&str) -> Option<Result<Value, String>>,
+{
+    match value {
+        Value::String(text) => render_text(&text, resolve),
+        Value::Array(list) => list
+            .into_iter()
+            .map(|item| render_value(item, resolve))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        Value::Object(map) => map
+            .into_iter()
+            .map(|(key, item)| render_value(item, resolve).map(|item| (key, item)))
+            .collect::<Result<Map<_, _>, _>>()
+            .map(Value::Object),
+        value => Ok(value),
+    }
+}
+
+fn render<F>(step_name: &str, operation_parameter: &str, resolve: &F) -> Result<String, ErrNo>
+where
+    F: Fn(&str) -> Option<Result<Value, String>>,
+{
+    let operation_parameter =
+        serde_json::from_str::<Value>(operation_parameter).map_err(|err| -> ErrNo {
+            log::error!("操作参数格式不正确：{}", err);
+            return ErrNo::CommonError(LightString::Static("操作参数格式不正确"));
+        })?;
+    let operation_parameter = render_value(operation_parameter, resolve).map_err(|err| {
+        ErrNo::CommonError(LightString::from(format!(
+            "步骤\"{}\"的操作参数渲染失败：{}",
+            step_name, err
+        )))
+    })?;
+    return serde_json::to_string(&operation_parameter).map_err(ErrNo::SerializeError);
+}
+
+//把操作参数中的占位符替换成执行参数的值，引用步骤输出的占位符保持原样
+pub fn render_operation_parameter(
+    step_name: &str,
+    operation_parameter: &str,
+    params: &Map<String, Value>,
+) -> Result<String, ErrNo> {
+    return render(step_name, operation_parameter, &|key: &str| {
+        key.strip_prefix(PARAMS_PREFIX)
+            .map(|name| get_param(params, name).cloned())
+    });
+}
+
+//执行步骤之前，把操作参数中的占位符一次替换成执行参数的值和前面步骤的输出，参数值里的占位符不会再被替换
+pub fn render_step_parameter(
+    step_name: &str,
+    operation_parameter: &str,
+    params: &Map<String, Value>,
+    step_outputs: &HashMap<String, Map<String, Value>>,
+) -> Result<String, ErrNo> {
+    return render(step_name, operation_parameter, &|key: &str| {
+        if let Some(name) = key.strip_prefix(PARAMS_PREFIX) {
+            return Some(get_param(params, name).cloned());
+        }
+        parse_step_ref(key).map(|(ref_step_name, output_name)| {
+            get_step_output(step_outputs, ref_step_name, output_name).cloned()
+        })
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sdk::extension::EnumOption;
+    use serde_json::json;
+
+    fn attribute(id: &str, required: bool, r#type: AttributeType) -> Attribute {
+        return Attribute {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: None,
+            required: required,
+            r#type: r#type,
+        };
+    }
+
+    fn step_node(seq: i32, name: &str, depends_on: Option<Vec<i32>>, is_auto: bool) -> StepNode {
+        return StepNode {
+            seq: seq,
+            name: name.to_string(),
+            depends_on: depends_on,
+            is_auto: is_auto,
+        };
+    }
+
+    fn to_map(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(map) => map,
+            _ => panic!("not an object"),
+        }
+    }
+
+    #[test]
+    fn test_check_parameter_schema() {
+        assert!(check_parameter_schema(&[
+            attribute("image_tag", true, AttributeType::String),
+            attribute("dry_run", false, AttributeType::Bool),
+        ])
+        .is_ok());
+        assert!(
+            check_parameter_schema(&[attribute("image-tag", true, AttributeType::String)]).is_err()
+        );
+        assert!(check_parameter_schema(&[attribute("", true, AttributeType::String)]).is_err());
+        assert!(check_parameter_schema(&[
+            attribute("tag", true, AttributeType::String),
+            attribute("tag", false, AttributeType::Bool),
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_check_params() {
+        let parameter_schema = [
+            attribute("tag", true, AttributeType::String),
+            attribute("dry_run", false, AttributeType::Bool),
+        ];
+        let params = check_params(
+            &parameter_schema,
+            Some(String::from(r#"{"tag":"v1","extra":1}"#)),
+        )
+        .unwrap();
+        //未定义的参数被丢弃，未填写的非必填参数是null
+        assert_eq!(to_map(json!({"tag": "v1", "dry_run": null})), params);
+        assert!(check_params(&parameter_schema, None).is_err());
+        assert!(check_params(&parameter_schema, Some(String::from(r#"{"tag":""}"#))).is_err());
+        assert!(check_params(&parameter_schema, Some(String::from(r#"{"tag":1}"#))).is_err());
+        assert!(check_params(&parameter_schema, Some(String::from("[]"))).is_err());
+    }
+
+    #[test]
+    fn test_check_params_enum() {
+        let options = vec![EnumOption {
+            value: String::from("prod"),
+            label: String::from("生产"),
+        }];
+        let parameter_schema = [attribute(
+            "target",
+            true,
+            AttributeType::EnumList { options: options },
+        )];
+        assert!(check_params(
+            &parameter_schema,
+            Some(String::from(r#"{"target":["prod"]}"#))
+        )
+        .is_ok());
+        assert!(check_params(
+            &parameter_schema,
+            Some(String::from(r#"{"target":["test"]}"#))
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_check_param_refs() {
+        let parameter_schema = [attribute("tag", true, AttributeType::String)];
+        let operation_parameter = json!({"image": "app:{{ params.tag }}"});
+        assert!(check_param_refs("deploy", &operation_parameter, &parameter_schema).unwrap());
+        assert!(!check_param_refs("deploy", &json!({"image": "app"}), &parameter_schema).unwrap());
+        assert!(
+            check_param_refs("deploy", &json!(["{{params.other}}"]), &parameter_schema).is_err()
+        );
+    }
+
+    #[test]
+    fn test_check_step_refs() {
+        let step_node_list = vec![
+            step_node(1, "build", None, true),
+            step_node(2, "approve", None, false),
+            step_node(3, "deploy", None, true),
+            step_node(4, "notify", Some(vec![1]), true),
+        ];
+        let operation_parameter = json!({"url": "{{steps.build.outputs.url}}"});
+        assert!(check_step_refs("deploy", 3, &operation_parameter, &step_node_list).unwrap());
+        assert!(!check_step_refs("deploy", 3, &json!({}), &step_node_list).unwrap());
+        //只能引用依赖的自动步骤
+        let operation_parameter = json!({"by": "{{steps.approve.outputs.user}}"});
+        assert!(check_step_refs("deploy", 3, &operation_parameter, &step_node_list).is_err());
+        let operation_parameter = json!({"url": "{{steps.deploy.outputs.url}}"});
+        assert!(check_step_refs("notify", 4, &operation_parameter, &step_node_list).is_err());
+        assert!(has_step_refs(&json!(["{{steps.build.outputs.url}}"])));
+        assert!(!has_step_refs(&json!(["{{params.tag}}"])));
+    }
+
+    #[test]
+    fn test_render_operation_parameter() {
+        let params = to_map(json!({"tag": "v1", "replicas": 3, "dry_run": null}));
+        let operation_parameter = json!({
+            "image": "app:{{params.tag}}",
+            "replicas": "{{params.replicas}}",
+            "dry_run": "{{params.dry_run}}",
+            "url": "{{steps.build.outputs.url}}",
+        })
+        .to_string();
+        let rendered = render_operation_parameter("deploy", &operation_parameter, &params).unwrap();
+        //整个文本只是一个占位符时保留原本的类型，引用步骤输出的占位符保持原样
+        assert_eq!(
+            json!({
+                "image": "app:v1",
+                "replicas": 3,
+                "dry_run": "",
+                "url": "{{steps.build.outputs.url}}",
+            }),
+            serde_json::from_str::<Value>(&rendered).unwrap()
+        );
+        let operation_parameter = json!({"image": "{{params.other}}"}).to_string();
+        assert!(render_operation_parameter("deploy", &operation_parameter, &params).is_err());
+        assert!(render_operation_parameter("deploy", "{", &params).is_err());
+    }
+
+    #[test]
+    fn test_render_step_parameter() {
+        let params = to_map(json!({"tag": "{{steps.build.outputs.url}}"}));
+        let mut step_outputs = HashMap::new();
+        step_outputs.insert(
+            String::from("build"),
+            to_map(json!({"url": "https://example.com/app.tar", "size": 10})),
+        );
+        let operation_parameter = json!({
+            "url": "{{steps.build.outputs.url}}",
+            "text": "size={{steps.build.outputs.size}}",
+            "tag": "{{params.tag}}",
+        })
+        .to_string();
+        let rendered =
+            render_step_parameter("deploy", &operation_parameter, &params, &step_outputs).unwrap();
+        //参数值里的占位符不会再被替换
+        assert_eq!(
+            json!({
+                "url": "https://example.com/app.tar",
+                "text": "size=10",
+                "tag": "{{steps.build.outputs.url}}",
+            }),
+            serde_json::from_str::<Value>(&rendered).unwrap()
+        );
+        let operation_parameter = json!({"url": "{{steps.build.outputs.md5}}"}).to_string();
+        assert!(
+            render_step_parameter("deploy", &operation_parameter, &params, &step_outputs).is_err()
+        );
+    }
+}
@@ -1,8 +1,25 @@
+use super::check_batch_policy;
+use super::check_retry_policy;
+use super::condition::check_run_expression;
+use super::from_sdk_failure_policy;
+use super::from_sdk_interrupt_policy;
+use super::from_sdk_run_if;
+use super::from_sdk_signoff_role;
+use super::graph::check_depends_on;
+use super::graph::serialize_depends_on;
+use super::graph::StepNode;
+use super::signoff::check_signoff_policy;
+use super::signoff::serialize_signoff_users;
+use super::template::check_param_refs;
+use super::template::check_parameter_schema;
+use super::template::check_step_refs;
+use super::template::serialize_parameter_schema;
 use crate::get_context;
 use crate::middleware::auth::User;
 use crate::model::environment_schema_resource::EnvironmentSchemaResourceOpt;
 use crate::model::job::JobOpt;
 use crate::model::job::JobProperty;
+use crate::model::job_step::enums::FailurePolicy;
 use crate::model::job_step::enums::StepType;
 use crate::model::job_step::JobStep;
 use crate::model::job_step::JobStepOpt;
@@ -30,8 +47,47 @@ pub async fn update_job(
         id,
         name,
         remark,
+        interrupt_policy,
+        parameter_schema,
         job_step_list,
     } = update_job_req;
+    check_parameter_schema(&parameter_schema)?;
+    //步骤的执行顺序，用于检查步骤之间的依赖
+    let seq_list: Vec<i32> = job_step_list
+        .iter()
+        .map(|job_step| match job_step {
+            sdk::job::update_job::JobStep::Auto { seq, .. }
+            | sdk::job::update_job::JobStep::Manual { seq, .. } => *seq,
+        })
+        .collect();
+    //步骤之间的依赖关系，用于检查步骤引用的输出
+    let step_node_list: Vec<StepNode> = job_step_list
+        .iter()
+        .map(|job_step| match job_step {
+            sdk::job::update_job::JobStep::Auto {
+                seq,
+                name,
+                depends_on,
+                ..
+            } => StepNode {
+                seq: *seq,
+                name: name.clone(),
+                depends_on: depends_on.clone(),
+                is_auto: true,
+            },
+            sdk::job::update_job::JobStep::Manual {
+                seq,
+                name,
+                depends_on,
+                ..
+            } => StepNode {
+                seq: *seq,
+                name: name.clone(),
+                depends_on: depends_on.clone(),
+                is_auto: false,
+            },
+        })
+        .collect();
     let job_id = id;
     let context = get_context()?;
     let mut client = context.get_db_client().await?;
@@ -69,8 +125,32 @@ pub async fn update_job(
                 operation_id,
                 operation_parameter,
                 remark,
+                timeout_seconds,
+                max_retries,
+                retry_backoff,
+                max_parallel,
+                batch_size,
+                pause_between_batches,
+                failure_policy,
+                run_if,
+                run_expression,
                 seq,
+                depends_on,
             } => {
+                check_retry_policy(&name, timeout_seconds, max_retries, retry_backoff)?;
+                check_batch_policy(&name, max_parallel, batch_size, pause_between_batches)?;
+                let batch_policy = (
+                    max_parallel,
+                    batch_size,
+                    pause_between_batches,
+                    from_sdk_failure_policy(failure_policy),
+                );
+                if let Some(run_expression) = &run_expression {
+                    check_run_expression(&name, run_expression, &parameter_schema)?;
+                }
+                let run_condition = (from_sdk_run_if(run_if), run_expression);
+                check_depends_on(&name, seq, depends_on.as_deref(), &seq_list)?;
+                let depends_on = serialize_depends_on(depends_on)?;
                 if let Some(schema_resource) = schema_resource_list
                     .iter()
                     .find(|schema_resource| schema_resource_id == schema_resource.id)
@@ -96,9 +176,16 @@ pub async fn update_job(
                                         "操作参数格式不正确",
                                     ));
                                 })?;
-                        extension
-                            .validate_operation_parameter(&operation_id, parameter)
-                            .map_err(|err| ErrNo::CommonError(err.into()))?;
+                        //引用了执行参数或者步骤输出的操作参数，等到渲染之后再校验
+                        let has_param_refs =
+                            check_param_refs(&name, &parameter, &parameter_schema)?;
+                        let has_step_refs =
+                            check_step_refs(&name, seq, &parameter, &step_node_list)?;
+                        if !has_param_refs && !has_step_refs {
+                            extension
+                                .validate_operation_parameter(&operation_id, parameter)
+                                .map_err(|err| ErrNo::CommonError(err.into()))?;
+                        }
                         if let Some(id) = id {
                             update_list.push((
                                 id,
@@ -109,7 +196,13 @@ pub async fn update_job(
                                     operation.name.clone(),
                                     operation_parameter,
                                     remark,
+                                    timeout_seconds,
+                                    max_retries,
+                                    retry_backoff,
+                                    batch_policy,
+                                    run_condition,
                                     seq,
+                                    depends_on,
                                 )),
                             ));
                         } else {
@@ -126,7 +219,20 @@ pub async fn update_job(
                                 operation_parameter: operation_parameter, //操作参数
                                 remark: remark,            //备注
                                 attachments: None,         //附件
+                                signoff_role: None,        //签收角色
+                                signoff_users: None,       //签收人
+                                required_signoffs: 1,      //需要的签收人数
+                                timeout_seconds: timeout_seconds, //超时时间（秒）
+                                max_retries: max_retries,  //最大重试次数
+                                retry_backoff: retry_backoff, //重试间隔（秒）
+                                max_parallel: batch_policy.0, //最大并发数
+                                batch_size: batch_policy.1, //每批资源数
+                                pause_between_batches: batch_policy.2, //批次间隔（秒）
+                                failure_policy: batch_policy.3, //失败策略
+                                run_if: run_condition.0,   //执行条件
+                                run_expression: run_condition.1, //条件表达式
                                 seq: seq,                  //执行顺序
+                                depends_on: depends_on,    //依赖的步骤
                                 created_time: curr_time,   //创建时间
                                 last_modified_time: curr_time, //更新时间
                             });
@@ -149,27 +255,77 @@ pub async fn update_job(
                 name,
                 remark,
                 attachments,
+                signoff_role,
+                signoff_user_list,
+                required_signoffs,
+                run_if,
+                run_expression,
                 seq,
+                depends_on,
             } => {
+                if let Some(run_expression) = &run_expression {
+                    check_run_expression(&name, run_expression, &parameter_schema)?;
+                }
+                let run_condition = (from_sdk_run_if(run_if), run_expression);
+                check_signoff_policy(
+                    &transaction,
+                    org_id,
+                    &name,
+                    signoff_role.is_some(),
+                    signoff_user_list.as_deref(),
+                    required_signoffs,
+                )
+                .await?;
+                let signoff_policy = (
+                    signoff_role.map(from_sdk_signoff_role),
+                    serialize_signoff_users(signoff_user_list)?,
+                    required_signoffs,
+                );
+                check_depends_on(&name, seq, depends_on.as_deref(), &seq_list)?;
+                let depends_on = serialize_depends_on(depends_on)?;
                 if let Some(id) = id {
-                    update_list.push((id, list::Either::Right((name, remark, attachments, seq))));
+                    update_list.push((
+                        id,
+                        list::Either::Right((
+                            name,
+                            remark,
+                            attachments,
+                            signoff_policy,
+                            run_condition,
+                            seq,
+                            depends_on,
+                        )),
+                    ));
                 } else {
                     let id = context.new_id();
                     add_list.push(JobStep {
                         id: id, //步骤id
                         org_id: org_id,
-                        job_id: job_id.into(),                 //任务id
-                        name: name,                            //步骤名称
-                        step_type: StepType::Manual,           //步骤类型
-                        schema_resource_id: None,              //环境规格资源id
-                        operation_id: String::from(""),        //操作id
-                        operation_name: String::from(""),      //操作名称
-                        operation_parameter: String::from(""), //操作参数
-                        remark: remark,                        //备注
-                        attachments: attachments,              //附件
-                        seq: seq,                              //执行顺序
-                        created_time: curr_time,               //创建时间
-                        last_modified_time: curr_time,         //更新时间
+                        job_id: job_id.into(),                   //任务id
+                        name: name,                              //步骤名称
+                        step_type: StepType::Manual,             //步骤类型
+                        schema_resource_id: None,                //环境规格资源id
+                        operation_id: String::from(""),          //操作id
+                        operation_name: String::from(""),        //操作名称
+                        operation_parameter: String::from(""),   //操作参数
+                        remark: remark,                          //备注
+                        attachments: attachments,                //附件
+                        signoff_role: signoff_policy.0,          //签收角色
+                        signoff_users: signoff_policy.1,         //签收人
+                        required_signoffs: signoff_policy.2,     //需要的签收人数
+                        timeout_seconds: None,                   //超时时间（秒）
+                        max_retries: 0,                          //最大重试次数
+                        retry_backoff: 0,                        //重试间隔（秒）
+                        max_parallel: None,                      //最大并发数
+                        batch_size: None,                        //每批资源数
+                        pause_between_batches: 0,                //批次间隔（秒）
+                        failure_policy: FailurePolicy::Continue, //失败策略
+                        run_if: run_condition.0,                 //执行条件
+                        run_expression: run_condition.1,         //条件表达式
+                        seq: seq,                                //执行顺序
+                        depends_on: depends_on,                  //依赖的步骤
+                        created_time: curr_time,                 //创建时间
+                        last_modified_time: curr_time,           //更新时间
                     });
                 }
             }
@@ -198,15 +354,27 @@ pub async fn update_job(
                         _operation_name,
                         _operation_parameter,
                         _remark,
+                        _timeout_seconds,
+                        _max_retries,
+                        _retry_backoff,
+                        _batch_policy,
+                        _run_condition,
                         _seq,
+                        _depends_on,
                     )) => {
                         StepType::Auto == existed.step_type
                             && Some(schema_resource_id) == existed.schema_resource_id.as_ref()
                             && operation_id == &existed.operation_id
                     }
-                    Either::Right((_name, _remark, _attachments, _seq)) => {
-                        StepType::Manual == existed.step_type
-                    }
+                    Either::Right((
+                        _name,
+                        _remark,
+                        _attachments,
+                        _signoff_policy,
+                        _run_condition,
+                        _seq,
+                        _depends_on,
+                    )) => StepType::Manual == existed.step_type,
                 }
         },
         |(id, step), existed| {
@@ -219,7 +387,13 @@ pub async fn update_job(
                         operation_name,
                         operation_parameter,
                         remark,
+                        timeout_seconds,
+                        max_retries,
+                        retry_backoff,
+                        batch_policy,
+                        run_condition,
                         seq,
+                        depends_on,
                     )) => {
                         StepType::Auto == existed.step_type
                             && Some(schema_resource_id) == existed.schema_resource_id.as_ref()
@@ -228,14 +402,38 @@ pub async fn update_job(
                             && operation_name == &existed.operation_name
                             && operation_parameter == &existed.operation_parameter
                             && remark == &existed.remark
+                            && timeout_seconds == &existed.timeout_seconds
+                            && max_retries == &existed.max_retries
+                            && retry_backoff == &existed.retry_backoff
+                            && batch_policy.0 == existed.max_parallel
+                            && batch_policy.1 == existed.batch_size
+                            && batch_policy.2 == existed.pause_between_batches
+                            && batch_policy.3 == existed.failure_policy
+                            && run_condition.0 == existed.run_if
+                            && run_condition.1 == existed.run_expression
                             && seq == &existed.seq
+                            && depends_on == &existed.depends_on
                     }
-                    Either::Right((name, remark, attachments, seq)) => {
+                    Either::Right((
+                        name,
+                        remark,
+                        attachments,
+                        signoff_policy,
+                        run_condition,
+                        seq,
+                        depends_on,
+                    )) => {
                         StepType::Manual == existed.step_type
                             && name == &existed.name
                             && remark == &existed.remark
                             && attachments == &existed.attachments
+                            && signoff_policy.0 == existed.signoff_role
+                            && signoff_policy.1 == existed.signoff_users
+                            && signoff_policy.2 == existed.required_signoffs
+                            && run_condition.0 == existed.run_if
+                            && run_condition.1 == existed.run_expression
                             && seq == &existed.seq
+                            && depends_on == &existed.depends_on
                     }
                 }
         },
@@ -249,7 +447,13 @@ pub async fn update_job(
                     operation_name,
                     operation_parameter,
                     remark,
+                    timeout_seconds,
+                    max_retries,
+                    retry_backoff,
+                    batch_policy,
+                    run_condition,
                     seq,
+                    depends_on,
                 )) => {
                     JobStep {
                         id: id, //步骤id
@@ -263,27 +467,61 @@ pub async fn update_job(
                         operation_parameter: operation_parameter,     //操作参数
                         remark: remark,                               //备注
                         attachments: None,                            //附件
+                        signoff_role: None,                           //签收角色
+                        signoff_users: None,                          //签收人
+                        required_signoffs: 1,                         //需要的签收人数
+                        timeout_seconds: timeout_seconds,             //超时时间（秒）
+                        max_retries: max_retries,                     //最大重试次数
+                        retry_backoff: retry_backoff,                 //重试间隔（秒）
+                        max_parallel: batch_policy.0,                 //最大并发数
+                        batch_size: batch_policy.1,                   //每批资源数
+                        pause_between_batches: batch_policy.2,        //批次间隔（秒）
+                        failure_policy: batch_policy.3,               //失败策略
+                        run_if: run_condition.0,                      //执行条件
+                        run_expression: run_condition.1,              //条件表达式
                         seq: seq,                                     //执行顺序
+                        depends_on: depends_on,                       //依赖的步骤
                         created_time: curr_time,                      //创建时间
                         last_modified_time: curr_time,                //更新时间
                     }
                 }
-                Either::Right((name, remark, attachments, seq)) => {
+                Either::Right((
+                    name,
+                    remark,
+                    attachments,
+                    signoff_policy,
+                    run_condition,
+                    seq,
+                    depends_on,
+                )) => {
                     JobStep {
                         id: id, //步骤id
                         org_id: org_id,
-                        job_id: job_id.into(),                 //任务id
-                        name: name,                            //步骤名称
-                        step_type: StepType::Manual,           //步骤类型
-                        schema_resource_id: None,              //环境规格资源id
-                        operation_id: String::from(""),        //操作id
-                        operation_name: String::from(""),      //操作名称
-                        operation_parameter: String::from(""), //操作参数
-                        remark: remark,                        //备注
-                        attachments: attachments,              //附件
-                        seq: seq,                              //执行顺序
-                        created_time: curr_time,               //创建时间
-                        last_modified_time: curr_time,         //更新时间
+                        job_id: job_id.into(),                   //任务id
+                        name: name,                              //步骤名称
+                        step_type: StepType::Manual,             //步骤类型
+                        schema_resource_id: None,                //环境规格资源id
+                        operation_id: String::from(""),          //操作id
+                        operation_name: String::from(""),        //操作名称
+                        operation_parameter: String::from(""),   //操作参数
+                        remark: remark,                          //备注
+                        attachments: attachments,                //附件
+                        signoff_role: signoff_policy.0,          //签收角色
+                        signoff_users: signoff_policy.1,         //签收人
+                        required_signoffs: signoff_policy.2,     //需要的签收人数
+                        timeout_seconds: None,                   //超时时间（秒）
+                        max_retries: 0,                          //最大重试次数
+                        retry_backoff: 0,                        //重试间隔（秒）
+                        max_parallel: None,                      //最大并发数
+                        batch_size: None,                        //每批资源数
+                        pause_between_batches: 0,                //批次间隔（秒）
+                        failure_policy: FailurePolicy::Continue, //失败策略
+                        run_if: run_condition.0,                 //执行条件
+                        run_expression: run_condition.1,         //条件表达式
+                        seq: seq,                                //执行顺序
+                        depends_on: depends_on,                  //依赖的步骤
+                        created_time: curr_time,                 //创建时间
+                        last_modified_time: curr_time,           //更新时间
                     }
                 }
             }
@@ -308,22 +546,52 @@ pub async fn update_job(
                 _operation_name,
                 operation_parameter,
                 remark,
+                timeout_seconds,
+                max_retries,
+                retry_backoff,
+                (max_parallel, batch_size, pause_between_batches, failure_policy),
+                (run_if, run_expression),
                 seq,
+                depends_on,
             )) => {
                 vec![
                     JobStepProperty::Name(name),
                     JobStepProperty::OperationParameter(operation_parameter),
                     JobStepProperty::Remark(remark),
+                    JobStepProperty::TimeoutSeconds(timeout_seconds),
+                    JobStepProperty::MaxRetries(max_retries),
+                    JobStepProperty::RetryBackoff(retry_backoff),
+                    JobStepProperty::MaxParallel(max_parallel),
+                    JobStepProperty::BatchSize(batch_size),
+                    JobStepProperty::PauseBetweenBatches(pause_between_batches),
+                    JobStepProperty::FailurePolicy(failure_policy),
+                    JobStepProperty::RunIf(run_if),
+                    JobStepProperty::RunExpression(run_expression),
                     JobStepProperty::Seq(seq),
+                    JobStepProperty::DependsOn(depends_on),
                     JobStepProperty::LastModifiedTime(curr_time),
                 ]
             }
-            Either::Right((name, remark, attachments, seq)) => {
+            Either::Right((
+                name,
+                remark,
+                attachments,
+                (signoff_role, signoff_users, required_signoffs),
+                (run_if, run_expression),
+                seq,
+                depends_on,
+            )) => {
                 vec![
                     JobStepProperty::Name(name),
                     JobStepProperty::Remark(remark),
                     JobStepProperty::Attachments(attachments),
+                    JobStepProperty::SignoffRole(signoff_role),
+                    JobStepProperty::SignoffUsers(signoff_users),
+                    JobStepProperty::RequiredSignoffs(required_signoffs),
+                    JobStepProperty::RunIf(run_if),
+                    JobStepProperty::RunExpression(run_expression),
                     JobStepProperty::Seq(seq),
+                    JobStepProperty::DependsOn(depends_on),
                     JobStepProperty::LastModifiedTime(curr_time),
                 ]
             }
@@ -342,6 +610,8 @@ pub async fn update_job(
     let mut changes: Vec<JobProperty> = vec![
         JobProperty::Name(name.into()),
         JobProperty::Remark(remark.into()),
+        JobProperty::InterruptPolicy(from_sdk_interrupt_policy(interrupt_policy)),
+        JobProperty::ParameterSchema(serialize_parameter_schema(&parameter_schema)?),
     ];
     changes.retain(|property| !job.eq(property));
     if !changes.is_empty() {
@@ -0,0 +1,17 @@
+use super::super::job::approval::decide_job_record;
+use crate::middleware::auth::User;
+use crate::model::job_record_approval::enums::Decision;
+use crate::sdk;
+use sdk::job_record::approve_job_record::ApproveJobRecordReq;
+use sdk::job_record::approve_job_record::ApproveJobRecordResp;
+use tihu::Id;
+use tihu_native::ErrNo;
+
+pub async fn approve_job_record(
+    org_id: Id,
+    user: User,
+    approve_job_record_req: ApproveJobRecordReq,
+) -> Result<ApproveJobRecordResp, ErrNo> {
+    let ApproveJobRecordReq { record_id, comment } = approve_job_record_req;
+    return decide_job_record(org_id, user, record_id, Decision::Approve, comment).await;
+}
@@ -0,0 +1,55 @@
+use super::purge_job_record::delete_job_records;
+use super::purge_job_record::remove_job_record_logs;
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::job_record::enums::Status;
+use crate::model::job_record::JobRecordOpt;
+use crate::sdk;
+use crate::service::base::JobRecordBaseService;
+use sdk::job_record::delete_job_record::DeleteJobRecordReq;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+pub async fn delete_job_record(
+    org_id: Id,
+    _user: User,
+    delete_job_record_req: DeleteJobRecordReq,
+) -> Result<(), ErrNo> {
+    let DeleteJobRecordReq { id } = delete_job_record_req;
+    let record_id = id;
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_record_base_service = JobRecordBaseService::new(&transaction);
+    let job_record = job_record_base_service
+        .query_job_record_one(&JobRecordOpt {
+            org_id: Some(org_id),
+            id: Some(record_id),
+            ..JobRecordOpt::empty()
+        })
+        .await?
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("待删除的执行记录不存在！")))?;
+    if Status::Running == job_record.status
+        || Status::Queued == job_record.status
+        || Status::PendingApproval == job_record.status
+    {
+        return Err(ErrNo::CommonError(LightString::from_static(
+            "该执行记录还没有结束，不能删除！",
+        )));
+    }
+    if job_record.pinned {
+        return Err(ErrNo::CommonError(LightString::from_static(
+            "该执行记录已置顶，取消置顶之后才能删除！",
+        )));
+    }
+    let removed = delete_job_records(&transaction, org_id, &[record_id]).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    remove_job_record_logs(&context, removed).await;
+    return Ok(());
+}
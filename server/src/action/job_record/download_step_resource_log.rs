@@ -0,0 +1,109 @@
+use super::log_store::load_step_resource_log;
+use super::watch_step_resource_log::LogStreamTicket;
+use crate::context::Context;
+use crate::get_context;
+use crate::log;
+use crate::middleware::auth::User;
+use crate::model::job_step_resource_record::JobStepResourceRecordOpt;
+use crate::native_common;
+use crate::sdk;
+use crate::service::base::JobStepResourceRecordBaseService;
+use native_common::cache::AsyncCache;
+use native_common::cache::EliminateType;
+use sdk::job_record::download_step_resource_log::DownloadStepResourceLogReq;
+use sdk::job_record::download_step_resource_log::DownloadStepResourceLogResp;
+use std::fmt::Write;
+use tihu::base62;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+use uuid::Uuid;
+
+pub const LOG_DOWNLOAD_TOKEN_PREFIX: &str = "step-resource-log-download-";
+//下载凭证的有效期（毫秒）
+const LOG_DOWNLOAD_TOKEN_EXPIRE_MILLIS: u64 = 60 * 1000;
+
+//浏览器直接打开下载地址时不能带签名请求头，所以和实时日志一样先换取一个短期凭证
+pub async fn download_step_resource_log(
+    org_id: Id,
+    _user: User,
+    download_step_resource_log_req: DownloadStepResourceLogReq,
+) -> Result<DownloadStepResourceLogResp, ErrNo> {
+    let DownloadStepResourceLogReq { id } = download_step_resource_log_req;
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
+    job_step_resource_record_base_service
+        .query_job_step_resource_record_one(&JobStepResourceRecordOpt {
+            org_id: Some(org_id),
+            id: Some(id),
+            ..JobStepResourceRecordOpt::empty()
+        })
+        .await?
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("该资源执行记录不存在")))?;
+    let ticket = LogStreamTicket {
+        org_id: org_id,
+        step_resource_record_id: id,
+    };
+    let ticket = serde_json::to_vec(&ticket).map_err(ErrNo::SerializeError)?;
+    let token = base62::encode(&Uuid::new_v4().as_u128().to_be_bytes());
+    let cache_mgr = context.get_cache_mgr().await?;
+    cache_mgr
+        .set(
+            &(String::from(LOG_DOWNLOAD_TOKEN_PREFIX) + &token).into_bytes(),
+            &ticket,
+            EliminateType::Expire(LOG_DOWNLOAD_TOKEN_EXPIRE_MILLIS),
+        )
+        .await
+        .map_err(|err| {
+            log::error!("缓存日志的下载凭证失败: {:?}", err);
+            err
+        })?;
+    return Ok(DownloadStepResourceLogResp { token: token });
+}
+
+/**
+ * 用下载凭证读取资源执行的完整日志，每条日志一行，凭证无效或者已过期时返回None
+ */
+pub async fn open_log_download(
+    context: &Context,
+    token: &str,
+) -> Result<Option<(Id, String)>, ErrNo> {
+    let cache_mgr = context.get_cache_mgr().await?;
+    let ticket = cache_mgr
+        .get(&(String::from(LOG_DOWNLOAD_TOKEN_PREFIX) + token).into_bytes())
+        .await?;
+    let ticket: LogStreamTicket = match ticket {
+        Some(ticket) => serde_json::from_slice(&ticket).map_err(ErrNo::DeserializeError)?,
+        None => return Ok(None),
+    };
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
+    let step_resource_record = job_step_resource_record_base_service
+        .query_job_step_resource_record_one(&JobStepResourceRecordOpt {
+            org_id: Some(ticket.org_id),
+            id: Some(ticket.step_resource_record_id),
+            ..JobStepResourceRecordOpt::empty()
+        })
+        .await?;
+    let step_resource_record = match step_resource_record {
+        Some(step_resource_record) => step_resource_record,
+        None => return Ok(None),
+    };
+    let logs = load_step_resource_log(context, &step_resource_record).await?;
+    let mut content = String::new();
+    for log in logs {
+        writeln!(
+            content,
+            "{} [{}] {}",
+            log.time.to_rfc3339(),
+            log.level,
+            log.content
+        )
+        .ok();
+    }
+    return Ok(Some((step_resource_record.id, content)));
+}
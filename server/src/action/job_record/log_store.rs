@@ -0,0 +1,288 @@
+use crate::context::Context;
+use crate::model::job_step_resource_record::JobStepResourceRecord;
+use crate::sdk;
+use aws_sdk_s3::primitives::ByteStream;
+use deadpool_redis::redis;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use sdk::job_record::read_job_record::LogLevel;
+use sdk::job_record::read_job_record::StepResLog;
+use sdk::job_record::read_job_record::StepResLogSummary;
+use std::io::ErrorKind;
+use std::io::Read;
+use std::io::Write;
+use std::sync::Arc;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::ErrNo;
+use tokio::sync::mpsc;
+
+//摘要里最后一条错误日志最多保留的字符数
+const MAX_LAST_ERROR_CHARS: usize = 500;
+//执行中的日志在redis里的key前缀
+const LIVE_LOG_PREFIX: &str = "step-resource-live-log-";
+//执行中的日志在redis里的有效期（毫秒），每次写入都会延长，执行结束后日志已经归档，等待自动过期即可
+const LIVE_LOG_EXPIRE_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
+//日志文件中每条日志都以逗号结尾，取出已经写完整的日志，不完整的部分留到下次再解析
+pub fn take_complete_logs(pending: &mut Vec<u8>) -> Vec<StepResLog> {
+    let mut logs = Vec::new();
+    let mut pos = 0;
+    loop {
+        while pos < pending.len() && (b',' == pending[pos] || pending[pos].is_ascii_whitespace()) {
+            pos += 1;
+        }
+        if pos >= pending.len() {
+            break;
+        }
+        let mut iter =
+            serde_json::Deserializer::from_slice(&pending[pos..]).into_iter::<StepResLog>();
+        match iter.next() {
+            Some(Ok(log)) => {
+                pos += iter.byte_offset();
+                logs.push(log);
+            }
+            Some(Err(err)) if err.is_eof() => break,
+            Some(Err(err)) => {
+                log::error!("解析任务日志失败: {:?}", err);
+                pos = pending.len();
+                break;
+            }
+            None => break,
+        }
+    }
+    pending.drain(..pos);
+    return logs;
+}
+
+/**
+ * 读取执行过程中的日志文件，日志文件不存在时返回空列表
+ */
+pub async fn read_log_file(log_file_path: &str) -> Result<Vec<StepResLog>, ErrNo> {
+    match tokio::fs::read(log_file_path).await {
+        Ok(mut content) => Ok(take_complete_logs(&mut content)),
+        Err(err) if ErrorKind::NotFound == err.kind() => Ok(Vec::new()),
+        Err(err) => Err(ErrNo::CommonError(LightString::from(format!(
+            "读取任务日志内容失败：{}",
+            err
+        )))),
+    }
+}
+
+pub fn summarize_logs(logs: &[StepResLog]) -> StepResLogSummary {
+    let mut summary = StepResLogSummary {
+        total: logs.len() as u64,
+        ..StepResLogSummary::default()
+    };
+    for log in logs {
+        match log.level {
+            LogLevel::Error => {
+                summary.error_count += 1;
+                summary.last_error = Some(log.content.chars().take(MAX_LAST_ERROR_CHARS).collect());
+            }
+            LogLevel::Warn => summary.warn_count += 1,
+            _ => (),
+        }
+    }
+    return summary;
+}
+
+/**
+ * 把资源执行的完整日志压缩后上传到对象存储，返回对象的key和日志摘要（json格式）
+ */
+pub async fn store_step_resource_log(
+    context: &Context,
+    org_id: Id,
+    step_resource_record_id: Id,
+    logs: &[StepResLog],
+) -> Result<(String, String), ErrNo> {
+    let content = serde_json::to_vec(logs).map_err(ErrNo::SerializeError)?;
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(&content)
+        .map_err(|err| ErrNo::CommonError(LightString::from(format!("压缩日志失败：{}", err))))?;
+    let data = encoder
+        .finish()
+        .map_err(|err| ErrNo::CommonError(LightString::from(format!("压缩日志失败：{}", err))))?;
+    let key = format!("job-logs/{}/{}.json.gz", org_id, step_resource_record_id);
+    context
+        .get_oss_client()
+        .put_object()
+        .bucket(context.get_bucket().to_string())
+        .key(&key)
+        .content_type("application/gzip")
+        .body(ByteStream::from(data))
+        .send()
+        .await
+        .map_err(|err| {
+            log::error!("上传任务日志失败: {:?}", err);
+            ErrNo::CommonError(LightString::from(format!("上传任务日志失败：{}", err)))
+        })?;
+    let summary = serde_json::to_string(&summarize_logs(logs)).map_err(ErrNo::SerializeError)?;
+    return Ok((key, summary));
+}
+
+async fn read_log_object(context: &Context, key: &str) -> Result<Vec<StepResLog>, ErrNo> {
+    let resp = context
+        .get_oss_client()
+        .get_object()
+        .bucket(context.get_bucket().to_string())
+        .key(key)
+        .send()
+        .await
+        .map_err(|err| {
+            log::error!("下载任务日志失败: {:?}", err);
+            ErrNo::CommonError(LightString::from(format!("下载任务日志失败：{}", err)))
+        })?;
+    let data = resp.body.collect().await.map_err(|err| {
+        log::error!("读取任务日志数据失败: {:?}", err);
+        ErrNo::CommonError(LightString::from(format!("读取任务日志数据失败：{}", err)))
+    })?;
+    let mut content = Vec::new();
+    GzDecoder::new(data.into_bytes().as_ref())
+        .read_to_end(&mut content)
+        .map_err(|err| ErrNo::CommonError(LightString::from(format!("解压日志失败：{}", err))))?;
+    return serde_json::from_slice(&content).map_err(ErrNo::DeserializeError);
+}
+
+/**
+ * 读取资源执行的完整日志，依次尝试对象存储、旧数据里保存在数据库的日志内容、执行中的日志文件
+ */
+pub async fn load_step_resource_log(
+    context: &Context,
+    step_resource_record: &JobStepResourceRecord,
+) -> Result<Vec<StepResLog>, ErrNo> {
+    if let Some(log_key) = step_resource_record.log_key.as_ref() {
+        return read_log_object(context, log_key).await;
+    }
+    if let Some(output_content) = step_resource_record.output_content.as_ref() {
+        return serde_json::from_str(output_content).map_err(ErrNo::DeserializeError);
+    }
+    if let Some(output_file) = step_resource_record.output_file.as_ref() {
+        let log_file_path = format!("{}/{}", context.config.job_log_dir, output_file);
+        return read_log_file(&log_file_path).await;
+    }
+    return Ok(Vec::new());
+}
+
+fn live_log_key(step_resource_record_id: Id) -> String {
+    return format!("{}{}", LIVE_LOG_PREFIX, step_resource_record_id);
+}
+
+/**
+ * 执行过程中把日志同时写到redis，其它实例上的实时日志订阅也能读到，返回写日志的发送端
+ * 所有发送端释放后写入任务结束
+ */
+pub fn start_live_log_writer(
+    context: Arc<Context>,
+    step_resource_record_id: Id,
+) -> mpsc::UnboundedSender<StepResLog> {
+    let (sender, mut receiver) = mpsc::unbounded_channel::<StepResLog>();
+    tokio::spawn(async move {
+        let key = live_log_key(step_resource_record_id);
+        while let Some(log) = receiver.recv().await {
+            //把已经积压的日志一起写入，保证日志的先后顺序
+            let mut logs = vec![log];
+            while let Ok(log) = receiver.try_recv() {
+                logs.push(log);
+            }
+            if let Err(err) = push_live_logs(&context, &key, &logs).await {
+                log::error!("写入实时日志失败: {:?}", err);
+            }
+        }
+    });
+    return sender;
+}
+
+async fn push_live_logs(context: &Context, key: &str, logs: &[StepResLog]) -> Result<(), ErrNo> {
+    let items = logs
+        .iter()
+        .map(|log| serde_json::to_string(log).map_err(ErrNo::SerializeError))
+        .collect::<Result<Vec<String>, _>>()?;
+    let mut cache_client = context.get_cache_client().await?;
+    let _: () = redis::pipe()
+        .atomic()
+        .cmd("RPUSH")
+        .arg(key)
+        .arg(&items)
+        .ignore()
+        .cmd("PEXPIRE")
+        .arg(key)
+        .arg(LIVE_LOG_EXPIRE_MILLIS)
+        .ignore()
+        .query_async(&mut cache_client)
+        .await
+        .map_err(|err| ErrNo::CacheOperationError(err.into()))?;
+    return Ok(());
+}
+
+/**
+ * 从redis读取执行中的日志，跳过前offset条已经读过的日志
+ */
+pub async fn read_live_logs(
+    context: &Context,
+    step_resource_record_id: Id,
+    offset: usize,
+) -> Result<Vec<StepResLog>, ErrNo> {
+    let mut cache_client = context.get_cache_client().await?;
+    let items: Vec<String> = redis::cmd("LRANGE")
+        .arg(live_log_key(step_resource_record_id))
+        .arg(offset)
+        .arg(-1)
+        .query_async(&mut cache_client)
+        .await
+        .map_err(|err| ErrNo::CacheOperationError(err.into()))?;
+    return items
+        .iter()
+        .map(|item| serde_json::from_str(item).map_err(ErrNo::DeserializeError))
+        .collect();
+}
+
+/**
+ * 读取日志摘要，旧数据没有摘要时根据数据库里的日志内容生成
+ */
+pub fn read_log_summary(step_resource_record: &JobStepResourceRecord) -> Option<StepResLogSummary> {
+    if let Some(log_summary) = step_resource_record.log_summary.as_ref() {
+        return serde_json::from_str(log_summary).ok();
+    }
+    return step_resource_record
+        .output_content
+        .as_ref()
+        .and_then(|output_content| serde_json::from_str::<Vec<StepResLog>>(output_content).ok())
+        .map(|logs| summarize_logs(&logs));
+}
+
+/**
+ * 删除已经没有记录引用的日志对象和日志文件，返回实际删除的对象数和文件数
+ */
+pub async fn remove_step_resource_logs(
+    context: &Context,
+    log_keys: &[String],
+    log_files: &[String],
+) -> (u64, u64) {
+    let mut object_count = 0;
+    for log_key in log_keys {
+        let result = context
+            .get_oss_client()
+            .delete_object()
+            .bucket(context.get_bucket().to_string())
+            .key(log_key)
+            .send()
+            .await;
+        match result {
+            Ok(_) => object_count += 1,
+            Err(err) => log::error!("删除任务日志{}失败: {:?}", log_key, err),
+        }
+    }
+    let mut file_count = 0;
+    for log_file in log_files {
+        let log_file_path = format!("{}/{}", context.config.job_log_dir, log_file);
+        match tokio::fs::remove_file(&log_file_path).await {
+            Ok(_) => file_count += 1,
+            Err(err) if ErrorKind::NotFound == err.kind() => (),
+            Err(err) => log::error!("删除任务日志文件{}失败: {:?}", log_file_path, err),
+        }
+    }
+    return (object_count, file_count);
+}
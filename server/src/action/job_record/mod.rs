@@ -1,6 +1,19 @@
+pub mod approve_job_record;
+pub mod delete_job_record;
+pub mod download_step_resource_log;
+pub mod log_store;
+pub mod pin_job_record;
+pub mod purge_job_record;
 pub mod query_job_record;
+pub mod query_step_resource_log;
 pub mod read_job_record;
+pub mod reject_job_record;
+pub mod watch_step_resource_log;
 use crate::model::job_record::enums::Status as RecordStatus;
+use crate::model::job_record::enums::TriggerType;
+use crate::model::job_step_record::enums::FailurePolicy;
+use crate::model::job_step_record::enums::RunIf;
+use crate::model::job_step_record::enums::SignoffRole;
 use crate::model::job_step_record::enums::Status as StepRecordStatus;
 use crate::model::job_step_record::enums::StepType;
 use crate::model::job_step_record::JobStepRecord;
@@ -14,6 +27,32 @@ pub fn to_sdk_record_status(val: RecordStatus) -> sdk::job_record::enums::Record
         RecordStatus::Running => sdk::job_record::enums::RecordStatus::Running,
         RecordStatus::Success => sdk::job_record::enums::RecordStatus::Success,
         RecordStatus::Failure => sdk::job_record::enums::RecordStatus::Failure,
+        RecordStatus::Interrupted => sdk::job_record::enums::RecordStatus::Interrupted,
+        RecordStatus::Cancelled => sdk::job_record::enums::RecordStatus::Cancelled,
+        RecordStatus::Queued => sdk::job_record::enums::RecordStatus::Queued,
+        RecordStatus::PendingApproval => sdk::job_record::enums::RecordStatus::PendingApproval,
+        RecordStatus::Rejected => sdk::job_record::enums::RecordStatus::Rejected,
+        RecordStatus::Expired => sdk::job_record::enums::RecordStatus::Expired,
+    }
+}
+
+pub fn to_sdk_trigger_type(val: TriggerType) -> sdk::job_record::enums::TriggerType {
+    match val {
+        TriggerType::Manual => sdk::job_record::enums::TriggerType::Manual,
+        TriggerType::Schedule => sdk::job_record::enums::TriggerType::Schedule,
+        TriggerType::Webhook => sdk::job_record::enums::TriggerType::Webhook,
+        TriggerType::Retry => sdk::job_record::enums::TriggerType::Retry,
+        TriggerType::ApiToken => sdk::job_record::enums::TriggerType::ApiToken,
+    }
+}
+
+pub fn from_sdk_trigger_type(val: sdk::job_record::enums::TriggerType) -> TriggerType {
+    match val {
+        sdk::job_record::enums::TriggerType::Manual => TriggerType::Manual,
+        sdk::job_record::enums::TriggerType::Schedule => TriggerType::Schedule,
+        sdk::job_record::enums::TriggerType::Webhook => TriggerType::Webhook,
+        sdk::job_record::enums::TriggerType::Retry => TriggerType::Retry,
+        sdk::job_record::enums::TriggerType::ApiToken => TriggerType::ApiToken,
     }
 }
 
@@ -23,10 +62,13 @@ fn to_sdk_step_record_status(val: StepRecordStatus) -> sdk::job_record::enums::S
         StepRecordStatus::Running => sdk::job_record::enums::StepRecordStatus::Running,
         StepRecordStatus::Success => sdk::job_record::enums::StepRecordStatus::Success,
         StepRecordStatus::Failure => sdk::job_record::enums::StepRecordStatus::Failure,
+        StepRecordStatus::Interrupted => sdk::job_record::enums::StepRecordStatus::Interrupted,
+        StepRecordStatus::Cancelled => sdk::job_record::enums::StepRecordStatus::Cancelled,
+        StepRecordStatus::Skipped => sdk::job_record::enums::StepRecordStatus::Skipped,
     }
 }
 
-fn to_sdk_step_resource_record_status(
+pub fn to_sdk_step_resource_record_status(
     val: StepResourceRecordStatus,
 ) -> sdk::job_record::enums::StepResourceRecordStatus {
     match val {
@@ -42,6 +84,38 @@ fn to_sdk_step_resource_record_status(
         StepResourceRecordStatus::Failure => {
             sdk::job_record::enums::StepResourceRecordStatus::Failure
         }
+        StepResourceRecordStatus::Interrupted => {
+            sdk::job_record::enums::StepResourceRecordStatus::Interrupted
+        }
+        StepResourceRecordStatus::Cancelled => {
+            sdk::job_record::enums::StepResourceRecordStatus::Cancelled
+        }
+        StepResourceRecordStatus::Skipped => {
+            sdk::job_record::enums::StepResourceRecordStatus::Skipped
+        }
+    }
+}
+
+fn to_sdk_failure_policy(val: FailurePolicy) -> sdk::job::enums::FailurePolicy {
+    match val {
+        FailurePolicy::Continue => sdk::job::enums::FailurePolicy::Continue,
+        FailurePolicy::Stop => sdk::job::enums::FailurePolicy::Stop,
+    }
+}
+
+fn to_sdk_run_if(val: RunIf) -> sdk::job::enums::RunIf {
+    match val {
+        RunIf::OnSuccess => sdk::job::enums::RunIf::OnSuccess,
+        RunIf::OnFailure => sdk::job::enums::RunIf::OnFailure,
+        RunIf::Always => sdk::job::enums::RunIf::Always,
+    }
+}
+
+fn to_sdk_signoff_role(val: SignoffRole) -> sdk::job::enums::SignoffRole {
+    match val {
+        SignoffRole::Owner => sdk::job::enums::SignoffRole::Owner,
+        SignoffRole::Admin => sdk::job::enums::SignoffRole::Admin,
+        SignoffRole::Operator => sdk::job::enums::SignoffRole::Operator,
     }
 }
 
@@ -0,0 +1,46 @@
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::job_record::JobRecordOpt;
+use crate::model::job_record::JobRecordProperty;
+use crate::sdk;
+use crate::service::base::JobRecordBaseService;
+use chrono::Utc;
+use sdk::job_record::pin_job_record::PinJobRecordReq;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+//置顶的执行记录不会被保留策略清理
+pub async fn pin_job_record(
+    org_id: Id,
+    _user: User,
+    pin_job_record_req: PinJobRecordReq,
+) -> Result<(), ErrNo> {
+    let PinJobRecordReq { id, pinned } = pin_job_record_req;
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_record_base_service = JobRecordBaseService::new(&transaction);
+    job_record_base_service
+        .query_job_record_one(&JobRecordOpt {
+            org_id: Some(org_id),
+            id: Some(id),
+            ..JobRecordOpt::empty()
+        })
+        .await?
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("该执行记录不存在")))?;
+    let changes: Vec<JobRecordProperty> = vec![
+        JobRecordProperty::Pinned(pinned),
+        JobRecordProperty::LastModifiedTime(Utc::now()),
+    ];
+    job_record_base_service
+        .update_job_record(id, &changes)
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(());
+}
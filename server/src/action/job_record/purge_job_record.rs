@@ -0,0 +1,253 @@
+use super::log_store::remove_step_resource_logs;
+use crate::context::Context;
+use crate::model::job_record_approval::JobRecordApprovalOpt;
+use crate::model::job_step_record::JobStepRecordOpt;
+use crate::model::job_step_resource_record::JobStepResourceRecordOpt;
+use crate::model::job_step_signoff::JobStepSignoffOpt;
+use crate::model::organization::Organization;
+use crate::model::organization::OrganizationProperty;
+use crate::sdk;
+use crate::service::base::JobRecordApprovalBaseService;
+use crate::service::base::JobRecordBaseService;
+use crate::service::base::JobStepRecordBaseService;
+use crate::service::base::JobStepResourceRecordBaseService;
+use crate::service::base::JobStepSignoffBaseService;
+use crate::service::base::OrganizationBaseService;
+use crate::service::job_record::JobRecordService;
+use chrono::Duration;
+use chrono::Utc;
+use sdk::organization::JobRecordPurgeReport;
+use std::collections::HashSet;
+use tihu::Id;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+use tokio_postgres::Transaction;
+
+//每个事务最多清理的执行记录数，避免长时间锁表
+const PURGE_BATCH_SIZE: u64 = 100;
+
+/**
+ * 删除执行记录的结果，日志需要在事务提交之后再删除
+ */
+pub struct RemovedJobRecords {
+    pub report: JobRecordPurgeReport,
+    pub log_keys: Vec<String>,
+    pub log_files: Vec<String>,
+}
+
+/**
+ * 删除执行记录以及下面的审批记录、步骤执行记录、签收记录、资源执行记录，同时找出已经没有记录引用的日志
+ */
+pub async fn delete_job_records(
+    transaction: &Transaction<'_>,
+    org_id: Id,
+    record_ids: &[Id],
+) -> Result<RemovedJobRecords, ErrNo> {
+    let job_record_base_service = JobRecordBaseService::new(transaction);
+    let job_step_record_base_service = JobStepRecordBaseService::new(transaction);
+    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(transaction);
+    let job_record_approval_base_service = JobRecordApprovalBaseService::new(transaction);
+    let job_step_signoff_base_service = JobStepSignoffBaseService::new(transaction);
+    let mut job_record_approval_ids = Vec::new();
+    let mut job_step_record_ids = Vec::new();
+    let mut job_step_signoff_ids = Vec::new();
+    let mut job_step_resource_record_ids = Vec::new();
+    let mut log_keys = HashSet::new();
+    let mut log_files = HashSet::new();
+    for record_id in record_ids {
+        let job_record_approval_list = job_record_approval_base_service
+            .query_job_record_approval_batch(&JobRecordApprovalOpt {
+                org_id: Some(org_id),
+                record_id: Some(*record_id),
+                ..JobRecordApprovalOpt::empty()
+            })
+            .await?;
+        job_record_approval_ids.extend(
+            job_record_approval_list
+                .into_iter()
+                .map(|job_record_approval| job_record_approval.id),
+        );
+        let job_step_record_list = job_step_record_base_service
+            .query_job_step_record_batch(&JobStepRecordOpt {
+                org_id: Some(org_id),
+                record_id: Some(*record_id),
+                ..JobStepRecordOpt::empty()
+            })
+            .await?;
+        job_step_record_ids.extend(
+            job_step_record_list
+                .into_iter()
+                .map(|job_step_record| job_step_record.id),
+        );
+        let job_step_signoff_list = job_step_signoff_base_service
+            .query_job_step_signoff_batch(&JobStepSignoffOpt {
+                org_id: Some(org_id),
+                record_id: Some(*record_id),
+                ..JobStepSignoffOpt::empty()
+            })
+            .await?;
+        job_step_signoff_ids.extend(
+            job_step_signoff_list
+                .into_iter()
+                .map(|job_step_signoff| job_step_signoff.id),
+        );
+        let job_step_resource_record_list = job_step_resource_record_base_service
+            .query_job_step_resource_record_batch(&JobStepResourceRecordOpt {
+                org_id: Some(org_id),
+                record_id: Some(*record_id),
+                ..JobStepResourceRecordOpt::empty()
+            })
+            .await?;
+        for job_step_resource_record in job_step_resource_record_list {
+            job_step_resource_record_ids.push(job_step_resource_record.id);
+            log_keys.extend(job_step_resource_record.log_key);
+            log_files.extend(job_step_resource_record.output_file);
+        }
+    }
+    if !record_ids.is_empty() {
+        job_record_base_service
+            .delete_job_record_batch(record_ids)
+            .await?;
+    }
+    if !job_record_approval_ids.is_empty() {
+        job_record_approval_base_service
+            .delete_job_record_approval_batch(&job_record_approval_ids)
+            .await?;
+    }
+    if !job_step_record_ids.is_empty() {
+        job_step_record_base_service
+            .delete_job_step_record_batch(&job_step_record_ids)
+            .await?;
+    }
+    if !job_step_signoff_ids.is_empty() {
+        job_step_signoff_base_service
+            .delete_job_step_signoff_batch(&job_step_signoff_ids)
+            .await?;
+    }
+    if !job_step_resource_record_ids.is_empty() {
+        job_step_resource_record_base_service
+            .delete_job_step_resource_record_batch(&job_step_resource_record_ids)
+            .await?;
+    }
+    //重新执行时跳过的资源会沿用原来的日志，还有别的记录引用的日志不能删除
+    let mut unused_log_keys = Vec::new();
+    for log_key in log_keys {
+        let count = job_step_resource_record_base_service
+            .query_job_step_resource_record_count(&JobStepResourceRecordOpt {
+                org_id: Some(org_id),
+                log_key: Some(log_key.clone()),
+                ..JobStepResourceRecordOpt::empty()
+            })
+            .await?;
+        if 0 == count {
+            unused_log_keys.push(log_key);
+        }
+    }
+    let mut unused_log_files = Vec::new();
+    for log_file in log_files {
+        let count = job_step_resource_record_base_service
+            .query_job_step_resource_record_count(&JobStepResourceRecordOpt {
+                org_id: Some(org_id),
+                output_file: Some(log_file.clone()),
+                ..JobStepResourceRecordOpt::empty()
+            })
+            .await?;
+        if 0 == count {
+            unused_log_files.push(log_file);
+        }
+    }
+    return Ok(RemovedJobRecords {
+        report: JobRecordPurgeReport {
+            record_count: record_ids.len() as u64,
+            step_record_count: job_step_record_ids.len() as u64,
+            step_resource_record_count: job_step_resource_record_ids.len() as u64,
+            log_object_count: 0,
+            log_file_count: 0,
+        },
+        log_keys: unused_log_keys,
+        log_files: unused_log_files,
+    });
+}
+
+/**
+ * 在事务提交之后删除日志，并把删除的数量记到清理结果里
+ */
+pub async fn remove_job_record_logs(
+    context: &Context,
+    removed: RemovedJobRecords,
+) -> JobRecordPurgeReport {
+    let RemovedJobRecords {
+        mut report,
+        log_keys,
+        log_files,
+    } = removed;
+    let (log_object_count, log_file_count) =
+        remove_step_resource_logs(context, &log_keys, &log_files).await;
+    report.log_object_count = log_object_count;
+    report.log_file_count = log_file_count;
+    return report;
+}
+
+/**
+ * 按照组织的保留策略清理过期的执行记录，没有设置保留策略时不清理
+ */
+pub async fn purge_job_records(
+    context: &Context,
+    organization: &Organization,
+) -> Result<Option<JobRecordPurgeReport>, ErrNo> {
+    if organization.retention_count.is_none() && organization.retention_days.is_none() {
+        return Ok(None);
+    }
+    let retention_count = organization.retention_count.map(|count| count as i64);
+    let expire_before = organization
+        .retention_days
+        .map(|days| Utc::now() - Duration::days(days as i64));
+    let mut total = JobRecordPurgeReport::default();
+    loop {
+        let mut client = context.get_db_client().await?;
+        let transaction = client.transaction().await.map_err(open_transaction_error)?;
+        let job_record_service = JobRecordService::new(&transaction);
+        let record_ids = job_record_service
+            .query_expired_job_record_ids(
+                &organization.id,
+                retention_count.as_ref(),
+                expire_before.as_ref(),
+                PURGE_BATCH_SIZE,
+            )
+            .await?;
+        if record_ids.is_empty() {
+            break;
+        }
+        let removed = delete_job_records(&transaction, organization.id, &record_ids).await?;
+        transaction
+            .commit()
+            .await
+            .map_err(commit_transaction_error)?;
+        let report = remove_job_record_logs(context, removed).await;
+        total.record_count += report.record_count;
+        total.step_record_count += report.step_record_count;
+        total.step_resource_record_count += report.step_resource_record_count;
+        total.log_object_count += report.log_object_count;
+        total.log_file_count += report.log_file_count;
+        if (record_ids.len() as u64) < PURGE_BATCH_SIZE {
+            break;
+        }
+    }
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let organization_base_service = OrganizationBaseService::new(&transaction);
+    let last_purge_report = serde_json::to_string(&total).map_err(ErrNo::SerializeError)?;
+    let changes: Vec<OrganizationProperty> = vec![
+        OrganizationProperty::LastPurgeTime(Some(Utc::now())),
+        OrganizationProperty::LastPurgeReport(Some(last_purge_report)),
+    ];
+    organization_base_service
+        .update_organization(organization.id, &changes)
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(Some(total));
+}
@@ -1,3 +1,5 @@
+use super::from_sdk_trigger_type;
+use super::to_sdk_trigger_type;
 use crate::get_context;
 use crate::middleware::auth::User;
 use crate::model::job_record::enums::Status;
@@ -7,6 +9,7 @@ use crate::sdk;
 use crate::service::base::EnvironmentBaseService;
 use crate::service::base::JobBaseService;
 use crate::service::base::JobRecordBaseService;
+use crate::service::base::UserBaseService;
 use crate::service::job_record::JobRecordService;
 use sdk::job_record::query_job_record::QueryJobRecordReq;
 use std::collections::HashMap;
@@ -22,6 +25,12 @@ fn from_sdk_status(val: sdk::job_record::enums::RecordStatus) -> Status {
         sdk::job_record::enums::RecordStatus::Running => Status::Running,
         sdk::job_record::enums::RecordStatus::Success => Status::Success,
         sdk::job_record::enums::RecordStatus::Failure => Status::Failure,
+        sdk::job_record::enums::RecordStatus::Interrupted => Status::Interrupted,
+        sdk::job_record::enums::RecordStatus::Cancelled => Status::Cancelled,
+        sdk::job_record::enums::RecordStatus::Queued => Status::Queued,
+        sdk::job_record::enums::RecordStatus::PendingApproval => Status::PendingApproval,
+        sdk::job_record::enums::RecordStatus::Rejected => Status::Rejected,
+        sdk::job_record::enums::RecordStatus::Expired => Status::Expired,
     }
 }
 fn to_sdk_status(val: Status) -> sdk::job_record::enums::RecordStatus {
@@ -29,6 +38,12 @@ fn to_sdk_status(val: Status) -> sdk::job_record::enums::RecordStatus {
         Status::Running => sdk::job_record::enums::RecordStatus::Running,
         Status::Success => sdk::job_record::enums::RecordStatus::Success,
         Status::Failure => sdk::job_record::enums::RecordStatus::Failure,
+        Status::Interrupted => sdk::job_record::enums::RecordStatus::Interrupted,
+        Status::Cancelled => sdk::job_record::enums::RecordStatus::Cancelled,
+        Status::Queued => sdk::job_record::enums::RecordStatus::Queued,
+        Status::PendingApproval => sdk::job_record::enums::RecordStatus::PendingApproval,
+        Status::Rejected => sdk::job_record::enums::RecordStatus::Rejected,
+        Status::Expired => sdk::job_record::enums::RecordStatus::Expired,
     }
 }
 
@@ -41,6 +56,8 @@ pub async fn query_job_record(
         job_id,
         environment_id,
         status,
+        created_by,
+        trigger_type,
         page_no,
         page_size,
     } = query_job_record_req;
@@ -49,6 +66,8 @@ pub async fn query_job_record(
         job_id: job_id.map(|v| v.into()),
         environment_id: environment_id.map(|v| v.into()),
         status: status.map(from_sdk_status),
+        created_by: created_by,
+        trigger_type: trigger_type.map(from_sdk_trigger_type),
         ..JobRecordOpt::empty()
     };
     let context = get_context()?;
@@ -58,6 +77,7 @@ pub async fn query_job_record(
     let job_record_service = JobRecordService::new(&transaction);
     let job_base_service = JobBaseService::new(&transaction);
     let environment_base_service = EnvironmentBaseService::new(&transaction);
+    let user_base_service = UserBaseService::new(&transaction);
     let count = job_record_base_service
         .query_job_record_count(&params)
         .await?;
@@ -84,6 +104,32 @@ pub async fn query_job_record(
         .into_iter()
         .map(|environment| (environment.id, environment.name))
         .collect();
+    let user_ids: HashSet<_> = job_record_list
+        .iter()
+        .filter_map(|job_record| job_record.created_by)
+        .collect();
+    let user_map: HashMap<_, _> = if user_ids.is_empty() {
+        HashMap::new()
+    } else {
+        let user_ids: Vec<_> = user_ids.into_iter().collect();
+        user_base_service
+            .read_user_batch(&user_ids)
+            .await?
+            .into_iter()
+            .map(|user| (user.id, user.name))
+            .collect()
+    };
+    //排队中的记录按照进入队列的先后计算位置
+    let queued_ids: Vec<Id> = job_record_list
+        .iter()
+        .filter(|job_record| Status::Queued == job_record.status)
+        .map(|job_record| job_record.id)
+        .collect();
+    let queue_position_map: HashMap<Id, u64> = job_record_service
+        .query_queue_positions(&org_id, &queued_ids)
+        .await?
+        .into_iter()
+        .collect();
     let list = job_record_list
         .into_iter()
         .map(
@@ -91,7 +137,12 @@ pub async fn query_job_record(
                  id,
                  job_id,
                  environment_id,
+                 attempt,
                  status,
+                 pinned,
+                 created_by,
+                 trigger_type,
+                 change_note,
                  created_time,
                  last_modified_time,
                  ..
@@ -104,7 +155,17 @@ pub async fn query_job_record(
                     environment_name: environment_map
                         .get(&environment_id)
                         .map(|name| name.clone()),
+                    attempt: attempt,
                     status: to_sdk_status(status),
+                    queue_position: queue_position_map.get(&id).cloned(),
+                    pinned: pinned,
+                    created_by: created_by,
+                    created_by_name: created_by
+                        .as_ref()
+                        .and_then(|created_by| user_map.get(created_by))
+                        .map(|name| name.clone()),
+                    trigger_type: to_sdk_trigger_type(trigger_type),
+                    change_note: change_note,
                     created_time: created_time.into(),
                     last_modified_time: last_modified_time.into(),
                 }
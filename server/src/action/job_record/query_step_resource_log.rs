@@ -0,0 +1,55 @@
+use super::log_store::load_step_resource_log;
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::job_step_resource_record::JobStepResourceRecordOpt;
+use crate::sdk;
+use crate::service::base::JobStepResourceRecordBaseService;
+use sdk::job_record::query_step_resource_log::QueryStepResourceLogReq;
+use sdk::job_record::query_step_resource_log::QueryStepResourceLogResp;
+use tihu::pagination::PaginationList;
+use tihu::Id;
+use tihu::LightString;
+use tihu::Pagination;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+pub async fn query_step_resource_log(
+    org_id: Id,
+    _user: User,
+    query_step_resource_log_req: QueryStepResourceLogReq,
+) -> Result<QueryStepResourceLogResp, ErrNo> {
+    let QueryStepResourceLogReq {
+        id,
+        level,
+        page_no,
+        page_size,
+    } = query_step_resource_log_req;
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
+    let step_resource_record = job_step_resource_record_base_service
+        .query_job_step_resource_record_one(&JobStepResourceRecordOpt {
+            org_id: Some(org_id),
+            id: Some(id),
+            ..JobStepResourceRecordOpt::empty()
+        })
+        .await?
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("该资源执行记录不存在")))?;
+    let mut logs = load_step_resource_log(&context, &step_resource_record).await?;
+    //日志级别数值越小越严重
+    if let Some(level) = level {
+        logs.retain(|log| log.level <= level);
+    }
+    let pagination = Pagination::new(logs.len() as u64, page_no.unwrap_or(1), page_size, None);
+    let skip = (pagination.page_no.max(1) - 1) * pagination.page_size;
+    let list = logs
+        .into_iter()
+        .skip(skip as usize)
+        .take(pagination.page_size as usize)
+        .collect();
+    return Ok(PaginationList {
+        pagination: pagination,
+        list: list,
+    });
+}
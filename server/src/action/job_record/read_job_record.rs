@@ -1,27 +1,50 @@
+use super::super::job::graph::parse_depends_on;
+use super::super::job::parse_record_params;
+use super::super::job::signoff::parse_signoff_users;
+use super::super::job::template::render_operation_parameter;
+use super::log_store::read_log_summary;
 use super::merge_step_and_resource_record;
+use super::to_sdk_failure_policy;
 use super::to_sdk_record_status;
+use super::to_sdk_run_if;
+use super::to_sdk_signoff_role;
 use super::to_sdk_step_record_status;
 use super::to_sdk_step_resource_record_status;
+use super::to_sdk_trigger_type;
 use crate::get_context;
 use crate::middleware::auth::User;
 use crate::model::environment::EnvironmentOpt;
 use crate::model::job::JobOpt;
 use crate::model::job_record::JobRecordOpt;
+use crate::model::job_record_approval::JobRecordApprovalOpt;
 use crate::model::job_step_record::JobStepRecordOpt;
 use crate::model::job_step_resource_record::JobStepResourceRecordOpt;
+use crate::model::job_step_signoff::JobStepSignoffOpt;
 use crate::sdk;
 use crate::service::base::EnvironmentBaseService;
 use crate::service::base::JobBaseService;
+use crate::service::base::JobRecordApprovalBaseService;
 use crate::service::base::JobRecordBaseService;
 use crate::service::base::JobStepRecordBaseService;
 use crate::service::base::JobStepResourceRecordBaseService;
+use crate::service::base::JobStepSignoffBaseService;
+use crate::service::base::UserBaseService;
+use crate::service::job_record::JobRecordService;
+use sdk::job_record::enums::ApprovalDecision;
 use sdk::job_record::enums::StepType;
 use sdk::job_record::read_job_record::JobRecord;
+use sdk::job_record::read_job_record::JobRecordApproval;
+use sdk::job_record::read_job_record::JobRecordAttempt;
 use sdk::job_record::read_job_record::JobStepRecord;
 use sdk::job_record::read_job_record::JobStepResourceRecord;
+use sdk::job_record::read_job_record::JobStepSignoff;
 use sdk::job_record::read_job_record::ReadJobRecordReq;
 use sdk::job_record::read_job_record::ReadJobRecordResp;
 use sdk::job_record::read_job_record::StepRecord;
+use serde_json::Map;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use tihu::Id;
 use tihu::LightString;
 use tihu_native::errno::open_transaction_error;
@@ -34,8 +57,49 @@ fn to_sdk_step_type(val: crate::model::job_step_record::enums::StepType) -> Step
     }
 }
 
-fn to_sdk_step_record(step_record: crate::model::job_step_record::JobStepRecord) -> JobStepRecord {
-    JobStepRecord {
+fn to_sdk_approval_decision(
+    val: crate::model::job_record_approval::enums::Decision,
+) -> ApprovalDecision {
+    match val {
+        crate::model::job_record_approval::enums::Decision::Approve => ApprovalDecision::Approve,
+        crate::model::job_record_approval::enums::Decision::Reject => ApprovalDecision::Reject,
+    }
+}
+
+fn to_sdk_step_record(
+    step_record: crate::model::job_step_record::JobStepRecord,
+    params: &Map<String, Value>,
+    signoff_map: &mut HashMap<Id, Vec<crate::model::job_step_signoff::JobStepSignoff>>,
+    user_map: &HashMap<Id, String>,
+) -> Result<JobStepRecord, ErrNo> {
+    //已经执行的步骤返回执行时渲染的操作参数，还没有执行的步骤用保存的执行参数渲染，密码类型的参数是掩码，引用步骤输出的占位符保持原样
+    let operation_parameter = if let Some(rendered_parameter) = step_record.rendered_parameter {
+        rendered_parameter
+    } else if step_record.operation_parameter.is_empty() {
+        step_record.operation_parameter
+    } else {
+        render_operation_parameter(
+            &step_record.step_name,
+            &step_record.operation_parameter,
+            params,
+        )
+        .unwrap_or(step_record.operation_parameter)
+    };
+    let signoff_list = signoff_map
+        .remove(&step_record.id)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|job_step_signoff| JobStepSignoff {
+            id: job_step_signoff.id,
+            user_id: job_step_signoff.user_id,
+            user_name: user_map.get(&job_step_signoff.user_id).cloned(),
+            success: job_step_signoff.success,
+            comment: job_step_signoff.comment,
+            attachments: job_step_signoff.attachments,
+            created_time: job_step_signoff.created_time,
+        })
+        .collect();
+    Ok(JobStepRecord {
         id: step_record.id.into(),
         record_id: step_record.record_id.into(),
         job_step_id: step_record.job_step_id.into(),
@@ -44,13 +108,34 @@ fn to_sdk_step_record(step_record: crate::model::job_step_record::JobStepRecord)
         step_remark: step_record.step_remark,
         extension_id: step_record.extension_id,
         operation_id: step_record.operation_id,
-        operation_parameter: step_record.operation_parameter,
+        operation_parameter: operation_parameter,
         attachments: step_record.attachments,
+        signoff_role: step_record.signoff_role.map(to_sdk_signoff_role),
+        signoff_user_list: parse_signoff_users(step_record.signoff_users.as_deref())?,
+        required_signoffs: step_record.required_signoffs,
+        signoff_list: signoff_list,
+        timeout_seconds: step_record.timeout_seconds,
+        max_retries: step_record.max_retries,
+        retry_backoff: step_record.retry_backoff,
+        max_parallel: step_record.max_parallel,
+        batch_size: step_record.batch_size,
+        pause_between_batches: step_record.pause_between_batches,
+        failure_policy: to_sdk_failure_policy(step_record.failure_policy),
+        run_if: to_sdk_run_if(step_record.run_if),
+        run_expression: step_record.run_expression,
+        skip_reason: step_record.skip_reason,
         job_step_seq: step_record.job_step_seq,
+        depends_on: parse_depends_on(step_record.depends_on.as_deref())?,
         status: to_sdk_step_record_status(step_record.status),
+        completed_by: step_record.completed_by,
+        completed_by_name: step_record
+            .completed_by
+            .as_ref()
+            .and_then(|completed_by| user_map.get(completed_by))
+            .map(|name| name.clone()),
         created_time: step_record.created_time,
         last_modified_time: step_record.last_modified_time,
-    }
+    })
 }
 
 fn to_sdk_step_resource_record(
@@ -62,8 +147,9 @@ fn to_sdk_step_resource_record(
         job_step_record_id: step_resource_record.job_step_record_id.into(),
         environment_resource_id: step_resource_record.environment_resource_id.into(),
         resource_name: step_resource_record.resource_name,
-        extension_configuration: step_resource_record.extension_configuration,
-        output: step_resource_record.output_content,
+        log_summary: read_log_summary(&step_resource_record),
+        outputs: step_resource_record.outputs,
+        attempts: step_resource_record.attempts,
         status: to_sdk_step_resource_record_status(step_resource_record.status),
         created_time: step_resource_record.created_time,
         last_modified_time: step_resource_record.last_modified_time,
@@ -85,6 +171,10 @@ pub async fn read_job_record(
     let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
     let job_base_service = JobBaseService::new(&transaction);
     let environment_base_service = EnvironmentBaseService::new(&transaction);
+    let job_record_service = JobRecordService::new(&transaction);
+    let user_base_service = UserBaseService::new(&transaction);
+    let job_record_approval_base_service = JobRecordApprovalBaseService::new(&transaction);
+    let job_step_signoff_base_service = JobStepSignoffBaseService::new(&transaction);
     let params = JobRecordOpt {
         org_id: Some(org_id),
         id: Some(record_id),
@@ -126,6 +216,79 @@ pub async fn read_job_record(
             ..EnvironmentOpt::empty()
         })
         .await?;
+    //查询同一次执行的所有尝试
+    let attempt_list = job_record_service
+        .query_job_record_attempts(
+            &org_id,
+            &job_record.origin_record_id.unwrap_or(job_record.id),
+        )
+        .await?
+        .into_iter()
+        .map(|attempt| JobRecordAttempt {
+            id: attempt.id,
+            attempt: attempt.attempt,
+            status: to_sdk_record_status(attempt.status),
+            created_time: attempt.created_time,
+        })
+        .collect();
+    //执行之前的审批记录
+    let mut job_record_approval_list = job_record_approval_base_service
+        .query_job_record_approval_batch(&JobRecordApprovalOpt {
+            org_id: Some(org_id),
+            record_id: Some(record_id),
+            ..JobRecordApprovalOpt::empty()
+        })
+        .await?;
+    job_record_approval_list.sort_by_key(|job_record_approval| job_record_approval.created_time);
+    //手动步骤的签收记录
+    let mut job_step_signoff_list = job_step_signoff_base_service
+        .query_job_step_signoff_batch(&JobStepSignoffOpt {
+            org_id: Some(org_id),
+            record_id: Some(record_id),
+            ..JobStepSignoffOpt::empty()
+        })
+        .await?;
+    job_step_signoff_list.sort_by_key(|job_step_signoff| job_step_signoff.created_time);
+    //发起执行的人、审批的人、签收的人和处理手动步骤的人
+    let user_ids: HashSet<Id> = job_record
+        .created_by
+        .iter()
+        .chain(
+            job_record_approval_list
+                .iter()
+                .map(|job_record_approval| &job_record_approval.approver_id),
+        )
+        .chain(
+            job_step_signoff_list
+                .iter()
+                .map(|job_step_signoff| &job_step_signoff.user_id),
+        )
+        .chain(
+            job_step_record_list
+                .iter()
+                .filter_map(|job_step_record| job_step_record.completed_by.as_ref()),
+        )
+        .cloned()
+        .collect();
+    let user_map: HashMap<Id, String> = if user_ids.is_empty() {
+        HashMap::new()
+    } else {
+        let user_ids: Vec<_> = user_ids.into_iter().collect();
+        user_base_service
+            .read_user_batch(&user_ids)
+            .await?
+            .into_iter()
+            .map(|user| (user.id, user.name))
+            .collect()
+    };
+    let mut signoff_map: HashMap<Id, Vec<_>> = HashMap::new();
+    for job_step_signoff in job_step_signoff_list {
+        signoff_map
+            .entry(job_step_signoff.job_step_record_id)
+            .or_default()
+            .push(job_step_signoff);
+    }
+    let record_params = parse_record_params(job_record.params.as_deref())?;
     let job_step_list =
         merge_step_and_resource_record(job_step_record_list, job_step_resource_record_list);
     let mut step_record_list = Vec::with_capacity(job_step_list.len());
@@ -136,32 +299,27 @@ pub async fn read_job_record(
                 step_resource_record_list,
             } => {
                 let mut list = Vec::with_capacity(step_resource_record_list.len());
-                for mut step_resource_record in step_resource_record_list {
-                    if step_resource_record.output_content.is_none() {
-                        if let Some(output_file) = step_resource_record.output_file.as_ref() {
-                            let log_file_path =
-                                format!("{}/{}", context.config.job_log_dir, output_file);
-                            if let Ok(mut content) = tokio::fs::read_to_string(log_file_path).await
-                            {
-                                if content.ends_with(",") {
-                                    content.pop();
-                                }
-                                step_resource_record
-                                    .output_content
-                                    .replace(format!("[{}]", content));
-                            }
-                        }
-                    }
+                for step_resource_record in step_resource_record_list {
                     list.push(to_sdk_step_resource_record(step_resource_record));
                 }
                 step_record_list.push(StepRecord::Auto {
-                    job_step_record: to_sdk_step_record(job_step_record),
+                    job_step_record: to_sdk_step_record(
+                        job_step_record,
+                        &record_params,
+                        &mut signoff_map,
+                        &user_map,
+                    )?,
                     step_resource_record_list: list,
                 });
             }
             super::StepRecord::Manual { job_step_record } => {
                 step_record_list.push(StepRecord::Manual {
-                    job_step_record: to_sdk_step_record(job_step_record),
+                    job_step_record: to_sdk_step_record(
+                        job_step_record,
+                        &record_params,
+                        &mut signoff_map,
+                        &user_map,
+                    )?,
                 });
             }
         }
@@ -172,7 +330,30 @@ pub async fn read_job_record(
         job_name: job_opt.map(|job| job.name),
         environment_id: job_record.environment_id.into(),
         environment_name: environment_opt.map(|environment| environment.name),
+        attempt: job_record.attempt,
+        attempt_list: attempt_list,
         status: to_sdk_record_status(job_record.status),
+        pinned: job_record.pinned,
+        created_by: job_record.created_by,
+        created_by_name: job_record
+            .created_by
+            .as_ref()
+            .and_then(|created_by| user_map.get(created_by))
+            .map(|name| name.clone()),
+        trigger_type: to_sdk_trigger_type(job_record.trigger_type),
+        change_note: job_record.change_note,
+        required_approvals: job_record.required_approvals,
+        approval_list: job_record_approval_list
+            .into_iter()
+            .map(|job_record_approval| JobRecordApproval {
+                id: job_record_approval.id,
+                approver_id: job_record_approval.approver_id,
+                approver_name: user_map.get(&job_record_approval.approver_id).cloned(),
+                decision: to_sdk_approval_decision(job_record_approval.decision),
+                comment: job_record_approval.comment,
+                created_time: job_record_approval.created_time,
+            })
+            .collect(),
         step_record_list: step_record_list,
         created_time: job_record.created_time,
         last_modified_time: job_record.last_modified_time,
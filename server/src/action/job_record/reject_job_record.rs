@@ -0,0 +1,17 @@
+use super::super::job::approval::decide_job_record;
+use crate::middleware::auth::User;
+use crate::model::job_record_approval::enums::Decision;
+use crate::sdk;
+use sdk::job_record::reject_job_record::RejectJobRecordReq;
+use sdk::job_record::reject_job_record::RejectJobRecordResp;
+use tihu::Id;
+use tihu_native::ErrNo;
+
+pub async fn reject_job_record(
+    org_id: Id,
+    user: User,
+    reject_job_record_req: RejectJobRecordReq,
+) -> Result<RejectJobRecordResp, ErrNo> {
+    let RejectJobRecordReq { record_id, comment } = reject_job_record_req;
+    return decide_job_record(org_id, user, record_id, Decision::Reject, comment).await;
+}
@@ -0,0 +1,71 @@
+use crate::get_context;
+use crate::log;
+use crate::middleware::auth::User;
+use crate::model::job_step_resource_record::JobStepResourceRecordOpt;
+use crate::native_common;
+use crate::sdk;
+use crate::service::base::JobStepResourceRecordBaseService;
+use native_common::cache::AsyncCache;
+use native_common::cache::EliminateType;
+use sdk::job_record::watch_step_resource_log::WatchStepResourceLogReq;
+use sdk::job_record::watch_step_resource_log::WatchStepResourceLogResp;
+use serde::{Deserialize, Serialize};
+use tihu::base62;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+use uuid::Uuid;
+
+pub const LOG_STREAM_TOKEN_PREFIX: &str = "step-resource-log-";
+//订阅凭证的有效期（毫秒），浏览器断线重连时还会再用到
+const LOG_STREAM_TOKEN_EXPIRE_MILLIS: u64 = 60 * 1000;
+
+/**
+ * 订阅凭证对应的资源执行记录
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct LogStreamTicket {
+    pub org_id: Id,
+    pub step_resource_record_id: Id,
+}
+
+//EventSource不能带签名请求头，所以先通过已登录的接口换取一个短期凭证，再用凭证订阅实时日志
+pub async fn watch_step_resource_log(
+    org_id: Id,
+    _user: User,
+    watch_step_resource_log_req: WatchStepResourceLogReq,
+) -> Result<WatchStepResourceLogResp, ErrNo> {
+    let WatchStepResourceLogReq { id } = watch_step_resource_log_req;
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
+    job_step_resource_record_base_service
+        .query_job_step_resource_record_one(&JobStepResourceRecordOpt {
+            org_id: Some(org_id),
+            id: Some(id),
+            ..JobStepResourceRecordOpt::empty()
+        })
+        .await?
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("该资源执行记录不存在")))?;
+    let ticket = LogStreamTicket {
+        org_id: org_id,
+        step_resource_record_id: id,
+    };
+    let ticket = serde_json::to_vec(&ticket).map_err(ErrNo::SerializeError)?;
+    let token = base62::encode(&Uuid::new_v4().as_u128().to_be_bytes());
+    let cache_mgr = context.get_cache_mgr().await?;
+    cache_mgr
+        .set(
+            &(String::from(LOG_STREAM_TOKEN_PREFIX) + &token).into_bytes(),
+            &ticket,
+            EliminateType::Expire(LOG_STREAM_TOKEN_EXPIRE_MILLIS),
+        )
+        .await
+        .map_err(|err| {
+            log::error!("缓存实时日志的订阅凭证失败: {:?}", err);
+            err
+        })?;
+    return Ok(WatchStepResourceLogResp { token: token });
+}
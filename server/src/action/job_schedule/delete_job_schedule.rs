@@ -0,0 +1,42 @@
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::job_schedule::JobScheduleOpt;
+use crate::sdk;
+use crate::service::base::JobScheduleBaseService;
+use sdk::job_schedule::delete_job_schedule::DeleteJobScheduleReq;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+pub async fn delete_job_schedule(
+    org_id: Id,
+    _user: User,
+    delete_job_schedule_req: DeleteJobScheduleReq,
+) -> Result<(), ErrNo> {
+    let DeleteJobScheduleReq { id } = delete_job_schedule_req;
+    let job_schedule_id = id;
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_schedule_base_service = JobScheduleBaseService::new(&transaction);
+    let job_schedule_opt = job_schedule_base_service
+        .query_job_schedule_one(&JobScheduleOpt {
+            org_id: Some(org_id),
+            id: Some(job_schedule_id),
+            ..JobScheduleOpt::empty()
+        })
+        .await?;
+    job_schedule_opt.ok_or_else(|| -> ErrNo {
+        ErrNo::CommonError(LightString::from_static("待删除的计划不存在！"))
+    })?;
+    job_schedule_base_service
+        .delete_job_schedule(job_schedule_id)
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(());
+}
@@ -0,0 +1,84 @@
+use super::super::environment::grant::check_environment_grant;
+use super::check_schedule_target;
+use super::from_sdk_schedule_status;
+use super::get_next_fire_time;
+use super::parse_cron_expression;
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::environment_grant::enums::GrantPermission;
+use crate::model::job_schedule::enums::Status;
+use crate::model::job_schedule::JobSchedule;
+use crate::sdk;
+use crate::service::base::JobScheduleBaseService;
+use chrono::Utc;
+use sdk::job_schedule::insert_job_schedule::InsertJobScheduleReq;
+use tihu::Id;
+use tihu::PrimaryKey;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+pub async fn insert_job_schedule(
+    org_id: Id,
+    user: User,
+    insert_job_schedule_req: InsertJobScheduleReq,
+) -> Result<PrimaryKey, ErrNo> {
+    let InsertJobScheduleReq {
+        job_id,
+        environment_id,
+        name,
+        cron_expression,
+        params,
+        status,
+    } = insert_job_schedule_req;
+    let schedule = parse_cron_expression(&cron_expression)?;
+    let status = from_sdk_schedule_status(status);
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_schedule_base_service = JobScheduleBaseService::new(&transaction);
+    let params =
+        check_schedule_target(&transaction, org_id, job_id, environment_id, params).await?;
+    //计划按照创建人的授权触发执行，创建时需要有执行任务的授权
+    check_environment_grant(
+        &transaction,
+        org_id,
+        &user,
+        environment_id,
+        GrantPermission::Run,
+    )
+    .await?;
+    let job_schedule_id = context.new_id();
+    let curr_time = Utc::now();
+    //停用的计划不计算下次触发时间，启用时再计算
+    let next_fire_time = if Status::Enabled == status {
+        get_next_fire_time(&schedule, curr_time)
+    } else {
+        None
+    };
+    job_schedule_base_service
+        .insert_job_schedule(&JobSchedule {
+            id: job_schedule_id,
+            org_id: org_id,
+            job_id: job_id,
+            environment_id: environment_id,
+            name: name,
+            cron_expression: cron_expression.trim().to_string(),
+            params: params,
+            status: status,
+            next_fire_time: next_fire_time,
+            last_fire_time: None,
+            last_record_id: None,
+            created_by: Some(user.user_id),
+            created_time: curr_time,
+            last_modified_time: curr_time,
+        })
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(PrimaryKey {
+        id: job_schedule_id,
+    });
+}
@@ -0,0 +1,99 @@
+pub mod delete_job_schedule;
+pub mod insert_job_schedule;
+pub mod query_job_schedule;
+pub mod read_job_schedule;
+pub mod update_job_schedule;
+
+use super::job::template::check_params;
+use super::job::template::parse_parameter_schema;
+use crate::model::environment::EnvironmentOpt;
+use crate::model::job::JobOpt;
+use crate::model::job_schedule::enums::Status;
+use crate::sdk;
+use crate::service::base::EnvironmentBaseService;
+use crate::service::base::JobBaseService;
+use chrono::DateTime;
+use chrono::Local;
+use chrono::Utc;
+use cron::Schedule;
+use std::str::FromStr;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::ErrNo;
+use tokio_postgres::Transaction;
+
+pub fn from_sdk_schedule_status(val: sdk::job_schedule::enums::ScheduleStatus) -> Status {
+    match val {
+        sdk::job_schedule::enums::ScheduleStatus::Enabled => Status::Enabled,
+        sdk::job_schedule::enums::ScheduleStatus::Disabled => Status::Disabled,
+    }
+}
+
+pub fn to_sdk_schedule_status(val: Status) -> sdk::job_schedule::enums::ScheduleStatus {
+    match val {
+        Status::Enabled => sdk::job_schedule::enums::ScheduleStatus::Enabled,
+        Status::Disabled => sdk::job_schedule::enums::ScheduleStatus::Disabled,
+    }
+}
+
+/**
+ * 解析cron表达式，支持常见的5段写法（分 时 日 月 周），也支持带秒的6段和带年的7段写法
+ */
+pub fn parse_cron_expression(cron_expression: &str) -> Result<Schedule, ErrNo> {
+    let cron_expression = cron_expression.trim();
+    let expression = if 5 == cron_expression.split_whitespace().count() {
+        format!("0 {}", cron_expression)
+    } else {
+        cron_expression.to_string()
+    };
+    return Schedule::from_str(&expression).map_err(|err| {
+        ErrNo::CommonError(LightString::from(format!(
+            "cron表达式\"{}\"不正确：{}",
+            cron_expression, err
+        )))
+    });
+}
+
+//按服务器所在时区计算某个时间之后的下一次触发时间
+pub fn get_next_fire_time(schedule: &Schedule, after: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    return schedule
+        .after(&after.with_timezone(&Local))
+        .next()
+        .map(|fire_time| fire_time.with_timezone(&Utc));
+}
+
+//检查计划关联的任务和环境，以及执行参数是否符合任务的参数定义，返回整理之后的执行参数
+async fn check_schedule_target(
+    transaction: &Transaction<'_>,
+    org_id: Id,
+    job_id: Id,
+    environment_id: Id,
+    params: Option<String>,
+) -> Result<String, ErrNo> {
+    let job_base_service = JobBaseService::new(transaction);
+    let environment_base_service = EnvironmentBaseService::new(transaction);
+    let job = job_base_service
+        .query_job_one(&JobOpt {
+            org_id: Some(org_id),
+            id: Some(job_id),
+            ..JobOpt::empty()
+        })
+        .await?
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("该任务不存在")))?;
+    let environment = environment_base_service
+        .query_environment_one(&EnvironmentOpt {
+            org_id: Some(org_id),
+            id: Some(environment_id),
+            ..EnvironmentOpt::empty()
+        })
+        .await?
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("目标环境不存在")))?;
+    if environment.environment_schema_id != job.environment_schema_id {
+        return Err(ErrNo::CommonError(LightString::from_static(
+            "目标环境的规格和任务的环境规格不一致",
+        )));
+    }
+    let parameter_schema = parse_parameter_schema(&job.parameter_schema)?;
+    let params = check_params(&parameter_schema, params)?;
+    return serde_json::to_string(&params).map_err(ErrNo::SerializeError);
+}
@@ -0,0 +1,98 @@
+use super::from_sdk_schedule_status;
+use super::to_sdk_schedule_status;
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::job_schedule::JobScheduleOpt;
+use crate::sdk;
+use crate::service::base::EnvironmentBaseService;
+use crate::service::base::JobBaseService;
+use crate::service::base::JobScheduleBaseService;
+use crate::service::job_schedule::JobScheduleService;
+use sdk::job_schedule::query_job_schedule::QueryJobScheduleReq;
+use sdk::job_schedule::read_job_schedule::JobSchedule;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use tihu::pagination::PaginationList;
+use tihu::Id;
+use tihu::Pagination;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+pub async fn query_job_schedule(
+    org_id: Id,
+    _user: User,
+    query_job_schedule_req: QueryJobScheduleReq,
+) -> Result<PaginationList<JobSchedule>, ErrNo> {
+    let QueryJobScheduleReq {
+        job_id,
+        environment_id,
+        status,
+        page_no,
+        page_size,
+    } = query_job_schedule_req;
+    let params = JobScheduleOpt {
+        org_id: Some(org_id),
+        job_id: job_id,
+        environment_id: environment_id,
+        status: status.map(from_sdk_schedule_status),
+        ..JobScheduleOpt::empty()
+    };
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_schedule_base_service = JobScheduleBaseService::new(&transaction);
+    let job_schedule_service = JobScheduleService::new(&transaction);
+    let job_base_service = JobBaseService::new(&transaction);
+    let environment_base_service = EnvironmentBaseService::new(&transaction);
+    let count = job_schedule_base_service
+        .query_job_schedule_count(&params)
+        .await?;
+    let pagination = Pagination::new(count, page_no.unwrap_or(1), page_size, None);
+    let job_schedule_list = job_schedule_service
+        .query_job_schedule(pagination.page_no, pagination.page_size, &params)
+        .await?;
+    let mut list = Vec::with_capacity(job_schedule_list.len());
+    if !job_schedule_list.is_empty() {
+        let job_ids: HashSet<_> = job_schedule_list
+            .iter()
+            .map(|job_schedule| job_schedule.job_id)
+            .collect();
+        let job_ids: Vec<_> = job_ids.into_iter().collect();
+        let job_list = job_base_service.read_job_batch(&job_ids).await?;
+        let job_map: HashMap<_, _> = job_list.into_iter().map(|job| (job.id, job.name)).collect();
+        let environment_ids: HashSet<_> = job_schedule_list
+            .iter()
+            .map(|job_schedule| job_schedule.environment_id)
+            .collect();
+        let environment_ids: Vec<_> = environment_ids.into_iter().collect();
+        let environment_list = environment_base_service
+            .read_environment_batch(&environment_ids)
+            .await?;
+        let environment_map: HashMap<_, _> = environment_list
+            .into_iter()
+            .map(|environment| (environment.id, environment.name))
+            .collect();
+        for job_schedule in job_schedule_list {
+            list.push(JobSchedule {
+                id: job_schedule.id,
+                job_id: job_schedule.job_id,
+                job_name: job_map.get(&job_schedule.job_id).cloned(),
+                environment_id: job_schedule.environment_id,
+                environment_name: environment_map.get(&job_schedule.environment_id).cloned(),
+                name: job_schedule.name,
+                cron_expression: job_schedule.cron_expression,
+                params: Some(job_schedule.params),
+                status: to_sdk_schedule_status(job_schedule.status),
+                next_fire_time: job_schedule.next_fire_time,
+                last_fire_time: job_schedule.last_fire_time,
+                last_record_id: job_schedule.last_record_id,
+                created_time: job_schedule.created_time,
+                last_modified_time: job_schedule.last_modified_time,
+            });
+        }
+    }
+    return Ok(PaginationList {
+        pagination: pagination,
+        list: list,
+    });
+}
@@ -0,0 +1,70 @@
+use super::to_sdk_schedule_status;
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::environment::EnvironmentOpt;
+use crate::model::job::JobOpt;
+use crate::model::job_schedule::JobScheduleOpt;
+use crate::sdk;
+use crate::service::base::EnvironmentBaseService;
+use crate::service::base::JobBaseService;
+use crate::service::base::JobScheduleBaseService;
+use sdk::job_schedule::read_job_schedule::JobSchedule;
+use sdk::job_schedule::read_job_schedule::ReadJobScheduleReq;
+use sdk::job_schedule::read_job_schedule::ReadJobScheduleResp;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+pub async fn read_job_schedule(
+    org_id: Id,
+    _user: User,
+    read_job_schedule_req: ReadJobScheduleReq,
+) -> Result<ReadJobScheduleResp, ErrNo> {
+    let ReadJobScheduleReq { id } = read_job_schedule_req;
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_schedule_base_service = JobScheduleBaseService::new(&transaction);
+    let job_base_service = JobBaseService::new(&transaction);
+    let environment_base_service = EnvironmentBaseService::new(&transaction);
+    let job_schedule_opt = job_schedule_base_service
+        .query_job_schedule_one(&JobScheduleOpt {
+            org_id: Some(org_id),
+            id: Some(id),
+            ..JobScheduleOpt::empty()
+        })
+        .await?;
+    let job_schedule = job_schedule_opt
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("该计划不存在")))?;
+    let job_opt = job_base_service
+        .query_job_one(&JobOpt {
+            org_id: Some(org_id),
+            id: Some(job_schedule.job_id),
+            ..JobOpt::empty()
+        })
+        .await?;
+    let environment_opt = environment_base_service
+        .query_environment_one(&EnvironmentOpt {
+            org_id: Some(org_id),
+            id: Some(job_schedule.environment_id),
+            ..EnvironmentOpt::empty()
+        })
+        .await?;
+    return Ok(JobSchedule {
+        id: job_schedule.id,
+        job_id: job_schedule.job_id,
+        job_name: job_opt.map(|job| job.name),
+        environment_id: job_schedule.environment_id,
+        environment_name: environment_opt.map(|environment| environment.name),
+        name: job_schedule.name,
+        cron_expression: job_schedule.cron_expression,
+        params: Some(job_schedule.params),
+        status: to_sdk_schedule_status(job_schedule.status),
+        next_fire_time: job_schedule.next_fire_time,
+        last_fire_time: job_schedule.last_fire_time,
+        last_record_id: job_schedule.last_record_id,
+        created_time: job_schedule.created_time,
+        last_modified_time: job_schedule.last_modified_time,
+    });
+}
@@ -0,0 +1,101 @@
+use super::super::environment::grant::check_environment_grant;
+use super::check_schedule_target;
+use super::from_sdk_schedule_status;
+use super::get_next_fire_time;
+use super::parse_cron_expression;
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::environment_grant::enums::GrantPermission;
+use crate::model::job_schedule::enums::Status;
+use crate::model::job_schedule::JobScheduleOpt;
+use crate::model::job_schedule::JobScheduleProperty;
+use crate::sdk;
+use crate::service::base::JobScheduleBaseService;
+use chrono::Utc;
+use sdk::job_schedule::update_job_schedule::UpdateJobScheduleReq;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+pub async fn update_job_schedule(
+    org_id: Id,
+    user: User,
+    update_job_schedule_req: UpdateJobScheduleReq,
+) -> Result<(), ErrNo> {
+    let UpdateJobScheduleReq {
+        id,
+        environment_id,
+        name,
+        cron_expression,
+        params,
+        status,
+    } = update_job_schedule_req;
+    let job_schedule_id = id;
+    let schedule = parse_cron_expression(&cron_expression)?;
+    let cron_expression = cron_expression.trim().to_string();
+    let status = from_sdk_schedule_status(status);
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_schedule_base_service = JobScheduleBaseService::new(&transaction);
+    let job_schedule_opt = job_schedule_base_service
+        .query_job_schedule_one(&JobScheduleOpt {
+            org_id: Some(org_id),
+            id: Some(job_schedule_id),
+            ..JobScheduleOpt::empty()
+        })
+        .await?;
+    let job_schedule = job_schedule_opt.ok_or_else(|| -> ErrNo {
+        ErrNo::CommonError(LightString::from_static("待修改的计划不存在！"))
+    })?;
+    let params = check_schedule_target(
+        &transaction,
+        org_id,
+        job_schedule.job_id,
+        environment_id,
+        params,
+    )
+    .await?;
+    check_environment_grant(
+        &transaction,
+        org_id,
+        &user,
+        environment_id,
+        GrantPermission::Run,
+    )
+    .await?;
+    let curr_time = Utc::now();
+    //表达式变化或者重新启用时，从当前时间开始重新计算下次触发时间
+    let next_fire_time = if Status::Enabled != status {
+        None
+    } else if cron_expression != job_schedule.cron_expression
+        || Status::Enabled != job_schedule.status
+        || job_schedule.next_fire_time.is_none()
+    {
+        get_next_fire_time(&schedule, curr_time)
+    } else {
+        job_schedule.next_fire_time
+    };
+    let mut changes: Vec<JobScheduleProperty> = vec![
+        JobScheduleProperty::EnvironmentId(environment_id),
+        JobScheduleProperty::Name(name),
+        JobScheduleProperty::CronExpression(cron_expression),
+        JobScheduleProperty::Params(params),
+        JobScheduleProperty::Status(status),
+        JobScheduleProperty::NextFireTime(next_fire_time),
+    ];
+    changes.retain(|property| !job_schedule.eq(property));
+    if !changes.is_empty() {
+        changes.push(JobScheduleProperty::LastModifiedTime(curr_time));
+        job_schedule_base_service
+            .update_job_schedule(job_schedule_id, &changes)
+            .await?;
+        transaction
+            .commit()
+            .await
+            .map_err(commit_transaction_error)?;
+    }
+    return Ok(());
+}
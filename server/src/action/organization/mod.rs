@@ -0,0 +1,2 @@
+pub mod read_retention_policy;
+pub mod update_retention_policy;
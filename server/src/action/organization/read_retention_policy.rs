@@ -0,0 +1,34 @@
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::sdk;
+use crate::service::base::OrganizationBaseService;
+use sdk::organization::read_retention_policy::ReadRetentionPolicyReq;
+use sdk::organization::read_retention_policy::ReadRetentionPolicyResp;
+use sdk::organization::read_retention_policy::RetentionPolicy;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+pub async fn read_retention_policy(
+    org_id: Id,
+    _user: User,
+    _read_retention_policy_req: ReadRetentionPolicyReq,
+) -> Result<ReadRetentionPolicyResp, ErrNo> {
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let organization_base_service = OrganizationBaseService::new(&transaction);
+    let organization = organization_base_service
+        .read_organization(org_id)
+        .await?
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("组织不存在")))?;
+    return Ok(RetentionPolicy {
+        retention_count: organization.retention_count.map(|count| count as u32),
+        retention_days: organization.retention_days.map(|days| days as u32),
+        last_purge_time: organization.last_purge_time,
+        last_purge_report: organization
+            .last_purge_report
+            .and_then(|report| serde_json::from_str(&report).ok()),
+    });
+}
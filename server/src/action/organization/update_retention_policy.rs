@@ -0,0 +1,62 @@
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::organization::OrganizationProperty;
+use crate::sdk;
+use crate::service::base::OrganizationBaseService;
+use chrono::Utc;
+use sdk::organization::update_retention_policy::UpdateRetentionPolicyReq;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+//保留天数的上限，大约100年
+const MAX_RETENTION_DAYS: u32 = 36500;
+
+pub async fn update_retention_policy(
+    org_id: Id,
+    _user: User,
+    update_retention_policy_req: UpdateRetentionPolicyReq,
+) -> Result<(), ErrNo> {
+    let UpdateRetentionPolicyReq {
+        retention_count,
+        retention_days,
+    } = update_retention_policy_req;
+    if let Some(retention_count) = retention_count {
+        if 0 == retention_count || i32::MAX as u32 <= retention_count {
+            return Err(ErrNo::CommonError(LightString::from_static(
+                "保留的执行记录条数必须大于0！",
+            )));
+        }
+    }
+    if let Some(retention_days) = retention_days {
+        if 0 == retention_days || MAX_RETENTION_DAYS < retention_days {
+            return Err(ErrNo::CommonError(LightString::from(format!(
+                "保留天数必须在1到{}之间！",
+                MAX_RETENTION_DAYS
+            ))));
+        }
+    }
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let organization_base_service = OrganizationBaseService::new(&transaction);
+    organization_base_service
+        .read_organization(org_id)
+        .await?
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("组织不存在")))?;
+    let changes: Vec<OrganizationProperty> = vec![
+        OrganizationProperty::RetentionCount(retention_count.map(|count| count as i32)),
+        OrganizationProperty::RetentionDays(retention_days.map(|days| days as i32)),
+        OrganizationProperty::LastModifiedTime(Utc::now()),
+    ];
+    organization_base_service
+        .update_organization(org_id, &changes)
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(());
+}
@@ -0,0 +1,117 @@
+use super::dispatch;
+use super::hash_runner_token;
+use crate::model::runner::Runner;
+use crate::model::runner::RunnerOpt;
+use crate::model::runner::RunnerProperty;
+use crate::sdk;
+use crate::service::base::RunnerBaseService;
+use crate::Context;
+use chrono::Duration;
+use chrono::Utc;
+use sdk::runner::agent::PullRunnerTaskReq;
+use sdk::runner::agent::PullRunnerTaskResp;
+use sdk::runner::agent::RegisterRunnerReq;
+use sdk::runner::agent::RegisterRunnerResp;
+use sdk::runner::agent::ReportRunnerTaskReq;
+use sdk::runner::agent::ReportRunnerTaskResp;
+use std::sync::Arc;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+//执行任务期间通过上报刷新心跳的最小间隔，避免每次上报都更新执行器
+const HEARTBEAT_REFRESH_SECONDS: i64 = 10;
+
+/**
+ * 用访问令牌找到对应的执行器，令牌无效时返回None
+ */
+pub async fn authenticate_runner(
+    context: &Arc<Context>,
+    token: &str,
+) -> Result<Option<Runner>, ErrNo> {
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let runner_base_service = RunnerBaseService::new(&transaction);
+    return runner_base_service
+        .query_runner_one(&RunnerOpt {
+            token_hash: Some(hash_runner_token(token)),
+            ..RunnerOpt::empty()
+        })
+        .await;
+}
+
+//记录执行器的心跳，执行器列表据此判断是否在线
+async fn update_heartbeat(
+    context: &Arc<Context>,
+    runner: &Runner,
+    mut changes: Vec<RunnerProperty>,
+) -> Result<(), ErrNo> {
+    let curr_time = Utc::now();
+    changes.push(RunnerProperty::LastHeartbeatTime(Some(curr_time)));
+    changes.push(RunnerProperty::LastModifiedTime(curr_time));
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let runner_base_service = RunnerBaseService::new(&transaction);
+    runner_base_service
+        .update_runner(runner.id, &changes)
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(());
+}
+
+pub async fn register_runner(
+    context: &Arc<Context>,
+    runner: Runner,
+    register_runner_req: RegisterRunnerReq,
+) -> Result<RegisterRunnerResp, ErrNo> {
+    let RegisterRunnerReq {
+        version,
+        extension_ids,
+    } = register_runner_req;
+    update_heartbeat(
+        context,
+        &runner,
+        vec![
+            RunnerProperty::Version(Some(version)),
+            RunnerProperty::ExtensionIds(Some(extension_ids.join(","))),
+        ],
+    )
+    .await?;
+    log::info!("执行器\"{}\"已连接，标签: {}", runner.name, runner.label);
+    return Ok(RegisterRunnerResp {
+        id: runner.id,
+        name: runner.name,
+        label: runner.label,
+    });
+}
+
+pub async fn pull_runner_task(
+    context: &Arc<Context>,
+    runner: Runner,
+    _pull_runner_task_req: PullRunnerTaskReq,
+) -> Result<PullRunnerTaskResp, ErrNo> {
+    update_heartbeat(context, &runner, vec![]).await?;
+    let task = dispatch::pull_runner_task(context, &runner).await?;
+    return Ok(PullRunnerTaskResp { task: task });
+}
+
+pub async fn report_runner_task(
+    context: &Arc<Context>,
+    runner: Runner,
+    report_runner_task_req: ReportRunnerTaskReq,
+) -> Result<ReportRunnerTaskResp, ErrNo> {
+    //执行器执行任务期间不会拉取新任务，靠上报刷新心跳，避免分配给它的其它任务被当成没有执行器在线
+    let heartbeat_expired = runner
+        .last_heartbeat_time
+        .map(|last_heartbeat_time| {
+            last_heartbeat_time + Duration::seconds(HEARTBEAT_REFRESH_SECONDS) < Utc::now()
+        })
+        .unwrap_or(true);
+    if heartbeat_expired {
+        update_heartbeat(context, &runner, vec![]).await?;
+    }
+    return dispatch::report_runner_task(context, &runner, report_runner_task_req).await;
+}
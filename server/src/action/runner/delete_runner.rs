@@ -0,0 +1,39 @@
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::runner::RunnerOpt;
+use crate::sdk;
+use crate::service::base::RunnerBaseService;
+use sdk::runner::delete_runner::DeleteRunnerReq;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+//删除之后执行器的访问令牌立即失效，正在执行的任务会因为执行器失去联系而失败
+pub async fn delete_runner(
+    org_id: Id,
+    _user: User,
+    delete_runner_req: DeleteRunnerReq,
+) -> Result<(), ErrNo> {
+    let DeleteRunnerReq { id } = delete_runner_req;
+    let runner_id = id;
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let runner_base_service = RunnerBaseService::new(&transaction);
+    runner_base_service
+        .query_runner_one(&RunnerOpt {
+            org_id: Some(org_id),
+            id: Some(runner_id),
+            ..RunnerOpt::empty()
+        })
+        .await?
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("待删除的执行器不存在！")))?;
+    runner_base_service.delete_runner(runner_id).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(());
+}
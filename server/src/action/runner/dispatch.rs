@@ -0,0 +1,537 @@
+use super::RUNNER_OFFLINE_SECONDS;
+use crate::model::runner::Runner;
+use crate::model::runner::RunnerOpt;
+use crate::model::runner_task::enums::Status;
+use crate::model::runner_task::RunnerTask as RunnerTaskModel;
+use crate::model::runner_task::RunnerTaskOpt;
+use crate::model::runner_task::RunnerTaskProperty;
+use crate::sdk;
+use crate::service::base::RunnerBaseService;
+use crate::service::base::RunnerTaskBaseService;
+use crate::service::runner_task::RunnerTaskService;
+use crate::Context;
+use chrono::Duration as ChronoDuration;
+use chrono::Utc;
+use mould_extension_sdk::AppendLog;
+use sdk::job_record::read_job_record::LogLevel;
+use sdk::job_record::read_job_record::StepResLog;
+use sdk::runner::agent::ReportRunnerTaskReq;
+use sdk::runner::agent::ReportRunnerTaskResp;
+use sdk::runner::agent::RunnerTask;
+use sdk::runner::agent::RunnerTaskResult;
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+use tihu::Id;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+use tokio::sync::watch;
+use tokio::sync::Notify;
+use tokio::time::sleep;
+
+//执行器拉取任务时最多等待多久
+const PULL_WAIT: Duration = Duration::from_secs(25);
+//执行器等待任务期间检查新任务的间隔，其他实例发起的任务只能靠检查发现
+const PULL_INTERVAL: Duration = Duration::from_secs(1);
+//发起任务的实例检查任务状态、写入执行日志的间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+//执行器超过这个时间没有上报执行情况，或者发起任务的实例超过这个时间没有检查任务，认为对方已经失去联系
+const LOST_SECONDS: i64 = 60;
+//发起任务的实例超过这个时间没有检查任务，认为任务已经被遗弃，可以清理
+const ABANDONED_SECONDS: i64 = 60 * 60;
+//每次最多清理的任务数
+const PURGE_BATCH_SIZE: u64 = 100;
+
+lazy_static::lazy_static! {
+    //本实例有新任务时唤醒正在等待的执行器，不用等到下一次检查
+    static ref RUNNER_TASK_NOTIFY: Notify = Notify::new();
+}
+
+/**
+ * 交给执行器执行的一次扩展调用
+ */
+pub struct RunnerCall {
+    pub org_id: Id,                      //组织id，只有同一个组织的执行器可以领取
+    pub label: String,                   //执行器标签
+    pub extension_id: String,            //扩展id
+    pub operation_id: String,            //操作id
+    pub operation_parameter: String,     //操作参数，json格式
+    pub extension_configuration: String, //加密保存的扩展配置，交给执行器时才解密
+    pub resource_index: u32,             //资源在步骤里的序号
+}
+
+//调用结束（包括超时和被强制终止）时删除任务，执行器再上报时会得知任务已被取消
+struct TaskGuard {
+    context: Arc<Context>,
+    task_id: Id,
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        let context = self.context.clone();
+        let task_id = self.task_id;
+        tokio::spawn(async move {
+            if let Err(err) = delete_runner_task(&context, task_id).await {
+                log::error!("删除执行器任务{}失败: {:?}", task_id, err);
+            }
+        });
+    }
+}
+
+async fn delete_runner_task(context: &Context, task_id: Id) -> Result<(), ErrNo> {
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let runner_task_base_service = RunnerTaskBaseService::new(&transaction);
+    runner_task_base_service.delete_runner_task(task_id).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(());
+}
+
+async fn insert_runner_task(
+    context: &Context,
+    task_id: Id,
+    runner_call: RunnerCall,
+) -> Result<(), ErrNo> {
+    let curr_time = Utc::now();
+    //操作参数里可能有密码类型的执行参数，和扩展配置一样加密保存
+    let operation_parameter = context.encrypt_configuration(&runner_call.operation_parameter)?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let runner_task_base_service = RunnerTaskBaseService::new(&transaction);
+    runner_task_base_service
+        .insert_runner_task(&RunnerTaskModel {
+            id: task_id,
+            org_id: runner_call.org_id,
+            label: runner_call.label,
+            extension_id: runner_call.extension_id,
+            operation_id: runner_call.operation_id,
+            operation_parameter: operation_parameter,
+            extension_configuration: runner_call.extension_configuration,
+            resource_index: runner_call.resource_index as i32,
+            status: Status::Pending,
+            runner_id: None,
+            cancelled: false,
+            pending_logs: None,
+            outputs: None,
+            error: None,
+            polled_time: curr_time,
+            reported_time: None,
+            created_time: curr_time,
+            last_modified_time: curr_time,
+        })
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(());
+}
+
+/**
+ * 交给带有指定标签的执行器执行，等待执行器上报结果
+ * 任务保存在数据库里，执行器可以从任意一个实例领取和上报
+ */
+pub async fn call_runner(
+    context: &Arc<Context>,
+    runner_call: RunnerCall,
+    append_log: AppendLog,
+    cancel_receiver: watch::Receiver<bool>,
+) -> Result<Value, String> {
+    let task_id = context.new_id();
+    insert_runner_task(context, task_id, runner_call)
+        .await
+        .map_err(|err| err.message().to_string())?;
+    let _task_guard = TaskGuard {
+        context: context.clone(),
+        task_id: task_id,
+    };
+    RUNNER_TASK_NOTIFY.notify_waiters();
+    loop {
+        sleep(POLL_INTERVAL).await;
+        let cancelled = *cancel_receiver.borrow();
+        match poll_runner_task(context, task_id, cancelled, &append_log).await {
+            Ok(Some(result)) => return result,
+            Ok(None) => (),
+            Err(err) => {
+                //偶尔访问数据库失败不影响执行，下次再检查
+                log::error!("检查执行器任务{}失败: {:?}", task_id, err);
+            }
+        }
+    }
+}
+
+//同组织同标签的执行器是否有在线的，执行器执行任务期间的上报也会刷新心跳
+async fn has_online_runner(context: &Context, org_id: Id, label: &str) -> Result<bool, ErrNo> {
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let runner_base_service = RunnerBaseService::new(&transaction);
+    let runner_list = runner_base_service
+        .query_runner_batch(&RunnerOpt {
+            org_id: Some(org_id),
+            label: Some(label.to_string()),
+            ..RunnerOpt::empty()
+        })
+        .await?;
+    let online_after = Utc::now() - ChronoDuration::seconds(RUNNER_OFFLINE_SECONDS);
+    return Ok(runner_list.iter().any(|runner| {
+        runner
+            .last_heartbeat_time
+            .map(|last_heartbeat_time| online_after < last_heartbeat_time)
+            .unwrap_or(false)
+    }));
+}
+
+//记下本实例还在等待任务，取走执行器上报的日志并同步取消信号，任务结束时返回执行结果
+async fn poll_runner_task(
+    context: &Context,
+    task_id: Id,
+    cancelled: bool,
+    append_log: &AppendLog,
+) -> Result<Option<Result<Value, String>>, ErrNo> {
+    let curr_time = Utc::now();
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let runner_task_base_service = RunnerTaskBaseService::new(&transaction);
+    //先更新检查时间锁住任务，避免和执行器的上报互相覆盖日志
+    runner_task_base_service
+        .update_runner_task(
+            task_id,
+            &[
+                RunnerTaskProperty::PolledTime(curr_time),
+                RunnerTaskProperty::LastModifiedTime(curr_time),
+            ],
+        )
+        .await?;
+    let runner_task = match runner_task_base_service.read_runner_task(task_id).await? {
+        Some(runner_task) => runner_task,
+        None => return Ok(Some(Err(String::from("执行器任务不存在")))),
+    };
+    let mut changes = Vec::new();
+    if runner_task.pending_logs.is_some() {
+        changes.push(RunnerTaskProperty::PendingLogs(None));
+    }
+    if cancelled && !runner_task.cancelled {
+        changes.push(RunnerTaskProperty::Cancelled(true));
+    }
+    runner_task_base_service
+        .update_runner_task(task_id, &changes)
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    if let Some(pending_logs) = runner_task.pending_logs.as_ref() {
+        let logs: Vec<StepResLog> = serde_json::from_str(pending_logs).unwrap_or_default();
+        for log in logs {
+            append_log(to_extension_log_level(log.level), log.content);
+        }
+    }
+    let lost_before = curr_time - ChronoDuration::seconds(LOST_SECONDS);
+    match runner_task.status {
+        Status::Success => match runner_task.outputs {
+            Some(outputs) => {
+                return Ok(Some(
+                    serde_json::from_str(&outputs).map_err(|err| err.to_string()),
+                ));
+            }
+            None => return Ok(Some(Ok(Value::Null))),
+        },
+        Status::Failure => {
+            return Ok(Some(Err(runner_task.error.unwrap_or_default())));
+        }
+        Status::Running => {
+            let reported_time = runner_task
+                .reported_time
+                .unwrap_or(runner_task.created_time);
+            if reported_time < lost_before {
+                return Ok(Some(Err(String::from("执行器失去联系"))));
+            }
+        }
+        Status::Pending => {
+            if cancelled {
+                //还没有被执行器领取就取消了，不用再等
+                return Ok(Some(Err(String::from("执行被取消"))));
+            }
+            //一直没有执行器来领取，并且没有这个标签的执行器在线
+            if runner_task.created_time < lost_before
+                && !has_online_runner(context, runner_task.org_id, &runner_task.label).await?
+            {
+                return Ok(Some(Err(format!(
+                    "没有标签为\"{}\"的在线执行器",
+                    runner_task.label
+                ))));
+            }
+        }
+    }
+    return Ok(None);
+}
+
+//领取最早的一个同组织同标签的任务，已经取消或者发起的实例已经退出的任务不再交给执行器
+async fn take_task(context: &Context, runner: &Runner) -> Result<Option<RunnerTask>, ErrNo> {
+    let curr_time = Utc::now();
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let runner_task_service = RunnerTaskService::new(&transaction);
+    let runner_task_base_service = RunnerTaskBaseService::new(&transaction);
+    let polled_after = curr_time - ChronoDuration::seconds(LOST_SECONDS);
+    let runner_task = match runner_task_service
+        .query_claimable_runner_task(&runner.org_id, &runner.label, &polled_after)
+        .await?
+    {
+        Some(runner_task) => runner_task,
+        None => return Ok(None),
+    };
+    runner_task_base_service
+        .update_runner_task(
+            runner_task.id,
+            &[
+                RunnerTaskProperty::Status(Status::Running),
+                RunnerTaskProperty::RunnerId(Some(runner.id)),
+                RunnerTaskProperty::ReportedTime(Some(curr_time)),
+                RunnerTaskProperty::LastModifiedTime(curr_time),
+            ],
+        )
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    //扩展配置和操作参数是加密保存的，领取提交之后、返回给执行器之前才解密
+    //明文只在组装这次响应时出现，不经过数据库事务；解密失败的任务标记为失败，不会再交给其它执行器
+    let decrypted = context
+        .decrypt_configuration(&runner_task.extension_configuration)
+        .and_then(|extension_configuration| {
+            context
+                .decrypt_configuration(&runner_task.operation_parameter)
+                .map(|operation_parameter| (extension_configuration, operation_parameter))
+        });
+    let (extension_configuration, operation_parameter) = match decrypted {
+        Ok(decrypted) => decrypted,
+        Err(err) => {
+            fail_runner_task(context, runner_task.id, err.message().to_string()).await?;
+            return Ok(None);
+        }
+    };
+    return Ok(Some(RunnerTask {
+        task_id: runner_task.id.to_string(),
+        extension_id: runner_task.extension_id,
+        operation_id: runner_task.operation_id,
+        operation_parameter: operation_parameter,
+        extension_configuration: extension_configuration,
+        resource_index: runner_task.resource_index as u32,
+    }));
+}
+
+//执行器领取之后无法交给执行器的任务，直接标记为失败，等待结果的一方会收到失败原因
+async fn fail_runner_task(context: &Context, task_id: Id, error: String) -> Result<(), ErrNo> {
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let runner_task_base_service = RunnerTaskBaseService::new(&transaction);
+    runner_task_base_service
+        .update_runner_task(
+            task_id,
+            &[
+                RunnerTaskProperty::Status(Status::Failure),
+                RunnerTaskProperty::Error(Some(error)),
+                RunnerTaskProperty::LastModifiedTime(Utc::now()),
+            ],
+        )
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(());
+}
+
+/**
+ * 执行器拉取任务，没有任务时等待新任务，最多等待一段时间
+ */
+pub async fn pull_runner_task(
+    context: &Context,
+    runner: &Runner,
+) -> Result<Option<RunnerTask>, ErrNo> {
+    let deadline = Instant::now() + PULL_WAIT;
+    loop {
+        //先登记等待再检查任务，避免错过检查之后到来的通知
+        let notified = RUNNER_TASK_NOTIFY.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+        if let Some(task) = take_task(context, runner).await? {
+            return Ok(Some(task));
+        }
+        let now = Instant::now();
+        if now >= deadline {
+            return Ok(None);
+        }
+        tokio::select! {
+            _ = &mut notified => (),
+            _ = sleep(PULL_INTERVAL.min(deadline - now)) => (),
+        }
+    }
+}
+
+fn to_extension_log_level(level: LogLevel) -> mould_extension_sdk::LogLevel {
+    match level {
+        LogLevel::Error => mould_extension_sdk::LogLevel::Error,
+        LogLevel::Warn => mould_extension_sdk::LogLevel::Warn,
+        LogLevel::Info => mould_extension_sdk::LogLevel::Info,
+        LogLevel::Debug => mould_extension_sdk::LogLevel::Debug,
+        LogLevel::Trace => mould_extension_sdk::LogLevel::Trace,
+    }
+}
+
+/**
+ * 执行器上报执行日志和结果，返回任务是否已经被取消
+ * 日志先暂存在任务上，由发起任务的实例取走写入执行日志
+ */
+pub async fn report_runner_task(
+    context: &Context,
+    runner: &Runner,
+    report_runner_task_req: ReportRunnerTaskReq,
+) -> Result<ReportRunnerTaskResp, ErrNo> {
+    let ReportRunnerTaskReq {
+        task_id,
+        logs,
+        result,
+    } = report_runner_task_req;
+    let task_id: Id = match task_id.parse() {
+        Ok(task_id) => task_id,
+        Err(_) => return Ok(ReportRunnerTaskResp { cancelled: true }),
+    };
+    let curr_time = Utc::now();
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let runner_task_base_service = RunnerTaskBaseService::new(&transaction);
+    runner_task_base_service
+        .update_runner_task(task_id, &[RunnerTaskProperty::LastModifiedTime(curr_time)])
+        .await?;
+    let runner_task = match runner_task_base_service.read_runner_task(task_id).await? {
+        Some(runner_task)
+            if Some(runner.id) == runner_task.runner_id
+                && runner.org_id == runner_task.org_id
+                && Status::Running == runner_task.status =>
+        {
+            runner_task
+        }
+        //任务已经结束，或者不是交给这个执行器的
+        _ => return Ok(ReportRunnerTaskResp { cancelled: true }),
+    };
+    //发起任务的实例已经退出，没有人等待执行结果了
+    if runner_task.polled_time < curr_time - ChronoDuration::seconds(LOST_SECONDS) {
+        return Ok(ReportRunnerTaskResp { cancelled: true });
+    }
+    let mut changes = vec![RunnerTaskProperty::ReportedTime(Some(curr_time))];
+    if !logs.is_empty() {
+        let mut pending_logs: Vec<StepResLog> = runner_task
+            .pending_logs
+            .as_ref()
+            .and_then(|pending_logs| serde_json::from_str(pending_logs).ok())
+            .unwrap_or_default();
+        pending_logs.extend(logs);
+        let pending_logs = serde_json::to_string(&pending_logs).map_err(|err| -> ErrNo {
+            log::error!("序列化执行器上报的日志失败: {:?}", err);
+            ErrNo::SerializeError(err)
+        })?;
+        changes.push(RunnerTaskProperty::PendingLogs(Some(pending_logs)));
+    }
+    match result {
+        Some(RunnerTaskResult::Success { outputs }) => {
+            changes.push(RunnerTaskProperty::Status(Status::Success));
+            changes.push(RunnerTaskProperty::Outputs(outputs));
+        }
+        Some(RunnerTaskResult::Failure { error }) => {
+            changes.push(RunnerTaskProperty::Status(Status::Failure));
+            changes.push(RunnerTaskProperty::Error(Some(error)));
+        }
+        None => (),
+    }
+    runner_task_base_service
+        .update_runner_task(task_id, &changes)
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(ReportRunnerTaskResp {
+        cancelled: runner_task.cancelled,
+    });
+}
+
+/**
+ * 清理被遗弃的任务，发起任务的实例退出之后任务不会被删除，返回清理的任务数
+ */
+pub async fn purge_abandoned_runner_tasks(context: &Context) -> Result<u64, ErrNo> {
+    let polled_before = Utc::now() - ChronoDuration::seconds(ABANDONED_SECONDS);
+    let mut task_count = 0;
+    loop {
+        let mut client = context.get_db_client().await?;
+        let transaction = client.transaction().await.map_err(open_transaction_error)?;
+        let runner_task_service = RunnerTaskService::new(&transaction);
+        let runner_task_base_service = RunnerTaskBaseService::new(&transaction);
+        let task_ids = runner_task_service
+            .query_abandoned_runner_task_ids(&polled_before, PURGE_BATCH_SIZE)
+            .await?;
+        if task_ids.is_empty() {
+            return Ok(task_count);
+        }
+        runner_task_base_service
+            .delete_runner_task_batch(&task_ids)
+            .await?;
+        transaction
+            .commit()
+            .await
+            .map_err(commit_transaction_error)?;
+        task_count += task_ids.len() as u64;
+        if (task_ids.len() as u64) < PURGE_BATCH_SIZE {
+            return Ok(task_count);
+        }
+    }
+}
+
+/**
+ * 执行器当前正在执行的任务里有没有引用这个文件，执行器只能下载分配给自己的任务需要的文件
+ */
+pub async fn is_runner_file_referenced(
+    context: &Context,
+    runner: &Runner,
+    key: &str,
+) -> Result<bool, ErrNo> {
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let runner_task_base_service = RunnerTaskBaseService::new(&transaction);
+    let runner_task_list = runner_task_base_service
+        .query_runner_task_batch(&RunnerTaskOpt {
+            org_id: Some(runner.org_id),
+            runner_id: Some(runner.id),
+            status: Some(Status::Running),
+            ..RunnerTaskOpt::empty()
+        })
+        .await?;
+    return Ok(runner_task_list.iter().any(|runner_task| {
+        //操作参数是加密保存的
+        context
+            .decrypt_configuration(&runner_task.operation_parameter)
+            .ok()
+            .and_then(|operation_parameter| {
+                serde_json::from_str::<Value>(&operation_parameter).ok()
+            })
+            .map(|operation_parameter| contains_string(&operation_parameter, key))
+            .unwrap_or(false)
+    }));
+}
+
+//json里是否有等于target的字符串
+fn contains_string(value: &Value, target: &str) -> bool {
+    match value {
+        Value::String(value) => target == value,
+        Value::Array(items) => items.iter().any(|item| contains_string(item, target)),
+        Value::Object(map) => map.values().any(|item| contains_string(item, target)),
+        _ => false,
+    }
+}
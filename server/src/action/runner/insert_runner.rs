@@ -0,0 +1,78 @@
+use super::hash_runner_token;
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::runner::Runner;
+use crate::model::runner::RunnerOpt;
+use crate::sdk;
+use crate::service::base::RunnerBaseService;
+use chrono::Utc;
+use sdk::runner::insert_runner::InsertRunnerReq;
+use sdk::runner::insert_runner::InsertRunnerResp;
+use tihu::base62;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+use uuid::Uuid;
+
+pub async fn insert_runner(
+    org_id: Id,
+    _user: User,
+    insert_runner_req: InsertRunnerReq,
+) -> Result<InsertRunnerResp, ErrNo> {
+    let InsertRunnerReq { name, label } = insert_runner_req;
+    let name = name.trim().to_string();
+    let label = label.trim().to_string();
+    if name.is_empty() || label.is_empty() {
+        return Err(ErrNo::CommonError(LightString::from_static(
+            "执行器名称和标签不能为空",
+        )));
+    }
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let runner_base_service = RunnerBaseService::new(&transaction);
+    let existed = runner_base_service
+        .query_runner_one(&RunnerOpt {
+            org_id: Some(org_id),
+            name: Some(name.clone()),
+            ..RunnerOpt::empty()
+        })
+        .await?;
+    if existed.is_some() {
+        return Err(ErrNo::CommonError(LightString::from(format!(
+            "执行器\"{}\"已经存在",
+            name
+        ))));
+    }
+    let token = format!(
+        "{}{}",
+        base62::encode(&Uuid::new_v4().as_u128().to_be_bytes()),
+        base62::encode(&Uuid::new_v4().as_u128().to_be_bytes())
+    );
+    let runner_id = context.new_id();
+    let curr_time = Utc::now();
+    runner_base_service
+        .insert_runner(&Runner {
+            id: runner_id,
+            org_id: org_id,
+            name: name,
+            label: label,
+            token_hash: hash_runner_token(&token),
+            version: None,
+            extension_ids: None,
+            last_heartbeat_time: None,
+            created_time: curr_time,
+            last_modified_time: curr_time,
+        })
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(InsertRunnerResp {
+        id: runner_id,
+        token: token,
+    });
+}
@@ -0,0 +1,16 @@
+pub mod agent;
+pub mod delete_runner;
+pub mod dispatch;
+pub mod insert_runner;
+pub mod query_runner;
+use crate::native_common;
+use native_common::utils::sha256;
+use native_common::utils::HexStr;
+
+//超过这个时间没有心跳，认为执行器已经离线
+pub const RUNNER_OFFLINE_SECONDS: i64 = 60;
+
+//数据库里只保存访问令牌的摘要
+pub fn hash_runner_token(token: &str) -> String {
+    return HexStr(&sha256(token.as_bytes())).to_string();
+}
@@ -0,0 +1,60 @@
+use super::RUNNER_OFFLINE_SECONDS;
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::runner::RunnerOpt;
+use crate::sdk;
+use crate::service::base::RunnerBaseService;
+use chrono::Duration;
+use chrono::Utc;
+use sdk::runner::query_runner::QueryRunnerReq;
+use sdk::runner::query_runner::QueryRunnerResp;
+use sdk::runner::query_runner::Runner;
+use tihu::Id;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+pub async fn query_runner(
+    org_id: Id,
+    _user: User,
+    query_runner_req: QueryRunnerReq,
+) -> Result<QueryRunnerResp, ErrNo> {
+    let QueryRunnerReq { label } = query_runner_req;
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let runner_base_service = RunnerBaseService::new(&transaction);
+    let mut runner_list = runner_base_service
+        .query_runner_batch(&RunnerOpt {
+            org_id: Some(org_id),
+            label: label,
+            ..RunnerOpt::empty()
+        })
+        .await?;
+    runner_list.sort_by_key(|runner| runner.created_time);
+    let online_after = Utc::now() - Duration::seconds(RUNNER_OFFLINE_SECONDS);
+    return Ok(runner_list
+        .into_iter()
+        .map(|runner| Runner {
+            id: runner.id,
+            name: runner.name,
+            label: runner.label,
+            version: runner.version,
+            extension_ids: runner
+                .extension_ids
+                .map(|extension_ids| {
+                    extension_ids
+                        .split(',')
+                        .filter(|extension_id| !extension_id.is_empty())
+                        .map(String::from)
+                        .collect()
+                })
+                .unwrap_or_default(),
+            online: runner
+                .last_heartbeat_time
+                .map(|last_heartbeat_time| last_heartbeat_time > online_after)
+                .unwrap_or(false),
+            last_heartbeat_time: runner.last_heartbeat_time,
+            created_time: runner.created_time,
+        })
+        .collect());
+}
@@ -0,0 +1,75 @@
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::api_token::ApiTokenOpt;
+use crate::model::environment_grant::EnvironmentGrantOpt;
+use crate::model::user::enums::UserSource;
+use crate::sdk;
+use crate::service::base::ApiTokenBaseService;
+use crate::service::base::EnvironmentGrantBaseService;
+use crate::service::base::UserBaseService;
+use sdk::user::delete_service_account::DeleteServiceAccountReq;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+//删除服务账号时一起删除它的访问令牌和环境授权，令牌立即失效
+pub async fn delete_service_account(
+    org_id: Id,
+    _user: User,
+    delete_service_account_req: DeleteServiceAccountReq,
+) -> Result<(), ErrNo> {
+    let DeleteServiceAccountReq { id } = delete_service_account_req;
+    let user_id = id;
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let user_base_service = UserBaseService::new(&transaction);
+    let api_token_base_service = ApiTokenBaseService::new(&transaction);
+    let environment_grant_base_service = EnvironmentGrantBaseService::new(&transaction);
+    user_base_service
+        .read_user(user_id)
+        .await?
+        .filter(|target_user| {
+            Some(org_id) == target_user.org_id
+                && UserSource::ServiceAccount == target_user.user_source
+        })
+        .ok_or_else(|| ErrNo::CommonError(LightString::from_static("待删除的服务账号不存在！")))?;
+    let api_token_ids: Vec<Id> = api_token_base_service
+        .query_api_token_batch(&ApiTokenOpt {
+            org_id: Some(org_id),
+            user_id: Some(user_id),
+            ..ApiTokenOpt::empty()
+        })
+        .await?
+        .into_iter()
+        .map(|api_token| api_token.id)
+        .collect();
+    if !api_token_ids.is_empty() {
+        api_token_base_service
+            .delete_api_token_batch(&api_token_ids)
+            .await?;
+    }
+    let environment_grant_ids: Vec<Id> = environment_grant_base_service
+        .query_environment_grant_batch(&EnvironmentGrantOpt {
+            org_id: Some(org_id),
+            user_id: Some(user_id),
+            ..EnvironmentGrantOpt::empty()
+        })
+        .await?
+        .into_iter()
+        .map(|environment_grant| environment_grant.id)
+        .collect();
+    if !environment_grant_ids.is_empty() {
+        environment_grant_base_service
+            .delete_environment_grant_batch(&environment_grant_ids)
+            .await?;
+    }
+    user_base_service.delete_user(user_id).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(());
+}
@@ -0,0 +1,77 @@
+use super::from_sdk_role;
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::user::enums::Role;
+use crate::model::user::enums::UserSource;
+use crate::model::user::User as UserModel;
+use crate::model::user::UserOpt;
+use crate::sdk;
+use crate::service::base::UserBaseService;
+use chrono::Utc;
+use sdk::user::insert_service_account::InsertServiceAccountReq;
+use tihu::Id;
+use tihu::LightString;
+use tihu::PrimaryKey;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+/**
+ * 新增服务账号，服务账号不能登录，只能通过访问令牌调用api
+ */
+pub async fn insert_service_account(
+    org_id: Id,
+    _user: User,
+    insert_service_account_req: InsertServiceAccountReq,
+) -> Result<PrimaryKey, ErrNo> {
+    let InsertServiceAccountReq { name, role } = insert_service_account_req;
+    let name = name.trim().to_string();
+    let role = from_sdk_role(role);
+    if name.is_empty() {
+        return Err(ErrNo::CommonError(LightString::from_static(
+            "服务账号名称不能为空",
+        )));
+    }
+    if Role::Owner == role {
+        return Err(ErrNo::CommonError(LightString::from_static(
+            "服务账号不能是所有者",
+        )));
+    }
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let user_base_service = UserBaseService::new(&transaction);
+    let existed = user_base_service
+        .query_user_one(&UserOpt {
+            org_id: Some(org_id),
+            user_source: Some(UserSource::ServiceAccount),
+            name: Some(name.clone()),
+            ..UserOpt::empty()
+        })
+        .await?;
+    if existed.is_some() {
+        return Err(ErrNo::CommonError(LightString::from(format!(
+            "服务账号\"{}\"已经存在",
+            name
+        ))));
+    }
+    let user_id = context.new_id();
+    let curr_time = Utc::now();
+    user_base_service
+        .insert_user(&UserModel {
+            id: user_id,
+            org_id: Some(org_id),
+            user_source: UserSource::ServiceAccount,
+            role: role,
+            name: name,
+            avatar_url: None,
+            created_time: curr_time,
+            last_modified_time: curr_time,
+        })
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(PrimaryKey { id: user_id.into() });
+}
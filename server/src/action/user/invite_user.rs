@@ -1,5 +1,7 @@
+use super::from_sdk_role;
 use crate::get_context;
 use crate::middleware::auth::User;
+use crate::model::user::enums::Role;
 use crate::model::user::UserProperty;
 use crate::sdk;
 use crate::service::base::UserBaseService;
@@ -13,10 +15,15 @@ use tihu_native::ErrNo;
 
 pub async fn invite_user(
     org_id: Id,
-    _user: User,
+    user: User,
     invite_user_req: InviteUserReq,
 ) -> Result<(), ErrNo> {
-    let InviteUserReq { user_id } = invite_user_req;
+    let InviteUserReq { user_id, role } = invite_user_req;
+    let role = from_sdk_role(role);
+    //只有所有者才能邀请新的所有者
+    if Role::Owner == role && Role::Owner != user.role {
+        return Err(ErrNo::NotAllowed);
+    }
     let context = get_context()?;
     let mut client = context.get_db_client().await?;
     let transaction = client.transaction().await.map_err(open_transaction_error)?;
@@ -37,6 +44,7 @@ pub async fn invite_user(
     let curr_time = Utc::now();
     let changes = vec![
         UserProperty::OrgId(Some(org_id)),
+        UserProperty::Role(role),
         UserProperty::LastModifiedTime(curr_time),
     ];
     user_base_service.update_user(user_id, &changes).await?;
@@ -1,3 +1,26 @@
+pub mod delete_service_account;
+pub mod insert_service_account;
 pub mod invite_user;
 pub mod query_user;
 pub mod read_user;
+pub mod update_user_role;
+use crate::model::user::enums::Role;
+use crate::sdk;
+
+pub fn to_sdk_role(val: Role) -> sdk::user::enums::Role {
+    match val {
+        Role::Owner => sdk::user::enums::Role::Owner,
+        Role::Admin => sdk::user::enums::Role::Admin,
+        Role::Operator => sdk::user::enums::Role::Operator,
+        Role::Viewer => sdk::user::enums::Role::Viewer,
+    }
+}
+
+pub fn from_sdk_role(val: sdk::user::enums::Role) -> Role {
+    match val {
+        sdk::user::enums::Role::Owner => Role::Owner,
+        sdk::user::enums::Role::Admin => Role::Admin,
+        sdk::user::enums::Role::Operator => Role::Operator,
+        sdk::user::enums::Role::Viewer => Role::Viewer,
+    }
+}
@@ -1,3 +1,4 @@
+use super::to_sdk_role;
 use crate::get_context;
 use crate::model::user::enums::UserSource;
 use crate::model::user::User;
@@ -15,6 +16,7 @@ fn from_sdk_user_source(val: sdk::user::enums::UserSource) -> UserSource {
     match val {
         sdk::user::enums::UserSource::System => UserSource::System,
         sdk::user::enums::UserSource::External => UserSource::External,
+        sdk::user::enums::UserSource::ServiceAccount => UserSource::ServiceAccount,
     }
 }
 
@@ -22,6 +24,7 @@ fn to_sdk_user_source(val: UserSource) -> sdk::user::enums::UserSource {
     match val {
         UserSource::System => sdk::user::enums::UserSource::System,
         UserSource::External => sdk::user::enums::UserSource::External,
+        UserSource::ServiceAccount => sdk::user::enums::UserSource::ServiceAccount,
     }
 }
 
@@ -59,6 +62,7 @@ pub async fn query_user(
             |User {
                  id,
                  user_source,
+                 role,
                  name,
                  avatar_url,
                  created_time,
@@ -68,6 +72,7 @@ pub async fn query_user(
                 sdk::user::query_user::User {
                     id: id.into(),
                     user_source: to_sdk_user_source(user_source),
+                    role: to_sdk_role(role),
                     name: name.into(),
                     avatar_url: avatar_url.into(),
                     created_time: created_time.into(),
@@ -1,3 +1,4 @@
+use super::to_sdk_role;
 use crate::get_context;
 use crate::model::external_user::enums::ProviderType;
 use crate::model::user::enums::UserSource;
@@ -71,10 +72,12 @@ pub async fn read_user(
                     last_modified_time: external_user.last_modified_time,
                 })
             }
+            UserSource::ServiceAccount => sdk::user::read_user::UserSource::ServiceAccount,
         };
         return Ok(Some(sdk::user::read_user::User {
             id: user.id.into(),
             user_source: user_source,
+            role: to_sdk_role(user.role),
             name: user.name.into(),
             avatar_url: user.avatar_url.into(),
             created_time: user.created_time.into(),
@@ -0,0 +1,62 @@
+use super::from_sdk_role;
+use crate::get_context;
+use crate::middleware::auth::User;
+use crate::model::user::enums::Role;
+use crate::model::user::enums::UserSource;
+use crate::model::user::UserProperty;
+use crate::sdk;
+use crate::service::base::UserBaseService;
+use chrono::Utc;
+use sdk::user::update_user_role::UpdateUserRoleReq;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+
+pub async fn update_user_role(
+    org_id: Id,
+    user: User,
+    update_user_role_req: UpdateUserRoleReq,
+) -> Result<(), ErrNo> {
+    let UpdateUserRoleReq { user_id, role } = update_user_role_req;
+    let role = from_sdk_role(role);
+    if user_id == user.user_id {
+        return Err(ErrNo::CommonError(LightString::from_static(
+            "不能修改自己的角色！",
+        )));
+    }
+    let context = get_context()?;
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let user_base_service = UserBaseService::new(&transaction);
+    let target_user_opt = user_base_service.read_user(user_id).await?;
+    let target_user = target_user_opt
+        .filter(|target_user| Some(org_id) == target_user.org_id)
+        .ok_or_else(|| -> ErrNo {
+            ErrNo::CommonError(LightString::from_static("组织内不存在此用户！"))
+        })?;
+    //只有所有者才能授予或者收回所有者角色
+    if (Role::Owner == role || Role::Owner == target_user.role) && Role::Owner != user.role {
+        return Err(ErrNo::NotAllowed);
+    }
+    if UserSource::ServiceAccount == target_user.user_source && Role::Owner == role {
+        return Err(ErrNo::CommonError(LightString::from_static(
+            "服务账号不能是所有者",
+        )));
+    }
+    if target_user.role == role {
+        return Ok(());
+    }
+    let curr_time = Utc::now();
+    let changes = vec![
+        UserProperty::Role(role),
+        UserProperty::LastModifiedTime(curr_time),
+    ];
+    user_base_service.update_user(user_id, &changes).await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    return Ok(());
+}
@@ -3,6 +3,8 @@ use tihu::LightString;
 
 pub struct Arguments {
     pub config_path: String,
+    //可选的命令，不传时启动服务
+    pub command: Option<String>,
 }
 
 impl Arguments {
@@ -16,6 +18,7 @@ impl Arguments {
             .ok_or_else(|| LightString::from_static("app require 1 parameter!"))?;
         return Ok(Arguments {
             config_path: config_path.clone(),
+            command: args.get(2).cloned(),
         });
     }
 }
@@ -21,6 +21,11 @@ use std::sync::Arc;
 use tihu::base62;
 use tihu::LightString;
 
+//默认同时执行的任务数上限
+const DEFAULT_MAX_RUNNING_JOBS: usize = 16;
+//默认等待审批的有效期（小时）
+const DEFAULT_APPROVAL_EXPIRE_HOURS: u32 = 24;
+
 #[derive(Debug)]
 pub struct Config {
     pub host: IpAddr,
@@ -28,6 +33,9 @@ pub struct Config {
     pub log_cfg_path: Option<LightString>,
     pub extension_dir: String,
     pub job_log_dir: String,
+    pub max_running_jobs: usize,
+    pub max_running_jobs_per_org: Option<usize>,
+    pub approval_expire_hours: u32,
     // pub worker_id: u16,
     pub sign_secret: Arc<Vec<u8>>,
     // pub session_timeout: u8,
@@ -35,6 +43,8 @@ pub struct Config {
     pub rsa_pri_key: RsaPrivateKey,
     pub rsa_pub_key_content: LightString,
     pub server_random_value: [u8; 32],
+    pub configuration_key: [u8; 32],
+    pub old_configuration_keys: Vec<[u8; 32]>,
     pub cache_server: CacheServer,
     pub data_source: DataSource,
     pub oss: Oss,
@@ -52,6 +62,20 @@ impl Config {
         if job_log_dir.ends_with(&['/', '\\']) {
             job_log_dir.pop();
         }
+        let max_running_jobs = config.max_running_jobs.unwrap_or(DEFAULT_MAX_RUNNING_JOBS);
+        if 0 == max_running_jobs || Some(0) == config.max_running_jobs_per_org {
+            return Err(LightString::from_static(
+                "max_running_jobs and max_running_jobs_per_org must be greater than 0",
+            ));
+        }
+        let approval_expire_hours = config
+            .approval_expire_hours
+            .unwrap_or(DEFAULT_APPROVAL_EXPIRE_HOURS);
+        if 0 == approval_expire_hours {
+            return Err(LightString::from_static(
+                "approval_expire_hours must be greater than 0",
+            ));
+        }
         let host = config.host.unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
         let sign_secret = Arc::new(config.sign_secret.as_bytes().to_vec());
         let port = config.port.unwrap_or(80);
@@ -66,6 +90,14 @@ impl Config {
         })?;
         let rsa_pri_key = new_rsa_pri_key(&rsa_pri_key)?;
         let server_random_value = try_decode_bytes(&config.server_random_value, "服务端随机数")?;
+        let configuration_key = try_decode_bytes(&config.configuration_key, "扩展配置主密钥")?;
+        let old_configuration_keys = config
+            .old_configuration_keys
+            .iter()
+            .map(|old_configuration_key| {
+                try_decode_bytes(old_configuration_key, "旧的扩展配置主密钥")
+            })
+            .collect::<Result<Vec<_>, _>>()?;
         let register_captcha_template = read_to_string(&config.email_template.register_captcha)
             .map_err(|err| {
                 log::error!("读取注册邮件模板文件失败: {}", err);
@@ -88,6 +120,9 @@ impl Config {
             log_cfg_path: config.log_cfg_path.map(From::from),
             extension_dir: config.extension_dir,
             job_log_dir: job_log_dir,
+            max_running_jobs: max_running_jobs,
+            max_running_jobs_per_org: config.max_running_jobs_per_org,
+            approval_expire_hours: approval_expire_hours,
             // worker_id: config.worker_id,
             sign_secret: sign_secret,
             // session_timeout: config.session_timeout,
@@ -95,6 +130,8 @@ impl Config {
             rsa_pri_key: rsa_pri_key,
             rsa_pub_key_content: rsa_pub_key_content.into(),
             server_random_value: server_random_value,
+            configuration_key: configuration_key,
+            old_configuration_keys: old_configuration_keys,
             cache_server: config.cache_server,
             data_source: config.data_source,
             oss: config.oss,
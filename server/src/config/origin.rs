@@ -75,12 +75,23 @@ pub struct Config {
     pub log_cfg_path: Option<String>,
     pub extension_dir: String,
     pub job_log_dir: String,
+    //每个实例同时执行的任务数上限，默认16
+    pub max_running_jobs: Option<usize>,
+    //每个组织在所有实例上同时执行的任务数上限，不设置时只受max_running_jobs限制
+    pub max_running_jobs_per_org: Option<usize>,
+    //等待审批的执行记录的有效期（小时），超过之后标记为审批已过期，默认24
+    pub approval_expire_hours: Option<u32>,
     // pub worker_id: u16,
     pub sign_secret: String,
     // pub session_timeout: u8,
     pub rsa_pub_key: String,
     pub rsa_pri_key: String,
     pub server_random_value: String,
+    //加密扩展配置的主密钥
+    pub configuration_key: String,
+    //轮换之前用过的主密钥，只用于解密
+    #[serde(default)]
+    pub old_configuration_keys: Vec<String>,
     pub cache_server: CacheServer,
     pub data_source: DataSource,
     pub oss: Oss,
@@ -29,7 +29,14 @@ use mould_extension_sdk::pluginator;
 use mould_extension_sdk::ContextTrait;
 use mould_extension_sdk::Extension;
 use native_common::cache::RedisCache;
+use native_common::utils::decrypt_by_aes_256;
+use native_common::utils::decrypt_by_base64;
+use native_common::utils::encrypt_by_aes_256;
+use native_common::utils::encrypt_by_base64;
+use native_common::utils::sha256;
+use native_common::utils::HexStr;
 use sdk::storage::UPLOAD_API;
+use sdk::utils::json_script;
 // use native_common::utils::Snowflake;
 use native_tls::{Certificate, TlsConnector};
 use oauth2::basic::BasicClient;
@@ -64,6 +71,7 @@ use tokio::fs::try_exists;
 use tokio::fs::File;
 use tokio::io::AsyncSeekExt;
 use tokio::io::AsyncWriteExt;
+use tokio::sync::watch;
 use tokio_postgres::config::SslMode;
 use tokio_postgres::{Config as DbConfig, NoTls};
 use url::Url;
@@ -74,10 +82,20 @@ mod extension;
 mould_extension_sdk::plugin_trait!(Extension);
 
 pub const RPC_TIMEOUT: u64 = 10;
+//加密后的扩展配置前缀，格式为aes256:版本:密钥指纹:base64密文，早期的密文没有版本
+const CONFIGURATION_CIPHER_PREFIX: &str = "aes256:";
+//密文的版本，以后换成其它加密方式（如AES-GCM）时增加版本，按照版本选择解密方式
+const CONFIGURATION_CIPHER_VERSION: &str = "v1";
+
+fn get_key_fingerprint(key: &[u8; 32]) -> String {
+    return HexStr(&sha256(key)[..4]).to_string();
+}
 
 pub struct ExtensionContext {
     pub oss_client: Arc<Client>,
     pub bucket: LightString,
+    //任务执行的取消信号，不是在执行任务时为None
+    pub cancel_receiver: Option<watch::Receiver<bool>>,
 }
 
 #[async_trait::async_trait]
@@ -102,29 +120,7 @@ impl ContextTrait for ExtensionContext {
         js_func: &str,
         resource_index: u32,
     ) -> Result<Value, String> {
-        let isolate = &mut v8::Isolate::new(Default::default());
-        let scope = &mut v8::HandleScope::new(isolate);
-        let context = v8::Context::new(scope, Default::default());
-        let mut error_opt = None;
-        let scope = &mut v8::ContextScope::new(scope, context);
-        let new_target = jsonpath_lib::replace_with(target, json_path, &mut |old_val| {
-            if error_opt.is_none() {
-                match try_replace(scope, js_func, old_val, resource_index) {
-                    Ok(new_val) => Some(new_val),
-                    Err(error) => {
-                        error_opt.replace(error);
-                        None
-                    }
-                }
-            } else {
-                None
-            }
-        })
-        .map_err(|err| err.to_string())?;
-        if let Some(error) = error_opt {
-            return Err(error);
-        }
-        return Ok(new_target);
+        return json_script::modify_json_custom(target, json_path, js_func, resource_index);
     }
     fn modify_json(
         &self,
@@ -132,10 +128,7 @@ impl ContextTrait for ExtensionContext {
         json_path: &str,
         new_value: Value,
     ) -> Result<Value, String> {
-        let new_target =
-            jsonpath_lib::replace_with(target, json_path, &mut |_old_val| Some(new_value.clone()))
-                .map_err(|err| err.to_string())?;
-        return Ok(new_target);
+        return json_script::modify_json(target, json_path, new_value);
     }
     async fn download_file(&self, key: &str) -> Result<std::fs::File, String> {
         let resp = self
@@ -183,31 +176,25 @@ impl ContextTrait for ExtensionContext {
         let tmp_file = tmp_file.into_std().await;
         return Ok(tmp_file);
     }
-}
-
-fn try_replace(
-    scope: &mut v8::ContextScope<v8::HandleScope>,
-    js_func: &str,
-    old_val: Value,
-    resource_index: u32,
-) -> Result<Value, String> {
-    let old_val = serde_json::to_string(&old_val).map_err(|err| err.to_string())?;
-    let replace_script = format!(
-        r#"JSON.stringify(({})({},{}))"#,
-        js_func, old_val, resource_index
-    );
-    let code =
-        v8::String::new(scope, &replace_script).ok_or_else(|| String::from("构建js脚本失败"))?;
-    let script =
-        v8::Script::compile(scope, code, None).ok_or_else(|| String::from("编译js脚本失败"))?;
-    let result = script
-        .run(scope)
-        .ok_or_else(|| String::from("运行js脚本失败"))?;
-    let result = result
-        .to_string(scope)
-        .ok_or_else(|| String::from("获取运行结果失败"))?;
-    let result = result.to_rust_string_lossy(scope);
-    return serde_json::from_str(&result).map_err(|err| err.to_string());
+    fn is_cancelled(&self) -> bool {
+        return self
+            .cancel_receiver
+            .as_ref()
+            .map(|cancel_receiver| *cancel_receiver.borrow())
+            .unwrap_or(false);
+    }
+    async fn cancelled(&self) -> bool {
+        if let Some(cancel_receiver) = self.cancel_receiver.as_ref() {
+            //发送端被丢弃说明任务已经执行结束
+            let mut cancel_receiver = cancel_receiver.clone();
+            return cancel_receiver
+                .wait_for(|cancelled| *cancelled)
+                .await
+                .is_ok();
+        } else {
+            return false;
+        }
+    }
 }
 
 fn adjust_oss_error_code(error_code: i32) -> i32 {
@@ -229,6 +216,7 @@ pub struct Context {
     extension_context: mould_extension_sdk::Context,
     oauth2_clients: HashMap<String, (Arc<BasicClient>, Oauth2Server)>,
     openid_clients: HashMap<String, (Arc<OpenidClient>, OpenidServer)>,
+    instance_id: Id, //本服务实例的id，每次启动都不一样，用于区分多个实例各自执行的任务
 }
 
 impl Context {
@@ -313,9 +301,11 @@ impl Context {
             extension_context: Arc::new(ExtensionContext {
                 oss_client: oss_client,
                 bucket: bucket,
+                cancel_receiver: None,
             }),
             oauth2_clients: oauth2_clients,
             openid_clients: openid_clients,
+            instance_id: Uuid::new_v4(),
         };
         return Ok(context);
     }
@@ -332,6 +322,64 @@ impl Context {
         return Ok(self.config.server_random_value.clone());
     }
 
+    //用主密钥加密扩展配置，密文带上版本和密钥指纹，便于轮换密钥之后找到对应的密钥和加密方式解密
+    pub fn encrypt_configuration(&self, configuration: &str) -> Result<String, ErrNo> {
+        let key = &self.config.configuration_key;
+        let cipher =
+            encrypt_by_aes_256(configuration.as_bytes(), key).map_err(ErrNo::CommonError)?;
+        let cipher = encrypt_by_base64(&cipher).map_err(ErrNo::CommonError)?;
+        return Ok(format!(
+            "{}{}",
+            self.current_configuration_cipher_prefix(),
+            cipher
+        ));
+    }
+
+    //解密扩展配置，没有加密前缀的是加密之前保存的明文配置，原样返回
+    pub fn decrypt_configuration(&self, configuration: &str) -> Result<String, ErrNo> {
+        let cipher = match configuration.strip_prefix(CONFIGURATION_CIPHER_PREFIX) {
+            Some(cipher) => cipher,
+            None => return Ok(configuration.to_string()),
+        };
+        //密钥指纹是十六进制，不会以v开头，据此区分早期没有版本的密文，早期的加密方式和v1相同
+        let (version, cipher) = match cipher.split_once(':') {
+            Some((version, cipher)) if version.starts_with('v') => (version, cipher),
+            _ => (CONFIGURATION_CIPHER_VERSION, cipher),
+        };
+        if CONFIGURATION_CIPHER_VERSION != version {
+            log::error!("不支持的扩展配置密文版本: {}", version);
+            return Err(ErrNo::CommonError(LightString::from_static(
+                "不支持的扩展配置密文版本",
+            )));
+        }
+        let (fingerprint, cipher) = cipher.split_once(':').ok_or_else(|| {
+            ErrNo::CommonError(LightString::from_static("扩展配置密文格式不正确"))
+        })?;
+        let key = std::iter::once(&self.config.configuration_key)
+            .chain(self.config.old_configuration_keys.iter())
+            .find(|key| get_key_fingerprint(key) == fingerprint)
+            .ok_or_else(|| {
+                log::error!("找不到指纹为{}的扩展配置主密钥", fingerprint);
+                ErrNo::CommonError(LightString::from_static("找不到扩展配置的主密钥"))
+            })?;
+        let cipher = decrypt_by_base64(cipher).map_err(ErrNo::CommonError)?;
+        let plain = decrypt_by_aes_256(&cipher, key).map_err(ErrNo::CommonError)?;
+        return String::from_utf8(plain).map_err(|err| {
+            log::error!("扩展配置解密结果不是utf8编码: {:?}", err);
+            ErrNo::CommonError(LightString::from_static("扩展配置解密失败"))
+        });
+    }
+
+    //用当前的主密钥和加密方式加密的密文前缀，前缀不同的密文需要重新加密
+    pub fn current_configuration_cipher_prefix(&self) -> String {
+        return format!(
+            "{}{}:{}:",
+            CONFIGURATION_CIPHER_PREFIX,
+            CONFIGURATION_CIPHER_VERSION,
+            get_key_fingerprint(&self.config.configuration_key)
+        );
+    }
+
     pub async fn get_rsa_pub_key(&self) -> Result<RsaPublicKey, ErrNo> {
         return Ok(self.config.rsa_pub_key.clone());
     }
@@ -376,6 +424,10 @@ impl Context {
         Uuid::now_v7()
     }
 
+    pub fn get_instance_id(&self) -> Id {
+        return self.instance_id;
+    }
+
     pub fn get_oss_handler(&self) -> &Arc<dyn HttpHandler> {
         return &self.oss_handler;
     }
@@ -413,6 +465,17 @@ impl Context {
         return &self.extension_context;
     }
 
+    pub fn new_job_extension_context(
+        &self,
+        cancel_receiver: watch::Receiver<bool>,
+    ) -> mould_extension_sdk::Context {
+        return Arc::new(ExtensionContext {
+            oss_client: self.oss_client.clone(),
+            bucket: self.get_bucket(),
+            cancel_receiver: Some(cancel_receiver),
+        });
+    }
+
     pub fn get_oauth2_client(
         &self,
         provider: &str,
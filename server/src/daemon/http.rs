@@ -1,11 +1,21 @@
 use crate::action;
 use crate::context::Context;
+use crate::daemon::log_stream::open_log_stream;
+use crate::daemon::runner_agent::dispatch_runner_agent_api;
+use crate::daemon::runner_agent::open_runner_file;
 use crate::native_common;
+use crate::route::dispatch_user_api;
+use crate::route::WHITE_LIST_NAMESPACE;
 use crate::sdk;
 use crate::Asset;
+use crate::CONTEXT;
+use action::api_token::authenticate_api_token;
+use action::job_record::download_step_resource_log::open_log_download;
+use action::runner::agent::authenticate_runner;
 use action::system::get_system_info::get_system_info;
 use form_urlencoded::Serializer;
 use headers::{ContentType, HeaderMapExt};
+use http_body_util::BodyExt;
 use hyper::body::Incoming;
 use hyper::header;
 use hyper::header::HeaderValue;
@@ -18,6 +28,10 @@ use native_common::errno::result_to_json_resp;
 use native_common::utils::HexStr;
 use oauth2::{CsrfToken, PkceCodeChallenge};
 use rust_embed::RustEmbed;
+use sdk::job_record::download_step_resource_log::STEP_RESOURCE_LOG_DOWNLOAD_PATH;
+use sdk::job_record::watch_step_resource_log::STEP_RESOURCE_LOG_STREAM_PATH;
+use sdk::runner::agent::DOWNLOAD_RUNNER_FILE_API;
+use sdk::runner::agent::RUNNER_AGENT_API_PREFIX;
 use sdk::system::get_system_info::GET_SYSTEM_INFO_API;
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -89,6 +103,177 @@ fn response_not_found() -> Response<Body> {
     return response;
 }
 
+fn parse_token(req: &Request<Incoming>) -> Option<String> {
+    return req.uri().query().and_then(|query| {
+        form_urlencoded::parse(query.as_bytes())
+            .find(|(key, _)| "token" == key)
+            .map(|(_, value)| value.into_owned())
+    });
+}
+
+async fn handle_log_stream(context: Arc<Context>, req: &Request<Incoming>) -> Response<Body> {
+    let token = match parse_token(req) {
+        Some(token) => token,
+        None => return response_bad_request(),
+    };
+    match open_log_stream(context, &token).await {
+        Ok(Some(body)) => {
+            return Response::builder()
+                .header(header::CONTENT_TYPE, "text/event-stream")
+                .header(header::CACHE_CONTROL, "no-cache")
+                //禁止nginx缓冲，否则日志要攒够一批才会推送到浏览器
+                .header("X-Accel-Buffering", "no")
+                .body(body)
+                .unwrap();
+        }
+        Ok(None) => {
+            //凭证无效或者已过期，返回非200状态码后浏览器不会再重连
+            return response_not_found();
+        }
+        Err(err_no) => {
+            log::error!("打开实时日志失败: {}", err_no.to_string());
+            let mut response = text_response("Internal Server Error");
+            *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+            return response;
+        }
+    }
+}
+
+async fn handle_log_download(context: Arc<Context>, req: &Request<Incoming>) -> Response<Body> {
+    let token = match parse_token(req) {
+        Some(token) => token,
+        None => return response_bad_request(),
+    };
+    match open_log_download(&context, &token).await {
+        Ok(Some((step_resource_record_id, content))) => {
+            return Response::builder()
+                .header(header::CONTENT_TYPE, "text/plain; charset=utf-8")
+                .header(
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}.log\"", step_resource_record_id),
+                )
+                .body(Body::from(content))
+                .unwrap();
+        }
+        Ok(None) => {
+            return response_not_found();
+        }
+        Err(err_no) => {
+            log::error!("下载任务日志失败: {}", err_no.to_string());
+            let mut response = text_response("Internal Server Error");
+            *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+            return response;
+        }
+    }
+}
+
+fn response_unauthorized() -> Response<Body> {
+    let status_code = StatusCode::UNAUTHORIZED;
+    let status_text = status_code.canonical_reason().unwrap_or("Unauthorized");
+    let mut response = Response::new(Body::from(status_text));
+    *response.status_mut() = status_code;
+    response
+        .headers_mut()
+        .typed_insert(ContentType::text_utf8());
+    return response;
+}
+fn response_forbidden() -> Response<Body> {
+    let status_code = StatusCode::FORBIDDEN;
+    let status_text = status_code.canonical_reason().unwrap_or("Forbidden");
+    let mut response = Response::new(Body::from(status_text));
+    *response.status_mut() = status_code;
+    response
+        .headers_mut()
+        .typed_insert(ContentType::text_utf8());
+    return response;
+}
+
+fn parse_bearer_token(req: &Request<Incoming>) -> Option<String> {
+    return req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|authorization| authorization.to_str().ok())
+        .and_then(|authorization| authorization.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string());
+}
+
+//执行器的接口用访问令牌认证，不走用户会话
+async fn handle_runner_agent_api(
+    context: Arc<Context>,
+    req: Request<Incoming>,
+) -> Result<Response<Body>, anyhow::Error> {
+    let token = match parse_bearer_token(&req) {
+        Some(token) => token,
+        None => return Ok(response_unauthorized()),
+    };
+    let (parts, body) = req.into_parts();
+    let route = parts.uri.path();
+    let body = body.collect().await?.to_bytes();
+    let runner = match authenticate_runner(&context, &token).await {
+        Ok(Some(runner)) => runner,
+        Ok(None) => return Ok(response_unauthorized()),
+        Err(err_no) => {
+            return Ok(json_response(result_to_json_resp::<()>(Err(err_no))));
+        }
+    };
+    if DOWNLOAD_RUNNER_FILE_API == route {
+        match open_runner_file(&context, &runner, &body).await {
+            Ok(Some(body)) => {
+                return Ok(Response::builder()
+                    .header(header::CONTENT_TYPE, "application/octet-stream")
+                    .body(body)
+                    .unwrap());
+            }
+            Ok(None) => return Ok(response_forbidden()),
+            Err(err_no) => {
+                log::error!("执行器下载文件失败: {}", err_no.to_string());
+                return Ok(response_not_found());
+            }
+        }
+    }
+    return Ok(json_response(
+        dispatch_runner_agent_api(&context, route, runner, &body).await,
+    ));
+}
+
+//用户的api也可以用访问令牌调用，方便在CI等自动化场景里使用，不走会话和请求签名
+async fn handle_token_api(
+    context: Arc<Context>,
+    req: Request<Incoming>,
+    token: String,
+) -> Result<Response<Body>, anyhow::Error> {
+    let route = req.uri().path().to_string();
+    //登录、注册等白名单里的api只能在浏览器里调用
+    if WHITE_LIST_NAMESPACE
+        .iter()
+        .any(|namespace| route.starts_with(namespace))
+    {
+        return Ok(response_not_found());
+    }
+    let user = match authenticate_api_token(&context, &token).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return Ok(response_unauthorized()),
+        Err(err_no) => {
+            return Ok(json_response(result_to_json_resp::<()>(Err(err_no))));
+        }
+    };
+    let body = req.into_body().collect().await?.to_bytes();
+    let resp_ret = CONTEXT
+        .scope(
+            context.clone(),
+            dispatch_user_api((route.into(), body, user)),
+        )
+        .await;
+    match resp_ret {
+        Ok(resp) => {
+            return Ok(json_response(resp));
+        }
+        Err(err_no) => {
+            return Ok(json_response(result_to_json_resp::<()>(Err(err_no))));
+        }
+    }
+}
+
 fn handle_embed<B: RustEmbed>(req: Request<Incoming>) -> Response<Body> {
     let mut path = req
         .uri()
@@ -249,6 +434,10 @@ async fn try_dispatch(
                     return Ok(response);
                 }
             }
+        } else if STEP_RESOURCE_LOG_STREAM_PATH == route {
+            return Ok(handle_log_stream(context.clone(), &req).await);
+        } else if STEP_RESOURCE_LOG_DOWNLOAD_PATH == route {
+            return Ok(handle_log_download(context.clone(), &req).await);
         } else if match_route(oss_handler.as_ref(), route) {
             let mut request_data = RequestData::new();
             let resp = oss_handler
@@ -263,6 +452,12 @@ async fn try_dispatch(
         if GET_SYSTEM_INFO_API == route {
             return Ok(json_response(result_to_json_resp(get_system_info().await)));
         }
+        if route.starts_with(RUNNER_AGENT_API_PREFIX) {
+            return handle_runner_agent_api(context.clone(), req).await;
+        }
+        if let Some(token) = parse_bearer_token(&req) {
+            return handle_token_api(context.clone(), req, token).await;
+        }
         return handler.handle((req, remote_addr, RequestData::new())).await;
     } else {
         return Ok(response_not_found());
@@ -0,0 +1,61 @@
+use crate::action::job::approval::expire_pending_approvals;
+use crate::context::Context;
+use crate::CONTEXT;
+use deadpool_redis::redis;
+use std::sync::Arc;
+use std::time::Duration;
+use tihu_native::ErrNo;
+use tokio::time::MissedTickBehavior;
+
+//检查过期审批的间隔
+const EXPIRE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+//检查锁的key，多个实例共用redis时每一轮只有一个实例检查
+const EXPIRE_LOCK_KEY: &'static str = "job-approval-expire";
+//检查锁的有效期（毫秒），比检查间隔短一些，保证下一轮可以重新加锁
+const EXPIRE_LOCK_EXPIRE_MILLIS: u64 = 4 * 60 * 1000;
+
+/**
+ * 启动过期审批的定期检查，超过有效期还没有审批完的执行记录标记为审批已过期
+ */
+pub fn start_job_approval_expirer(context: Arc<Context>) {
+    tokio::spawn(CONTEXT.scope(context.clone(), async move {
+        let mut interval = tokio::time::interval(EXPIRE_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(err) = expire_all(&context).await {
+                log::error!("处理过期的审批失败: {:?}", err);
+            }
+        }
+    }));
+}
+
+//抢占本轮检查，返回是否抢到
+async fn try_lock_expire(context: &Arc<Context>) -> Result<bool, ErrNo> {
+    let mut cache_client = context.get_cache_client().await?;
+    let locked: Option<String> = redis::cmd("SET")
+        .arg(EXPIRE_LOCK_KEY)
+        .arg(1)
+        .arg("NX")
+        .arg("PX")
+        .arg(EXPIRE_LOCK_EXPIRE_MILLIS)
+        .query_async(&mut cache_client)
+        .await
+        .map_err(|err| -> ErrNo {
+            log::error!("获取审批的过期检查锁失败: {:?}", err);
+            ErrNo::CacheOperationError(err.into())
+        })?;
+    return Ok(locked.is_some());
+}
+
+async fn expire_all(context: &Arc<Context>) -> Result<(), ErrNo> {
+    if !try_lock_expire(context).await? {
+        //其他实例已经在检查了
+        return Ok(());
+    }
+    let count = expire_pending_approvals(context, context.config.approval_expire_hours).await?;
+    if 0 < count {
+        log::info!("{}条执行记录的审批已过期", count);
+    }
+    return Ok(());
+}
@@ -0,0 +1,26 @@
+use crate::action::job::recover_job::recover_job_records;
+use crate::action::job::recover_job::renew_job_record_leases;
+use crate::action::job::recover_job::LEASE_RENEW_INTERVAL;
+use crate::context::Context;
+use crate::CONTEXT;
+use std::sync::Arc;
+use tokio::time::MissedTickBehavior;
+
+/**
+ * 启动执行记录的续约，同时接手其它已经退出的实例留下的执行记录
+ */
+pub fn start_job_lease_keeper(context: Arc<Context>) {
+    tokio::spawn(CONTEXT.scope(context.clone(), async move {
+        let mut interval = tokio::time::interval(LEASE_RENEW_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(err) = renew_job_record_leases(&context).await {
+                log::error!("任务执行记录续约失败: {:?}", err);
+            }
+            if let Err(err) = recover_job_records(context.clone()).await {
+                log::error!("恢复中断的任务执行记录失败: {:?}", err);
+            }
+        }
+    }));
+}
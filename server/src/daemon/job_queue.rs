@@ -0,0 +1,29 @@
+use crate::action::job::queue::dispatch_queued_jobs;
+use crate::action::job::queue::JOB_QUEUE_NOTIFY;
+use crate::context::Context;
+use crate::CONTEXT;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::MissedTickBehavior;
+
+//没有收到通知时也定期检查队列，其它实例释放的环境不会通知到本实例
+const DISPATCH_INTERVAL: Duration = Duration::from_secs(10);
+
+/**
+ * 启动任务队列，收到通知或者定时从队列里取出记录开始执行
+ */
+pub fn start_job_queue(context: Arc<Context>) {
+    tokio::spawn(CONTEXT.scope(context.clone(), async move {
+        let mut interval = tokio::time::interval(DISPATCH_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            tokio::select! {
+                _ = JOB_QUEUE_NOTIFY.notified() => {}
+                _ = interval.tick() => {}
+            }
+            if let Err(err) = dispatch_queued_jobs(context.clone()).await {
+                log::error!("从队列开始执行任务失败: {:?}", err);
+            }
+        }
+    }));
+}
@@ -0,0 +1,100 @@
+use crate::action::job_record::purge_job_record::purge_job_records;
+use crate::action::runner::dispatch::purge_abandoned_runner_tasks;
+use crate::context::Context;
+use crate::model::organization::OrganizationOpt;
+use crate::service::base::OrganizationBaseService;
+use crate::CONTEXT;
+use deadpool_redis::redis;
+use std::sync::Arc;
+use std::time::Duration;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+use tokio::time::MissedTickBehavior;
+
+//清理过期执行记录的间隔
+const PURGE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+//清理锁的key，多个实例共用redis时每一轮只有一个实例清理
+const PURGE_LOCK_KEY: &'static str = "job-record-purge";
+//清理锁的有效期（毫秒），比清理间隔短一些，保证下一轮可以重新加锁
+const PURGE_LOCK_EXPIRE_MILLIS: u64 = 50 * 60 * 1000;
+
+/**
+ * 启动执行记录的定期清理，按照各个组织的保留策略删除过期的执行记录和日志，同时清理被遗弃的执行器任务
+ */
+pub fn start_job_record_purger(context: Arc<Context>) {
+    tokio::spawn(CONTEXT.scope(context.clone(), async move {
+        let mut interval = tokio::time::interval(PURGE_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(err) = purge_all(&context).await {
+                log::error!("清理过期的执行记录失败: {:?}", err);
+            }
+        }
+    }));
+}
+
+//抢占本轮清理，返回是否抢到
+async fn try_lock_purge(context: &Arc<Context>) -> Result<bool, ErrNo> {
+    let mut cache_client = context.get_cache_client().await?;
+    let locked: Option<String> = redis::cmd("SET")
+        .arg(PURGE_LOCK_KEY)
+        .arg(1)
+        .arg("NX")
+        .arg("PX")
+        .arg(PURGE_LOCK_EXPIRE_MILLIS)
+        .query_async(&mut cache_client)
+        .await
+        .map_err(|err| -> ErrNo {
+            log::error!("获取执行记录的清理锁失败: {:?}", err);
+            ErrNo::CacheOperationError(err.into())
+        })?;
+    return Ok(locked.is_some());
+}
+
+async fn purge_all(context: &Arc<Context>) -> Result<(), ErrNo> {
+    if !try_lock_purge(context).await? {
+        //其他实例已经在清理了
+        return Ok(());
+    }
+    let organization_list = {
+        let mut client = context.get_db_client().await?;
+        let transaction = client.transaction().await.map_err(open_transaction_error)?;
+        let organization_base_service = OrganizationBaseService::new(&transaction);
+        organization_base_service
+            .query_organization_batch(&OrganizationOpt::empty())
+            .await?
+    };
+    for organization in organization_list {
+        match purge_job_records(context, &organization).await {
+            Ok(Some(report)) => {
+                if 0 < report.record_count {
+                    log::info!(
+                        "组织{}清理了{}条执行记录、{}条步骤执行记录、{}条资源执行记录，删除了{}个日志对象、{}个日志文件",
+                        organization.id,
+                        report.record_count,
+                        report.step_record_count,
+                        report.step_resource_record_count,
+                        report.log_object_count,
+                        report.log_file_count
+                    );
+                }
+            }
+            Ok(None) => (),
+            Err(err) => {
+                log::error!("清理组织{}的执行记录失败: {:?}", organization.id, err);
+            }
+        }
+    }
+    match purge_abandoned_runner_tasks(context).await {
+        Ok(task_count) => {
+            if 0 < task_count {
+                log::info!("清理了{}个被遗弃的执行器任务", task_count);
+            }
+        }
+        Err(err) => {
+            log::error!("清理被遗弃的执行器任务失败: {:?}", err);
+        }
+    }
+    return Ok(());
+}
@@ -0,0 +1,309 @@
+use crate::action::job_record::log_store::load_step_resource_log;
+use crate::action::job_record::log_store::read_live_logs;
+use crate::action::job_record::to_sdk_step_resource_record_status;
+use crate::action::job_record::watch_step_resource_log::LogStreamTicket;
+use crate::action::job_record::watch_step_resource_log::LOG_STREAM_TOKEN_PREFIX;
+use crate::context::Context;
+use crate::model::job_record::enums::Status as RecordStatus;
+use crate::model::job_record::JobRecordOpt;
+use crate::model::job_step_resource_record::enums::Status as StepResourceStatus;
+use crate::model::job_step_resource_record::JobStepResourceRecord;
+use crate::model::job_step_resource_record::JobStepResourceRecordOpt;
+use crate::native_common;
+use crate::sdk;
+use crate::service::base::JobRecordBaseService;
+use crate::service::base::JobStepResourceRecordBaseService;
+use crate::CONTEXT;
+use bytes::Bytes;
+use native_common::cache::AsyncCache;
+use sdk::job_record::watch_step_resource_log::StepResLogEvent;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+use tihu::Id;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::http::Body;
+use tihu_native::ErrNo;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+use tokio::time::sleep;
+
+//轮询资源执行记录和实时日志的间隔
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+//一直没有新事件时发送心跳的间隔，避免连接被代理断开，同时也能及时发现浏览器已经断开
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(15);
+//每个订阅最多积压的消息数，超过之后断开连接，浏览器重连后重新补发
+const BROADCAST_CAPACITY: usize = 1024;
+
+lazy_static::lazy_static! {
+    //订阅同一个资源执行记录的连接共用一个轮询，按照资源执行记录id登记
+    static ref LOG_POLLERS: Mutex<HashMap<Id, LogPoller>> = Mutex::new(HashMap::new());
+}
+
+/**
+ * 资源执行记录的轮询，缓存已经推送过的消息，后来的订阅先补发这些消息
+ */
+struct LogPoller {
+    status_message: Option<Bytes>, //最新的状态
+    log_messages: Vec<Bytes>,      //已经推送过的日志
+    sender: broadcast::Sender<Bytes>,
+}
+
+/**
+ * 用订阅凭证打开资源执行的实时日志，凭证无效或者已过期时返回None
+ */
+pub async fn open_log_stream(context: Arc<Context>, token: &str) -> Result<Option<Body>, ErrNo> {
+    let cache_mgr = context.get_cache_mgr().await?;
+    let ticket = cache_mgr
+        .get(&(String::from(LOG_STREAM_TOKEN_PREFIX) + token).into_bytes())
+        .await?;
+    let ticket: LogStreamTicket = match ticket {
+        Some(ticket) => serde_json::from_slice(&ticket).map_err(ErrNo::DeserializeError)?,
+        None => return Ok(None),
+    };
+    let (history, broadcast_receiver) = subscribe_log_poller(&context, ticket);
+    let (sender, receiver) = mpsc::channel::<Bytes>(64);
+    tokio::spawn(forward_messages(history, broadcast_receiver, sender));
+    let stream = futures::stream::unfold(receiver, |mut receiver| async move {
+        receiver
+            .recv()
+            .await
+            .map(|data| (Ok::<Bytes, anyhow::Error>(data), receiver))
+    });
+    return Ok(Some(Body::from_bytes_stream(stream)));
+}
+
+fn to_message(event: &StepResLogEvent) -> Result<Bytes, ErrNo> {
+    let data = serde_json::to_string(event).map_err(ErrNo::SerializeError)?;
+    return Ok(Bytes::from(format!("data: {}\n\n", data)));
+}
+
+//订阅资源执行记录的轮询，还没有轮询时启动一个，返回需要补发的消息和后续消息的接收端
+fn subscribe_log_poller(
+    context: &Arc<Context>,
+    ticket: LogStreamTicket,
+) -> (Vec<Bytes>, broadcast::Receiver<Bytes>) {
+    let step_resource_record_id = ticket.step_resource_record_id;
+    let mut log_pollers = LOG_POLLERS.lock().unwrap();
+    if let Some(log_poller) = log_pollers.get(&step_resource_record_id) {
+        let history = log_poller
+            .status_message
+            .iter()
+            .chain(log_poller.log_messages.iter())
+            .cloned()
+            .collect();
+        return (history, log_poller.sender.subscribe());
+    }
+    let (sender, receiver) = broadcast::channel(BROADCAST_CAPACITY);
+    log_pollers.insert(
+        step_resource_record_id,
+        LogPoller {
+            status_message: None,
+            log_messages: Vec::new(),
+            sender: sender,
+        },
+    );
+    let context = context.clone();
+    tokio::spawn(CONTEXT.scope(context.clone(), async move {
+        if let Err(err) = poll_events(&context, &ticket).await {
+            log::error!(
+                "推送资源执行记录{}的实时日志失败: {:?}",
+                step_resource_record_id,
+                err
+            );
+            //移除轮询之后，订阅的连接都会断开
+            LOG_POLLERS.lock().unwrap().remove(&step_resource_record_id);
+        }
+    }));
+    return (Vec::new(), receiver);
+}
+
+//把轮询推送的消息转发给浏览器，浏览器断开或者轮询结束时退出
+async fn forward_messages(
+    history: Vec<Bytes>,
+    mut receiver: broadcast::Receiver<Bytes>,
+    sender: mpsc::Sender<Bytes>,
+) {
+    for message in history {
+        if sender.send(message).await.is_err() {
+            return;
+        }
+    }
+    loop {
+        let message = match receiver.recv().await {
+            Ok(message) => message,
+            Err(RecvError::Lagged(count)) => {
+                log::warn!("实时日志的订阅积压了{}条消息，断开连接", count);
+                return;
+            }
+            Err(RecvError::Closed) => return,
+        };
+        if sender.send(message).await.is_err() {
+            return;
+        }
+    }
+}
+
+/**
+ * 推送轮询到的消息并缓存下来，返回是否继续轮询
+ * 没有订阅了或者已经结束时移除轮询，之后的订阅会重新开始轮询
+ */
+fn publish_messages(
+    step_resource_record_id: Id,
+    status_message: Option<Bytes>,
+    log_messages: Vec<Bytes>,
+    end_message: Option<Bytes>,
+    heartbeat: bool,
+) -> bool {
+    let mut log_pollers = LOG_POLLERS.lock().unwrap();
+    let log_poller = match log_pollers.get_mut(&step_resource_record_id) {
+        Some(log_poller) => log_poller,
+        None => return false,
+    };
+    if let Some(status_message) = status_message {
+        let _ = log_poller.sender.send(status_message.clone());
+        log_poller.status_message = Some(status_message);
+    }
+    for log_message in log_messages {
+        let _ = log_poller.sender.send(log_message.clone());
+        log_poller.log_messages.push(log_message);
+    }
+    let finished = end_message.is_some();
+    if let Some(end_message) = end_message {
+        let _ = log_poller.sender.send(end_message);
+    }
+    if heartbeat {
+        //以冒号开头的是SSE注释，浏览器会忽略
+        let _ = log_poller.sender.send(Bytes::from_static(b": ping\n\n"));
+    }
+    if finished || 0 == log_poller.sender.receiver_count() {
+        log_pollers.remove(&step_resource_record_id);
+        return false;
+    }
+    return true;
+}
+
+async fn query_step_resource_record(
+    context: &Arc<Context>,
+    ticket: &LogStreamTicket,
+) -> Result<Option<(JobStepResourceRecord, bool)>, ErrNo> {
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_step_resource_record_base_service = JobStepResourceRecordBaseService::new(&transaction);
+    let job_record_base_service = JobRecordBaseService::new(&transaction);
+    let step_resource_record = job_step_resource_record_base_service
+        .query_job_step_resource_record_one(&JobStepResourceRecordOpt {
+            org_id: Some(ticket.org_id),
+            id: Some(ticket.step_resource_record_id),
+            ..JobStepResourceRecordOpt::empty()
+        })
+        .await?;
+    let step_resource_record = match step_resource_record {
+        Some(step_resource_record) => step_resource_record,
+        None => return Ok(None),
+    };
+    //还在等待执行的资源，需要根据任务是否还在执行判断是否还会有后续的日志
+    let job_running = if StepResourceStatus::Pending == step_resource_record.status {
+        job_record_base_service
+            .query_job_record_one(&JobRecordOpt {
+                org_id: Some(ticket.org_id),
+                id: Some(step_resource_record.record_id),
+                ..JobRecordOpt::empty()
+            })
+            .await?
+            .map(|job_record| {
+                RecordStatus::Running == job_record.status
+                    || RecordStatus::Queued == job_record.status
+            })
+            .unwrap_or(false)
+    } else {
+        true
+    };
+    return Ok(Some((step_resource_record, job_running)));
+}
+
+async fn poll_events(context: &Arc<Context>, ticket: &LogStreamTicket) -> Result<(), ErrNo> {
+    let step_resource_record_id = ticket.step_resource_record_id;
+    let mut last_status = None;
+    let mut sent_count: usize = 0;
+    let mut last_send_time = Instant::now();
+    loop {
+        let (step_resource_record, job_running) =
+            match query_step_resource_record(context, ticket).await? {
+                Some(result) => result,
+                None => {
+                    let end_message = to_message(&StepResLogEvent::End)?;
+                    publish_messages(
+                        step_resource_record_id,
+                        None,
+                        Vec::new(),
+                        Some(end_message),
+                        false,
+                    );
+                    return Ok(());
+                }
+            };
+        let status = to_sdk_step_resource_record_status(step_resource_record.status);
+        let status_message = if Some(status) != last_status {
+            last_status = Some(status);
+            Some(to_message(&StepResLogEvent::Status(status))?)
+        } else {
+            None
+        };
+        //执行结束后日志已经整理到对象存储（旧数据在数据库里）
+        let archived =
+            step_resource_record.log_key.is_some() || step_resource_record.output_content.is_some();
+        let logs = if archived {
+            //只补发还没有推送过的部分（如最后追加的错误日志）
+            load_step_resource_log(context, &step_resource_record)
+                .await
+                .map(|logs| logs.into_iter().skip(sent_count).collect())
+        } else if StepResourceStatus::Pending == step_resource_record.status {
+            Ok(Vec::new())
+        } else {
+            //执行中的日志由执行的实例写到redis，不管在哪个实例上执行都能读到
+            read_live_logs(context, step_resource_record_id, sent_count).await
+        };
+        let logs = logs.unwrap_or_else(|err| {
+            log::error!("读取任务日志失败: {:?}", err);
+            Vec::new()
+        });
+        sent_count += logs.len();
+        let log_messages = logs
+            .into_iter()
+            .map(|log| to_message(&StepResLogEvent::Log(log)))
+            .collect::<Result<Vec<_>, _>>()?;
+        //资源已经执行结束，或者任务结束了资源还没有执行，都不会再有新的日志
+        let finished = archived
+            || !job_running
+            || !matches!(
+                step_resource_record.status,
+                StepResourceStatus::Pending | StepResourceStatus::Running
+            );
+        let end_message = if finished {
+            Some(to_message(&StepResLogEvent::End)?)
+        } else {
+            None
+        };
+        if status_message.is_some() || !log_messages.is_empty() || end_message.is_some() {
+            last_send_time = Instant::now();
+        }
+        let heartbeat = last_send_time.elapsed() >= HEARTBEAT_INTERVAL;
+        if heartbeat {
+            last_send_time = Instant::now();
+        }
+        if !publish_messages(
+            step_resource_record_id,
+            status_message,
+            log_messages,
+            end_message,
+            heartbeat,
+        ) {
+            return Ok(());
+        }
+        sleep(POLL_INTERVAL).await;
+    }
+}
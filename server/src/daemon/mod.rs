@@ -1 +1,8 @@
 pub mod http;
+pub mod job_approval_expirer;
+pub mod job_lease;
+pub mod job_queue;
+pub mod job_record_purger;
+pub mod log_stream;
+pub mod runner_agent;
+pub mod scheduler;
@@ -0,0 +1,92 @@
+use crate::action::runner::agent;
+use crate::action::runner::dispatch;
+use crate::context::Context;
+use crate::model::runner::Runner;
+use crate::native_common;
+use crate::sdk;
+use native_common::errno::gen_no_such_api;
+use native_common::errno::result_to_json_resp;
+use sdk::runner::agent::DownloadRunnerFileReq;
+use sdk::runner::agent::PULL_RUNNER_TASK_API;
+use sdk::runner::agent::REGISTER_RUNNER_API;
+use sdk::runner::agent::REPORT_RUNNER_TASK_API;
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+use tihu::LightString;
+use tihu_native::http::Body;
+use tihu_native::ErrNo;
+
+fn parse_req<T: DeserializeOwned>(body: &[u8]) -> Result<T, ErrNo> {
+    return serde_json::from_slice(body).map_err(ErrNo::DeserializeError);
+}
+
+/**
+ * 处理执行器的接口调用，返回json格式的响应
+ */
+pub async fn dispatch_runner_agent_api(
+    context: &Arc<Context>,
+    route: &str,
+    runner: Runner,
+    body: &[u8],
+) -> Vec<u8> {
+    match route {
+        REGISTER_RUNNER_API => {
+            let result = match parse_req(body) {
+                Ok(req) => agent::register_runner(context, runner, req).await,
+                Err(err_no) => Err(err_no),
+            };
+            return result_to_json_resp(result);
+        }
+        PULL_RUNNER_TASK_API => {
+            let result = match parse_req(body) {
+                Ok(req) => agent::pull_runner_task(context, runner, req).await,
+                Err(err_no) => Err(err_no),
+            };
+            return result_to_json_resp(result);
+        }
+        REPORT_RUNNER_TASK_API => {
+            let result = match parse_req(body) {
+                Ok(req) => agent::report_runner_task(context, runner, req).await,
+                Err(err_no) => Err(err_no),
+            };
+            return result_to_json_resp(result);
+        }
+        _ => {
+            return gen_no_such_api();
+        }
+    }
+}
+
+/**
+ * 执行器下载步骤参数里引用的文件，直接返回文件内容
+ * 只能下载当前分配给这个执行器的任务引用的文件，否则返回None
+ */
+pub async fn open_runner_file(
+    context: &Arc<Context>,
+    runner: &Runner,
+    body: &[u8],
+) -> Result<Option<Body>, ErrNo> {
+    let DownloadRunnerFileReq { key } = parse_req(body)?;
+    if !dispatch::is_runner_file_referenced(context, runner, &key).await? {
+        log::warn!("执行器\"{}\"下载了没有分配给它的文件: {}", runner.name, key);
+        return Ok(None);
+    }
+    let resp = context
+        .get_oss_client()
+        .get_object()
+        .bucket(context.get_bucket().to_string())
+        .key(key)
+        .send()
+        .await
+        .map_err(|err| {
+            log::error!("下载文件失败: {:?}", err);
+            ErrNo::CommonError(LightString::from(err.to_string()))
+        })?;
+    let stream = futures::stream::unfold(resp.body, |mut stream| async move {
+        stream
+            .next()
+            .await
+            .map(|chunk| (chunk.map_err(anyhow::Error::from), stream))
+    });
+    return Ok(Some(Body::from_bytes_stream(stream)));
+}
@@ -0,0 +1,192 @@
+use crate::action::environment::grant::check_user_environment_grant;
+use crate::action::job::environment_lock::LockHolder;
+use crate::action::job::start_job::run_job;
+use crate::action::job_schedule::get_next_fire_time;
+use crate::action::job_schedule::parse_cron_expression;
+use crate::context::Context;
+use crate::model::environment_grant::enums::GrantPermission;
+use crate::model::job_record::enums::TriggerType;
+use crate::model::job_schedule::JobSchedule;
+use crate::model::job_schedule::JobScheduleProperty;
+use crate::sdk;
+use crate::service::base::JobScheduleBaseService;
+use crate::service::base::UserBaseService;
+use crate::service::job_schedule::JobScheduleService;
+use crate::CONTEXT;
+use chrono::DateTime;
+use chrono::Utc;
+use deadpool_redis::redis;
+use sdk::job::start_job::StartJobReq;
+use std::sync::Arc;
+use std::time::Duration;
+use tihu::Id;
+use tihu::LightString;
+use tihu_native::errno::commit_transaction_error;
+use tihu_native::errno::open_transaction_error;
+use tihu_native::ErrNo;
+use tokio::time::MissedTickBehavior;
+
+//扫描到期计划的间隔
+const SCAN_INTERVAL: Duration = Duration::from_secs(10);
+//触发锁的前缀，key里包含计划id和本次触发时间
+const FIRE_LOCK_PREFIX: &'static str = "job-schedule-fire-";
+//触发锁的有效期（毫秒），需要大于一次触发从加锁到更新下次触发时间的耗时
+const FIRE_LOCK_EXPIRE_MILLIS: u64 = 10 * 60 * 1000;
+
+/**
+ * 启动任务计划的调度，多个实例共用redis时，同一个计划的同一次触发只会有一个实例执行
+ */
+pub fn start_scheduler(context: Arc<Context>) {
+    tokio::spawn(CONTEXT.scope(context.clone(), async move {
+        let mut interval = tokio::time::interval(SCAN_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            if let Err(err) = fire_due_schedules(&context).await {
+                log::error!("扫描到期的任务计划失败: {:?}", err);
+            }
+        }
+    }));
+}
+
+async fn fire_due_schedules(context: &Arc<Context>) -> Result<(), ErrNo> {
+    let curr_time = Utc::now();
+    let job_schedule_list = {
+        let mut client = context.get_db_client().await?;
+        let transaction = client.transaction().await.map_err(open_transaction_error)?;
+        let job_schedule_service = JobScheduleService::new(&transaction);
+        job_schedule_service
+            .query_due_job_schedule(&curr_time)
+            .await?
+    };
+    for job_schedule in job_schedule_list {
+        let job_schedule_id = job_schedule.id;
+        if let Err(err) = fire_schedule(context, job_schedule).await {
+            log::error!("触发任务计划{}失败: {:?}", job_schedule_id, err);
+        }
+    }
+    return Ok(());
+}
+
+//抢占本次触发，返回是否抢到
+async fn try_lock_fire(
+    context: &Arc<Context>,
+    job_schedule_id: Id,
+    fire_time: DateTime<Utc>,
+) -> Result<bool, ErrNo> {
+    let mut cache_client = context.get_cache_client().await?;
+    let key = format!(
+        "{}{}-{}",
+        FIRE_LOCK_PREFIX,
+        job_schedule_id,
+        fire_time.timestamp()
+    );
+    let locked: Option<String> = redis::cmd("SET")
+        .arg(key)
+        .arg(1)
+        .arg("NX")
+        .arg("PX")
+        .arg(FIRE_LOCK_EXPIRE_MILLIS)
+        .query_async(&mut cache_client)
+        .await
+        .map_err(|err| -> ErrNo {
+            log::error!("获取任务计划的触发锁失败: {:?}", err);
+            ErrNo::CacheOperationError(err.into())
+        })?;
+    return Ok(locked.is_some());
+}
+
+//计划按照创建人的授权执行，创建人离开组织或者失去授权之后不再触发
+async fn check_creator_grant(
+    context: &Arc<Context>,
+    job_schedule: &JobSchedule,
+) -> Result<(), ErrNo> {
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let user_base_service = UserBaseService::new(&transaction);
+    let creator = match job_schedule.created_by {
+        Some(created_by) => user_base_service
+            .read_user(created_by)
+            .await?
+            .filter(|user| Some(job_schedule.org_id) == user.org_id),
+        None => None,
+    };
+    let creator = creator.ok_or_else(|| -> ErrNo {
+        ErrNo::CommonError(LightString::from_static(
+            "计划的创建人已经不在组织中，请重新保存计划！",
+        ))
+    })?;
+    return check_user_environment_grant(
+        &transaction,
+        job_schedule.org_id,
+        creator.id,
+        creator.role,
+        job_schedule.environment_id,
+        GrantPermission::Run,
+    )
+    .await;
+}
+
+async fn fire_schedule(context: &Arc<Context>, job_schedule: JobSchedule) -> Result<(), ErrNo> {
+    let fire_time = match job_schedule.next_fire_time {
+        Some(fire_time) => fire_time,
+        None => return Ok(()),
+    };
+    if !try_lock_fire(context, job_schedule.id, fire_time).await? {
+        //其他实例已经触发了这一次
+        return Ok(());
+    }
+    let start_result = match check_creator_grant(context, &job_schedule).await {
+        Ok(()) => {
+            run_job(
+                job_schedule.org_id,
+                StartJobReq {
+                    job_id: job_schedule.job_id,
+                    environment_id: job_schedule.environment_id,
+                    params: Some(job_schedule.params.clone()),
+                    change_note: None,
+                },
+                LockHolder {
+                    holder_id: None,
+                    holder_name: format!("任务计划\"{}\"", job_schedule.name),
+                },
+                TriggerType::Schedule,
+            )
+            .await
+        }
+        Err(err) => Err(err),
+    };
+    //错过的触发不再补偿，从当前时间开始计算下次触发时间
+    let curr_time = Utc::now();
+    let next_fire_time = match parse_cron_expression(&job_schedule.cron_expression) {
+        Ok(schedule) => get_next_fire_time(&schedule, curr_time),
+        Err(err) => {
+            log::error!(
+                "任务计划{}的cron表达式不正确，不再触发: {:?}",
+                job_schedule.id,
+                err
+            );
+            None
+        }
+    };
+    let mut changes = vec![
+        JobScheduleProperty::LastFireTime(Some(fire_time)),
+        JobScheduleProperty::NextFireTime(next_fire_time),
+        JobScheduleProperty::LastModifiedTime(curr_time),
+    ];
+    if let Ok(start_job_resp) = start_result.as_ref() {
+        changes.push(JobScheduleProperty::LastRecordId(Some(start_job_resp.id)));
+    }
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let job_schedule_base_service = JobScheduleBaseService::new(&transaction);
+    job_schedule_base_service
+        .update_job_schedule(job_schedule.id, &changes)
+        .await?;
+    transaction
+        .commit()
+        .await
+        .map_err(commit_transaction_error)?;
+    start_result?;
+    return Ok(());
+}
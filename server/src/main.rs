@@ -44,6 +44,9 @@ use tihu_native::http::HttpHandler;
 use tihu_native::http::RequestData;
 use tihu_native::ErrNo;
 
+//重新加密扩展配置的命令
+const ROTATE_CONFIGURATION_KEY_COMMAND: &str = "rotate-configuration-key";
+
 pub const VERSION_INFO: &'static str = include_str!("../version.txt");
 
 #[derive(RustEmbed)]
@@ -248,10 +251,29 @@ fn init_v8() {
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
     init_v8();
-    let config = Arguments::try_from_args()?;
-    let config = Config::try_load_from_file(&config.config_path)?;
+    let arguments = Arguments::try_from_args()?;
+    let config = Config::try_load_from_file(&arguments.config_path)?;
     let context = Context::try_init_from_config(config).await?;
     let context = Arc::new(context);
+    if let Some(command) = arguments.command.as_ref() {
+        match command.as_str() {
+            //轮换主密钥之后，用新的主密钥重新加密已保存的扩展配置
+            ROTATE_CONFIGURATION_KEY_COMMAND => {
+                action::environment::rotate_configuration_key::rotate_configuration_key(context)
+                    .await
+                    .map_err(|err| anyhow::anyhow!("{}", err.message()))?;
+                return Ok(());
+            }
+            _ => {
+                return Err(anyhow::anyhow!("不支持的命令: {}", command));
+            }
+        }
+    }
+    daemon::job_lease::start_job_lease_keeper(context.clone());
+    daemon::job_queue::start_job_queue(context.clone());
+    daemon::scheduler::start_scheduler(context.clone());
+    daemon::job_record_purger::start_job_record_purger(context.clone());
+    daemon::job_approval_expirer::start_job_approval_expirer(context.clone());
     let handler = get_handler(context.clone()).await?;
     let handler = Arc::new(handler);
     daemon::http::start_service(context, handler).await?;
@@ -2,9 +2,13 @@ use super::session::SessionId;
 use super::session::SessionState;
 use crate::get_context;
 use crate::json_response;
+use crate::model::user::enums::Role;
 use crate::native_common;
 use crate::native_common::cache::AsyncCache;
+use crate::route::JOB_SCOPED_NAMESPACE;
+use crate::route::SESSION_ONLY_NAMESPACE;
 use crate::route::WHITE_LIST_NAMESPACE;
+use crate::service::base::UserBaseService;
 use crate::Context;
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -23,6 +27,7 @@ use tihu::Handler;
 use tihu::Id;
 use tihu::LightString;
 use tihu::Middleware;
+use tihu_native::errno::open_transaction_error;
 use tihu_native::http::Body;
 use tihu_native::http::FromRequest;
 use tihu_native::http::RequestData;
@@ -89,6 +94,51 @@ pub struct User {
     pub session_id: SessionId,
     pub user_id: Id,
     pub org_id: Option<Id>,
+    pub role: Role, //组织内的角色，每次请求都从数据库读取，修改之后立即生效
+    pub token: Option<TokenScope>, //通过访问令牌调用时令牌的权限范围，会话登录时为None
+}
+
+/**
+ * 访问令牌的权限范围，令牌的权限不会超过所属用户的角色
+ */
+#[derive(Clone, Copy, Debug)]
+pub struct TokenScope {
+    pub token_id: Id,
+    pub execute: bool,    //是否可以执行任务，否则只能查看
+    pub job_scoped: bool, //是否只能执行令牌指定的任务
+}
+
+/**
+ * 受控api需要的权限，根据用户在组织内的角色判断
+ */
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Permission {
+    Read,    //查看组织内的配置和执行记录
+    Execute, //执行任务、处理手动步骤
+    Manage,  //管理环境规格、环境、任务、任务计划、执行器、成员和组织设置
+}
+
+pub fn has_permission(role: Role, permission: Permission) -> bool {
+    match permission {
+        Permission::Read => true,
+        Permission::Execute => Role::Viewer != role,
+        Permission::Manage => Role::Owner == role || Role::Admin == role,
+    }
+}
+
+//访问令牌不能调用管理类的api，只能执行指定任务的令牌只能调用和指定任务相关的执行类api
+fn token_allows(token: &TokenScope, namespace: &str, permission: Permission) -> bool {
+    if SESSION_ONLY_NAMESPACE.iter().any(|item| namespace == *item) {
+        return false;
+    }
+    match permission {
+        Permission::Read => true,
+        Permission::Execute => {
+            token.execute
+                && (!token.job_scoped || JOB_SCOPED_NAMESPACE.iter().any(|item| namespace == *item))
+        }
+        Permission::Manage => false,
+    }
 }
 
 #[derive(Clone, Copy)]
@@ -134,10 +184,13 @@ impl FromRequest for AuthLevel {
         let context = get_context()?;
         let session_info = get_session_data(&context, &session_id.to_string()).await?;
         let auth_level = if let Some(session_info) = session_info {
+            let role = get_user_role(&context, session_info.user_id).await?;
             AuthLevel::User(User {
                 session_id: session_id,
                 user_id: session_info.user_id,
                 org_id: session_info.org_id,
+                role: role,
+                token: None,
             })
         } else {
             AuthLevel::Guest(Guest {
@@ -202,6 +255,15 @@ async fn get_session_data(
     }
 }
 
+//用户不存在时按照只读成员处理
+async fn get_user_role(context: &Arc<Context>, user_id: Id) -> Result<Role, anyhow::Error> {
+    let mut client = context.get_db_client().await?;
+    let transaction = client.transaction().await.map_err(open_transaction_error)?;
+    let user_base_service = UserBaseService::new(&transaction);
+    let user_opt = user_base_service.read_user(user_id).await?;
+    return Ok(user_opt.map(|user| user.role).unwrap_or(Role::Viewer));
+}
+
 pub type In = (Request<Incoming>, SocketAddr, RequestData);
 pub type Out = Result<Response<Body>, anyhow::Error>;
 
@@ -370,11 +432,12 @@ where
 }
 
 /**
- * 调用受控api
+ * 调用受控api，用户必须属于某个组织，并且角色拥有api需要的权限
  */
 pub async fn try_call_user_api<F, I>(
     api: I,
     handler: impl Fn(Id, User, I::Input) -> F,
+    permission: Permission,
     user: User,
     req: &[u8],
 ) -> Result<I::Output, ErrNo>
@@ -385,6 +448,26 @@ where
     I::Output: Serialize,
 {
     let org_id = user.org_id.ok_or_else(|| ErrNo::NotAllowed)?;
+    if !has_permission(user.role, permission) {
+        log::warn!(
+            "用户{}的角色{:?}没有调用{}需要的权限{:?}",
+            user.user_id,
+            user.role,
+            I::namespace(),
+            permission
+        );
+        return Err(ErrNo::NotAllowed);
+    }
+    if let Some(token) = user.token.as_ref() {
+        if !token_allows(token, I::namespace().as_str(), permission) {
+            log::warn!(
+                "访问令牌{}的权限范围不允许调用{}",
+                token.token_id,
+                I::namespace()
+            );
+            return Err(ErrNo::NotAllowed);
+        }
+    }
     let req = get_and_validate_req(api, req)?;
     return handler(org_id, user, req).await;
 }
@@ -395,6 +478,7 @@ where
 pub async fn call_user_api<F, I>(
     api: I,
     handler: impl Fn(Id, User, I::Input) -> F,
+    permission: Permission,
     user: User,
     req: &[u8],
 ) -> Bytes
@@ -404,5 +488,47 @@ where
     I::Input: DeserializeOwned,
     I::Output: Serialize,
 {
-    result_to_json_resp(try_call_user_api(api, handler, user, req).await).into()
+    result_to_json_resp(try_call_user_api(api, handler, permission, user, req).await).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sdk;
+
+    fn token_scope(execute: bool, job_scoped: bool) -> TokenScope {
+        return TokenScope {
+            token_id: Id::from_u128(1),
+            execute: execute,
+            job_scoped: job_scoped,
+        };
+    }
+
+    #[test]
+    fn test_token_allows() {
+        let query_job = sdk::job::query_job::QUERY_JOB_API;
+        let start_job = sdk::job::start_job::START_JOB_API;
+        let cancel_job = sdk::job::cancel_job::CANCEL_JOB_API;
+        //只读令牌只能调用查看类的api
+        let token = token_scope(false, false);
+        assert!(token_allows(&token, query_job, Permission::Read));
+        assert!(!token_allows(&token, start_job, Permission::Execute));
+        //执行令牌不能调用管理类的api
+        let token = token_scope(true, false);
+        assert!(token_allows(&token, start_job, Permission::Execute));
+        assert!(token_allows(&token, cancel_job, Permission::Execute));
+        assert!(!token_allows(&token, query_job, Permission::Manage));
+        //只能执行指定任务的令牌，只能调用会检查任务的执行类api
+        let token = token_scope(true, true);
+        assert!(token_allows(&token, query_job, Permission::Read));
+        assert!(token_allows(&token, start_job, Permission::Execute));
+        assert!(!token_allows(&token, cancel_job, Permission::Execute));
+        //只能在登录会话里调用的api，令牌都不能调用
+        let change_password = sdk::auth::change_password::CHANGE_PASSWORD_API;
+        assert!(!token_allows(
+            &token_scope(true, false),
+            change_password,
+            Permission::Read
+        ));
+    }
 }
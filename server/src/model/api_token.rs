@@ -0,0 +1,248 @@
+use serde::{Serialize, Deserialize};
+use chrono::DateTime;
+use chrono::Utc;
+use tihu::datetime_format;
+use tihu::datetime_format_opt;
+use tihu::Id;
+use tihu::LightString;
+use native_common::model::Property;
+use native_common::model::PropertyDefine;
+use native_common::model::PropertyType;
+use crate::native_common;
+
+pub mod properties {
+    pub const ID: &str = "id";
+    pub const ORG_ID: &str = "org_id";
+    pub const USER_ID: &str = "user_id";
+    pub const NAME: &str = "name";
+    pub const TOKEN_PREFIX: &str = "token_prefix";
+    pub const TOKEN_HASH: &str = "token_hash";
+    pub const SCOPE: &str = "scope";
+    pub const JOB_IDS: &str = "job_ids";
+    pub const EXPIRE_TIME: &str = "expire_time";
+    pub const LAST_USED_TIME: &str = "last_used_time";
+    pub const CREATED_BY: &str = "created_by";
+    pub const CREATED_TIME: &str = "created_time";
+    pub const LAST_MODIFIED_TIME: &str = "last_modified_time";
+}
+
+pub mod enums {
+    use tihu::LightString;
+    use std::error::Error;
+    use serde::{Serialize, Deserialize};
+    use tokio_postgres::types::{ToSql, Type, IsNull, to_sql_checked};
+    use bytes::BytesMut;
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum Scope {
+        Read = 1, //只读
+        Execute = 2, //执行任务
+    }
+    pub fn try_i16_to_scope(val: i16) -> Result<Scope, LightString> {
+        match val {
+            1 => Ok(Scope::Read),
+            2 => Ok(Scope::Execute),
+            _ => Err(format!("未定义的令牌权限范围枚举值:{}", val).into())
+        }
+    }
+    impl ToSql for Scope {
+        fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+            (*self as i16).to_sql(ty, out)
+        }
+        fn accepts(ty: &Type) -> bool {
+            <i16 as ToSql>::accepts(ty)
+        }
+        to_sql_checked!();
+    }
+}
+
+
+/**
+ * 访问令牌列
+ */
+pub enum ApiTokenProperty {
+    Id(Id),
+    OrgId(Id),
+    UserId(Id),
+    Name(String),
+    TokenPrefix(String),
+    TokenHash(String),
+    Scope(enums::Scope),
+    JobIds(Option<String>),
+    ExpireTime(DateTime<Utc>),
+    LastUsedTime(Option<DateTime<Utc>>),
+    CreatedBy(Id),
+    CreatedTime(DateTime<Utc>),
+    LastModifiedTime(DateTime<Utc>),
+}
+
+impl Property for ApiTokenProperty {
+    fn property_define(&self) -> PropertyDefine {
+        match self {
+			ApiTokenProperty::Id(_) => PropertyDefine {
+                key: LightString::from_static(properties::ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			ApiTokenProperty::OrgId(_) => PropertyDefine {
+                key: LightString::from_static(properties::ORG_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			ApiTokenProperty::UserId(_) => PropertyDefine {
+                key: LightString::from_static(properties::USER_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			ApiTokenProperty::Name(_) => PropertyDefine {
+                key: LightString::from_static(properties::NAME),
+                value_type: PropertyType::String,
+				required: true,
+            },
+			ApiTokenProperty::TokenPrefix(_) => PropertyDefine {
+                key: LightString::from_static(properties::TOKEN_PREFIX),
+                value_type: PropertyType::String,
+				required: true,
+            },
+			ApiTokenProperty::TokenHash(_) => PropertyDefine {
+                key: LightString::from_static(properties::TOKEN_HASH),
+                value_type: PropertyType::String,
+				required: true,
+            },
+			ApiTokenProperty::Scope(_) => PropertyDefine {
+                key: LightString::from_static(properties::SCOPE),
+                value_type: PropertyType::Enum,
+				required: true,
+            },
+			ApiTokenProperty::JobIds(_) => PropertyDefine {
+                key: LightString::from_static(properties::JOB_IDS),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			ApiTokenProperty::ExpireTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::EXPIRE_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+			ApiTokenProperty::LastUsedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::LAST_USED_TIME),
+                value_type: PropertyType::DateTime,
+				required: false,
+            },
+			ApiTokenProperty::CreatedBy(_) => PropertyDefine {
+                key: LightString::from_static(properties::CREATED_BY),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			ApiTokenProperty::CreatedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::CREATED_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+			ApiTokenProperty::LastModifiedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::LAST_MODIFIED_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+        }
+    }
+}
+
+/**
+ * 访问令牌
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ApiToken {
+    pub id: Id, //令牌id
+    pub org_id: Id, //组织id
+    pub user_id: Id, //令牌所属用户id
+    pub name: String, //令牌名称
+    pub token_prefix: String, //令牌前缀，用于辨认令牌
+    pub token_hash: String, //令牌摘要
+    pub scope: enums::Scope, //令牌权限范围
+    pub job_ids: Option<String>, //可以执行的任务id列表，json格式
+    #[serde(with = "datetime_format")]
+    pub expire_time: DateTime<Utc>, //过期时间
+    #[serde(with = "datetime_format_opt")]
+    pub last_used_time: Option<DateTime<Utc>>, //最后使用时间
+    pub created_by: Id, //创建人id
+    #[serde(with = "datetime_format")]
+    pub created_time: DateTime<Utc>, //创建时间
+    #[serde(with = "datetime_format")]
+    pub last_modified_time: DateTime<Utc>, //更新时间
+}
+
+impl ApiToken {
+    pub fn into_properties(self) -> Vec<ApiTokenProperty> {
+        return vec![
+			ApiTokenProperty::Id(self.id),
+			ApiTokenProperty::OrgId(self.org_id),
+			ApiTokenProperty::UserId(self.user_id),
+			ApiTokenProperty::Name(self.name),
+			ApiTokenProperty::TokenPrefix(self.token_prefix),
+			ApiTokenProperty::TokenHash(self.token_hash),
+			ApiTokenProperty::Scope(self.scope),
+			ApiTokenProperty::JobIds(self.job_ids),
+			ApiTokenProperty::ExpireTime(self.expire_time),
+			ApiTokenProperty::LastUsedTime(self.last_used_time),
+			ApiTokenProperty::CreatedBy(self.created_by),
+			ApiTokenProperty::CreatedTime(self.created_time),
+			ApiTokenProperty::LastModifiedTime(self.last_modified_time),
+        ];
+    }
+}
+
+impl PartialEq<ApiTokenProperty> for ApiToken {
+    fn eq(&self, property: &ApiTokenProperty) -> bool {
+        match property {
+			ApiTokenProperty::Id(id) => id == &self.id,
+			ApiTokenProperty::OrgId(org_id) => org_id == &self.org_id,
+			ApiTokenProperty::UserId(user_id) => user_id == &self.user_id,
+			ApiTokenProperty::Name(name) => name == &self.name,
+			ApiTokenProperty::TokenPrefix(token_prefix) => token_prefix == &self.token_prefix,
+			ApiTokenProperty::TokenHash(token_hash) => token_hash == &self.token_hash,
+			ApiTokenProperty::Scope(scope) => scope == &self.scope,
+			ApiTokenProperty::JobIds(job_ids) => job_ids == &self.job_ids,
+			ApiTokenProperty::ExpireTime(expire_time) => expire_time == &self.expire_time,
+			ApiTokenProperty::LastUsedTime(last_used_time) => last_used_time == &self.last_used_time,
+			ApiTokenProperty::CreatedBy(created_by) => created_by == &self.created_by,
+			ApiTokenProperty::CreatedTime(created_time) => created_time == &self.created_time,
+			ApiTokenProperty::LastModifiedTime(last_modified_time) => last_modified_time == &self.last_modified_time,
+        }
+    }
+}
+
+pub struct ApiTokenOpt {
+    pub id: Option<Id>,
+    pub org_id: Option<Id>,
+    pub user_id: Option<Id>,
+    pub name: Option<String>,
+    pub token_prefix: Option<String>,
+    pub token_hash: Option<String>,
+    pub scope: Option<enums::Scope>,
+    pub job_ids: Option<String>,
+    pub expire_time: Option<DateTime<Utc>>,
+    pub last_used_time: Option<DateTime<Utc>>,
+    pub created_by: Option<Id>,
+    pub created_time: Option<DateTime<Utc>>,
+    pub last_modified_time: Option<DateTime<Utc>>,
+}
+
+impl ApiTokenOpt {
+    pub fn empty() -> ApiTokenOpt {
+        return ApiTokenOpt {
+            id: None,
+            org_id: None,
+            user_id: None,
+            name: None,
+            token_prefix: None,
+            token_hash: None,
+            scope: None,
+            job_ids: None,
+            expire_time: None,
+            last_used_time: None,
+            created_by: None,
+            created_time: None,
+            last_modified_time: None,
+        };
+    }
+}
\ No newline at end of file
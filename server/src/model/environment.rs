@@ -15,6 +15,10 @@ pub mod properties {
     pub const ORG_ID: &str = "org_id";
     pub const ENVIRONMENT_SCHEMA_ID: &str = "environment_schema_id";
     pub const NAME: &str = "name";
+    pub const LOCK_POLICY: &str = "lock_policy";
+    pub const RUNNER_LABEL: &str = "runner_label";
+    pub const PROTECTED: &str = "protected";
+    pub const REQUIRED_APPROVALS: &str = "required_approvals";
     pub const CREATED_TIME: &str = "created_time";
     pub const LAST_MODIFIED_TIME: &str = "last_modified_time";
 }
@@ -25,6 +29,27 @@ pub mod enums {
     use serde::{Serialize, Deserialize};
     use tokio_postgres::types::{ToSql, Type, IsNull, to_sql_checked};
     use bytes::BytesMut;
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum LockPolicy {
+        Reject = 1, //拒绝执行
+        Queue = 2, //排队等待
+    }
+    pub fn try_i16_to_lock_policy(val: i16) -> Result<LockPolicy, LightString> {
+        match val {
+            1 => Ok(LockPolicy::Reject),
+            2 => Ok(LockPolicy::Queue),
+            _ => Err(format!("未定义的环境被占用时的处理方式枚举值:{}", val).into())
+        }
+    }
+    impl ToSql for LockPolicy {
+        fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+            (*self as i16).to_sql(ty, out)
+        }
+        fn accepts(ty: &Type) -> bool {
+            <i16 as ToSql>::accepts(ty)
+        }
+        to_sql_checked!();
+    }
 }
 
 
@@ -36,6 +61,10 @@ pub enum EnvironmentProperty {
     OrgId(Id),
     EnvironmentSchemaId(Id),
     Name(String),
+    LockPolicy(enums::LockPolicy),
+    RunnerLabel(Option<String>),
+    Protected(bool),
+    RequiredApprovals(i32),
     CreatedTime(DateTime<Utc>),
     LastModifiedTime(DateTime<Utc>),
 }
@@ -62,6 +91,26 @@ impl Property for EnvironmentProperty {
                 key: LightString::from_static(properties::NAME),
                 value_type: PropertyType::String,
 				required: true,
+            },
+			EnvironmentProperty::LockPolicy(_) => PropertyDefine {
+                key: LightString::from_static(properties::LOCK_POLICY),
+                value_type: PropertyType::Enum,
+				required: true,
+            },
+			EnvironmentProperty::RunnerLabel(_) => PropertyDefine {
+                key: LightString::from_static(properties::RUNNER_LABEL),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			EnvironmentProperty::Protected(_) => PropertyDefine {
+                key: LightString::from_static(properties::PROTECTED),
+                value_type: PropertyType::Bool,
+				required: true,
+            },
+			EnvironmentProperty::RequiredApprovals(_) => PropertyDefine {
+                key: LightString::from_static(properties::REQUIRED_APPROVALS),
+                value_type: PropertyType::Integer,
+				required: true,
             },
 			EnvironmentProperty::CreatedTime(_) => PropertyDefine {
                 key: LightString::from_static(properties::CREATED_TIME),
@@ -86,6 +135,10 @@ pub struct Environment {
     pub org_id: Id, //组织id
     pub environment_schema_id: Id, //环境规格id
     pub name: String, //环境名称
+    pub lock_policy: enums::LockPolicy, //环境被占用时的处理方式
+    pub runner_label: Option<String>, //执行器标签，为空时在服务端执行
+    pub protected: bool, //是否受保护，受保护的环境只有单独授权的用户才能执行任务、修改资源和审批
+    pub required_approvals: i32, //执行前需要的审批人数，只对受保护的环境生效，0表示不需要审批
     #[serde(with = "datetime_format")]
     pub created_time: DateTime<Utc>, //创建时间
     #[serde(with = "datetime_format")]
@@ -99,6 +152,10 @@ impl Environment {
 			EnvironmentProperty::OrgId(self.org_id),
 			EnvironmentProperty::EnvironmentSchemaId(self.environment_schema_id),
 			EnvironmentProperty::Name(self.name),
+			EnvironmentProperty::LockPolicy(self.lock_policy),
+			EnvironmentProperty::RunnerLabel(self.runner_label),
+			EnvironmentProperty::Protected(self.protected),
+			EnvironmentProperty::RequiredApprovals(self.required_approvals),
 			EnvironmentProperty::CreatedTime(self.created_time),
 			EnvironmentProperty::LastModifiedTime(self.last_modified_time),
         ];
@@ -112,6 +169,10 @@ impl PartialEq<EnvironmentProperty> for Environment {
 			EnvironmentProperty::OrgId(org_id) => org_id == &self.org_id,
 			EnvironmentProperty::EnvironmentSchemaId(environment_schema_id) => environment_schema_id == &self.environment_schema_id,
 			EnvironmentProperty::Name(name) => name == &self.name,
+			EnvironmentProperty::LockPolicy(lock_policy) => lock_policy == &self.lock_policy,
+			EnvironmentProperty::RunnerLabel(runner_label) => runner_label == &self.runner_label,
+			EnvironmentProperty::Protected(protected) => protected == &self.protected,
+			EnvironmentProperty::RequiredApprovals(required_approvals) => required_approvals == &self.required_approvals,
 			EnvironmentProperty::CreatedTime(created_time) => created_time == &self.created_time,
 			EnvironmentProperty::LastModifiedTime(last_modified_time) => last_modified_time == &self.last_modified_time,
         }
@@ -123,6 +184,10 @@ pub struct EnvironmentOpt {
     pub org_id: Option<Id>,
     pub environment_schema_id: Option<Id>,
     pub name: Option<String>,
+    pub lock_policy: Option<enums::LockPolicy>,
+    pub runner_label: Option<String>,
+    pub protected: Option<bool>,
+    pub required_approvals: Option<i32>,
     pub created_time: Option<DateTime<Utc>>,
     pub last_modified_time: Option<DateTime<Utc>>,
 }
@@ -134,6 +199,10 @@ impl EnvironmentOpt {
             org_id: None,
             environment_schema_id: None,
             name: None,
+            lock_policy: None,
+            runner_label: None,
+            protected: None,
+            required_approvals: None,
             created_time: None,
             last_modified_time: None,
         };
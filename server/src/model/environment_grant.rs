@@ -0,0 +1,176 @@
+use serde::{Serialize, Deserialize};
+use chrono::DateTime;
+use chrono::Utc;
+use tihu::datetime_format;
+use tihu::datetime_format_opt;
+use tihu::Id;
+use tihu::LightString;
+use native_common::model::Property;
+use native_common::model::PropertyDefine;
+use native_common::model::PropertyType;
+use crate::native_common;
+
+pub mod properties {
+    pub const ID: &str = "id";
+    pub const ORG_ID: &str = "org_id";
+    pub const ENVIRONMENT_ID: &str = "environment_id";
+    pub const USER_ID: &str = "user_id";
+    pub const PERMISSION: &str = "permission";
+    pub const CREATED_TIME: &str = "created_time";
+    pub const LAST_MODIFIED_TIME: &str = "last_modified_time";
+}
+
+pub mod enums {
+    use tihu::LightString;
+    use std::error::Error;
+    use serde::{Serialize, Deserialize};
+    use tokio_postgres::types::{ToSql, Type, IsNull, to_sql_checked};
+    use bytes::BytesMut;
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum GrantPermission {
+        Run = 1, //执行任务
+        Edit = 2, //修改资源
+        Approve = 3, //审批
+    }
+    pub fn try_i16_to_grant_permission(val: i16) -> Result<GrantPermission, LightString> {
+        match val {
+            1 => Ok(GrantPermission::Run),
+            2 => Ok(GrantPermission::Edit),
+            3 => Ok(GrantPermission::Approve),
+            _ => Err(format!("未定义的环境授权的权限枚举值:{}", val).into())
+        }
+    }
+    impl ToSql for GrantPermission {
+        fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+            (*self as i16).to_sql(ty, out)
+        }
+        fn accepts(ty: &Type) -> bool {
+            <i16 as ToSql>::accepts(ty)
+        }
+        to_sql_checked!();
+    }
+}
+
+
+/**
+ * 环境授权列
+ */
+pub enum EnvironmentGrantProperty {
+    Id(Id),
+    OrgId(Id),
+    EnvironmentId(Id),
+    UserId(Id),
+    Permission(enums::GrantPermission),
+    CreatedTime(DateTime<Utc>),
+    LastModifiedTime(DateTime<Utc>),
+}
+
+impl Property for EnvironmentGrantProperty {
+    fn property_define(&self) -> PropertyDefine {
+        match self {
+			EnvironmentGrantProperty::Id(_) => PropertyDefine {
+                key: LightString::from_static(properties::ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			EnvironmentGrantProperty::OrgId(_) => PropertyDefine {
+                key: LightString::from_static(properties::ORG_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			EnvironmentGrantProperty::EnvironmentId(_) => PropertyDefine {
+                key: LightString::from_static(properties::ENVIRONMENT_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			EnvironmentGrantProperty::UserId(_) => PropertyDefine {
+                key: LightString::from_static(properties::USER_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			EnvironmentGrantProperty::Permission(_) => PropertyDefine {
+                key: LightString::from_static(properties::PERMISSION),
+                value_type: PropertyType::Enum,
+				required: true,
+            },
+			EnvironmentGrantProperty::CreatedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::CREATED_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+			EnvironmentGrantProperty::LastModifiedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::LAST_MODIFIED_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+        }
+    }
+}
+
+/**
+ * 环境授权
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EnvironmentGrant {
+    pub id: Id, //授权id
+    pub org_id: Id, //组织id
+    pub environment_id: Id, //环境id
+    pub user_id: Id, //被授权的用户id
+    pub permission: enums::GrantPermission, //授予的权限
+    #[serde(with = "datetime_format")]
+    pub created_time: DateTime<Utc>, //创建时间
+    #[serde(with = "datetime_format")]
+    pub last_modified_time: DateTime<Utc>, //更新时间
+}
+
+impl EnvironmentGrant {
+    pub fn into_properties(self) -> Vec<EnvironmentGrantProperty> {
+        return vec![
+			EnvironmentGrantProperty::Id(self.id),
+			EnvironmentGrantProperty::OrgId(self.org_id),
+			EnvironmentGrantProperty::EnvironmentId(self.environment_id),
+			EnvironmentGrantProperty::UserId(self.user_id),
+			EnvironmentGrantProperty::Permission(self.permission),
+			EnvironmentGrantProperty::CreatedTime(self.created_time),
+			EnvironmentGrantProperty::LastModifiedTime(self.last_modified_time),
+        ];
+    }
+}
+
+impl PartialEq<EnvironmentGrantProperty> for EnvironmentGrant {
+    fn eq(&self, property: &EnvironmentGrantProperty) -> bool {
+        match property {
+			EnvironmentGrantProperty::Id(id) => id == &self.id,
+			EnvironmentGrantProperty::OrgId(org_id) => org_id == &self.org_id,
+			EnvironmentGrantProperty::EnvironmentId(environment_id) => environment_id == &self.environment_id,
+			EnvironmentGrantProperty::UserId(user_id) => user_id == &self.user_id,
+			EnvironmentGrantProperty::Permission(permission) => permission == &self.permission,
+			EnvironmentGrantProperty::CreatedTime(created_time) => created_time == &self.created_time,
+			EnvironmentGrantProperty::LastModifiedTime(last_modified_time) => last_modified_time == &self.last_modified_time,
+        }
+    }
+}
+
+pub struct EnvironmentGrantOpt {
+    pub id: Option<Id>,
+    pub org_id: Option<Id>,
+    pub environment_id: Option<Id>,
+    pub user_id: Option<Id>,
+    pub permission: Option<enums::GrantPermission>,
+    pub created_time: Option<DateTime<Utc>>,
+    pub last_modified_time: Option<DateTime<Utc>>,
+}
+
+impl EnvironmentGrantOpt {
+    pub fn empty() -> EnvironmentGrantOpt {
+        return EnvironmentGrantOpt {
+            id: None,
+            org_id: None,
+            environment_id: None,
+            user_id: None,
+            permission: None,
+            created_time: None,
+            last_modified_time: None,
+        };
+    }
+}
\ No newline at end of file
@@ -0,0 +1,210 @@
+use serde::{Serialize, Deserialize};
+use chrono::DateTime;
+use chrono::Utc;
+use tihu::datetime_format;
+use tihu::datetime_format_opt;
+use tihu::Id;
+use tihu::LightString;
+use native_common::model::Property;
+use native_common::model::PropertyDefine;
+use native_common::model::PropertyType;
+use crate::native_common;
+
+pub mod properties {
+    pub const ID: &str = "id";
+    pub const ORG_ID: &str = "org_id";
+    pub const ENVIRONMENT_ID: &str = "environment_id";
+    pub const RECORD_ID: &str = "record_id";
+    pub const JOB_ID: &str = "job_id";
+    pub const HOLDER_ID: &str = "holder_id";
+    pub const HOLDER_NAME: &str = "holder_name";
+    pub const STATUS: &str = "status";
+    pub const CREATED_TIME: &str = "created_time";
+    pub const LAST_MODIFIED_TIME: &str = "last_modified_time";
+}
+
+pub mod enums {
+    use tihu::LightString;
+    use std::error::Error;
+    use serde::{Serialize, Deserialize};
+    use tokio_postgres::types::{ToSql, Type, IsNull, to_sql_checked};
+    use bytes::BytesMut;
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum Status {
+        Holding = 1, //占用中
+        Waiting = 2, //等待中
+    }
+    pub fn try_i16_to_status(val: i16) -> Result<Status, LightString> {
+        match val {
+            1 => Ok(Status::Holding),
+            2 => Ok(Status::Waiting),
+            _ => Err(format!("未定义的占用状态枚举值:{}", val).into())
+        }
+    }
+    impl ToSql for Status {
+        fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+            (*self as i16).to_sql(ty, out)
+        }
+        fn accepts(ty: &Type) -> bool {
+            <i16 as ToSql>::accepts(ty)
+        }
+        to_sql_checked!();
+    }
+}
+
+
+/**
+ * 环境占用列
+ */
+pub enum EnvironmentLockProperty {
+    Id(Id),
+    OrgId(Id),
+    EnvironmentId(Id),
+    RecordId(Id),
+    JobId(Id),
+    HolderId(Option<Id>),
+    HolderName(String),
+    Status(enums::Status),
+    CreatedTime(DateTime<Utc>),
+    LastModifiedTime(DateTime<Utc>),
+}
+
+impl Property for EnvironmentLockProperty {
+    fn property_define(&self) -> PropertyDefine {
+        match self {
+			EnvironmentLockProperty::Id(_) => PropertyDefine {
+                key: LightString::from_static(properties::ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			EnvironmentLockProperty::OrgId(_) => PropertyDefine {
+                key: LightString::from_static(properties::ORG_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			EnvironmentLockProperty::EnvironmentId(_) => PropertyDefine {
+                key: LightString::from_static(properties::ENVIRONMENT_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			EnvironmentLockProperty::RecordId(_) => PropertyDefine {
+                key: LightString::from_static(properties::RECORD_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			EnvironmentLockProperty::JobId(_) => PropertyDefine {
+                key: LightString::from_static(properties::JOB_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			EnvironmentLockProperty::HolderId(_) => PropertyDefine {
+                key: LightString::from_static(properties::HOLDER_ID),
+                value_type: PropertyType::Id,
+				required: false,
+            },
+			EnvironmentLockProperty::HolderName(_) => PropertyDefine {
+                key: LightString::from_static(properties::HOLDER_NAME),
+                value_type: PropertyType::String,
+				required: true,
+            },
+			EnvironmentLockProperty::Status(_) => PropertyDefine {
+                key: LightString::from_static(properties::STATUS),
+                value_type: PropertyType::Enum,
+				required: true,
+            },
+			EnvironmentLockProperty::CreatedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::CREATED_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+			EnvironmentLockProperty::LastModifiedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::LAST_MODIFIED_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+        }
+    }
+}
+
+/**
+ * 环境占用
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct EnvironmentLock {
+    pub id: Id, //环境占用id
+    pub org_id: Id, //组织id
+    pub environment_id: Id, //环境id
+    pub record_id: Id, //任务执行记录id
+    pub job_id: Id, //任务id
+    pub holder_id: Option<Id>, //发起执行的用户id
+    pub holder_name: String, //发起执行的人
+    pub status: enums::Status, //占用状态
+    #[serde(with = "datetime_format")]
+    pub created_time: DateTime<Utc>, //创建时间
+    #[serde(with = "datetime_format")]
+    pub last_modified_time: DateTime<Utc>, //更新时间
+}
+
+impl EnvironmentLock {
+    pub fn into_properties(self) -> Vec<EnvironmentLockProperty> {
+        return vec![
+			EnvironmentLockProperty::Id(self.id),
+			EnvironmentLockProperty::OrgId(self.org_id),
+			EnvironmentLockProperty::EnvironmentId(self.environment_id),
+			EnvironmentLockProperty::RecordId(self.record_id),
+			EnvironmentLockProperty::JobId(self.job_id),
+			EnvironmentLockProperty::HolderId(self.holder_id),
+			EnvironmentLockProperty::HolderName(self.holder_name),
+			EnvironmentLockProperty::Status(self.status),
+			EnvironmentLockProperty::CreatedTime(self.created_time),
+			EnvironmentLockProperty::LastModifiedTime(self.last_modified_time),
+        ];
+    }
+}
+
+impl PartialEq<EnvironmentLockProperty> for EnvironmentLock {
+    fn eq(&self, property: &EnvironmentLockProperty) -> bool {
+        match property {
+			EnvironmentLockProperty::Id(id) => id == &self.id,
+			EnvironmentLockProperty::OrgId(org_id) => org_id == &self.org_id,
+			EnvironmentLockProperty::EnvironmentId(environment_id) => environment_id == &self.environment_id,
+			EnvironmentLockProperty::RecordId(record_id) => record_id == &self.record_id,
+			EnvironmentLockProperty::JobId(job_id) => job_id == &self.job_id,
+			EnvironmentLockProperty::HolderId(holder_id) => holder_id == &self.holder_id,
+			EnvironmentLockProperty::HolderName(holder_name) => holder_name == &self.holder_name,
+			EnvironmentLockProperty::Status(status) => status == &self.status,
+			EnvironmentLockProperty::CreatedTime(created_time) => created_time == &self.created_time,
+			EnvironmentLockProperty::LastModifiedTime(last_modified_time) => last_modified_time == &self.last_modified_time,
+        }
+    }
+}
+
+pub struct EnvironmentLockOpt {
+    pub id: Option<Id>,
+    pub org_id: Option<Id>,
+    pub environment_id: Option<Id>,
+    pub record_id: Option<Id>,
+    pub job_id: Option<Id>,
+    pub holder_id: Option<Id>,
+    pub holder_name: Option<String>,
+    pub status: Option<enums::Status>,
+    pub created_time: Option<DateTime<Utc>>,
+    pub last_modified_time: Option<DateTime<Utc>>,
+}
+
+impl EnvironmentLockOpt {
+    pub fn empty() -> EnvironmentLockOpt {
+        return EnvironmentLockOpt {
+            id: None,
+            org_id: None,
+            environment_id: None,
+            record_id: None,
+            job_id: None,
+            holder_id: None,
+            holder_name: None,
+            status: None,
+            created_time: None,
+            last_modified_time: None,
+        };
+    }
+}
\ No newline at end of file
@@ -16,6 +16,8 @@ pub mod properties {
     pub const ENVIRONMENT_SCHEMA_ID: &str = "environment_schema_id";
     pub const NAME: &str = "name";
     pub const REMARK: &str = "remark";
+    pub const INTERRUPT_POLICY: &str = "interrupt_policy";
+    pub const PARAMETER_SCHEMA: &str = "parameter_schema";
     pub const CREATED_TIME: &str = "created_time";
     pub const LAST_MODIFIED_TIME: &str = "last_modified_time";
 }
@@ -26,6 +28,27 @@ pub mod enums {
     use serde::{Serialize, Deserialize};
     use tokio_postgres::types::{ToSql, Type, IsNull, to_sql_checked};
     use bytes::BytesMut;
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum InterruptPolicy {
+        Interrupt = 1, //标记中断
+        Resume = 2, //继续执行
+    }
+    pub fn try_i16_to_interrupt_policy(val: i16) -> Result<InterruptPolicy, LightString> {
+        match val {
+            1 => Ok(InterruptPolicy::Interrupt),
+            2 => Ok(InterruptPolicy::Resume),
+            _ => Err(format!("未定义的中断策略枚举值:{}", val).into())
+        }
+    }
+    impl ToSql for InterruptPolicy {
+        fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+            (*self as i16).to_sql(ty, out)
+        }
+        fn accepts(ty: &Type) -> bool {
+            <i16 as ToSql>::accepts(ty)
+        }
+        to_sql_checked!();
+    }
 }
 
 
@@ -38,6 +61,8 @@ pub enum JobProperty {
     EnvironmentSchemaId(Id),
     Name(String),
     Remark(Option<String>),
+    InterruptPolicy(enums::InterruptPolicy),
+    ParameterSchema(String),
     CreatedTime(DateTime<Utc>),
     LastModifiedTime(DateTime<Utc>),
 }
@@ -69,6 +94,16 @@ impl Property for JobProperty {
                 key: LightString::from_static(properties::REMARK),
                 value_type: PropertyType::String,
 				required: false,
+            },
+			JobProperty::InterruptPolicy(_) => PropertyDefine {
+                key: LightString::from_static(properties::INTERRUPT_POLICY),
+                value_type: PropertyType::Enum,
+				required: true,
+            },
+			JobProperty::ParameterSchema(_) => PropertyDefine {
+                key: LightString::from_static(properties::PARAMETER_SCHEMA),
+                value_type: PropertyType::String,
+				required: true,
             },
 			JobProperty::CreatedTime(_) => PropertyDefine {
                 key: LightString::from_static(properties::CREATED_TIME),
@@ -94,6 +129,8 @@ pub struct Job {
     pub environment_schema_id: Id, //环境规格id
     pub name: String, //任务名称
     pub remark: Option<String>, //备注
+    pub interrupt_policy: enums::InterruptPolicy, //中断策略
+    pub parameter_schema: String, //任务参数定义
     #[serde(with = "datetime_format")]
     pub created_time: DateTime<Utc>, //创建时间
     #[serde(with = "datetime_format")]
@@ -108,6 +145,8 @@ impl Job {
 			JobProperty::EnvironmentSchemaId(self.environment_schema_id),
 			JobProperty::Name(self.name),
 			JobProperty::Remark(self.remark),
+			JobProperty::InterruptPolicy(self.interrupt_policy),
+			JobProperty::ParameterSchema(self.parameter_schema),
 			JobProperty::CreatedTime(self.created_time),
 			JobProperty::LastModifiedTime(self.last_modified_time),
         ];
@@ -122,6 +161,8 @@ impl PartialEq<JobProperty> for Job {
 			JobProperty::EnvironmentSchemaId(environment_schema_id) => environment_schema_id == &self.environment_schema_id,
 			JobProperty::Name(name) => name == &self.name,
 			JobProperty::Remark(remark) => remark == &self.remark,
+			JobProperty::InterruptPolicy(interrupt_policy) => interrupt_policy == &self.interrupt_policy,
+			JobProperty::ParameterSchema(parameter_schema) => parameter_schema == &self.parameter_schema,
 			JobProperty::CreatedTime(created_time) => created_time == &self.created_time,
 			JobProperty::LastModifiedTime(last_modified_time) => last_modified_time == &self.last_modified_time,
         }
@@ -134,6 +175,8 @@ pub struct JobOpt {
     pub environment_schema_id: Option<Id>,
     pub name: Option<String>,
     pub remark: Option<String>,
+    pub interrupt_policy: Option<enums::InterruptPolicy>,
+    pub parameter_schema: Option<String>,
     pub created_time: Option<DateTime<Utc>>,
     pub last_modified_time: Option<DateTime<Utc>>,
 }
@@ -146,6 +189,8 @@ impl JobOpt {
             environment_schema_id: None,
             name: None,
             remark: None,
+            interrupt_policy: None,
+            parameter_schema: None,
             created_time: None,
             last_modified_time: None,
         };
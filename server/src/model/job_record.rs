@@ -15,7 +15,19 @@ pub mod properties {
     pub const ORG_ID: &str = "org_id";
     pub const JOB_ID: &str = "job_id";
     pub const ENVIRONMENT_ID: &str = "environment_id";
+    pub const ORIGIN_RECORD_ID: &str = "origin_record_id";
+    pub const ATTEMPT: &str = "attempt";
     pub const STATUS: &str = "status";
+    pub const PINNED: &str = "pinned";
+    pub const CREATED_BY: &str = "created_by";
+    pub const TRIGGER_TYPE: &str = "trigger_type";
+    pub const CHANGE_NOTE: &str = "change_note";
+    pub const REQUIRED_APPROVALS: &str = "required_approvals";
+    pub const PARAMS: &str = "params";
+    pub const SECRET_PARAMS: &str = "secret_params";
+    pub const INSTANCE_ID: &str = "instance_id";
+    pub const HEARTBEAT_TIME: &str = "heartbeat_time";
+    pub const CANCEL_REQUESTED: &str = "cancel_requested";
     pub const CREATED_TIME: &str = "created_time";
     pub const LAST_MODIFIED_TIME: &str = "last_modified_time";
 }
@@ -31,12 +43,24 @@ pub mod enums {
         Running = 1, //进行中
         Success = 2, //成功
         Failure = 3, //失败
+        Interrupted = 4, //已中断
+        Cancelled = 5, //已取消
+        Queued = 6, //排队中
+        PendingApproval = 7, //待审批
+        Rejected = 8, //已驳回
+        Expired = 9, //审批已过期
     }
     pub fn try_i16_to_status(val: i16) -> Result<Status, LightString> {
         match val {
             1 => Ok(Status::Running),
             2 => Ok(Status::Success),
             3 => Ok(Status::Failure),
+            4 => Ok(Status::Interrupted),
+            5 => Ok(Status::Cancelled),
+            6 => Ok(Status::Queued),
+            7 => Ok(Status::PendingApproval),
+            8 => Ok(Status::Rejected),
+            9 => Ok(Status::Expired),
             _ => Err(format!("未定义的状态枚举值:{}", val).into())
         }
     }
@@ -49,6 +73,33 @@ pub mod enums {
         }
         to_sql_checked!();
     }
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum TriggerType {
+        Manual = 1, //手动
+        Schedule = 2, //定时
+        Webhook = 3, //Webhook
+        Retry = 4, //重新执行
+        ApiToken = 5, //访问令牌
+    }
+    pub fn try_i16_to_trigger_type(val: i16) -> Result<TriggerType, LightString> {
+        match val {
+            1 => Ok(TriggerType::Manual),
+            2 => Ok(TriggerType::Schedule),
+            3 => Ok(TriggerType::Webhook),
+            4 => Ok(TriggerType::Retry),
+            5 => Ok(TriggerType::ApiToken),
+            _ => Err(format!("未定义的触发方式枚举值:{}", val).into())
+        }
+    }
+    impl ToSql for TriggerType {
+        fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+            (*self as i16).to_sql(ty, out)
+        }
+        fn accepts(ty: &Type) -> bool {
+            <i16 as ToSql>::accepts(ty)
+        }
+        to_sql_checked!();
+    }
 }
 
 
@@ -60,7 +111,19 @@ pub enum JobRecordProperty {
     OrgId(Id),
     JobId(Id),
     EnvironmentId(Id),
+    OriginRecordId(Option<Id>),
+    Attempt(i32),
     Status(enums::Status),
+    Pinned(bool),
+    CreatedBy(Option<Id>),
+    TriggerType(enums::TriggerType),
+    ChangeNote(Option<String>),
+    RequiredApprovals(Option<i32>),
+    Params(Option<String>),
+    SecretParams(Option<String>),
+    InstanceId(Option<Id>),
+    HeartbeatTime(Option<DateTime<Utc>>),
+    CancelRequested(bool),
     CreatedTime(DateTime<Utc>),
     LastModifiedTime(DateTime<Utc>),
 }
@@ -87,11 +150,71 @@ impl Property for JobRecordProperty {
                 key: LightString::from_static(properties::ENVIRONMENT_ID),
                 value_type: PropertyType::Id,
 				required: true,
+            },
+			JobRecordProperty::OriginRecordId(_) => PropertyDefine {
+                key: LightString::from_static(properties::ORIGIN_RECORD_ID),
+                value_type: PropertyType::Id,
+				required: false,
+            },
+			JobRecordProperty::Attempt(_) => PropertyDefine {
+                key: LightString::from_static(properties::ATTEMPT),
+                value_type: PropertyType::Integer,
+				required: true,
             },
 			JobRecordProperty::Status(_) => PropertyDefine {
                 key: LightString::from_static(properties::STATUS),
                 value_type: PropertyType::Enum,
 				required: true,
+            },
+			JobRecordProperty::Pinned(_) => PropertyDefine {
+                key: LightString::from_static(properties::PINNED),
+                value_type: PropertyType::Bool,
+				required: true,
+            },
+			JobRecordProperty::CreatedBy(_) => PropertyDefine {
+                key: LightString::from_static(properties::CREATED_BY),
+                value_type: PropertyType::Id,
+				required: false,
+            },
+			JobRecordProperty::TriggerType(_) => PropertyDefine {
+                key: LightString::from_static(properties::TRIGGER_TYPE),
+                value_type: PropertyType::Enum,
+				required: true,
+            },
+			JobRecordProperty::ChangeNote(_) => PropertyDefine {
+                key: LightString::from_static(properties::CHANGE_NOTE),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			JobRecordProperty::RequiredApprovals(_) => PropertyDefine {
+                key: LightString::from_static(properties::REQUIRED_APPROVALS),
+                value_type: PropertyType::Integer,
+				required: false,
+            },
+			JobRecordProperty::Params(_) => PropertyDefine {
+                key: LightString::from_static(properties::PARAMS),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			JobRecordProperty::SecretParams(_) => PropertyDefine {
+                key: LightString::from_static(properties::SECRET_PARAMS),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			JobRecordProperty::InstanceId(_) => PropertyDefine {
+                key: LightString::from_static(properties::INSTANCE_ID),
+                value_type: PropertyType::Id,
+				required: false,
+            },
+			JobRecordProperty::HeartbeatTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::HEARTBEAT_TIME),
+                value_type: PropertyType::DateTime,
+				required: false,
+            },
+			JobRecordProperty::CancelRequested(_) => PropertyDefine {
+                key: LightString::from_static(properties::CANCEL_REQUESTED),
+                value_type: PropertyType::Bool,
+				required: true,
             },
 			JobRecordProperty::CreatedTime(_) => PropertyDefine {
                 key: LightString::from_static(properties::CREATED_TIME),
@@ -116,7 +239,20 @@ pub struct JobRecord {
     pub org_id: Id, //组织id
     pub job_id: Id, //任务id
     pub environment_id: Id, //环境id
+    pub origin_record_id: Option<Id>, //最初的执行记录id，重新执行产生的记录才有
+    pub attempt: i32, //第几次执行，从1开始
     pub status: enums::Status, //执行状态
+    pub pinned: bool, //是否置顶，置顶的记录不会被清理
+    pub created_by: Option<Id>, //发起执行的用户id，定时触发时为空
+    pub trigger_type: enums::TriggerType, //触发方式
+    pub change_note: Option<String>, //变更说明
+    pub required_approvals: Option<i32>, //需要的审批人数，不需要审批时为空
+    pub params: Option<String>, //执行参数，密码类型的参数替换成掩码
+    pub secret_params: Option<String>, //加密保存的密码类型的参数
+    pub instance_id: Option<Id>, //正在执行的服务实例id
+    #[serde(with = "datetime_format_opt")]
+    pub heartbeat_time: Option<DateTime<Utc>>, //执行实例最后一次续约的时间
+    pub cancel_requested: bool, //是否请求了取消，由正在执行的实例取消
     #[serde(with = "datetime_format")]
     pub created_time: DateTime<Utc>, //创建时间
     #[serde(with = "datetime_format")]
@@ -130,7 +266,19 @@ impl JobRecord {
 			JobRecordProperty::OrgId(self.org_id),
 			JobRecordProperty::JobId(self.job_id),
 			JobRecordProperty::EnvironmentId(self.environment_id),
+			JobRecordProperty::OriginRecordId(self.origin_record_id),
+			JobRecordProperty::Attempt(self.attempt),
 			JobRecordProperty::Status(self.status),
+			JobRecordProperty::Pinned(self.pinned),
+			JobRecordProperty::CreatedBy(self.created_by),
+			JobRecordProperty::TriggerType(self.trigger_type),
+			JobRecordProperty::ChangeNote(self.change_note),
+			JobRecordProperty::RequiredApprovals(self.required_approvals),
+			JobRecordProperty::Params(self.params),
+			JobRecordProperty::SecretParams(self.secret_params),
+			JobRecordProperty::InstanceId(self.instance_id),
+			JobRecordProperty::HeartbeatTime(self.heartbeat_time),
+			JobRecordProperty::CancelRequested(self.cancel_requested),
 			JobRecordProperty::CreatedTime(self.created_time),
 			JobRecordProperty::LastModifiedTime(self.last_modified_time),
         ];
@@ -144,7 +292,19 @@ impl PartialEq<JobRecordProperty> for JobRecord {
 			JobRecordProperty::OrgId(org_id) => org_id == &self.org_id,
 			JobRecordProperty::JobId(job_id) => job_id == &self.job_id,
 			JobRecordProperty::EnvironmentId(environment_id) => environment_id == &self.environment_id,
+			JobRecordProperty::OriginRecordId(origin_record_id) => origin_record_id == &self.origin_record_id,
+			JobRecordProperty::Attempt(attempt) => attempt == &self.attempt,
 			JobRecordProperty::Status(status) => status == &self.status,
+			JobRecordProperty::Pinned(pinned) => pinned == &self.pinned,
+			JobRecordProperty::CreatedBy(created_by) => created_by == &self.created_by,
+			JobRecordProperty::TriggerType(trigger_type) => trigger_type == &self.trigger_type,
+			JobRecordProperty::ChangeNote(change_note) => change_note == &self.change_note,
+			JobRecordProperty::RequiredApprovals(required_approvals) => required_approvals == &self.required_approvals,
+			JobRecordProperty::Params(params) => params == &self.params,
+			JobRecordProperty::SecretParams(secret_params) => secret_params == &self.secret_params,
+			JobRecordProperty::InstanceId(instance_id) => instance_id == &self.instance_id,
+			JobRecordProperty::HeartbeatTime(heartbeat_time) => heartbeat_time == &self.heartbeat_time,
+			JobRecordProperty::CancelRequested(cancel_requested) => cancel_requested == &self.cancel_requested,
 			JobRecordProperty::CreatedTime(created_time) => created_time == &self.created_time,
 			JobRecordProperty::LastModifiedTime(last_modified_time) => last_modified_time == &self.last_modified_time,
         }
@@ -156,7 +316,19 @@ pub struct JobRecordOpt {
     pub org_id: Option<Id>,
     pub job_id: Option<Id>,
     pub environment_id: Option<Id>,
+    pub origin_record_id: Option<Id>,
+    pub attempt: Option<i32>,
     pub status: Option<enums::Status>,
+    pub pinned: Option<bool>,
+    pub created_by: Option<Id>,
+    pub trigger_type: Option<enums::TriggerType>,
+    pub change_note: Option<String>,
+    pub required_approvals: Option<i32>,
+    pub params: Option<String>,
+    pub secret_params: Option<String>,
+    pub instance_id: Option<Id>,
+    pub heartbeat_time: Option<DateTime<Utc>>,
+    pub cancel_requested: Option<bool>,
     pub created_time: Option<DateTime<Utc>>,
     pub last_modified_time: Option<DateTime<Utc>>,
 }
@@ -168,7 +340,19 @@ impl JobRecordOpt {
             org_id: None,
             job_id: None,
             environment_id: None,
+            origin_record_id: None,
+            attempt: None,
             status: None,
+            pinned: None,
+            created_by: None,
+            trigger_type: None,
+            change_note: None,
+            required_approvals: None,
+            params: None,
+            secret_params: None,
+            instance_id: None,
+            heartbeat_time: None,
+            cancel_requested: None,
             created_time: None,
             last_modified_time: None,
         };
@@ -0,0 +1,186 @@
+use serde::{Serialize, Deserialize};
+use chrono::DateTime;
+use chrono::Utc;
+use tihu::datetime_format;
+use tihu::datetime_format_opt;
+use tihu::Id;
+use tihu::LightString;
+use native_common::model::Property;
+use native_common::model::PropertyDefine;
+use native_common::model::PropertyType;
+use crate::native_common;
+
+pub mod properties {
+    pub const ID: &str = "id";
+    pub const ORG_ID: &str = "org_id";
+    pub const RECORD_ID: &str = "record_id";
+    pub const APPROVER_ID: &str = "approver_id";
+    pub const DECISION: &str = "decision";
+    pub const COMMENT: &str = "comment";
+    pub const CREATED_TIME: &str = "created_time";
+    pub const LAST_MODIFIED_TIME: &str = "last_modified_time";
+}
+
+pub mod enums {
+    use tihu::LightString;
+    use std::error::Error;
+    use serde::{Serialize, Deserialize};
+    use tokio_postgres::types::{ToSql, Type, IsNull, to_sql_checked};
+    use bytes::BytesMut;
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum Decision {
+        Approve = 1, //通过
+        Reject = 2, //驳回
+    }
+    pub fn try_i16_to_decision(val: i16) -> Result<Decision, LightString> {
+        match val {
+            1 => Ok(Decision::Approve),
+            2 => Ok(Decision::Reject),
+            _ => Err(format!("未定义的审批结果枚举值:{}", val).into())
+        }
+    }
+    impl ToSql for Decision {
+        fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+            (*self as i16).to_sql(ty, out)
+        }
+        fn accepts(ty: &Type) -> bool {
+            <i16 as ToSql>::accepts(ty)
+        }
+        to_sql_checked!();
+    }
+}
+
+
+/**
+ * 执行记录审批列
+ */
+pub enum JobRecordApprovalProperty {
+    Id(Id),
+    OrgId(Id),
+    RecordId(Id),
+    ApproverId(Id),
+    Decision(enums::Decision),
+    Comment(Option<String>),
+    CreatedTime(DateTime<Utc>),
+    LastModifiedTime(DateTime<Utc>),
+}
+
+impl Property for JobRecordApprovalProperty {
+    fn property_define(&self) -> PropertyDefine {
+        match self {
+			JobRecordApprovalProperty::Id(_) => PropertyDefine {
+                key: LightString::from_static(properties::ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			JobRecordApprovalProperty::OrgId(_) => PropertyDefine {
+                key: LightString::from_static(properties::ORG_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			JobRecordApprovalProperty::RecordId(_) => PropertyDefine {
+                key: LightString::from_static(properties::RECORD_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			JobRecordApprovalProperty::ApproverId(_) => PropertyDefine {
+                key: LightString::from_static(properties::APPROVER_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			JobRecordApprovalProperty::Decision(_) => PropertyDefine {
+                key: LightString::from_static(properties::DECISION),
+                value_type: PropertyType::Enum,
+				required: true,
+            },
+			JobRecordApprovalProperty::Comment(_) => PropertyDefine {
+                key: LightString::from_static(properties::COMMENT),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			JobRecordApprovalProperty::CreatedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::CREATED_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+			JobRecordApprovalProperty::LastModifiedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::LAST_MODIFIED_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+        }
+    }
+}
+
+/**
+ * 执行记录审批
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobRecordApproval {
+    pub id: Id, //审批id
+    pub org_id: Id, //组织id
+    pub record_id: Id, //执行记录id
+    pub approver_id: Id, //审批人id
+    pub decision: enums::Decision, //审批结果
+    pub comment: Option<String>, //审批意见
+    #[serde(with = "datetime_format")]
+    pub created_time: DateTime<Utc>, //创建时间
+    #[serde(with = "datetime_format")]
+    pub last_modified_time: DateTime<Utc>, //更新时间
+}
+
+impl JobRecordApproval {
+    pub fn into_properties(self) -> Vec<JobRecordApprovalProperty> {
+        return vec![
+			JobRecordApprovalProperty::Id(self.id),
+			JobRecordApprovalProperty::OrgId(self.org_id),
+			JobRecordApprovalProperty::RecordId(self.record_id),
+			JobRecordApprovalProperty::ApproverId(self.approver_id),
+			JobRecordApprovalProperty::Decision(self.decision),
+			JobRecordApprovalProperty::Comment(self.comment),
+			JobRecordApprovalProperty::CreatedTime(self.created_time),
+			JobRecordApprovalProperty::LastModifiedTime(self.last_modified_time),
+        ];
+    }
+}
+
+impl PartialEq<JobRecordApprovalProperty> for JobRecordApproval {
+    fn eq(&self, property: &JobRecordApprovalProperty) -> bool {
+        match property {
+			JobRecordApprovalProperty::Id(id) => id == &self.id,
+			JobRecordApprovalProperty::OrgId(org_id) => org_id == &self.org_id,
+			JobRecordApprovalProperty::RecordId(record_id) => record_id == &self.record_id,
+			JobRecordApprovalProperty::ApproverId(approver_id) => approver_id == &self.approver_id,
+			JobRecordApprovalProperty::Decision(decision) => decision == &self.decision,
+			JobRecordApprovalProperty::Comment(comment) => comment == &self.comment,
+			JobRecordApprovalProperty::CreatedTime(created_time) => created_time == &self.created_time,
+			JobRecordApprovalProperty::LastModifiedTime(last_modified_time) => last_modified_time == &self.last_modified_time,
+        }
+    }
+}
+
+pub struct JobRecordApprovalOpt {
+    pub id: Option<Id>,
+    pub org_id: Option<Id>,
+    pub record_id: Option<Id>,
+    pub approver_id: Option<Id>,
+    pub decision: Option<enums::Decision>,
+    pub comment: Option<String>,
+    pub created_time: Option<DateTime<Utc>>,
+    pub last_modified_time: Option<DateTime<Utc>>,
+}
+
+impl JobRecordApprovalOpt {
+    pub fn empty() -> JobRecordApprovalOpt {
+        return JobRecordApprovalOpt {
+            id: None,
+            org_id: None,
+            record_id: None,
+            approver_id: None,
+            decision: None,
+            comment: None,
+            created_time: None,
+            last_modified_time: None,
+        };
+    }
+}
\ No newline at end of file
@@ -0,0 +1,260 @@
+use serde::{Serialize, Deserialize};
+use chrono::DateTime;
+use chrono::Utc;
+use tihu::datetime_format;
+use tihu::datetime_format_opt;
+use tihu::Id;
+use tihu::LightString;
+use native_common::model::Property;
+use native_common::model::PropertyDefine;
+use native_common::model::PropertyType;
+use crate::native_common;
+
+pub mod properties {
+    pub const ID: &str = "id";
+    pub const ORG_ID: &str = "org_id";
+    pub const JOB_ID: &str = "job_id";
+    pub const ENVIRONMENT_ID: &str = "environment_id";
+    pub const NAME: &str = "name";
+    pub const CRON_EXPRESSION: &str = "cron_expression";
+    pub const PARAMS: &str = "params";
+    pub const STATUS: &str = "status";
+    pub const NEXT_FIRE_TIME: &str = "next_fire_time";
+    pub const LAST_FIRE_TIME: &str = "last_fire_time";
+    pub const LAST_RECORD_ID: &str = "last_record_id";
+    pub const CREATED_BY: &str = "created_by";
+    pub const CREATED_TIME: &str = "created_time";
+    pub const LAST_MODIFIED_TIME: &str = "last_modified_time";
+}
+
+pub mod enums {
+    use tihu::LightString;
+    use std::error::Error;
+    use serde::{Serialize, Deserialize};
+    use tokio_postgres::types::{ToSql, Type, IsNull, to_sql_checked};
+    use bytes::BytesMut;
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum Status {
+        Enabled = 1, //启用
+        Disabled = 2, //停用
+    }
+    pub fn try_i16_to_status(val: i16) -> Result<Status, LightString> {
+        match val {
+            1 => Ok(Status::Enabled),
+            2 => Ok(Status::Disabled),
+            _ => Err(format!("未定义的计划状态枚举值:{}", val).into())
+        }
+    }
+    impl ToSql for Status {
+        fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+            (*self as i16).to_sql(ty, out)
+        }
+        fn accepts(ty: &Type) -> bool {
+            <i16 as ToSql>::accepts(ty)
+        }
+        to_sql_checked!();
+    }
+}
+
+
+/**
+ * 任务计划列
+ */
+pub enum JobScheduleProperty {
+    Id(Id),
+    OrgId(Id),
+    JobId(Id),
+    EnvironmentId(Id),
+    Name(String),
+    CronExpression(String),
+    Params(String),
+    Status(enums::Status),
+    NextFireTime(Option<DateTime<Utc>>),
+    LastFireTime(Option<DateTime<Utc>>),
+    LastRecordId(Option<Id>),
+    CreatedBy(Option<Id>),
+    CreatedTime(DateTime<Utc>),
+    LastModifiedTime(DateTime<Utc>),
+}
+
+impl Property for JobScheduleProperty {
+    fn property_define(&self) -> PropertyDefine {
+        match self {
+			JobScheduleProperty::Id(_) => PropertyDefine {
+                key: LightString::from_static(properties::ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			JobScheduleProperty::OrgId(_) => PropertyDefine {
+                key: LightString::from_static(properties::ORG_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			JobScheduleProperty::JobId(_) => PropertyDefine {
+                key: LightString::from_static(properties::JOB_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			JobScheduleProperty::EnvironmentId(_) => PropertyDefine {
+                key: LightString::from_static(properties::ENVIRONMENT_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			JobScheduleProperty::Name(_) => PropertyDefine {
+                key: LightString::from_static(properties::NAME),
+                value_type: PropertyType::String,
+				required: true,
+            },
+			JobScheduleProperty::CronExpression(_) => PropertyDefine {
+                key: LightString::from_static(properties::CRON_EXPRESSION),
+                value_type: PropertyType::String,
+				required: true,
+            },
+			JobScheduleProperty::Params(_) => PropertyDefine {
+                key: LightString::from_static(properties::PARAMS),
+                value_type: PropertyType::String,
+				required: true,
+            },
+			JobScheduleProperty::Status(_) => PropertyDefine {
+                key: LightString::from_static(properties::STATUS),
+                value_type: PropertyType::Enum,
+				required: true,
+            },
+			JobScheduleProperty::NextFireTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::NEXT_FIRE_TIME),
+                value_type: PropertyType::DateTime,
+				required: false,
+            },
+			JobScheduleProperty::LastFireTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::LAST_FIRE_TIME),
+                value_type: PropertyType::DateTime,
+				required: false,
+            },
+			JobScheduleProperty::LastRecordId(_) => PropertyDefine {
+                key: LightString::from_static(properties::LAST_RECORD_ID),
+                value_type: PropertyType::Id,
+				required: false,
+            },
+			JobScheduleProperty::CreatedBy(_) => PropertyDefine {
+                key: LightString::from_static(properties::CREATED_BY),
+                value_type: PropertyType::Id,
+				required: false,
+            },
+			JobScheduleProperty::CreatedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::CREATED_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+			JobScheduleProperty::LastModifiedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::LAST_MODIFIED_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+        }
+    }
+}
+
+/**
+ * 任务计划
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobSchedule {
+    pub id: Id, //任务计划id
+    pub org_id: Id, //组织id
+    pub job_id: Id, //任务id
+    pub environment_id: Id, //环境id
+    pub name: String, //计划名称
+    pub cron_expression: String, //cron表达式
+    pub params: String, //执行参数
+    pub status: enums::Status, //计划状态
+    #[serde(with = "datetime_format_opt")]
+    pub next_fire_time: Option<DateTime<Utc>>, //下次触发时间
+    #[serde(with = "datetime_format_opt")]
+    pub last_fire_time: Option<DateTime<Utc>>, //上次触发时间
+    pub last_record_id: Option<Id>, //上次触发的任务记录id
+    pub created_by: Option<Id>, //创建人id
+    #[serde(with = "datetime_format")]
+    pub created_time: DateTime<Utc>, //创建时间
+    #[serde(with = "datetime_format")]
+    pub last_modified_time: DateTime<Utc>, //更新时间
+}
+
+impl JobSchedule {
+    pub fn into_properties(self) -> Vec<JobScheduleProperty> {
+        return vec![
+			JobScheduleProperty::Id(self.id),
+			JobScheduleProperty::OrgId(self.org_id),
+			JobScheduleProperty::JobId(self.job_id),
+			JobScheduleProperty::EnvironmentId(self.environment_id),
+			JobScheduleProperty::Name(self.name),
+			JobScheduleProperty::CronExpression(self.cron_expression),
+			JobScheduleProperty::Params(self.params),
+			JobScheduleProperty::Status(self.status),
+			JobScheduleProperty::NextFireTime(self.next_fire_time),
+			JobScheduleProperty::LastFireTime(self.last_fire_time),
+			JobScheduleProperty::LastRecordId(self.last_record_id),
+			JobScheduleProperty::CreatedBy(self.created_by),
+			JobScheduleProperty::CreatedTime(self.created_time),
+			JobScheduleProperty::LastModifiedTime(self.last_modified_time),
+        ];
+    }
+}
+
+impl PartialEq<JobScheduleProperty> for JobSchedule {
+    fn eq(&self, property: &JobScheduleProperty) -> bool {
+        match property {
+			JobScheduleProperty::Id(id) => id == &self.id,
+			JobScheduleProperty::OrgId(org_id) => org_id == &self.org_id,
+			JobScheduleProperty::JobId(job_id) => job_id == &self.job_id,
+			JobScheduleProperty::EnvironmentId(environment_id) => environment_id == &self.environment_id,
+			JobScheduleProperty::Name(name) => name == &self.name,
+			JobScheduleProperty::CronExpression(cron_expression) => cron_expression == &self.cron_expression,
+			JobScheduleProperty::Params(params) => params == &self.params,
+			JobScheduleProperty::Status(status) => status == &self.status,
+			JobScheduleProperty::NextFireTime(next_fire_time) => next_fire_time == &self.next_fire_time,
+			JobScheduleProperty::LastFireTime(last_fire_time) => last_fire_time == &self.last_fire_time,
+			JobScheduleProperty::LastRecordId(last_record_id) => last_record_id == &self.last_record_id,
+			JobScheduleProperty::CreatedBy(created_by) => created_by == &self.created_by,
+			JobScheduleProperty::CreatedTime(created_time) => created_time == &self.created_time,
+			JobScheduleProperty::LastModifiedTime(last_modified_time) => last_modified_time == &self.last_modified_time,
+        }
+    }
+}
+
+pub struct JobScheduleOpt {
+    pub id: Option<Id>,
+    pub org_id: Option<Id>,
+    pub job_id: Option<Id>,
+    pub environment_id: Option<Id>,
+    pub name: Option<String>,
+    pub cron_expression: Option<String>,
+    pub params: Option<String>,
+    pub status: Option<enums::Status>,
+    pub next_fire_time: Option<DateTime<Utc>>,
+    pub last_fire_time: Option<DateTime<Utc>>,
+    pub last_record_id: Option<Id>,
+    pub created_by: Option<Id>,
+    pub created_time: Option<DateTime<Utc>>,
+    pub last_modified_time: Option<DateTime<Utc>>,
+}
+
+impl JobScheduleOpt {
+    pub fn empty() -> JobScheduleOpt {
+        return JobScheduleOpt {
+            id: None,
+            org_id: None,
+            job_id: None,
+            environment_id: None,
+            name: None,
+            cron_expression: None,
+            params: None,
+            status: None,
+            next_fire_time: None,
+            last_fire_time: None,
+            last_record_id: None,
+            created_by: None,
+            created_time: None,
+            last_modified_time: None,
+        };
+    }
+}
\ No newline at end of file
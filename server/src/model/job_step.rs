@@ -21,8 +21,21 @@ pub mod properties {
     pub const OPERATION_NAME: &str = "operation_name";
     pub const OPERATION_PARAMETER: &str = "operation_parameter";
     pub const ATTACHMENTS: &str = "attachments";
+    pub const SIGNOFF_ROLE: &str = "signoff_role";
+    pub const SIGNOFF_USERS: &str = "signoff_users";
+    pub const REQUIRED_SIGNOFFS: &str = "required_signoffs";
     pub const REMARK: &str = "remark";
+    pub const TIMEOUT_SECONDS: &str = "timeout_seconds";
+    pub const MAX_RETRIES: &str = "max_retries";
+    pub const RETRY_BACKOFF: &str = "retry_backoff";
+    pub const MAX_PARALLEL: &str = "max_parallel";
+    pub const BATCH_SIZE: &str = "batch_size";
+    pub const PAUSE_BETWEEN_BATCHES: &str = "pause_between_batches";
+    pub const FAILURE_POLICY: &str = "failure_policy";
+    pub const RUN_IF: &str = "run_if";
+    pub const RUN_EXPRESSION: &str = "run_expression";
     pub const SEQ: &str = "seq";
+    pub const DEPENDS_ON: &str = "depends_on";
     pub const CREATED_TIME: &str = "created_time";
     pub const LAST_MODIFIED_TIME: &str = "last_modified_time";
 }
@@ -54,6 +67,73 @@ pub mod enums {
         }
         to_sql_checked!();
     }
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum FailurePolicy {
+        Continue = 1, //继续执行
+        Stop = 2, //停止执行
+    }
+    pub fn try_i16_to_failure_policy(val: i16) -> Result<FailurePolicy, LightString> {
+        match val {
+            1 => Ok(FailurePolicy::Continue),
+            2 => Ok(FailurePolicy::Stop),
+            _ => Err(format!("未定义的失败策略枚举值:{}", val).into())
+        }
+    }
+    impl ToSql for FailurePolicy {
+        fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+            (*self as i16).to_sql(ty, out)
+        }
+        fn accepts(ty: &Type) -> bool {
+            <i16 as ToSql>::accepts(ty)
+        }
+        to_sql_checked!();
+    }
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum RunIf {
+        OnSuccess = 1, //前置步骤成功时
+        OnFailure = 2, //有步骤失败时
+        Always = 3, //总是执行
+    }
+    pub fn try_i16_to_run_if(val: i16) -> Result<RunIf, LightString> {
+        match val {
+            1 => Ok(RunIf::OnSuccess),
+            2 => Ok(RunIf::OnFailure),
+            3 => Ok(RunIf::Always),
+            _ => Err(format!("未定义的执行条件枚举值:{}", val).into())
+        }
+    }
+    impl ToSql for RunIf {
+        fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+            (*self as i16).to_sql(ty, out)
+        }
+        fn accepts(ty: &Type) -> bool {
+            <i16 as ToSql>::accepts(ty)
+        }
+        to_sql_checked!();
+    }
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum SignoffRole {
+        Owner = 1, //所有者
+        Admin = 2, //管理员
+        Operator = 3, //操作员
+    }
+    pub fn try_i16_to_signoff_role(val: i16) -> Result<SignoffRole, LightString> {
+        match val {
+            1 => Ok(SignoffRole::Owner),
+            2 => Ok(SignoffRole::Admin),
+            3 => Ok(SignoffRole::Operator),
+            _ => Err(format!("未定义的签收角色枚举值:{}", val).into())
+        }
+    }
+    impl ToSql for SignoffRole {
+        fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+            (*self as i16).to_sql(ty, out)
+        }
+        fn accepts(ty: &Type) -> bool {
+            <i16 as ToSql>::accepts(ty)
+        }
+        to_sql_checked!();
+    }
 }
 
 
@@ -71,8 +151,21 @@ pub enum JobStepProperty {
     OperationName(String),
     OperationParameter(String),
     Attachments(Option<String>),
+    SignoffRole(Option<enums::SignoffRole>),
+    SignoffUsers(Option<String>),
+    RequiredSignoffs(i32),
     Remark(Option<String>),
+    TimeoutSeconds(Option<i32>),
+    MaxRetries(i32),
+    RetryBackoff(i32),
+    MaxParallel(Option<i32>),
+    BatchSize(Option<i32>),
+    PauseBetweenBatches(i32),
+    FailurePolicy(enums::FailurePolicy),
+    RunIf(enums::RunIf),
+    RunExpression(Option<String>),
     Seq(i32),
+    DependsOn(Option<String>),
     CreatedTime(DateTime<Utc>),
     LastModifiedTime(DateTime<Utc>),
 }
@@ -129,16 +222,81 @@ impl Property for JobStepProperty {
                 key: LightString::from_static(properties::ATTACHMENTS),
                 value_type: PropertyType::String,
 				required: false,
+            },
+			JobStepProperty::SignoffRole(_) => PropertyDefine {
+                key: LightString::from_static(properties::SIGNOFF_ROLE),
+                value_type: PropertyType::Enum,
+				required: false,
+            },
+			JobStepProperty::SignoffUsers(_) => PropertyDefine {
+                key: LightString::from_static(properties::SIGNOFF_USERS),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			JobStepProperty::RequiredSignoffs(_) => PropertyDefine {
+                key: LightString::from_static(properties::REQUIRED_SIGNOFFS),
+                value_type: PropertyType::Integer,
+				required: true,
             },
 			JobStepProperty::Remark(_) => PropertyDefine {
                 key: LightString::from_static(properties::REMARK),
                 value_type: PropertyType::String,
 				required: false,
+            },
+			JobStepProperty::TimeoutSeconds(_) => PropertyDefine {
+                key: LightString::from_static(properties::TIMEOUT_SECONDS),
+                value_type: PropertyType::Integer,
+				required: false,
+            },
+			JobStepProperty::MaxRetries(_) => PropertyDefine {
+                key: LightString::from_static(properties::MAX_RETRIES),
+                value_type: PropertyType::Integer,
+				required: true,
+            },
+			JobStepProperty::RetryBackoff(_) => PropertyDefine {
+                key: LightString::from_static(properties::RETRY_BACKOFF),
+                value_type: PropertyType::Integer,
+				required: true,
+            },
+			JobStepProperty::MaxParallel(_) => PropertyDefine {
+                key: LightString::from_static(properties::MAX_PARALLEL),
+                value_type: PropertyType::Integer,
+				required: false,
+            },
+			JobStepProperty::BatchSize(_) => PropertyDefine {
+                key: LightString::from_static(properties::BATCH_SIZE),
+                value_type: PropertyType::Integer,
+				required: false,
+            },
+			JobStepProperty::PauseBetweenBatches(_) => PropertyDefine {
+                key: LightString::from_static(properties::PAUSE_BETWEEN_BATCHES),
+                value_type: PropertyType::Integer,
+				required: true,
+            },
+			JobStepProperty::FailurePolicy(_) => PropertyDefine {
+                key: LightString::from_static(properties::FAILURE_POLICY),
+                value_type: PropertyType::Enum,
+				required: true,
+            },
+			JobStepProperty::RunIf(_) => PropertyDefine {
+                key: LightString::from_static(properties::RUN_IF),
+                value_type: PropertyType::Enum,
+				required: true,
+            },
+			JobStepProperty::RunExpression(_) => PropertyDefine {
+                key: LightString::from_static(properties::RUN_EXPRESSION),
+                value_type: PropertyType::String,
+				required: false,
             },
 			JobStepProperty::Seq(_) => PropertyDefine {
                 key: LightString::from_static(properties::SEQ),
                 value_type: PropertyType::Integer,
 				required: true,
+            },
+			JobStepProperty::DependsOn(_) => PropertyDefine {
+                key: LightString::from_static(properties::DEPENDS_ON),
+                value_type: PropertyType::String,
+				required: false,
             },
 			JobStepProperty::CreatedTime(_) => PropertyDefine {
                 key: LightString::from_static(properties::CREATED_TIME),
@@ -169,8 +327,21 @@ pub struct JobStep {
     pub operation_name: String, //操作名称
     pub operation_parameter: String, //操作参数
     pub attachments: Option<String>, //附件
+    pub signoff_role: Option<enums::SignoffRole>, //可以签收的最低角色，为空表示不按角色限制
+    pub signoff_users: Option<String>, //可以签收的用户id列表，json格式
+    pub required_signoffs: i32, //需要的签收人数
     pub remark: Option<String>, //备注
+    pub timeout_seconds: Option<i32>, //超时时间（秒）
+    pub max_retries: i32, //最大重试次数
+    pub retry_backoff: i32, //重试间隔（秒）
+    pub max_parallel: Option<i32>, //最大并发数
+    pub batch_size: Option<i32>, //每批资源数
+    pub pause_between_batches: i32, //批次间隔（秒）
+    pub failure_policy: enums::FailurePolicy, //失败策略
+    pub run_if: enums::RunIf, //执行条件
+    pub run_expression: Option<String>, //条件表达式
     pub seq: i32, //执行顺序
+    pub depends_on: Option<String>, //依赖的步骤
     #[serde(with = "datetime_format")]
     pub created_time: DateTime<Utc>, //创建时间
     #[serde(with = "datetime_format")]
@@ -190,8 +361,21 @@ impl JobStep {
 			JobStepProperty::OperationName(self.operation_name),
 			JobStepProperty::OperationParameter(self.operation_parameter),
 			JobStepProperty::Attachments(self.attachments),
+			JobStepProperty::SignoffRole(self.signoff_role),
+			JobStepProperty::SignoffUsers(self.signoff_users),
+			JobStepProperty::RequiredSignoffs(self.required_signoffs),
 			JobStepProperty::Remark(self.remark),
+			JobStepProperty::TimeoutSeconds(self.timeout_seconds),
+			JobStepProperty::MaxRetries(self.max_retries),
+			JobStepProperty::RetryBackoff(self.retry_backoff),
+			JobStepProperty::MaxParallel(self.max_parallel),
+			JobStepProperty::BatchSize(self.batch_size),
+			JobStepProperty::PauseBetweenBatches(self.pause_between_batches),
+			JobStepProperty::FailurePolicy(self.failure_policy),
+			JobStepProperty::RunIf(self.run_if),
+			JobStepProperty::RunExpression(self.run_expression),
 			JobStepProperty::Seq(self.seq),
+			JobStepProperty::DependsOn(self.depends_on),
 			JobStepProperty::CreatedTime(self.created_time),
 			JobStepProperty::LastModifiedTime(self.last_modified_time),
         ];
@@ -211,8 +395,21 @@ impl PartialEq<JobStepProperty> for JobStep {
 			JobStepProperty::OperationName(operation_name) => operation_name == &self.operation_name,
 			JobStepProperty::OperationParameter(operation_parameter) => operation_parameter == &self.operation_parameter,
 			JobStepProperty::Attachments(attachments) => attachments == &self.attachments,
+			JobStepProperty::SignoffRole(signoff_role) => signoff_role == &self.signoff_role,
+			JobStepProperty::SignoffUsers(signoff_users) => signoff_users == &self.signoff_users,
+			JobStepProperty::RequiredSignoffs(required_signoffs) => required_signoffs == &self.required_signoffs,
 			JobStepProperty::Remark(remark) => remark == &self.remark,
+			JobStepProperty::TimeoutSeconds(timeout_seconds) => timeout_seconds == &self.timeout_seconds,
+			JobStepProperty::MaxRetries(max_retries) => max_retries == &self.max_retries,
+			JobStepProperty::RetryBackoff(retry_backoff) => retry_backoff == &self.retry_backoff,
+			JobStepProperty::MaxParallel(max_parallel) => max_parallel == &self.max_parallel,
+			JobStepProperty::BatchSize(batch_size) => batch_size == &self.batch_size,
+			JobStepProperty::PauseBetweenBatches(pause_between_batches) => pause_between_batches == &self.pause_between_batches,
+			JobStepProperty::FailurePolicy(failure_policy) => failure_policy == &self.failure_policy,
+			JobStepProperty::RunIf(run_if) => run_if == &self.run_if,
+			JobStepProperty::RunExpression(run_expression) => run_expression == &self.run_expression,
 			JobStepProperty::Seq(seq) => seq == &self.seq,
+			JobStepProperty::DependsOn(depends_on) => depends_on == &self.depends_on,
 			JobStepProperty::CreatedTime(created_time) => created_time == &self.created_time,
 			JobStepProperty::LastModifiedTime(last_modified_time) => last_modified_time == &self.last_modified_time,
         }
@@ -230,8 +427,21 @@ pub struct JobStepOpt {
     pub operation_name: Option<String>,
     pub operation_parameter: Option<String>,
     pub attachments: Option<String>,
+    pub signoff_role: Option<enums::SignoffRole>,
+    pub signoff_users: Option<String>,
+    pub required_signoffs: Option<i32>,
     pub remark: Option<String>,
+    pub timeout_seconds: Option<i32>,
+    pub max_retries: Option<i32>,
+    pub retry_backoff: Option<i32>,
+    pub max_parallel: Option<i32>,
+    pub batch_size: Option<i32>,
+    pub pause_between_batches: Option<i32>,
+    pub failure_policy: Option<enums::FailurePolicy>,
+    pub run_if: Option<enums::RunIf>,
+    pub run_expression: Option<String>,
     pub seq: Option<i32>,
+    pub depends_on: Option<String>,
     pub created_time: Option<DateTime<Utc>>,
     pub last_modified_time: Option<DateTime<Utc>>,
 }
@@ -249,8 +459,21 @@ impl JobStepOpt {
             operation_name: None,
             operation_parameter: None,
             attachments: None,
+            signoff_role: None,
+            signoff_users: None,
+            required_signoffs: None,
             remark: None,
+            timeout_seconds: None,
+            max_retries: None,
+            retry_backoff: None,
+            max_parallel: None,
+            batch_size: None,
+            pause_between_batches: None,
+            failure_policy: None,
+            run_if: None,
+            run_expression: None,
             seq: None,
+            depends_on: None,
             created_time: None,
             last_modified_time: None,
         };
@@ -24,9 +24,25 @@ pub mod properties {
     pub const OPERATION_ID: &str = "operation_id";
     pub const OPERATION_NAME: &str = "operation_name";
     pub const OPERATION_PARAMETER: &str = "operation_parameter";
+    pub const RENDERED_PARAMETER: &str = "rendered_parameter";
     pub const ATTACHMENTS: &str = "attachments";
+    pub const SIGNOFF_ROLE: &str = "signoff_role";
+    pub const SIGNOFF_USERS: &str = "signoff_users";
+    pub const REQUIRED_SIGNOFFS: &str = "required_signoffs";
+    pub const TIMEOUT_SECONDS: &str = "timeout_seconds";
+    pub const MAX_RETRIES: &str = "max_retries";
+    pub const RETRY_BACKOFF: &str = "retry_backoff";
+    pub const MAX_PARALLEL: &str = "max_parallel";
+    pub const BATCH_SIZE: &str = "batch_size";
+    pub const PAUSE_BETWEEN_BATCHES: &str = "pause_between_batches";
+    pub const FAILURE_POLICY: &str = "failure_policy";
+    pub const RUN_IF: &str = "run_if";
+    pub const RUN_EXPRESSION: &str = "run_expression";
+    pub const SKIP_REASON: &str = "skip_reason";
     pub const JOB_STEP_SEQ: &str = "job_step_seq";
+    pub const DEPENDS_ON: &str = "depends_on";
     pub const STATUS: &str = "status";
+    pub const COMPLETED_BY: &str = "completed_by";
     pub const CREATED_TIME: &str = "created_time";
     pub const LAST_MODIFIED_TIME: &str = "last_modified_time";
 }
@@ -59,11 +75,58 @@ pub mod enums {
         to_sql_checked!();
     }
     #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum FailurePolicy {
+        Continue = 1, //继续执行
+        Stop = 2, //停止执行
+    }
+    pub fn try_i16_to_failure_policy(val: i16) -> Result<FailurePolicy, LightString> {
+        match val {
+            1 => Ok(FailurePolicy::Continue),
+            2 => Ok(FailurePolicy::Stop),
+            _ => Err(format!("未定义的失败策略枚举值:{}", val).into())
+        }
+    }
+    impl ToSql for FailurePolicy {
+        fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+            (*self as i16).to_sql(ty, out)
+        }
+        fn accepts(ty: &Type) -> bool {
+            <i16 as ToSql>::accepts(ty)
+        }
+        to_sql_checked!();
+    }
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum RunIf {
+        OnSuccess = 1, //前置步骤成功时
+        OnFailure = 2, //有步骤失败时
+        Always = 3, //总是执行
+    }
+    pub fn try_i16_to_run_if(val: i16) -> Result<RunIf, LightString> {
+        match val {
+            1 => Ok(RunIf::OnSuccess),
+            2 => Ok(RunIf::OnFailure),
+            3 => Ok(RunIf::Always),
+            _ => Err(format!("未定义的执行条件枚举值:{}", val).into())
+        }
+    }
+    impl ToSql for RunIf {
+        fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+            (*self as i16).to_sql(ty, out)
+        }
+        fn accepts(ty: &Type) -> bool {
+            <i16 as ToSql>::accepts(ty)
+        }
+        to_sql_checked!();
+    }
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
     pub enum Status {
         Pending = 1, //未开始
         Running = 2, //进行中
         Success = 3, //成功
         Failure = 4, //失败
+        Interrupted = 5, //已中断
+        Cancelled = 6, //已取消
+        Skipped = 7, //已跳过
     }
     pub fn try_i16_to_status(val: i16) -> Result<Status, LightString> {
         match val {
@@ -71,6 +134,9 @@ pub mod enums {
             2 => Ok(Status::Running),
             3 => Ok(Status::Success),
             4 => Ok(Status::Failure),
+            5 => Ok(Status::Interrupted),
+            6 => Ok(Status::Cancelled),
+            7 => Ok(Status::Skipped),
             _ => Err(format!("未定义的状态枚举值:{}", val).into())
         }
     }
@@ -83,6 +149,29 @@ pub mod enums {
         }
         to_sql_checked!();
     }
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum SignoffRole {
+        Owner = 1, //所有者
+        Admin = 2, //管理员
+        Operator = 3, //操作员
+    }
+    pub fn try_i16_to_signoff_role(val: i16) -> Result<SignoffRole, LightString> {
+        match val {
+            1 => Ok(SignoffRole::Owner),
+            2 => Ok(SignoffRole::Admin),
+            3 => Ok(SignoffRole::Operator),
+            _ => Err(format!("未定义的签收角色枚举值:{}", val).into())
+        }
+    }
+    impl ToSql for SignoffRole {
+        fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+            (*self as i16).to_sql(ty, out)
+        }
+        fn accepts(ty: &Type) -> bool {
+            <i16 as ToSql>::accepts(ty)
+        }
+        to_sql_checked!();
+    }
 }
 
 
@@ -103,9 +192,25 @@ pub enum JobStepRecordProperty {
     OperationId(String),
     OperationName(String),
     OperationParameter(String),
+    RenderedParameter(Option<String>),
     Attachments(Option<String>),
+    SignoffRole(Option<enums::SignoffRole>),
+    SignoffUsers(Option<String>),
+    RequiredSignoffs(i32),
+    TimeoutSeconds(Option<i32>),
+    MaxRetries(i32),
+    RetryBackoff(i32),
+    MaxParallel(Option<i32>),
+    BatchSize(Option<i32>),
+    PauseBetweenBatches(i32),
+    FailurePolicy(enums::FailurePolicy),
+    RunIf(enums::RunIf),
+    RunExpression(Option<String>),
+    SkipReason(Option<String>),
     JobStepSeq(i32),
+    DependsOn(Option<String>),
     Status(enums::Status),
+    CompletedBy(Option<Id>),
     CreatedTime(DateTime<Utc>),
     LastModifiedTime(DateTime<Utc>),
 }
@@ -177,21 +282,101 @@ impl Property for JobStepRecordProperty {
                 key: LightString::from_static(properties::OPERATION_PARAMETER),
                 value_type: PropertyType::String,
 				required: true,
+            },
+			JobStepRecordProperty::RenderedParameter(_) => PropertyDefine {
+                key: LightString::from_static(properties::RENDERED_PARAMETER),
+                value_type: PropertyType::String,
+				required: false,
             },
 			JobStepRecordProperty::Attachments(_) => PropertyDefine {
                 key: LightString::from_static(properties::ATTACHMENTS),
                 value_type: PropertyType::String,
 				required: false,
+            },
+			JobStepRecordProperty::SignoffRole(_) => PropertyDefine {
+                key: LightString::from_static(properties::SIGNOFF_ROLE),
+                value_type: PropertyType::Enum,
+				required: false,
+            },
+			JobStepRecordProperty::SignoffUsers(_) => PropertyDefine {
+                key: LightString::from_static(properties::SIGNOFF_USERS),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			JobStepRecordProperty::RequiredSignoffs(_) => PropertyDefine {
+                key: LightString::from_static(properties::REQUIRED_SIGNOFFS),
+                value_type: PropertyType::Integer,
+				required: true,
+            },
+			JobStepRecordProperty::TimeoutSeconds(_) => PropertyDefine {
+                key: LightString::from_static(properties::TIMEOUT_SECONDS),
+                value_type: PropertyType::Integer,
+				required: false,
+            },
+			JobStepRecordProperty::MaxRetries(_) => PropertyDefine {
+                key: LightString::from_static(properties::MAX_RETRIES),
+                value_type: PropertyType::Integer,
+				required: true,
+            },
+			JobStepRecordProperty::RetryBackoff(_) => PropertyDefine {
+                key: LightString::from_static(properties::RETRY_BACKOFF),
+                value_type: PropertyType::Integer,
+				required: true,
+            },
+			JobStepRecordProperty::MaxParallel(_) => PropertyDefine {
+                key: LightString::from_static(properties::MAX_PARALLEL),
+                value_type: PropertyType::Integer,
+				required: false,
+            },
+			JobStepRecordProperty::BatchSize(_) => PropertyDefine {
+                key: LightString::from_static(properties::BATCH_SIZE),
+                value_type: PropertyType::Integer,
+				required: false,
+            },
+			JobStepRecordProperty::PauseBetweenBatches(_) => PropertyDefine {
+                key: LightString::from_static(properties::PAUSE_BETWEEN_BATCHES),
+                value_type: PropertyType::Integer,
+				required: true,
+            },
+			JobStepRecordProperty::FailurePolicy(_) => PropertyDefine {
+                key: LightString::from_static(properties::FAILURE_POLICY),
+                value_type: PropertyType::Enum,
+				required: true,
+            },
+			JobStepRecordProperty::RunIf(_) => PropertyDefine {
+                key: LightString::from_static(properties::RUN_IF),
+                value_type: PropertyType::Enum,
+				required: true,
+            },
+			JobStepRecordProperty::RunExpression(_) => PropertyDefine {
+                key: LightString::from_static(properties::RUN_EXPRESSION),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			JobStepRecordProperty::SkipReason(_) => PropertyDefine {
+                key: LightString::from_static(properties::SKIP_REASON),
+                value_type: PropertyType::String,
+				required: false,
             },
 			JobStepRecordProperty::JobStepSeq(_) => PropertyDefine {
                 key: LightString::from_static(properties::JOB_STEP_SEQ),
                 value_type: PropertyType::Integer,
 				required: true,
+            },
+			JobStepRecordProperty::DependsOn(_) => PropertyDefine {
+                key: LightString::from_static(properties::DEPENDS_ON),
+                value_type: PropertyType::String,
+				required: false,
             },
 			JobStepRecordProperty::Status(_) => PropertyDefine {
                 key: LightString::from_static(properties::STATUS),
                 value_type: PropertyType::Enum,
 				required: true,
+            },
+			JobStepRecordProperty::CompletedBy(_) => PropertyDefine {
+                key: LightString::from_static(properties::COMPLETED_BY),
+                value_type: PropertyType::Id,
+				required: false,
             },
 			JobStepRecordProperty::CreatedTime(_) => PropertyDefine {
                 key: LightString::from_static(properties::CREATED_TIME),
@@ -225,9 +410,25 @@ pub struct JobStepRecord {
     pub operation_id: String, //操作id
     pub operation_name: String, //操作名称
     pub operation_parameter: String, //操作参数
+    pub rendered_parameter: Option<String>, //渲染之后的操作参数，密码类型的参数是掩码
     pub attachments: Option<String>, //附件
+    pub signoff_role: Option<enums::SignoffRole>, //可以签收的最低角色，为空表示不按角色限制
+    pub signoff_users: Option<String>, //可以签收的用户id列表，json格式
+    pub required_signoffs: i32, //需要的签收人数
+    pub timeout_seconds: Option<i32>, //超时时间（秒）
+    pub max_retries: i32, //最大重试次数
+    pub retry_backoff: i32, //重试间隔（秒）
+    pub max_parallel: Option<i32>, //最大并发数
+    pub batch_size: Option<i32>, //每批资源数
+    pub pause_between_batches: i32, //批次间隔（秒）
+    pub failure_policy: enums::FailurePolicy, //失败策略
+    pub run_if: enums::RunIf, //执行条件
+    pub run_expression: Option<String>, //条件表达式
+    pub skip_reason: Option<String>, //跳过原因
     pub job_step_seq: i32, //任务步骤顺序
+    pub depends_on: Option<String>, //依赖的步骤
     pub status: enums::Status, //执行状态
+    pub completed_by: Option<Id>, //处理手动步骤的用户id
     #[serde(with = "datetime_format")]
     pub created_time: DateTime<Utc>, //创建时间
     #[serde(with = "datetime_format")]
@@ -250,9 +451,25 @@ impl JobStepRecord {
 			JobStepRecordProperty::OperationId(self.operation_id),
 			JobStepRecordProperty::OperationName(self.operation_name),
 			JobStepRecordProperty::OperationParameter(self.operation_parameter),
+			JobStepRecordProperty::RenderedParameter(self.rendered_parameter),
 			JobStepRecordProperty::Attachments(self.attachments),
+			JobStepRecordProperty::SignoffRole(self.signoff_role),
+			JobStepRecordProperty::SignoffUsers(self.signoff_users),
+			JobStepRecordProperty::RequiredSignoffs(self.required_signoffs),
+			JobStepRecordProperty::TimeoutSeconds(self.timeout_seconds),
+			JobStepRecordProperty::MaxRetries(self.max_retries),
+			JobStepRecordProperty::RetryBackoff(self.retry_backoff),
+			JobStepRecordProperty::MaxParallel(self.max_parallel),
+			JobStepRecordProperty::BatchSize(self.batch_size),
+			JobStepRecordProperty::PauseBetweenBatches(self.pause_between_batches),
+			JobStepRecordProperty::FailurePolicy(self.failure_policy),
+			JobStepRecordProperty::RunIf(self.run_if),
+			JobStepRecordProperty::RunExpression(self.run_expression),
+			JobStepRecordProperty::SkipReason(self.skip_reason),
 			JobStepRecordProperty::JobStepSeq(self.job_step_seq),
+			JobStepRecordProperty::DependsOn(self.depends_on),
 			JobStepRecordProperty::Status(self.status),
+			JobStepRecordProperty::CompletedBy(self.completed_by),
 			JobStepRecordProperty::CreatedTime(self.created_time),
 			JobStepRecordProperty::LastModifiedTime(self.last_modified_time),
         ];
@@ -275,9 +492,25 @@ impl PartialEq<JobStepRecordProperty> for JobStepRecord {
 			JobStepRecordProperty::OperationId(operation_id) => operation_id == &self.operation_id,
 			JobStepRecordProperty::OperationName(operation_name) => operation_name == &self.operation_name,
 			JobStepRecordProperty::OperationParameter(operation_parameter) => operation_parameter == &self.operation_parameter,
+			JobStepRecordProperty::RenderedParameter(rendered_parameter) => rendered_parameter == &self.rendered_parameter,
 			JobStepRecordProperty::Attachments(attachments) => attachments == &self.attachments,
+			JobStepRecordProperty::SignoffRole(signoff_role) => signoff_role == &self.signoff_role,
+			JobStepRecordProperty::SignoffUsers(signoff_users) => signoff_users == &self.signoff_users,
+			JobStepRecordProperty::RequiredSignoffs(required_signoffs) => required_signoffs == &self.required_signoffs,
+			JobStepRecordProperty::TimeoutSeconds(timeout_seconds) => timeout_seconds == &self.timeout_seconds,
+			JobStepRecordProperty::MaxRetries(max_retries) => max_retries == &self.max_retries,
+			JobStepRecordProperty::RetryBackoff(retry_backoff) => retry_backoff == &self.retry_backoff,
+			JobStepRecordProperty::MaxParallel(max_parallel) => max_parallel == &self.max_parallel,
+			JobStepRecordProperty::BatchSize(batch_size) => batch_size == &self.batch_size,
+			JobStepRecordProperty::PauseBetweenBatches(pause_between_batches) => pause_between_batches == &self.pause_between_batches,
+			JobStepRecordProperty::FailurePolicy(failure_policy) => failure_policy == &self.failure_policy,
+			JobStepRecordProperty::RunIf(run_if) => run_if == &self.run_if,
+			JobStepRecordProperty::RunExpression(run_expression) => run_expression == &self.run_expression,
+			JobStepRecordProperty::SkipReason(skip_reason) => skip_reason == &self.skip_reason,
 			JobStepRecordProperty::JobStepSeq(job_step_seq) => job_step_seq == &self.job_step_seq,
+			JobStepRecordProperty::DependsOn(depends_on) => depends_on == &self.depends_on,
 			JobStepRecordProperty::Status(status) => status == &self.status,
+			JobStepRecordProperty::CompletedBy(completed_by) => completed_by == &self.completed_by,
 			JobStepRecordProperty::CreatedTime(created_time) => created_time == &self.created_time,
 			JobStepRecordProperty::LastModifiedTime(last_modified_time) => last_modified_time == &self.last_modified_time,
         }
@@ -298,9 +531,25 @@ pub struct JobStepRecordOpt {
     pub operation_id: Option<String>,
     pub operation_name: Option<String>,
     pub operation_parameter: Option<String>,
+    pub rendered_parameter: Option<String>,
     pub attachments: Option<String>,
+    pub signoff_role: Option<enums::SignoffRole>,
+    pub signoff_users: Option<String>,
+    pub required_signoffs: Option<i32>,
+    pub timeout_seconds: Option<i32>,
+    pub max_retries: Option<i32>,
+    pub retry_backoff: Option<i32>,
+    pub max_parallel: Option<i32>,
+    pub batch_size: Option<i32>,
+    pub pause_between_batches: Option<i32>,
+    pub failure_policy: Option<enums::FailurePolicy>,
+    pub run_if: Option<enums::RunIf>,
+    pub run_expression: Option<String>,
+    pub skip_reason: Option<String>,
     pub job_step_seq: Option<i32>,
+    pub depends_on: Option<String>,
     pub status: Option<enums::Status>,
+    pub completed_by: Option<Id>,
     pub created_time: Option<DateTime<Utc>>,
     pub last_modified_time: Option<DateTime<Utc>>,
 }
@@ -321,9 +570,25 @@ impl JobStepRecordOpt {
             operation_id: None,
             operation_name: None,
             operation_parameter: None,
+            rendered_parameter: None,
             attachments: None,
+            signoff_role: None,
+            signoff_users: None,
+            required_signoffs: None,
+            timeout_seconds: None,
+            max_retries: None,
+            retry_backoff: None,
+            max_parallel: None,
+            batch_size: None,
+            pause_between_batches: None,
+            failure_policy: None,
+            run_if: None,
+            run_expression: None,
+            skip_reason: None,
             job_step_seq: None,
+            depends_on: None,
             status: None,
+            completed_by: None,
             created_time: None,
             last_modified_time: None,
         };
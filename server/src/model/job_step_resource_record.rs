@@ -22,6 +22,10 @@ pub mod properties {
     pub const EXTENSION_CONFIGURATION: &str = "extension_configuration";
     pub const OUTPUT_FILE: &str = "output_file";
     pub const OUTPUT_CONTENT: &str = "output_content";
+    pub const LOG_KEY: &str = "log_key";
+    pub const LOG_SUMMARY: &str = "log_summary";
+    pub const OUTPUTS: &str = "outputs";
+    pub const ATTEMPTS: &str = "attempts";
     pub const STATUS: &str = "status";
     pub const CREATED_TIME: &str = "created_time";
     pub const LAST_MODIFIED_TIME: &str = "last_modified_time";
@@ -39,6 +43,9 @@ pub mod enums {
         Running = 2, //进行中
         Success = 3, //成功
         Failure = 4, //失败
+        Interrupted = 5, //已中断
+        Cancelled = 6, //已取消
+        Skipped = 7, //已跳过
     }
     pub fn try_i16_to_status(val: i16) -> Result<Status, LightString> {
         match val {
@@ -46,6 +53,9 @@ pub mod enums {
             2 => Ok(Status::Running),
             3 => Ok(Status::Success),
             4 => Ok(Status::Failure),
+            5 => Ok(Status::Interrupted),
+            6 => Ok(Status::Cancelled),
+            7 => Ok(Status::Skipped),
             _ => Err(format!("未定义的状态枚举值:{}", val).into())
         }
     }
@@ -76,6 +86,10 @@ pub enum JobStepResourceRecordProperty {
     ExtensionConfiguration(String),
     OutputFile(Option<String>),
     OutputContent(Option<String>),
+    LogKey(Option<String>),
+    LogSummary(Option<String>),
+    Outputs(Option<String>),
+    Attempts(i32),
     Status(enums::Status),
     CreatedTime(DateTime<Utc>),
     LastModifiedTime(DateTime<Utc>),
@@ -138,6 +152,26 @@ impl Property for JobStepResourceRecordProperty {
                 key: LightString::from_static(properties::OUTPUT_CONTENT),
                 value_type: PropertyType::String,
 				required: false,
+            },
+			JobStepResourceRecordProperty::LogKey(_) => PropertyDefine {
+                key: LightString::from_static(properties::LOG_KEY),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			JobStepResourceRecordProperty::LogSummary(_) => PropertyDefine {
+                key: LightString::from_static(properties::LOG_SUMMARY),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			JobStepResourceRecordProperty::Outputs(_) => PropertyDefine {
+                key: LightString::from_static(properties::OUTPUTS),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			JobStepResourceRecordProperty::Attempts(_) => PropertyDefine {
+                key: LightString::from_static(properties::ATTEMPTS),
+                value_type: PropertyType::Integer,
+				required: true,
             },
 			JobStepResourceRecordProperty::Status(_) => PropertyDefine {
                 key: LightString::from_static(properties::STATUS),
@@ -174,6 +208,10 @@ pub struct JobStepResourceRecord {
     pub extension_configuration: String, //扩展配置
     pub output_file: Option<String>, //日志文件
     pub output_content: Option<String>, //日志内容
+    pub log_key: Option<String>, //日志在对象存储里的key，gzip压缩的json数组
+    pub log_summary: Option<String>, //日志摘要，json格式
+    pub outputs: Option<String>, //步骤输出
+    pub attempts: i32, //执行次数
     pub status: enums::Status, //执行状态
     #[serde(with = "datetime_format")]
     pub created_time: DateTime<Utc>, //创建时间
@@ -195,6 +233,10 @@ impl JobStepResourceRecord {
 			JobStepResourceRecordProperty::ExtensionConfiguration(self.extension_configuration),
 			JobStepResourceRecordProperty::OutputFile(self.output_file),
 			JobStepResourceRecordProperty::OutputContent(self.output_content),
+			JobStepResourceRecordProperty::LogKey(self.log_key),
+			JobStepResourceRecordProperty::LogSummary(self.log_summary),
+			JobStepResourceRecordProperty::Outputs(self.outputs),
+			JobStepResourceRecordProperty::Attempts(self.attempts),
 			JobStepResourceRecordProperty::Status(self.status),
 			JobStepResourceRecordProperty::CreatedTime(self.created_time),
 			JobStepResourceRecordProperty::LastModifiedTime(self.last_modified_time),
@@ -216,6 +258,10 @@ impl PartialEq<JobStepResourceRecordProperty> for JobStepResourceRecord {
 			JobStepResourceRecordProperty::ExtensionConfiguration(extension_configuration) => extension_configuration == &self.extension_configuration,
 			JobStepResourceRecordProperty::OutputFile(output_file) => output_file == &self.output_file,
 			JobStepResourceRecordProperty::OutputContent(output_content) => output_content == &self.output_content,
+			JobStepResourceRecordProperty::LogKey(log_key) => log_key == &self.log_key,
+			JobStepResourceRecordProperty::LogSummary(log_summary) => log_summary == &self.log_summary,
+			JobStepResourceRecordProperty::Outputs(outputs) => outputs == &self.outputs,
+			JobStepResourceRecordProperty::Attempts(attempts) => attempts == &self.attempts,
 			JobStepResourceRecordProperty::Status(status) => status == &self.status,
 			JobStepResourceRecordProperty::CreatedTime(created_time) => created_time == &self.created_time,
 			JobStepResourceRecordProperty::LastModifiedTime(last_modified_time) => last_modified_time == &self.last_modified_time,
@@ -235,6 +281,10 @@ pub struct JobStepResourceRecordOpt {
     pub extension_configuration: Option<String>,
     pub output_file: Option<String>,
     pub output_content: Option<String>,
+    pub log_key: Option<String>,
+    pub log_summary: Option<String>,
+    pub outputs: Option<String>,
+    pub attempts: Option<i32>,
     pub status: Option<enums::Status>,
     pub created_time: Option<DateTime<Utc>>,
     pub last_modified_time: Option<DateTime<Utc>>,
@@ -254,6 +304,10 @@ impl JobStepResourceRecordOpt {
             extension_configuration: None,
             output_file: None,
             output_content: None,
+            log_key: None,
+            log_summary: None,
+            outputs: None,
+            attempts: None,
             status: None,
             created_time: None,
             last_modified_time: None,
@@ -0,0 +1,189 @@
+use serde::{Serialize, Deserialize};
+use chrono::DateTime;
+use chrono::Utc;
+use tihu::datetime_format;
+use tihu::datetime_format_opt;
+use tihu::Id;
+use tihu::LightString;
+use native_common::model::Property;
+use native_common::model::PropertyDefine;
+use native_common::model::PropertyType;
+use crate::native_common;
+
+pub mod properties {
+    pub const ID: &str = "id";
+    pub const ORG_ID: &str = "org_id";
+    pub const RECORD_ID: &str = "record_id";
+    pub const JOB_STEP_RECORD_ID: &str = "job_step_record_id";
+    pub const USER_ID: &str = "user_id";
+    pub const SUCCESS: &str = "success";
+    pub const COMMENT: &str = "comment";
+    pub const ATTACHMENTS: &str = "attachments";
+    pub const CREATED_TIME: &str = "created_time";
+    pub const LAST_MODIFIED_TIME: &str = "last_modified_time";
+}
+
+pub mod enums {
+    use tihu::LightString;
+    use std::error::Error;
+    use serde::{Serialize, Deserialize};
+    use tokio_postgres::types::{ToSql, Type, IsNull, to_sql_checked};
+    use bytes::BytesMut;
+}
+
+
+/**
+ * 手动步骤签收列
+ */
+pub enum JobStepSignoffProperty {
+    Id(Id),
+    OrgId(Id),
+    RecordId(Id),
+    JobStepRecordId(Id),
+    UserId(Id),
+    Success(bool),
+    Comment(Option<String>),
+    Attachments(Option<String>),
+    CreatedTime(DateTime<Utc>),
+    LastModifiedTime(DateTime<Utc>),
+}
+
+impl Property for JobStepSignoffProperty {
+    fn property_define(&self) -> PropertyDefine {
+        match self {
+			JobStepSignoffProperty::Id(_) => PropertyDefine {
+                key: LightString::from_static(properties::ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			JobStepSignoffProperty::OrgId(_) => PropertyDefine {
+                key: LightString::from_static(properties::ORG_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			JobStepSignoffProperty::RecordId(_) => PropertyDefine {
+                key: LightString::from_static(properties::RECORD_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			JobStepSignoffProperty::JobStepRecordId(_) => PropertyDefine {
+                key: LightString::from_static(properties::JOB_STEP_RECORD_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			JobStepSignoffProperty::UserId(_) => PropertyDefine {
+                key: LightString::from_static(properties::USER_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			JobStepSignoffProperty::Success(_) => PropertyDefine {
+                key: LightString::from_static(properties::SUCCESS),
+                value_type: PropertyType::Bool,
+				required: true,
+            },
+			JobStepSignoffProperty::Comment(_) => PropertyDefine {
+                key: LightString::from_static(properties::COMMENT),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			JobStepSignoffProperty::Attachments(_) => PropertyDefine {
+                key: LightString::from_static(properties::ATTACHMENTS),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			JobStepSignoffProperty::CreatedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::CREATED_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+			JobStepSignoffProperty::LastModifiedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::LAST_MODIFIED_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+        }
+    }
+}
+
+/**
+ * 手动步骤签收
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct JobStepSignoff {
+    pub id: Id, //签收id
+    pub org_id: Id, //组织id
+    pub record_id: Id, //执行记录id
+    pub job_step_record_id: Id, //步骤执行记录id
+    pub user_id: Id, //签收人id
+    pub success: bool, //是否确认成功
+    pub comment: Option<String>, //签收意见
+    pub attachments: Option<String>, //签收凭证
+    #[serde(with = "datetime_format")]
+    pub created_time: DateTime<Utc>, //创建时间
+    #[serde(with = "datetime_format")]
+    pub last_modified_time: DateTime<Utc>, //更新时间
+}
+
+impl JobStepSignoff {
+    pub fn into_properties(self) -> Vec<JobStepSignoffProperty> {
+        return vec![
+			JobStepSignoffProperty::Id(self.id),
+			JobStepSignoffProperty::OrgId(self.org_id),
+			JobStepSignoffProperty::RecordId(self.record_id),
+			JobStepSignoffProperty::JobStepRecordId(self.job_step_record_id),
+			JobStepSignoffProperty::UserId(self.user_id),
+			JobStepSignoffProperty::Success(self.success),
+			JobStepSignoffProperty::Comment(self.comment),
+			JobStepSignoffProperty::Attachments(self.attachments),
+			JobStepSignoffProperty::CreatedTime(self.created_time),
+			JobStepSignoffProperty::LastModifiedTime(self.last_modified_time),
+        ];
+    }
+}
+
+impl PartialEq<JobStepSignoffProperty> for JobStepSignoff {
+    fn eq(&self, property: &JobStepSignoffProperty) -> bool {
+        match property {
+			JobStepSignoffProperty::Id(id) => id == &self.id,
+			JobStepSignoffProperty::OrgId(org_id) => org_id == &self.org_id,
+			JobStepSignoffProperty::RecordId(record_id) => record_id == &self.record_id,
+			JobStepSignoffProperty::JobStepRecordId(job_step_record_id) => job_step_record_id == &self.job_step_record_id,
+			JobStepSignoffProperty::UserId(user_id) => user_id == &self.user_id,
+			JobStepSignoffProperty::Success(success) => success == &self.success,
+			JobStepSignoffProperty::Comment(comment) => comment == &self.comment,
+			JobStepSignoffProperty::Attachments(attachments) => attachments == &self.attachments,
+			JobStepSignoffProperty::CreatedTime(created_time) => created_time == &self.created_time,
+			JobStepSignoffProperty::LastModifiedTime(last_modified_time) => last_modified_time == &self.last_modified_time,
+        }
+    }
+}
+
+pub struct JobStepSignoffOpt {
+    pub id: Option<Id>,
+    pub org_id: Option<Id>,
+    pub record_id: Option<Id>,
+    pub job_step_record_id: Option<Id>,
+    pub user_id: Option<Id>,
+    pub success: Option<bool>,
+    pub comment: Option<String>,
+    pub attachments: Option<String>,
+    pub created_time: Option<DateTime<Utc>>,
+    pub last_modified_time: Option<DateTime<Utc>>,
+}
+
+impl JobStepSignoffOpt {
+    pub fn empty() -> JobStepSignoffOpt {
+        return JobStepSignoffOpt {
+            id: None,
+            org_id: None,
+            record_id: None,
+            job_step_record_id: None,
+            user_id: None,
+            success: None,
+            comment: None,
+            attachments: None,
+            created_time: None,
+            last_modified_time: None,
+        };
+    }
+}
\ No newline at end of file
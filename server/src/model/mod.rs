@@ -1,16 +1,24 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 
+pub mod api_token;
 pub mod environment;
+pub mod environment_grant;
+pub mod environment_lock;
 pub mod environment_resource;
 pub mod environment_schema;
 pub mod environment_schema_resource;
 pub mod external_user;
 pub mod job;
 pub mod job_record;
+pub mod job_record_approval;
+pub mod job_schedule;
 pub mod job_step;
 pub mod job_step_record;
 pub mod job_step_resource_record;
+pub mod job_step_signoff;
 pub mod organization;
+pub mod runner;
+pub mod runner_task;
 pub mod system_user;
 pub mod user;
@@ -13,6 +13,10 @@ use crate::native_common;
 pub mod properties {
     pub const ID: &str = "id";
     pub const NAME: &str = "name";
+    pub const RETENTION_COUNT: &str = "retention_count";
+    pub const RETENTION_DAYS: &str = "retention_days";
+    pub const LAST_PURGE_TIME: &str = "last_purge_time";
+    pub const LAST_PURGE_REPORT: &str = "last_purge_report";
     pub const CREATED_TIME: &str = "created_time";
     pub const LAST_MODIFIED_TIME: &str = "last_modified_time";
 }
@@ -32,6 +36,10 @@ pub mod enums {
 pub enum OrganizationProperty {
     Id(Id),
     Name(String),
+    RetentionCount(Option<i32>),
+    RetentionDays(Option<i32>),
+    LastPurgeTime(Option<DateTime<Utc>>),
+    LastPurgeReport(Option<String>),
     CreatedTime(DateTime<Utc>),
     LastModifiedTime(DateTime<Utc>),
 }
@@ -48,6 +56,26 @@ impl Property for OrganizationProperty {
                 key: LightString::from_static(properties::NAME),
                 value_type: PropertyType::String,
 				required: true,
+            },
+			OrganizationProperty::RetentionCount(_) => PropertyDefine {
+                key: LightString::from_static(properties::RETENTION_COUNT),
+                value_type: PropertyType::Integer,
+				required: false,
+            },
+			OrganizationProperty::RetentionDays(_) => PropertyDefine {
+                key: LightString::from_static(properties::RETENTION_DAYS),
+                value_type: PropertyType::Integer,
+				required: false,
+            },
+			OrganizationProperty::LastPurgeTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::LAST_PURGE_TIME),
+                value_type: PropertyType::DateTime,
+				required: false,
+            },
+			OrganizationProperty::LastPurgeReport(_) => PropertyDefine {
+                key: LightString::from_static(properties::LAST_PURGE_REPORT),
+                value_type: PropertyType::String,
+				required: false,
             },
 			OrganizationProperty::CreatedTime(_) => PropertyDefine {
                 key: LightString::from_static(properties::CREATED_TIME),
@@ -70,6 +98,11 @@ impl Property for OrganizationProperty {
 pub struct Organization {
     pub id: Id, //id
     pub name: String, //组织名称
+    pub retention_count: Option<i32>, //每个任务在每个环境下保留最近的执行记录条数
+    pub retention_days: Option<i32>, //保留最近多少天的执行记录
+    #[serde(with = "datetime_format_opt")]
+    pub last_purge_time: Option<DateTime<Utc>>, //最后一次清理执行记录的时间
+    pub last_purge_report: Option<String>, //最后一次清理执行记录的结果，json格式
     #[serde(with = "datetime_format")]
     pub created_time: DateTime<Utc>, //创建时间
     #[serde(with = "datetime_format")]
@@ -81,6 +114,10 @@ impl Organization {
         return vec![
 			OrganizationProperty::Id(self.id),
 			OrganizationProperty::Name(self.name),
+			OrganizationProperty::RetentionCount(self.retention_count),
+			OrganizationProperty::RetentionDays(self.retention_days),
+			OrganizationProperty::LastPurgeTime(self.last_purge_time),
+			OrganizationProperty::LastPurgeReport(self.last_purge_report),
 			OrganizationProperty::CreatedTime(self.created_time),
 			OrganizationProperty::LastModifiedTime(self.last_modified_time),
         ];
@@ -92,6 +129,10 @@ impl PartialEq<OrganizationProperty> for Organization {
         match property {
 			OrganizationProperty::Id(id) => id == &self.id,
 			OrganizationProperty::Name(name) => name == &self.name,
+			OrganizationProperty::RetentionCount(retention_count) => retention_count == &self.retention_count,
+			OrganizationProperty::RetentionDays(retention_days) => retention_days == &self.retention_days,
+			OrganizationProperty::LastPurgeTime(last_purge_time) => last_purge_time == &self.last_purge_time,
+			OrganizationProperty::LastPurgeReport(last_purge_report) => last_purge_report == &self.last_purge_report,
 			OrganizationProperty::CreatedTime(created_time) => created_time == &self.created_time,
 			OrganizationProperty::LastModifiedTime(last_modified_time) => last_modified_time == &self.last_modified_time,
         }
@@ -101,6 +142,10 @@ impl PartialEq<OrganizationProperty> for Organization {
 pub struct OrganizationOpt {
     pub id: Option<Id>,
     pub name: Option<String>,
+    pub retention_count: Option<i32>,
+    pub retention_days: Option<i32>,
+    pub last_purge_time: Option<DateTime<Utc>>,
+    pub last_purge_report: Option<String>,
     pub created_time: Option<DateTime<Utc>>,
     pub last_modified_time: Option<DateTime<Utc>>,
 }
@@ -110,6 +155,10 @@ impl OrganizationOpt {
         return OrganizationOpt {
             id: None,
             name: None,
+            retention_count: None,
+            retention_days: None,
+            last_purge_time: None,
+            last_purge_report: None,
             created_time: None,
             last_modified_time: None,
         };
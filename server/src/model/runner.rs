@@ -0,0 +1,190 @@
+use serde::{Serialize, Deserialize};
+use chrono::DateTime;
+use chrono::Utc;
+use tihu::datetime_format;
+use tihu::datetime_format_opt;
+use tihu::Id;
+use tihu::LightString;
+use native_common::model::Property;
+use native_common::model::PropertyDefine;
+use native_common::model::PropertyType;
+use crate::native_common;
+
+pub mod properties {
+    pub const ID: &str = "id";
+    pub const ORG_ID: &str = "org_id";
+    pub const NAME: &str = "name";
+    pub const LABEL: &str = "label";
+    pub const TOKEN_HASH: &str = "token_hash";
+    pub const VERSION: &str = "version";
+    pub const EXTENSION_IDS: &str = "extension_ids";
+    pub const LAST_HEARTBEAT_TIME: &str = "last_heartbeat_time";
+    pub const CREATED_TIME: &str = "created_time";
+    pub const LAST_MODIFIED_TIME: &str = "last_modified_time";
+}
+
+pub mod enums {
+    use tihu::LightString;
+    use std::error::Error;
+    use serde::{Serialize, Deserialize};
+    use tokio_postgres::types::{ToSql, Type, IsNull, to_sql_checked};
+    use bytes::BytesMut;
+}
+
+
+/**
+ * 执行器列
+ */
+pub enum RunnerProperty {
+    Id(Id),
+    OrgId(Id),
+    Name(String),
+    Label(String),
+    TokenHash(String),
+    Version(Option<String>),
+    ExtensionIds(Option<String>),
+    LastHeartbeatTime(Option<DateTime<Utc>>),
+    CreatedTime(DateTime<Utc>),
+    LastModifiedTime(DateTime<Utc>),
+}
+
+impl Property for RunnerProperty {
+    fn property_define(&self) -> PropertyDefine {
+        match self {
+			RunnerProperty::Id(_) => PropertyDefine {
+                key: LightString::from_static(properties::ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			RunnerProperty::OrgId(_) => PropertyDefine {
+                key: LightString::from_static(properties::ORG_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			RunnerProperty::Name(_) => PropertyDefine {
+                key: LightString::from_static(properties::NAME),
+                value_type: PropertyType::String,
+				required: true,
+            },
+			RunnerProperty::Label(_) => PropertyDefine {
+                key: LightString::from_static(properties::LABEL),
+                value_type: PropertyType::String,
+				required: true,
+            },
+			RunnerProperty::TokenHash(_) => PropertyDefine {
+                key: LightString::from_static(properties::TOKEN_HASH),
+                value_type: PropertyType::String,
+				required: true,
+            },
+			RunnerProperty::Version(_) => PropertyDefine {
+                key: LightString::from_static(properties::VERSION),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			RunnerProperty::ExtensionIds(_) => PropertyDefine {
+                key: LightString::from_static(properties::EXTENSION_IDS),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			RunnerProperty::LastHeartbeatTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::LAST_HEARTBEAT_TIME),
+                value_type: PropertyType::DateTime,
+				required: false,
+            },
+			RunnerProperty::CreatedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::CREATED_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+			RunnerProperty::LastModifiedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::LAST_MODIFIED_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+        }
+    }
+}
+
+/**
+ * 执行器
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Runner {
+    pub id: Id, //执行器id
+    pub org_id: Id, //组织id
+    pub name: String, //执行器名称
+    pub label: String, //执行器标签
+    pub token_hash: String, //访问令牌的摘要
+    pub version: Option<String>, //执行器版本
+    pub extension_ids: Option<String>, //执行器加载的扩展id，逗号分隔
+    #[serde(with = "datetime_format_opt")]
+    pub last_heartbeat_time: Option<DateTime<Utc>>, //最后一次心跳时间
+    #[serde(with = "datetime_format")]
+    pub created_time: DateTime<Utc>, //创建时间
+    #[serde(with = "datetime_format")]
+    pub last_modified_time: DateTime<Utc>, //更新时间
+}
+
+impl Runner {
+    pub fn into_properties(self) -> Vec<RunnerProperty> {
+        return vec![
+			RunnerProperty::Id(self.id),
+			RunnerProperty::OrgId(self.org_id),
+			RunnerProperty::Name(self.name),
+			RunnerProperty::Label(self.label),
+			RunnerProperty::TokenHash(self.token_hash),
+			RunnerProperty::Version(self.version),
+			RunnerProperty::ExtensionIds(self.extension_ids),
+			RunnerProperty::LastHeartbeatTime(self.last_heartbeat_time),
+			RunnerProperty::CreatedTime(self.created_time),
+			RunnerProperty::LastModifiedTime(self.last_modified_time),
+        ];
+    }
+}
+
+impl PartialEq<RunnerProperty> for Runner {
+    fn eq(&self, property: &RunnerProperty) -> bool {
+        match property {
+			RunnerProperty::Id(id) => id == &self.id,
+			RunnerProperty::OrgId(org_id) => org_id == &self.org_id,
+			RunnerProperty::Name(name) => name == &self.name,
+			RunnerProperty::Label(label) => label == &self.label,
+			RunnerProperty::TokenHash(token_hash) => token_hash == &self.token_hash,
+			RunnerProperty::Version(version) => version == &self.version,
+			RunnerProperty::ExtensionIds(extension_ids) => extension_ids == &self.extension_ids,
+			RunnerProperty::LastHeartbeatTime(last_heartbeat_time) => last_heartbeat_time == &self.last_heartbeat_time,
+			RunnerProperty::CreatedTime(created_time) => created_time == &self.created_time,
+			RunnerProperty::LastModifiedTime(last_modified_time) => last_modified_time == &self.last_modified_time,
+        }
+    }
+}
+
+pub struct RunnerOpt {
+    pub id: Option<Id>,
+    pub org_id: Option<Id>,
+    pub name: Option<String>,
+    pub label: Option<String>,
+    pub token_hash: Option<String>,
+    pub version: Option<String>,
+    pub extension_ids: Option<String>,
+    pub last_heartbeat_time: Option<DateTime<Utc>>,
+    pub created_time: Option<DateTime<Utc>>,
+    pub last_modified_time: Option<DateTime<Utc>>,
+}
+
+impl RunnerOpt {
+    pub fn empty() -> RunnerOpt {
+        return RunnerOpt {
+            id: None,
+            org_id: None,
+            name: None,
+            label: None,
+            token_hash: None,
+            version: None,
+            extension_ids: None,
+            last_heartbeat_time: None,
+            created_time: None,
+            last_modified_time: None,
+        };
+    }
+}
\ No newline at end of file
@@ -0,0 +1,312 @@
+use serde::{Serialize, Deserialize};
+use chrono::DateTime;
+use chrono::Utc;
+use tihu::datetime_format;
+use tihu::datetime_format_opt;
+use tihu::Id;
+use tihu::LightString;
+use native_common::model::Property;
+use native_common::model::PropertyDefine;
+use native_common::model::PropertyType;
+use crate::native_common;
+
+pub mod properties {
+    pub const ID: &str = "id";
+    pub const ORG_ID: &str = "org_id";
+    pub const LABEL: &str = "label";
+    pub const EXTENSION_ID: &str = "extension_id";
+    pub const OPERATION_ID: &str = "operation_id";
+    pub const OPERATION_PARAMETER: &str = "operation_parameter";
+    pub const EXTENSION_CONFIGURATION: &str = "extension_configuration";
+    pub const RESOURCE_INDEX: &str = "resource_index";
+    pub const STATUS: &str = "status";
+    pub const RUNNER_ID: &str = "runner_id";
+    pub const CANCELLED: &str = "cancelled";
+    pub const PENDING_LOGS: &str = "pending_logs";
+    pub const OUTPUTS: &str = "outputs";
+    pub const ERROR: &str = "error";
+    pub const POLLED_TIME: &str = "polled_time";
+    pub const REPORTED_TIME: &str = "reported_time";
+    pub const CREATED_TIME: &str = "created_time";
+    pub const LAST_MODIFIED_TIME: &str = "last_modified_time";
+}
+
+pub mod enums {
+    use tihu::LightString;
+    use std::error::Error;
+    use serde::{Serialize, Deserialize};
+    use tokio_postgres::types::{ToSql, Type, IsNull, to_sql_checked};
+    use bytes::BytesMut;
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum Status {
+        Pending = 1, //等待领取
+        Running = 2, //执行中
+        Success = 3, //执行成功
+        Failure = 4, //执行失败
+    }
+    pub fn try_i16_to_status(val: i16) -> Result<Status, LightString> {
+        match val {
+            1 => Ok(Status::Pending),
+            2 => Ok(Status::Running),
+            3 => Ok(Status::Success),
+            4 => Ok(Status::Failure),
+            _ => Err(format!("未定义的任务状态枚举值:{}", val).into())
+        }
+    }
+    impl ToSql for Status {
+        fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+            (*self as i16).to_sql(ty, out)
+        }
+        fn accepts(ty: &Type) -> bool {
+            <i16 as ToSql>::accepts(ty)
+        }
+        to_sql_checked!();
+    }
+}
+
+
+/**
+ * 执行器任务列
+ */
+pub enum RunnerTaskProperty {
+    Id(Id),
+    OrgId(Id),
+    Label(String),
+    ExtensionId(String),
+    OperationId(String),
+    OperationParameter(String),
+    ExtensionConfiguration(String),
+    ResourceIndex(i32),
+    Status(enums::Status),
+    RunnerId(Option<Id>),
+    Cancelled(bool),
+    PendingLogs(Option<String>),
+    Outputs(Option<String>),
+    Error(Option<String>),
+    PolledTime(DateTime<Utc>),
+    ReportedTime(Option<DateTime<Utc>>),
+    CreatedTime(DateTime<Utc>),
+    LastModifiedTime(DateTime<Utc>),
+}
+
+impl Property for RunnerTaskProperty {
+    fn property_define(&self) -> PropertyDefine {
+        match self {
+			RunnerTaskProperty::Id(_) => PropertyDefine {
+                key: LightString::from_static(properties::ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			RunnerTaskProperty::OrgId(_) => PropertyDefine {
+                key: LightString::from_static(properties::ORG_ID),
+                value_type: PropertyType::Id,
+				required: true,
+            },
+			RunnerTaskProperty::Label(_) => PropertyDefine {
+                key: LightString::from_static(properties::LABEL),
+                value_type: PropertyType::String,
+				required: true,
+            },
+			RunnerTaskProperty::ExtensionId(_) => PropertyDefine {
+                key: LightString::from_static(properties::EXTENSION_ID),
+                value_type: PropertyType::String,
+				required: true,
+            },
+			RunnerTaskProperty::OperationId(_) => PropertyDefine {
+                key: LightString::from_static(properties::OPERATION_ID),
+                value_type: PropertyType::String,
+				required: true,
+            },
+			RunnerTaskProperty::OperationParameter(_) => PropertyDefine {
+                key: LightString::from_static(properties::OPERATION_PARAMETER),
+                value_type: PropertyType::String,
+				required: true,
+            },
+			RunnerTaskProperty::ExtensionConfiguration(_) => PropertyDefine {
+                key: LightString::from_static(properties::EXTENSION_CONFIGURATION),
+                value_type: PropertyType::String,
+				required: true,
+            },
+			RunnerTaskProperty::ResourceIndex(_) => PropertyDefine {
+                key: LightString::from_static(properties::RESOURCE_INDEX),
+                value_type: PropertyType::Integer,
+				required: true,
+            },
+			RunnerTaskProperty::Status(_) => PropertyDefine {
+                key: LightString::from_static(properties::STATUS),
+                value_type: PropertyType::Enum,
+				required: true,
+            },
+			RunnerTaskProperty::RunnerId(_) => PropertyDefine {
+                key: LightString::from_static(properties::RUNNER_ID),
+                value_type: PropertyType::Id,
+				required: false,
+            },
+			RunnerTaskProperty::Cancelled(_) => PropertyDefine {
+                key: LightString::from_static(properties::CANCELLED),
+                value_type: PropertyType::Bool,
+				required: true,
+            },
+			RunnerTaskProperty::PendingLogs(_) => PropertyDefine {
+                key: LightString::from_static(properties::PENDING_LOGS),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			RunnerTaskProperty::Outputs(_) => PropertyDefine {
+                key: LightString::from_static(properties::OUTPUTS),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			RunnerTaskProperty::Error(_) => PropertyDefine {
+                key: LightString::from_static(properties::ERROR),
+                value_type: PropertyType::String,
+				required: false,
+            },
+			RunnerTaskProperty::PolledTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::POLLED_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+			RunnerTaskProperty::ReportedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::REPORTED_TIME),
+                value_type: PropertyType::DateTime,
+				required: false,
+            },
+			RunnerTaskProperty::CreatedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::CREATED_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+			RunnerTaskProperty::LastModifiedTime(_) => PropertyDefine {
+                key: LightString::from_static(properties::LAST_MODIFIED_TIME),
+                value_type: PropertyType::DateTime,
+				required: true,
+            },
+        }
+    }
+}
+
+/**
+ * 执行器任务
+ */
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RunnerTask {
+    pub id: Id, //任务id
+    pub org_id: Id, //组织id
+    pub label: String, //执行器标签
+    pub extension_id: String, //扩展id
+    pub operation_id: String, //操作id
+    pub operation_parameter: String, //操作参数，json格式
+    pub extension_configuration: String, //加密保存的扩展配置
+    pub resource_index: i32, //资源在步骤里的序号
+    pub status: enums::Status, //任务状态
+    pub runner_id: Option<Id>, //领取任务的执行器id
+    pub cancelled: bool, //是否已被取消
+    pub pending_logs: Option<String>, //执行器上报、还没有写入执行日志的日志，json格式
+    pub outputs: Option<String>, //执行成功时操作的输出，json格式
+    pub error: Option<String>, //执行失败的原因
+    #[serde(with = "datetime_format")]
+    pub polled_time: DateTime<Utc>, //服务端最后一次检查任务的时间
+    #[serde(with = "datetime_format_opt")]
+    pub reported_time: Option<DateTime<Utc>>, //执行器最后一次上报的时间
+    #[serde(with = "datetime_format")]
+    pub created_time: DateTime<Utc>, //创建时间
+    #[serde(with = "datetime_format")]
+    pub last_modified_time: DateTime<Utc>, //更新时间
+}
+
+impl RunnerTask {
+    pub fn into_properties(self) -> Vec<RunnerTaskProperty> {
+        return vec![
+			RunnerTaskProperty::Id(self.id),
+			RunnerTaskProperty::OrgId(self.org_id),
+			RunnerTaskProperty::Label(self.label),
+			RunnerTaskProperty::ExtensionId(self.extension_id),
+			RunnerTaskProperty::OperationId(self.operation_id),
+			RunnerTaskProperty::OperationParameter(self.operation_parameter),
+			RunnerTaskProperty::ExtensionConfiguration(self.extension_configuration),
+			RunnerTaskProperty::ResourceIndex(self.resource_index),
+			RunnerTaskProperty::Status(self.status),
+			RunnerTaskProperty::RunnerId(self.runner_id),
+			RunnerTaskProperty::Cancelled(self.cancelled),
+			RunnerTaskProperty::PendingLogs(self.pending_logs),
+			RunnerTaskProperty::Outputs(self.outputs),
+			RunnerTaskProperty::Error(self.error),
+			RunnerTaskProperty::PolledTime(self.polled_time),
+			RunnerTaskProperty::ReportedTime(self.reported_time),
+			RunnerTaskProperty::CreatedTime(self.created_time),
+			RunnerTaskProperty::LastModifiedTime(self.last_modified_time),
+        ];
+    }
+}
+
+impl PartialEq<RunnerTaskProperty> for RunnerTask {
+    fn eq(&self, property: &RunnerTaskProperty) -> bool {
+        match property {
+			RunnerTaskProperty::Id(id) => id == &self.id,
+			RunnerTaskProperty::OrgId(org_id) => org_id == &self.org_id,
+			RunnerTaskProperty::Label(label) => label == &self.label,
+			RunnerTaskProperty::ExtensionId(extension_id) => extension_id == &self.extension_id,
+			RunnerTaskProperty::OperationId(operation_id) => operation_id == &self.operation_id,
+			RunnerTaskProperty::OperationParameter(operation_parameter) => operation_parameter == &self.operation_parameter,
+			RunnerTaskProperty::ExtensionConfiguration(extension_configuration) => extension_configuration == &self.extension_configuration,
+			RunnerTaskProperty::ResourceIndex(resource_index) => resource_index == &self.resource_index,
+			RunnerTaskProperty::Status(status) => status == &self.status,
+			RunnerTaskProperty::RunnerId(runner_id) => runner_id == &self.runner_id,
+			RunnerTaskProperty::Cancelled(cancelled) => cancelled == &self.cancelled,
+			RunnerTaskProperty::PendingLogs(pending_logs) => pending_logs == &self.pending_logs,
+			RunnerTaskProperty::Outputs(outputs) => outputs == &self.outputs,
+			RunnerTaskProperty::Error(error) => error == &self.error,
+			RunnerTaskProperty::PolledTime(polled_time) => polled_time == &self.polled_time,
+			RunnerTaskProperty::ReportedTime(reported_time) => reported_time == &self.reported_time,
+			RunnerTaskProperty::CreatedTime(created_time) => created_time == &self.created_time,
+			RunnerTaskProperty::LastModifiedTime(last_modified_time) => last_modified_time == &self.last_modified_time,
+        }
+    }
+}
+
+pub struct RunnerTaskOpt {
+    pub id: Option<Id>,
+    pub org_id: Option<Id>,
+    pub label: Option<String>,
+    pub extension_id: Option<String>,
+    pub operation_id: Option<String>,
+    pub operation_parameter: Option<String>,
+    pub extension_configuration: Option<String>,
+    pub resource_index: Option<i32>,
+    pub status: Option<enums::Status>,
+    pub runner_id: Option<Id>,
+    pub cancelled: Option<bool>,
+    pub pending_logs: Option<String>,
+    pub outputs: Option<String>,
+    pub error: Option<String>,
+    pub polled_time: Option<DateTime<Utc>>,
+    pub reported_time: Option<DateTime<Utc>>,
+    pub created_time: Option<DateTime<Utc>>,
+    pub last_modified_time: Option<DateTime<Utc>>,
+}
+
+impl RunnerTaskOpt {
+    pub fn empty() -> RunnerTaskOpt {
+        return RunnerTaskOpt {
+            id: None,
+            org_id: None,
+            label: None,
+            extension_id: None,
+            operation_id: None,
+            operation_parameter: None,
+            extension_configuration: None,
+            resource_index: None,
+            status: None,
+            runner_id: None,
+            cancelled: None,
+            pending_logs: None,
+            outputs: None,
+            error: None,
+            polled_time: None,
+            reported_time: None,
+            created_time: None,
+            last_modified_time: None,
+        };
+    }
+}
\ No newline at end of file
@@ -14,6 +14,7 @@ pub mod properties {
     pub const ID: &str = "id";
     pub const ORG_ID: &str = "org_id";
     pub const USER_SOURCE: &str = "user_source";
+    pub const ROLE: &str = "role";
     pub const NAME: &str = "name";
     pub const AVATAR_URL: &str = "avatar_url";
     pub const CREATED_TIME: &str = "created_time";
@@ -30,11 +31,13 @@ pub mod enums {
     pub enum UserSource {
         System = 1, //系统用户
         External = 2, //外部用户
+        ServiceAccount = 3, //服务账号
     }
     pub fn try_i16_to_user_source(val: i16) -> Result<UserSource, LightString> {
         match val {
             1 => Ok(UserSource::System),
             2 => Ok(UserSource::External),
+            3 => Ok(UserSource::ServiceAccount),
             _ => Err(format!("未定义的用户来源枚举值:{}", val).into())
         }
     }
@@ -47,6 +50,31 @@ pub mod enums {
         }
         to_sql_checked!();
     }
+    #[derive(Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum Role {
+        Owner = 1, //所有者
+        Admin = 2, //管理员
+        Operator = 3, //操作员
+        Viewer = 4, //只读成员
+    }
+    pub fn try_i16_to_role(val: i16) -> Result<Role, LightString> {
+        match val {
+            1 => Ok(Role::Owner),
+            2 => Ok(Role::Admin),
+            3 => Ok(Role::Operator),
+            4 => Ok(Role::Viewer),
+            _ => Err(format!("未定义的角色枚举值:{}", val).into())
+        }
+    }
+    impl ToSql for Role {
+        fn to_sql(&self, ty: &Type, out: &mut BytesMut) -> Result<IsNull, Box<dyn Error + 'static + Send + Sync>> {
+            (*self as i16).to_sql(ty, out)
+        }
+        fn accepts(ty: &Type) -> bool {
+            <i16 as ToSql>::accepts(ty)
+        }
+        to_sql_checked!();
+    }
 }
 
 
@@ -57,6 +85,7 @@ pub enum UserProperty {
     Id(Id),
     OrgId(Option<Id>),
     UserSource(enums::UserSource),
+    Role(enums::Role),
     Name(String),
     AvatarUrl(Option<String>),
     CreatedTime(DateTime<Utc>),
@@ -80,6 +109,11 @@ impl Property for UserProperty {
                 key: LightString::from_static(properties::USER_SOURCE),
                 value_type: PropertyType::Enum,
 				required: true,
+            },
+			UserProperty::Role(_) => PropertyDefine {
+                key: LightString::from_static(properties::ROLE),
+                value_type: PropertyType::Enum,
+				required: true,
             },
 			UserProperty::Name(_) => PropertyDefine {
                 key: LightString::from_static(properties::NAME),
@@ -113,6 +147,7 @@ pub struct User {
     pub id: Id, //id
     pub org_id: Option<Id>, //组织id
     pub user_source: enums::UserSource, //用户来源
+    pub role: enums::Role, //组织内的角色
     pub name: String, //名称
     pub avatar_url: Option<String>, //头像
     #[serde(with = "datetime_format")]
@@ -127,6 +162,7 @@ impl User {
 			UserProperty::Id(self.id),
 			UserProperty::OrgId(self.org_id),
 			UserProperty::UserSource(self.user_source),
+			UserProperty::Role(self.role),
 			UserProperty::Name(self.name),
 			UserProperty::AvatarUrl(self.avatar_url),
 			UserProperty::CreatedTime(self.created_time),
@@ -141,6 +177,7 @@ impl PartialEq<UserProperty> for User {
 			UserProperty::Id(id) => id == &self.id,
 			UserProperty::OrgId(org_id) => org_id == &self.org_id,
 			UserProperty::UserSource(user_source) => user_source == &self.user_source,
+			UserProperty::Role(role) => role == &self.role,
 			UserProperty::Name(name) => name == &self.name,
 			UserProperty::AvatarUrl(avatar_url) => avatar_url == &self.avatar_url,
 			UserProperty::CreatedTime(created_time) => created_time == &self.created_time,
@@ -153,6 +190,7 @@ pub struct UserOpt {
     pub id: Option<Id>,
     pub org_id: Option<Id>,
     pub user_source: Option<enums::UserSource>,
+    pub role: Option<enums::Role>,
     pub name: Option<String>,
     pub avatar_url: Option<String>,
     pub created_time: Option<DateTime<Utc>>,
@@ -165,6 +203,7 @@ impl UserOpt {
             id: None,
             org_id: None,
             user_source: None,
+            role: None,
             name: None,
             avatar_url: None,
             created_time: None,
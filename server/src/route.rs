@@ -2,6 +2,7 @@ use crate::action;
 use crate::middleware::auth::call_guest_api;
 use crate::middleware::auth::call_user_api;
 use crate::middleware::auth::Guest;
+use crate::middleware::auth::Permission;
 use crate::middleware::auth::User;
 use crate::native_common;
 use crate::sdk;
@@ -19,6 +20,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::auth::change_password::ChangePasswordApi,
                 action::auth::change_password::change_password,
+                Permission::Read,
                 user,
                 &req,
             )
@@ -29,6 +31,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::user::query_user::QueryUserApi,
                 action::user::query_user::query_user,
+                Permission::Read,
                 user,
                 &req,
             )
@@ -39,6 +42,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::user::read_user::ReadUserApi,
                 action::user::read_user::read_user,
+                Permission::Read,
                 user,
                 &req,
             )
@@ -49,6 +53,73 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::user::invite_user::InviteUserApi,
                 action::user::invite_user::invite_user,
+                Permission::Manage,
+                user,
+                &req,
+            )
+            .await
+        }
+        //修改用户角色
+        sdk::user::update_user_role::UPDATE_USER_ROLE_API => {
+            call_user_api(
+                sdk::user::update_user_role::UpdateUserRoleApi,
+                action::user::update_user_role::update_user_role,
+                Permission::Manage,
+                user,
+                &req,
+            )
+            .await
+        }
+        //新增服务账号
+        sdk::user::insert_service_account::INSERT_SERVICE_ACCOUNT_API => {
+            call_user_api(
+                sdk::user::insert_service_account::InsertServiceAccountApi,
+                action::user::insert_service_account::insert_service_account,
+                Permission::Manage,
+                user,
+                &req,
+            )
+            .await
+        }
+        //删除服务账号
+        sdk::user::delete_service_account::DELETE_SERVICE_ACCOUNT_API => {
+            call_user_api(
+                sdk::user::delete_service_account::DeleteServiceAccountApi,
+                action::user::delete_service_account::delete_service_account,
+                Permission::Manage,
+                user,
+                &req,
+            )
+            .await
+        }
+        //新增访问令牌
+        sdk::api_token::insert_api_token::INSERT_API_TOKEN_API => {
+            call_user_api(
+                sdk::api_token::insert_api_token::InsertApiTokenApi,
+                action::api_token::insert_api_token::insert_api_token,
+                Permission::Execute,
+                user,
+                &req,
+            )
+            .await
+        }
+        //删除访问令牌
+        sdk::api_token::delete_api_token::DELETE_API_TOKEN_API => {
+            call_user_api(
+                sdk::api_token::delete_api_token::DeleteApiTokenApi,
+                action::api_token::delete_api_token::delete_api_token,
+                Permission::Execute,
+                user,
+                &req,
+            )
+            .await
+        }
+        //查询访问令牌
+        sdk::api_token::query_api_token::QUERY_API_TOKEN_API => {
+            call_user_api(
+                sdk::api_token::query_api_token::QueryApiTokenApi,
+                action::api_token::query_api_token::query_api_token,
+                Permission::Read,
                 user,
                 &req,
             )
@@ -59,6 +130,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::extension::query_extension::QueryExtensionApi,
                 action::extension::query_extension::query_extension,
+                Permission::Read,
                 user,
                 &req,
             )
@@ -69,6 +141,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::extension::test_configuration::TestConfigurationApi,
                 action::extension::test_configuration::test_configuration,
+                Permission::Manage,
                 user,
                 &req,
             )
@@ -79,6 +152,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::environment_schema::save_environment_schema::SaveEnvironmentSchemaApi,
                 action::environment_schema::save_environment_schema::save_environment_schema,
+                Permission::Manage,
                 user,
                 &req,
             )
@@ -89,6 +163,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::environment_schema::read_environment_schema::ReadEnvironmentSchemaApi,
                 action::environment_schema::read_environment_schema::read_environment_schema,
+                Permission::Read,
                 user,
                 &req,
             )
@@ -99,6 +174,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::environment_schema::delete_environment_schema::DeleteEnvironmentSchemaApi,
                 action::environment_schema::delete_environment_schema::delete_environment_schema,
+                Permission::Manage,
                 user,
                 &req,
             )
@@ -109,6 +185,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::environment_schema::query_environment_schema::QueryEnvironmentSchemaApi,
                 action::environment_schema::query_environment_schema::query_environment_schema,
+                Permission::Read,
                 user,
                 &req,
             )
@@ -119,6 +196,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::environment::insert_environment::InsertEnvironmentApi,
                 action::environment::insert_environment::insert_environment,
+                Permission::Manage,
                 user,
                 &req,
             )
@@ -129,6 +207,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::environment::update_environment::UpdateEnvironmentApi,
                 action::environment::update_environment::update_environment,
+                Permission::Manage,
                 user,
                 &req,
             )
@@ -139,6 +218,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::environment::read_environment::ReadEnvironmentApi,
                 action::environment::read_environment::read_environment,
+                Permission::Read,
                 user,
                 &req,
             )
@@ -149,6 +229,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::environment::delete_environment::DeleteEnvironmentApi,
                 action::environment::delete_environment::delete_environment,
+                Permission::Manage,
                 user,
                 &req,
             )
@@ -159,6 +240,84 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::environment::query_environment::QueryEnvironmentApi,
                 action::environment::query_environment::query_environment,
+                Permission::Read,
+                user,
+                &req,
+            )
+            .await
+        }
+        //查询环境授权
+        sdk::environment::query_environment_grant::QUERY_ENVIRONMENT_GRANT_API => {
+            call_user_api(
+                sdk::environment::query_environment_grant::QueryEnvironmentGrantApi,
+                action::environment::query_environment_grant::query_environment_grant,
+                Permission::Read,
+                user,
+                &req,
+            )
+            .await
+        }
+        //保存环境授权
+        sdk::environment::save_environment_grant::SAVE_ENVIRONMENT_GRANT_API => {
+            call_user_api(
+                sdk::environment::save_environment_grant::SaveEnvironmentGrantApi,
+                action::environment::save_environment_grant::save_environment_grant,
+                Permission::Manage,
+                user,
+                &req,
+            )
+            .await
+        }
+        //新增执行器
+        sdk::runner::insert_runner::INSERT_RUNNER_API => {
+            call_user_api(
+                sdk::runner::insert_runner::InsertRunnerApi,
+                action::runner::insert_runner::insert_runner,
+                Permission::Manage,
+                user,
+                &req,
+            )
+            .await
+        }
+        //删除执行器
+        sdk::runner::delete_runner::DELETE_RUNNER_API => {
+            call_user_api(
+                sdk::runner::delete_runner::DeleteRunnerApi,
+                action::runner::delete_runner::delete_runner,
+                Permission::Manage,
+                user,
+                &req,
+            )
+            .await
+        }
+        //查询执行器
+        sdk::runner::query_runner::QUERY_RUNNER_API => {
+            call_user_api(
+                sdk::runner::query_runner::QueryRunnerApi,
+                action::runner::query_runner::query_runner,
+                Permission::Read,
+                user,
+                &req,
+            )
+            .await
+        }
+        //读取执行记录的保留策略
+        sdk::organization::read_retention_policy::READ_RETENTION_POLICY_API => {
+            call_user_api(
+                sdk::organization::read_retention_policy::ReadRetentionPolicyApi,
+                action::organization::read_retention_policy::read_retention_policy,
+                Permission::Read,
+                user,
+                &req,
+            )
+            .await
+        }
+        //修改执行记录的保留策略
+        sdk::organization::update_retention_policy::UPDATE_RETENTION_POLICY_API => {
+            call_user_api(
+                sdk::organization::update_retention_policy::UpdateRetentionPolicyApi,
+                action::organization::update_retention_policy::update_retention_policy,
+                Permission::Manage,
                 user,
                 &req,
             )
@@ -169,6 +328,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::job::insert_job::InsertJobApi,
                 action::job::insert_job::insert_job,
+                Permission::Manage,
                 user,
                 &req,
             )
@@ -179,6 +339,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::job::update_job::UpdateJobApi,
                 action::job::update_job::update_job,
+                Permission::Manage,
                 user,
                 &req,
             )
@@ -189,6 +350,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::job::read_job::ReadJobApi,
                 action::job::read_job::read_job,
+                Permission::Read,
                 user,
                 &req,
             )
@@ -199,6 +361,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::job::delete_job::DeleteJobApi,
                 action::job::delete_job::delete_job,
+                Permission::Manage,
                 user,
                 &req,
             )
@@ -209,6 +372,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::job::query_job::QueryJobApi,
                 action::job::query_job::query_job,
+                Permission::Read,
                 user,
                 &req,
             )
@@ -219,6 +383,7 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::job_record::query_job_record::QueryJobRecordApi,
                 action::job_record::query_job_record::query_job_record,
+                Permission::Read,
                 user,
                 &req,
             )
@@ -229,6 +394,18 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::job::start_job::StartJobApi,
                 action::job::start_job::start_job,
+                Permission::Execute,
+                user,
+                &req,
+            )
+            .await
+        }
+        //预览job任务
+        sdk::job::plan_job::PLAN_JOB_API => {
+            call_user_api(
+                sdk::job::plan_job::PlanJobApi,
+                action::job::plan_job::plan_job,
+                Permission::Execute,
                 user,
                 &req,
             )
@@ -239,6 +416,29 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::job::continue_job::ContinueJobApi,
                 action::job::continue_job::continue_job,
+                Permission::Execute,
+                user,
+                &req,
+            )
+            .await
+        }
+        //从失败的步骤开始重新执行
+        sdk::job::retry_job_record::RETRY_JOB_RECORD_API => {
+            call_user_api(
+                sdk::job::retry_job_record::RetryJobRecordApi,
+                action::job::retry_job_record::retry_job_record,
+                Permission::Execute,
+                user,
+                &req,
+            )
+            .await
+        }
+        //取消job任务
+        sdk::job::cancel_job::CANCEL_JOB_API => {
+            call_user_api(
+                sdk::job::cancel_job::CancelJobApi,
+                action::job::cancel_job::cancel_job,
+                Permission::Execute,
                 user,
                 &req,
             )
@@ -249,6 +449,139 @@ pub async fn dispatch_user_api(
             call_user_api(
                 sdk::job_record::read_job_record::ReadJobRecordApi,
                 action::job_record::read_job_record::read_job_record,
+                Permission::Read,
+                user,
+                &req,
+            )
+            .await
+        }
+        //置顶或者取消置顶执行记录
+        sdk::job_record::pin_job_record::PIN_JOB_RECORD_API => {
+            call_user_api(
+                sdk::job_record::pin_job_record::PinJobRecordApi,
+                action::job_record::pin_job_record::pin_job_record,
+                Permission::Execute,
+                user,
+                &req,
+            )
+            .await
+        }
+        //审批通过执行记录
+        sdk::job_record::approve_job_record::APPROVE_JOB_RECORD_API => {
+            call_user_api(
+                sdk::job_record::approve_job_record::ApproveJobRecordApi,
+                action::job_record::approve_job_record::approve_job_record,
+                Permission::Execute,
+                user,
+                &req,
+            )
+            .await
+        }
+        //驳回执行记录
+        sdk::job_record::reject_job_record::REJECT_JOB_RECORD_API => {
+            call_user_api(
+                sdk::job_record::reject_job_record::RejectJobRecordApi,
+                action::job_record::reject_job_record::reject_job_record,
+                Permission::Execute,
+                user,
+                &req,
+            )
+            .await
+        }
+        //删除执行记录
+        sdk::job_record::delete_job_record::DELETE_JOB_RECORD_API => {
+            call_user_api(
+                sdk::job_record::delete_job_record::DeleteJobRecordApi,
+                action::job_record::delete_job_record::delete_job_record,
+                Permission::Manage,
+                user,
+                &req,
+            )
+            .await
+        }
+        //订阅资源执行的实时日志
+        sdk::job_record::watch_step_resource_log::WATCH_STEP_RESOURCE_LOG_API => {
+            call_user_api(
+                sdk::job_record::watch_step_resource_log::WatchStepResourceLogApi,
+                action::job_record::watch_step_resource_log::watch_step_resource_log,
+                Permission::Read,
+                user,
+                &req,
+            )
+            .await
+        }
+        //分页查询资源执行的日志
+        sdk::job_record::query_step_resource_log::QUERY_STEP_RESOURCE_LOG_API => {
+            call_user_api(
+                sdk::job_record::query_step_resource_log::QueryStepResourceLogApi,
+                action::job_record::query_step_resource_log::query_step_resource_log,
+                Permission::Read,
+                user,
+                &req,
+            )
+            .await
+        }
+        //下载资源执行的完整日志
+        sdk::job_record::download_step_resource_log::DOWNLOAD_STEP_RESOURCE_LOG_API => {
+            call_user_api(
+                sdk::job_record::download_step_resource_log::DownloadStepResourceLogApi,
+                action::job_record::download_step_resource_log::download_step_resource_log,
+                Permission::Read,
+                user,
+                &req,
+            )
+            .await
+        }
+        //新增任务计划
+        sdk::job_schedule::insert_job_schedule::INSERT_JOB_SCHEDULE_API => {
+            call_user_api(
+                sdk::job_schedule::insert_job_schedule::InsertJobScheduleApi,
+                action::job_schedule::insert_job_schedule::insert_job_schedule,
+                Permission::Manage,
+                user,
+                &req,
+            )
+            .await
+        }
+        //修改任务计划
+        sdk::job_schedule::update_job_schedule::UPDATE_JOB_SCHEDULE_API => {
+            call_user_api(
+                sdk::job_schedule::update_job_schedule::UpdateJobScheduleApi,
+                action::job_schedule::update_job_schedule::update_job_schedule,
+                Permission::Manage,
+                user,
+                &req,
+            )
+            .await
+        }
+        //删除任务计划
+        sdk::job_schedule::delete_job_schedule::DELETE_JOB_SCHEDULE_API => {
+            call_user_api(
+                sdk::job_schedule::delete_job_schedule::DeleteJobScheduleApi,
+                action::job_schedule::delete_job_schedule::delete_job_schedule,
+                Permission::Manage,
+                user,
+                &req,
+            )
+            .await
+        }
+        //查询任务计划
+        sdk::job_schedule::query_job_schedule::QUERY_JOB_SCHEDULE_API => {
+            call_user_api(
+                sdk::job_schedule::query_job_schedule::QueryJobScheduleApi,
+                action::job_schedule::query_job_schedule::query_job_schedule,
+                Permission::Read,
+                user,
+                &req,
+            )
+            .await
+        }
+        //读取任务计划
+        sdk::job_schedule::read_job_schedule::READ_JOB_SCHEDULE_API => {
+            call_user_api(
+                sdk::job_schedule::read_job_schedule::ReadJobScheduleApi,
+                action::job_schedule::read_job_schedule::read_job_schedule,
+                Permission::Read,
                 user,
                 &req,
             )
@@ -403,3 +736,20 @@ pub static WHITE_LIST_NAMESPACE: &[&'static str] = &[
     sdk::auth::logout::LOGOUT_API,
     sdk::auth::get_curr_user::GET_CURR_USER_API,
 ];
+
+//只能在浏览器里登录之后调用的api，访问令牌不能调用，避免令牌被用来修改密码、签发新令牌或者代替人做审批和签收
+pub static SESSION_ONLY_NAMESPACE: &[&'static str] = &[
+    sdk::auth::change_password::CHANGE_PASSWORD_API,
+    sdk::api_token::insert_api_token::INSERT_API_TOKEN_API,
+    sdk::api_token::delete_api_token::DELETE_API_TOKEN_API,
+    sdk::api_token::query_api_token::QUERY_API_TOKEN_API,
+    sdk::job::continue_job::CONTINUE_JOB_API,
+    sdk::job_record::approve_job_record::APPROVE_JOB_RECORD_API,
+    sdk::job_record::reject_job_record::REJECT_JOB_RECORD_API,
+];
+
+//只能执行指定任务的访问令牌可以调用的执行类api，这些api会检查任务是否在令牌指定的任务里
+pub static JOB_SCOPED_NAMESPACE: &[&'static str] = &[
+    sdk::job::start_job::START_JOB_API,
+    sdk::job::plan_job::PLAN_JOB_API,
+];
@@ -0,0 +1,366 @@
+use tihu_native::errno::execute_error;
+use tihu_native::errno::extract_data_error;
+use tihu_native::errno::prepare_statement_error;
+use tihu_native::errno::query_error;
+use tihu_native::errno::undefined_enum_value;
+use tihu_native::ErrNo;
+use tihu::Id;
+use tihu::LightString;
+use lazy_static;
+use format_xml;
+use std::borrow::Cow;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{Row, Transaction};
+use native_common::utils::add_val;
+use native_common::utils::add_vals;
+use native_common::utils::Condition;
+use native_common::utils::add_conditions;
+use native_common::utils::calc_sql_pagination;
+use crate::log;
+use crate::model::api_token::properties;
+use crate::model::api_token::ApiToken;
+use crate::model::api_token::ApiTokenProperty;
+use crate::model::api_token::ApiTokenOpt;
+use crate::model::api_token::enums::try_i16_to_scope;
+use crate::native_common;
+
+const ENTITY: &str = "api_token";
+const EXTRA_PROPERTIES: [&str; 12] = [properties::ORG_ID,properties::USER_ID,properties::NAME,properties::TOKEN_PREFIX,properties::TOKEN_HASH,properties::SCOPE,properties::JOB_IDS,properties::EXPIRE_TIME,properties::LAST_USED_TIME,properties::CREATED_BY,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
+const PROPERTY_COUNT: usize = EXTRA_PROPERTIES.len()+1;
+
+fn gen_properties() -> String {
+    let properties:Vec<&str> = [properties::ID].iter().chain(EXTRA_PROPERTIES.iter()).map(|item|*item).collect();
+    return properties.join(",");
+}
+
+lazy_static::lazy_static! {
+    static ref PROPERTIES: String = gen_properties();
+}
+
+fn extract_api_token(row: &Row) -> Result<ApiToken, ErrNo> {
+    return Ok(ApiToken {
+        id: row.try_get(properties::ID).map_err(extract_data_error)?,
+        org_id: row.try_get(properties::ORG_ID).map_err(extract_data_error)?,
+        user_id: row.try_get(properties::USER_ID).map_err(extract_data_error)?,
+        name: row.try_get(properties::NAME).map_err(extract_data_error)?,
+        token_prefix: row.try_get(properties::TOKEN_PREFIX).map_err(extract_data_error)?,
+        token_hash: row.try_get(properties::TOKEN_HASH).map_err(extract_data_error)?,
+        scope: try_i16_to_scope(row.try_get(properties::SCOPE).map_err(extract_data_error)?).map_err(undefined_enum_value)?,
+        job_ids: row.try_get(properties::JOB_IDS).map_err(extract_data_error)?,
+        expire_time: row.try_get(properties::EXPIRE_TIME).map_err(extract_data_error)?,
+        last_used_time: row.try_get(properties::LAST_USED_TIME).map_err(extract_data_error)?,
+        created_by: row.try_get(properties::CREATED_BY).map_err(extract_data_error)?,
+        created_time: row.try_get(properties::CREATED_TIME).map_err(extract_data_error)?,
+        last_modified_time: row.try_get(properties::LAST_MODIFIED_TIME).map_err(extract_data_error)?,
+    });
+}
+
+fn opt_to_conditions<'a>(opt: &'a ApiTokenOpt) -> Vec::<(Condition, &'a (dyn ToSql + std::marker::Sync))> {
+    let mut pairs = Vec::<(Condition,&(dyn ToSql + std::marker::Sync))>::new();
+    if let Some(id) = opt.id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ID), operator: None}, id));
+    }
+    if let Some(org_id) = opt.org_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ORG_ID), operator: None}, org_id));
+    }
+    if let Some(user_id) = opt.user_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::USER_ID), operator: None}, user_id));
+    }
+    if let Some(name) = opt.name.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::NAME), operator: None}, name));
+    }
+    if let Some(token_prefix) = opt.token_prefix.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::TOKEN_PREFIX), operator: None}, token_prefix));
+    }
+    if let Some(token_hash) = opt.token_hash.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::TOKEN_HASH), operator: None}, token_hash));
+    }
+    if let Some(scope) = opt.scope.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::SCOPE), operator: None}, scope));
+    }
+    if let Some(job_ids) = opt.job_ids.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::JOB_IDS), operator: None}, job_ids));
+    }
+    if let Some(expire_time) = opt.expire_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::EXPIRE_TIME), operator: None}, expire_time));
+    }
+    if let Some(last_used_time) = opt.last_used_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::LAST_USED_TIME), operator: None}, last_used_time));
+    }
+    if let Some(created_by) = opt.created_by.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::CREATED_BY), operator: None}, created_by));
+    }
+    if let Some(created_time) = opt.created_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::CREATED_TIME), operator: None}, created_time));
+    }
+    if let Some(last_modified_time) = opt.last_modified_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::LAST_MODIFIED_TIME), operator: None}, last_modified_time));
+    }
+    return pairs;
+}
+
+pub struct ApiTokenBaseService<'a> {
+    transaction: &'a Transaction<'a>
+}
+
+impl<'a> ApiTokenBaseService<'a> {
+
+    pub fn new(transaction: &'a Transaction) -> ApiTokenBaseService<'a> {
+        return ApiTokenBaseService {
+            transaction: transaction
+        };
+    }
+
+    pub async fn read_api_token(&self, id: Id) -> Result<Option<ApiToken>, ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}" where "{properties::ID}"="{add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let row_opt: Option<Row> = self.transaction.query_opt(&statement, &vals).await.map_err(query_error)?;
+        return Ok(row_opt.as_ref().map(extract_api_token).transpose()?);
+    }
+
+    pub async fn read_api_token_batch(&self, ids: &[Id]) -> Result<Vec<ApiToken>, ErrNo>
+    {
+        if ids.is_empty() {
+            log::warn!("待批量读取的访问令牌id集合为空");
+            return Ok(Vec::new());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(ids.len());
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}" where "{properties::ID}" in (" {add_vals(&mut vals, &ids)} ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<ApiToken> = rows.iter().map(extract_api_token).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+    pub async fn insert_api_token(&self, api_token: &ApiToken) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT);
+        let sql = format_xml::template! {
+            "insert into "{ENTITY}"(" {PROPERTIES.as_str()} ") values(" {
+                vec![
+                    add_val(&mut vals, &api_token.id),
+                    add_val(&mut vals, &api_token.org_id),
+                    add_val(&mut vals, &api_token.user_id),
+                    add_val(&mut vals, &api_token.name),
+                    add_val(&mut vals, &api_token.token_prefix),
+                    add_val(&mut vals, &api_token.token_hash),
+                    add_val(&mut vals, &api_token.scope),
+                    add_val(&mut vals, &api_token.job_ids),
+                    add_val(&mut vals, &api_token.expire_time),
+                    add_val(&mut vals, &api_token.last_used_time),
+                    add_val(&mut vals, &api_token.created_by),
+                    add_val(&mut vals, &api_token.created_time),
+                    add_val(&mut vals, &api_token.last_modified_time),
+                ].join(",")
+            } ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn insert_api_token_batch(&self, api_token_list: &[ApiToken]) -> Result<(), ErrNo> {
+        if api_token_list.is_empty() {
+            log::warn!("待批量新增的访问令牌集合为空");
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT * api_token_list.len());
+        let sql = format_xml::template! {
+            "insert into "{ENTITY}"(" {PROPERTIES.as_str()} ") values" {
+                api_token_list.iter().map(|api_token|{
+                    let trunks:Vec<String> = vec![
+                        add_val(&mut vals, &api_token.id),
+                        add_val(&mut vals, &api_token.org_id),
+                        add_val(&mut vals, &api_token.user_id),
+                        add_val(&mut vals, &api_token.name),
+                        add_val(&mut vals, &api_token.token_prefix),
+                        add_val(&mut vals, &api_token.token_hash),
+                        add_val(&mut vals, &api_token.scope),
+                        add_val(&mut vals, &api_token.job_ids),
+                        add_val(&mut vals, &api_token.expire_time),
+                        add_val(&mut vals, &api_token.last_used_time),
+                        add_val(&mut vals, &api_token.created_by),
+                        add_val(&mut vals, &api_token.created_time),
+                        add_val(&mut vals, &api_token.last_modified_time),
+                    ];
+                    ["(", &trunks.join(","), ")"].concat()
+                }).collect::<Vec<String>>().join(",")
+            }
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn update_api_token_full(&self, api_token: &ApiToken) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT);
+        let sql = format_xml::template! {
+            "update "{ENTITY}" set " {
+                [
+                    properties::ORG_ID, "=", &add_val(&mut vals, &api_token.org_id),
+                    ",", properties::USER_ID, "=", &add_val(&mut vals, &api_token.user_id),
+                    ",", properties::NAME, "=", &add_val(&mut vals, &api_token.name),
+                    ",", properties::TOKEN_PREFIX, "=", &add_val(&mut vals, &api_token.token_prefix),
+                    ",", properties::TOKEN_HASH, "=", &add_val(&mut vals, &api_token.token_hash),
+                    ",", properties::SCOPE, "=", &add_val(&mut vals, &api_token.scope),
+                    ",", properties::JOB_IDS, "=", &add_val(&mut vals, &api_token.job_ids),
+                    ",", properties::EXPIRE_TIME, "=", &add_val(&mut vals, &api_token.expire_time),
+                    ",", properties::LAST_USED_TIME, "=", &add_val(&mut vals, &api_token.last_used_time),
+                    ",", properties::CREATED_BY, "=", &add_val(&mut vals, &api_token.created_by),
+                    ",", properties::CREATED_TIME, "=", &add_val(&mut vals, &api_token.created_time),
+                    ",", properties::LAST_MODIFIED_TIME, "=", &add_val(&mut vals, &api_token.last_modified_time),
+                ].concat()
+            } " where "{properties::ID}"=" {add_val(&mut vals, &api_token.id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn update_api_token(&self, id: Id, changes: &[ApiTokenProperty]) -> Result<(), ErrNo> {
+        let changes: Vec<&ApiTokenProperty> = changes
+            .iter()
+            .filter(|change| match change {
+                ApiTokenProperty::Id(_) => false,
+                _ => true,
+            })
+            .collect();
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1 + changes.len());
+        let mut trunks: Vec<Cow<str>> = Vec::with_capacity(4 * changes.len());
+        for (index, property) in changes.iter().enumerate() {
+            if 0 < index {
+                trunks.push(",".into());
+            }
+            trunks.extend(match property {
+                ApiTokenProperty::Id(id) => {
+                    [properties::ID.into(), "=".into(), add_val(&mut vals, id).into()]
+                },
+                ApiTokenProperty::OrgId(org_id) => {
+                    [properties::ORG_ID.into(), "=".into(), add_val(&mut vals, org_id).into()]
+                },
+                ApiTokenProperty::UserId(user_id) => {
+                    [properties::USER_ID.into(), "=".into(), add_val(&mut vals, user_id).into()]
+                },
+                ApiTokenProperty::Name(name) => {
+                    [properties::NAME.into(), "=".into(), add_val(&mut vals, name).into()]
+                },
+                ApiTokenProperty::TokenPrefix(token_prefix) => {
+                    [properties::TOKEN_PREFIX.into(), "=".into(), add_val(&mut vals, token_prefix).into()]
+                },
+                ApiTokenProperty::TokenHash(token_hash) => {
+                    [properties::TOKEN_HASH.into(), "=".into(), add_val(&mut vals, token_hash).into()]
+                },
+                ApiTokenProperty::Scope(scope) => {
+                    [properties::SCOPE.into(), "=".into(), add_val(&mut vals, scope).into()]
+                },
+                ApiTokenProperty::JobIds(job_ids) => {
+                    [properties::JOB_IDS.into(), "=".into(), add_val(&mut vals, job_ids).into()]
+                },
+                ApiTokenProperty::ExpireTime(expire_time) => {
+                    [properties::EXPIRE_TIME.into(), "=".into(), add_val(&mut vals, expire_time).into()]
+                },
+                ApiTokenProperty::LastUsedTime(last_used_time) => {
+                    [properties::LAST_USED_TIME.into(), "=".into(), add_val(&mut vals, last_used_time).into()]
+                },
+                ApiTokenProperty::CreatedBy(created_by) => {
+                    [properties::CREATED_BY.into(), "=".into(), add_val(&mut vals, created_by).into()]
+                },
+                ApiTokenProperty::CreatedTime(created_time) => {
+                    [properties::CREATED_TIME.into(), "=".into(), add_val(&mut vals, created_time).into()]
+                },
+                ApiTokenProperty::LastModifiedTime(last_modified_time) => {
+                    [properties::LAST_MODIFIED_TIME.into(), "=".into(), add_val(&mut vals, last_modified_time).into()]
+                },
+            });
+        }
+        let change_content: String = trunks.concat();
+        let sql = format_xml::template! {
+            "update "{ENTITY}" set " {change_content} " where "{properties::ID}"=" {add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn delete_api_token(&self, id: Id) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1);
+        let sql = format_xml::template! {
+            "delete from "{ENTITY}" where "{properties::ID}"="{add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn delete_api_token_batch(&self, ids: &[Id]) -> Result<(), ErrNo>
+    {
+        if ids.is_empty() {
+            log::warn!("待批量删除的访问令牌id集合为空");
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(ids.len());
+        let sql = format_xml::template! {
+            "delete from "{ENTITY}" where "{properties::ID}" in (" {add_vals(&mut vals, &ids)} ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn query_api_token_count(&self, opt: &ApiTokenOpt) -> Result<u64, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let sql = format_xml::template! {
+            "select count(1) from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let row: Row = self.transaction.query_one(&statement, &vals).await.map_err(query_error)?;
+        let count:i64 = row.get(0);
+        return Ok(count as u64);
+    }
+
+    pub async fn query_api_token(&self, page_no: u64, page_size: u64, opt: &ApiTokenOpt) -> Result<Vec<ApiToken>, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let (limit, offset) = calc_sql_pagination(page_no, page_size);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+            " limit "{limit}" offset "{offset}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<ApiToken> = rows.iter().map(extract_api_token).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+    pub async fn query_api_token_one(&self, opt: &ApiTokenOpt) -> Result<Option<ApiToken>, ErrNo> {
+        let list = self.query_api_token(1, 1, opt).await?;
+        return Ok(list.into_iter().next());
+    }
+
+    pub async fn query_api_token_batch(&self, opt: &ApiTokenOpt) -> Result<Vec<ApiToken>, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<ApiToken> = rows.iter().map(extract_api_token).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+}
\ No newline at end of file
@@ -21,10 +21,11 @@ use crate::model::environment::properties;
 use crate::model::environment::Environment;
 use crate::model::environment::EnvironmentProperty;
 use crate::model::environment::EnvironmentOpt;
+use crate::model::environment::enums::try_i16_to_lock_policy;
 use crate::native_common;
 
 const ENTITY: &str = "environment";
-const EXTRA_PROPERTIES: [&str; 5] = [properties::ORG_ID,properties::ENVIRONMENT_SCHEMA_ID,properties::NAME,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
+const EXTRA_PROPERTIES: [&str; 9] = [properties::ORG_ID,properties::ENVIRONMENT_SCHEMA_ID,properties::NAME,properties::LOCK_POLICY,properties::RUNNER_LABEL,properties::PROTECTED,properties::REQUIRED_APPROVALS,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
 const PROPERTY_COUNT: usize = EXTRA_PROPERTIES.len()+1;
 
 fn gen_properties() -> String {
@@ -42,6 +43,10 @@ fn extract_environment(row: &Row) -> Result<Environment, ErrNo> {
         org_id: row.try_get(properties::ORG_ID).map_err(extract_data_error)?,
         environment_schema_id: row.try_get(properties::ENVIRONMENT_SCHEMA_ID).map_err(extract_data_error)?,
         name: row.try_get(properties::NAME).map_err(extract_data_error)?,
+        lock_policy: try_i16_to_lock_policy(row.try_get(properties::LOCK_POLICY).map_err(extract_data_error)?).map_err(undefined_enum_value)?,
+        runner_label: row.try_get(properties::RUNNER_LABEL).map_err(extract_data_error)?,
+        protected: row.try_get(properties::PROTECTED).map_err(extract_data_error)?,
+        required_approvals: row.try_get(properties::REQUIRED_APPROVALS).map_err(extract_data_error)?,
         created_time: row.try_get(properties::CREATED_TIME).map_err(extract_data_error)?,
         last_modified_time: row.try_get(properties::LAST_MODIFIED_TIME).map_err(extract_data_error)?,
     });
@@ -61,6 +66,18 @@ fn opt_to_conditions<'a>(opt: &'a EnvironmentOpt) -> Vec::<(Condition, &'a (dyn
     if let Some(name) = opt.name.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::NAME), operator: None}, name));
     }
+    if let Some(lock_policy) = opt.lock_policy.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::LOCK_POLICY), operator: None}, lock_policy));
+    }
+    if let Some(runner_label) = opt.runner_label.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::RUNNER_LABEL), operator: None}, runner_label));
+    }
+    if let Some(protected) = opt.protected.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::PROTECTED), operator: None}, protected));
+    }
+    if let Some(required_approvals) = opt.required_approvals.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::REQUIRED_APPROVALS), operator: None}, required_approvals));
+    }
     if let Some(created_time) = opt.created_time.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::CREATED_TIME), operator: None}, created_time));
     }
@@ -117,6 +134,10 @@ impl<'a> EnvironmentBaseService<'a> {
                     add_val(&mut vals, &environment.org_id),
                     add_val(&mut vals, &environment.environment_schema_id),
                     add_val(&mut vals, &environment.name),
+                    add_val(&mut vals, &environment.lock_policy),
+                    add_val(&mut vals, &environment.runner_label),
+                    add_val(&mut vals, &environment.protected),
+                    add_val(&mut vals, &environment.required_approvals),
                     add_val(&mut vals, &environment.created_time),
                     add_val(&mut vals, &environment.last_modified_time),
                 ].join(",")
@@ -141,6 +162,10 @@ impl<'a> EnvironmentBaseService<'a> {
                         add_val(&mut vals, &environment.org_id),
                         add_val(&mut vals, &environment.environment_schema_id),
                         add_val(&mut vals, &environment.name),
+                        add_val(&mut vals, &environment.lock_policy),
+                        add_val(&mut vals, &environment.runner_label),
+                        add_val(&mut vals, &environment.protected),
+                        add_val(&mut vals, &environment.required_approvals),
                         add_val(&mut vals, &environment.created_time),
                         add_val(&mut vals, &environment.last_modified_time),
                     ];
@@ -161,6 +186,10 @@ impl<'a> EnvironmentBaseService<'a> {
                     properties::ORG_ID, "=", &add_val(&mut vals, &environment.org_id),
                     ",", properties::ENVIRONMENT_SCHEMA_ID, "=", &add_val(&mut vals, &environment.environment_schema_id),
                     ",", properties::NAME, "=", &add_val(&mut vals, &environment.name),
+                    ",", properties::LOCK_POLICY, "=", &add_val(&mut vals, &environment.lock_policy),
+                    ",", properties::RUNNER_LABEL, "=", &add_val(&mut vals, &environment.runner_label),
+                    ",", properties::PROTECTED, "=", &add_val(&mut vals, &environment.protected),
+                    ",", properties::REQUIRED_APPROVALS, "=", &add_val(&mut vals, &environment.required_approvals),
                     ",", properties::CREATED_TIME, "=", &add_val(&mut vals, &environment.created_time),
                     ",", properties::LAST_MODIFIED_TIME, "=", &add_val(&mut vals, &environment.last_modified_time),
                 ].concat()
@@ -201,6 +230,18 @@ impl<'a> EnvironmentBaseService<'a> {
                 EnvironmentProperty::Name(name) => {
                     [properties::NAME.into(), "=".into(), add_val(&mut vals, name).into()]
                 },
+                EnvironmentProperty::LockPolicy(lock_policy) => {
+                    [properties::LOCK_POLICY.into(), "=".into(), add_val(&mut vals, lock_policy).into()]
+                },
+                EnvironmentProperty::RunnerLabel(runner_label) => {
+                    [properties::RUNNER_LABEL.into(), "=".into(), add_val(&mut vals, runner_label).into()]
+                },
+                EnvironmentProperty::Protected(protected) => {
+                    [properties::PROTECTED.into(), "=".into(), add_val(&mut vals, protected).into()]
+                },
+                EnvironmentProperty::RequiredApprovals(required_approvals) => {
+                    [properties::REQUIRED_APPROVALS.into(), "=".into(), add_val(&mut vals, required_approvals).into()]
+                },
                 EnvironmentProperty::CreatedTime(created_time) => {
                     [properties::CREATED_TIME.into(), "=".into(), add_val(&mut vals, created_time).into()]
                 },
@@ -0,0 +1,336 @@
+use tihu_native::errno::execute_error;
+use tihu_native::errno::extract_data_error;
+use tihu_native::errno::prepare_statement_error;
+use tihu_native::errno::query_error;
+use tihu_native::errno::undefined_enum_value;
+use tihu_native::ErrNo;
+use tihu::Id;
+use tihu::LightString;
+use lazy_static;
+use format_xml;
+use std::borrow::Cow;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{Row, Transaction};
+use native_common::utils::add_val;
+use native_common::utils::add_vals;
+use native_common::utils::Condition;
+use native_common::utils::add_conditions;
+use native_common::utils::calc_sql_pagination;
+use crate::log;
+use crate::model::environment_lock::properties;
+use crate::model::environment_lock::EnvironmentLock;
+use crate::model::environment_lock::EnvironmentLockProperty;
+use crate::model::environment_lock::EnvironmentLockOpt;
+use crate::model::environment_lock::enums::try_i16_to_status;
+use crate::native_common;
+
+const ENTITY: &str = "environment_lock";
+const EXTRA_PROPERTIES: [&str; 9] = [properties::ORG_ID,properties::ENVIRONMENT_ID,properties::RECORD_ID,properties::JOB_ID,properties::HOLDER_ID,properties::HOLDER_NAME,properties::STATUS,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
+const PROPERTY_COUNT: usize = EXTRA_PROPERTIES.len()+1;
+
+fn gen_properties() -> String {
+    let properties:Vec<&str> = [properties::ID].iter().chain(EXTRA_PROPERTIES.iter()).map(|item|*item).collect();
+    return properties.join(",");
+}
+
+lazy_static::lazy_static! {
+    static ref PROPERTIES: String = gen_properties();
+}
+
+fn extract_environment_lock(row: &Row) -> Result<EnvironmentLock, ErrNo> {
+    return Ok(EnvironmentLock {
+        id: row.try_get(properties::ID).map_err(extract_data_error)?,
+        org_id: row.try_get(properties::ORG_ID).map_err(extract_data_error)?,
+        environment_id: row.try_get(properties::ENVIRONMENT_ID).map_err(extract_data_error)?,
+        record_id: row.try_get(properties::RECORD_ID).map_err(extract_data_error)?,
+        job_id: row.try_get(properties::JOB_ID).map_err(extract_data_error)?,
+        holder_id: row.try_get(properties::HOLDER_ID).map_err(extract_data_error)?,
+        holder_name: row.try_get(properties::HOLDER_NAME).map_err(extract_data_error)?,
+        status: try_i16_to_status(row.try_get(properties::STATUS).map_err(extract_data_error)?).map_err(undefined_enum_value)?,
+        created_time: row.try_get(properties::CREATED_TIME).map_err(extract_data_error)?,
+        last_modified_time: row.try_get(properties::LAST_MODIFIED_TIME).map_err(extract_data_error)?,
+    });
+}
+
+fn opt_to_conditions<'a>(opt: &'a EnvironmentLockOpt) -> Vec::<(Condition, &'a (dyn ToSql + std::marker::Sync))> {
+    let mut pairs = Vec::<(Condition,&(dyn ToSql + std::marker::Sync))>::new();
+    if let Some(id) = opt.id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ID), operator: None}, id));
+    }
+    if let Some(org_id) = opt.org_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ORG_ID), operator: None}, org_id));
+    }
+    if let Some(environment_id) = opt.environment_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ENVIRONMENT_ID), operator: None}, environment_id));
+    }
+    if let Some(record_id) = opt.record_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::RECORD_ID), operator: None}, record_id));
+    }
+    if let Some(job_id) = opt.job_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::JOB_ID), operator: None}, job_id));
+    }
+    if let Some(holder_id) = opt.holder_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::HOLDER_ID), operator: None}, holder_id));
+    }
+    if let Some(holder_name) = opt.holder_name.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::HOLDER_NAME), operator: None}, holder_name));
+    }
+    if let Some(status) = opt.status.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::STATUS), operator: None}, status));
+    }
+    if let Some(created_time) = opt.created_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::CREATED_TIME), operator: None}, created_time));
+    }
+    if let Some(last_modified_time) = opt.last_modified_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::LAST_MODIFIED_TIME), operator: None}, last_modified_time));
+    }
+    return pairs;
+}
+
+pub struct EnvironmentLockBaseService<'a> {
+    transaction: &'a Transaction<'a>
+}
+
+impl<'a> EnvironmentLockBaseService<'a> {
+
+    pub fn new(transaction: &'a Transaction) -> EnvironmentLockBaseService<'a> {
+        return EnvironmentLockBaseService {
+            transaction: transaction
+        };
+    }
+
+    pub async fn read_environment_lock(&self, id: Id) -> Result<Option<EnvironmentLock>, ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}" where "{properties::ID}"="{add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let row_opt: Option<Row> = self.transaction.query_opt(&statement, &vals).await.map_err(query_error)?;
+        return Ok(row_opt.as_ref().map(extract_environment_lock).transpose()?);
+    }
+
+    pub async fn read_environment_lock_batch(&self, ids: &[Id]) -> Result<Vec<EnvironmentLock>, ErrNo>
+    {
+        if ids.is_empty() {
+            log::warn!("待批量读取的环境占用id集合为空");
+            return Ok(Vec::new());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(ids.len());
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}" where "{properties::ID}" in (" {add_vals(&mut vals, &ids)} ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<EnvironmentLock> = rows.iter().map(extract_environment_lock).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+    pub async fn insert_environment_lock(&self, environment_lock: &EnvironmentLock) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT);
+        let sql = format_xml::template! {
+            "insert into "{ENTITY}"(" {PROPERTIES.as_str()} ") values(" {
+                vec![
+                    add_val(&mut vals, &environment_lock.id),
+                    add_val(&mut vals, &environment_lock.org_id),
+                    add_val(&mut vals, &environment_lock.environment_id),
+                    add_val(&mut vals, &environment_lock.record_id),
+                    add_val(&mut vals, &environment_lock.job_id),
+                    add_val(&mut vals, &environment_lock.holder_id),
+                    add_val(&mut vals, &environment_lock.holder_name),
+                    add_val(&mut vals, &environment_lock.status),
+                    add_val(&mut vals, &environment_lock.created_time),
+                    add_val(&mut vals, &environment_lock.last_modified_time),
+                ].join(",")
+            } ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn insert_environment_lock_batch(&self, environment_lock_list: &[EnvironmentLock]) -> Result<(), ErrNo> {
+        if environment_lock_list.is_empty() {
+            log::warn!("待批量新增的环境占用集合为空");
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT * environment_lock_list.len());
+        let sql = format_xml::template! {
+            "insert into "{ENTITY}"(" {PROPERTIES.as_str()} ") values" {
+                environment_lock_list.iter().map(|environment_lock|{
+                    let trunks:Vec<String> = vec![
+                        add_val(&mut vals, &environment_lock.id),
+                        add_val(&mut vals, &environment_lock.org_id),
+                        add_val(&mut vals, &environment_lock.environment_id),
+                        add_val(&mut vals, &environment_lock.record_id),
+                        add_val(&mut vals, &environment_lock.job_id),
+                        add_val(&mut vals, &environment_lock.holder_id),
+                        add_val(&mut vals, &environment_lock.holder_name),
+                        add_val(&mut vals, &environment_lock.status),
+                        add_val(&mut vals, &environment_lock.created_time),
+                        add_val(&mut vals, &environment_lock.last_modified_time),
+                    ];
+                    ["(", &trunks.join(","), ")"].concat()
+                }).collect::<Vec<String>>().join(",")
+            }
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn update_environment_lock_full(&self, environment_lock: &EnvironmentLock) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT);
+        let sql = format_xml::template! {
+            "update "{ENTITY}" set " {
+                [
+                    properties::ORG_ID, "=", &add_val(&mut vals, &environment_lock.org_id),
+                    ",", properties::ENVIRONMENT_ID, "=", &add_val(&mut vals, &environment_lock.environment_id),
+                    ",", properties::RECORD_ID, "=", &add_val(&mut vals, &environment_lock.record_id),
+                    ",", properties::JOB_ID, "=", &add_val(&mut vals, &environment_lock.job_id),
+                    ",", properties::HOLDER_ID, "=", &add_val(&mut vals, &environment_lock.holder_id),
+                    ",", properties::HOLDER_NAME, "=", &add_val(&mut vals, &environment_lock.holder_name),
+                    ",", properties::STATUS, "=", &add_val(&mut vals, &environment_lock.status),
+                    ",", properties::CREATED_TIME, "=", &add_val(&mut vals, &environment_lock.created_time),
+                    ",", properties::LAST_MODIFIED_TIME, "=", &add_val(&mut vals, &environment_lock.last_modified_time),
+                ].concat()
+            } " where "{properties::ID}"=" {add_val(&mut vals, &environment_lock.id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn update_environment_lock(&self, id: Id, changes: &[EnvironmentLockProperty]) -> Result<(), ErrNo> {
+        let changes: Vec<&EnvironmentLockProperty> = changes
+            .iter()
+            .filter(|change| match change {
+                EnvironmentLockProperty::Id(_) => false,
+                _ => true,
+            })
+            .collect();
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1 + changes.len());
+        let mut trunks: Vec<Cow<str>> = Vec::with_capacity(4 * changes.len());
+        for (index, property) in changes.iter().enumerate() {
+            if 0 < index {
+                trunks.push(",".into());
+            }
+            trunks.extend(match property {
+                EnvironmentLockProperty::Id(id) => {
+                    [properties::ID.into(), "=".into(), add_val(&mut vals, id).into()]
+                },
+                EnvironmentLockProperty::OrgId(org_id) => {
+                    [properties::ORG_ID.into(), "=".into(), add_val(&mut vals, org_id).into()]
+                },
+                EnvironmentLockProperty::EnvironmentId(environment_id) => {
+                    [properties::ENVIRONMENT_ID.into(), "=".into(), add_val(&mut vals, environment_id).into()]
+                },
+                EnvironmentLockProperty::RecordId(record_id) => {
+                    [properties::RECORD_ID.into(), "=".into(), add_val(&mut vals, record_id).into()]
+                },
+                EnvironmentLockProperty::JobId(job_id) => {
+                    [properties::JOB_ID.into(), "=".into(), add_val(&mut vals, job_id).into()]
+                },
+                EnvironmentLockProperty::HolderId(holder_id) => {
+                    [properties::HOLDER_ID.into(), "=".into(), add_val(&mut vals, holder_id).into()]
+                },
+                EnvironmentLockProperty::HolderName(holder_name) => {
+                    [properties::HOLDER_NAME.into(), "=".into(), add_val(&mut vals, holder_name).into()]
+                },
+                EnvironmentLockProperty::Status(status) => {
+                    [properties::STATUS.into(), "=".into(), add_val(&mut vals, status).into()]
+                },
+                EnvironmentLockProperty::CreatedTime(created_time) => {
+                    [properties::CREATED_TIME.into(), "=".into(), add_val(&mut vals, created_time).into()]
+                },
+                EnvironmentLockProperty::LastModifiedTime(last_modified_time) => {
+                    [properties::LAST_MODIFIED_TIME.into(), "=".into(), add_val(&mut vals, last_modified_time).into()]
+                },
+            });
+        }
+        let change_content: String = trunks.concat();
+        let sql = format_xml::template! {
+            "update "{ENTITY}" set " {change_content} " where "{properties::ID}"=" {add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn delete_environment_lock(&self, id: Id) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1);
+        let sql = format_xml::template! {
+            "delete from "{ENTITY}" where "{properties::ID}"="{add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn delete_environment_lock_batch(&self, ids: &[Id]) -> Result<(), ErrNo>
+    {
+        if ids.is_empty() {
+            log::warn!("待批量删除的环境占用id集合为空");
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(ids.len());
+        let sql = format_xml::template! {
+            "delete from "{ENTITY}" where "{properties::ID}" in (" {add_vals(&mut vals, &ids)} ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn query_environment_lock_count(&self, opt: &EnvironmentLockOpt) -> Result<u64, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let sql = format_xml::template! {
+            "select count(1) from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let row: Row = self.transaction.query_one(&statement, &vals).await.map_err(query_error)?;
+        let count:i64 = row.get(0);
+        return Ok(count as u64);
+    }
+
+    pub async fn query_environment_lock(&self, page_no: u64, page_size: u64, opt: &EnvironmentLockOpt) -> Result<Vec<EnvironmentLock>, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let (limit, offset) = calc_sql_pagination(page_no, page_size);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+            " limit "{limit}" offset "{offset}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<EnvironmentLock> = rows.iter().map(extract_environment_lock).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+    pub async fn query_environment_lock_one(&self, opt: &EnvironmentLockOpt) -> Result<Option<EnvironmentLock>, ErrNo> {
+        let list = self.query_environment_lock(1, 1, opt).await?;
+        return Ok(list.into_iter().next());
+    }
+
+    pub async fn query_environment_lock_batch(&self, opt: &EnvironmentLockOpt) -> Result<Vec<EnvironmentLock>, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<EnvironmentLock> = rows.iter().map(extract_environment_lock).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+}
\ No newline at end of file
@@ -21,10 +21,11 @@ use crate::model::job::properties;
 use crate::model::job::Job;
 use crate::model::job::JobProperty;
 use crate::model::job::JobOpt;
+use crate::model::job::enums::try_i16_to_interrupt_policy;
 use crate::native_common;
 
 const ENTITY: &str = "job";
-const EXTRA_PROPERTIES: [&str; 6] = [properties::ORG_ID,properties::ENVIRONMENT_SCHEMA_ID,properties::NAME,properties::REMARK,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
+const EXTRA_PROPERTIES: [&str; 8] = [properties::ORG_ID,properties::ENVIRONMENT_SCHEMA_ID,properties::NAME,properties::REMARK,properties::INTERRUPT_POLICY,properties::PARAMETER_SCHEMA,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
 const PROPERTY_COUNT: usize = EXTRA_PROPERTIES.len()+1;
 
 fn gen_properties() -> String {
@@ -43,6 +44,8 @@ fn extract_job(row: &Row) -> Result<Job, ErrNo> {
         environment_schema_id: row.try_get(properties::ENVIRONMENT_SCHEMA_ID).map_err(extract_data_error)?,
         name: row.try_get(properties::NAME).map_err(extract_data_error)?,
         remark: row.try_get(properties::REMARK).map_err(extract_data_error)?,
+        interrupt_policy: try_i16_to_interrupt_policy(row.try_get(properties::INTERRUPT_POLICY).map_err(extract_data_error)?).map_err(undefined_enum_value)?,
+        parameter_schema: row.try_get(properties::PARAMETER_SCHEMA).map_err(extract_data_error)?,
         created_time: row.try_get(properties::CREATED_TIME).map_err(extract_data_error)?,
         last_modified_time: row.try_get(properties::LAST_MODIFIED_TIME).map_err(extract_data_error)?,
     });
@@ -65,6 +68,12 @@ fn opt_to_conditions<'a>(opt: &'a JobOpt) -> Vec::<(Condition, &'a (dyn ToSql +
     if let Some(remark) = opt.remark.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::REMARK), operator: None}, remark));
     }
+    if let Some(interrupt_policy) = opt.interrupt_policy.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::INTERRUPT_POLICY), operator: None}, interrupt_policy));
+    }
+    if let Some(parameter_schema) = opt.parameter_schema.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::PARAMETER_SCHEMA), operator: None}, parameter_schema));
+    }
     if let Some(created_time) = opt.created_time.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::CREATED_TIME), operator: None}, created_time));
     }
@@ -122,6 +131,8 @@ impl<'a> JobBaseService<'a> {
                     add_val(&mut vals, &job.environment_schema_id),
                     add_val(&mut vals, &job.name),
                     add_val(&mut vals, &job.remark),
+                    add_val(&mut vals, &job.interrupt_policy),
+                    add_val(&mut vals, &job.parameter_schema),
                     add_val(&mut vals, &job.created_time),
                     add_val(&mut vals, &job.last_modified_time),
                 ].join(",")
@@ -147,6 +158,8 @@ impl<'a> JobBaseService<'a> {
                         add_val(&mut vals, &job.environment_schema_id),
                         add_val(&mut vals, &job.name),
                         add_val(&mut vals, &job.remark),
+                        add_val(&mut vals, &job.interrupt_policy),
+                        add_val(&mut vals, &job.parameter_schema),
                         add_val(&mut vals, &job.created_time),
                         add_val(&mut vals, &job.last_modified_time),
                     ];
@@ -168,6 +181,8 @@ impl<'a> JobBaseService<'a> {
                     ",", properties::ENVIRONMENT_SCHEMA_ID, "=", &add_val(&mut vals, &job.environment_schema_id),
                     ",", properties::NAME, "=", &add_val(&mut vals, &job.name),
                     ",", properties::REMARK, "=", &add_val(&mut vals, &job.remark),
+                    ",", properties::INTERRUPT_POLICY, "=", &add_val(&mut vals, &job.interrupt_policy),
+                    ",", properties::PARAMETER_SCHEMA, "=", &add_val(&mut vals, &job.parameter_schema),
                     ",", properties::CREATED_TIME, "=", &add_val(&mut vals, &job.created_time),
                     ",", properties::LAST_MODIFIED_TIME, "=", &add_val(&mut vals, &job.last_modified_time),
                 ].concat()
@@ -211,6 +226,12 @@ impl<'a> JobBaseService<'a> {
                 JobProperty::Remark(remark) => {
                     [properties::REMARK.into(), "=".into(), add_val(&mut vals, remark).into()]
                 },
+                JobProperty::InterruptPolicy(interrupt_policy) => {
+                    [properties::INTERRUPT_POLICY.into(), "=".into(), add_val(&mut vals, interrupt_policy).into()]
+                },
+                JobProperty::ParameterSchema(parameter_schema) => {
+                    [properties::PARAMETER_SCHEMA.into(), "=".into(), add_val(&mut vals, parameter_schema).into()]
+                },
                 JobProperty::CreatedTime(created_time) => {
                     [properties::CREATED_TIME.into(), "=".into(), add_val(&mut vals, created_time).into()]
                 },
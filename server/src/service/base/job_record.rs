@@ -22,10 +22,11 @@ use crate::model::job_record::JobRecord;
 use crate::model::job_record::JobRecordProperty;
 use crate::model::job_record::JobRecordOpt;
 use crate::model::job_record::enums::try_i16_to_status;
+use crate::model::job_record::enums::try_i16_to_trigger_type;
 use crate::native_common;
 
 const ENTITY: &str = "job_record";
-const EXTRA_PROPERTIES: [&str; 6] = [properties::ORG_ID,properties::JOB_ID,properties::ENVIRONMENT_ID,properties::STATUS,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
+const EXTRA_PROPERTIES: [&str; 18] = [properties::ORG_ID,properties::JOB_ID,properties::ENVIRONMENT_ID,properties::ORIGIN_RECORD_ID,properties::ATTEMPT,properties::STATUS,properties::PINNED,properties::CREATED_BY,properties::TRIGGER_TYPE,properties::CHANGE_NOTE,properties::REQUIRED_APPROVALS,properties::PARAMS,properties::SECRET_PARAMS,properties::INSTANCE_ID,properties::HEARTBEAT_TIME,properties::CANCEL_REQUESTED,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
 const PROPERTY_COUNT: usize = EXTRA_PROPERTIES.len()+1;
 
 fn gen_properties() -> String {
@@ -43,7 +44,19 @@ fn extract_job_record(row: &Row) -> Result<JobRecord, ErrNo> {
         org_id: row.try_get(properties::ORG_ID).map_err(extract_data_error)?,
         job_id: row.try_get(properties::JOB_ID).map_err(extract_data_error)?,
         environment_id: row.try_get(properties::ENVIRONMENT_ID).map_err(extract_data_error)?,
+        origin_record_id: row.try_get(properties::ORIGIN_RECORD_ID).map_err(extract_data_error)?,
+        attempt: row.try_get(properties::ATTEMPT).map_err(extract_data_error)?,
         status: try_i16_to_status(row.try_get(properties::STATUS).map_err(extract_data_error)?).map_err(undefined_enum_value)?,
+        pinned: row.try_get(properties::PINNED).map_err(extract_data_error)?,
+        created_by: row.try_get(properties::CREATED_BY).map_err(extract_data_error)?,
+        trigger_type: try_i16_to_trigger_type(row.try_get(properties::TRIGGER_TYPE).map_err(extract_data_error)?).map_err(undefined_enum_value)?,
+        change_note: row.try_get(properties::CHANGE_NOTE).map_err(extract_data_error)?,
+        required_approvals: row.try_get(properties::REQUIRED_APPROVALS).map_err(extract_data_error)?,
+        params: row.try_get(properties::PARAMS).map_err(extract_data_error)?,
+        secret_params: row.try_get(properties::SECRET_PARAMS).map_err(extract_data_error)?,
+        instance_id: row.try_get(properties::INSTANCE_ID).map_err(extract_data_error)?,
+        heartbeat_time: row.try_get(properties::HEARTBEAT_TIME).map_err(extract_data_error)?,
+        cancel_requested: row.try_get(properties::CANCEL_REQUESTED).map_err(extract_data_error)?,
         created_time: row.try_get(properties::CREATED_TIME).map_err(extract_data_error)?,
         last_modified_time: row.try_get(properties::LAST_MODIFIED_TIME).map_err(extract_data_error)?,
     });
@@ -63,9 +76,45 @@ fn opt_to_conditions<'a>(opt: &'a JobRecordOpt) -> Vec::<(Condition, &'a (dyn To
     if let Some(environment_id) = opt.environment_id.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::ENVIRONMENT_ID), operator: None}, environment_id));
     }
+    if let Some(origin_record_id) = opt.origin_record_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ORIGIN_RECORD_ID), operator: None}, origin_record_id));
+    }
+    if let Some(attempt) = opt.attempt.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ATTEMPT), operator: None}, attempt));
+    }
     if let Some(status) = opt.status.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::STATUS), operator: None}, status));
     }
+    if let Some(pinned) = opt.pinned.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::PINNED), operator: None}, pinned));
+    }
+    if let Some(created_by) = opt.created_by.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::CREATED_BY), operator: None}, created_by));
+    }
+    if let Some(trigger_type) = opt.trigger_type.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::TRIGGER_TYPE), operator: None}, trigger_type));
+    }
+    if let Some(change_note) = opt.change_note.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::CHANGE_NOTE), operator: None}, change_note));
+    }
+    if let Some(required_approvals) = opt.required_approvals.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::REQUIRED_APPROVALS), operator: None}, required_approvals));
+    }
+    if let Some(params) = opt.params.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::PARAMS), operator: None}, params));
+    }
+    if let Some(secret_params) = opt.secret_params.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::SECRET_PARAMS), operator: None}, secret_params));
+    }
+    if let Some(instance_id) = opt.instance_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::INSTANCE_ID), operator: None}, instance_id));
+    }
+    if let Some(heartbeat_time) = opt.heartbeat_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::HEARTBEAT_TIME), operator: None}, heartbeat_time));
+    }
+    if let Some(cancel_requested) = opt.cancel_requested.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::CANCEL_REQUESTED), operator: None}, cancel_requested));
+    }
     if let Some(created_time) = opt.created_time.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::CREATED_TIME), operator: None}, created_time));
     }
@@ -122,7 +171,19 @@ impl<'a> JobRecordBaseService<'a> {
                     add_val(&mut vals, &job_record.org_id),
                     add_val(&mut vals, &job_record.job_id),
                     add_val(&mut vals, &job_record.environment_id),
+                    add_val(&mut vals, &job_record.origin_record_id),
+                    add_val(&mut vals, &job_record.attempt),
                     add_val(&mut vals, &job_record.status),
+                    add_val(&mut vals, &job_record.pinned),
+                    add_val(&mut vals, &job_record.created_by),
+                    add_val(&mut vals, &job_record.trigger_type),
+                    add_val(&mut vals, &job_record.change_note),
+                    add_val(&mut vals, &job_record.required_approvals),
+                    add_val(&mut vals, &job_record.params),
+                    add_val(&mut vals, &job_record.secret_params),
+                    add_val(&mut vals, &job_record.instance_id),
+                    add_val(&mut vals, &job_record.heartbeat_time),
+                    add_val(&mut vals, &job_record.cancel_requested),
                     add_val(&mut vals, &job_record.created_time),
                     add_val(&mut vals, &job_record.last_modified_time),
                 ].join(",")
@@ -147,7 +208,19 @@ impl<'a> JobRecordBaseService<'a> {
                         add_val(&mut vals, &job_record.org_id),
                         add_val(&mut vals, &job_record.job_id),
                         add_val(&mut vals, &job_record.environment_id),
+                        add_val(&mut vals, &job_record.origin_record_id),
+                        add_val(&mut vals, &job_record.attempt),
                         add_val(&mut vals, &job_record.status),
+                        add_val(&mut vals, &job_record.pinned),
+                        add_val(&mut vals, &job_record.created_by),
+                        add_val(&mut vals, &job_record.trigger_type),
+                        add_val(&mut vals, &job_record.change_note),
+                        add_val(&mut vals, &job_record.required_approvals),
+                        add_val(&mut vals, &job_record.params),
+                        add_val(&mut vals, &job_record.secret_params),
+                        add_val(&mut vals, &job_record.instance_id),
+                        add_val(&mut vals, &job_record.heartbeat_time),
+                        add_val(&mut vals, &job_record.cancel_requested),
                         add_val(&mut vals, &job_record.created_time),
                         add_val(&mut vals, &job_record.last_modified_time),
                     ];
@@ -168,7 +241,19 @@ impl<'a> JobRecordBaseService<'a> {
                     properties::ORG_ID, "=", &add_val(&mut vals, &job_record.org_id),
                     ",", properties::JOB_ID, "=", &add_val(&mut vals, &job_record.job_id),
                     ",", properties::ENVIRONMENT_ID, "=", &add_val(&mut vals, &job_record.environment_id),
+                    ",", properties::ORIGIN_RECORD_ID, "=", &add_val(&mut vals, &job_record.origin_record_id),
+                    ",", properties::ATTEMPT, "=", &add_val(&mut vals, &job_record.attempt),
                     ",", properties::STATUS, "=", &add_val(&mut vals, &job_record.status),
+                    ",", properties::PINNED, "=", &add_val(&mut vals, &job_record.pinned),
+                    ",", properties::CREATED_BY, "=", &add_val(&mut vals, &job_record.created_by),
+                    ",", properties::TRIGGER_TYPE, "=", &add_val(&mut vals, &job_record.trigger_type),
+                    ",", properties::CHANGE_NOTE, "=", &add_val(&mut vals, &job_record.change_note),
+                    ",", properties::REQUIRED_APPROVALS, "=", &add_val(&mut vals, &job_record.required_approvals),
+                    ",", properties::PARAMS, "=", &add_val(&mut vals, &job_record.params),
+                    ",", properties::SECRET_PARAMS, "=", &add_val(&mut vals, &job_record.secret_params),
+                    ",", properties::INSTANCE_ID, "=", &add_val(&mut vals, &job_record.instance_id),
+                    ",", properties::HEARTBEAT_TIME, "=", &add_val(&mut vals, &job_record.heartbeat_time),
+                    ",", properties::CANCEL_REQUESTED, "=", &add_val(&mut vals, &job_record.cancel_requested),
                     ",", properties::CREATED_TIME, "=", &add_val(&mut vals, &job_record.created_time),
                     ",", properties::LAST_MODIFIED_TIME, "=", &add_val(&mut vals, &job_record.last_modified_time),
                 ].concat()
@@ -209,9 +294,45 @@ impl<'a> JobRecordBaseService<'a> {
                 JobRecordProperty::EnvironmentId(environment_id) => {
                     [properties::ENVIRONMENT_ID.into(), "=".into(), add_val(&mut vals, environment_id).into()]
                 },
+                JobRecordProperty::OriginRecordId(origin_record_id) => {
+                    [properties::ORIGIN_RECORD_ID.into(), "=".into(), add_val(&mut vals, origin_record_id).into()]
+                },
+                JobRecordProperty::Attempt(attempt) => {
+                    [properties::ATTEMPT.into(), "=".into(), add_val(&mut vals, attempt).into()]
+                },
                 JobRecordProperty::Status(status) => {
                     [properties::STATUS.into(), "=".into(), add_val(&mut vals, status).into()]
                 },
+                JobRecordProperty::Pinned(pinned) => {
+                    [properties::PINNED.into(), "=".into(), add_val(&mut vals, pinned).into()]
+                },
+                JobRecordProperty::CreatedBy(created_by) => {
+                    [properties::CREATED_BY.into(), "=".into(), add_val(&mut vals, created_by).into()]
+                },
+                JobRecordProperty::TriggerType(trigger_type) => {
+                    [properties::TRIGGER_TYPE.into(), "=".into(), add_val(&mut vals, trigger_type).into()]
+                },
+                JobRecordProperty::ChangeNote(change_note) => {
+                    [properties::CHANGE_NOTE.into(), "=".into(), add_val(&mut vals, change_note).into()]
+                },
+                JobRecordProperty::RequiredApprovals(required_approvals) => {
+                    [properties::REQUIRED_APPROVALS.into(), "=".into(), add_val(&mut vals, required_approvals).into()]
+                },
+                JobRecordProperty::Params(params) => {
+                    [properties::PARAMS.into(), "=".into(), add_val(&mut vals, params).into()]
+                },
+                JobRecordProperty::SecretParams(secret_params) => {
+                    [properties::SECRET_PARAMS.into(), "=".into(), add_val(&mut vals, secret_params).into()]
+                },
+                JobRecordProperty::InstanceId(instance_id) => {
+                    [properties::INSTANCE_ID.into(), "=".into(), add_val(&mut vals, instance_id).into()]
+                },
+                JobRecordProperty::HeartbeatTime(heartbeat_time) => {
+                    [properties::HEARTBEAT_TIME.into(), "=".into(), add_val(&mut vals, heartbeat_time).into()]
+                },
+                JobRecordProperty::CancelRequested(cancel_requested) => {
+                    [properties::CANCEL_REQUESTED.into(), "=".into(), add_val(&mut vals, cancel_requested).into()]
+                },
                 JobRecordProperty::CreatedTime(created_time) => {
                     [properties::CREATED_TIME.into(), "=".into(), add_val(&mut vals, created_time).into()]
                 },
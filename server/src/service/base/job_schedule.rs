@@ -0,0 +1,376 @@
+use tihu_native::errno::execute_error;
+use tihu_native::errno::extract_data_error;
+use tihu_native::errno::prepare_statement_error;
+use tihu_native::errno::query_error;
+use tihu_native::errno::undefined_enum_value;
+use tihu_native::ErrNo;
+use tihu::Id;
+use tihu::LightString;
+use lazy_static;
+use format_xml;
+use std::borrow::Cow;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{Row, Transaction};
+use native_common::utils::add_val;
+use native_common::utils::add_vals;
+use native_common::utils::Condition;
+use native_common::utils::add_conditions;
+use native_common::utils::calc_sql_pagination;
+use crate::log;
+use crate::model::job_schedule::properties;
+use crate::model::job_schedule::JobSchedule;
+use crate::model::job_schedule::JobScheduleProperty;
+use crate::model::job_schedule::JobScheduleOpt;
+use crate::model::job_schedule::enums::try_i16_to_status;
+use crate::native_common;
+
+const ENTITY: &str = "job_schedule";
+const EXTRA_PROPERTIES: [&str; 13] = [properties::ORG_ID,properties::JOB_ID,properties::ENVIRONMENT_ID,properties::NAME,properties::CRON_EXPRESSION,properties::PARAMS,properties::STATUS,properties::NEXT_FIRE_TIME,properties::LAST_FIRE_TIME,properties::LAST_RECORD_ID,properties::CREATED_BY,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
+const PROPERTY_COUNT: usize = EXTRA_PROPERTIES.len()+1;
+
+fn gen_properties() -> String {
+    let properties:Vec<&str> = [properties::ID].iter().chain(EXTRA_PROPERTIES.iter()).map(|item|*item).collect();
+    return properties.join(",");
+}
+
+lazy_static::lazy_static! {
+    static ref PROPERTIES: String = gen_properties();
+}
+
+fn extract_job_schedule(row: &Row) -> Result<JobSchedule, ErrNo> {
+    return Ok(JobSchedule {
+        id: row.try_get(properties::ID).map_err(extract_data_error)?,
+        org_id: row.try_get(properties::ORG_ID).map_err(extract_data_error)?,
+        job_id: row.try_get(properties::JOB_ID).map_err(extract_data_error)?,
+        environment_id: row.try_get(properties::ENVIRONMENT_ID).map_err(extract_data_error)?,
+        name: row.try_get(properties::NAME).map_err(extract_data_error)?,
+        cron_expression: row.try_get(properties::CRON_EXPRESSION).map_err(extract_data_error)?,
+        params: row.try_get(properties::PARAMS).map_err(extract_data_error)?,
+        status: try_i16_to_status(row.try_get(properties::STATUS).map_err(extract_data_error)?).map_err(undefined_enum_value)?,
+        next_fire_time: row.try_get(properties::NEXT_FIRE_TIME).map_err(extract_data_error)?,
+        last_fire_time: row.try_get(properties::LAST_FIRE_TIME).map_err(extract_data_error)?,
+        last_record_id: row.try_get(properties::LAST_RECORD_ID).map_err(extract_data_error)?,
+        created_by: row.try_get(properties::CREATED_BY).map_err(extract_data_error)?,
+        created_time: row.try_get(properties::CREATED_TIME).map_err(extract_data_error)?,
+        last_modified_time: row.try_get(properties::LAST_MODIFIED_TIME).map_err(extract_data_error)?,
+    });
+}
+
+fn opt_to_conditions<'a>(opt: &'a JobScheduleOpt) -> Vec::<(Condition, &'a (dyn ToSql + std::marker::Sync))> {
+    let mut pairs = Vec::<(Condition,&(dyn ToSql + std::marker::Sync))>::new();
+    if let Some(id) = opt.id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ID), operator: None}, id));
+    }
+    if let Some(org_id) = opt.org_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ORG_ID), operator: None}, org_id));
+    }
+    if let Some(job_id) = opt.job_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::JOB_ID), operator: None}, job_id));
+    }
+    if let Some(environment_id) = opt.environment_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ENVIRONMENT_ID), operator: None}, environment_id));
+    }
+    if let Some(name) = opt.name.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::NAME), operator: None}, name));
+    }
+    if let Some(cron_expression) = opt.cron_expression.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::CRON_EXPRESSION), operator: None}, cron_expression));
+    }
+    if let Some(params) = opt.params.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::PARAMS), operator: None}, params));
+    }
+    if let Some(status) = opt.status.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::STATUS), operator: None}, status));
+    }
+    if let Some(next_fire_time) = opt.next_fire_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::NEXT_FIRE_TIME), operator: None}, next_fire_time));
+    }
+    if let Some(last_fire_time) = opt.last_fire_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::LAST_FIRE_TIME), operator: None}, last_fire_time));
+    }
+    if let Some(last_record_id) = opt.last_record_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::LAST_RECORD_ID), operator: None}, last_record_id));
+    }
+    if let Some(created_by) = opt.created_by.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::CREATED_BY), operator: None}, created_by));
+    }
+    if let Some(created_time) = opt.created_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::CREATED_TIME), operator: None}, created_time));
+    }
+    if let Some(last_modified_time) = opt.last_modified_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::LAST_MODIFIED_TIME), operator: None}, last_modified_time));
+    }
+    return pairs;
+}
+
+pub struct JobScheduleBaseService<'a> {
+    transaction: &'a Transaction<'a>
+}
+
+impl<'a> JobScheduleBaseService<'a> {
+
+    pub fn new(transaction: &'a Transaction) -> JobScheduleBaseService<'a> {
+        return JobScheduleBaseService {
+            transaction: transaction
+        };
+    }
+
+    pub async fn read_job_schedule(&self, id: Id) -> Result<Option<JobSchedule>, ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}" where "{properties::ID}"="{add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let row_opt: Option<Row> = self.transaction.query_opt(&statement, &vals).await.map_err(query_error)?;
+        return Ok(row_opt.as_ref().map(extract_job_schedule).transpose()?);
+    }
+
+    pub async fn read_job_schedule_batch(&self, ids: &[Id]) -> Result<Vec<JobSchedule>, ErrNo>
+    {
+        if ids.is_empty() {
+            log::warn!("待批量读取的任务计划id集合为空");
+            return Ok(Vec::new());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(ids.len());
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}" where "{properties::ID}" in (" {add_vals(&mut vals, &ids)} ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<JobSchedule> = rows.iter().map(extract_job_schedule).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+    pub async fn insert_job_schedule(&self, job_schedule: &JobSchedule) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT);
+        let sql = format_xml::template! {
+            "insert into "{ENTITY}"(" {PROPERTIES.as_str()} ") values(" {
+                vec![
+                    add_val(&mut vals, &job_schedule.id),
+                    add_val(&mut vals, &job_schedule.org_id),
+                    add_val(&mut vals, &job_schedule.job_id),
+                    add_val(&mut vals, &job_schedule.environment_id),
+                    add_val(&mut vals, &job_schedule.name),
+                    add_val(&mut vals, &job_schedule.cron_expression),
+                    add_val(&mut vals, &job_schedule.params),
+                    add_val(&mut vals, &job_schedule.status),
+                    add_val(&mut vals, &job_schedule.next_fire_time),
+                    add_val(&mut vals, &job_schedule.last_fire_time),
+                    add_val(&mut vals, &job_schedule.last_record_id),
+                    add_val(&mut vals, &job_schedule.created_by),
+                    add_val(&mut vals, &job_schedule.created_time),
+                    add_val(&mut vals, &job_schedule.last_modified_time),
+                ].join(",")
+            } ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn insert_job_schedule_batch(&self, job_schedule_list: &[JobSchedule]) -> Result<(), ErrNo> {
+        if job_schedule_list.is_empty() {
+            log::warn!("待批量新增的任务计划集合为空");
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT * job_schedule_list.len());
+        let sql = format_xml::template! {
+            "insert into "{ENTITY}"(" {PROPERTIES.as_str()} ") values" {
+                job_schedule_list.iter().map(|job_schedule|{
+                    let trunks:Vec<String> = vec![
+                        add_val(&mut vals, &job_schedule.id),
+                        add_val(&mut vals, &job_schedule.org_id),
+                        add_val(&mut vals, &job_schedule.job_id),
+                        add_val(&mut vals, &job_schedule.environment_id),
+                        add_val(&mut vals, &job_schedule.name),
+                        add_val(&mut vals, &job_schedule.cron_expression),
+                        add_val(&mut vals, &job_schedule.params),
+                        add_val(&mut vals, &job_schedule.status),
+                        add_val(&mut vals, &job_schedule.next_fire_time),
+                        add_val(&mut vals, &job_schedule.last_fire_time),
+                        add_val(&mut vals, &job_schedule.last_record_id),
+                        add_val(&mut vals, &job_schedule.created_by),
+                        add_val(&mut vals, &job_schedule.created_time),
+                        add_val(&mut vals, &job_schedule.last_modified_time),
+                    ];
+                    ["(", &trunks.join(","), ")"].concat()
+                }).collect::<Vec<String>>().join(",")
+            }
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn update_job_schedule_full(&self, job_schedule: &JobSchedule) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT);
+        let sql = format_xml::template! {
+            "update "{ENTITY}" set " {
+                [
+                    properties::ORG_ID, "=", &add_val(&mut vals, &job_schedule.org_id),
+                    ",", properties::JOB_ID, "=", &add_val(&mut vals, &job_schedule.job_id),
+                    ",", properties::ENVIRONMENT_ID, "=", &add_val(&mut vals, &job_schedule.environment_id),
+                    ",", properties::NAME, "=", &add_val(&mut vals, &job_schedule.name),
+                    ",", properties::CRON_EXPRESSION, "=", &add_val(&mut vals, &job_schedule.cron_expression),
+                    ",", properties::PARAMS, "=", &add_val(&mut vals, &job_schedule.params),
+                    ",", properties::STATUS, "=", &add_val(&mut vals, &job_schedule.status),
+                    ",", properties::NEXT_FIRE_TIME, "=", &add_val(&mut vals, &job_schedule.next_fire_time),
+                    ",", properties::LAST_FIRE_TIME, "=", &add_val(&mut vals, &job_schedule.last_fire_time),
+                    ",", properties::LAST_RECORD_ID, "=", &add_val(&mut vals, &job_schedule.last_record_id),
+                    ",", properties::CREATED_BY, "=", &add_val(&mut vals, &job_schedule.created_by),
+                    ",", properties::CREATED_TIME, "=", &add_val(&mut vals, &job_schedule.created_time),
+                    ",", properties::LAST_MODIFIED_TIME, "=", &add_val(&mut vals, &job_schedule.last_modified_time),
+                ].concat()
+            } " where "{properties::ID}"=" {add_val(&mut vals, &job_schedule.id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn update_job_schedule(&self, id: Id, changes: &[JobScheduleProperty]) -> Result<(), ErrNo> {
+        let changes: Vec<&JobScheduleProperty> = changes
+            .iter()
+            .filter(|change| match change {
+                JobScheduleProperty::Id(_) => false,
+                _ => true,
+            })
+            .collect();
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1 + changes.len());
+        let mut trunks: Vec<Cow<str>> = Vec::with_capacity(4 * changes.len());
+        for (index, property) in changes.iter().enumerate() {
+            if 0 < index {
+                trunks.push(",".into());
+            }
+            trunks.extend(match property {
+                JobScheduleProperty::Id(id) => {
+                    [properties::ID.into(), "=".into(), add_val(&mut vals, id).into()]
+                },
+                JobScheduleProperty::OrgId(org_id) => {
+                    [properties::ORG_ID.into(), "=".into(), add_val(&mut vals, org_id).into()]
+                },
+                JobScheduleProperty::JobId(job_id) => {
+                    [properties::JOB_ID.into(), "=".into(), add_val(&mut vals, job_id).into()]
+                },
+                JobScheduleProperty::EnvironmentId(environment_id) => {
+                    [properties::ENVIRONMENT_ID.into(), "=".into(), add_val(&mut vals, environment_id).into()]
+                },
+                JobScheduleProperty::Name(name) => {
+                    [properties::NAME.into(), "=".into(), add_val(&mut vals, name).into()]
+                },
+                JobScheduleProperty::CronExpression(cron_expression) => {
+                    [properties::CRON_EXPRESSION.into(), "=".into(), add_val(&mut vals, cron_expression).into()]
+                },
+                JobScheduleProperty::Params(params) => {
+                    [properties::PARAMS.into(), "=".into(), add_val(&mut vals, params).into()]
+                },
+                JobScheduleProperty::Status(status) => {
+                    [properties::STATUS.into(), "=".into(), add_val(&mut vals, status).into()]
+                },
+                JobScheduleProperty::NextFireTime(next_fire_time) => {
+                    [properties::NEXT_FIRE_TIME.into(), "=".into(), add_val(&mut vals, next_fire_time).into()]
+                },
+                JobScheduleProperty::LastFireTime(last_fire_time) => {
+                    [properties::LAST_FIRE_TIME.into(), "=".into(), add_val(&mut vals, last_fire_time).into()]
+                },
+                JobScheduleProperty::LastRecordId(last_record_id) => {
+                    [properties::LAST_RECORD_ID.into(), "=".into(), add_val(&mut vals, last_record_id).into()]
+                },
+                JobScheduleProperty::CreatedBy(created_by) => {
+                    [properties::CREATED_BY.into(), "=".into(), add_val(&mut vals, created_by).into()]
+                },
+                JobScheduleProperty::CreatedTime(created_time) => {
+                    [properties::CREATED_TIME.into(), "=".into(), add_val(&mut vals, created_time).into()]
+                },
+                JobScheduleProperty::LastModifiedTime(last_modified_time) => {
+                    [properties::LAST_MODIFIED_TIME.into(), "=".into(), add_val(&mut vals, last_modified_time).into()]
+                },
+            });
+        }
+        let change_content: String = trunks.concat();
+        let sql = format_xml::template! {
+            "update "{ENTITY}" set " {change_content} " where "{properties::ID}"=" {add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn delete_job_schedule(&self, id: Id) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1);
+        let sql = format_xml::template! {
+            "delete from "{ENTITY}" where "{properties::ID}"="{add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn delete_job_schedule_batch(&self, ids: &[Id]) -> Result<(), ErrNo>
+    {
+        if ids.is_empty() {
+            log::warn!("待批量删除的任务计划id集合为空");
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(ids.len());
+        let sql = format_xml::template! {
+            "delete from "{ENTITY}" where "{properties::ID}" in (" {add_vals(&mut vals, &ids)} ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn query_job_schedule_count(&self, opt: &JobScheduleOpt) -> Result<u64, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let sql = format_xml::template! {
+            "select count(1) from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let row: Row = self.transaction.query_one(&statement, &vals).await.map_err(query_error)?;
+        let count:i64 = row.get(0);
+        return Ok(count as u64);
+    }
+
+    pub async fn query_job_schedule(&self, page_no: u64, page_size: u64, opt: &JobScheduleOpt) -> Result<Vec<JobSchedule>, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let (limit, offset) = calc_sql_pagination(page_no, page_size);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+            " limit "{limit}" offset "{offset}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<JobSchedule> = rows.iter().map(extract_job_schedule).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+    pub async fn query_job_schedule_one(&self, opt: &JobScheduleOpt) -> Result<Option<JobSchedule>, ErrNo> {
+        let list = self.query_job_schedule(1, 1, opt).await?;
+        return Ok(list.into_iter().next());
+    }
+
+    pub async fn query_job_schedule_batch(&self, opt: &JobScheduleOpt) -> Result<Vec<JobSchedule>, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<JobSchedule> = rows.iter().map(extract_job_schedule).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+}
\ No newline at end of file
@@ -22,10 +22,13 @@ use crate::model::job_step::JobStep;
 use crate::model::job_step::JobStepProperty;
 use crate::model::job_step::JobStepOpt;
 use crate::model::job_step::enums::try_i16_to_step_type;
+use crate::model::job_step::enums::try_i16_to_failure_policy;
+use crate::model::job_step::enums::try_i16_to_run_if;
+use crate::model::job_step::enums::try_i16_to_signoff_role;
 use crate::native_common;
 
 const ENTITY: &str = "job_step";
-const EXTRA_PROPERTIES: [&str; 13] = [properties::ORG_ID,properties::JOB_ID,properties::NAME,properties::STEP_TYPE,properties::SCHEMA_RESOURCE_ID,properties::OPERATION_ID,properties::OPERATION_NAME,properties::OPERATION_PARAMETER,properties::ATTACHMENTS,properties::REMARK,properties::SEQ,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
+const EXTRA_PROPERTIES: [&str; 26] = [properties::ORG_ID,properties::JOB_ID,properties::NAME,properties::STEP_TYPE,properties::SCHEMA_RESOURCE_ID,properties::OPERATION_ID,properties::OPERATION_NAME,properties::OPERATION_PARAMETER,properties::ATTACHMENTS,properties::SIGNOFF_ROLE,properties::SIGNOFF_USERS,properties::REQUIRED_SIGNOFFS,properties::REMARK,properties::TIMEOUT_SECONDS,properties::MAX_RETRIES,properties::RETRY_BACKOFF,properties::MAX_PARALLEL,properties::BATCH_SIZE,properties::PAUSE_BETWEEN_BATCHES,properties::FAILURE_POLICY,properties::RUN_IF,properties::RUN_EXPRESSION,properties::SEQ,properties::DEPENDS_ON,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
 const PROPERTY_COUNT: usize = EXTRA_PROPERTIES.len()+1;
 
 fn gen_properties() -> String {
@@ -49,8 +52,21 @@ fn extract_job_step(row: &Row) -> Result<JobStep, ErrNo> {
         operation_name: row.try_get(properties::OPERATION_NAME).map_err(extract_data_error)?,
         operation_parameter: row.try_get(properties::OPERATION_PARAMETER).map_err(extract_data_error)?,
         attachments: row.try_get(properties::ATTACHMENTS).map_err(extract_data_error)?,
+        signoff_role: row.try_get::<_, Option<i16>>(properties::SIGNOFF_ROLE).map_err(extract_data_error)?.map(try_i16_to_signoff_role).transpose().map_err(undefined_enum_value)?,
+        signoff_users: row.try_get(properties::SIGNOFF_USERS).map_err(extract_data_error)?,
+        required_signoffs: row.try_get(properties::REQUIRED_SIGNOFFS).map_err(extract_data_error)?,
         remark: row.try_get(properties::REMARK).map_err(extract_data_error)?,
+        timeout_seconds: row.try_get(properties::TIMEOUT_SECONDS).map_err(extract_data_error)?,
+        max_retries: row.try_get(properties::MAX_RETRIES).map_err(extract_data_error)?,
+        retry_backoff: row.try_get(properties::RETRY_BACKOFF).map_err(extract_data_error)?,
+        max_parallel: row.try_get(properties::MAX_PARALLEL).map_err(extract_data_error)?,
+        batch_size: row.try_get(properties::BATCH_SIZE).map_err(extract_data_error)?,
+        pause_between_batches: row.try_get(properties::PAUSE_BETWEEN_BATCHES).map_err(extract_data_error)?,
+        failure_policy: try_i16_to_failure_policy(row.try_get(properties::FAILURE_POLICY).map_err(extract_data_error)?).map_err(undefined_enum_value)?,
+        run_if: try_i16_to_run_if(row.try_get(properties::RUN_IF).map_err(extract_data_error)?).map_err(undefined_enum_value)?,
+        run_expression: row.try_get(properties::RUN_EXPRESSION).map_err(extract_data_error)?,
         seq: row.try_get(properties::SEQ).map_err(extract_data_error)?,
+        depends_on: row.try_get(properties::DEPENDS_ON).map_err(extract_data_error)?,
         created_time: row.try_get(properties::CREATED_TIME).map_err(extract_data_error)?,
         last_modified_time: row.try_get(properties::LAST_MODIFIED_TIME).map_err(extract_data_error)?,
     });
@@ -88,12 +104,51 @@ fn opt_to_conditions<'a>(opt: &'a JobStepOpt) -> Vec::<(Condition, &'a (dyn ToSq
     if let Some(attachments) = opt.attachments.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::ATTACHMENTS), operator: None}, attachments));
     }
+    if let Some(signoff_role) = opt.signoff_role.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::SIGNOFF_ROLE), operator: None}, signoff_role));
+    }
+    if let Some(signoff_users) = opt.signoff_users.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::SIGNOFF_USERS), operator: None}, signoff_users));
+    }
+    if let Some(required_signoffs) = opt.required_signoffs.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::REQUIRED_SIGNOFFS), operator: None}, required_signoffs));
+    }
     if let Some(remark) = opt.remark.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::REMARK), operator: None}, remark));
     }
+    if let Some(timeout_seconds) = opt.timeout_seconds.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::TIMEOUT_SECONDS), operator: None}, timeout_seconds));
+    }
+    if let Some(max_retries) = opt.max_retries.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::MAX_RETRIES), operator: None}, max_retries));
+    }
+    if let Some(retry_backoff) = opt.retry_backoff.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::RETRY_BACKOFF), operator: None}, retry_backoff));
+    }
+    if let Some(max_parallel) = opt.max_parallel.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::MAX_PARALLEL), operator: None}, max_parallel));
+    }
+    if let Some(batch_size) = opt.batch_size.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::BATCH_SIZE), operator: None}, batch_size));
+    }
+    if let Some(pause_between_batches) = opt.pause_between_batches.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::PAUSE_BETWEEN_BATCHES), operator: None}, pause_between_batches));
+    }
+    if let Some(failure_policy) = opt.failure_policy.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::FAILURE_POLICY), operator: None}, failure_policy));
+    }
+    if let Some(run_if) = opt.run_if.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::RUN_IF), operator: None}, run_if));
+    }
+    if let Some(run_expression) = opt.run_expression.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::RUN_EXPRESSION), operator: None}, run_expression));
+    }
     if let Some(seq) = opt.seq.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::SEQ), operator: None}, seq));
     }
+    if let Some(depends_on) = opt.depends_on.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::DEPENDS_ON), operator: None}, depends_on));
+    }
     if let Some(created_time) = opt.created_time.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::CREATED_TIME), operator: None}, created_time));
     }
@@ -156,8 +211,21 @@ impl<'a> JobStepBaseService<'a> {
                     add_val(&mut vals, &job_step.operation_name),
                     add_val(&mut vals, &job_step.operation_parameter),
                     add_val(&mut vals, &job_step.attachments),
+                    add_val(&mut vals, &job_step.signoff_role),
+                    add_val(&mut vals, &job_step.signoff_users),
+                    add_val(&mut vals, &job_step.required_signoffs),
                     add_val(&mut vals, &job_step.remark),
+                    add_val(&mut vals, &job_step.timeout_seconds),
+                    add_val(&mut vals, &job_step.max_retries),
+                    add_val(&mut vals, &job_step.retry_backoff),
+                    add_val(&mut vals, &job_step.max_parallel),
+                    add_val(&mut vals, &job_step.batch_size),
+                    add_val(&mut vals, &job_step.pause_between_batches),
+                    add_val(&mut vals, &job_step.failure_policy),
+                    add_val(&mut vals, &job_step.run_if),
+                    add_val(&mut vals, &job_step.run_expression),
                     add_val(&mut vals, &job_step.seq),
+                    add_val(&mut vals, &job_step.depends_on),
                     add_val(&mut vals, &job_step.created_time),
                     add_val(&mut vals, &job_step.last_modified_time),
                 ].join(",")
@@ -188,8 +256,21 @@ impl<'a> JobStepBaseService<'a> {
                         add_val(&mut vals, &job_step.operation_name),
                         add_val(&mut vals, &job_step.operation_parameter),
                         add_val(&mut vals, &job_step.attachments),
+                        add_val(&mut vals, &job_step.signoff_role),
+                        add_val(&mut vals, &job_step.signoff_users),
+                        add_val(&mut vals, &job_step.required_signoffs),
                         add_val(&mut vals, &job_step.remark),
+                        add_val(&mut vals, &job_step.timeout_seconds),
+                        add_val(&mut vals, &job_step.max_retries),
+                        add_val(&mut vals, &job_step.retry_backoff),
+                        add_val(&mut vals, &job_step.max_parallel),
+                        add_val(&mut vals, &job_step.batch_size),
+                        add_val(&mut vals, &job_step.pause_between_batches),
+                        add_val(&mut vals, &job_step.failure_policy),
+                        add_val(&mut vals, &job_step.run_if),
+                        add_val(&mut vals, &job_step.run_expression),
                         add_val(&mut vals, &job_step.seq),
+                        add_val(&mut vals, &job_step.depends_on),
                         add_val(&mut vals, &job_step.created_time),
                         add_val(&mut vals, &job_step.last_modified_time),
                     ];
@@ -216,8 +297,21 @@ impl<'a> JobStepBaseService<'a> {
                     ",", properties::OPERATION_NAME, "=", &add_val(&mut vals, &job_step.operation_name),
                     ",", properties::OPERATION_PARAMETER, "=", &add_val(&mut vals, &job_step.operation_parameter),
                     ",", properties::ATTACHMENTS, "=", &add_val(&mut vals, &job_step.attachments),
+                    ",", properties::SIGNOFF_ROLE, "=", &add_val(&mut vals, &job_step.signoff_role),
+                    ",", properties::SIGNOFF_USERS, "=", &add_val(&mut vals, &job_step.signoff_users),
+                    ",", properties::REQUIRED_SIGNOFFS, "=", &add_val(&mut vals, &job_step.required_signoffs),
                     ",", properties::REMARK, "=", &add_val(&mut vals, &job_step.remark),
+                    ",", properties::TIMEOUT_SECONDS, "=", &add_val(&mut vals, &job_step.timeout_seconds),
+                    ",", properties::MAX_RETRIES, "=", &add_val(&mut vals, &job_step.max_retries),
+                    ",", properties::RETRY_BACKOFF, "=", &add_val(&mut vals, &job_step.retry_backoff),
+                    ",", properties::MAX_PARALLEL, "=", &add_val(&mut vals, &job_step.max_parallel),
+                    ",", properties::BATCH_SIZE, "=", &add_val(&mut vals, &job_step.batch_size),
+                    ",", properties::PAUSE_BETWEEN_BATCHES, "=", &add_val(&mut vals, &job_step.pause_between_batches),
+                    ",", properties::FAILURE_POLICY, "=", &add_val(&mut vals, &job_step.failure_policy),
+                    ",", properties::RUN_IF, "=", &add_val(&mut vals, &job_step.run_if),
+                    ",", properties::RUN_EXPRESSION, "=", &add_val(&mut vals, &job_step.run_expression),
                     ",", properties::SEQ, "=", &add_val(&mut vals, &job_step.seq),
+                    ",", properties::DEPENDS_ON, "=", &add_val(&mut vals, &job_step.depends_on),
                     ",", properties::CREATED_TIME, "=", &add_val(&mut vals, &job_step.created_time),
                     ",", properties::LAST_MODIFIED_TIME, "=", &add_val(&mut vals, &job_step.last_modified_time),
                 ].concat()
@@ -276,12 +370,51 @@ impl<'a> JobStepBaseService<'a> {
                 JobStepProperty::Attachments(attachments) => {
                     [properties::ATTACHMENTS.into(), "=".into(), add_val(&mut vals, attachments).into()]
                 },
+                JobStepProperty::SignoffRole(signoff_role) => {
+                    [properties::SIGNOFF_ROLE.into(), "=".into(), add_val(&mut vals, signoff_role).into()]
+                },
+                JobStepProperty::SignoffUsers(signoff_users) => {
+                    [properties::SIGNOFF_USERS.into(), "=".into(), add_val(&mut vals, signoff_users).into()]
+                },
+                JobStepProperty::RequiredSignoffs(required_signoffs) => {
+                    [properties::REQUIRED_SIGNOFFS.into(), "=".into(), add_val(&mut vals, required_signoffs).into()]
+                },
                 JobStepProperty::Remark(remark) => {
                     [properties::REMARK.into(), "=".into(), add_val(&mut vals, remark).into()]
                 },
+                JobStepProperty::TimeoutSeconds(timeout_seconds) => {
+                    [properties::TIMEOUT_SECONDS.into(), "=".into(), add_val(&mut vals, timeout_seconds).into()]
+                },
+                JobStepProperty::MaxRetries(max_retries) => {
+                    [properties::MAX_RETRIES.into(), "=".into(), add_val(&mut vals, max_retries).into()]
+                },
+                JobStepProperty::RetryBackoff(retry_backoff) => {
+                    [properties::RETRY_BACKOFF.into(), "=".into(), add_val(&mut vals, retry_backoff).into()]
+                },
+                JobStepProperty::MaxParallel(max_parallel) => {
+                    [properties::MAX_PARALLEL.into(), "=".into(), add_val(&mut vals, max_parallel).into()]
+                },
+                JobStepProperty::BatchSize(batch_size) => {
+                    [properties::BATCH_SIZE.into(), "=".into(), add_val(&mut vals, batch_size).into()]
+                },
+                JobStepProperty::PauseBetweenBatches(pause_between_batches) => {
+                    [properties::PAUSE_BETWEEN_BATCHES.into(), "=".into(), add_val(&mut vals, pause_between_batches).into()]
+                },
+                JobStepProperty::FailurePolicy(failure_policy) => {
+                    [properties::FAILURE_POLICY.into(), "=".into(), add_val(&mut vals, failure_policy).into()]
+                },
+                JobStepProperty::RunIf(run_if) => {
+                    [properties::RUN_IF.into(), "=".into(), add_val(&mut vals, run_if).into()]
+                },
+                JobStepProperty::RunExpression(run_expression) => {
+                    [properties::RUN_EXPRESSION.into(), "=".into(), add_val(&mut vals, run_expression).into()]
+                },
                 JobStepProperty::Seq(seq) => {
                     [properties::SEQ.into(), "=".into(), add_val(&mut vals, seq).into()]
                 },
+                JobStepProperty::DependsOn(depends_on) => {
+                    [properties::DEPENDS_ON.into(), "=".into(), add_val(&mut vals, depends_on).into()]
+                },
                 JobStepProperty::CreatedTime(created_time) => {
                     [properties::CREATED_TIME.into(), "=".into(), add_val(&mut vals, created_time).into()]
                 },
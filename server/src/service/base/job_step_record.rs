@@ -23,10 +23,13 @@ use crate::model::job_step_record::JobStepRecordProperty;
 use crate::model::job_step_record::JobStepRecordOpt;
 use crate::model::job_step_record::enums::try_i16_to_step_type;
 use crate::model::job_step_record::enums::try_i16_to_status;
+use crate::model::job_step_record::enums::try_i16_to_failure_policy;
+use crate::model::job_step_record::enums::try_i16_to_run_if;
+use crate::model::job_step_record::enums::try_i16_to_signoff_role;
 use crate::native_common;
 
 const ENTITY: &str = "job_step_record";
-const EXTRA_PROPERTIES: [&str; 17] = [properties::ORG_ID,properties::JOB_ID,properties::ENVIRONMENT_ID,properties::RECORD_ID,properties::JOB_STEP_ID,properties::STEP_NAME,properties::STEP_TYPE,properties::STEP_REMARK,properties::EXTENSION_ID,properties::OPERATION_ID,properties::OPERATION_NAME,properties::OPERATION_PARAMETER,properties::ATTACHMENTS,properties::JOB_STEP_SEQ,properties::STATUS,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
+const EXTRA_PROPERTIES: [&str; 33] = [properties::ORG_ID,properties::JOB_ID,properties::ENVIRONMENT_ID,properties::RECORD_ID,properties::JOB_STEP_ID,properties::STEP_NAME,properties::STEP_TYPE,properties::STEP_REMARK,properties::EXTENSION_ID,properties::OPERATION_ID,properties::OPERATION_NAME,properties::OPERATION_PARAMETER,properties::RENDERED_PARAMETER,properties::ATTACHMENTS,properties::SIGNOFF_ROLE,properties::SIGNOFF_USERS,properties::REQUIRED_SIGNOFFS,properties::TIMEOUT_SECONDS,properties::MAX_RETRIES,properties::RETRY_BACKOFF,properties::MAX_PARALLEL,properties::BATCH_SIZE,properties::PAUSE_BETWEEN_BATCHES,properties::FAILURE_POLICY,properties::RUN_IF,properties::RUN_EXPRESSION,properties::SKIP_REASON,properties::JOB_STEP_SEQ,properties::DEPENDS_ON,properties::STATUS,properties::COMPLETED_BY,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
 const PROPERTY_COUNT: usize = EXTRA_PROPERTIES.len()+1;
 
 fn gen_properties() -> String {
@@ -53,9 +56,25 @@ fn extract_job_step_record(row: &Row) -> Result<JobStepRecord, ErrNo> {
         operation_id: row.try_get(properties::OPERATION_ID).map_err(extract_data_error)?,
         operation_name: row.try_get(properties::OPERATION_NAME).map_err(extract_data_error)?,
         operation_parameter: row.try_get(properties::OPERATION_PARAMETER).map_err(extract_data_error)?,
+        rendered_parameter: row.try_get(properties::RENDERED_PARAMETER).map_err(extract_data_error)?,
         attachments: row.try_get(properties::ATTACHMENTS).map_err(extract_data_error)?,
+        signoff_role: row.try_get::<_, Option<i16>>(properties::SIGNOFF_ROLE).map_err(extract_data_error)?.map(try_i16_to_signoff_role).transpose().map_err(undefined_enum_value)?,
+        signoff_users: row.try_get(properties::SIGNOFF_USERS).map_err(extract_data_error)?,
+        required_signoffs: row.try_get(properties::REQUIRED_SIGNOFFS).map_err(extract_data_error)?,
+        timeout_seconds: row.try_get(properties::TIMEOUT_SECONDS).map_err(extract_data_error)?,
+        max_retries: row.try_get(properties::MAX_RETRIES).map_err(extract_data_error)?,
+        retry_backoff: row.try_get(properties::RETRY_BACKOFF).map_err(extract_data_error)?,
+        max_parallel: row.try_get(properties::MAX_PARALLEL).map_err(extract_data_error)?,
+        batch_size: row.try_get(properties::BATCH_SIZE).map_err(extract_data_error)?,
+        pause_between_batches: row.try_get(properties::PAUSE_BETWEEN_BATCHES).map_err(extract_data_error)?,
+        failure_policy: try_i16_to_failure_policy(row.try_get(properties::FAILURE_POLICY).map_err(extract_data_error)?).map_err(undefined_enum_value)?,
+        run_if: try_i16_to_run_if(row.try_get(properties::RUN_IF).map_err(extract_data_error)?).map_err(undefined_enum_value)?,
+        run_expression: row.try_get(properties::RUN_EXPRESSION).map_err(extract_data_error)?,
+        skip_reason: row.try_get(properties::SKIP_REASON).map_err(extract_data_error)?,
         job_step_seq: row.try_get(properties::JOB_STEP_SEQ).map_err(extract_data_error)?,
+        depends_on: row.try_get(properties::DEPENDS_ON).map_err(extract_data_error)?,
         status: try_i16_to_status(row.try_get(properties::STATUS).map_err(extract_data_error)?).map_err(undefined_enum_value)?,
+        completed_by: row.try_get(properties::COMPLETED_BY).map_err(extract_data_error)?,
         created_time: row.try_get(properties::CREATED_TIME).map_err(extract_data_error)?,
         last_modified_time: row.try_get(properties::LAST_MODIFIED_TIME).map_err(extract_data_error)?,
     });
@@ -102,15 +121,63 @@ fn opt_to_conditions<'a>(opt: &'a JobStepRecordOpt) -> Vec::<(Condition, &'a (dy
     if let Some(operation_parameter) = opt.operation_parameter.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::OPERATION_PARAMETER), operator: None}, operation_parameter));
     }
+    if let Some(rendered_parameter) = opt.rendered_parameter.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::RENDERED_PARAMETER), operator: None}, rendered_parameter));
+    }
     if let Some(attachments) = opt.attachments.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::ATTACHMENTS), operator: None}, attachments));
     }
+    if let Some(signoff_role) = opt.signoff_role.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::SIGNOFF_ROLE), operator: None}, signoff_role));
+    }
+    if let Some(signoff_users) = opt.signoff_users.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::SIGNOFF_USERS), operator: None}, signoff_users));
+    }
+    if let Some(required_signoffs) = opt.required_signoffs.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::REQUIRED_SIGNOFFS), operator: None}, required_signoffs));
+    }
+    if let Some(timeout_seconds) = opt.timeout_seconds.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::TIMEOUT_SECONDS), operator: None}, timeout_seconds));
+    }
+    if let Some(max_retries) = opt.max_retries.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::MAX_RETRIES), operator: None}, max_retries));
+    }
+    if let Some(retry_backoff) = opt.retry_backoff.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::RETRY_BACKOFF), operator: None}, retry_backoff));
+    }
+    if let Some(max_parallel) = opt.max_parallel.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::MAX_PARALLEL), operator: None}, max_parallel));
+    }
+    if let Some(batch_size) = opt.batch_size.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::BATCH_SIZE), operator: None}, batch_size));
+    }
+    if let Some(pause_between_batches) = opt.pause_between_batches.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::PAUSE_BETWEEN_BATCHES), operator: None}, pause_between_batches));
+    }
+    if let Some(failure_policy) = opt.failure_policy.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::FAILURE_POLICY), operator: None}, failure_policy));
+    }
+    if let Some(run_if) = opt.run_if.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::RUN_IF), operator: None}, run_if));
+    }
+    if let Some(run_expression) = opt.run_expression.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::RUN_EXPRESSION), operator: None}, run_expression));
+    }
+    if let Some(skip_reason) = opt.skip_reason.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::SKIP_REASON), operator: None}, skip_reason));
+    }
     if let Some(job_step_seq) = opt.job_step_seq.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::JOB_STEP_SEQ), operator: None}, job_step_seq));
     }
+    if let Some(depends_on) = opt.depends_on.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::DEPENDS_ON), operator: None}, depends_on));
+    }
     if let Some(status) = opt.status.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::STATUS), operator: None}, status));
     }
+    if let Some(completed_by) = opt.completed_by.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::COMPLETED_BY), operator: None}, completed_by));
+    }
     if let Some(created_time) = opt.created_time.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::CREATED_TIME), operator: None}, created_time));
     }
@@ -176,9 +243,25 @@ impl<'a> JobStepRecordBaseService<'a> {
                     add_val(&mut vals, &job_step_record.operation_id),
                     add_val(&mut vals, &job_step_record.operation_name),
                     add_val(&mut vals, &job_step_record.operation_parameter),
+                    add_val(&mut vals, &job_step_record.rendered_parameter),
                     add_val(&mut vals, &job_step_record.attachments),
+                    add_val(&mut vals, &job_step_record.signoff_role),
+                    add_val(&mut vals, &job_step_record.signoff_users),
+                    add_val(&mut vals, &job_step_record.required_signoffs),
+                    add_val(&mut vals, &job_step_record.timeout_seconds),
+                    add_val(&mut vals, &job_step_record.max_retries),
+                    add_val(&mut vals, &job_step_record.retry_backoff),
+                    add_val(&mut vals, &job_step_record.max_parallel),
+                    add_val(&mut vals, &job_step_record.batch_size),
+                    add_val(&mut vals, &job_step_record.pause_between_batches),
+                    add_val(&mut vals, &job_step_record.failure_policy),
+                    add_val(&mut vals, &job_step_record.run_if),
+                    add_val(&mut vals, &job_step_record.run_expression),
+                    add_val(&mut vals, &job_step_record.skip_reason),
                     add_val(&mut vals, &job_step_record.job_step_seq),
+                    add_val(&mut vals, &job_step_record.depends_on),
                     add_val(&mut vals, &job_step_record.status),
+                    add_val(&mut vals, &job_step_record.completed_by),
                     add_val(&mut vals, &job_step_record.created_time),
                     add_val(&mut vals, &job_step_record.last_modified_time),
                 ].join(",")
@@ -212,9 +295,25 @@ impl<'a> JobStepRecordBaseService<'a> {
                         add_val(&mut vals, &job_step_record.operation_id),
                         add_val(&mut vals, &job_step_record.operation_name),
                         add_val(&mut vals, &job_step_record.operation_parameter),
+                        add_val(&mut vals, &job_step_record.rendered_parameter),
                         add_val(&mut vals, &job_step_record.attachments),
+                        add_val(&mut vals, &job_step_record.signoff_role),
+                        add_val(&mut vals, &job_step_record.signoff_users),
+                        add_val(&mut vals, &job_step_record.required_signoffs),
+                        add_val(&mut vals, &job_step_record.timeout_seconds),
+                        add_val(&mut vals, &job_step_record.max_retries),
+                        add_val(&mut vals, &job_step_record.retry_backoff),
+                        add_val(&mut vals, &job_step_record.max_parallel),
+                        add_val(&mut vals, &job_step_record.batch_size),
+                        add_val(&mut vals, &job_step_record.pause_between_batches),
+                        add_val(&mut vals, &job_step_record.failure_policy),
+                        add_val(&mut vals, &job_step_record.run_if),
+                        add_val(&mut vals, &job_step_record.run_expression),
+                        add_val(&mut vals, &job_step_record.skip_reason),
                         add_val(&mut vals, &job_step_record.job_step_seq),
+                        add_val(&mut vals, &job_step_record.depends_on),
                         add_val(&mut vals, &job_step_record.status),
+                        add_val(&mut vals, &job_step_record.completed_by),
                         add_val(&mut vals, &job_step_record.created_time),
                         add_val(&mut vals, &job_step_record.last_modified_time),
                     ];
@@ -244,9 +343,25 @@ impl<'a> JobStepRecordBaseService<'a> {
                     ",", properties::OPERATION_ID, "=", &add_val(&mut vals, &job_step_record.operation_id),
                     ",", properties::OPERATION_NAME, "=", &add_val(&mut vals, &job_step_record.operation_name),
                     ",", properties::OPERATION_PARAMETER, "=", &add_val(&mut vals, &job_step_record.operation_parameter),
+                    ",", properties::RENDERED_PARAMETER, "=", &add_val(&mut vals, &job_step_record.rendered_parameter),
                     ",", properties::ATTACHMENTS, "=", &add_val(&mut vals, &job_step_record.attachments),
+                    ",", properties::SIGNOFF_ROLE, "=", &add_val(&mut vals, &job_step_record.signoff_role),
+                    ",", properties::SIGNOFF_USERS, "=", &add_val(&mut vals, &job_step_record.signoff_users),
+                    ",", properties::REQUIRED_SIGNOFFS, "=", &add_val(&mut vals, &job_step_record.required_signoffs),
+                    ",", properties::TIMEOUT_SECONDS, "=", &add_val(&mut vals, &job_step_record.timeout_seconds),
+                    ",", properties::MAX_RETRIES, "=", &add_val(&mut vals, &job_step_record.max_retries),
+                    ",", properties::RETRY_BACKOFF, "=", &add_val(&mut vals, &job_step_record.retry_backoff),
+                    ",", properties::MAX_PARALLEL, "=", &add_val(&mut vals, &job_step_record.max_parallel),
+                    ",", properties::BATCH_SIZE, "=", &add_val(&mut vals, &job_step_record.batch_size),
+                    ",", properties::PAUSE_BETWEEN_BATCHES, "=", &add_val(&mut vals, &job_step_record.pause_between_batches),
+                    ",", properties::FAILURE_POLICY, "=", &add_val(&mut vals, &job_step_record.failure_policy),
+                    ",", properties::RUN_IF, "=", &add_val(&mut vals, &job_step_record.run_if),
+                    ",", properties::RUN_EXPRESSION, "=", &add_val(&mut vals, &job_step_record.run_expression),
+                    ",", properties::SKIP_REASON, "=", &add_val(&mut vals, &job_step_record.skip_reason),
                     ",", properties::JOB_STEP_SEQ, "=", &add_val(&mut vals, &job_step_record.job_step_seq),
+                    ",", properties::DEPENDS_ON, "=", &add_val(&mut vals, &job_step_record.depends_on),
                     ",", properties::STATUS, "=", &add_val(&mut vals, &job_step_record.status),
+                    ",", properties::COMPLETED_BY, "=", &add_val(&mut vals, &job_step_record.completed_by),
                     ",", properties::CREATED_TIME, "=", &add_val(&mut vals, &job_step_record.created_time),
                     ",", properties::LAST_MODIFIED_TIME, "=", &add_val(&mut vals, &job_step_record.last_modified_time),
                 ].concat()
@@ -314,15 +429,63 @@ impl<'a> JobStepRecordBaseService<'a> {
                 JobStepRecordProperty::OperationParameter(operation_parameter) => {
                     [properties::OPERATION_PARAMETER.into(), "=".into(), add_val(&mut vals, operation_parameter).into()]
                 },
+                JobStepRecordProperty::RenderedParameter(rendered_parameter) => {
+                    [properties::RENDERED_PARAMETER.into(), "=".into(), add_val(&mut vals, rendered_parameter).into()]
+                },
                 JobStepRecordProperty::Attachments(attachments) => {
                     [properties::ATTACHMENTS.into(), "=".into(), add_val(&mut vals, attachments).into()]
                 },
+                JobStepRecordProperty::SignoffRole(signoff_role) => {
+                    [properties::SIGNOFF_ROLE.into(), "=".into(), add_val(&mut vals, signoff_role).into()]
+                },
+                JobStepRecordProperty::SignoffUsers(signoff_users) => {
+                    [properties::SIGNOFF_USERS.into(), "=".into(), add_val(&mut vals, signoff_users).into()]
+                },
+                JobStepRecordProperty::RequiredSignoffs(required_signoffs) => {
+                    [properties::REQUIRED_SIGNOFFS.into(), "=".into(), add_val(&mut vals, required_signoffs).into()]
+                },
+                JobStepRecordProperty::TimeoutSeconds(timeout_seconds) => {
+                    [properties::TIMEOUT_SECONDS.into(), "=".into(), add_val(&mut vals, timeout_seconds).into()]
+                },
+                JobStepRecordProperty::MaxRetries(max_retries) => {
+                    [properties::MAX_RETRIES.into(), "=".into(), add_val(&mut vals, max_retries).into()]
+                },
+                JobStepRecordProperty::RetryBackoff(retry_backoff) => {
+                    [properties::RETRY_BACKOFF.into(), "=".into(), add_val(&mut vals, retry_backoff).into()]
+                },
+                JobStepRecordProperty::MaxParallel(max_parallel) => {
+                    [properties::MAX_PARALLEL.into(), "=".into(), add_val(&mut vals, max_parallel).into()]
+                },
+                JobStepRecordProperty::BatchSize(batch_size) => {
+                    [properties::BATCH_SIZE.into(), "=".into(), add_val(&mut vals, batch_size).into()]
+                },
+                JobStepRecordProperty::PauseBetweenBatches(pause_between_batches) => {
+                    [properties::PAUSE_BETWEEN_BATCHES.into(), "=".into(), add_val(&mut vals, pause_between_batches).into()]
+                },
+                JobStepRecordProperty::FailurePolicy(failure_policy) => {
+                    [properties::FAILURE_POLICY.into(), "=".into(), add_val(&mut vals, failure_policy).into()]
+                },
+                JobStepRecordProperty::RunIf(run_if) => {
+                    [properties::RUN_IF.into(), "=".into(), add_val(&mut vals, run_if).into()]
+                },
+                JobStepRecordProperty::RunExpression(run_expression) => {
+                    [properties::RUN_EXPRESSION.into(), "=".into(), add_val(&mut vals, run_expression).into()]
+                },
+                JobStepRecordProperty::SkipReason(skip_reason) => {
+                    [properties::SKIP_REASON.into(), "=".into(), add_val(&mut vals, skip_reason).into()]
+                },
                 JobStepRecordProperty::JobStepSeq(job_step_seq) => {
                     [properties::JOB_STEP_SEQ.into(), "=".into(), add_val(&mut vals, job_step_seq).into()]
                 },
+                JobStepRecordProperty::DependsOn(depends_on) => {
+                    [properties::DEPENDS_ON.into(), "=".into(), add_val(&mut vals, depends_on).into()]
+                },
                 JobStepRecordProperty::Status(status) => {
                     [properties::STATUS.into(), "=".into(), add_val(&mut vals, status).into()]
                 },
+                JobStepRecordProperty::CompletedBy(completed_by) => {
+                    [properties::COMPLETED_BY.into(), "=".into(), add_val(&mut vals, completed_by).into()]
+                },
                 JobStepRecordProperty::CreatedTime(created_time) => {
                     [properties::CREATED_TIME.into(), "=".into(), add_val(&mut vals, created_time).into()]
                 },
@@ -25,7 +25,7 @@ use crate::model::job_step_resource_record::enums::try_i16_to_status;
 use crate::native_common;
 
 const ENTITY: &str = "job_step_resource_record";
-const EXTRA_PROPERTIES: [&str; 13] = [properties::ORG_ID,properties::JOB_ID,properties::ENVIRONMENT_ID,properties::RECORD_ID,properties::JOB_STEP_RECORD_ID,properties::ENVIRONMENT_RESOURCE_ID,properties::RESOURCE_NAME,properties::EXTENSION_CONFIGURATION,properties::OUTPUT_FILE,properties::OUTPUT_CONTENT,properties::STATUS,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
+const EXTRA_PROPERTIES: [&str; 17] = [properties::ORG_ID,properties::JOB_ID,properties::ENVIRONMENT_ID,properties::RECORD_ID,properties::JOB_STEP_RECORD_ID,properties::ENVIRONMENT_RESOURCE_ID,properties::RESOURCE_NAME,properties::EXTENSION_CONFIGURATION,properties::OUTPUT_FILE,properties::OUTPUT_CONTENT,properties::LOG_KEY,properties::LOG_SUMMARY,properties::OUTPUTS,properties::ATTEMPTS,properties::STATUS,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
 const PROPERTY_COUNT: usize = EXTRA_PROPERTIES.len()+1;
 
 fn gen_properties() -> String {
@@ -50,6 +50,10 @@ fn extract_job_step_resource_record(row: &Row) -> Result<JobStepResourceRecord,
         extension_configuration: row.try_get(properties::EXTENSION_CONFIGURATION).map_err(extract_data_error)?,
         output_file: row.try_get(properties::OUTPUT_FILE).map_err(extract_data_error)?,
         output_content: row.try_get(properties::OUTPUT_CONTENT).map_err(extract_data_error)?,
+        log_key: row.try_get(properties::LOG_KEY).map_err(extract_data_error)?,
+        log_summary: row.try_get(properties::LOG_SUMMARY).map_err(extract_data_error)?,
+        outputs: row.try_get(properties::OUTPUTS).map_err(extract_data_error)?,
+        attempts: row.try_get(properties::ATTEMPTS).map_err(extract_data_error)?,
         status: try_i16_to_status(row.try_get(properties::STATUS).map_err(extract_data_error)?).map_err(undefined_enum_value)?,
         created_time: row.try_get(properties::CREATED_TIME).map_err(extract_data_error)?,
         last_modified_time: row.try_get(properties::LAST_MODIFIED_TIME).map_err(extract_data_error)?,
@@ -91,6 +95,18 @@ fn opt_to_conditions<'a>(opt: &'a JobStepResourceRecordOpt) -> Vec::<(Condition,
     if let Some(output_content) = opt.output_content.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::OUTPUT_CONTENT), operator: None}, output_content));
     }
+    if let Some(log_key) = opt.log_key.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::LOG_KEY), operator: None}, log_key));
+    }
+    if let Some(log_summary) = opt.log_summary.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::LOG_SUMMARY), operator: None}, log_summary));
+    }
+    if let Some(outputs) = opt.outputs.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::OUTPUTS), operator: None}, outputs));
+    }
+    if let Some(attempts) = opt.attempts.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ATTEMPTS), operator: None}, attempts));
+    }
     if let Some(status) = opt.status.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::STATUS), operator: None}, status));
     }
@@ -157,6 +173,10 @@ impl<'a> JobStepResourceRecordBaseService<'a> {
                     add_val(&mut vals, &job_step_resource_record.extension_configuration),
                     add_val(&mut vals, &job_step_resource_record.output_file),
                     add_val(&mut vals, &job_step_resource_record.output_content),
+                    add_val(&mut vals, &job_step_resource_record.log_key),
+                    add_val(&mut vals, &job_step_resource_record.log_summary),
+                    add_val(&mut vals, &job_step_resource_record.outputs),
+                    add_val(&mut vals, &job_step_resource_record.attempts),
                     add_val(&mut vals, &job_step_resource_record.status),
                     add_val(&mut vals, &job_step_resource_record.created_time),
                     add_val(&mut vals, &job_step_resource_record.last_modified_time),
@@ -189,6 +209,10 @@ impl<'a> JobStepResourceRecordBaseService<'a> {
                         add_val(&mut vals, &job_step_resource_record.extension_configuration),
                         add_val(&mut vals, &job_step_resource_record.output_file),
                         add_val(&mut vals, &job_step_resource_record.output_content),
+                        add_val(&mut vals, &job_step_resource_record.log_key),
+                        add_val(&mut vals, &job_step_resource_record.log_summary),
+                        add_val(&mut vals, &job_step_resource_record.outputs),
+                        add_val(&mut vals, &job_step_resource_record.attempts),
                         add_val(&mut vals, &job_step_resource_record.status),
                         add_val(&mut vals, &job_step_resource_record.created_time),
                         add_val(&mut vals, &job_step_resource_record.last_modified_time),
@@ -217,6 +241,10 @@ impl<'a> JobStepResourceRecordBaseService<'a> {
                     ",", properties::EXTENSION_CONFIGURATION, "=", &add_val(&mut vals, &job_step_resource_record.extension_configuration),
                     ",", properties::OUTPUT_FILE, "=", &add_val(&mut vals, &job_step_resource_record.output_file),
                     ",", properties::OUTPUT_CONTENT, "=", &add_val(&mut vals, &job_step_resource_record.output_content),
+                    ",", properties::LOG_KEY, "=", &add_val(&mut vals, &job_step_resource_record.log_key),
+                    ",", properties::LOG_SUMMARY, "=", &add_val(&mut vals, &job_step_resource_record.log_summary),
+                    ",", properties::OUTPUTS, "=", &add_val(&mut vals, &job_step_resource_record.outputs),
+                    ",", properties::ATTEMPTS, "=", &add_val(&mut vals, &job_step_resource_record.attempts),
                     ",", properties::STATUS, "=", &add_val(&mut vals, &job_step_resource_record.status),
                     ",", properties::CREATED_TIME, "=", &add_val(&mut vals, &job_step_resource_record.created_time),
                     ",", properties::LAST_MODIFIED_TIME, "=", &add_val(&mut vals, &job_step_resource_record.last_modified_time),
@@ -279,6 +307,18 @@ impl<'a> JobStepResourceRecordBaseService<'a> {
                 JobStepResourceRecordProperty::OutputContent(output_content) => {
                     [properties::OUTPUT_CONTENT.into(), "=".into(), add_val(&mut vals, output_content).into()]
                 },
+                JobStepResourceRecordProperty::LogKey(log_key) => {
+                    [properties::LOG_KEY.into(), "=".into(), add_val(&mut vals, log_key).into()]
+                },
+                JobStepResourceRecordProperty::LogSummary(log_summary) => {
+                    [properties::LOG_SUMMARY.into(), "=".into(), add_val(&mut vals, log_summary).into()]
+                },
+                JobStepResourceRecordProperty::Outputs(outputs) => {
+                    [properties::OUTPUTS.into(), "=".into(), add_val(&mut vals, outputs).into()]
+                },
+                JobStepResourceRecordProperty::Attempts(attempts) => {
+                    [properties::ATTEMPTS.into(), "=".into(), add_val(&mut vals, attempts).into()]
+                },
                 JobStepResourceRecordProperty::Status(status) => {
                     [properties::STATUS.into(), "=".into(), add_val(&mut vals, status).into()]
                 },
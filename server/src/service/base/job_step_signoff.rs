@@ -0,0 +1,335 @@
+use tihu_native::errno::execute_error;
+use tihu_native::errno::extract_data_error;
+use tihu_native::errno::prepare_statement_error;
+use tihu_native::errno::query_error;
+use tihu_native::errno::undefined_enum_value;
+use tihu_native::ErrNo;
+use tihu::Id;
+use tihu::LightString;
+use lazy_static;
+use format_xml;
+use std::borrow::Cow;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{Row, Transaction};
+use native_common::utils::add_val;
+use native_common::utils::add_vals;
+use native_common::utils::Condition;
+use native_common::utils::add_conditions;
+use native_common::utils::calc_sql_pagination;
+use crate::log;
+use crate::model::job_step_signoff::properties;
+use crate::model::job_step_signoff::JobStepSignoff;
+use crate::model::job_step_signoff::JobStepSignoffProperty;
+use crate::model::job_step_signoff::JobStepSignoffOpt;
+use crate::native_common;
+
+const ENTITY: &str = "job_step_signoff";
+const EXTRA_PROPERTIES: [&str; 9] = [properties::ORG_ID,properties::RECORD_ID,properties::JOB_STEP_RECORD_ID,properties::USER_ID,properties::SUCCESS,properties::COMMENT,properties::ATTACHMENTS,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
+const PROPERTY_COUNT: usize = EXTRA_PROPERTIES.len()+1;
+
+fn gen_properties() -> String {
+    let properties:Vec<&str> = [properties::ID].iter().chain(EXTRA_PROPERTIES.iter()).map(|item|*item).collect();
+    return properties.join(",");
+}
+
+lazy_static::lazy_static! {
+    static ref PROPERTIES: String = gen_properties();
+}
+
+fn extract_job_step_signoff(row: &Row) -> Result<JobStepSignoff, ErrNo> {
+    return Ok(JobStepSignoff {
+        id: row.try_get(properties::ID).map_err(extract_data_error)?,
+        org_id: row.try_get(properties::ORG_ID).map_err(extract_data_error)?,
+        record_id: row.try_get(properties::RECORD_ID).map_err(extract_data_error)?,
+        job_step_record_id: row.try_get(properties::JOB_STEP_RECORD_ID).map_err(extract_data_error)?,
+        user_id: row.try_get(properties::USER_ID).map_err(extract_data_error)?,
+        success: row.try_get(properties::SUCCESS).map_err(extract_data_error)?,
+        comment: row.try_get(properties::COMMENT).map_err(extract_data_error)?,
+        attachments: row.try_get(properties::ATTACHMENTS).map_err(extract_data_error)?,
+        created_time: row.try_get(properties::CREATED_TIME).map_err(extract_data_error)?,
+        last_modified_time: row.try_get(properties::LAST_MODIFIED_TIME).map_err(extract_data_error)?,
+    });
+}
+
+fn opt_to_conditions<'a>(opt: &'a JobStepSignoffOpt) -> Vec::<(Condition, &'a (dyn ToSql + std::marker::Sync))> {
+    let mut pairs = Vec::<(Condition,&(dyn ToSql + std::marker::Sync))>::new();
+    if let Some(id) = opt.id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ID), operator: None}, id));
+    }
+    if let Some(org_id) = opt.org_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ORG_ID), operator: None}, org_id));
+    }
+    if let Some(record_id) = opt.record_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::RECORD_ID), operator: None}, record_id));
+    }
+    if let Some(job_step_record_id) = opt.job_step_record_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::JOB_STEP_RECORD_ID), operator: None}, job_step_record_id));
+    }
+    if let Some(user_id) = opt.user_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::USER_ID), operator: None}, user_id));
+    }
+    if let Some(success) = opt.success.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::SUCCESS), operator: None}, success));
+    }
+    if let Some(comment) = opt.comment.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::COMMENT), operator: None}, comment));
+    }
+    if let Some(attachments) = opt.attachments.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ATTACHMENTS), operator: None}, attachments));
+    }
+    if let Some(created_time) = opt.created_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::CREATED_TIME), operator: None}, created_time));
+    }
+    if let Some(last_modified_time) = opt.last_modified_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::LAST_MODIFIED_TIME), operator: None}, last_modified_time));
+    }
+    return pairs;
+}
+
+pub struct JobStepSignoffBaseService<'a> {
+    transaction: &'a Transaction<'a>
+}
+
+impl<'a> JobStepSignoffBaseService<'a> {
+
+    pub fn new(transaction: &'a Transaction) -> JobStepSignoffBaseService<'a> {
+        return JobStepSignoffBaseService {
+            transaction: transaction
+        };
+    }
+
+    pub async fn read_job_step_signoff(&self, id: Id) -> Result<Option<JobStepSignoff>, ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}" where "{properties::ID}"="{add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let row_opt: Option<Row> = self.transaction.query_opt(&statement, &vals).await.map_err(query_error)?;
+        return Ok(row_opt.as_ref().map(extract_job_step_signoff).transpose()?);
+    }
+
+    pub async fn read_job_step_signoff_batch(&self, ids: &[Id]) -> Result<Vec<JobStepSignoff>, ErrNo>
+    {
+        if ids.is_empty() {
+            log::warn!("待批量读取的手动步骤签收id集合为空");
+            return Ok(Vec::new());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(ids.len());
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}" where "{properties::ID}" in (" {add_vals(&mut vals, &ids)} ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<JobStepSignoff> = rows.iter().map(extract_job_step_signoff).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+    pub async fn insert_job_step_signoff(&self, job_step_signoff: &JobStepSignoff) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT);
+        let sql = format_xml::template! {
+            "insert into "{ENTITY}"(" {PROPERTIES.as_str()} ") values(" {
+                vec![
+                    add_val(&mut vals, &job_step_signoff.id),
+                    add_val(&mut vals, &job_step_signoff.org_id),
+                    add_val(&mut vals, &job_step_signoff.record_id),
+                    add_val(&mut vals, &job_step_signoff.job_step_record_id),
+                    add_val(&mut vals, &job_step_signoff.user_id),
+                    add_val(&mut vals, &job_step_signoff.success),
+                    add_val(&mut vals, &job_step_signoff.comment),
+                    add_val(&mut vals, &job_step_signoff.attachments),
+                    add_val(&mut vals, &job_step_signoff.created_time),
+                    add_val(&mut vals, &job_step_signoff.last_modified_time),
+                ].join(",")
+            } ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn insert_job_step_signoff_batch(&self, job_step_signoff_list: &[JobStepSignoff]) -> Result<(), ErrNo> {
+        if job_step_signoff_list.is_empty() {
+            log::warn!("待批量新增的手动步骤签收集合为空");
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT * job_step_signoff_list.len());
+        let sql = format_xml::template! {
+            "insert into "{ENTITY}"(" {PROPERTIES.as_str()} ") values" {
+                job_step_signoff_list.iter().map(|job_step_signoff|{
+                    let trunks:Vec<String> = vec![
+                        add_val(&mut vals, &job_step_signoff.id),
+                        add_val(&mut vals, &job_step_signoff.org_id),
+                        add_val(&mut vals, &job_step_signoff.record_id),
+                        add_val(&mut vals, &job_step_signoff.job_step_record_id),
+                        add_val(&mut vals, &job_step_signoff.user_id),
+                        add_val(&mut vals, &job_step_signoff.success),
+                        add_val(&mut vals, &job_step_signoff.comment),
+                        add_val(&mut vals, &job_step_signoff.attachments),
+                        add_val(&mut vals, &job_step_signoff.created_time),
+                        add_val(&mut vals, &job_step_signoff.last_modified_time),
+                    ];
+                    ["(", &trunks.join(","), ")"].concat()
+                }).collect::<Vec<String>>().join(",")
+            }
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn update_job_step_signoff_full(&self, job_step_signoff: &JobStepSignoff) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT);
+        let sql = format_xml::template! {
+            "update "{ENTITY}" set " {
+                [
+                    properties::ORG_ID, "=", &add_val(&mut vals, &job_step_signoff.org_id),
+                    ",", properties::RECORD_ID, "=", &add_val(&mut vals, &job_step_signoff.record_id),
+                    ",", properties::JOB_STEP_RECORD_ID, "=", &add_val(&mut vals, &job_step_signoff.job_step_record_id),
+                    ",", properties::USER_ID, "=", &add_val(&mut vals, &job_step_signoff.user_id),
+                    ",", properties::SUCCESS, "=", &add_val(&mut vals, &job_step_signoff.success),
+                    ",", properties::COMMENT, "=", &add_val(&mut vals, &job_step_signoff.comment),
+                    ",", properties::ATTACHMENTS, "=", &add_val(&mut vals, &job_step_signoff.attachments),
+                    ",", properties::CREATED_TIME, "=", &add_val(&mut vals, &job_step_signoff.created_time),
+                    ",", properties::LAST_MODIFIED_TIME, "=", &add_val(&mut vals, &job_step_signoff.last_modified_time),
+                ].concat()
+            } " where "{properties::ID}"=" {add_val(&mut vals, &job_step_signoff.id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn update_job_step_signoff(&self, id: Id, changes: &[JobStepSignoffProperty]) -> Result<(), ErrNo> {
+        let changes: Vec<&JobStepSignoffProperty> = changes
+            .iter()
+            .filter(|change| match change {
+                JobStepSignoffProperty::Id(_) => false,
+                _ => true,
+            })
+            .collect();
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1 + changes.len());
+        let mut trunks: Vec<Cow<str>> = Vec::with_capacity(4 * changes.len());
+        for (index, property) in changes.iter().enumerate() {
+            if 0 < index {
+                trunks.push(",".into());
+            }
+            trunks.extend(match property {
+                JobStepSignoffProperty::Id(id) => {
+                    [properties::ID.into(), "=".into(), add_val(&mut vals, id).into()]
+                },
+                JobStepSignoffProperty::OrgId(org_id) => {
+                    [properties::ORG_ID.into(), "=".into(), add_val(&mut vals, org_id).into()]
+                },
+                JobStepSignoffProperty::RecordId(record_id) => {
+                    [properties::RECORD_ID.into(), "=".into(), add_val(&mut vals, record_id).into()]
+                },
+                JobStepSignoffProperty::JobStepRecordId(job_step_record_id) => {
+                    [properties::JOB_STEP_RECORD_ID.into(), "=".into(), add_val(&mut vals, job_step_record_id).into()]
+                },
+                JobStepSignoffProperty::UserId(user_id) => {
+                    [properties::USER_ID.into(), "=".into(), add_val(&mut vals, user_id).into()]
+                },
+                JobStepSignoffProperty::Success(success) => {
+                    [properties::SUCCESS.into(), "=".into(), add_val(&mut vals, success).into()]
+                },
+                JobStepSignoffProperty::Comment(comment) => {
+                    [properties::COMMENT.into(), "=".into(), add_val(&mut vals, comment).into()]
+                },
+                JobStepSignoffProperty::Attachments(attachments) => {
+                    [properties::ATTACHMENTS.into(), "=".into(), add_val(&mut vals, attachments).into()]
+                },
+                JobStepSignoffProperty::CreatedTime(created_time) => {
+                    [properties::CREATED_TIME.into(), "=".into(), add_val(&mut vals, created_time).into()]
+                },
+                JobStepSignoffProperty::LastModifiedTime(last_modified_time) => {
+                    [properties::LAST_MODIFIED_TIME.into(), "=".into(), add_val(&mut vals, last_modified_time).into()]
+                },
+            });
+        }
+        let change_content: String = trunks.concat();
+        let sql = format_xml::template! {
+            "update "{ENTITY}" set " {change_content} " where "{properties::ID}"=" {add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn delete_job_step_signoff(&self, id: Id) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1);
+        let sql = format_xml::template! {
+            "delete from "{ENTITY}" where "{properties::ID}"="{add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn delete_job_step_signoff_batch(&self, ids: &[Id]) -> Result<(), ErrNo>
+    {
+        if ids.is_empty() {
+            log::warn!("待批量删除的手动步骤签收id集合为空");
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(ids.len());
+        let sql = format_xml::template! {
+            "delete from "{ENTITY}" where "{properties::ID}" in (" {add_vals(&mut vals, &ids)} ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn query_job_step_signoff_count(&self, opt: &JobStepSignoffOpt) -> Result<u64, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let sql = format_xml::template! {
+            "select count(1) from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let row: Row = self.transaction.query_one(&statement, &vals).await.map_err(query_error)?;
+        let count:i64 = row.get(0);
+        return Ok(count as u64);
+    }
+
+    pub async fn query_job_step_signoff(&self, page_no: u64, page_size: u64, opt: &JobStepSignoffOpt) -> Result<Vec<JobStepSignoff>, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let (limit, offset) = calc_sql_pagination(page_no, page_size);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+            " limit "{limit}" offset "{offset}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<JobStepSignoff> = rows.iter().map(extract_job_step_signoff).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+    pub async fn query_job_step_signoff_one(&self, opt: &JobStepSignoffOpt) -> Result<Option<JobStepSignoff>, ErrNo> {
+        let list = self.query_job_step_signoff(1, 1, opt).await?;
+        return Ok(list.into_iter().next());
+    }
+
+    pub async fn query_job_step_signoff_batch(&self, opt: &JobStepSignoffOpt) -> Result<Vec<JobStepSignoff>, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<JobStepSignoff> = rows.iter().map(extract_job_step_signoff).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+}
\ No newline at end of file
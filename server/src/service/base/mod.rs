@@ -1,28 +1,44 @@
 #![allow(dead_code)]
 
+mod api_token;
 mod environment;
+mod environment_grant;
+mod environment_lock;
 mod environment_resource;
 mod environment_schema;
 mod environment_schema_resource;
 mod external_user;
 mod job;
 mod job_record;
+mod job_record_approval;
+mod job_schedule;
 mod job_step;
 mod job_step_record;
 mod job_step_resource_record;
+mod job_step_signoff;
 mod organization;
+mod runner;
+mod runner_task;
 mod system_user;
 mod user;
+pub use api_token::*;
 pub use environment::*;
+pub use environment_grant::*;
+pub use environment_lock::*;
 pub use environment_resource::*;
 pub use environment_schema::*;
 pub use environment_schema_resource::*;
 pub use external_user::*;
 pub use job::*;
 pub use job_record::*;
+pub use job_record_approval::*;
+pub use job_schedule::*;
 pub use job_step::*;
 pub use job_step_record::*;
 pub use job_step_resource_record::*;
+pub use job_step_signoff::*;
 pub use organization::*;
+pub use runner::*;
+pub use runner_task::*;
 pub use system_user::*;
 pub use user::*;
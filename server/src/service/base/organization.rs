@@ -24,7 +24,7 @@ use crate::model::organization::OrganizationOpt;
 use crate::native_common;
 
 const ENTITY: &str = "organization";
-const EXTRA_PROPERTIES: [&str; 3] = [properties::NAME,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
+const EXTRA_PROPERTIES: [&str; 7] = [properties::NAME,properties::RETENTION_COUNT,properties::RETENTION_DAYS,properties::LAST_PURGE_TIME,properties::LAST_PURGE_REPORT,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
 const PROPERTY_COUNT: usize = EXTRA_PROPERTIES.len()+1;
 
 fn gen_properties() -> String {
@@ -40,6 +40,10 @@ fn extract_organization(row: &Row) -> Result<Organization, ErrNo> {
     return Ok(Organization {
         id: row.try_get(properties::ID).map_err(extract_data_error)?,
         name: row.try_get(properties::NAME).map_err(extract_data_error)?,
+        retention_count: row.try_get(properties::RETENTION_COUNT).map_err(extract_data_error)?,
+        retention_days: row.try_get(properties::RETENTION_DAYS).map_err(extract_data_error)?,
+        last_purge_time: row.try_get(properties::LAST_PURGE_TIME).map_err(extract_data_error)?,
+        last_purge_report: row.try_get(properties::LAST_PURGE_REPORT).map_err(extract_data_error)?,
         created_time: row.try_get(properties::CREATED_TIME).map_err(extract_data_error)?,
         last_modified_time: row.try_get(properties::LAST_MODIFIED_TIME).map_err(extract_data_error)?,
     });
@@ -53,6 +57,18 @@ fn opt_to_conditions<'a>(opt: &'a OrganizationOpt) -> Vec::<(Condition, &'a (dyn
     if let Some(name) = opt.name.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::NAME), operator: None}, name));
     }
+    if let Some(retention_count) = opt.retention_count.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::RETENTION_COUNT), operator: None}, retention_count));
+    }
+    if let Some(retention_days) = opt.retention_days.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::RETENTION_DAYS), operator: None}, retention_days));
+    }
+    if let Some(last_purge_time) = opt.last_purge_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::LAST_PURGE_TIME), operator: None}, last_purge_time));
+    }
+    if let Some(last_purge_report) = opt.last_purge_report.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::LAST_PURGE_REPORT), operator: None}, last_purge_report));
+    }
     if let Some(created_time) = opt.created_time.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::CREATED_TIME), operator: None}, created_time));
     }
@@ -107,6 +123,10 @@ impl<'a> OrganizationBaseService<'a> {
                 vec![
                     add_val(&mut vals, &organization.id),
                     add_val(&mut vals, &organization.name),
+                    add_val(&mut vals, &organization.retention_count),
+                    add_val(&mut vals, &organization.retention_days),
+                    add_val(&mut vals, &organization.last_purge_time),
+                    add_val(&mut vals, &organization.last_purge_report),
                     add_val(&mut vals, &organization.created_time),
                     add_val(&mut vals, &organization.last_modified_time),
                 ].join(",")
@@ -129,6 +149,10 @@ impl<'a> OrganizationBaseService<'a> {
                     let trunks:Vec<String> = vec![
                         add_val(&mut vals, &organization.id),
                         add_val(&mut vals, &organization.name),
+                        add_val(&mut vals, &organization.retention_count),
+                        add_val(&mut vals, &organization.retention_days),
+                        add_val(&mut vals, &organization.last_purge_time),
+                        add_val(&mut vals, &organization.last_purge_report),
                         add_val(&mut vals, &organization.created_time),
                         add_val(&mut vals, &organization.last_modified_time),
                     ];
@@ -181,6 +205,18 @@ impl<'a> OrganizationBaseService<'a> {
                 OrganizationProperty::Name(name) => {
                     [properties::NAME.into(), "=".into(), add_val(&mut vals, name).into()]
                 },
+                OrganizationProperty::RetentionCount(retention_count) => {
+                    [properties::RETENTION_COUNT.into(), "=".into(), add_val(&mut vals, retention_count).into()]
+                },
+                OrganizationProperty::RetentionDays(retention_days) => {
+                    [properties::RETENTION_DAYS.into(), "=".into(), add_val(&mut vals, retention_days).into()]
+                },
+                OrganizationProperty::LastPurgeTime(last_purge_time) => {
+                    [properties::LAST_PURGE_TIME.into(), "=".into(), add_val(&mut vals, last_purge_time).into()]
+                },
+                OrganizationProperty::LastPurgeReport(last_purge_report) => {
+                    [properties::LAST_PURGE_REPORT.into(), "=".into(), add_val(&mut vals, last_purge_report).into()]
+                },
                 OrganizationProperty::CreatedTime(created_time) => {
                     [properties::CREATED_TIME.into(), "=".into(), add_val(&mut vals, created_time).into()]
                 },
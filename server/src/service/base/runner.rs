@@ -0,0 +1,335 @@
+use tihu_native::errno::execute_error;
+use tihu_native::errno::extract_data_error;
+use tihu_native::errno::prepare_statement_error;
+use tihu_native::errno::query_error;
+use tihu_native::errno::undefined_enum_value;
+use tihu_native::ErrNo;
+use tihu::Id;
+use tihu::LightString;
+use lazy_static;
+use format_xml;
+use std::borrow::Cow;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{Row, Transaction};
+use native_common::utils::add_val;
+use native_common::utils::add_vals;
+use native_common::utils::Condition;
+use native_common::utils::add_conditions;
+use native_common::utils::calc_sql_pagination;
+use crate::log;
+use crate::model::runner::properties;
+use crate::model::runner::Runner;
+use crate::model::runner::RunnerProperty;
+use crate::model::runner::RunnerOpt;
+use crate::native_common;
+
+const ENTITY: &str = "runner";
+const EXTRA_PROPERTIES: [&str; 9] = [properties::ORG_ID,properties::NAME,properties::LABEL,properties::TOKEN_HASH,properties::VERSION,properties::EXTENSION_IDS,properties::LAST_HEARTBEAT_TIME,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
+const PROPERTY_COUNT: usize = EXTRA_PROPERTIES.len()+1;
+
+fn gen_properties() -> String {
+    let properties:Vec<&str> = [properties::ID].iter().chain(EXTRA_PROPERTIES.iter()).map(|item|*item).collect();
+    return properties.join(",");
+}
+
+lazy_static::lazy_static! {
+    static ref PROPERTIES: String = gen_properties();
+}
+
+fn extract_runner(row: &Row) -> Result<Runner, ErrNo> {
+    return Ok(Runner {
+        id: row.try_get(properties::ID).map_err(extract_data_error)?,
+        org_id: row.try_get(properties::ORG_ID).map_err(extract_data_error)?,
+        name: row.try_get(properties::NAME).map_err(extract_data_error)?,
+        label: row.try_get(properties::LABEL).map_err(extract_data_error)?,
+        token_hash: row.try_get(properties::TOKEN_HASH).map_err(extract_data_error)?,
+        version: row.try_get(properties::VERSION).map_err(extract_data_error)?,
+        extension_ids: row.try_get(properties::EXTENSION_IDS).map_err(extract_data_error)?,
+        last_heartbeat_time: row.try_get(properties::LAST_HEARTBEAT_TIME).map_err(extract_data_error)?,
+        created_time: row.try_get(properties::CREATED_TIME).map_err(extract_data_error)?,
+        last_modified_time: row.try_get(properties::LAST_MODIFIED_TIME).map_err(extract_data_error)?,
+    });
+}
+
+fn opt_to_conditions<'a>(opt: &'a RunnerOpt) -> Vec::<(Condition, &'a (dyn ToSql + std::marker::Sync))> {
+    let mut pairs = Vec::<(Condition,&(dyn ToSql + std::marker::Sync))>::new();
+    if let Some(id) = opt.id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ID), operator: None}, id));
+    }
+    if let Some(org_id) = opt.org_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ORG_ID), operator: None}, org_id));
+    }
+    if let Some(name) = opt.name.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::NAME), operator: None}, name));
+    }
+    if let Some(label) = opt.label.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::LABEL), operator: None}, label));
+    }
+    if let Some(token_hash) = opt.token_hash.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::TOKEN_HASH), operator: None}, token_hash));
+    }
+    if let Some(version) = opt.version.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::VERSION), operator: None}, version));
+    }
+    if let Some(extension_ids) = opt.extension_ids.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::EXTENSION_IDS), operator: None}, extension_ids));
+    }
+    if let Some(last_heartbeat_time) = opt.last_heartbeat_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::LAST_HEARTBEAT_TIME), operator: None}, last_heartbeat_time));
+    }
+    if let Some(created_time) = opt.created_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::CREATED_TIME), operator: None}, created_time));
+    }
+    if let Some(last_modified_time) = opt.last_modified_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::LAST_MODIFIED_TIME), operator: None}, last_modified_time));
+    }
+    return pairs;
+}
+
+pub struct RunnerBaseService<'a> {
+    transaction: &'a Transaction<'a>
+}
+
+impl<'a> RunnerBaseService<'a> {
+
+    pub fn new(transaction: &'a Transaction) -> RunnerBaseService<'a> {
+        return RunnerBaseService {
+            transaction: transaction
+        };
+    }
+
+    pub async fn read_runner(&self, id: Id) -> Result<Option<Runner>, ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}" where "{properties::ID}"="{add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let row_opt: Option<Row> = self.transaction.query_opt(&statement, &vals).await.map_err(query_error)?;
+        return Ok(row_opt.as_ref().map(extract_runner).transpose()?);
+    }
+
+    pub async fn read_runner_batch(&self, ids: &[Id]) -> Result<Vec<Runner>, ErrNo>
+    {
+        if ids.is_empty() {
+            log::warn!("待批量读取的执行器id集合为空");
+            return Ok(Vec::new());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(ids.len());
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}" where "{properties::ID}" in (" {add_vals(&mut vals, &ids)} ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<Runner> = rows.iter().map(extract_runner).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+    pub async fn insert_runner(&self, runner: &Runner) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT);
+        let sql = format_xml::template! {
+            "insert into "{ENTITY}"(" {PROPERTIES.as_str()} ") values(" {
+                vec![
+                    add_val(&mut vals, &runner.id),
+                    add_val(&mut vals, &runner.org_id),
+                    add_val(&mut vals, &runner.name),
+                    add_val(&mut vals, &runner.label),
+                    add_val(&mut vals, &runner.token_hash),
+                    add_val(&mut vals, &runner.version),
+                    add_val(&mut vals, &runner.extension_ids),
+                    add_val(&mut vals, &runner.last_heartbeat_time),
+                    add_val(&mut vals, &runner.created_time),
+                    add_val(&mut vals, &runner.last_modified_time),
+                ].join(",")
+            } ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn insert_runner_batch(&self, runner_list: &[Runner]) -> Result<(), ErrNo> {
+        if runner_list.is_empty() {
+            log::warn!("待批量新增的执行器集合为空");
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT * runner_list.len());
+        let sql = format_xml::template! {
+            "insert into "{ENTITY}"(" {PROPERTIES.as_str()} ") values" {
+                runner_list.iter().map(|runner|{
+                    let trunks:Vec<String> = vec![
+                        add_val(&mut vals, &runner.id),
+                        add_val(&mut vals, &runner.org_id),
+                        add_val(&mut vals, &runner.name),
+                        add_val(&mut vals, &runner.label),
+                        add_val(&mut vals, &runner.token_hash),
+                        add_val(&mut vals, &runner.version),
+                        add_val(&mut vals, &runner.extension_ids),
+                        add_val(&mut vals, &runner.last_heartbeat_time),
+                        add_val(&mut vals, &runner.created_time),
+                        add_val(&mut vals, &runner.last_modified_time),
+                    ];
+                    ["(", &trunks.join(","), ")"].concat()
+                }).collect::<Vec<String>>().join(",")
+            }
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn update_runner_full(&self, runner: &Runner) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT);
+        let sql = format_xml::template! {
+            "update "{ENTITY}" set " {
+                [
+                    properties::ORG_ID, "=", &add_val(&mut vals, &runner.org_id),
+                    ",", properties::NAME, "=", &add_val(&mut vals, &runner.name),
+                    ",", properties::LABEL, "=", &add_val(&mut vals, &runner.label),
+                    ",", properties::TOKEN_HASH, "=", &add_val(&mut vals, &runner.token_hash),
+                    ",", properties::VERSION, "=", &add_val(&mut vals, &runner.version),
+                    ",", properties::EXTENSION_IDS, "=", &add_val(&mut vals, &runner.extension_ids),
+                    ",", properties::LAST_HEARTBEAT_TIME, "=", &add_val(&mut vals, &runner.last_heartbeat_time),
+                    ",", properties::CREATED_TIME, "=", &add_val(&mut vals, &runner.created_time),
+                    ",", properties::LAST_MODIFIED_TIME, "=", &add_val(&mut vals, &runner.last_modified_time),
+                ].concat()
+            } " where "{properties::ID}"=" {add_val(&mut vals, &runner.id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn update_runner(&self, id: Id, changes: &[RunnerProperty]) -> Result<(), ErrNo> {
+        let changes: Vec<&RunnerProperty> = changes
+            .iter()
+            .filter(|change| match change {
+                RunnerProperty::Id(_) => false,
+                _ => true,
+            })
+            .collect();
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1 + changes.len());
+        let mut trunks: Vec<Cow<str>> = Vec::with_capacity(4 * changes.len());
+        for (index, property) in changes.iter().enumerate() {
+            if 0 < index {
+                trunks.push(",".into());
+            }
+            trunks.extend(match property {
+                RunnerProperty::Id(id) => {
+                    [properties::ID.into(), "=".into(), add_val(&mut vals, id).into()]
+                },
+                RunnerProperty::OrgId(org_id) => {
+                    [properties::ORG_ID.into(), "=".into(), add_val(&mut vals, org_id).into()]
+                },
+                RunnerProperty::Name(name) => {
+                    [properties::NAME.into(), "=".into(), add_val(&mut vals, name).into()]
+                },
+                RunnerProperty::Label(label) => {
+                    [properties::LABEL.into(), "=".into(), add_val(&mut vals, label).into()]
+                },
+                RunnerProperty::TokenHash(token_hash) => {
+                    [properties::TOKEN_HASH.into(), "=".into(), add_val(&mut vals, token_hash).into()]
+                },
+                RunnerProperty::Version(version) => {
+                    [properties::VERSION.into(), "=".into(), add_val(&mut vals, version).into()]
+                },
+                RunnerProperty::ExtensionIds(extension_ids) => {
+                    [properties::EXTENSION_IDS.into(), "=".into(), add_val(&mut vals, extension_ids).into()]
+                },
+                RunnerProperty::LastHeartbeatTime(last_heartbeat_time) => {
+                    [properties::LAST_HEARTBEAT_TIME.into(), "=".into(), add_val(&mut vals, last_heartbeat_time).into()]
+                },
+                RunnerProperty::CreatedTime(created_time) => {
+                    [properties::CREATED_TIME.into(), "=".into(), add_val(&mut vals, created_time).into()]
+                },
+                RunnerProperty::LastModifiedTime(last_modified_time) => {
+                    [properties::LAST_MODIFIED_TIME.into(), "=".into(), add_val(&mut vals, last_modified_time).into()]
+                },
+            });
+        }
+        let change_content: String = trunks.concat();
+        let sql = format_xml::template! {
+            "update "{ENTITY}" set " {change_content} " where "{properties::ID}"=" {add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn delete_runner(&self, id: Id) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1);
+        let sql = format_xml::template! {
+            "delete from "{ENTITY}" where "{properties::ID}"="{add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn delete_runner_batch(&self, ids: &[Id]) -> Result<(), ErrNo>
+    {
+        if ids.is_empty() {
+            log::warn!("待批量删除的执行器id集合为空");
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(ids.len());
+        let sql = format_xml::template! {
+            "delete from "{ENTITY}" where "{properties::ID}" in (" {add_vals(&mut vals, &ids)} ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn query_runner_count(&self, opt: &RunnerOpt) -> Result<u64, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let sql = format_xml::template! {
+            "select count(1) from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let row: Row = self.transaction.query_one(&statement, &vals).await.map_err(query_error)?;
+        let count:i64 = row.get(0);
+        return Ok(count as u64);
+    }
+
+    pub async fn query_runner(&self, page_no: u64, page_size: u64, opt: &RunnerOpt) -> Result<Vec<Runner>, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let (limit, offset) = calc_sql_pagination(page_no, page_size);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+            " limit "{limit}" offset "{offset}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<Runner> = rows.iter().map(extract_runner).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+    pub async fn query_runner_one(&self, opt: &RunnerOpt) -> Result<Option<Runner>, ErrNo> {
+        let list = self.query_runner(1, 1, opt).await?;
+        return Ok(list.into_iter().next());
+    }
+
+    pub async fn query_runner_batch(&self, opt: &RunnerOpt) -> Result<Vec<Runner>, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<Runner> = rows.iter().map(extract_runner).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+}
\ No newline at end of file
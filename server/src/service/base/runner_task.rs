@@ -0,0 +1,416 @@
+use tihu_native::errno::execute_error;
+use tihu_native::errno::extract_data_error;
+use tihu_native::errno::prepare_statement_error;
+use tihu_native::errno::query_error;
+use tihu_native::errno::undefined_enum_value;
+use tihu_native::ErrNo;
+use tihu::Id;
+use tihu::LightString;
+use lazy_static;
+use format_xml;
+use std::borrow::Cow;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{Row, Transaction};
+use native_common::utils::add_val;
+use native_common::utils::add_vals;
+use native_common::utils::Condition;
+use native_common::utils::add_conditions;
+use native_common::utils::calc_sql_pagination;
+use crate::log;
+use crate::model::runner_task::properties;
+use crate::model::runner_task::RunnerTask;
+use crate::model::runner_task::RunnerTaskProperty;
+use crate::model::runner_task::RunnerTaskOpt;
+use crate::model::runner_task::enums::try_i16_to_status;
+use crate::native_common;
+
+const ENTITY: &str = "runner_task";
+const EXTRA_PROPERTIES: [&str; 17] = [properties::ORG_ID,properties::LABEL,properties::EXTENSION_ID,properties::OPERATION_ID,properties::OPERATION_PARAMETER,properties::EXTENSION_CONFIGURATION,properties::RESOURCE_INDEX,properties::STATUS,properties::RUNNER_ID,properties::CANCELLED,properties::PENDING_LOGS,properties::OUTPUTS,properties::ERROR,properties::POLLED_TIME,properties::REPORTED_TIME,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
+const PROPERTY_COUNT: usize = EXTRA_PROPERTIES.len()+1;
+
+fn gen_properties() -> String {
+    let properties:Vec<&str> = [properties::ID].iter().chain(EXTRA_PROPERTIES.iter()).map(|item|*item).collect();
+    return properties.join(",");
+}
+
+lazy_static::lazy_static! {
+    static ref PROPERTIES: String = gen_properties();
+}
+
+fn extract_runner_task(row: &Row) -> Result<RunnerTask, ErrNo> {
+    return Ok(RunnerTask {
+        id: row.try_get(properties::ID).map_err(extract_data_error)?,
+        org_id: row.try_get(properties::ORG_ID).map_err(extract_data_error)?,
+        label: row.try_get(properties::LABEL).map_err(extract_data_error)?,
+        extension_id: row.try_get(properties::EXTENSION_ID).map_err(extract_data_error)?,
+        operation_id: row.try_get(properties::OPERATION_ID).map_err(extract_data_error)?,
+        operation_parameter: row.try_get(properties::OPERATION_PARAMETER).map_err(extract_data_error)?,
+        extension_configuration: row.try_get(properties::EXTENSION_CONFIGURATION).map_err(extract_data_error)?,
+        resource_index: row.try_get(properties::RESOURCE_INDEX).map_err(extract_data_error)?,
+        status: try_i16_to_status(row.try_get(properties::STATUS).map_err(extract_data_error)?).map_err(undefined_enum_value)?,
+        runner_id: row.try_get(properties::RUNNER_ID).map_err(extract_data_error)?,
+        cancelled: row.try_get(properties::CANCELLED).map_err(extract_data_error)?,
+        pending_logs: row.try_get(properties::PENDING_LOGS).map_err(extract_data_error)?,
+        outputs: row.try_get(properties::OUTPUTS).map_err(extract_data_error)?,
+        error: row.try_get(properties::ERROR).map_err(extract_data_error)?,
+        polled_time: row.try_get(properties::POLLED_TIME).map_err(extract_data_error)?,
+        reported_time: row.try_get(properties::REPORTED_TIME).map_err(extract_data_error)?,
+        created_time: row.try_get(properties::CREATED_TIME).map_err(extract_data_error)?,
+        last_modified_time: row.try_get(properties::LAST_MODIFIED_TIME).map_err(extract_data_error)?,
+    });
+}
+
+fn opt_to_conditions<'a>(opt: &'a RunnerTaskOpt) -> Vec::<(Condition, &'a (dyn ToSql + std::marker::Sync))> {
+    let mut pairs = Vec::<(Condition,&(dyn ToSql + std::marker::Sync))>::new();
+    if let Some(id) = opt.id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ID), operator: None}, id));
+    }
+    if let Some(org_id) = opt.org_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ORG_ID), operator: None}, org_id));
+    }
+    if let Some(label) = opt.label.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::LABEL), operator: None}, label));
+    }
+    if let Some(extension_id) = opt.extension_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::EXTENSION_ID), operator: None}, extension_id));
+    }
+    if let Some(operation_id) = opt.operation_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::OPERATION_ID), operator: None}, operation_id));
+    }
+    if let Some(operation_parameter) = opt.operation_parameter.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::OPERATION_PARAMETER), operator: None}, operation_parameter));
+    }
+    if let Some(extension_configuration) = opt.extension_configuration.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::EXTENSION_CONFIGURATION), operator: None}, extension_configuration));
+    }
+    if let Some(resource_index) = opt.resource_index.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::RESOURCE_INDEX), operator: None}, resource_index));
+    }
+    if let Some(status) = opt.status.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::STATUS), operator: None}, status));
+    }
+    if let Some(runner_id) = opt.runner_id.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::RUNNER_ID), operator: None}, runner_id));
+    }
+    if let Some(cancelled) = opt.cancelled.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::CANCELLED), operator: None}, cancelled));
+    }
+    if let Some(pending_logs) = opt.pending_logs.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::PENDING_LOGS), operator: None}, pending_logs));
+    }
+    if let Some(outputs) = opt.outputs.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::OUTPUTS), operator: None}, outputs));
+    }
+    if let Some(error) = opt.error.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ERROR), operator: None}, error));
+    }
+    if let Some(polled_time) = opt.polled_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::POLLED_TIME), operator: None}, polled_time));
+    }
+    if let Some(reported_time) = opt.reported_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::REPORTED_TIME), operator: None}, reported_time));
+    }
+    if let Some(created_time) = opt.created_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::CREATED_TIME), operator: None}, created_time));
+    }
+    if let Some(last_modified_time) = opt.last_modified_time.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::LAST_MODIFIED_TIME), operator: None}, last_modified_time));
+    }
+    return pairs;
+}
+
+pub struct RunnerTaskBaseService<'a> {
+    transaction: &'a Transaction<'a>
+}
+
+impl<'a> RunnerTaskBaseService<'a> {
+
+    pub fn new(transaction: &'a Transaction) -> RunnerTaskBaseService<'a> {
+        return RunnerTaskBaseService {
+            transaction: transaction
+        };
+    }
+
+    pub async fn read_runner_task(&self, id: Id) -> Result<Option<RunnerTask>, ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}" where "{properties::ID}"="{add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let row_opt: Option<Row> = self.transaction.query_opt(&statement, &vals).await.map_err(query_error)?;
+        return Ok(row_opt.as_ref().map(extract_runner_task).transpose()?);
+    }
+
+    pub async fn read_runner_task_batch(&self, ids: &[Id]) -> Result<Vec<RunnerTask>, ErrNo>
+    {
+        if ids.is_empty() {
+            log::warn!("待批量读取的执行器任务id集合为空");
+            return Ok(Vec::new());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(ids.len());
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}" where "{properties::ID}" in (" {add_vals(&mut vals, &ids)} ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<RunnerTask> = rows.iter().map(extract_runner_task).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+    pub async fn insert_runner_task(&self, runner_task: &RunnerTask) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT);
+        let sql = format_xml::template! {
+            "insert into "{ENTITY}"(" {PROPERTIES.as_str()} ") values(" {
+                vec![
+                    add_val(&mut vals, &runner_task.id),
+                    add_val(&mut vals, &runner_task.org_id),
+                    add_val(&mut vals, &runner_task.label),
+                    add_val(&mut vals, &runner_task.extension_id),
+                    add_val(&mut vals, &runner_task.operation_id),
+                    add_val(&mut vals, &runner_task.operation_parameter),
+                    add_val(&mut vals, &runner_task.extension_configuration),
+                    add_val(&mut vals, &runner_task.resource_index),
+                    add_val(&mut vals, &runner_task.status),
+                    add_val(&mut vals, &runner_task.runner_id),
+                    add_val(&mut vals, &runner_task.cancelled),
+                    add_val(&mut vals, &runner_task.pending_logs),
+                    add_val(&mut vals, &runner_task.outputs),
+                    add_val(&mut vals, &runner_task.error),
+                    add_val(&mut vals, &runner_task.polled_time),
+                    add_val(&mut vals, &runner_task.reported_time),
+                    add_val(&mut vals, &runner_task.created_time),
+                    add_val(&mut vals, &runner_task.last_modified_time),
+                ].join(",")
+            } ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn insert_runner_task_batch(&self, runner_task_list: &[RunnerTask]) -> Result<(), ErrNo> {
+        if runner_task_list.is_empty() {
+            log::warn!("待批量新增的执行器任务集合为空");
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT * runner_task_list.len());
+        let sql = format_xml::template! {
+            "insert into "{ENTITY}"(" {PROPERTIES.as_str()} ") values" {
+                runner_task_list.iter().map(|runner_task|{
+                    let trunks:Vec<String> = vec![
+                        add_val(&mut vals, &runner_task.id),
+                        add_val(&mut vals, &runner_task.org_id),
+                        add_val(&mut vals, &runner_task.label),
+                        add_val(&mut vals, &runner_task.extension_id),
+                        add_val(&mut vals, &runner_task.operation_id),
+                        add_val(&mut vals, &runner_task.operation_parameter),
+                        add_val(&mut vals, &runner_task.extension_configuration),
+                        add_val(&mut vals, &runner_task.resource_index),
+                        add_val(&mut vals, &runner_task.status),
+                        add_val(&mut vals, &runner_task.runner_id),
+                        add_val(&mut vals, &runner_task.cancelled),
+                        add_val(&mut vals, &runner_task.pending_logs),
+                        add_val(&mut vals, &runner_task.outputs),
+                        add_val(&mut vals, &runner_task.error),
+                        add_val(&mut vals, &runner_task.polled_time),
+                        add_val(&mut vals, &runner_task.reported_time),
+                        add_val(&mut vals, &runner_task.created_time),
+                        add_val(&mut vals, &runner_task.last_modified_time),
+                    ];
+                    ["(", &trunks.join(","), ")"].concat()
+                }).collect::<Vec<String>>().join(",")
+            }
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn update_runner_task_full(&self, runner_task: &RunnerTask) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(PROPERTY_COUNT);
+        let sql = format_xml::template! {
+            "update "{ENTITY}" set " {
+                [
+                    properties::ORG_ID, "=", &add_val(&mut vals, &runner_task.org_id),
+                    ",", properties::LABEL, "=", &add_val(&mut vals, &runner_task.label),
+                    ",", properties::EXTENSION_ID, "=", &add_val(&mut vals, &runner_task.extension_id),
+                    ",", properties::OPERATION_ID, "=", &add_val(&mut vals, &runner_task.operation_id),
+                    ",", properties::OPERATION_PARAMETER, "=", &add_val(&mut vals, &runner_task.operation_parameter),
+                    ",", properties::EXTENSION_CONFIGURATION, "=", &add_val(&mut vals, &runner_task.extension_configuration),
+                    ",", properties::RESOURCE_INDEX, "=", &add_val(&mut vals, &runner_task.resource_index),
+                    ",", properties::STATUS, "=", &add_val(&mut vals, &runner_task.status),
+                    ",", properties::RUNNER_ID, "=", &add_val(&mut vals, &runner_task.runner_id),
+                    ",", properties::CANCELLED, "=", &add_val(&mut vals, &runner_task.cancelled),
+                    ",", properties::PENDING_LOGS, "=", &add_val(&mut vals, &runner_task.pending_logs),
+                    ",", properties::OUTPUTS, "=", &add_val(&mut vals, &runner_task.outputs),
+                    ",", properties::ERROR, "=", &add_val(&mut vals, &runner_task.error),
+                    ",", properties::POLLED_TIME, "=", &add_val(&mut vals, &runner_task.polled_time),
+                    ",", properties::REPORTED_TIME, "=", &add_val(&mut vals, &runner_task.reported_time),
+                    ",", properties::CREATED_TIME, "=", &add_val(&mut vals, &runner_task.created_time),
+                    ",", properties::LAST_MODIFIED_TIME, "=", &add_val(&mut vals, &runner_task.last_modified_time),
+                ].concat()
+            } " where "{properties::ID}"=" {add_val(&mut vals, &runner_task.id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn update_runner_task(&self, id: Id, changes: &[RunnerTaskProperty]) -> Result<(), ErrNo> {
+        let changes: Vec<&RunnerTaskProperty> = changes
+            .iter()
+            .filter(|change| match change {
+                RunnerTaskProperty::Id(_) => false,
+                _ => true,
+            })
+            .collect();
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1 + changes.len());
+        let mut trunks: Vec<Cow<str>> = Vec::with_capacity(4 * changes.len());
+        for (index, property) in changes.iter().enumerate() {
+            if 0 < index {
+                trunks.push(",".into());
+            }
+            trunks.extend(match property {
+                RunnerTaskProperty::Id(id) => {
+                    [properties::ID.into(), "=".into(), add_val(&mut vals, id).into()]
+                },
+                RunnerTaskProperty::OrgId(org_id) => {
+                    [properties::ORG_ID.into(), "=".into(), add_val(&mut vals, org_id).into()]
+                },
+                RunnerTaskProperty::Label(label) => {
+                    [properties::LABEL.into(), "=".into(), add_val(&mut vals, label).into()]
+                },
+                RunnerTaskProperty::ExtensionId(extension_id) => {
+                    [properties::EXTENSION_ID.into(), "=".into(), add_val(&mut vals, extension_id).into()]
+                },
+                RunnerTaskProperty::OperationId(operation_id) => {
+                    [properties::OPERATION_ID.into(), "=".into(), add_val(&mut vals, operation_id).into()]
+                },
+                RunnerTaskProperty::OperationParameter(operation_parameter) => {
+                    [properties::OPERATION_PARAMETER.into(), "=".into(), add_val(&mut vals, operation_parameter).into()]
+                },
+                RunnerTaskProperty::ExtensionConfiguration(extension_configuration) => {
+                    [properties::EXTENSION_CONFIGURATION.into(), "=".into(), add_val(&mut vals, extension_configuration).into()]
+                },
+                RunnerTaskProperty::ResourceIndex(resource_index) => {
+                    [properties::RESOURCE_INDEX.into(), "=".into(), add_val(&mut vals, resource_index).into()]
+                },
+                RunnerTaskProperty::Status(status) => {
+                    [properties::STATUS.into(), "=".into(), add_val(&mut vals, status).into()]
+                },
+                RunnerTaskProperty::RunnerId(runner_id) => {
+                    [properties::RUNNER_ID.into(), "=".into(), add_val(&mut vals, runner_id).into()]
+                },
+                RunnerTaskProperty::Cancelled(cancelled) => {
+                    [properties::CANCELLED.into(), "=".into(), add_val(&mut vals, cancelled).into()]
+                },
+                RunnerTaskProperty::PendingLogs(pending_logs) => {
+                    [properties::PENDING_LOGS.into(), "=".into(), add_val(&mut vals, pending_logs).into()]
+                },
+                RunnerTaskProperty::Outputs(outputs) => {
+                    [properties::OUTPUTS.into(), "=".into(), add_val(&mut vals, outputs).into()]
+                },
+                RunnerTaskProperty::Error(error) => {
+                    [properties::ERROR.into(), "=".into(), add_val(&mut vals, error).into()]
+                },
+                RunnerTaskProperty::PolledTime(polled_time) => {
+                    [properties::POLLED_TIME.into(), "=".into(), add_val(&mut vals, polled_time).into()]
+                },
+                RunnerTaskProperty::ReportedTime(reported_time) => {
+                    [properties::REPORTED_TIME.into(), "=".into(), add_val(&mut vals, reported_time).into()]
+                },
+                RunnerTaskProperty::CreatedTime(created_time) => {
+                    [properties::CREATED_TIME.into(), "=".into(), add_val(&mut vals, created_time).into()]
+                },
+                RunnerTaskProperty::LastModifiedTime(last_modified_time) => {
+                    [properties::LAST_MODIFIED_TIME.into(), "=".into(), add_val(&mut vals, last_modified_time).into()]
+                },
+            });
+        }
+        let change_content: String = trunks.concat();
+        let sql = format_xml::template! {
+            "update "{ENTITY}" set " {change_content} " where "{properties::ID}"=" {add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn delete_runner_task(&self, id: Id) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1);
+        let sql = format_xml::template! {
+            "delete from "{ENTITY}" where "{properties::ID}"="{add_val(&mut vals, &id)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn delete_runner_task_batch(&self, ids: &[Id]) -> Result<(), ErrNo>
+    {
+        if ids.is_empty() {
+            log::warn!("待批量删除的执行器任务id集合为空");
+            return Ok(());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(ids.len());
+        let sql = format_xml::template! {
+            "delete from "{ENTITY}" where "{properties::ID}" in (" {add_vals(&mut vals, &ids)} ")"
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        self.transaction.execute(&statement, &vals).await.map_err(execute_error)?;
+        return Ok(());
+    }
+
+    pub async fn query_runner_task_count(&self, opt: &RunnerTaskOpt) -> Result<u64, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let sql = format_xml::template! {
+            "select count(1) from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let row: Row = self.transaction.query_one(&statement, &vals).await.map_err(query_error)?;
+        let count:i64 = row.get(0);
+        return Ok(count as u64);
+    }
+
+    pub async fn query_runner_task(&self, page_no: u64, page_size: u64, opt: &RunnerTaskOpt) -> Result<Vec<RunnerTask>, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let (limit, offset) = calc_sql_pagination(page_no, page_size);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+            " limit "{limit}" offset "{offset}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<RunnerTask> = rows.iter().map(extract_runner_task).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+    pub async fn query_runner_task_one(&self, opt: &RunnerTaskOpt) -> Result<Option<RunnerTask>, ErrNo> {
+        let list = self.query_runner_task(1, 1, opt).await?;
+        return Ok(list.into_iter().next());
+    }
+
+    pub async fn query_runner_task_batch(&self, opt: &RunnerTaskOpt) -> Result<Vec<RunnerTask>, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+        }.to_string();
+        let statement = self.transaction.prepare(&sql).await.map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self.transaction.query(&statement, &vals).await.map_err(query_error)?;
+        let list: Vec<RunnerTask> = rows.iter().map(extract_runner_task).collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+}
\ No newline at end of file
@@ -22,10 +22,11 @@ use crate::model::user::User;
 use crate::model::user::UserProperty;
 use crate::model::user::UserOpt;
 use crate::model::user::enums::try_i16_to_user_source;
+use crate::model::user::enums::try_i16_to_role;
 use crate::native_common;
 
 const ENTITY: &str = "\"user\"";
-const EXTRA_PROPERTIES: [&str; 6] = [properties::ORG_ID,properties::USER_SOURCE,properties::NAME,properties::AVATAR_URL,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
+const EXTRA_PROPERTIES: [&str; 7] = [properties::ORG_ID,properties::USER_SOURCE,properties::ROLE,properties::NAME,properties::AVATAR_URL,properties::CREATED_TIME,properties::LAST_MODIFIED_TIME,];
 const PROPERTY_COUNT: usize = EXTRA_PROPERTIES.len()+1;
 
 fn gen_properties() -> String {
@@ -42,6 +43,7 @@ fn extract_user(row: &Row) -> Result<User, ErrNo> {
         id: row.try_get(properties::ID).map_err(extract_data_error)?,
         org_id: row.try_get(properties::ORG_ID).map_err(extract_data_error)?,
         user_source: try_i16_to_user_source(row.try_get(properties::USER_SOURCE).map_err(extract_data_error)?).map_err(undefined_enum_value)?,
+        role: try_i16_to_role(row.try_get(properties::ROLE).map_err(extract_data_error)?).map_err(undefined_enum_value)?,
         name: row.try_get(properties::NAME).map_err(extract_data_error)?,
         avatar_url: row.try_get(properties::AVATAR_URL).map_err(extract_data_error)?,
         created_time: row.try_get(properties::CREATED_TIME).map_err(extract_data_error)?,
@@ -60,6 +62,9 @@ fn opt_to_conditions<'a>(opt: &'a UserOpt) -> Vec::<(Condition, &'a (dyn ToSql +
     if let Some(user_source) = opt.user_source.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::USER_SOURCE), operator: None}, user_source));
     }
+    if let Some(role) = opt.role.as_ref() {
+        pairs.push((Condition {field: LightString::from_static(properties::ROLE), operator: None}, role));
+    }
     if let Some(name) = opt.name.as_ref() {
         pairs.push((Condition {field: LightString::from_static(properties::NAME), operator: None}, name));
     }
@@ -121,6 +126,7 @@ impl<'a> UserBaseService<'a> {
                     add_val(&mut vals, &user.id),
                     add_val(&mut vals, &user.org_id),
                     add_val(&mut vals, &user.user_source),
+                    add_val(&mut vals, &user.role),
                     add_val(&mut vals, &user.name),
                     add_val(&mut vals, &user.avatar_url),
                     add_val(&mut vals, &user.created_time),
@@ -146,6 +152,7 @@ impl<'a> UserBaseService<'a> {
                         add_val(&mut vals, &user.id),
                         add_val(&mut vals, &user.org_id),
                         add_val(&mut vals, &user.user_source),
+                        add_val(&mut vals, &user.role),
                         add_val(&mut vals, &user.name),
                         add_val(&mut vals, &user.avatar_url),
                         add_val(&mut vals, &user.created_time),
@@ -167,6 +174,7 @@ impl<'a> UserBaseService<'a> {
                 [
                     properties::ORG_ID, "=", &add_val(&mut vals, &user.org_id),
                     ",", properties::USER_SOURCE, "=", &add_val(&mut vals, &user.user_source),
+                    ",", properties::ROLE, "=", &add_val(&mut vals, &user.role),
                     ",", properties::NAME, "=", &add_val(&mut vals, &user.name),
                     ",", properties::AVATAR_URL, "=", &add_val(&mut vals, &user.avatar_url),
                     ",", properties::CREATED_TIME, "=", &add_val(&mut vals, &user.created_time),
@@ -206,6 +214,9 @@ impl<'a> UserBaseService<'a> {
                 UserProperty::UserSource(user_source) => {
                     [properties::USER_SOURCE.into(), "=".into(), add_val(&mut vals, user_source).into()]
                 },
+                UserProperty::Role(role) => {
+                    [properties::ROLE.into(), "=".into(), add_val(&mut vals, role).into()]
+                },
                 UserProperty::Name(name) => {
                     [properties::NAME.into(), "=".into(), add_val(&mut vals, name).into()]
                 },
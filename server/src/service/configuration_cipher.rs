@@ -0,0 +1,96 @@
+use crate::native_common;
+use format_xml;
+use native_common::utils::add_val;
+use tihu::Id;
+use tihu_native::errno::execute_error;
+use tihu_native::errno::extract_data_error;
+use tihu_native::errno::prepare_statement_error;
+use tihu_native::errno::query_error;
+use tihu_native::ErrNo;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{Row, Transaction};
+
+/**
+ * 加密保存的字段，轮换主密钥时需要重新加密
+ */
+pub struct CipherColumn {
+    pub entity: &'static str, //表名
+    pub column: &'static str, //加密保存的字段
+}
+
+pub struct ConfigurationCipherService<'a> {
+    transaction: &'a Transaction<'a>,
+}
+
+impl<'a> ConfigurationCipherService<'a> {
+    pub fn new(transaction: &'a Transaction) -> ConfigurationCipherService<'a> {
+        return ConfigurationCipherService {
+            transaction: transaction,
+        };
+    }
+
+    /**
+     * 查询不是以cipher_prefix开头的密文（包括加密之前保存的明文），返回记录id和密文
+     * 锁住查到的记录，多个实例同时轮换时跳过别的实例正在处理的记录
+     */
+    pub async fn query_stale_cipher(
+        &self,
+        cipher_column: &CipherColumn,
+        cipher_prefix: &str,
+        limit: u64,
+    ) -> Result<Vec<(Id, String)>, ErrNo> {
+        let pattern = format!("{}%", cipher_prefix);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1);
+        let sql = format_xml::template! {
+            "select id,"{cipher_column.column}" from "{cipher_column.entity}
+            " where "{cipher_column.column}" is not null"
+            " and "{cipher_column.column}" not like "{add_val(&mut vals, &pattern)}
+            " order by id limit "{limit}
+            " for update skip locked"
+        }
+        .to_string();
+        let statement = self
+            .transaction
+            .prepare(&sql)
+            .await
+            .map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self
+            .transaction
+            .query(&statement, &vals)
+            .await
+            .map_err(query_error)?;
+        let list = rows
+            .iter()
+            .map(|row| -> Result<(Id, String), ErrNo> {
+                let id = row.try_get(0).map_err(extract_data_error)?;
+                let cipher = row.try_get(1).map_err(extract_data_error)?;
+                return Ok((id, cipher));
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+    pub async fn update_cipher(
+        &self,
+        cipher_column: &CipherColumn,
+        id: &Id,
+        cipher: &str,
+    ) -> Result<(), ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(2);
+        let sql = format_xml::template! {
+            "update "{cipher_column.entity}" set "{cipher_column.column}"="{add_val(&mut vals, &cipher)}
+            " where id="{add_val(&mut vals, id)}
+        }
+        .to_string();
+        let statement = self
+            .transaction
+            .prepare(&sql)
+            .await
+            .map_err(prepare_statement_error)?;
+        self.transaction
+            .execute(&statement, &vals)
+            .await
+            .map_err(execute_error)?;
+        return Ok(());
+    }
+}
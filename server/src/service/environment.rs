@@ -1,4 +1,5 @@
 use crate::log;
+use crate::model::environment::enums::try_i16_to_lock_policy;
 use crate::model::environment::properties;
 use crate::model::environment::Environment;
 use crate::model::environment::EnvironmentOpt;
@@ -12,6 +13,7 @@ use native_common::utils::add_vals;
 use native_common::utils::calc_sql_pagination;
 use native_common::utils::Condition;
 use std::borrow::Cow;
+use tihu::Id;
 use tihu::LightString;
 use tihu_native::errno::execute_error;
 use tihu_native::errno::extract_data_error;
@@ -23,10 +25,14 @@ use tokio_postgres::types::ToSql;
 use tokio_postgres::{Row, Transaction};
 
 const ENTITY: &str = "environment";
-const EXTRA_PROPERTIES: [&str; 5] = [
+const EXTRA_PROPERTIES: [&str; 9] = [
     properties::ORG_ID,
     properties::ENVIRONMENT_SCHEMA_ID,
     properties::NAME,
+    properties::LOCK_POLICY,
+    properties::RUNNER_LABEL,
+    properties::PROTECTED,
+    properties::REQUIRED_APPROVALS,
     properties::CREATED_TIME,
     properties::LAST_MODIFIED_TIME,
 ];
@@ -55,6 +61,20 @@ fn extract_environment(row: &Row) -> Result<Environment, ErrNo> {
             .try_get(properties::ENVIRONMENT_SCHEMA_ID)
             .map_err(extract_data_error)?,
         name: row.try_get(properties::NAME).map_err(extract_data_error)?,
+        lock_policy: try_i16_to_lock_policy(
+            row.try_get(properties::LOCK_POLICY)
+                .map_err(extract_data_error)?,
+        )
+        .map_err(undefined_enum_value)?,
+        runner_label: row
+            .try_get(properties::RUNNER_LABEL)
+            .map_err(extract_data_error)?,
+        protected: row
+            .try_get(properties::PROTECTED)
+            .map_err(extract_data_error)?,
+        required_approvals: row
+            .try_get(properties::REQUIRED_APPROVALS)
+            .map_err(extract_data_error)?,
         created_time: row
             .try_get(properties::CREATED_TIME)
             .map_err(extract_data_error)?,
@@ -95,6 +115,42 @@ fn opt_to_conditions<'a>(
             name,
         ));
     }
+    if let Some(lock_policy) = opt.lock_policy.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::LOCK_POLICY),
+                operator: None,
+            },
+            lock_policy,
+        ));
+    }
+    if let Some(runner_label) = opt.runner_label.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::RUNNER_LABEL),
+                operator: None,
+            },
+            runner_label,
+        ));
+    }
+    if let Some(protected) = opt.protected.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::PROTECTED),
+                operator: None,
+            },
+            protected,
+        ));
+    }
+    if let Some(required_approvals) = opt.required_approvals.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::REQUIRED_APPROVALS),
+                operator: None,
+            },
+            required_approvals,
+        ));
+    }
     if let Some(created_time) = opt.created_time.as_ref() {
         pairs.push((
             Condition {
@@ -159,4 +215,25 @@ impl<'a> EnvironmentService<'a> {
             .collect::<Result<Vec<_>, _>>()?;
         return Ok(list);
     }
+
+    //锁住环境这一行直到事务结束，同一个环境的占用和释放需要串行进行
+    pub async fn lock_environment(&self, id: &Id) -> Result<Option<Environment>, ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            " where "{properties::ID}"="{add_val(&mut vals, id)}" for update"
+        }
+        .to_string();
+        let statement = self
+            .transaction
+            .prepare(&sql)
+            .await
+            .map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self
+            .transaction
+            .query(&statement, &vals)
+            .await
+            .map_err(query_error)?;
+        return rows.first().map(extract_environment).transpose();
+    }
 }
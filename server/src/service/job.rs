@@ -1,4 +1,5 @@
 use crate::log;
+use crate::model::job::enums::try_i16_to_interrupt_policy;
 use crate::model::job::properties;
 use crate::model::job::Job;
 use crate::model::job::JobOpt;
@@ -23,11 +24,13 @@ use tokio_postgres::types::ToSql;
 use tokio_postgres::{Row, Transaction};
 
 const ENTITY: &str = "job";
-const EXTRA_PROPERTIES: [&str; 6] = [
+const EXTRA_PROPERTIES: [&str; 8] = [
     properties::ORG_ID,
     properties::ENVIRONMENT_SCHEMA_ID,
     properties::NAME,
     properties::REMARK,
+    properties::INTERRUPT_POLICY,
+    properties::PARAMETER_SCHEMA,
     properties::CREATED_TIME,
     properties::LAST_MODIFIED_TIME,
 ];
@@ -59,6 +62,14 @@ fn extract_job(row: &Row) -> Result<Job, ErrNo> {
         remark: row
             .try_get(properties::REMARK)
             .map_err(extract_data_error)?,
+        interrupt_policy: try_i16_to_interrupt_policy(
+            row.try_get(properties::INTERRUPT_POLICY)
+                .map_err(extract_data_error)?,
+        )
+        .map_err(undefined_enum_value)?,
+        parameter_schema: row
+            .try_get(properties::PARAMETER_SCHEMA)
+            .map_err(extract_data_error)?,
         created_time: row
             .try_get(properties::CREATED_TIME)
             .map_err(extract_data_error)?,
@@ -106,6 +117,24 @@ fn opt_to_conditions<'a>(opt: &'a JobOpt) -> Vec<(Condition, &'a (dyn ToSql + st
             remark,
         ));
     }
+    if let Some(interrupt_policy) = opt.interrupt_policy.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::INTERRUPT_POLICY),
+                operator: None,
+            },
+            interrupt_policy,
+        ));
+    }
+    if let Some(parameter_schema) = opt.parameter_schema.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::PARAMETER_SCHEMA),
+                operator: None,
+            },
+            parameter_schema,
+        ));
+    }
     if let Some(created_time) = opt.created_time.as_ref() {
         pairs.push((
             Condition {
@@ -1,10 +1,16 @@
 use crate::log;
+use crate::model::environment_lock::enums::Status as LockStatus;
+use crate::model::environment_lock::properties as lock_properties;
 use crate::model::job_record::enums::try_i16_to_status;
+use crate::model::job_record::enums::try_i16_to_trigger_type;
+use crate::model::job_record::enums::Status;
 use crate::model::job_record::properties;
 use crate::model::job_record::JobRecord;
 use crate::model::job_record::JobRecordOpt;
 use crate::model::job_record::JobRecordProperty;
 use crate::native_common;
+use chrono::DateTime;
+use chrono::Utc;
 use format_xml;
 use lazy_static;
 use native_common::utils::add_conditions;
@@ -13,6 +19,7 @@ use native_common::utils::add_vals;
 use native_common::utils::calc_sql_pagination;
 use native_common::utils::Condition;
 use std::borrow::Cow;
+use tihu::Id;
 use tihu::LightString;
 use tihu_native::errno::execute_error;
 use tihu_native::errno::extract_data_error;
@@ -24,15 +31,29 @@ use tokio_postgres::types::ToSql;
 use tokio_postgres::{Row, Transaction};
 
 const ENTITY: &str = "job_record";
-const EXTRA_PROPERTIES: [&str; 6] = [
+const EXTRA_PROPERTIES: [&str; 18] = [
     properties::ORG_ID,
     properties::JOB_ID,
     properties::ENVIRONMENT_ID,
+    properties::ORIGIN_RECORD_ID,
+    properties::ATTEMPT,
     properties::STATUS,
+    properties::PINNED,
+    properties::CREATED_BY,
+    properties::TRIGGER_TYPE,
+    properties::CHANGE_NOTE,
+    properties::REQUIRED_APPROVALS,
+    properties::PARAMS,
+    properties::SECRET_PARAMS,
+    properties::INSTANCE_ID,
+    properties::HEARTBEAT_TIME,
+    properties::CANCEL_REQUESTED,
     properties::CREATED_TIME,
     properties::LAST_MODIFIED_TIME,
 ];
 const PROPERTY_COUNT: usize = EXTRA_PROPERTIES.len() + 1;
+//任务队列调度用的咨询锁
+const JOB_QUEUE_LOCK_KEY: i64 = 0x6a6f_6271_7565_7565;
 
 fn gen_properties() -> String {
     let properties: Vec<&str> = [properties::ID]
@@ -59,11 +80,49 @@ fn extract_job_record(row: &Row) -> Result<JobRecord, ErrNo> {
         environment_id: row
             .try_get(properties::ENVIRONMENT_ID)
             .map_err(extract_data_error)?,
+        origin_record_id: row
+            .try_get(properties::ORIGIN_RECORD_ID)
+            .map_err(extract_data_error)?,
+        attempt: row
+            .try_get(properties::ATTEMPT)
+            .map_err(extract_data_error)?,
         status: try_i16_to_status(
             row.try_get(properties::STATUS)
                 .map_err(extract_data_error)?,
         )
         .map_err(undefined_enum_value)?,
+        pinned: row
+            .try_get(properties::PINNED)
+            .map_err(extract_data_error)?,
+        created_by: row
+            .try_get(properties::CREATED_BY)
+            .map_err(extract_data_error)?,
+        trigger_type: try_i16_to_trigger_type(
+            row.try_get(properties::TRIGGER_TYPE)
+                .map_err(extract_data_error)?,
+        )
+        .map_err(undefined_enum_value)?,
+        change_note: row
+            .try_get(properties::CHANGE_NOTE)
+            .map_err(extract_data_error)?,
+        required_approvals: row
+            .try_get(properties::REQUIRED_APPROVALS)
+            .map_err(extract_data_error)?,
+        params: row
+            .try_get(properties::PARAMS)
+            .map_err(extract_data_error)?,
+        secret_params: row
+            .try_get(properties::SECRET_PARAMS)
+            .map_err(extract_data_error)?,
+        instance_id: row
+            .try_get(properties::INSTANCE_ID)
+            .map_err(extract_data_error)?,
+        heartbeat_time: row
+            .try_get(properties::HEARTBEAT_TIME)
+            .map_err(extract_data_error)?,
+        cancel_requested: row
+            .try_get(properties::CANCEL_REQUESTED)
+            .map_err(extract_data_error)?,
         created_time: row
             .try_get(properties::CREATED_TIME)
             .map_err(extract_data_error)?,
@@ -104,6 +163,24 @@ fn opt_to_conditions<'a>(
             environment_id,
         ));
     }
+    if let Some(origin_record_id) = opt.origin_record_id.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::ORIGIN_RECORD_ID),
+                operator: None,
+            },
+            origin_record_id,
+        ));
+    }
+    if let Some(attempt) = opt.attempt.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::ATTEMPT),
+                operator: None,
+            },
+            attempt,
+        ));
+    }
     if let Some(status) = opt.status.as_ref() {
         pairs.push((
             Condition {
@@ -113,6 +190,69 @@ fn opt_to_conditions<'a>(
             status,
         ));
     }
+    if let Some(pinned) = opt.pinned.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::PINNED),
+                operator: None,
+            },
+            pinned,
+        ));
+    }
+    if let Some(created_by) = opt.created_by.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::CREATED_BY),
+                operator: None,
+            },
+            created_by,
+        ));
+    }
+    if let Some(trigger_type) = opt.trigger_type.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::TRIGGER_TYPE),
+                operator: None,
+            },
+            trigger_type,
+        ));
+    }
+    if let Some(change_note) = opt.change_note.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::CHANGE_NOTE),
+                operator: None,
+            },
+            change_note,
+        ));
+    }
+    if let Some(required_approvals) = opt.required_approvals.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::REQUIRED_APPROVALS),
+                operator: None,
+            },
+            required_approvals,
+        ));
+    }
+    if let Some(instance_id) = opt.instance_id.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::INSTANCE_ID),
+                operator: None,
+            },
+            instance_id,
+        ));
+    }
+    if let Some(heartbeat_time) = opt.heartbeat_time.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::HEARTBEAT_TIME),
+                operator: None,
+            },
+            heartbeat_time,
+        ));
+    }
     if let Some(created_time) = opt.created_time.as_ref() {
         pairs.push((
             Condition {
@@ -177,4 +317,429 @@ impl<'a> JobRecordService<'a> {
             .collect::<Result<Vec<_>, _>>()?;
         return Ok(list);
     }
+
+    /**
+     * 查询同一次执行的所有尝试（最初的执行记录以及重新执行产生的记录），按照第几次执行排序
+     */
+    pub async fn query_job_record_attempts(
+        &self,
+        org_id: &Id,
+        origin_record_id: &Id,
+    ) -> Result<Vec<JobRecord>, ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(3);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            " where "{properties::ORG_ID}"="{add_val(&mut vals, org_id)}
+            " and ("{properties::ID}"="{add_val(&mut vals, origin_record_id)}
+            " or "{properties::ORIGIN_RECORD_ID}"="{add_val(&mut vals, origin_record_id)}")"
+            " order by "{properties::ATTEMPT}
+        }
+        .to_string();
+        let statement = self
+            .transaction
+            .prepare(&sql)
+            .await
+            .map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self
+            .transaction
+            .query(&statement, &vals)
+            .await
+            .map_err(query_error)?;
+        let list: Vec<JobRecord> = rows
+            .iter()
+            .map(extract_job_record)
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+    /**
+     * 加上事务级的咨询锁，多个实例的调度依次检查组织的执行数并开始执行，避免同时调度时超出组织的执行上限
+     * 事务结束时自动释放
+     */
+    pub async fn lock_job_queue(&self) -> Result<(), ErrNo> {
+        let lock_key = JOB_QUEUE_LOCK_KEY;
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1);
+        let sql = format_xml::template! {
+            "select pg_advisory_xact_lock("{add_val(&mut vals, &lock_key)}")"
+        }
+        .to_string();
+        let statement = self
+            .transaction
+            .prepare(&sql)
+            .await
+            .map_err(prepare_statement_error)?;
+        self.transaction
+            .execute(&statement, &vals)
+            .await
+            .map_err(execute_error)?;
+        return Ok(());
+    }
+
+    /**
+     * 查询已经占用到环境、可以开始执行的排队记录，按照排队的先后顺序
+     * 锁住查到的记录，多个实例同时调度时跳过别的实例正在处理的记录
+     */
+    pub async fn query_dispatchable_job_record(&self, limit: u64) -> Result<Vec<JobRecord>, ErrNo> {
+        let status = Status::Queued;
+        let lock_status = LockStatus::Holding;
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(2);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            " where "{properties::STATUS}"="{add_val(&mut vals, &status)}
+            " and "{properties::ID}" in (select "{lock_properties::RECORD_ID}" from environment_lock"
+            " where "{lock_properties::STATUS}"="{add_val(&mut vals, &lock_status)}")"
+            " order by "{properties::CREATED_TIME}" limit "{limit}
+            " for update skip locked"
+        }
+        .to_string();
+        let statement = self
+            .transaction
+            .prepare(&sql)
+            .await
+            .map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self
+            .transaction
+            .query(&statement, &vals)
+            .await
+            .map_err(query_error)?;
+        let list: Vec<JobRecord> = rows
+            .iter()
+            .map(extract_job_record)
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+    /**
+     * 查询执行实例超过expire_before没有续约的执行中记录，执行实例可能已经退出
+     * 锁住查到的记录，多个实例同时接手时跳过别的实例正在处理的记录
+     */
+    pub async fn query_lease_expired_job_record(
+        &self,
+        expire_before: &DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<JobRecord>, ErrNo> {
+        let status = Status::Running;
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(2);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            " where "{properties::STATUS}"="{add_val(&mut vals, &status)}
+            " and "{properties::INSTANCE_ID}" is not null"
+            " and "{properties::HEARTBEAT_TIME}"<"{add_val(&mut vals, expire_before)}
+            " order by "{properties::HEARTBEAT_TIME}" limit "{limit}
+            " for update skip locked"
+        }
+        .to_string();
+        let statement = self
+            .transaction
+            .prepare(&sql)
+            .await
+            .map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self
+            .transaction
+            .query(&statement, &vals)
+            .await
+            .map_err(query_error)?;
+        let list: Vec<JobRecord> = rows
+            .iter()
+            .map(extract_job_record)
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+    /**
+     * 为本实例正在执行的记录续约，返回续约成功的记录id以及是否请求了取消，已经被其它实例接手的记录不会续约
+     */
+    pub async fn renew_job_record_lease(
+        &self,
+        instance_id: &Id,
+        record_ids: &[Id],
+        heartbeat_time: &DateTime<Utc>,
+    ) -> Result<Vec<(Id, bool)>, ErrNo> {
+        if record_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> =
+            Vec::with_capacity(2 + record_ids.len());
+        let sql = format_xml::template! {
+            "update "{ENTITY}" set "{properties::HEARTBEAT_TIME}"="{add_val(&mut vals, heartbeat_time)}
+            " where "{properties::INSTANCE_ID}"="{add_val(&mut vals, instance_id)}
+            " and "{properties::ID}" in ("{add_vals(&mut vals, record_ids)}")"
+            " returning "{properties::ID}","{properties::CANCEL_REQUESTED}
+        }
+        .to_string();
+        let statement = self
+            .transaction
+            .prepare(&sql)
+            .await
+            .map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self
+            .transaction
+            .query(&statement, &vals)
+            .await
+            .map_err(query_error)?;
+        let renewed_list = rows
+            .iter()
+            .map(|row| -> Result<(Id, bool), ErrNo> {
+                let id = row.try_get(properties::ID).map_err(extract_data_error)?;
+                let cancel_requested = row
+                    .try_get(properties::CANCEL_REQUESTED)
+                    .map_err(extract_data_error)?;
+                return Ok((id, cancel_requested));
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(renewed_list);
+    }
+
+    /**
+     * 查询org_id组织下record_ids中排队记录在组织队列里的位置，从1开始
+     */
+    pub async fn query_queue_positions(
+        &self,
+        org_id: &Id,
+        record_ids: &[Id],
+    ) -> Result<Vec<(Id, u64)>, ErrNo> {
+        if record_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let status = Status::Queued;
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> =
+            Vec::with_capacity(4 + record_ids.len());
+        let sql = format_xml::template! {
+            "select r."{properties::ID}", (select count(1) from "{ENTITY}" q"
+            " where q."{properties::ORG_ID}"="{add_val(&mut vals, org_id)}
+            " and q."{properties::STATUS}"="{add_val(&mut vals, &status)}
+            " and q."{properties::CREATED_TIME}"<=r."{properties::CREATED_TIME}") as position"
+            " from "{ENTITY}" r"
+            " where r."{properties::ORG_ID}"="{add_val(&mut vals, org_id)}
+            " and r."{properties::STATUS}"="{add_val(&mut vals, &status)}
+            " and r."{properties::ID}" in ("{add_vals(&mut vals, record_ids)}")"
+        }
+        .to_string();
+        let statement = self
+            .transaction
+            .prepare(&sql)
+            .await
+            .map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self
+            .transaction
+            .query(&statement, &vals)
+            .await
+            .map_err(query_error)?;
+        let positions = rows
+            .iter()
+            .map(|row| -> Result<(Id, u64), ErrNo> {
+                let id = row.try_get(properties::ID).map_err(extract_data_error)?;
+                let position: i64 = row.try_get("position").map_err(extract_data_error)?;
+                return Ok((id, position as u64));
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(positions);
+    }
+
+    /**
+     * 查询instance_id实例正在执行的记录数，等待人工处理的记录已经交出执行实例，不算在内
+     */
+    pub async fn query_instance_running_count(&self, instance_id: &Id) -> Result<u64, ErrNo> {
+        let status = Status::Running;
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(2);
+        let sql = format_xml::template! {
+            "select count(1) from "{ENTITY}
+            " where "{properties::STATUS}"="{add_val(&mut vals, &status)}
+            " and "{properties::INSTANCE_ID}"="{add_val(&mut vals, instance_id)}
+        }
+        .to_string();
+        let statement = self
+            .transaction
+            .prepare(&sql)
+            .await
+            .map_err(prepare_statement_error)?;
+        let row: Row = self
+            .transaction
+            .query_one(&statement, &vals)
+            .await
+            .map_err(query_error)?;
+        let count: i64 = row.get(0);
+        return Ok(count as u64);
+    }
+
+    /**
+     * 查询所有实例上各个组织正在执行的记录数，等待人工处理的记录已经交出执行实例，不算在内
+     */
+    pub async fn query_org_running_counts(&self) -> Result<Vec<(Id, u64)>, ErrNo> {
+        let status = Status::Running;
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1);
+        let sql = format_xml::template! {
+            "select "{properties::ORG_ID}", count(1) from "{ENTITY}
+            " where "{properties::STATUS}"="{add_val(&mut vals, &status)}
+            " and "{properties::INSTANCE_ID}" is not null"
+            " group by "{properties::ORG_ID}
+        }
+        .to_string();
+        let statement = self
+            .transaction
+            .prepare(&sql)
+            .await
+            .map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self
+            .transaction
+            .query(&statement, &vals)
+            .await
+            .map_err(query_error)?;
+        let counts = rows
+            .iter()
+            .map(|row| -> Result<(Id, u64), ErrNo> {
+                let org_id = row
+                    .try_get(properties::ORG_ID)
+                    .map_err(extract_data_error)?;
+                let count: i64 = row.try_get(1).map_err(extract_data_error)?;
+                return Ok((org_id, count as u64));
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(counts);
+    }
+
+    /**
+     * 查询created_before之前发起、到现在还没有审批完的执行记录id
+     */
+    pub async fn query_stale_pending_approval_ids(
+        &self,
+        created_before: &DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<Id>, ErrNo> {
+        let status = Status::PendingApproval;
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(2);
+        let sql = format_xml::template! {
+            "select "{properties::ID}" from "{ENTITY}
+            " where "{properties::STATUS}"="{add_val(&mut vals, &status)}
+            " and "{properties::CREATED_TIME}"<"{add_val(&mut vals, created_before)}
+            " order by "{properties::CREATED_TIME}
+            " limit "{limit}
+        }
+        .to_string();
+        let statement = self
+            .transaction
+            .prepare(&sql)
+            .await
+            .map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self
+            .transaction
+            .query(&statement, &vals)
+            .await
+            .map_err(query_error)?;
+        let ids: Vec<Id> = rows
+            .iter()
+            .map(|row| row.try_get(properties::ID).map_err(extract_data_error))
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(ids);
+    }
+
+    /**
+     * 查询组织里已经过期、可以清理的执行记录id，按照创建时间从早到晚
+     * 每个任务在每个环境下最近的retention_count条记录，以及expire_before之后创建的记录都会保留
+     * 置顶的记录以及还在执行、排队或者等待审批的记录不会被清理
+     */
+    pub async fn query_expired_job_record_ids(
+        &self,
+        org_id: &Id,
+        retention_count: Option<&i64>,
+        expire_before: Option<&DateTime<Utc>>,
+        limit: u64,
+    ) -> Result<Vec<Id>, ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(7);
+        let org_id_val = add_val(&mut vals, org_id);
+        let conditions = expired_job_record_conditions(&mut vals, retention_count, expire_before);
+        let sql = format_xml::template! {
+            "select "{properties::ID}" from (select "{properties::ID}","{properties::STATUS}","{properties::PINNED}","{properties::CREATED_TIME}","
+            " row_number() over (partition by "{properties::JOB_ID}","{properties::ENVIRONMENT_ID}" order by "{properties::CREATED_TIME}" desc) as row_no"
+            " from "{ENTITY}" where "{properties::ORG_ID}"="{org_id_val}") t"
+            " where "{conditions.join(" and ")}
+            " order by "{properties::CREATED_TIME}" limit "{limit}
+        }
+        .to_string();
+        let statement = self
+            .transaction
+            .prepare(&sql)
+            .await
+            .map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self
+            .transaction
+            .query(&statement, &vals)
+            .await
+            .map_err(query_error)?;
+        let ids: Vec<Id> = rows
+            .iter()
+            .map(|row| row.try_get(properties::ID).map_err(extract_data_error))
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(ids);
+    }
+}
+
+//清理执行记录的过滤条件，置顶的记录以及还在执行、排队或者等待审批的记录总是排除在外
+fn expired_job_record_conditions<'a>(
+    vals: &mut Vec<&'a (dyn ToSql + std::marker::Sync)>,
+    retention_count: Option<&'a i64>,
+    expire_before: Option<&'a DateTime<Utc>>,
+) -> Vec<String> {
+    let mut conditions = vec![
+        format!("{}={}", properties::PINNED, add_val(vals, &false)),
+        format!(
+            "{}<>{}",
+            properties::STATUS,
+            add_val(vals, &Status::Running)
+        ),
+        format!("{}<>{}", properties::STATUS, add_val(vals, &Status::Queued)),
+        format!(
+            "{}<>{}",
+            properties::STATUS,
+            add_val(vals, &Status::PendingApproval)
+        ),
+    ];
+    if let Some(retention_count) = retention_count {
+        conditions.push(format!("row_no>{}", add_val(vals, retention_count)));
+    }
+    if let Some(expire_before) = expire_before {
+        conditions.push(format!(
+            "{}<{}",
+            properties::CREATED_TIME,
+            add_val(vals, expire_before)
+        ));
+    }
+    return conditions;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expired_job_record_conditions() {
+        //没有设置保留条数和保留天数时，也只清理没有置顶并且已经结束的记录
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::new();
+        let conditions = expired_job_record_conditions(&mut vals, None, None);
+        assert_eq!(
+            vec!["pinned=$1", "status<>$2", "status<>$3", "status<>$4"],
+            conditions
+        );
+        assert_eq!(4, vals.len());
+        let retention_count: i64 = 10;
+        let expire_before = Utc::now();
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::new();
+        let conditions =
+            expired_job_record_conditions(&mut vals, Some(&retention_count), Some(&expire_before));
+        assert_eq!(
+            vec![
+                "pinned=$1",
+                "status<>$2",
+                "status<>$3",
+                "status<>$4",
+                "row_no>$5",
+                "created_time<$6"
+            ],
+            conditions
+        );
+        assert_eq!(6, vals.len());
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::new();
+        let conditions = expired_job_record_conditions(&mut vals, None, Some(&expire_before));
+        assert_eq!(Some(&String::from("created_time<$5")), conditions.last());
+    }
 }
@@ -0,0 +1,216 @@
+use crate::model::job_schedule::enums::try_i16_to_status;
+use crate::model::job_schedule::enums::Status;
+use crate::model::job_schedule::properties;
+use crate::model::job_schedule::JobSchedule;
+use crate::model::job_schedule::JobScheduleOpt;
+use crate::native_common;
+use chrono::DateTime;
+use chrono::Utc;
+use format_xml;
+use lazy_static;
+use native_common::utils::add_conditions;
+use native_common::utils::add_val;
+use native_common::utils::calc_sql_pagination;
+use native_common::utils::Condition;
+use tihu::LightString;
+use tihu_native::errno::extract_data_error;
+use tihu_native::errno::prepare_statement_error;
+use tihu_native::errno::query_error;
+use tihu_native::errno::undefined_enum_value;
+use tihu_native::ErrNo;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{Row, Transaction};
+
+const ENTITY: &str = "job_schedule";
+const EXTRA_PROPERTIES: [&str; 13] = [
+    properties::ORG_ID,
+    properties::JOB_ID,
+    properties::ENVIRONMENT_ID,
+    properties::NAME,
+    properties::CRON_EXPRESSION,
+    properties::PARAMS,
+    properties::STATUS,
+    properties::NEXT_FIRE_TIME,
+    properties::LAST_FIRE_TIME,
+    properties::LAST_RECORD_ID,
+    properties::CREATED_BY,
+    properties::CREATED_TIME,
+    properties::LAST_MODIFIED_TIME,
+];
+
+fn gen_properties() -> String {
+    let properties: Vec<&str> = [properties::ID]
+        .iter()
+        .chain(EXTRA_PROPERTIES.iter())
+        .map(|item| *item)
+        .collect();
+    return properties.join(",");
+}
+
+lazy_static::lazy_static! {
+    static ref PROPERTIES: String = gen_properties();
+}
+
+fn extract_job_schedule(row: &Row) -> Result<JobSchedule, ErrNo> {
+    return Ok(JobSchedule {
+        id: row.try_get(properties::ID).map_err(extract_data_error)?,
+        org_id: row
+            .try_get(properties::ORG_ID)
+            .map_err(extract_data_error)?,
+        job_id: row
+            .try_get(properties::JOB_ID)
+            .map_err(extract_data_error)?,
+        environment_id: row
+            .try_get(properties::ENVIRONMENT_ID)
+            .map_err(extract_data_error)?,
+        name: row.try_get(properties::NAME).map_err(extract_data_error)?,
+        cron_expression: row
+            .try_get(properties::CRON_EXPRESSION)
+            .map_err(extract_data_error)?,
+        params: row
+            .try_get(properties::PARAMS)
+            .map_err(extract_data_error)?,
+        status: try_i16_to_status(
+            row.try_get(properties::STATUS)
+                .map_err(extract_data_error)?,
+        )
+        .map_err(undefined_enum_value)?,
+        next_fire_time: row
+            .try_get(properties::NEXT_FIRE_TIME)
+            .map_err(extract_data_error)?,
+        last_fire_time: row
+            .try_get(properties::LAST_FIRE_TIME)
+            .map_err(extract_data_error)?,
+        last_record_id: row
+            .try_get(properties::LAST_RECORD_ID)
+            .map_err(extract_data_error)?,
+        created_by: row
+            .try_get(properties::CREATED_BY)
+            .map_err(extract_data_error)?,
+        created_time: row
+            .try_get(properties::CREATED_TIME)
+            .map_err(extract_data_error)?,
+        last_modified_time: row
+            .try_get(properties::LAST_MODIFIED_TIME)
+            .map_err(extract_data_error)?,
+    });
+}
+
+fn opt_to_conditions<'a>(
+    opt: &'a JobScheduleOpt,
+) -> Vec<(Condition, &'a (dyn ToSql + std::marker::Sync))> {
+    let mut pairs = Vec::<(Condition, &(dyn ToSql + std::marker::Sync))>::new();
+    if let Some(org_id) = opt.org_id.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::ORG_ID),
+                operator: None,
+            },
+            org_id,
+        ));
+    }
+    if let Some(job_id) = opt.job_id.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::JOB_ID),
+                operator: None,
+            },
+            job_id,
+        ));
+    }
+    if let Some(environment_id) = opt.environment_id.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::ENVIRONMENT_ID),
+                operator: None,
+            },
+            environment_id,
+        ));
+    }
+    if let Some(status) = opt.status.as_ref() {
+        pairs.push((
+            Condition {
+                field: LightString::from_static(properties::STATUS),
+                operator: None,
+            },
+            status,
+        ));
+    }
+    return pairs;
+}
+
+pub struct JobScheduleService<'a> {
+    transaction: &'a Transaction<'a>,
+}
+
+impl<'a> JobScheduleService<'a> {
+    pub fn new(transaction: &'a Transaction) -> JobScheduleService<'a> {
+        return JobScheduleService {
+            transaction: transaction,
+        };
+    }
+
+    pub async fn query_job_schedule(
+        &self,
+        page_no: u64,
+        page_size: u64,
+        opt: &JobScheduleOpt,
+    ) -> Result<Vec<JobSchedule>, ErrNo> {
+        let pairs = opt_to_conditions(opt);
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(pairs.len());
+        let (limit, offset) = calc_sql_pagination(page_no, page_size);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            {if pairs.is_empty() {""} else {" where "}}
+            {add_conditions(&mut vals, &pairs)}
+            " order by "{properties::CREATED_TIME}" desc limit "{limit}" offset "{offset}
+        }
+        .to_string();
+        let statement = self
+            .transaction
+            .prepare(&sql)
+            .await
+            .map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self
+            .transaction
+            .query(&statement, &vals)
+            .await
+            .map_err(query_error)?;
+        let list: Vec<JobSchedule> = rows
+            .iter()
+            .map(extract_job_schedule)
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+
+    //查询已启用并且到了触发时间的计划
+    pub async fn query_due_job_schedule(
+        &self,
+        curr_time: &DateTime<Utc>,
+    ) -> Result<Vec<JobSchedule>, ErrNo> {
+        let status = Status::Enabled;
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(2);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            " where "{properties::STATUS}"="{add_val(&mut vals, &status)}
+            " and "{properties::NEXT_FIRE_TIME}"<="{add_val(&mut vals, curr_time)}
+            " order by "{properties::NEXT_FIRE_TIME}
+        }
+        .to_string();
+        let statement = self
+            .transaction
+            .prepare(&sql)
+            .await
+            .map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self
+            .transaction
+            .query(&statement, &vals)
+            .await
+            .map_err(query_error)?;
+        let list: Vec<JobSchedule> = rows
+            .iter()
+            .map(extract_job_schedule)
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(list);
+    }
+}
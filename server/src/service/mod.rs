@@ -1,6 +1,9 @@
 pub mod base;
+pub mod configuration_cipher;
 pub mod environment;
 pub mod environment_schema;
 pub mod external_user;
 pub mod job;
 pub mod job_record;
+pub mod job_schedule;
+pub mod runner_task;
@@ -0,0 +1,188 @@
+use crate::model::runner_task::enums::try_i16_to_status;
+use crate::model::runner_task::enums::Status;
+use crate::model::runner_task::properties;
+use crate::model::runner_task::RunnerTask;
+use crate::native_common;
+use chrono::DateTime;
+use chrono::Utc;
+use format_xml;
+use lazy_static;
+use native_common::utils::add_val;
+use tihu::Id;
+use tihu_native::errno::extract_data_error;
+use tihu_native::errno::prepare_statement_error;
+use tihu_native::errno::query_error;
+use tihu_native::errno::undefined_enum_value;
+use tihu_native::ErrNo;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{Row, Transaction};
+
+const ENTITY: &str = "runner_task";
+const EXTRA_PROPERTIES: [&str; 17] = [
+    properties::ORG_ID,
+    properties::LABEL,
+    properties::EXTENSION_ID,
+    properties::OPERATION_ID,
+    properties::OPERATION_PARAMETER,
+    properties::EXTENSION_CONFIGURATION,
+    properties::RESOURCE_INDEX,
+    properties::STATUS,
+    properties::RUNNER_ID,
+    properties::CANCELLED,
+    properties::PENDING_LOGS,
+    properties::OUTPUTS,
+    properties::ERROR,
+    properties::POLLED_TIME,
+    properties::REPORTED_TIME,
+    properties::CREATED_TIME,
+    properties::LAST_MODIFIED_TIME,
+];
+
+fn gen_properties() -> String {
+    let properties: Vec<&str> = [properties::ID]
+        .iter()
+        .chain(EXTRA_PROPERTIES.iter())
+        .map(|item| *item)
+        .collect();
+    return properties.join(",");
+}
+
+lazy_static::lazy_static! {
+    static ref PROPERTIES: String = gen_properties();
+}
+
+fn extract_runner_task(row: &Row) -> Result<RunnerTask, ErrNo> {
+    return Ok(RunnerTask {
+        id: row.try_get(properties::ID).map_err(extract_data_error)?,
+        org_id: row
+            .try_get(properties::ORG_ID)
+            .map_err(extract_data_error)?,
+        label: row.try_get(properties::LABEL).map_err(extract_data_error)?,
+        extension_id: row
+            .try_get(properties::EXTENSION_ID)
+            .map_err(extract_data_error)?,
+        operation_id: row
+            .try_get(properties::OPERATION_ID)
+            .map_err(extract_data_error)?,
+        operation_parameter: row
+            .try_get(properties::OPERATION_PARAMETER)
+            .map_err(extract_data_error)?,
+        extension_configuration: row
+            .try_get(properties::EXTENSION_CONFIGURATION)
+            .map_err(extract_data_error)?,
+        resource_index: row
+            .try_get(properties::RESOURCE_INDEX)
+            .map_err(extract_data_error)?,
+        status: try_i16_to_status(
+            row.try_get(properties::STATUS)
+                .map_err(extract_data_error)?,
+        )
+        .map_err(undefined_enum_value)?,
+        runner_id: row
+            .try_get(properties::RUNNER_ID)
+            .map_err(extract_data_error)?,
+        cancelled: row
+            .try_get(properties::CANCELLED)
+            .map_err(extract_data_error)?,
+        pending_logs: row
+            .try_get(properties::PENDING_LOGS)
+            .map_err(extract_data_error)?,
+        outputs: row
+            .try_get(properties::OUTPUTS)
+            .map_err(extract_data_error)?,
+        error: row.try_get(properties::ERROR).map_err(extract_data_error)?,
+        polled_time: row
+            .try_get(properties::POLLED_TIME)
+            .map_err(extract_data_error)?,
+        reported_time: row
+            .try_get(properties::REPORTED_TIME)
+            .map_err(extract_data_error)?,
+        created_time: row
+            .try_get(properties::CREATED_TIME)
+            .map_err(extract_data_error)?,
+        last_modified_time: row
+            .try_get(properties::LAST_MODIFIED_TIME)
+            .map_err(extract_data_error)?,
+    });
+}
+
+pub struct RunnerTaskService<'a> {
+    transaction: &'a Transaction<'a>,
+}
+
+impl<'a> RunnerTaskService<'a> {
+    pub fn new(transaction: &'a Transaction) -> RunnerTaskService<'a> {
+        return RunnerTaskService {
+            transaction: transaction,
+        };
+    }
+
+    /**
+     * 查询最早的一个同组织同标签、还没有被领取也没有被取消的任务，发起任务的实例在polled_after之后还检查过
+     * 锁住查到的任务，多个执行器同时拉取时跳过别的执行器正在领取的任务
+     */
+    pub async fn query_claimable_runner_task(
+        &self,
+        org_id: &Id,
+        label: &str,
+        polled_after: &DateTime<Utc>,
+    ) -> Result<Option<RunnerTask>, ErrNo> {
+        let status = Status::Pending;
+        let cancelled = false;
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(5);
+        let sql = format_xml::template! {
+            "select "{PROPERTIES.as_str()}" from "{ENTITY}
+            " where "{properties::ORG_ID}"="{add_val(&mut vals, org_id)}
+            " and "{properties::LABEL}"="{add_val(&mut vals, &label)}
+            " and "{properties::STATUS}"="{add_val(&mut vals, &status)}
+            " and "{properties::CANCELLED}"="{add_val(&mut vals, &cancelled)}
+            " and "{properties::POLLED_TIME}">"{add_val(&mut vals, polled_after)}
+            " order by "{properties::CREATED_TIME}" limit 1"
+            " for update skip locked"
+        }
+        .to_string();
+        let statement = self
+            .transaction
+            .prepare(&sql)
+            .await
+            .map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self
+            .transaction
+            .query(&statement, &vals)
+            .await
+            .map_err(query_error)?;
+        return rows.first().map(extract_runner_task).transpose();
+    }
+
+    /**
+     * 查询发起任务的实例在polled_before之前就不再检查的任务id，发起任务的实例已经退出，任务没有人清理
+     */
+    pub async fn query_abandoned_runner_task_ids(
+        &self,
+        polled_before: &DateTime<Utc>,
+        limit: u64,
+    ) -> Result<Vec<Id>, ErrNo> {
+        let mut vals: Vec<&(dyn ToSql + std::marker::Sync)> = Vec::with_capacity(1);
+        let sql = format_xml::template! {
+            "select "{properties::ID}" from "{ENTITY}
+            " where "{properties::POLLED_TIME}"<"{add_val(&mut vals, polled_before)}
+            " order by "{properties::POLLED_TIME}" limit "{limit}
+        }
+        .to_string();
+        let statement = self
+            .transaction
+            .prepare(&sql)
+            .await
+            .map_err(prepare_statement_error)?;
+        let rows: Vec<Row> = self
+            .transaction
+            .query(&statement, &vals)
+            .await
+            .map_err(query_error)?;
+        let ids: Vec<Id> = rows
+            .iter()
+            .map(|row| row.try_get(properties::ID).map_err(extract_data_error))
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(ids);
+    }
+}
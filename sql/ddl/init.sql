@@ -4,6 +4,7 @@ create table "user"
     id uuid not null primary key,
     org_id uuid, --组织id
     user_source smallint not null, --用户来源
+    role smallint not null, --组织内的角色
     name varchar(128) not null, --名称
     avatar_url text, --头像
     created_time timestamptz not null,
@@ -38,6 +39,10 @@ create table organization
 (
     id uuid not null primary key,
     name varchar(128) not null, --名称
+    retention_count int4, --每个任务在每个环境下保留最近的执行记录条数
+    retention_days int4, --保留最近多少天的执行记录
+    last_purge_time timestamptz, --最后一次清理执行记录的时间
+    last_purge_report text, --最后一次清理执行记录的结果，json格式
     created_time timestamptz not null,
     last_modified_time timestamptz not null
 );
@@ -72,6 +77,37 @@ create table environment
     org_id uuid not null, --组织id
     environment_schema_id uuid not null, --环境规格id
     name varchar(128) not null, --环境名称
+    lock_policy smallint not null, --环境被占用时的处理方式
+    runner_label varchar(64), --执行器标签，为空时在服务端执行
+    protected boolean not null, --是否受保护，受保护的环境只有单独授权的用户才能执行任务、修改资源和审批
+    required_approvals int4 not null, --执行前需要的审批人数，只对受保护的环境生效，0表示不需要审批
+    created_time timestamptz not null,
+    last_modified_time timestamptz not null
+);
+
+--环境授权
+create table environment_grant
+(
+    id uuid not null primary key,
+    org_id uuid not null, --组织id
+    environment_id uuid not null, --环境id
+    user_id uuid not null, --被授权的用户id
+    permission smallint not null, --授予的权限
+    created_time timestamptz not null,
+    last_modified_time timestamptz not null
+);
+
+--环境占用
+create table environment_lock
+(
+    id uuid not null primary key,
+    org_id uuid not null, --组织id
+    environment_id uuid not null, --环境id
+    record_id uuid not null, --任务执行记录id
+    job_id uuid not null, --任务id
+    holder_id uuid, --发起执行的用户id
+    holder_name varchar(128) not null, --发起执行的人
+    status smallint not null, --占用状态
     created_time timestamptz not null,
     last_modified_time timestamptz not null
 );
@@ -99,6 +135,8 @@ create table job
     environment_schema_id uuid not null, --环境规格id
     name varchar(128) not null, --任务名称
     remark text, --备注
+    interrupt_policy smallint not null, --中断策略
+    parameter_schema text not null, --任务参数定义
     created_time timestamptz not null,
     last_modified_time timestamptz not null
 );
@@ -116,8 +154,21 @@ create table job_step
     operation_name varchar(512) not null, --操作名称
     operation_parameter text not null, --操作参数
     attachments text, --附件
+    signoff_role smallint, --可以签收的最低角色，为空表示不按角色限制
+    signoff_users text, --可以签收的用户id列表，json格式
+    required_signoffs int4 not null, --需要的签收人数
     remark text, --备注
+    timeout_seconds int4, --超时时间（秒）
+    max_retries int4 not null, --最大重试次数
+    retry_backoff int4 not null, --重试间隔（秒）
+    max_parallel int4, --最大并发数
+    batch_size int4, --每批资源数
+    pause_between_batches int4 not null, --批次间隔（秒）
+    failure_policy smallint not null, --失败策略
+    run_if smallint not null, --执行条件
+    run_expression varchar(512), --条件表达式
     seq int4 not null, --执行顺序
+    depends_on text, --依赖的步骤
     created_time timestamptz not null,
     last_modified_time timestamptz not null
 );
@@ -129,7 +180,32 @@ create table job_record
     org_id uuid not null, --组织id
     job_id uuid not null, --任务id
     environment_id uuid not null, --环境id
+    origin_record_id uuid, --最初的执行记录id，重新执行产生的记录才有
+    attempt int4 not null, --第几次执行，从1开始
     status smallint not null, --执行状态
+    pinned boolean not null, --是否置顶，置顶的记录不会被清理
+    created_by uuid, --发起执行的用户id，定时触发时为空
+    trigger_type smallint not null, --触发方式
+    change_note text, --变更说明
+    required_approvals int4, --需要的审批人数，不需要审批时为空
+    params text, --执行参数，密码类型的参数替换成掩码
+    secret_params text, --加密保存的密码类型的参数
+    instance_id uuid, --正在执行的服务实例id
+    heartbeat_time timestamptz, --执行实例最后一次续约的时间
+    cancel_requested boolean not null, --是否请求了取消，由正在执行的实例取消
+    created_time timestamptz not null,
+    last_modified_time timestamptz not null
+);
+
+--执行记录审批
+create table job_record_approval
+(
+    id uuid not null primary key,
+    org_id uuid not null, --组织id
+    record_id uuid not null, --执行记录id
+    approver_id uuid not null, --审批人id
+    decision smallint not null, --审批结果
+    comment text, --审批意见
     created_time timestamptz not null,
     last_modified_time timestamptz not null
 );
@@ -150,9 +226,25 @@ create table job_step_record
     operation_id varchar(512) not null, --操作id
     operation_name varchar(512) not null, --操作名称
     operation_parameter text not null, --操作参数
+    rendered_parameter text, --渲染之后的操作参数，密码类型的参数是掩码
     attachments text, --附件
+    signoff_role smallint, --可以签收的最低角色，为空表示不按角色限制
+    signoff_users text, --可以签收的用户id列表，json格式
+    required_signoffs int4 not null, --需要的签收人数
+    timeout_seconds int4, --超时时间（秒）
+    max_retries int4 not null, --最大重试次数
+    retry_backoff int4 not null, --重试间隔（秒）
+    max_parallel int4, --最大并发数
+    batch_size int4, --每批资源数
+    pause_between_batches int4 not null, --批次间隔（秒）
+    failure_policy smallint not null, --失败策略
+    run_if smallint not null, --执行条件
+    run_expression varchar(512), --条件表达式
+    skip_reason varchar(512), --跳过原因
     job_step_seq int4 not null, --任务步骤顺序
+    depends_on text, --依赖的步骤
     status smallint not null, --执行状态
+    completed_by uuid, --处理手动步骤的用户id
     created_time timestamptz not null,
     last_modified_time timestamptz not null
 );
@@ -171,7 +263,101 @@ create table job_step_resource_record
     extension_configuration text not null, --扩展配置
     output_file varchar(256), --日志文件
     output_content text, --日志内容
+    log_key varchar(256), --日志在对象存储里的key，gzip压缩的json数组
+    log_summary text, --日志摘要，json格式
+    outputs text, --步骤输出
+    attempts int4 not null, --执行次数
     status smallint not null, --执行状态
     created_time timestamptz not null,
     last_modified_time timestamptz not null
 );
+
+--手动步骤签收
+create table job_step_signoff
+(
+    id uuid not null primary key,
+    org_id uuid not null, --组织id
+    record_id uuid not null, --执行记录id
+    job_step_record_id uuid not null, --步骤执行记录id
+    user_id uuid not null, --签收人id
+    success boolean not null, --是否确认成功
+    comment text, --签收意见
+    attachments text, --签收凭证，格式和步骤附件一样
+    created_time timestamptz not null,
+    last_modified_time timestamptz not null
+);
+
+--任务计划
+create table job_schedule
+(
+    id uuid not null primary key,
+    org_id uuid not null, --组织id
+    job_id uuid not null, --任务id
+    environment_id uuid not null, --环境id
+    name varchar(128) not null, --计划名称
+    cron_expression varchar(128) not null, --cron表达式
+    params text not null, --执行参数
+    status smallint not null, --计划状态
+    next_fire_time timestamptz, --下次触发时间
+    last_fire_time timestamptz, --上次触发时间
+    last_record_id uuid, --上次触发的任务记录id
+    created_by uuid, --创建人id
+    created_time timestamptz not null,
+    last_modified_time timestamptz not null
+);
+
+--执行器
+create table runner
+(
+    id uuid not null primary key,
+    org_id uuid not null, --组织id
+    name varchar(64) not null, --执行器名称
+    label varchar(64) not null, --执行器标签
+    token_hash varchar(128) not null, --访问令牌的摘要
+    version varchar(64), --执行器版本
+    extension_ids text, --执行器加载的扩展id，逗号分隔
+    last_heartbeat_time timestamptz, --最后一次心跳时间
+    created_time timestamptz not null,
+    last_modified_time timestamptz not null
+);
+
+--执行器任务
+create table runner_task
+(
+    id uuid not null primary key,
+    org_id uuid not null, --组织id
+    label varchar(64) not null, --执行器标签
+    extension_id varchar(64) not null, --扩展id
+    operation_id varchar(64) not null, --操作id
+    operation_parameter text not null, --操作参数，json格式
+    extension_configuration text not null, --加密保存的扩展配置
+    resource_index integer not null, --资源在步骤里的序号
+    status smallint not null, --任务状态
+    runner_id uuid, --领取任务的执行器id
+    cancelled boolean not null, --是否已被取消
+    pending_logs text, --执行器上报、还没有写入执行日志的日志，json格式
+    outputs text, --执行成功时操作的输出，json格式
+    error text, --执行失败的原因
+    polled_time timestamptz not null, --服务端最后一次检查任务的时间
+    reported_time timestamptz, --执行器最后一次上报的时间
+    created_time timestamptz not null,
+    last_modified_time timestamptz not null
+);
+
+--访问令牌
+create table api_token
+(
+    id uuid not null primary key,
+    org_id uuid not null, --组织id
+    user_id uuid not null, --令牌所属用户id
+    name varchar(64) not null, --令牌名称
+    token_prefix varchar(16) not null, --令牌前缀，用于辨认令牌
+    token_hash varchar(64) not null, --令牌摘要
+    scope smallint not null, --令牌权限范围
+    job_ids text, --可以执行的任务id列表，json格式
+    expire_time timestamptz not null, --过期时间
+    last_used_time timestamptz, --最后使用时间
+    created_by uuid not null, --创建人id
+    created_time timestamptz not null,
+    last_modified_time timestamptz not null
+);